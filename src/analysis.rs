@@ -0,0 +1,952 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+use crate::cpu::{instruction_length, is_illegal};
+use crate::disassembler::{self, byte, word};
+use crate::hex;
+use crate::recompiler::{flow, Flow};
+use crate::symbols::SymbolTable;
+
+// the reset vector and the RST 1 and RST 2 handlers `GameState` interrupts
+// into, where tracing starts unless other entries are given
+pub const VECTORS: [u16; 3] = [0x00, 0x08, 0x10];
+// first line of a database
+const HEADER: &str = "8080 analysis 1";
+// a jump table longer than this is more likely data that happens to look
+// like addresses
+const MAX_TABLE: usize = 128;
+// instructions looked back through from a PCHL for the table it reads
+const MAX_LOOK_BACK: usize = 16;
+const DATA_PER_LINE: usize = 8;
+// callers listed above a label, the rest are counted
+const MAX_LISTED: usize = 8;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ReferenceKind {
+    Call,
+    Jump,
+    Read,
+    Write,
+    // LXI of an address in the ROM, a pointer to a table or a string
+    Address,
+    // the target is a port
+    In,
+    Out,
+}
+
+impl ReferenceKind {
+    pub fn name(self) -> &'static str {
+        match self {
+            ReferenceKind::Call => "call",
+            ReferenceKind::Jump => "jump",
+            ReferenceKind::Read => "read",
+            ReferenceKind::Write => "write",
+            ReferenceKind::Address => "address",
+            ReferenceKind::In => "in",
+            ReferenceKind::Out => "out",
+        }
+    }
+
+    fn parse(name: &str) -> Option<Self> {
+        [
+            ReferenceKind::Call,
+            ReferenceKind::Jump,
+            ReferenceKind::Read,
+            ReferenceKind::Write,
+            ReferenceKind::Address,
+            ReferenceKind::In,
+            ReferenceKind::Out,
+        ].iter().copied().find(|kind| kind.name() == name)
+    }
+
+    pub fn is_port(self) -> bool {
+        self == ReferenceKind::In || self == ReferenceKind::Out
+    }
+}
+
+// an instruction that calls, jumps to, reads, writes or points at an
+// address, or reads or writes a port
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Reference {
+    pub from: u16,
+    pub to: u16,
+    pub kind: ReferenceKind,
+    // the function the instruction belongs to
+    pub function: Option<u16>,
+}
+
+// words in the ROM a PCHL jumps through
+#[derive(Clone, Debug, PartialEq)]
+pub struct JumpTable {
+    pub address: u16,
+    // the PCHL
+    pub dispatch: u16,
+    pub targets: Vec<u16>,
+}
+
+// what tracing the code of a ROM found, as lines of text:
+//
+// 8080 analysis 1
+// rom 0000 2000
+// entry 0000
+// function 18d4
+// code 0000 0007
+// data 1b00 1fff
+// table 0310 0305 0320 0348
+// reference 0003 18d4 jump 0000
+// label 18d4 Init
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Analysis {
+    pub origin: u16,
+    pub size: u32,
+    pub entries: Vec<u16>,
+    pub functions: BTreeSet<u16>,
+    // the first and last address of the runs of traced instructions and of
+    // the bytes between them
+    pub code: Vec<(u16, u16)>,
+    pub data: Vec<(u16, u16)>,
+    pub tables: Vec<JumpTable>,
+    pub references: Vec<Reference>,
+    // the names of the symbol file and generated ones for the rest
+    pub symbols: SymbolTable,
+    // the length of every traced instruction and where control goes after
+    // it without following calls, only known right after tracing
+    instructions: BTreeMap<u16, u16>,
+    successors: BTreeMap<u16, Vec<u16>>,
+}
+
+// follows the code from the entries through jumps, calls, jump tables and
+// return addresses pushed before a PCHL; the names in `names` are kept
+pub fn analyze(rom: &[u8], origin: u16, entries: &[u16], names: &SymbolTable) -> Analysis {
+    let mut analysis = Analysis {
+        origin,
+        size: rom.len() as u32,
+        entries: entries.to_vec(),
+        ..Analysis::default()
+    };
+    let mut pending = entries.iter().copied().filter(|&entry| analysis.contains(entry)).collect::<Vec<_>>();
+    analysis.functions.extend(pending.iter().copied());
+
+    let mut examined = BTreeSet::new();
+    while !pending.is_empty() {
+        while let Some(address) = pending.pop() {
+            analysis.trace(rom, address, &mut pending);
+        }
+        // tables and pushed return addresses show once the code before them
+        // is known
+        let unexamined = analysis.instructions.keys().copied().filter(|address| !examined.contains(address)).collect::<Vec<_>>();
+        for address in unexamined {
+            examined.insert(address);
+            pending.extend(analysis.indirect_targets(rom, address));
+        }
+    }
+
+    let named = analysis.find_references(rom);
+    analysis.find_regions();
+    analysis.name(names, &named);
+    analysis
+}
+
+// straight-line code of a function, control only enters at the start and
+// leaves after the last instruction; calls and conditional returns end
+// blocks too
+#[derive(Clone, Debug, PartialEq)]
+pub struct BasicBlock {
+    pub instructions: Vec<u16>,
+    // where control goes after the last instruction without following a
+    // call, in or out of the function
+    pub successors: Vec<u16>,
+}
+
+impl BasicBlock {
+    pub fn start(&self) -> u16 {
+        self.instructions[0]
+    }
+
+    pub fn last(&self) -> u16 {
+        self.instructions[self.instructions.len() - 1]
+    }
+}
+
+// how a line of the annotated source shows the bytes it starts at
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Line {
+    Instruction,
+    // an entry of a jump table, with its target
+    Table(u16),
+    Data,
+}
+
+impl Analysis {
+    pub fn parse(text: &str) -> Result<Analysis, String> {
+        let mut lines = text.lines().enumerate();
+        match lines.next() {
+            Some((_, HEADER)) => (),
+            _ => return Err("not an analysis database".to_string()),
+        }
+
+        let mut analysis = Analysis::default();
+        for (index, line) in lines {
+            let error = || format!("line {}: bad {}", index + 1, line.split_whitespace().next().unwrap_or("line"));
+            let hex = |digits: &str| u16::from_str_radix(digits, 16).map_err(|_| error());
+
+            // names and comments run to the end of the line
+            if let Some(rest) = line.strip_prefix("comment ") {
+                let (address, comment) = rest.split_once(' ').ok_or_else(error)?;
+                analysis.symbols.add_comment(hex(address)?, comment);
+                continue;
+            }
+            match line.split_whitespace().collect::<Vec<_>>().as_slice() {
+                [] => (),
+                ["rom", origin, size] => {
+                    analysis.origin = hex(origin)?;
+                    analysis.size = u32::from_str_radix(size, 16).map_err(|_| error())?;
+                },
+                ["entry", address] => analysis.entries.push(hex(address)?),
+                ["function", address] => {
+                    analysis.functions.insert(hex(address)?);
+                },
+                ["code", start, end] => analysis.code.push((hex(start)?, hex(end)?)),
+                ["data", start, end] => analysis.data.push((hex(start)?, hex(end)?)),
+                ["table", address, dispatch, targets @ ..] => analysis.tables.push(JumpTable {
+                    address: hex(address)?,
+                    dispatch: hex(dispatch)?,
+                    targets: targets.iter().map(|target| hex(target)).collect::<Result<_, _>>()?,
+                }),
+                ["reference", from, to, kind, function] => analysis.references.push(Reference {
+                    from: hex(from)?,
+                    to: hex(to)?,
+                    kind: ReferenceKind::parse(kind).ok_or_else(error)?,
+                    function: if *function == "-" { None } else { Some(hex(function)?) },
+                }),
+                ["label", address, name] => analysis.symbols.insert(name, hex(address)?),
+                _ => return Err(error()),
+            }
+        }
+        Ok(analysis)
+    }
+
+    // answers `functions`, `tables`, `data`, `ports`, `port <number>` or an
+    // address or name with what is there, what refers to it and what it
+    // refers to
+    pub fn query(&self, words: &[&str]) -> Result<String, String> {
+        let mut text = String::new();
+        match words {
+            ["functions"] => {
+                for &function in &self.functions {
+                    let callers = self.references_to(function).filter(|reference| reference.kind == ReferenceKind::Call).count();
+                    let plural = if callers == 1 { "" } else { "s" };
+                    text += &format!("{:<32} called from {} place{}\n", self.symbols.describe(function), callers, plural);
+                }
+            },
+            ["tables"] => {
+                for table in &self.tables {
+                    text += &format!("{} for the pchl at {}\n", self.symbols.describe(table.address), self.symbols.describe(table.dispatch));
+                    for target in &table.targets {
+                        text += &format!("  {}\n", self.symbols.describe(*target));
+                    }
+                }
+            },
+            ["data"] => {
+                for &(start, end) in &self.data {
+                    text += &format!("{:04x}-{:04x} {:>5} bytes  {}\n", start, end, end.wrapping_sub(start) as u32 + 1, self.symbols.label(start).unwrap_or_default());
+                }
+            },
+            ["ports"] => {
+                let ports = self.references.iter().filter(|reference| reference.kind.is_port()).map(|reference| reference.to).collect::<BTreeSet<_>>();
+                for port in ports {
+                    text += &self.port_references(port);
+                }
+            },
+            ["port", port] => {
+                let port = hex::byte(port).ok_or_else(|| format!("{} is not a port", port))?;
+                text += &self.port_references(port as u16);
+            },
+            [what] => {
+                let address = self.symbols.address(what)
+                    .or_else(|| hex::address(what))
+                    .ok_or_else(|| format!("{} is not an address or a name", what))?;
+
+                let region = if self.functions.contains(&address) {
+                    "a function"
+                } else if self.code.iter().any(|&(start, end)| (start..=end).contains(&address)) {
+                    "code"
+                } else if self.data.iter().any(|&(start, end)| (start..=end).contains(&address)) {
+                    "data"
+                } else {
+                    "outside the ROM"
+                };
+                text += &format!("{} is {}\n", self.symbols.describe(address), region);
+                if let Some(comment) = self.symbols.comment(address) {
+                    text += &format!("; {}\n", comment);
+                }
+
+                text += "references to it:\n";
+                for reference in self.references_to(address) {
+                    text += &format!("  {:<32} {}\n", self.symbols.describe(reference.from), reference.kind.name());
+                }
+                // a function's are those of all its instructions
+                text += "references from it:\n";
+                let from = self.references.iter().filter(|reference| {
+                    reference.from == address || (self.functions.contains(&address) && reference.function == Some(address))
+                });
+                for reference in from {
+                    let to = if reference.kind.is_port() { format!("port {:02x}", reference.to) } else { self.symbols.describe(reference.to) };
+                    text += &format!("  {:<32} {:<7} {}\n", self.symbols.describe(reference.from), reference.kind.name(), to);
+                }
+            },
+            _ => return Err("query functions, tables, data, ports, port <number> or an address or name".to_string()),
+        }
+        Ok(text)
+    }
+
+    // the blocks of the instructions that belong to `function`, by address;
+    // none when the database was read rather than traced
+    pub fn basic_blocks(&self, rom: &[u8], function: u16) -> Vec<BasicBlock> {
+        let owned = self.owners().into_iter()
+            .filter(|&(_, owner)| owner == function)
+            .map(|(address, _)| address)
+            .collect::<BTreeSet<_>>();
+        let targets = self.targets();
+        let ends_block = |address: u16| {
+            let next = address.wrapping_add(self.instructions[&address]);
+            // a conditional return leaves the function too
+            let leaves = self.decode(rom, address).is_some_and(|(opcode, operand, _)| matches!(flow(opcode, operand), Flow::Call(_) | Flow::ConditionalReturn));
+            leaves || self.successors[&address] != [next]
+        };
+
+        let mut blocks = Vec::new();
+        let mut block: Option<BasicBlock> = None;
+        for &address in &owned {
+            let continues = block.as_ref().is_some_and(|block| {
+                let last = block.last();
+                !ends_block(last) && last.wrapping_add(self.instructions[&last]) == address && !targets.contains(&address)
+            });
+            if !continues {
+                blocks.extend(block.take());
+                block = Some(BasicBlock { instructions: Vec::new(), successors: Vec::new() });
+            }
+            let block = block.as_mut().unwrap();
+            block.instructions.push(address);
+            block.successors = self.successors[&address].clone();
+        }
+        blocks.extend(block);
+        blocks
+    }
+
+    fn references_to(&self, address: u16) -> impl Iterator<Item = &Reference> {
+        self.references.iter().filter(move |reference| reference.to == address && !reference.kind.is_port())
+    }
+
+    fn port_references(&self, port: u16) -> String {
+        let mut text = format!("port {:02x}\n", port);
+        for reference in self.references.iter().filter(|reference| reference.kind.is_port() && reference.to == port) {
+            text += &format!("  {:<32} {}\n", self.symbols.describe(reference.from), reference.kind.name());
+        }
+        text
+    }
+
+    // the ROM as source the assembler turns back into the same bytes: the
+    // traced code as instructions, jump tables as DW and the rest as DB,
+    // with what calls, jumps to, reads and writes a label above it
+    pub fn annotated(&self, rom: &[u8]) -> String {
+        let lines = self.lines(rom);
+        let starts = lines.iter().map(|&(address, _, _)| address).collect::<BTreeSet<_>>();
+        let peek = |address: u16| rom.get(address.wrapping_sub(self.origin) as usize).copied().unwrap_or(0);
+
+        let data_size = self.data.iter().map(|&(start, end)| end.wrapping_sub(start) as u32 + 1).sum::<u32>();
+        let mut text = format!("; {} functions, {} jump tables, {} bytes of data\n\n", self.functions.len(), self.tables.len(), data_size);
+        let mut equates = false;
+        for (address, name) in self.symbols.labels().filter(|(address, _)| !starts.contains(address)) {
+            text += &format!("{:<15} equ {}\n", name, word(address));
+            equates = true;
+        }
+        if equates {
+            text += "\n";
+        }
+        text += &format!("        org {}\n", word(self.origin));
+
+        for &(address, length, line) in &lines {
+            if let Some(name) = self.symbols.name(address) {
+                text += "\n";
+                if let Some(comment) = self.symbols.comment(address) {
+                    text += &format!("; {}\n", comment);
+                }
+                text += &self.cross_references(address);
+                text += &format!("{}:\n", name);
+            } else if let Some(comment) = self.symbols.comment(address) {
+                text += &format!("\n; {}\n", comment);
+            }
+
+            let code = match line {
+                Line::Instruction => disassembler::disassemble_at(&peek, address, &self.symbols).0,
+                Line::Table(target) => format!("dw {}", self.symbols.label(target).unwrap_or_else(|| word(target))),
+                Line::Data => {
+                    let bytes = (0..length).map(|i| byte(peek(address.wrapping_add(i)))).collect::<Vec<_>>();
+                    format!("db {}", bytes.join(","))
+                },
+            };
+            text += &format!("        {:<20}; {:04x}\n", code, address);
+        }
+        text
+    }
+
+    // comment lines of who refers to an address, by kind
+    fn cross_references(&self, address: u16) -> String {
+        let mut text = String::new();
+        let kinds = [
+            (ReferenceKind::Call, "called from"),
+            (ReferenceKind::Jump, "jumped to from"),
+            (ReferenceKind::Read, "read at"),
+            (ReferenceKind::Write, "written at"),
+            (ReferenceKind::Address, "pointed to at"),
+        ];
+        for &(kind, verb) in &kinds {
+            let from = self.references_to(address).filter(|reference| reference.kind == kind).map(|reference| reference.from).collect::<Vec<_>>();
+            if from.is_empty() {
+                continue;
+            }
+            let listed = from.iter().take(MAX_LISTED).map(|from| format!("{:04x}", from)).collect::<Vec<_>>();
+            let more = match from.len().saturating_sub(MAX_LISTED) {
+                0 => String::new(),
+                more => format!(" and {} more", more),
+            };
+            text += &format!("; {} {}{}\n", verb, listed.join(", "), more);
+        }
+        text
+    }
+
+    // the address, length and kind of every line of the annotated source,
+    // data lines end where a label or the code starts
+    fn lines(&self, rom: &[u8]) -> Vec<(u16, u16, Line)> {
+        let entries = self.tables.iter()
+            .flat_map(|table| table.targets.iter().enumerate().map(move |(i, &target)| (table.address.wrapping_add(2 * i as u16), target)))
+            .collect::<BTreeMap<_, _>>();
+        let is_code = |address: u16| self.instructions.contains_key(&address);
+
+        let mut lines = Vec::new();
+        let mut at = 0;
+        while at < rom.len() {
+            let address = self.origin.wrapping_add(at as u16);
+            let (length, line) = match (self.instructions.get(&address), entries.get(&address)) {
+                (Some(&length), _) if at + length as usize <= rom.len() => (length, Line::Instruction),
+                (None, Some(&target)) if at + 2 <= rom.len() && !is_code(address.wrapping_add(1)) => (2, Line::Table(target)),
+                _ => {
+                    let mut length = 1;
+                    while length < DATA_PER_LINE && at + length < rom.len() {
+                        let next = address.wrapping_add(length as u16);
+                        if is_code(next) || entries.contains_key(&next) || self.symbols.name(next).is_some() || self.symbols.comment(next).is_some() {
+                            break;
+                        }
+                        length += 1;
+                    }
+                    (length as u16, Line::Data)
+                },
+            };
+            lines.push((address, length, line));
+            at += length as usize;
+        }
+        lines
+    }
+
+    fn contains(&self, address: u16) -> bool {
+        (address.wrapping_sub(self.origin) as u32) < self.size
+    }
+
+    pub fn decode(&self, rom: &[u8], address: u16) -> Option<(u8, u16, u16)> {
+        if !self.contains(address) {
+            return None;
+        }
+        let at = address.wrapping_sub(self.origin) as usize;
+        let opcode = rom[at];
+        let length = instruction_length(opcode);
+        if is_illegal(opcode) || at + length as usize > rom.len() {
+            return None;
+        }
+        let operand = match length {
+            2 => rom[at + 1] as u16,
+            3 => (rom[at + 2] as u16) << 8 | rom[at + 1] as u16,
+            _ => 0,
+        };
+        Some((opcode, operand, length))
+    }
+
+    fn peek_word(&self, rom: &[u8], address: u16) -> Option<u16> {
+        if !self.contains(address) || !self.contains(address.wrapping_add(1)) {
+            return None;
+        }
+        let at = address.wrapping_sub(self.origin) as usize;
+        Some((rom[at + 1] as u16) << 8 | rom[at] as u16)
+    }
+
+    fn trace(&mut self, rom: &[u8], address: u16, pending: &mut Vec<u16>) {
+        if self.instructions.contains_key(&address) {
+            return;
+        }
+        let (opcode, operand, length) = match self.decode(rom, address) {
+            Some(instruction) => instruction,
+            None => return,
+        };
+        self.instructions.insert(address, length);
+
+        let next = address.wrapping_add(length);
+        let mut successors = Vec::new();
+        match flow(opcode, operand) {
+            Flow::Jump(target) => successors.push(target),
+            Flow::ConditionalJump(target) => successors.extend(&[target, next]),
+            Flow::Call(target) => {
+                if self.contains(target) {
+                    self.functions.insert(target);
+                    pending.push(target);
+                }
+                successors.push(next);
+            },
+            Flow::Return | Flow::Indirect => (),
+            Flow::Next | Flow::ConditionalReturn => successors.push(next),
+        }
+        successors.retain(|&successor| self.contains(successor));
+        pending.extend(&successors);
+        self.successors.insert(address, successors);
+    }
+
+    // the instructions that run into `address`, nearest first
+    fn straight_line_before(&self, address: u16) -> Vec<u16> {
+        let mut before = Vec::new();
+        let mut at = address;
+        while before.len() < MAX_LOOK_BACK {
+            let previous = (1..=3)
+                .map(|length| at.wrapping_sub(length))
+                .find(|previous| {
+                    self.instructions.get(previous) == Some(&at.wrapping_sub(*previous))
+                        && self.successors[previous].contains(&at)
+                });
+            match previous {
+                Some(previous) => {
+                    before.push(previous);
+                    at = previous;
+                },
+                None => break,
+            }
+        }
+        before
+    }
+
+    // the targets of a jump table a PCHL at `address` reads, or the address
+    // an LXI H at `address` pushes as the return address of a PCHL
+    fn indirect_targets(&mut self, rom: &[u8], address: u16) -> Vec<u16> {
+        let (opcode, operand, length) = match self.decode(rom, address) {
+            Some(instruction) => instruction,
+            None => return Vec::new(),
+        };
+        let opcode_at = |at: u16| self.decode(rom, at).map_or(0, |(opcode, _, _)| opcode);
+
+        match opcode {
+            // LXI H followed by XTHL, or by PUSH H and a JMP or PCHL, calls
+            // by hand; anything else pushed may as well be a pointer
+            0x21 if self.contains(operand) && match opcode_at(address.wrapping_add(length)) {
+                0xe3 => true,
+                0xe5 => matches!(opcode_at(address.wrapping_add(length + 1)), 0xc3 | 0xe9),
+                _ => false,
+            } => {
+                let pusher = address.wrapping_add(length);
+                if self.instructions.contains_key(&pusher) {
+                    self.successors.entry(pusher).or_default().push(operand);
+                }
+                vec![operand]
+            },
+            0xe9 => {
+                // the nearest LXI H or LXI D into the ROM the code then reads
+                // through, an XTHL between means the value is a return
+                // address instead
+                let mut reads = false;
+                let mut table = None;
+                for at in self.straight_line_before(address) {
+                    match opcode_at(at) {
+                        0xe3 => break,
+                        0x0a | 0x1a => reads = true,
+                        opcode if opcode & 0xc7 == 0x46 && opcode != 0x76 => reads = true,
+                        0x11 | 0x21 if reads => {
+                            let (_, start, _) = self.decode(rom, at).unwrap();
+                            if self.contains(start) {
+                                table = Some(start);
+                            }
+                            break;
+                        },
+                        _ => (),
+                    }
+                }
+                let start = match table {
+                    Some(start) => start,
+                    None => return Vec::new(),
+                };
+
+                let jumped_to = self.targets();
+                let mut targets = Vec::new();
+                while targets.len() < MAX_TABLE {
+                    let entry = start.wrapping_add(2 * targets.len() as u16);
+                    let target = match self.peek_word(rom, entry) {
+                        Some(target) => target,
+                        None => break,
+                    };
+                    let ends = self.in_code(entry)
+                        || self.in_code(entry.wrapping_add(1))
+                        || self.decode(rom, target).is_none()
+                        || (!targets.is_empty() && jumped_to.contains(&entry));
+                    if ends {
+                        break;
+                    }
+                    targets.push(target);
+                }
+                if targets.is_empty() {
+                    return Vec::new();
+                }
+
+                self.successors.entry(address).or_default().extend(&targets);
+                self.tables.push(JumpTable { address: start, dispatch: address, targets: targets.clone() });
+                targets
+            },
+            _ => Vec::new(),
+        }
+    }
+
+    // the address is one of the bytes of a traced instruction
+    fn in_code(&self, address: u16) -> bool {
+        self.instructions.range(..=address).next_back()
+            .is_some_and(|(&start, &length)| address.wrapping_sub(start) < length)
+    }
+
+    // where control arrives other than from the instruction before
+    fn targets(&self) -> BTreeSet<u16> {
+        let mut targets = self.functions.clone();
+        for (&from, successors) in &self.successors {
+            let next = from.wrapping_add(self.instructions[&from]);
+            targets.extend(successors.iter().filter(|&&successor| successor != next));
+        }
+        targets
+    }
+
+    // the function an instruction belongs to: the first one, by address,
+    // it is reached from without calls
+    fn owners(&self) -> BTreeMap<u16, u16> {
+        let mut owners = BTreeMap::new();
+        for &function in &self.functions {
+            let mut pending = vec![function];
+            while let Some(address) = pending.pop() {
+                if owners.contains_key(&address) || !self.instructions.contains_key(&address) {
+                    continue;
+                }
+                owners.insert(address, function);
+                pending.extend(self.successors[&address].iter().filter(|successor| !self.functions.contains(successor)));
+            }
+        }
+        owners
+    }
+
+    // every reference of every instruction; the addresses BC, DE and HL hold
+    // are followed from LXI through INX, DCX and XCHG until something else
+    // changes them or control can arrive from elsewhere; returns the
+    // addresses instructions name themselves
+    fn find_references(&mut self, rom: &[u8]) -> BTreeSet<u16> {
+        let owners = self.owners();
+        let targets = self.targets();
+        let mut references = BTreeSet::new();
+        let mut named = BTreeSet::new();
+        let mut pairs: [Option<u16>; 3] = [None; 3];
+        let mut falls_into = None;
+
+        for (&address, &length) in &self.instructions {
+            if falls_into != Some(address) || targets.contains(&address) {
+                pairs = [None; 3];
+            }
+            let next = address.wrapping_add(length);
+            falls_into = if self.successors[&address].contains(&next) { Some(next) } else { None };
+
+            let (opcode, operand, _) = self.decode(rom, address).unwrap();
+            let mut add = |to: u16, kind: ReferenceKind| {
+                references.insert(Reference { from: address, to, kind, function: owners.get(&address).copied() });
+            };
+            let pair = (opcode >> 4 & 3) as usize;
+            let destination = (opcode >> 3 & 7) as usize;
+
+            match flow(opcode, operand) {
+                Flow::Call(target) => add(target, ReferenceKind::Call),
+                Flow::Jump(target) | Flow::ConditionalJump(target) => add(target, ReferenceKind::Jump),
+                _ => (),
+            }
+            if opcode == 0xe9 {
+                for table in self.tables.iter().filter(|table| table.dispatch == address) {
+                    add(table.address, ReferenceKind::Read);
+                    for &target in &table.targets {
+                        add(target, ReferenceKind::Jump);
+                    }
+                }
+            }
+
+            match opcode {
+                // a value inside the code is more likely a number than a
+                // pointer, unless control arrives there
+                0x01 | 0x11 | 0x21 => {
+                    pairs[pair] = Some(operand);
+                    if self.contains(operand) && (!self.in_code(operand) || targets.contains(&operand)) {
+                        add(operand, ReferenceKind::Address);
+                        named.insert(operand);
+                    }
+                },
+                0x31 => (),
+                0x03 | 0x13 | 0x23 => pairs[pair] = pairs[pair].map(|value| value.wrapping_add(1)),
+                0x0b | 0x1b | 0x2b => pairs[pair] = pairs[pair].map(|value| value.wrapping_sub(1)),
+                0xeb => pairs.swap(1, 2),
+                0x0a | 0x1a => {
+                    if let Some(value) = pairs[pair] {
+                        add(value, ReferenceKind::Read);
+                    }
+                },
+                0x02 | 0x12 => {
+                    if let Some(value) = pairs[pair] {
+                        add(value, ReferenceKind::Write);
+                    }
+                },
+                0x3a | 0x2a => {
+                    add(operand, ReferenceKind::Read);
+                    named.insert(operand);
+                    if opcode == 0x2a {
+                        pairs[2] = None;
+                    }
+                },
+                0x32 | 0x22 => {
+                    add(operand, ReferenceKind::Write);
+                    named.insert(operand);
+                },
+                0xdb => add(operand & 0xff, ReferenceKind::In),
+                0xd3 => add(operand & 0xff, ReferenceKind::Out),
+                // DAD, XTHL
+                0x09 | 0x19 | 0x29 | 0x39 | 0xe3 => pairs[2] = None,
+                // POP B, D and H
+                0xc1 | 0xd1 | 0xe1 => pairs[pair] = None,
+                _ => {
+                    let reads_m = (opcode & 0xc7 == 0x46 && opcode != 0x76) || (opcode & 0xc7 == 0x86) || opcode == 0x34 || opcode == 0x35;
+                    let writes_m = (0x70..=0x77).contains(&opcode) && opcode != 0x76 || opcode == 0x34 || opcode == 0x35 || opcode == 0x36;
+                    if let Some(value) = pairs[2] {
+                        if reads_m {
+                            add(value, ReferenceKind::Read);
+                        }
+                        if writes_m {
+                            add(value, ReferenceKind::Write);
+                        }
+                    }
+                    // MOV, MVI, INR and DCR of B to L
+                    let writes_register = (0x40..=0x7f).contains(&opcode) || matches!(opcode & 0xc7, 0x04..=0x06);
+                    if writes_register && opcode != 0x76 && destination < 6 {
+                        pairs[destination / 2] = None;
+                    }
+                },
+            }
+            // a routine leaves the registers as it likes
+            if let Flow::Call(_) = flow(opcode, operand) {
+                pairs = [None; 3];
+            }
+        }
+        self.references = references.into_iter().collect();
+        named
+    }
+
+    fn find_regions(&mut self) {
+        let mut covered = vec![false; self.size as usize];
+        for (&address, &length) in &self.instructions {
+            let at = address.wrapping_sub(self.origin) as usize;
+            for byte in &mut covered[at..at + length as usize] {
+                *byte = true;
+            }
+        }
+
+        let mut start = 0;
+        for at in 1..=covered.len() {
+            if at == covered.len() || covered[at] != covered[start] {
+                let run = (self.origin.wrapping_add(start as u16), self.origin.wrapping_add(at as u16 - 1));
+                if covered[start] {
+                    self.code.push(run);
+                } else {
+                    self.data.push(run);
+                }
+                start = at;
+            }
+        }
+    }
+
+    // sub_ for functions, loc_ for other places jumped to, tbl_ for jump
+    // tables, data_ and ram_ for what is read, written or pointed at in and
+    // outside the ROM; the symbol file's names win
+    fn name(&mut self, names: &SymbolTable, named: &BTreeSet<u16>) {
+        let mut symbols = SymbolTable::new();
+        for &function in &self.functions {
+            symbols.insert(&format!("sub_{:04x}", function), function);
+        }
+        for target in self.targets() {
+            symbols.insert(&format!("loc_{:04x}", target), target);
+        }
+        for table in &self.tables {
+            symbols.insert(&format!("tbl_{:04x}", table.address), table.address);
+        }
+        for &address in named {
+            let prefix = if self.contains(address) { "data" } else { "ram" };
+            symbols.insert(&format!("{}_{:04x}", prefix, address), address);
+        }
+        symbols.merge(names);
+        self.symbols = symbols;
+    }
+}
+
+impl fmt::Display for Analysis {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        writeln!(f, "{}", HEADER)?;
+        writeln!(f, "rom {:04x} {:04x}", self.origin, self.size)?;
+        for entry in &self.entries {
+            writeln!(f, "entry {:04x}", entry)?;
+        }
+        for function in &self.functions {
+            writeln!(f, "function {:04x}", function)?;
+        }
+        for (start, end) in &self.code {
+            writeln!(f, "code {:04x} {:04x}", start, end)?;
+        }
+        for (start, end) in &self.data {
+            writeln!(f, "data {:04x} {:04x}", start, end)?;
+        }
+        for table in &self.tables {
+            let targets = table.targets.iter().map(|target| format!(" {:04x}", target)).collect::<String>();
+            writeln!(f, "table {:04x} {:04x}{}", table.address, table.dispatch, targets)?;
+        }
+        for reference in &self.references {
+            let function = reference.function.map_or("-".to_string(), |function| format!("{:04x}", function));
+            writeln!(f, "reference {:04x} {:04x} {} {}", reference.from, reference.to, reference.kind.name(), function)?;
+        }
+        for (address, name) in self.symbols.labels() {
+            writeln!(f, "label {:04x} {}", address, name)?;
+        }
+        for (address, comment) in self.symbols.comments() {
+            writeln!(f, "comment {:04x} {}", address, comment)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assembler;
+
+    // a dispatch through a jump table of two routines, a compare that
+    // branches, a port and RAM read and written, and bytes nothing reaches
+    const PROGRAM: &str = "
+            org 0
+    start:  lxi sp,2400h
+            call dispatch
+            call compare
+            hlt
+    dispatch:
+            lda 2000h
+            add a
+            mov e,a
+            mvi d,0
+            lxi h,table
+            dad d
+            mov e,m
+            inx h
+            mov d,m
+            xchg
+            pchl
+    compare:
+            in 1
+            cpi 5
+            jz equal
+            out 3
+    equal:  ret
+    one:    ret
+    two:    sta 2001h
+            ret
+    table:  dw one, two
+    message:
+            db 'hi'
+    ";
+
+    fn analysis(names: &SymbolTable) -> (Vec<u8>, Analysis) {
+        let (_, rom) = assembler::assemble(PROGRAM).unwrap().image.binary();
+        let analysis = analyze(&rom, 0, &[0], names);
+        (rom, analysis)
+    }
+
+    #[test]
+    fn tracing_finds_functions_jump_tables_and_references() {
+        let (_, analysis) = analysis(&SymbolTable::new());
+
+        assert_eq!(analysis.functions.iter().copied().collect::<Vec<_>>(), [0x0000, 0x000a, 0x001a]);
+        assert_eq!(analysis.code, [(0x0000, 0x0028)]);
+        assert_eq!(analysis.data, [(0x0029, 0x002e)]);
+        assert_eq!(analysis.tables, [JumpTable { address: 0x0029, dispatch: 0x0019, targets: vec![0x0024, 0x0025] }]);
+
+        let reference = |from, to, kind, function| Reference { from, to, kind, function: Some(function) };
+        assert_eq!(analysis.references, [
+            reference(0x0003, 0x000a, ReferenceKind::Call, 0x0000),
+            reference(0x0006, 0x001a, ReferenceKind::Call, 0x0000),
+            reference(0x000a, 0x2000, ReferenceKind::Read, 0x000a),
+            reference(0x0011, 0x0029, ReferenceKind::Address, 0x000a),
+            reference(0x0019, 0x0024, ReferenceKind::Jump, 0x000a),
+            reference(0x0019, 0x0025, ReferenceKind::Jump, 0x000a),
+            reference(0x0019, 0x0029, ReferenceKind::Read, 0x000a),
+            reference(0x001a, 0x0001, ReferenceKind::In, 0x001a),
+            reference(0x001e, 0x0023, ReferenceKind::Jump, 0x001a),
+            reference(0x0021, 0x0003, ReferenceKind::Out, 0x001a),
+            reference(0x0025, 0x2001, ReferenceKind::Write, 0x000a),
+        ]);
+
+        assert_eq!(analysis.symbols.name(0x001a), Some("sub_001a"));
+        assert_eq!(analysis.symbols.name(0x0023), Some("loc_0023"));
+        assert_eq!(analysis.symbols.name(0x0029), Some("tbl_0029"));
+        assert_eq!(analysis.symbols.name(0x2001), Some("ram_2001"));
+    }
+
+    #[test]
+    fn queries_answer_with_what_is_there_and_what_refers_to_it() {
+        let (_, analysis) = analysis(&SymbolTable::parse("Compare = 001ah").unwrap());
+
+        assert_eq!(analysis.query(&["functions"]).unwrap(), "\
+0000 sub_0000                    called from 0 places
+000a sub_000a                    called from 1 place
+001a Compare                     called from 1 place
+");
+        assert_eq!(analysis.query(&["tables"]).unwrap(), "\
+0029 tbl_0029 for the pchl at 0019 sub_000a+15
+  0024 loc_0024
+  0025 loc_0025
+");
+        assert_eq!(analysis.query(&["port", "3"]).unwrap(), "port 03\n  0021 Compare+7                   out\n");
+        assert_eq!(analysis.query(&["Compare"]).unwrap(), "\
+001a Compare is a function
+references to it:
+  0006 sub_0000+6                  call
+references from it:
+  001a Compare                     in      port 01
+  001e Compare+4                   jump    0023 loc_0023
+  0021 Compare+7                   out     port 03
+");
+        assert_eq!(analysis.query(&["2001"]).unwrap(), "\
+2001 ram_2001 is outside the ROM
+references to it:
+  0025 loc_0025                    write
+references from it:
+");
+        assert_eq!(analysis.query(&["nowhere"]).err(), Some("nowhere is not an address or a name".to_string()));
+    }
+
+    #[test]
+    fn the_database_reads_back() {
+        let (_, analysis) = analysis(&SymbolTable::new());
+        let text = analysis.to_string();
+        let read = Analysis::parse(&text).unwrap();
+
+        assert_eq!(read.to_string(), text);
+        assert_eq!(read.tables, analysis.tables);
+        assert_eq!(read.references, analysis.references);
+        assert!(read.symbols.labels().eq(analysis.symbols.labels()));
+    }
+}
@@ -0,0 +1,1640 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt;
+use std::fs;
+use std::mem;
+use std::path::{Path, PathBuf};
+
+use crate::disassembler::{ACCUMULATOR, ALU, ALU_IMMEDIATE, CONDITIONS, PAIRS, REGISTERS, STACK_PAIRS};
+use crate::image::Image;
+use crate::linker;
+use crate::listing;
+use crate::object::{self, Object, Relocation, RelocationKind, Target, ABSOLUTE};
+
+// includes nested deeper are taken to include themselves
+const MAX_INCLUDE_DEPTH: usize = 16;
+// macros expanded deeper are taken to expand themselves
+const MAX_EXPANSION_DEPTH: usize = 64;
+// name of source given as text, includes are relative to the working directory
+const INLINE_SOURCE: &str = "<source>";
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct AsmError {
+    pub file: PathBuf,
+    pub line: u32,
+    pub message: String,
+}
+
+impl fmt::Display for AsmError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(f, "{}:{}: {}", self.file.display(), self.line, self.message)
+    }
+}
+
+// what a source line produced, for the listing
+#[derive(Clone, Debug, PartialEq)]
+enum Listed {
+    // the lines after it are from this file
+    File(PathBuf),
+    Line {
+        line: u32,
+        address: Option<u16>,
+        bytes: Vec<u8>,
+        // the value of an EQU
+        value: Option<u16>,
+        text: String,
+    },
+}
+
+// an assembled program
+pub struct Assembly {
+    // the code placed with ORG outside the relocatable sections, with the
+    // operand of END as the entry when it is an address there
+    pub image: Image,
+    // the value of every name, offsets into their section for the names in
+    // relocatable sections
+    pub symbols: BTreeMap<String, u16>,
+    object: Object,
+    listed: Vec<Listed>,
+}
+
+// assembles source given as text, e.g. a test program written inline
+pub fn assemble(source: &str) -> Result<Assembly, AsmError> {
+    let mut assembler = Assembler::new();
+    assembler.sources.insert(PathBuf::from(INLINE_SOURCE), source.to_string());
+    assembler.run(Path::new(INLINE_SOURCE))
+}
+
+pub fn assemble_file(path: &Path) -> Result<Assembly, AsmError> {
+    let mut assembler = Assembler::new();
+    let text = fs::read_to_string(path).map_err(|error| AsmError {
+        file: path.to_path_buf(),
+        line: 0,
+        message: error.to_string(),
+    })?;
+    assembler.sources.insert(path.to_path_buf(), text);
+    assembler.run(path)
+}
+
+impl Assembly {
+    // every section with its relocations, for the linker
+    pub fn object(&self) -> &Object {
+        &self.object
+    }
+
+    // the source next to the addresses and bytes of its code, in the format
+    // `ListingMap` reads, files are named relative to `directory`; addresses
+    // in relocatable sections are offsets, lines macros expanded to start
+    // with a +
+    pub fn listing(&self, directory: &Path) -> String {
+        let mut text = String::new();
+
+        for listed in &self.listed {
+            match listed {
+                Listed::File(path) => text += &format!("; {}\n", relative(path, directory).display()),
+                Listed::Line { line, address, bytes, value, text: source } => {
+                    if let Some(value) = value {
+                        text += &listing::format_line(*line, None, &format!("= {:04x}", value), source);
+                        text.push('\n');
+                        continue;
+                    }
+
+                    // four bytes a line, the rest continue on lines of their own
+                    let mut chunks = bytes.chunks(listing::BYTES_COLUMNS.len() / 3);
+                    let first = chunks.next().unwrap_or(&[]);
+                    text += &listing::format_line(*line, *address, &hex_bytes(first), source);
+                    text.push('\n');
+
+                    let mut offset = first.len() as u16;
+                    for chunk in chunks {
+                        let address = address.map(|address| address.wrapping_add(offset));
+                        text += &listing::format_line(*line, address, &hex_bytes(chunk), "");
+                        text.push('\n');
+                        offset += chunk.len() as u16;
+                    }
+                },
+            }
+        }
+        text
+    }
+
+    // a line of address and name for every symbol, by address
+    pub fn symbol_file(&self) -> String {
+        let symbols = self.symbols.iter()
+            .map(|(name, &value)| (name.clone(), value))
+            .collect::<Vec<_>>();
+        linker::symbol_file(&symbols)
+    }
+}
+
+fn hex_bytes(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x} ", byte)).collect()
+}
+
+fn relative(path: &Path, directory: &Path) -> PathBuf {
+    match (path.canonicalize(), directory.canonicalize()) {
+        (Ok(path), Ok(directory)) => path.strip_prefix(&directory).map(Path::to_path_buf).unwrap_or(path),
+        _ => path.to_path_buf(),
+    }
+}
+
+// how names in an expression are resolved
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Lookup {
+    // the first pass only needs the sizes, undefined names are 0 and
+    // relocatable values mix freely
+    Lenient,
+    // ORG, DS, EQU, IF and REPT need the value in the first pass, the name
+    // has to be defined above
+    Strict,
+    Final,
+}
+
+// which part of a relocatable value is used
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Part {
+    Whole,
+    // from LOW and HIGH
+    Low,
+    High,
+}
+
+// the value of an expression, a number or an offset from what the linker
+// places
+#[derive(Clone, Debug, PartialEq)]
+struct Value {
+    number: i64,
+    // none for plain numbers
+    base: Option<Target>,
+    part: Part,
+}
+
+impl Value {
+    fn absolute(number: i64) -> Self {
+        Self { number, base: None, part: Part::Whole }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Symbol {
+    value: Value,
+    // the pass that defined it last
+    pass: u8,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Condition {
+    // whether the lines around the IF are assembled
+    enclosing: bool,
+    value: bool,
+    in_else: bool,
+}
+
+impl Condition {
+    fn active(&self) -> bool {
+        self.enclosing && self.value != self.in_else
+    }
+}
+
+// code goes to the absolute section, placed by ORG, or to a named section
+// the linker places
+struct Section {
+    name: String,
+    // where the next line goes while another section is assembled
+    location: u32,
+    // the end of the code and DS, relocatable sections only
+    size: u32,
+    image: Image,
+}
+
+struct Macro {
+    parameters: Vec<String>,
+    body: Vec<String>,
+}
+
+// the lines up to ENDM
+enum Block {
+    Macro { name: String, parameters: Vec<String> },
+    Rept(i64),
+    Irp { parameter: String, items: Vec<String> },
+}
+
+struct Recording {
+    block: Block,
+    body: Vec<String>,
+    // MACRO, REPT and IRP in the body waiting for their ENDM
+    depth: usize,
+}
+
+// what assembling a line leaves to do
+enum Next {
+    Include(String),
+    // lines a macro, REPT or IRP expanded to
+    Expand(Vec<String>),
+}
+
+// a value in the code the linker fills in, at an offset into the code
+struct Fixup {
+    offset: u16,
+    kind: RelocationKind,
+    target: Target,
+    addend: i64,
+}
+
+// the bytes of a line
+#[derive(Default)]
+struct Code {
+    bytes: Vec<u8>,
+    fixups: Vec<Fixup>,
+}
+
+impl From<Vec<u8>> for Code {
+    fn from(bytes: Vec<u8>) -> Self {
+        Self { bytes, fixups: Vec::new() }
+    }
+}
+
+impl Code {
+    // a relocatable value fits in a byte with LOW or HIGH
+    fn byte(&mut self, value: Value, text: &str) -> Result<(), String> {
+        let target = match value.base {
+            None if !(-0x100..=0xff).contains(&value.number) => return Err(format!("{} does not fit in a byte", text.trim())),
+            None => {
+                self.bytes.push(value.number as u8);
+                return Ok(());
+            },
+            Some(target) => target,
+        };
+
+        let (kind, placeholder) = match value.part {
+            Part::Low => (RelocationKind::Low, value.number as u8),
+            Part::High => (RelocationKind::High, (value.number >> 8) as u8),
+            Part::Whole => return Err(format!("{} is relocatable, a byte takes its LOW or HIGH", text.trim())),
+        };
+        self.fixups.push(Fixup { offset: self.bytes.len() as u16, kind, target, addend: value.number });
+        self.bytes.push(placeholder);
+        Ok(())
+    }
+
+    fn word(&mut self, value: Value, text: &str) -> Result<(), String> {
+        let target = match value.base {
+            None if !(-0x10000..=0xffff).contains(&value.number) => return Err(format!("{} does not fit in a word", text.trim())),
+            None => {
+                self.bytes.extend_from_slice(&(value.number as u16).to_le_bytes());
+                return Ok(());
+            },
+            Some(target) => target,
+        };
+
+        let (kind, placeholder) = match value.part {
+            Part::Whole => (RelocationKind::Word, value.number as u16),
+            Part::Low => (RelocationKind::Low, value.number as u8 as u16),
+            Part::High => (RelocationKind::High, (value.number >> 8) as u8 as u16),
+        };
+        self.fixups.push(Fixup { offset: self.bytes.len() as u16, kind, target, addend: value.number });
+        self.bytes.extend_from_slice(&placeholder.to_le_bytes());
+        Ok(())
+    }
+}
+
+// two passes over the source, the first finds the address of every label
+// and the second produces the code
+struct Assembler {
+    pass: u8,
+    // past 0xffff only while nothing more is placed
+    address: u32,
+    // the address of the line, `$` in expressions
+    line_address: u32,
+    // the section lines go to, the absolute section first
+    sections: Vec<Section>,
+    section: usize,
+    symbols: HashMap<String, Symbol>,
+    publics: BTreeSet<String>,
+    relocations: Vec<Relocation>,
+    conditions: Vec<Condition>,
+    // by their name in lowercase, defined again every pass
+    macros: HashMap<String, Macro>,
+    recording: Option<Recording>,
+    // LOCAL names made so far this pass
+    locals: u32,
+    // the text of every file, read once for both passes
+    sources: HashMap<PathBuf, String>,
+    listed: Vec<Listed>,
+    entry: Option<(String, u16)>,
+    ended: bool,
+}
+
+impl Assembler {
+    fn new() -> Self {
+        Self {
+            pass: 0,
+            address: 0,
+            line_address: 0,
+            sections: vec![Section { name: ABSOLUTE.to_string(), location: 0, size: 0, image: Image::new() }],
+            section: 0,
+            symbols: HashMap::new(),
+            publics: BTreeSet::new(),
+            relocations: Vec::new(),
+            conditions: Vec::new(),
+            macros: HashMap::new(),
+            recording: None,
+            locals: 0,
+            sources: HashMap::new(),
+            listed: Vec::new(),
+            entry: None,
+            ended: false,
+        }
+    }
+
+    fn run(mut self, root: &Path) -> Result<Assembly, AsmError> {
+        for pass in 1..=2 {
+            self.pass = pass;
+            for section in &mut self.sections {
+                section.location = 0;
+                section.size = 0;
+            }
+            self.section = 0;
+            self.address = 0;
+            self.conditions.clear();
+            self.macros.clear();
+            self.recording = None;
+            self.locals = 0;
+            self.ended = false;
+
+            let lines = self.file(root, 0)?;
+            let error = |message: &str| AsmError { file: root.to_path_buf(), line: lines, message: message.to_string() };
+            if !self.conditions.is_empty() {
+                return Err(error("IF without ENDIF"));
+            }
+            if self.recording.is_some() {
+                return Err(error("MACRO, REPT or IRP without ENDM"));
+            }
+        }
+
+        let object = self.object();
+        let mut image = mem::replace(&mut self.sections[0].image, Image::new());
+        image.entry = match &self.entry {
+            Some((section, offset)) if section == ABSOLUTE => Some(*offset),
+            _ => None,
+        };
+        let symbols = self.symbols.into_iter()
+            .filter(|(_, symbol)| !matches!(symbol.value.base, Some(Target::External(_))))
+            .map(|(name, symbol)| (name, symbol.value.number as u16))
+            .collect();
+
+        Ok(Assembly { image, symbols, object, listed: self.listed })
+    }
+
+    fn object(&self) -> Object {
+        let sections = self.sections.iter()
+            .map(|section| object::Section {
+                name: section.name.clone(),
+                size: section.size,
+                chunks: section.image.chunks(),
+            })
+            .collect();
+
+        let mut symbols = Vec::new();
+        let mut externs = Vec::new();
+        for (name, symbol) in &self.symbols {
+            let section = match &symbol.value.base {
+                None => ABSOLUTE.to_string(),
+                Some(Target::Section(section)) => section.clone(),
+                Some(Target::External(_)) => {
+                    externs.push(name.clone());
+                    continue;
+                },
+            };
+            symbols.push(object::Symbol {
+                name: name.clone(),
+                section,
+                value: symbol.value.number as u16,
+                public: self.publics.contains(name),
+            });
+        }
+        symbols.sort_by(|a, b| a.name.cmp(&b.name));
+        externs.sort();
+
+        Object { sections, symbols, externs, relocations: self.relocations.clone(), entry: self.entry.clone() }
+    }
+
+    // assembles the lines of a file read into `sources`, returning how many
+    // there were
+    fn file(&mut self, path: &Path, depth: usize) -> Result<u32, AsmError> {
+        let text = self.sources[path].clone();
+        if self.pass == 2 {
+            self.listed.push(Listed::File(path.to_path_buf()));
+        }
+
+        let mut count = 0;
+        for (index, text) in text.lines().enumerate() {
+            if self.ended {
+                break;
+            }
+            let line = index as u32 + 1;
+            count = line;
+            self.source_line(path, line, text, depth, 0)?;
+        }
+        Ok(count)
+    }
+
+    // assembles a line of a file or of an expansion of one, `depth` counts
+    // the includes and `expansion` the macros it is in
+    fn source_line(&mut self, path: &Path, line: u32, text: &str, depth: usize, expansion: usize) -> Result<(), AsmError> {
+        let error = |message| AsmError { file: path.to_path_buf(), line, message };
+
+        match self.line(line, text, expansion > 0).map_err(error)? {
+            Some(Next::Include(include)) => {
+                let included = path.parent().unwrap_or_else(|| Path::new("")).join(include);
+                if depth == MAX_INCLUDE_DEPTH {
+                    return Err(error(format!("includes nested deeper than {}", MAX_INCLUDE_DEPTH)));
+                }
+                if !self.sources.contains_key(&included) {
+                    let text = fs::read_to_string(&included)
+                        .map_err(|io_error| error(format!("{}: {}", included.display(), io_error)))?;
+                    self.sources.insert(included.clone(), text);
+                }
+
+                self.file(&included, depth + 1)?;
+                if self.pass == 2 {
+                    self.listed.push(Listed::File(path.to_path_buf()));
+                }
+            },
+            Some(Next::Expand(lines)) => {
+                if expansion == MAX_EXPANSION_DEPTH {
+                    return Err(error(format!("macros expanded deeper than {}", MAX_EXPANSION_DEPTH)));
+                }
+                for text in lines {
+                    if self.ended {
+                        break;
+                    }
+                    self.source_line(path, line, &text, depth, expansion + 1)?;
+                }
+            },
+            None => (),
+        }
+        Ok(())
+    }
+
+    // assembles one line, what is left to do for an INCLUDE or an expansion
+    fn line(&mut self, line: u32, text: &str, expanded: bool) -> Result<Option<Next>, String> {
+        let text = text.trim_end();
+        let code = strip_comment(text);
+        let listed_text = if expanded { format!("+{}", text) } else { text.to_string() };
+        let mut listed = Listed::Line { line, address: None, bytes: Vec::new(), value: None, text: listed_text };
+
+        if let Some(recording) = &mut self.recording {
+            match directive_of(code).as_str() {
+                "macro" | "rept" | "irp" => recording.depth += 1,
+                "endm" if recording.depth == 0 => {
+                    let recording = self.recording.take().unwrap();
+                    self.list(listed);
+                    return self.close(recording);
+                },
+                "endm" => recording.depth -= 1,
+                _ => (),
+            }
+            recording.body.push(text.to_string());
+            self.list(listed);
+            return Ok(None);
+        }
+
+        let (label, rest) = split_label(code)?;
+        let (mut word, mut operands) = split_word(rest);
+        let mut name = label;
+
+        // `name equ value` and `name macro` name the line without a colon
+        let (second, second_operands) = split_word(operands);
+        if name.is_none() && (second.eq_ignore_ascii_case("equ") || second.eq_ignore_ascii_case("macro")) && is_name(word) {
+            name = Some(word);
+            word = second;
+            operands = second_operands;
+        }
+        let directive = word.to_ascii_lowercase();
+
+        self.line_address = self.address;
+        let active = self.conditions.last().is_none_or(Condition::active);
+
+        if self.conditional(&directive, operands, active)? || !active {
+            self.list(listed);
+            return Ok(None);
+        }
+
+        match directive.as_str() {
+            "equ" => {
+                let name = name.ok_or("EQU needs a name")?;
+                let value = self.expression(operands, Lookup::Strict)?;
+                if let Listed::Line { value: listed_value, .. } = &mut listed {
+                    *listed_value = Some(value.number as u16);
+                }
+                self.define(name, value)?;
+                self.list(listed);
+                return Ok(None);
+            },
+            "macro" => {
+                let name = name.ok_or("MACRO needs a name")?;
+                if !is_name(name) || is_reserved(name) {
+                    return Err(format!("{} can not be a name", name));
+                }
+                let parameters = split_arguments(operands)?.into_iter()
+                    .map(|parameter| if is_name(parameter) { Ok(parameter.to_string()) } else { Err(format!("{} can not be a parameter", parameter)) })
+                    .collect::<Result<Vec<_>, _>>()?;
+                self.recording = Some(Recording { block: Block::Macro { name: name.to_string(), parameters }, body: Vec::new(), depth: 0 });
+                self.list(listed);
+                return Ok(None);
+            },
+            _ => (),
+        }
+        if let Some(label) = name {
+            self.define(label, self.here(self.address))?;
+        }
+
+        let lookup = if self.pass == 1 { Lookup::Lenient } else { Lookup::Final };
+        let mut next = None;
+        let code = match directive.as_str() {
+            "" => Code::default(),
+            "org" => {
+                let value = self.expression(operands, Lookup::Strict)?;
+                if value.part != Part::Whole || (value.base.is_some() && value.base != self.here(0).base) {
+                    return Err(format!("{} is not an address in this section", operands.trim()));
+                }
+                self.address = value.number as u16 as u32;
+                Code::default()
+            },
+            "ds" => {
+                let size = self.number(operands, Lookup::Strict)?;
+                if size < 0 {
+                    return Err(format!("DS of {} bytes", size));
+                }
+                self.address += size as u32;
+                self.grow();
+                if let Listed::Line { address, .. } = &mut listed {
+                    *address = Some(self.line_address as u16);
+                }
+                Code::default()
+            },
+            "db" => self.data(operands, lookup)?,
+            "dw" => {
+                let mut code = Code::default();
+                for operand in split_operands(operands)? {
+                    code.word(self.expression(operand, lookup)?, operand)?;
+                }
+                code
+            },
+            "end" => {
+                if !operands.is_empty() {
+                    let value = self.expression(operands, lookup)?;
+                    self.entry = match value.base {
+                        None if (0..=0xffff).contains(&value.number) => Some((ABSOLUTE.to_string(), value.number as u16)),
+                        Some(Target::Section(section)) if value.part == Part::Whole => Some((section, value.number as u16)),
+                        _ => return Err(format!("{} is not an address to start at", operands.trim())),
+                    };
+                }
+                self.ended = true;
+                Code::default()
+            },
+            "include" => {
+                let path = operands.trim_matches(|c| c == '"' || c == '\'');
+                if path.is_empty() {
+                    return Err("INCLUDE needs a file".to_string());
+                }
+                next = Some(Next::Include(path.to_string()));
+                Code::default()
+            },
+            "section" => {
+                let name = operands.trim();
+                if !is_name(name) {
+                    return Err(format!("{} can not name a section", name));
+                }
+                self.switch(name);
+                Code::default()
+            },
+            "aseg" | "cseg" | "dseg" => {
+                self.switch(match directive.as_str() {
+                    "aseg" => ABSOLUTE,
+                    "cseg" => "code",
+                    _ => "data",
+                });
+                Code::default()
+            },
+            "public" => {
+                for name in split_operands(operands)? {
+                    // every name is known after the first pass
+                    if self.pass == 2 {
+                        match self.symbols.get(name) {
+                            Some(symbol) if matches!(symbol.value.base, Some(Target::External(_))) => {
+                                return Err(format!("{} is external", name));
+                            },
+                            Some(_) => self.publics.insert(name.to_string()),
+                            None => return Err(format!("{} is not defined", name)),
+                        };
+                    }
+                }
+                Code::default()
+            },
+            "extrn" | "extern" => {
+                for name in split_operands(operands)? {
+                    let value = Value { number: 0, base: Some(Target::External(name.to_string())), part: Part::Whole };
+                    self.define(name, value)?;
+                }
+                Code::default()
+            },
+            "rept" => {
+                let count = self.number(operands, Lookup::Strict)?;
+                if !(0..=0xffff).contains(&count) {
+                    return Err(format!("REPT {} times", count));
+                }
+                self.recording = Some(Recording { block: Block::Rept(count), body: Vec::new(), depth: 0 });
+                Code::default()
+            },
+            "irp" => {
+                let arguments = split_arguments(operands)?;
+                let (parameter, items) = match arguments.split_first() {
+                    Some((parameter, items)) if is_name(parameter) => (parameter.to_string(), items),
+                    _ => return Err("IRP needs a parameter and a list".to_string()),
+                };
+                let items = match items {
+                    [list] if list.starts_with('<') => split_arguments(unbracket(list))?,
+                    items => items.to_vec(),
+                };
+                let items = items.into_iter().map(|item| unbracket(item).to_string()).collect();
+                self.recording = Some(Recording { block: Block::Irp { parameter, items }, body: Vec::new(), depth: 0 });
+                Code::default()
+            },
+            "endm" => return Err("ENDM without MACRO, REPT or IRP".to_string()),
+            "local" => return Err("LOCAL outside a macro".to_string()),
+            _ => match self.macros.get(&directive) {
+                Some(definition) => {
+                    let arguments = split_arguments(operands)?;
+                    if arguments.len() > definition.parameters.len() {
+                        let expected = definition.parameters.len();
+                        return Err(format!("{} takes {} argument{}", word, expected, if expected == 1 { "" } else { "s" }));
+                    }
+                    let names = definition.parameters.iter()
+                        .enumerate()
+                        .map(|(i, parameter)| (parameter.clone(), arguments.get(i).map_or("", |argument| unbracket(argument)).to_string()))
+                        .collect::<Vec<_>>();
+                    let body = definition.body.clone();
+                    next = Some(Next::Expand(self.expand(&body, names)?));
+                    Code::default()
+                },
+                None => self.instruction(&directive, operands, lookup)?,
+            },
+        };
+
+        if !code.bytes.is_empty() {
+            self.emit(&code)?;
+            if let Listed::Line { address, bytes, .. } = &mut listed {
+                *address = Some(self.line_address as u16);
+                *bytes = code.bytes;
+            }
+        }
+        self.list(listed);
+        Ok(next)
+    }
+
+    // IF, IFDEF, IFNDEF, ELSE and ENDIF, which are followed even where
+    // lines are skipped, false for other lines
+    fn conditional(&mut self, directive: &str, operands: &str, active: bool) -> Result<bool, String> {
+        match directive {
+            "if" | "ifdef" | "ifndef" => {
+                let value = if !active {
+                    false
+                } else if directive == "if" {
+                    self.number(operands, Lookup::Strict)? != 0
+                } else {
+                    let defined = self.symbols.get(operands.trim()).is_some_and(|symbol| symbol.pass == self.pass);
+                    defined == (directive == "ifdef")
+                };
+                self.conditions.push(Condition { enclosing: active, value, in_else: false });
+            },
+            "else" => match self.conditions.last_mut() {
+                Some(condition) if !condition.in_else => condition.in_else = true,
+                Some(_) => return Err("second ELSE".to_string()),
+                None => return Err("ELSE without IF".to_string()),
+            },
+            "endif" => {
+                self.conditions.pop().ok_or("ENDIF without IF")?;
+            },
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    // a macro definition is kept, REPT and IRP expand right away
+    fn close(&mut self, recording: Recording) -> Result<Option<Next>, String> {
+        let mut lines = Vec::new();
+        match recording.block {
+            Block::Macro { name, parameters } => {
+                self.macros.insert(name.to_ascii_lowercase(), Macro { parameters, body: recording.body });
+                return Ok(None);
+            },
+            Block::Rept(count) => {
+                for _ in 0..count {
+                    lines.extend(self.expand(&recording.body, Vec::new())?);
+                }
+            },
+            Block::Irp { parameter, items } => {
+                for item in items {
+                    lines.extend(self.expand(&recording.body, vec![(parameter.clone(), item)])?);
+                }
+            },
+        }
+        Ok(Some(Next::Expand(lines)))
+    }
+
+    // the body with the parameters replaced by their arguments and the LOCAL
+    // names by names of their own
+    fn expand(&mut self, body: &[String], mut names: Vec<(String, String)>) -> Result<Vec<String>, String> {
+        let mut lines = Vec::new();
+        // LOCAL in a macro defined in the body is for that macro
+        let mut depth = 0;
+
+        for text in body {
+            let code = strip_comment(text);
+            match directive_of(code).as_str() {
+                "macro" | "rept" | "irp" => depth += 1,
+                "endm" => depth -= 1,
+                "local" if depth == 0 => {
+                    let (_, operands) = split_word(split_label(code)?.1);
+                    for local in split_operands(operands)? {
+                        if !is_name(local) {
+                            return Err(format!("{} can not be a name", local));
+                        }
+                        self.locals += 1;
+                        names.push((local.to_string(), format!("??{:04}", self.locals)));
+                    }
+                    continue;
+                },
+                _ => (),
+            }
+            lines.push(substitute(text, &names));
+        }
+        Ok(lines)
+    }
+
+    fn list(&mut self, listed: Listed) {
+        if self.pass == 2 {
+            self.listed.push(listed);
+        }
+    }
+
+    fn define(&mut self, name: &str, value: Value) -> Result<(), String> {
+        if !is_name(name) || is_reserved(name) {
+            return Err(format!("{} can not be a name", name));
+        }
+
+        match self.symbols.get(name) {
+            Some(symbol) if symbol.pass == self.pass => return Err(format!("{} is defined twice", name)),
+            // a label somewhere else than in the first pass means the size of
+            // a line above changed
+            Some(symbol) if symbol.value != value => {
+                return Err(format!("{} moved from {:04x} to {:04x}", name, symbol.value.number, value.number));
+            },
+            _ => (),
+        }
+        self.symbols.insert(name.to_string(), Symbol { value, pass: self.pass });
+        Ok(())
+    }
+
+    // an address in the section lines go to
+    fn here(&self, address: u32) -> Value {
+        match self.section {
+            0 => Value::absolute(address as i64),
+            section => Value {
+                number: address as i64,
+                base: Some(Target::Section(self.sections[section].name.clone())),
+                part: Part::Whole,
+            },
+        }
+    }
+
+    fn switch(&mut self, name: &str) {
+        self.sections[self.section].location = self.address;
+
+        self.section = match self.sections.iter().position(|section| section.name == name) {
+            Some(section) => section,
+            None => {
+                self.sections.push(Section { name: name.to_string(), location: 0, size: 0, image: Image::new() });
+                self.sections.len() - 1
+            },
+        };
+        self.address = self.sections[self.section].location;
+    }
+
+    fn grow(&mut self) {
+        let section = &mut self.sections[self.section];
+        section.size = section.size.max(self.address);
+    }
+
+    fn emit(&mut self, code: &Code) -> Result<(), String> {
+        let start = self.address;
+        for &byte in &code.bytes {
+            if self.address > 0xffff {
+                return Err("code past the end of memory".to_string());
+            }
+            if self.pass == 2 && !self.sections[self.section].image.place(self.address as u16, byte) {
+                return Err(format!("{:04x} is written twice", self.address));
+            }
+            self.address += 1;
+        }
+        self.grow();
+
+        if self.pass == 2 {
+            for fixup in &code.fixups {
+                self.relocations.push(Relocation {
+                    section: self.sections[self.section].name.clone(),
+                    offset: (start + fixup.offset as u32) as u16,
+                    kind: fixup.kind,
+                    target: fixup.target.clone(),
+                    addend: fixup.addend,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    // DB items, a quoted string stands for its characters
+    fn data(&self, operands: &str, lookup: Lookup) -> Result<Code, String> {
+        let mut code = Code::default();
+
+        for operand in split_operands(operands)? {
+            match parse_string(operand)? {
+                Some((string, rest)) if rest.trim().is_empty() => code.bytes.extend_from_slice(&string),
+                _ => code.byte(self.expression(operand, lookup)?, operand)?,
+            }
+        }
+        Ok(code)
+    }
+
+    fn expression(&self, text: &str, lookup: Lookup) -> Result<Value, String> {
+        if text.trim().is_empty() {
+            return Err("missing operand".to_string());
+        }
+        let mut parser = ExpressionParser { tokens: tokenize(text)?, at: 0, assembler: self, lookup };
+        let value = parser.or()?;
+
+        match parser.tokens.get(parser.at) {
+            Some(token) => Err(format!("unexpected {} in {}", token, text.trim())),
+            None => Ok(value),
+        }
+    }
+
+    // an expression that has to be a plain number
+    fn number(&self, text: &str, lookup: Lookup) -> Result<i64, String> {
+        let value = self.expression(text, lookup)?;
+        match value.base {
+            Some(_) if lookup != Lookup::Lenient => Err(format!("{} is relocatable", text.trim())),
+            _ => Ok(value.number),
+        }
+    }
+
+    fn instruction(&self, mnemonic: &str, operands: &str, lookup: Lookup) -> Result<Code, String> {
+        let operands = split_operands(operands)?;
+        let count = |expected: usize| {
+            if operands.len() == expected {
+                Ok(())
+            } else {
+                Err(format!("{} takes {} operand{}", mnemonic, expected, if expected == 1 { "" } else { "s" }))
+            }
+        };
+        let byte = |opcode: u8, text: &str| -> Result<Code, String> {
+            let mut code = Code::from(vec![opcode]);
+            code.byte(self.expression(text, lookup)?, text)?;
+            Ok(code)
+        };
+        let word = |opcode: u8, text: &str| -> Result<Code, String> {
+            let mut code = Code::from(vec![opcode]);
+            code.word(self.expression(text, lookup)?, text)?;
+            Ok(code)
+        };
+        let position = |names: &[&str], name: &str| names.iter().position(|&candidate| candidate == name).map(|i| i as u8);
+
+        let implied = match mnemonic {
+            "nop" => Some(0x00),
+            "hlt" => Some(0x76),
+            "ret" => Some(0xc9),
+            "xchg" => Some(0xeb),
+            "xthl" => Some(0xe3),
+            "sphl" => Some(0xf9),
+            "pchl" => Some(0xe9),
+            "di" => Some(0xf3),
+            "ei" => Some(0xfb),
+            _ => position(&ACCUMULATOR, mnemonic).map(|i| 0x07 | i << 3),
+        };
+        if let Some(opcode) = implied {
+            count(0)?;
+            return Ok(vec![opcode].into());
+        }
+
+        if let Some(i) = position(&ALU, mnemonic) {
+            count(1)?;
+            return Ok(vec![0x80 | i << 3 | register(operands[0])?].into());
+        }
+        if let Some(i) = position(&ALU_IMMEDIATE, mnemonic) {
+            count(1)?;
+            return byte(0xc6 | i << 3, operands[0]);
+        }
+
+        let code = match mnemonic {
+            "mov" => {
+                count(2)?;
+                let (destination, source) = (register(operands[0])?, register(operands[1])?);
+                if destination == 6 && source == 6 {
+                    return Err("mov m,m is not an instruction".to_string());
+                }
+                vec![0x40 | destination << 3 | source].into()
+            },
+            "mvi" => {
+                count(2)?;
+                byte(0x06 | register(operands[0])? << 3, operands[1])?
+            },
+            "inr" | "dcr" => {
+                count(1)?;
+                let opcode = if mnemonic == "inr" { 0x04 } else { 0x05 };
+                vec![opcode | register(operands[0])? << 3].into()
+            },
+            "lxi" => {
+                count(2)?;
+                word(0x01 | pair(&PAIRS, operands[0])? << 4, operands[1])?
+            },
+            "inx" | "dcx" | "dad" => {
+                count(1)?;
+                let opcode = match mnemonic {
+                    "inx" => 0x03,
+                    "dcx" => 0x0b,
+                    _ => 0x09,
+                };
+                vec![opcode | pair(&PAIRS, operands[0])? << 4].into()
+            },
+            "stax" | "ldax" => {
+                count(1)?;
+                let pair = pair(&PAIRS[..2], operands[0])?;
+                vec![if mnemonic == "stax" { 0x02 } else { 0x0a } | pair << 4].into()
+            },
+            "push" | "pop" => {
+                count(1)?;
+                vec![if mnemonic == "push" { 0xc5 } else { 0xc1 } | pair(&STACK_PAIRS, operands[0])? << 4].into()
+            },
+            "jmp" | "call" | "sta" | "lda" | "shld" | "lhld" => {
+                count(1)?;
+                let opcode = match mnemonic {
+                    "jmp" => 0xc3,
+                    "call" => 0xcd,
+                    "sta" => 0x32,
+                    "lda" => 0x3a,
+                    "shld" => 0x22,
+                    _ => 0x2a,
+                };
+                word(opcode, operands[0])?
+            },
+            "in" | "out" => {
+                count(1)?;
+                byte(if mnemonic == "in" { 0xdb } else { 0xd3 }, operands[0])?
+            },
+            "rst" => {
+                count(1)?;
+                let vector = self.number(operands[0], lookup)?;
+                if !(0..8).contains(&vector) {
+                    return Err(format!("rst {} is not a vector", vector));
+                }
+                vec![0xc7 | (vector as u8) << 3].into()
+            },
+            _ => {
+                let (kind, condition) = (mnemonic.get(..1).unwrap_or(""), mnemonic.get(1..).unwrap_or(""));
+                let condition = position(&CONDITIONS, condition).ok_or_else(|| format!("unknown instruction {}", mnemonic))?;
+                match kind {
+                    "j" => {
+                        count(1)?;
+                        word(0xc2 | condition << 3, operands[0])?
+                    },
+                    "c" => {
+                        count(1)?;
+                        word(0xc4 | condition << 3, operands[0])?
+                    },
+                    "r" => {
+                        count(0)?;
+                        vec![0xc0 | condition << 3].into()
+                    },
+                    _ => return Err(format!("unknown instruction {}", mnemonic)),
+                }
+            },
+        };
+        Ok(code)
+    }
+}
+
+fn register(operand: &str) -> Result<u8, String> {
+    let name = operand.trim().to_ascii_lowercase();
+    REGISTERS.iter().position(|&register| register == name)
+        .map(|i| i as u8)
+        .ok_or_else(|| format!("{} is not a register", operand.trim()))
+}
+
+fn pair(pairs: &[&str], operand: &str) -> Result<u8, String> {
+    let name = operand.trim().to_ascii_lowercase();
+    pairs.iter().position(|&pair| pair == name)
+        .map(|i| i as u8)
+        .ok_or_else(|| format!("{} is not one of the pairs {}", operand.trim(), pairs.join(", ")))
+}
+
+fn is_name_start(c: char) -> bool {
+    c.is_ascii_alphabetic() || matches!(c, '_' | '?' | '@' | '.')
+}
+
+fn is_name_char(c: char) -> bool {
+    is_name_start(c) || c.is_ascii_digit()
+}
+
+pub fn is_name(text: &str) -> bool {
+    text.starts_with(is_name_start) && text.chars().all(is_name_char)
+}
+
+// registers and operators can not name a label
+pub fn is_reserved(name: &str) -> bool {
+    let name = name.to_ascii_lowercase();
+    REGISTERS.contains(&name.as_str())
+        || name == "sp"
+        || name == "psw"
+        || word_operator(&name).is_some()
+}
+
+// the code of a line without its comment, a ; in quotes is kept
+fn strip_comment(text: &str) -> &str {
+    let mut quote = None;
+
+    for (i, c) in text.char_indices() {
+        match (quote, c) {
+            (None, ';') => return &text[..i],
+            (None, '\'') | (None, '"') => quote = Some(c),
+            (Some(open), c) if c == open => quote = None,
+            _ => (),
+        }
+    }
+    text
+}
+
+// a leading `name:`, the rest of the line
+fn split_label(code: &str) -> Result<(Option<&str>, &str), String> {
+    let code = code.trim_start();
+    let end = code.find(|c: char| !is_name_char(c)).unwrap_or(code.len());
+
+    if code[end..].starts_with(':') {
+        let name = &code[..end];
+        if !is_name(name) {
+            return Err(format!("{} can not be a label", name));
+        }
+        return Ok((Some(name), &code[end + 1..]));
+    }
+    Ok((None, code))
+}
+
+// the first word and the text after it
+fn split_word(text: &str) -> (&str, &str) {
+    let text = text.trim();
+    match text.find(char::is_whitespace) {
+        Some(end) => (&text[..end], text[end..].trim()),
+        None => (text, ""),
+    }
+}
+
+// operands separated by commas outside quotes
+fn split_operands(text: &str) -> Result<Vec<&str>, String> {
+    if text.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut operands = Vec::new();
+    let mut quote = None;
+    let mut start = 0;
+    for (i, c) in text.char_indices() {
+        match (quote, c) {
+            (None, ',') => {
+                operands.push(text[start..i].trim());
+                start = i + 1;
+            },
+            (None, '\'') | (None, '"') => quote = Some(c),
+            (Some(open), c) if c == open => quote = None,
+            _ => (),
+        }
+    }
+    if quote.is_some() {
+        return Err("unterminated string".to_string());
+    }
+    operands.push(text[start..].trim());
+
+    if operands.iter().any(|operand| operand.is_empty()) {
+        return Err("empty operand".to_string());
+    }
+    Ok(operands)
+}
+
+// macro arguments separated by commas outside quotes and <>, which may
+// be left empty
+fn split_arguments(text: &str) -> Result<Vec<&str>, String> {
+    if text.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut arguments = Vec::new();
+    let mut quote = None;
+    let mut depth = 0;
+    let mut start = 0;
+    for (i, c) in text.char_indices() {
+        match (quote, c) {
+            (None, ',') if depth == 0 => {
+                arguments.push(text[start..i].trim());
+                start = i + 1;
+            },
+            (None, '<') => depth += 1,
+            (None, '>') if depth > 0 => depth -= 1,
+            (None, '\'') | (None, '"') => quote = Some(c),
+            (Some(open), c) if c == open => quote = None,
+            _ => (),
+        }
+    }
+    if quote.is_some() {
+        return Err("unterminated string".to_string());
+    }
+    if depth > 0 {
+        return Err("missing >".to_string());
+    }
+    arguments.push(text[start..].trim());
+    Ok(arguments)
+}
+
+// an argument without the <> around it
+fn unbracket(argument: &str) -> &str {
+    argument.strip_prefix('<').and_then(|inner| inner.strip_suffix('>')).unwrap_or(argument)
+}
+
+// the directive of a line as far as recording a macro body goes, MACRO
+// for `name macro`
+fn directive_of(code: &str) -> String {
+    let (_, rest) = split_label(code).unwrap_or((None, code));
+    let (word, operands) = split_word(rest);
+    let (second, _) = split_word(operands);
+    let directive = if second.eq_ignore_ascii_case("macro") { second } else { word };
+    directive.to_ascii_lowercase()
+}
+
+// the line with the names replaced outside quotes, ignoring case; an &
+// next to a name joins it to the text around and goes away
+fn substitute(text: &str, names: &[(String, String)]) -> String {
+    let replacement = |word: &str| names.iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(word))
+        .map(|(_, value)| value.as_str());
+
+    let mut result = String::new();
+    let mut quote = None;
+    let mut after_name = false;
+    let mut rest = text;
+    while let Some(c) = rest.chars().next() {
+        let length = if quote.is_some() {
+            if quote == Some(c) {
+                quote = None;
+            }
+            result.push(c);
+            after_name = false;
+            c.len_utf8()
+        } else if is_name_start(c) || c.is_ascii_digit() {
+            let end = rest.find(|c: char| !is_name_char(c)).unwrap_or(rest.len());
+            let word = &rest[..end];
+            // a number can not be a name, the h of 10h is not replaced
+            let value = if c.is_ascii_digit() { None } else { replacement(word) };
+            result += value.unwrap_or(word);
+            after_name = value.is_some();
+            end
+        } else if c == '&' && (after_name || name_at(&rest[1..]).and_then(replacement).is_some()) {
+            after_name = false;
+            1
+        } else {
+            if c == '\'' || c == '"' {
+                quote = Some(c);
+            }
+            result.push(c);
+            after_name = false;
+            c.len_utf8()
+        };
+        rest = &rest[length..];
+    }
+    result
+}
+
+// the name `text` starts with
+fn name_at(text: &str) -> Option<&str> {
+    if !text.starts_with(is_name_start) {
+        return None;
+    }
+    Some(&text[..text.find(|c: char| !is_name_char(c)).unwrap_or(text.len())])
+}
+
+// a string in single or double quotes at the start of `text`, a doubled
+// quote stands for itself, with the text after it
+fn parse_string(text: &str) -> Result<Option<(Vec<u8>, &str)>, String> {
+    let text = text.trim_start();
+    let quote = match text.chars().next() {
+        Some(quote @ '\'') | Some(quote @ '"') => quote,
+        _ => return Ok(None),
+    };
+
+    let mut bytes = Vec::new();
+    let mut chars = text.char_indices().skip(1).peekable();
+    while let Some((i, c)) = chars.next() {
+        if c == quote {
+            match chars.peek() {
+                Some(&(_, next)) if next == quote => {
+                    chars.next();
+                },
+                _ => return Ok(Some((bytes, &text[i + 1..]))),
+            }
+        }
+        if !c.is_ascii() {
+            return Err(format!("{} is not ASCII", c));
+        }
+        bytes.push(c as u8);
+    }
+    Err("unterminated string".to_string())
+}
+
+fn parse_number(text: &str) -> Result<i64, String> {
+    let lower = text.to_ascii_lowercase();
+    let (digits, radix) = if let Some(digits) = lower.strip_prefix("0x") {
+        (digits, 16)
+    } else if let Some(digits) = lower.strip_suffix('h') {
+        (digits, 16)
+    } else if let Some(digits) = lower.strip_suffix('b') {
+        (digits, 2)
+    } else if let Some(digits) = lower.strip_suffix('o').or_else(|| lower.strip_suffix('q')) {
+        (digits, 8)
+    } else if let Some(digits) = lower.strip_suffix('d') {
+        (digits, 10)
+    } else {
+        (lower.as_str(), 10)
+    };
+
+    i64::from_str_radix(digits, radix)
+        .ok()
+        .filter(|&value| value <= 0xffff_ffff)
+        .ok_or_else(|| format!("{} is not a number", text))
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Number(i64),
+    Name(String),
+    // `$`, the address of the line
+    Here,
+    Operator(&'static str),
+    Open,
+    Close,
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            Token::Number(value) => write!(f, "{}", value),
+            Token::Name(name) => write!(f, "{}", name),
+            Token::Here => write!(f, "$"),
+            Token::Operator(operator) => write!(f, "{}", operator),
+            Token::Open => write!(f, "("),
+            Token::Close => write!(f, ")"),
+        }
+    }
+}
+
+// operators spelled as words, by the symbol the parser knows them by
+fn word_operator(word: &str) -> Option<&'static str> {
+    Some(match word {
+        "mod" => "%",
+        "shl" => "<<",
+        "shr" => ">>",
+        "and" => "&",
+        "or" => "|",
+        "xor" => "^",
+        "not" => "~",
+        "high" => "high",
+        "low" => "low",
+        "eq" => "==",
+        "ne" => "!=",
+        "lt" => "<",
+        "le" => "<=",
+        "gt" => ">",
+        "ge" => ">=",
+        _ => return None,
+    })
+}
+
+// the longer ones first
+const SYMBOL_OPERATORS: [&str; 19] = [
+    "<<", ">>", "<=", ">=", "<>", "==", "!=", "+", "-", "*", "/", "%", "&", "|", "^", "~", "=", "<", ">",
+];
+
+fn tokenize(text: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut rest = text.trim_start();
+
+    while let Some(c) = rest.chars().next() {
+        let length = if c.is_ascii_digit() || (c == '$' && rest[1..].starts_with(|c: char| c.is_ascii_hexdigit())) {
+            let start = if c == '$' { 1 } else { 0 };
+            let end = rest[start..].find(|c: char| !c.is_ascii_alphanumeric()).map_or(rest.len(), |end| end + start);
+            let value = if c == '$' {
+                i64::from_str_radix(&rest[1..end], 16).map_err(|_| format!("{} is not a number", &rest[..end]))?
+            } else {
+                parse_number(&rest[..end])?
+            };
+            tokens.push(Token::Number(value));
+            end
+        } else if c == '$' {
+            tokens.push(Token::Here);
+            1
+        } else if c == '\'' || c == '"' {
+            let (string, after) = parse_string(rest)?.ok_or("bad string")?;
+            if string.is_empty() || string.len() > 2 {
+                return Err(format!("{} is not one or two characters", &rest[..rest.len() - after.len()]));
+            }
+            tokens.push(Token::Number(string.iter().fold(0, |value, &byte| value << 8 | byte as i64)));
+            rest.len() - after.len()
+        } else if is_name_start(c) {
+            let end = rest.find(|c: char| !is_name_char(c)).unwrap_or(rest.len());
+            let word = &rest[..end];
+            match word_operator(&word.to_ascii_lowercase()) {
+                Some(operator) => tokens.push(Token::Operator(operator)),
+                None => tokens.push(Token::Name(word.to_string())),
+            }
+            end
+        } else if c == '(' {
+            tokens.push(Token::Open);
+            1
+        } else if c == ')' {
+            tokens.push(Token::Close);
+            1
+        } else {
+            let operator = SYMBOL_OPERATORS.iter().find(|&&operator| rest.starts_with(operator))
+                .ok_or_else(|| format!("unexpected {}", c))?;
+            tokens.push(Token::Operator(match *operator {
+                "<>" => "!=",
+                "=" => "==",
+                operator => operator,
+            }));
+            operator.len()
+        };
+        rest = rest[length..].trim_start();
+    }
+    Ok(tokens)
+}
+
+// precedence from loosest to tightest: OR XOR, AND, comparisons, + -,
+// * / MOD SHL SHR, then the unary operators; comparisons give 0ffffh when
+// they hold. a relocatable value can have a number added or taken away,
+// two in the same section can be taken from each other and HIGH and LOW
+// give its bytes, nothing else works on them
+struct ExpressionParser<'a> {
+    tokens: Vec<Token>,
+    at: usize,
+    assembler: &'a Assembler,
+    lookup: Lookup,
+}
+
+impl<'a> ExpressionParser<'a> {
+    fn operator(&mut self, operators: &[&str]) -> Option<&'static str> {
+        match self.tokens.get(self.at) {
+            Some(Token::Operator(operator)) if operators.contains(operator) => {
+                self.at += 1;
+                Some(operator)
+            },
+            _ => None,
+        }
+    }
+
+    // the number of a value an operator works on
+    fn number(&self, value: &Value, operator: &str) -> Result<i64, String> {
+        match value.base {
+            Some(_) if self.lookup != Lookup::Lenient => Err(format!("{} does not work on relocatable values", operator)),
+            _ => Ok(value.number),
+        }
+    }
+
+    // values that can not be combined, the first pass does not tell them
+    // apart
+    fn mixed(&self, number: i64, operator: &str) -> Result<Value, String> {
+        match self.lookup {
+            Lookup::Lenient => Ok(Value::absolute(number)),
+            _ => Err(format!("{} does not work on these relocatable values", operator)),
+        }
+    }
+
+    fn or(&mut self) -> Result<Value, String> {
+        let mut value = self.and()?;
+        while let Some(operator) = self.operator(&["|", "^"]) {
+            let (left, right) = (self.number(&value, operator)?, self.and()?);
+            let right = self.number(&right, operator)?;
+            value = Value::absolute(if operator == "|" { left | right } else { left ^ right });
+        }
+        Ok(value)
+    }
+
+    fn and(&mut self) -> Result<Value, String> {
+        let mut value = self.comparison()?;
+        while let Some(operator) = self.operator(&["&"]) {
+            let (left, right) = (self.number(&value, operator)?, self.comparison()?);
+            value = Value::absolute(left & self.number(&right, operator)?);
+        }
+        Ok(value)
+    }
+
+    fn comparison(&mut self) -> Result<Value, String> {
+        let mut value = self.sum()?;
+        while let Some(operator) = self.operator(&["==", "!=", "<", "<=", ">", ">="]) {
+            let (left, right) = (self.number(&value, operator)?, self.sum()?);
+            let right = self.number(&right, operator)?;
+            let holds = match operator {
+                "==" => left == right,
+                "!=" => left != right,
+                "<" => left < right,
+                "<=" => left <= right,
+                ">" => left > right,
+                _ => left >= right,
+            };
+            value = Value::absolute(if holds { 0xffff } else { 0 });
+        }
+        Ok(value)
+    }
+
+    fn sum(&mut self) -> Result<Value, String> {
+        let mut value = self.product()?;
+        while let Some(operator) = self.operator(&["+", "-"]) {
+            let right = self.product()?;
+            let whole = |value: &Value| value.base.is_some() && value.part == Part::Whole;
+
+            value = match (operator, &value.base, &right.base) {
+                (_, None, None) if operator == "+" => Value::absolute(value.number + right.number),
+                (_, None, None) => Value::absolute(value.number - right.number),
+                ("+", Some(_), None) if whole(&value) => Value { number: value.number + right.number, ..value },
+                ("+", None, Some(_)) if whole(&right) => Value { number: value.number + right.number, ..right },
+                ("-", Some(_), None) if whole(&value) => Value { number: value.number - right.number, ..value },
+                ("-", Some(left), Some(base)) if left == base && whole(&value) && whole(&right) => {
+                    Value::absolute(value.number - right.number)
+                },
+                _ if operator == "+" => self.mixed(value.number + right.number, operator)?,
+                _ => self.mixed(value.number - right.number, operator)?,
+            };
+        }
+        Ok(value)
+    }
+
+    fn product(&mut self) -> Result<Value, String> {
+        let mut value = self.unary()?;
+        while let Some(operator) = self.operator(&["*", "/", "%", "<<", ">>"]) {
+            let (left, right) = (self.number(&value, operator)?, self.unary()?);
+            let right = self.number(&right, operator)?;
+            value = Value::absolute(match operator {
+                "*" => left.wrapping_mul(right),
+                "/" | "%" if right == 0 => return Err("division by zero".to_string()),
+                "/" => left / right,
+                "%" => left % right,
+                "<<" => left.checked_shl(right as u32).unwrap_or(0),
+                _ => left.checked_shr(right as u32).unwrap_or(0),
+            });
+        }
+        Ok(value)
+    }
+
+    fn unary(&mut self) -> Result<Value, String> {
+        let operator = match self.operator(&["-", "+", "~", "high", "low"]) {
+            Some(operator) => operator,
+            None => return self.primary(),
+        };
+        let value = self.unary()?;
+
+        match operator {
+            "+" => Ok(value),
+            "high" | "low" if value.base.is_some() && value.part == Part::Whole => {
+                Ok(Value { part: if operator == "high" { Part::High } else { Part::Low }, ..value })
+            },
+            "high" => Ok(Value::absolute(self.number(&value, "HIGH")? >> 8 & 0xff)),
+            "low" => Ok(Value::absolute(self.number(&value, "LOW")? & 0xff)),
+            "-" => Ok(Value::absolute(-self.number(&value, operator)?)),
+            _ => Ok(Value::absolute(!self.number(&value, operator)? & 0xffff)),
+        }
+    }
+
+    fn primary(&mut self) -> Result<Value, String> {
+        let token = self.tokens.get(self.at).cloned().ok_or("missing operand")?;
+        self.at += 1;
+
+        match token {
+            Token::Number(value) => Ok(Value::absolute(value)),
+            Token::Here => Ok(self.assembler.here(self.assembler.line_address)),
+            Token::Name(name) => self.name(&name),
+            Token::Open => {
+                let value = self.or()?;
+                match self.tokens.get(self.at) {
+                    Some(Token::Close) => {
+                        self.at += 1;
+                        Ok(value)
+                    },
+                    _ => Err("missing )".to_string()),
+                }
+            },
+            token => Err(format!("unexpected {}", token)),
+        }
+    }
+
+    fn name(&self, name: &str) -> Result<Value, String> {
+        match (self.assembler.symbols.get(name), self.lookup) {
+            (Some(symbol), Lookup::Strict) if symbol.pass != self.assembler.pass => {
+                Err(format!("{} has to be defined before it is used here", name))
+            },
+            (Some(symbol), _) => Ok(symbol.value.clone()),
+            (None, Lookup::Lenient) => Ok(Value::absolute(0)),
+            (None, _) => Err(format!("{} is not defined", name)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::disassembler;
+    use crate::symbols::SymbolTable;
+
+    fn bytes(source: &str) -> Vec<u8> {
+        assemble(source).unwrap().image.binary().1
+    }
+
+    fn error(source: &str) -> (u32, String) {
+        let error = assemble(source).err().expect("the source does not assemble");
+        (error.line, error.message)
+    }
+
+    #[test]
+    fn invaders_disassembles_and_reassembles_to_the_same_rom() {
+        let rom = include_bytes!("invaders.rom");
+
+        for symbols in &[SymbolTable::new(), SymbolTable::invaders()] {
+            let source = disassembler::source(rom, 0, symbols);
+            assert_eq!(assemble(&source).unwrap().image.binary(), (0, rom.to_vec()));
+        }
+    }
+
+    #[test]
+    fn expressions_follow_precedence_and_take_names_defined_later() {
+        let source = "
+    size    equ 10
+            org 100h
+            db 1 + 2 * 3, (1 + 2) * 3, 10 mod 3, 1 shl 4, 0f0h shr 4
+            db high 1234h, low 1234h, 'A' + 1, -1
+            dw 5 gt 3, 5 eq 3, not 0, $, later
+    later   equ 2 * size
+        ";
+
+        assert_eq!(bytes(source), vec![
+            7, 9, 1, 16, 15,
+            0x12, 0x34, 0x42, 0xff,
+            0xff, 0xff, 0x00, 0x00, 0xff, 0xff, 0x09, 0x01, 20, 0,
+        ]);
+    }
+
+    #[test]
+    fn macros_take_arguments_and_local_labels() {
+        let source = "
+    store   macro value, address
+            mvi a,value
+            sta address
+            endm
+    delay   macro
+            local wait
+    wait:   dcr a
+            jnz wait
+            endm
+
+            store 5, 2000h
+            delay
+            delay
+            rept 2
+            nop
+            endm
+            irp register, <b, c>
+            inr register
+            endm
+        ";
+
+        assert_eq!(bytes(source), vec![
+            0x3e, 0x05, 0x32, 0x00, 0x20,
+            0x3d, 0xc2, 0x05, 0x00,
+            0x3d, 0xc2, 0x09, 0x00,
+            0x00, 0x00,
+            0x04, 0x0c,
+        ]);
+    }
+
+    #[test]
+    fn errors_name_the_line_and_what_is_wrong() {
+        assert_eq!(error("nop\nmov m,m"), (2, "mov m,m is not an instruction".to_string()));
+        assert_eq!(error("jnq 0"), (1, "unknown instruction jnq".to_string()));
+        assert_eq!(error("mvi a"), (1, "mvi takes 2 operands".to_string()));
+        assert_eq!(error("rst 8"), (1, "rst 8 is not a vector".to_string()));
+        assert_eq!(error("if 1\nnop"), (2, "IF without ENDIF".to_string()));
+        assert_eq!(error("again macro\nagain\nendm\nagain").1, "macros expanded deeper than 64");
+    }
+}
@@ -0,0 +1,359 @@
+use std::fmt;
+use std::time::{Duration, Instant};
+
+use crate::assembler::{self, Assembly};
+use crate::block_cache::{BlockCache, CacheStatistics};
+use crate::cpu::State8080;
+use crate::error::{CpuError, MachineError};
+use crate::invaders_native;
+use crate::native::{Machine, Stop};
+use crate::processor::{Cpu, Interrupt};
+use crate::space_invader::{self, GameState, IOState, SpaceInvaderIO};
+use crate::step::{RunUntil, StopReason};
+
+const ALU_LOOP: &str = "
+        add b
+        sub c
+        ana d
+        xra e
+        ora h
+        cmp l
+        inr b
+        dcr c
+        adi 3
+        adc a
+        sbb d
+        inr e
+        rlc
+        cma
+        cmc
+        jmp 0
+";
+
+// copies 1000-1fff to 2000-2fff a byte at a time, over and over
+const BLOCK_COPY: &str = "
+        lxi h,1000h
+        lxi d,2000h
+        lxi b,1000h
+loop:   mov a,m
+        stax d
+        inx h
+        inx d
+        dcx b
+        mov a,b
+        ora c
+        jnz loop
+        jmp 0
+";
+
+// every ALU operation and DAA on every pair of operands, folding A and the
+// flags into a checksum in HL that differs when any of them is computed
+// differently
+const OPERATIONS: &str = "
+        lxi sp,1000h
+        lxi h,0
+        mvi d,0
+outer:  mvi e,0
+inner:  mov a,d
+        add e
+        call fold
+        mov a,d
+        adc e
+        call fold
+        mov a,d
+        sub e
+        call fold
+        mov a,d
+        sbb e
+        call fold
+        mov a,d
+        ana e
+        call fold
+        mov a,d
+        xra e
+        call fold
+        mov a,d
+        ora e
+        call fold
+        mov a,d
+        cmp e
+        call fold
+        mov a,d
+        add e
+        daa
+        call fold
+        inr e
+        jnz inner
+        inr d
+        jnz outer
+done:   hlt
+
+fold:   push psw
+        pop b
+        dad b
+        ret
+";
+
+// what OPERATIONS leaves in HL when the core computes like an 8080
+pub const OPERATIONS_CHECKSUM: u16 = 0xd33c;
+
+// where the CP/M BDOS is entered, it is also the top of the program's memory
+const BDOS: usize = 0x3f00;
+// longest CP/M program that fits below the BDOS
+pub const MAX_PROGRAM_SIZE: usize = BDOS - 0x100;
+
+// the page zero a program finds, the BDOS functions 2 (print E) and 9 (print
+// the string at DE up to '$') through port 1, and after them what the CCP
+// does before it runs a program: a stack below the BDOS with the warm boot
+// to return to, the address of the BDOS is defined before it
+const CPM: &str = "
+        org 0
+        hlt
+        org 5
+        jmp bdos
+
+        org bdos
+        mov a,c
+        cpi 2
+        jz putc
+        cpi 9
+        rnz
+puts:   ldax d
+        cpi '$'
+        rz
+        out 1
+        inx d
+        jmp puts
+putc:   mov a,e
+        out 1
+        ret
+
+boot:   lxi sp,bdos
+        lxi h,0
+        push h
+        jmp 100h
+";
+
+// one benchmark run, rates are per second of host time
+#[derive(Clone, Debug, PartialEq)]
+pub struct Measurement {
+    pub name: &'static str,
+    pub cycles: u64,
+    pub instructions: u64,
+    // frames completed by the whole-machine benchmarks
+    pub frames: Option<u64>,
+    // what the block cache did, when the run used one
+    pub blocks: Option<CacheStatistics>,
+    pub elapsed: Duration,
+}
+
+impl Measurement {
+    // emulated clock rate
+    pub fn mhz(&self) -> f64 {
+        self.cycles as f64 / self.elapsed.as_secs_f64() / 1e6
+    }
+
+    pub fn mips(&self) -> f64 {
+        self.instructions as f64 / self.elapsed.as_secs_f64() / 1e6
+    }
+
+    pub fn fps(&self) -> Option<f64> {
+        self.frames.map(|frames| frames as f64 / self.elapsed.as_secs_f64())
+    }
+}
+
+impl fmt::Display for Measurement {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(f, "{:<16} {:>8.3} s {:>9.2} MHz", self.name, self.elapsed.as_secs_f64(), self.mhz())?;
+        // the recompiled code does not count instructions
+        if self.instructions > 0 {
+            write!(f, " {:>8.2} MIPS", self.mips())?;
+        } else {
+            write!(f, " {:>13}", "")?;
+        }
+        if let Some(fps) = self.fps() {
+            write!(f, " {:>9.1} fps", fps)?;
+        }
+        if let Some(blocks) = self.blocks {
+            write!(f, "  {} blocks compiled, {} invalidated", blocks.compiled, blocks.invalidated)?;
+        }
+        Ok(())
+    }
+}
+
+// collects what a CP/M program prints, other ports read as 0
+pub struct Console {
+    pub output: String,
+}
+
+impl IOState for Console {
+    fn input(&self, _port: u8) -> Result<u8, MachineError> {
+        Ok(0)
+    }
+
+    fn output(&mut self, port: u8, value: u8) -> Result<(), MachineError> {
+        if port == 1 {
+            self.output.push(value as char);
+        }
+        Ok(())
+    }
+}
+
+// the benchmark programs are fixed, they assemble
+fn assemble(source: &str) -> Assembly {
+    assembler::assemble(source).unwrap_or_else(|error| panic!("benchmark program: {}", error))
+}
+
+fn core(image: &[u8], pc: u16, block_cache: bool) -> State8080 {
+    let mut cpu = State8080::load_from_rom(image, 0, pc);
+    if block_cache {
+        cpu.set_block_cache(BlockCache::new());
+    }
+    cpu
+}
+
+fn statistics(cpu: &State8080) -> Option<CacheStatistics> {
+    cpu.block_cache().map(BlockCache::statistics)
+}
+
+fn run_program(name: &'static str, image: &[u8], cycles: u64, block_cache: bool) -> Result<Measurement, CpuError> {
+    let mut cpu = core(image, 0, block_cache);
+    let mut console = Console { output: String::new() };
+
+    let start = Instant::now();
+    let (stop, cycles, instructions) = cpu.run(RunUntil::Cycles(cycles), &mut console)?;
+    let elapsed = start.elapsed();
+
+    if let StopReason::IllegalOpcode { address, opcode } = stop {
+        return Err(CpuError::IllegalOpcode { address, opcode });
+    }
+
+    Ok(Measurement { name, cycles, instructions, frames: None, blocks: statistics(&cpu), elapsed })
+}
+
+// register arithmetic and logic with a jump back every 16 instructions
+pub fn alu(cycles: u64, block_cache: bool) -> Result<Measurement, CpuError> {
+    run_program("alu loop", &assemble(ALU_LOOP).image.binary().1, cycles, block_cache)
+}
+
+// a load and a store for every byte, the pattern of memory-bound code
+pub fn block_copy(cycles: u64, block_cache: bool) -> Result<Measurement, CpuError> {
+    let program = assemble(BLOCK_COPY).image.binary().1;
+    let mut image = vec![0; 0x3000];
+    image[..program.len()].copy_from_slice(&program);
+    for (i, byte) in image[0x1000..0x2000].iter_mut().enumerate() {
+        *byte = i as u8;
+    }
+
+    run_program("block copy", &image, cycles, block_cache)
+}
+
+// the operations exerciser up to its end, with the checksum it computed
+pub fn operations(block_cache: bool) -> Result<(Measurement, u16), CpuError> {
+    let assembly = assemble(OPERATIONS);
+    let program = assembly.image.binary().1;
+    // and a stack
+    let mut image = vec![0; 0x1000];
+    image[..program.len()].copy_from_slice(&program);
+
+    let mut cpu = core(&image, 0, block_cache);
+    let mut console = Console { output: String::new() };
+
+    let start = Instant::now();
+    let (stop, cycles, instructions) = cpu.run(RunUntil::Pc(assembly.symbols["done"]), &mut console)?;
+    let elapsed = start.elapsed();
+
+    if let StopReason::IllegalOpcode { address, opcode } = stop {
+        return Err(CpuError::IllegalOpcode { address, opcode });
+    }
+
+    let registers = cpu.registers();
+    let checksum = (registers.h as u16) << 8 | registers.l as u16;
+    let measurement = Measurement { name: "operations", cycles, instructions, frames: None, blocks: statistics(&cpu), elapsed };
+    Ok((measurement, checksum))
+}
+
+// headless frames from power on, the attract mode runs without input
+pub fn invaders(frames: u64, block_cache: bool) -> Result<Measurement, CpuError> {
+    let mut game = GameState::new_game();
+    if block_cache {
+        game.cpu_mut().set_block_cache(BlockCache::new());
+    }
+
+    let start = Instant::now();
+    for _ in 0..frames {
+        game.run_frame()?;
+    }
+
+    Ok(Measurement {
+        name: "invaders",
+        cycles: game.cycles(),
+        instructions: game.instructions(),
+        frames: Some(frames),
+        blocks: statistics(game.cpu()),
+        elapsed: start.elapsed(),
+    })
+}
+
+// the same frames in the recompiled ROM, instructions are not counted
+pub fn invaders_native(frames: u64) -> Result<Measurement, CpuError> {
+    let mut cpu = space_invader::invaders_cpu();
+    let mut io = SpaceInvaderIO::new();
+    let budget = GameState::<State8080>::CYCLES_PER_FRAME / 2;
+    let mut halves = 0;
+
+    let mut deadline = |cpu: &mut State8080, _: &mut dyn IOState| {
+        cpu.interrupt(Interrupt::Rst(if halves % 2 == 0 { 1 } else { 2 }));
+        halves += 1;
+        halves < 2 * frames
+    };
+
+    let start = Instant::now();
+    let stop = Machine::new(&mut cpu, &mut io, invaders_native::routine, budget, &mut deadline).run();
+    let elapsed = start.elapsed();
+
+    if let Stop::Fault(error) = stop {
+        return Err(error);
+    }
+    Ok(Measurement { name: "invaders native", cycles: frames * 2 * budget, instructions: 0, frames: Some(frames), blocks: None, elapsed })
+}
+
+// a CP/M program such as 8080EXER.COM, run until it warm boots, with what it
+// printed, up to `MAX_PROGRAM_SIZE` bytes
+pub fn exerciser(program: &[u8], block_cache: bool) -> Result<(Measurement, String), CpuError> {
+    let cpm = assemble(&format!("bdos equ {}\n{}", BDOS, CPM));
+    let mut image = cpm.image.binary().1;
+    image[0x100..0x100 + program.len()].copy_from_slice(program);
+
+    let mut cpu = core(&image, cpm.symbols["boot"], block_cache);
+    let mut console = Console { output: String::new() };
+
+    // timed up to the warm boot, a jump to 0 or BDOS function 0, not through
+    // the halt after it
+    let start = Instant::now();
+    let until = RunUntil::Predicate(Box::new(|cpu: &State8080| {
+        cpu.pc() == 0 || cpu.pc() as usize == BDOS && cpu.registers().c == 0
+    }));
+    let (stop, cycles, instructions) = cpu.run(until, &mut console)?;
+    let elapsed = start.elapsed();
+
+    if let StopReason::IllegalOpcode { address, opcode } = stop {
+        return Err(CpuError::IllegalOpcode { address, opcode });
+    }
+
+    let measurement = Measurement { name: "exerciser", cycles, instructions, frames: None, blocks: statistics(&cpu), elapsed };
+    Ok((measurement, console.output))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn operations_checksum_is_the_known_good_one() {
+        for block_cache in [false, true] {
+            let (_, checksum) = operations(block_cache).unwrap();
+            assert_eq!(checksum, OPERATIONS_CHECKSUM, "block cache {}", block_cache);
+        }
+    }
+}
@@ -0,0 +1,245 @@
+use std::rc::Rc;
+
+use crate::cpu::{instruction_length, is_illegal};
+
+// longest run of instructions decoded into one block
+const MAX_BLOCK_LENGTH: usize = 64;
+const PAGE_SIZE: usize = 0x100;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DecodedInstruction {
+    pub address: u16,
+    pub opcode: u8,
+    // immediate data or address, little endian as in memory
+    pub operand: u16,
+    pub length: u16,
+}
+
+// straight-line code up to and including the first instruction that may
+// transfer control
+#[derive(Debug, PartialEq)]
+pub struct Block {
+    pub start: u16,
+    // first address after the block
+    pub end: u16,
+    pub instructions: Vec<DecodedInstruction>,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct CacheStatistics {
+    pub compiled: u64,
+    pub invalidated: u64,
+}
+
+// pre-decoded blocks by start address, dropped when their bytes are written
+pub struct BlockCache {
+    blocks: Vec<Option<Rc<Block>>>,
+    // starts of the blocks overlapping every 256 byte page
+    pages: Vec<Vec<u16>>,
+    // set when a write dropped a block, the running block may be stale
+    invalidated: bool,
+    statistics: CacheStatistics,
+}
+
+impl BlockCache {
+    pub fn new() -> Self {
+        Self {
+            blocks: vec![None; 0x10000],
+            pages: vec![Vec::new(); 0x10000 / PAGE_SIZE],
+            invalidated: false,
+            statistics: CacheStatistics::default(),
+        }
+    }
+
+    pub fn statistics(&self) -> CacheStatistics {
+        self.statistics
+    }
+
+    pub fn clear(&mut self) {
+        self.blocks.iter_mut().for_each(|block| *block = None);
+        self.pages.iter_mut().for_each(|page| page.clear());
+        self.invalidated = true;
+    }
+
+    // the block starting at `address`, decoded from memory on first use,
+    // none when the first instruction can not be cached
+    pub fn block(&mut self, address: u16, memory: &[u8]) -> Option<Rc<Block>> {
+        if let Some(block) = &self.blocks[address as usize] {
+            return Some(Rc::clone(block));
+        }
+
+        let block = Rc::new(decode(address, memory)?);
+        for page in (block.start as usize / PAGE_SIZE)..=((block.end as usize - 1) / PAGE_SIZE) {
+            self.pages[page].push(block.start);
+        }
+        self.blocks[address as usize] = Some(Rc::clone(&block));
+        self.statistics.compiled += 1;
+
+        Some(block)
+    }
+
+    pub fn invalidate(&mut self, address: u16) {
+        let page = address as usize / PAGE_SIZE;
+        if self.pages[page].is_empty() {
+            return;
+        }
+
+        let blocks = &mut self.blocks;
+        let mut dropped = Vec::new();
+        for &start in &self.pages[page] {
+            let covers = match &blocks[start as usize] {
+                Some(block) => block.start <= address && address < block.end,
+                None => false,
+            };
+            if covers {
+                dropped.push(blocks[start as usize].take().unwrap());
+            }
+        }
+
+        for block in dropped {
+            for page in (block.start as usize / PAGE_SIZE)..=((block.end as usize - 1) / PAGE_SIZE) {
+                self.pages[page].retain(|&start| start != block.start);
+            }
+            self.statistics.invalidated += 1;
+            self.invalidated = true;
+        }
+    }
+
+    // whether a block was dropped since the last call
+    pub fn take_invalidated(&mut self) -> bool {
+        std::mem::replace(&mut self.invalidated, false)
+    }
+}
+
+// instructions that can leave the block: jumps, calls, returns, RST, PCHL and HLT
+fn ends_block(opcode: u8) -> bool {
+    match opcode {
+        0x76 | 0xe9 => true,
+        // Jcc, JMP, Ccc, CALL, Rcc, RET and RST
+        _ => opcode & 0xc0 == 0xc0 && match opcode & 7 {
+            0 | 2 | 4 | 7 => true,
+            1 => opcode & 0xf == 9 && opcode != 0xf9,
+            3 => opcode == 0xc3,
+            5 => opcode & 0xf == 0xd,
+            _ => false,
+        },
+    }
+}
+
+fn decode(start: u16, memory: &[u8]) -> Option<Block> {
+    let mut instructions = Vec::new();
+    let mut address = start as usize;
+
+    while instructions.len() < MAX_BLOCK_LENGTH {
+        let opcode = match memory.get(address) {
+            Some(&opcode) => opcode,
+            None => break,
+        };
+        let length = instruction_length(opcode);
+        let end = address + length as usize;
+
+        // illegal opcodes and instructions running off the memory are left
+        // to the interpreter, which reports them
+        if is_illegal(opcode) || end > memory.len() || end > 0xffff {
+            break;
+        }
+
+        let operand = match length {
+            2 => memory[address + 1] as u16,
+            3 => (memory[address + 2] as u16) << 8 | memory[address + 1] as u16,
+            _ => 0,
+        };
+        instructions.push(DecodedInstruction { address: address as u16, opcode, operand, length });
+        address = end;
+
+        if ends_block(opcode) {
+            break;
+        }
+    }
+
+    if instructions.is_empty() {
+        None
+    } else {
+        Some(Block { start, end: address as u16, instructions })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assembler;
+    use crate::bench::Console;
+    use crate::cpu::State8080;
+    use crate::processor::Cpu;
+    use crate::space_invader::GameState;
+    use crate::step::{RunUntil, StopReason};
+
+    #[test]
+    fn invaders_runs_the_same_through_cached_blocks() {
+        let mut interpreted = GameState::new_game();
+        let mut cached = GameState::new_game();
+        cached.cpu_mut().set_block_cache(BlockCache::new());
+
+        // past the self test and into the attract mode
+        for _ in 0..300 {
+            interpreted.run_frame().unwrap();
+            cached.run_frame().unwrap();
+        }
+
+        assert_eq!(Cpu::registers(cached.cpu()), Cpu::registers(interpreted.cpu()));
+        assert_eq!(cached.cpu().memory(), interpreted.cpu().memory());
+        assert_eq!(cached.cycles(), interpreted.cycles());
+        assert_eq!(cached.instructions(), interpreted.instructions());
+        assert!(cached.cpu().block_cache().unwrap().statistics().compiled > 0);
+    }
+
+    // the program to its halt with a cache, A and C afterwards
+    fn run_cached(source: &str) -> (u8, u8, CacheStatistics) {
+        let assembly = assembler::assemble(source).unwrap();
+        let mut cpu = State8080::new();
+        cpu.load_image(&assembly.image).unwrap();
+        cpu.set_block_cache(BlockCache::new());
+
+        let (stop, _, _) = cpu.run(RunUntil::Cycles(100_000), &mut Console { output: String::new() }).unwrap();
+        assert_eq!(stop, StopReason::Halted);
+        let registers = Cpu::registers(&cpu);
+        (registers.a, registers.c, cpu.block_cache().unwrap().statistics())
+    }
+
+    #[test]
+    fn writes_to_a_cached_block_drop_it() {
+        // the immediate of the MVI goes up by one every call
+        let (_, c, statistics) = run_cached("
+        lxi sp,3000h
+        mvi c,0
+        mvi b,3
+again:  call target
+        lxi h,target+1
+        inr m
+        dcr b
+        jnz again
+        hlt
+target: mvi a,10h
+        add c
+        mov c,a
+        ret
+        ");
+
+        // compiled again for every call after the first
+        assert_eq!(c, 0x10 + 0x11 + 0x12);
+        assert_eq!(statistics.invalidated, 3);
+    }
+
+    #[test]
+    fn writes_to_the_running_block_are_seen_by_its_next_instruction() {
+        let (a, _, statistics) = run_cached("
+        lxi h,next+1
+        mvi m,42h
+next:   mvi a,0
+        hlt
+        ");
+
+        assert_eq!(a, 0x42);
+        assert_eq!(statistics.invalidated, 1);
+    }
+}
@@ -0,0 +1,66 @@
+// bits of the status word the 8080 puts on the data bus during T1 of every
+// machine cycle, latched by the 8228 system controller
+pub const STATUS_INTA: u8 = 1;
+pub const STATUS_WO: u8 = 1 << 1;
+pub const STATUS_STACK: u8 = 1 << 2;
+pub const STATUS_HLTA: u8 = 1 << 3;
+pub const STATUS_OUT: u8 = 1 << 4;
+pub const STATUS_M1: u8 = 1 << 5;
+pub const STATUS_INP: u8 = 1 << 6;
+pub const STATUS_MEMR: u8 = 1 << 7;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CycleKind {
+    Fetch,
+    MemoryRead,
+    MemoryWrite,
+    StackRead,
+    StackWrite,
+    InputRead,
+    OutputWrite,
+    InterruptAck,
+    HaltAck,
+    // T-states without bus activity, e.g. the 16 bit addition of DAD
+    Internal,
+}
+
+impl CycleKind {
+    // the status word of the cycle, 0 for internal cycles that output none
+    pub fn status(self) -> u8 {
+        match self {
+            CycleKind::Fetch => STATUS_MEMR | STATUS_M1 | STATUS_WO,
+            CycleKind::MemoryRead => STATUS_MEMR | STATUS_WO,
+            CycleKind::MemoryWrite => 0,
+            CycleKind::StackRead => STATUS_MEMR | STATUS_STACK | STATUS_WO,
+            CycleKind::StackWrite => STATUS_STACK,
+            CycleKind::InputRead => STATUS_INP | STATUS_WO,
+            CycleKind::OutputWrite => STATUS_OUT,
+            CycleKind::InterruptAck => STATUS_M1 | STATUS_WO | STATUS_INTA,
+            CycleKind::HaltAck => STATUS_MEMR | STATUS_HLTA | STATUS_WO,
+            CycleKind::Internal => 0,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MachineCycle {
+    pub kind: CycleKind,
+    pub status: u8,
+    // IO cycles carry the port on both halves of the address bus
+    pub address: u16,
+    pub data: u8,
+    // including the wait states
+    pub t_states: u8,
+    pub wait_states: u8,
+}
+
+pub trait BusMonitor {
+    // T-states READY is held low for, asked before the cycle completes the
+    // way slow memory or an 8224 wait state generator would
+    fn wait_states(&mut self, _cycle: &MachineCycle) -> u8 {
+        0
+    }
+
+    // called after every machine cycle
+    fn machine_cycle(&mut self, cycle: &MachineCycle);
+}
@@ -0,0 +1,289 @@
+use std::collections::VecDeque;
+use std::fmt;
+
+use crate::symbols::SymbolTable;
+
+// deeper nesting than this is a program that never returns, the oldest
+// frames are forgotten
+const MAX_DEPTH: usize = 1024;
+const MAX_ANOMALIES: usize = 64;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FrameKind {
+    Call,
+    Rst,
+    Interrupt,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Frame {
+    pub kind: FrameKind,
+    // address of the CALL or RST, the interrupted PC for interrupts
+    pub call_site: u16,
+    pub target: u16,
+    pub return_address: u16,
+    // where the return address is stored on the stack
+    pub slot: u16,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Anomaly {
+    // RET to another address than the one the frame pushed
+    MismatchedReturn { address: u16, expected: u16, actual: u16 },
+    // RET with no frame at SP, e.g. a computed jump through PUSH and RET
+    UnmatchedReturn { address: u16, target: u16 },
+    // XTHL swapped a return address with HL
+    ReturnAddressExchanged { address: u16, old: u16, new: u16 },
+    // a return address was popped into a register pair
+    ReturnAddressPopped { address: u16, return_address: u16 },
+    // SP moved above frames that never returned, by SPHL, LXI SP or a RET
+    // further up the stack
+    FramesDiscarded { address: u16, count: usize },
+}
+
+impl fmt::Display for Anomaly {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match *self {
+            Anomaly::MismatchedReturn { address, expected, actual } => write!(
+                f, "{:04x}: returned to {:04x} instead of {:04x}", address, actual, expected
+            ),
+            Anomaly::UnmatchedReturn { address, target } => write!(
+                f, "{:04x}: returned to {:04x} without a matching call", address, target
+            ),
+            Anomaly::ReturnAddressExchanged { address, old, new } => write!(
+                f, "{:04x}: return address {:04x} exchanged for {:04x}", address, old, new
+            ),
+            Anomaly::ReturnAddressPopped { address, return_address } => write!(
+                f, "{:04x}: return address {:04x} popped into a register", address, return_address
+            ),
+            Anomaly::FramesDiscarded { address, count } => write!(
+                f, "{:04x}: stack pointer moved past {} frames", address, count
+            ),
+        }
+    }
+}
+
+// the calls the program is in, tracked next to the real stack
+#[derive(Default)]
+pub struct CallStack {
+    frames: Vec<Frame>,
+    anomalies: VecDeque<Anomaly>,
+    symbols: SymbolTable,
+}
+
+impl CallStack {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn frames(&self) -> &[Frame] {
+        &self.frames
+    }
+
+    // the most recent anomalies, oldest first
+    pub fn anomalies(&self) -> impl Iterator<Item = &Anomaly> {
+        self.anomalies.iter()
+    }
+
+    pub fn symbols(&self) -> &SymbolTable {
+        &self.symbols
+    }
+
+    pub fn symbols_mut(&mut self) -> &mut SymbolTable {
+        &mut self.symbols
+    }
+
+    pub fn set_symbols(&mut self, symbols: SymbolTable) {
+        self.symbols = symbols;
+    }
+
+    // the frames as they were at an earlier point of the program
+    pub fn set_frames(&mut self, frames: &[Frame]) {
+        self.frames.clear();
+        self.frames.extend_from_slice(frames);
+    }
+
+    pub fn clear(&mut self) {
+        self.frames.clear();
+        self.anomalies.clear();
+    }
+
+    // after the return address has been pushed to `slot`
+    pub fn enter(&mut self, kind: FrameKind, call_site: u16, target: u16, return_address: u16, slot: u16) {
+        self.discard_below(call_site, slot.saturating_add(2));
+
+        if self.frames.len() == MAX_DEPTH {
+            self.frames.remove(0);
+        }
+        self.frames.push(Frame { kind, call_site, target, return_address, slot });
+    }
+
+    // before a RET at `address` pops `target` from `sp`
+    pub fn leave(&mut self, address: u16, sp: u16, target: u16) {
+        self.discard_below(address, sp);
+
+        match self.frames.last() {
+            Some(frame) if frame.slot == sp => {
+                if frame.return_address != target {
+                    self.report(Anomaly::MismatchedReturn {
+                        address,
+                        expected: frame.return_address,
+                        actual: target,
+                    });
+                }
+                self.frames.pop();
+            },
+            _ => self.report(Anomaly::UnmatchedReturn { address, target }),
+        }
+    }
+
+    // before a POP at `address` reads from `sp`
+    pub fn pop(&mut self, address: u16, sp: u16) {
+        self.discard_below(address, sp);
+
+        if let Some(frame) = self.frames.last() {
+            if frame.slot == sp {
+                let return_address = frame.return_address;
+                self.frames.pop();
+                self.report(Anomaly::ReturnAddressPopped { address, return_address });
+            }
+        }
+    }
+
+    // after XTHL at `address` put `value` at `sp`
+    pub fn exchange(&mut self, address: u16, sp: u16, value: u16) {
+        if let Some(frame) = self.frames.last_mut() {
+            if frame.slot == sp && frame.return_address != value {
+                let old = frame.return_address;
+                // the RET is expected to use the new address
+                frame.return_address = value;
+                self.report(Anomaly::ReturnAddressExchanged { address, old, new: value });
+            }
+        }
+    }
+
+    // after SPHL or LXI SP at `address` loaded `sp`
+    pub fn set_stack_pointer(&mut self, address: u16, sp: u16) {
+        self.discard_below(address, sp);
+    }
+
+    // frames stored below SP were abandoned, the stack grows downwards
+    fn discard_below(&mut self, address: u16, sp: u16) {
+        let live = self.frames.iter().rposition(|frame| frame.slot >= sp).map_or(0, |i| i + 1);
+        let count = self.frames.len() - live;
+
+        if count > 0 {
+            self.frames.truncate(live);
+            self.report(Anomaly::FramesDiscarded { address, count });
+        }
+    }
+
+    fn report(&mut self, anomaly: Anomaly) {
+        if self.anomalies.len() == MAX_ANOMALIES {
+            self.anomalies.pop_front();
+        }
+        self.anomalies.push_back(anomaly);
+    }
+
+    // innermost frame first, starting at the current PC
+    pub fn backtrace(&self, pc: u16) -> Backtrace {
+        let mut address = pc;
+        let mut frames = Vec::with_capacity(self.frames.len() + 1);
+
+        for frame in self.frames.iter().rev() {
+            frames.push(BacktraceFrame {
+                address,
+                function: Some(frame.target),
+                name: self.symbols.label(frame.target),
+                kind: Some(frame.kind),
+            });
+            address = frame.return_address;
+        }
+        frames.push(BacktraceFrame { address, function: None, name: None, kind: None });
+
+        Backtrace { frames, anomalies: self.anomalies.iter().cloned().collect() }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct BacktraceFrame {
+    // the PC for the innermost frame, the return address for the others
+    pub address: u16,
+    // entry of the routine, unknown for the outermost frame
+    pub function: Option<u16>,
+    pub name: Option<String>,
+    pub kind: Option<FrameKind>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Backtrace {
+    pub frames: Vec<BacktraceFrame>,
+    // why the frames may not be what the program thinks they are
+    pub anomalies: Vec<Anomaly>,
+}
+
+impl fmt::Display for Backtrace {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        for (depth, frame) in self.frames.iter().enumerate() {
+            write!(f, "#{:<3} {:04x} in ", depth, frame.address)?;
+
+            match (&frame.name, frame.function) {
+                (Some(name), _) => write!(f, "{}", name)?,
+                (None, Some(function)) => write!(f, "{:04x}", function)?,
+                (None, None) => write!(f, "??")?,
+            }
+
+            match frame.kind {
+                Some(FrameKind::Rst) => writeln!(f, " (rst)")?,
+                Some(FrameKind::Interrupt) => writeln!(f, " (interrupt)")?,
+                _ => writeln!(f)?,
+            }
+        }
+        if !self.anomalies.is_empty() {
+            writeln!(f, "stack anomalies, oldest first:")?;
+            for anomaly in &self.anomalies {
+                writeln!(f, "    {}", anomaly)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assembler;
+    use crate::bench::Console;
+    use crate::cpu::State8080;
+
+    #[test]
+    fn backtrace_lists_the_anomalies_after_the_frames() {
+        let assembly = assembler::assemble("
+        lxi sp,3000h
+        call sub
+        hlt
+        hlt
+sub:    lxi h,7
+        xthl
+        ret
+        ").unwrap();
+        let mut cpu = State8080::new();
+        cpu.load_image(&assembly.image).unwrap();
+        let mut console = Console { output: String::new() };
+        for _ in 0..4 {
+            cpu.emulate(&mut console).unwrap();
+        }
+
+        let backtrace = cpu.backtrace();
+        assert_eq!(backtrace.anomalies, vec![Anomaly::ReturnAddressExchanged { address: 0x0b, old: 6, new: 7 }]);
+        assert_eq!(
+            backtrace.to_string(),
+            "#0   000c in 0008\n#1   0007 in ??\nstack anomalies, oldest first:\n    000b: return address 0006 exchanged for 0007\n"
+        );
+
+        // the RET takes the exchanged address the frame now expects
+        cpu.emulate(&mut console).unwrap();
+        assert_eq!(cpu.pc(), 7);
+        assert_eq!(cpu.backtrace().anomalies.len(), 1);
+    }
+}
@@ -0,0 +1,203 @@
+use std::io::{self, Write};
+
+use crate::bus::{CycleKind, MachineCycle};
+use crate::cpu::instruction_length;
+use crate::png;
+
+// how an address has been accessed, a byte can collect several
+pub const OPCODE: u8 = 1;
+pub const OPERAND: u8 = 1 << 1;
+pub const READ: u8 = 1 << 2;
+pub const WRITTEN: u8 = 1 << 3;
+
+// records how every address of the 64 KiB space has been used
+pub struct Coverage {
+    map: Vec<u8>,
+    // operand bytes of the current instruction still to be read
+    operands: u16,
+    // operands supplied by an interrupting device are not in memory
+    from_bus: bool,
+}
+
+impl Coverage {
+    pub fn new() -> Self {
+        Self {
+            map: vec![0; 0x10000],
+            operands: 0,
+            from_bus: false,
+        }
+    }
+
+    // the machine cycles of one instruction
+    pub fn record(&mut self, cycles: &[MachineCycle]) {
+        for cycle in cycles {
+            let access = &mut self.map[cycle.address as usize];
+
+            match cycle.kind {
+                CycleKind::Fetch | CycleKind::InterruptAck => {
+                    if cycle.kind == CycleKind::Fetch {
+                        *access |= OPCODE;
+                    }
+                    self.operands = instruction_length(cycle.data) - 1;
+                    self.from_bus = cycle.kind == CycleKind::InterruptAck;
+                },
+                CycleKind::MemoryRead if self.operands > 0 => {
+                    if !self.from_bus {
+                        *access |= OPERAND;
+                    }
+                    self.operands -= 1;
+                },
+                CycleKind::MemoryRead | CycleKind::StackRead => *access |= READ,
+                CycleKind::MemoryWrite | CycleKind::StackWrite => *access |= WRITTEN,
+                _ => (),
+            }
+        }
+    }
+
+    // classifies every byte of the ROM as code, data or unused and lists
+    // the RAM the program really touched
+    pub fn write_report(&self, out: &mut dyn Write, rom_size: usize) -> io::Result<()> {
+        let rom = &self.map[..rom_size];
+        let count = |mask: u8| rom.iter().filter(|&&access| access & mask != 0).count();
+        let percent = |n: usize| 100.0 * n as f64 / rom_size.max(1) as f64;
+
+        let code = count(OPCODE | OPERAND);
+        let data = rom.iter().filter(|&&access| access & (OPCODE | OPERAND) == 0 && access & READ != 0).count();
+        let unused = rom.iter().filter(|&&access| access == 0).count();
+
+        writeln!(out, "rom 0000-{:04x}", rom_size.saturating_sub(1))?;
+        writeln!(out, "  code    {:>6} bytes {:>6.2}%", code, percent(code))?;
+        writeln!(out, "  data    {:>6} bytes {:>6.2}%", data, percent(data))?;
+        writeln!(out, "  unused  {:>6} bytes {:>6.2}%", unused, percent(unused))?;
+        writeln!(out, "  written {:>6} bytes", count(WRITTEN))?;
+        writeln!(out)?;
+
+        for (start, end, class) in runs(&self.map, 0, rom_size, rom_class) {
+            writeln!(out, "{:04x}-{:04x} {}", start, end - 1, class)?;
+        }
+
+        let ram = &self.map[rom_size..];
+        let written = ram.iter().filter(|&&access| access & WRITTEN != 0).count();
+        let read = ram.iter().filter(|&&access| access & READ != 0).count();
+
+        writeln!(out)?;
+        writeln!(out, "ram {:04x}-ffff", rom_size)?;
+        writeln!(out, "  written {:>6} bytes", written)?;
+        writeln!(out, "  read    {:>6} bytes", read)?;
+        writeln!(out)?;
+
+        for (start, end, class) in runs(&self.map, rom_size, self.map.len(), ram_class) {
+            if class != "unused" {
+                writeln!(out, "{:04x}-{:04x} {}", start, end - 1, class)?;
+            }
+        }
+        Ok(())
+    }
+
+    // one pixel per address, a row per 256 bytes: opcodes bright green,
+    // operands dark green, reads blue and writes red, mixed where combined
+    pub fn write_png(&self, out: &mut dyn Write) -> io::Result<()> {
+        let mut pixels = Vec::with_capacity(self.map.len() * 3);
+
+        for &access in &self.map {
+            let green = if access & OPCODE != 0 {
+                0xff
+            } else if access & OPERAND != 0 {
+                0x90
+            } else {
+                0
+            };
+            let red = if access & WRITTEN != 0 { 0xff } else { 0 };
+            let blue = if access & READ != 0 { 0xff } else { 0 };
+
+            pixels.extend_from_slice(&[red, green, blue]);
+        }
+
+        png::write_rgb(out, 256, 256, &pixels)
+    }
+}
+
+fn rom_class(access: u8) -> &'static str {
+    if access & WRITTEN != 0 {
+        "written"
+    } else if access & (OPCODE | OPERAND) != 0 && access & READ != 0 {
+        "code, read as data"
+    } else if access & (OPCODE | OPERAND) != 0 {
+        "code"
+    } else if access & READ != 0 {
+        "data"
+    } else {
+        "unused"
+    }
+}
+
+fn ram_class(access: u8) -> &'static str {
+    if access & (OPCODE | OPERAND) != 0 {
+        "executed"
+    } else if access & (READ | WRITTEN) == READ | WRITTEN {
+        "read and written"
+    } else if access & WRITTEN != 0 {
+        "written only"
+    } else if access & READ != 0 {
+        "read only"
+    } else {
+        "unused"
+    }
+}
+
+// ranges of neighbouring addresses in the same class, end exclusive
+fn runs(map: &[u8], start: usize, end: usize, class: fn(u8) -> &'static str) -> Vec<(usize, usize, &'static str)> {
+    let mut runs: Vec<(usize, usize, &'static str)> = Vec::new();
+
+    for (address, &access) in map.iter().enumerate().take(end).skip(start) {
+        let class = class(access);
+        match runs.last_mut() {
+            Some(run) if run.2 == class => run.1 = address + 1,
+            _ => runs.push((address, address + 1, class)),
+        }
+    }
+    runs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assembler;
+    use crate::bench::Console;
+    use crate::cpu::State8080;
+    use crate::processor::Cpu;
+
+    #[test]
+    fn every_address_is_classed_by_how_it_was_used() {
+        let assembly = assembler::assemble("
+                lxi sp,3000h
+                lda table
+                sta 2000h
+                lda 2000h
+                push b
+                hlt
+            table:
+                db 5, 6
+        ").unwrap();
+        let mut cpu = State8080::new();
+        cpu.load_image(&assembly.image).unwrap();
+        cpu.set_coverage(Coverage::new());
+        let mut console = Console { output: String::new() };
+        while !cpu.halted() {
+            cpu.emulate(&mut console).unwrap();
+        }
+        let coverage = cpu.coverage().unwrap();
+
+        assert_eq!(coverage.map[..3], [OPCODE, OPERAND, OPERAND]);
+        assert_eq!(coverage.map[0xd..0x10], [OPCODE, READ, 0]);
+        assert_eq!(coverage.map[0x2000], READ | WRITTEN);
+        assert_eq!(coverage.map[0x2ffe..0x3000], [WRITTEN, WRITTEN]);
+
+        let mut out = Vec::new();
+        coverage.write_report(&mut out, 0x10).unwrap();
+        let report = String::from_utf8(out).unwrap();
+        assert!(report.contains("0000-000d code\n000e-000e data\n000f-000f unused\n"));
+        assert!(report.contains("2000-2000 read and written\n"));
+        assert!(report.contains("2ffe-2fff written only\n"));
+    }
+}
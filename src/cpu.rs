@@ -1,6 +1,17 @@
 use std::fmt;
 
+use crate::block_cache::{BlockCache, DecodedInstruction};
+use crate::bus::{BusMonitor, CycleKind, MachineCycle, STATUS_HLTA};
+use crate::call_stack::{Backtrace, CallStack, Frame, FrameKind};
+use crate::coverage::Coverage;
+use crate::error::CpuError;
+use crate::image::Image;
+use crate::lint::Lint;
+use crate::memory_map::{BankState, MemoryMap};
+use crate::processor::{Cpu, Interrupt, Registers};
+use crate::profiler::Profiler;
 use crate::space_invader::IOState;
+use crate::step::{RunUntil, StepRecord, StopReason};
 
 #[derive(Clone, Copy)]
 #[repr(C)]
@@ -134,7 +145,7 @@ impl Flags {
     }
 
     fn set_pariry(&mut self, value: u8) {
-        self.parity = value.count_ones() % 2 == 0;
+        self.parity = value.count_ones().is_multiple_of(2);
     }
 
     pub fn set_carry(&mut self, value: u16) {
@@ -156,7 +167,7 @@ impl Flags {
     }
 
     pub fn set_all(&mut self, value: u16, aux_value: u8) {
-        self.set_carry(value);
+        self.set_zero(value as u8);
         self.set_pariry(value as u8);
         self.set_sign(value as u8);
         self.set_carry(value);
@@ -164,8 +175,70 @@ impl Flags {
     }
 }
 
+// RAM unless another size is set, what the Space Invaders board has
 const MEMORY_SIZE: usize = 0x4000;
 
+// size in bytes of every instruction including the opcode
+const INSTRUCTION_LENGTHS: [u8; 256] = [
+    1, 3, 1, 1, 1, 1, 2, 1, 1, 1, 1, 1, 1, 1, 2, 1,
+    1, 3, 1, 1, 1, 1, 2, 1, 1, 1, 1, 1, 1, 1, 2, 1,
+    1, 3, 3, 1, 1, 1, 2, 1, 1, 1, 3, 1, 1, 1, 2, 1,
+    1, 3, 3, 1, 1, 1, 2, 1, 1, 1, 3, 1, 1, 1, 2, 1,
+    1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+    1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+    1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+    1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+    1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+    1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+    1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+    1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+    1, 1, 3, 3, 3, 1, 2, 1, 1, 1, 3, 3, 3, 3, 2, 1,
+    1, 1, 3, 2, 3, 1, 2, 1, 1, 1, 3, 2, 3, 3, 2, 1,
+    1, 1, 3, 1, 3, 1, 2, 1, 1, 1, 3, 1, 3, 3, 2, 1,
+    1, 1, 3, 1, 3, 1, 2, 1, 1, 1, 3, 1, 3, 3, 2, 1,
+];
+
+pub fn instruction_length(opcode: u8) -> u16 {
+    INSTRUCTION_LENGTHS[opcode as usize] as u16
+}
+
+// undocumented opcodes, not implemented by this core
+pub fn is_illegal(opcode: u8) -> bool {
+    matches!(opcode, 0x08 | 0x10 | 0x18 | 0x28 | 0x30 | 0x38 | 0xcb | 0xd9 | 0xdd | 0xed | 0xfd)
+}
+
+// T-states of the M1 cycle, instructions that work on the fetched opcode
+// alone or prepare the stack take an extra one
+fn fetch_t_states(opcode: u8) -> u8 {
+    match opcode {
+        // MOV r, r
+        0x40..=0x7f if opcode & 0x07 != 0x06 && opcode & 0xf8 != 0x70 => 5,
+        // INR r, DCR r
+        _ if opcode & 0xc6 == 0x04 && opcode & 0x38 != 0x30 => 5,
+        // INX, DCX
+        _ if opcode & 0xc7 == 0x03 => 5,
+        // Rcc, Ccc, PUSH, RST
+        _ if opcode & 0xc7 == 0xc0 || opcode & 0xc7 == 0xc4 || opcode & 0xc7 == 0xc7 => 5,
+        _ if opcode & 0xcf == 0xc5 => 5,
+        // CALL, PCHL, SPHL
+        0xcd | 0xdd | 0xed | 0xfd | 0xe9 | 0xf9 => 5,
+        _ => 4,
+    }
+}
+
+// what the next instruction depends on, the instruments are not part of it
+#[derive(Clone)]
+pub struct Snapshot {
+    registers: Registers,
+    memory: Vec<u8>,
+    banks: BankState,
+    interupts_enabled: bool,
+    interrupt_delay: bool,
+    pending_interrupt: Option<Interrupt>,
+    halted: bool,
+    frames: Vec<Frame>,
+}
+
 pub struct State8080 {
     a: u8,
     bc: RegisterPair,
@@ -173,9 +246,40 @@ pub struct State8080 {
     hl: RegisterPair,
     sp: u16,
     pc: u16,
-    memory: [u8; MEMORY_SIZE],
+    memory: Vec<u8>,
+    // devices and banks in front of the RAM
+    memory_map: MemoryMap,
     flags: Flags,
     interupts_enabled: bool,
+    // set by EI, interrupts are only accepted after the next instruction
+    interrupt_delay: bool,
+    // the INT line, held until the interrupt is acknowledged
+    pending_interrupt: Option<Interrupt>,
+    // instruction supplied by the interrupting device during INTA
+    bus_instruction: Option<[u8; 3]>,
+    // operand of an instruction run from the block cache
+    decoded_operand: Option<u16>,
+    // interrupt acknowledged by the last instruction
+    acknowledged_interrupt: Option<Interrupt>,
+    // first error of the current instruction, reported once it completes
+    fault: Option<CpuError>,
+    halted: bool,
+    // decode addresses modulo the memory size, like boards that leave the
+    // upper address lines unconnected
+    mirror_memory: bool,
+    // writes below this address are ignored
+    rom_size: usize,
+    // machine cycles of the last instruction
+    machine_cycles: Vec<MachineCycle>,
+    // off while blocks run unobserved, instructions then take their
+    // documented cycles
+    record_cycles: bool,
+    bus_monitor: Option<Box<dyn BusMonitor>>,
+    call_stack: CallStack,
+    profiler: Option<Profiler>,
+    coverage: Option<Coverage>,
+    lint: Option<Lint>,
+    block_cache: Option<BlockCache>,
 }
 
 impl fmt::Display for State8080 {
@@ -195,7 +299,7 @@ impl fmt::Display for State8080 {
        self.sp,
        self.flags,
        self.pc,
-       self.read_next_instruction_byte()) 
+       self.peek(self.pc.wrapping_add(1))) 
     }
 }
 
@@ -208,7 +312,8 @@ impl State8080 {
             hl: RegisterPair::new(),
             sp: 0,
             pc: 0,
-            memory: [0; MEMORY_SIZE],
+            memory: vec![0; MEMORY_SIZE],
+            memory_map: MemoryMap::new(),
             flags: Flags {
                 zero: false,
                 sign: false,
@@ -217,6 +322,23 @@ impl State8080 {
                 aux_carry: false,
             },
             interupts_enabled: false,
+            interrupt_delay: false,
+            pending_interrupt: None,
+            bus_instruction: None,
+            decoded_operand: None,
+            acknowledged_interrupt: None,
+            fault: None,
+            halted: false,
+            mirror_memory: false,
+            rom_size: 0,
+            machine_cycles: Vec::with_capacity(6),
+            record_cycles: true,
+            bus_monitor: None,
+            call_stack: CallStack::new(),
+            profiler: None,
+            coverage: None,
+            lint: None,
+            block_cache: None,
         }
     }
 
@@ -227,54 +349,419 @@ impl State8080 {
         cpu
     }
 
-    pub fn interrupt(&mut self, idn: u16) {
-        if self.interupts_enabled {
-            self.push(self.pc);
-            self.pc = 8 * idn;
-            self.interupts_enabled = false;
+    // a program over what is in memory, started at its entry when it has one
+    pub fn load_image(&mut self, image: &Image) -> Result<(), String> {
+        let chunks = image.chunks();
+        if let Some((start, bytes)) = chunks.iter().find(|(start, bytes)| *start as usize + bytes.len() > self.memory.len()) {
+            return Err(format!("{} bytes at {:04x} do not fit in {} KiB of memory", bytes.len(), start, self.memory.len() / 1024));
+        }
+
+        for (start, bytes) in &chunks {
+            self.load_rom(bytes, *start as usize);
         }
+        if let Some(entry) = image.entry {
+            self.pc = entry;
+        }
+        Ok(())
     }
 
+    // the RAM, not what devices and banks put in front of it
     pub fn memory(&self) -> &[u8] {
         &self.memory
     }
 
+    // cleared, a power of two up to 64 KiB; addresses above it are bus
+    // faults or mirrors
+    pub fn set_memory_size(&mut self, size: usize) -> Result<(), String> {
+        if !size.is_power_of_two() || size > 0x10000 {
+            return Err(format!("{} bytes of memory is not a power of two up to 64 KiB", size));
+        }
+        self.memory = vec![0; size];
+        if let Some(cache) = &mut self.block_cache {
+            cache.clear();
+        }
+        Ok(())
+    }
+
+    // e.g. to attach a device or a banked region
+    pub fn memory_map_mut(&mut self) -> &mut MemoryMap {
+        &mut self.memory_map
+    }
+
+    pub fn pc(&self) -> u16 {
+        self.pc
+    }
+
+    // the registers as a debugger sets them, the call stack is kept
+    pub fn set_registers(&mut self, registers: Registers) {
+        self.a = registers.a;
+        self.flags.set_with_psw(registers.flags);
+        *self.bc.msb_mut() = registers.b;
+        *self.bc.lsb_mut() = registers.c;
+        *self.de.msb_mut() = registers.d;
+        *self.de.lsb_mut() = registers.e;
+        *self.hl.msb_mut() = registers.h;
+        *self.hl.lsb_mut() = registers.l;
+        self.sp = registers.sp;
+        self.pc = registers.pc;
+    }
+
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            registers: Cpu::registers(self),
+            memory: self.memory.to_vec(),
+            banks: self.memory_map.bank_state(),
+            interupts_enabled: self.interupts_enabled,
+            interrupt_delay: self.interrupt_delay,
+            pending_interrupt: self.pending_interrupt,
+            halted: self.halted,
+            frames: self.call_stack.frames().to_vec(),
+        }
+    }
+
+    // puts the core back the way it was at `snapshot`
+    pub fn restore(&mut self, snapshot: &Snapshot) {
+        self.set_registers(snapshot.registers);
+        self.memory.copy_from_slice(&snapshot.memory);
+        self.memory_map.restore_banks(&snapshot.banks);
+        self.interupts_enabled = snapshot.interupts_enabled;
+        self.interrupt_delay = snapshot.interrupt_delay;
+        self.pending_interrupt = snapshot.pending_interrupt;
+        self.halted = snapshot.halted;
+        self.call_stack.set_frames(&snapshot.frames);
+        if let Some(cache) = &mut self.block_cache {
+            cache.clear();
+        }
+    }
+
+    // accesses above the memory are bus faults unless it is mirrored
+    pub fn set_memory_mirroring(&mut self, mirror: bool) {
+        self.mirror_memory = mirror;
+    }
+
+    // the first `size` bytes ignore writes, which still take their bus cycle
+    pub fn protect_rom(&mut self, size: usize) {
+        self.rom_size = size;
+    }
+
+    pub fn machine_cycles(&self) -> &[MachineCycle] {
+        &self.machine_cycles
+    }
+
+    pub fn set_bus_monitor(&mut self, monitor: Box<dyn BusMonitor>) {
+        self.bus_monitor = Some(monitor);
+    }
+
+    pub fn take_bus_monitor(&mut self) -> Option<Box<dyn BusMonitor>> {
+        self.bus_monitor.take()
+    }
+
+    pub fn call_stack(&self) -> &CallStack {
+        &self.call_stack
+    }
+
+    // e.g. to name the routines in backtraces
+    pub fn call_stack_mut(&mut self) -> &mut CallStack {
+        &mut self.call_stack
+    }
+
+    pub fn backtrace(&self) -> Backtrace {
+        self.call_stack.backtrace(self.pc)
+    }
+
+    // profiling is off until a profiler is set
+    pub fn set_profiler(&mut self, profiler: Profiler) {
+        self.profiler = Some(profiler);
+    }
+
+    pub fn profiler(&self) -> Option<&Profiler> {
+        self.profiler.as_ref()
+    }
+
+    pub fn take_profiler(&mut self) -> Option<Profiler> {
+        self.profiler.take()
+    }
+
+    // access maps are recorded while a coverage is set
+    pub fn set_coverage(&mut self, coverage: Coverage) {
+        self.coverage = Some(coverage);
+    }
+
+    pub fn coverage(&self) -> Option<&Coverage> {
+        self.coverage.as_ref()
+    }
+
+    pub fn take_coverage(&mut self) -> Option<Coverage> {
+        self.coverage.take()
+    }
+
+    // checks every instruction for suspicious accesses while set
+    pub fn set_lint(&mut self, lint: Lint) {
+        self.lint = Some(lint);
+    }
+
+    pub fn lint(&self) -> Option<&Lint> {
+        self.lint.as_ref()
+    }
+
+    pub fn take_lint(&mut self) -> Option<Lint> {
+        self.lint.take()
+    }
+
+    // run and run_for execute cached blocks while nothing observes the bus
+    pub fn set_block_cache(&mut self, cache: BlockCache) {
+        self.block_cache = Some(cache);
+    }
+
+    pub fn block_cache(&self) -> Option<&BlockCache> {
+        self.block_cache.as_ref()
+    }
+
     fn load_rom(&mut self, rom: &[u8], rom_start: usize) {
         self.memory[rom_start..rom_start + rom.len()].clone_from_slice(rom);
+        if let Some(cache) = &mut self.block_cache {
+            cache.clear();
+        }
     }
 
-    fn m(&self) -> u8 {
-        self.memory[self.hl.both() as usize]
+    fn bus_cycle(&mut self, kind: CycleKind, address: u16, data: u8, t_states: u8) {
+        if !self.record_cycles {
+            return;
+        }
+
+        let mut status = kind.status();
+        if kind == CycleKind::InterruptAck && self.halted {
+            status |= STATUS_HLTA;
+        }
+
+        let mut cycle = MachineCycle {
+            kind,
+            status,
+            address,
+            data,
+            t_states,
+            wait_states: 0,
+        };
+
+        if let Some(monitor) = self.bus_monitor.as_mut() {
+            if kind != CycleKind::Internal {
+                cycle.wait_states = monitor.wait_states(&cycle);
+                cycle.t_states += cycle.wait_states;
+            }
+            monitor.machine_cycle(&cycle);
+        }
+
+        self.machine_cycles.push(cycle);
     }
 
-    fn m_mut(&mut self) -> &mut u8 {
-        &mut self.memory[self.hl.both() as usize]
+    fn raise(&mut self, error: CpuError) {
+        if self.fault.is_none() {
+            self.fault = Some(error);
+        }
+    }
+
+    // reads memory without a bus cycle, the bus floats high outside of it
+    pub fn peek(&self, address: u16) -> u8 {
+        if let Some(value) = self.memory_map.peek(address) {
+            return value;
+        }
+        match self.decode_address(address) {
+            Some(index) => self.memory[index],
+            None => 0xff,
+        }
     }
 
-    fn read_byte(&self, address: u16) -> u8 {
-        self.memory[address as usize]
+    // writes memory without a bus cycle, the way a debugger patches it: ROM
+    // is written too and unmapped addresses are ignored
+    pub fn poke(&mut self, address: u16, value: u8) {
+        if self.memory_map.poke(address, value) {
+            return;
+        }
+        if let Some(index) = self.decode_address(address) {
+            self.store(index, value);
+        }
+    }
+
+    // the accesses of recompiled code, which runs outside of `emulate`:
+    // devices, banks, ROM and bus faults as for an instruction, no cycles
+    pub fn read_memory(&mut self, address: u16) -> Result<u8, CpuError> {
+        let value = self.load(address);
+        self.fault.take().map_or(Ok(value), Err)
+    }
+
+    pub fn write_memory(&mut self, address: u16, value: u8) -> Result<(), CpuError> {
+        self.record_cycles = false;
+        self.write_byte(address, value);
+        self.record_cycles = true;
+        self.fault.take().map_or(Ok(()), Err)
+    }
+
+    pub fn read_port(&mut self, state: &mut dyn IOState, port: u8) -> Result<u8, CpuError> {
+        self.record_cycles = false;
+        let value = self.input(state, port);
+        self.record_cycles = true;
+        self.fault.take().map_or(Ok(value), Err)
+    }
+
+    pub fn write_port(&mut self, state: &mut dyn IOState, port: u8, value: u8) -> Result<(), CpuError> {
+        self.record_cycles = false;
+        self.output(state, port, value);
+        self.record_cycles = true;
+        self.fault.take().map_or(Ok(()), Err)
+    }
+
+    fn decode_address(&self, address: u16) -> Option<usize> {
+        let address = address as usize;
+
+        if address < self.memory.len() {
+            Some(address)
+        } else if self.mirror_memory {
+            Some(address % self.memory.len())
+        } else {
+            None
+        }
+    }
+
+    fn check_address(&mut self, address: u16) -> bool {
+        let mapped = self.memory_map.covers(address) || self.decode_address(address).is_some();
+        if !mapped {
+            self.raise(CpuError::BusFault { address });
+        }
+        mapped
+    }
+
+    // a read with the side effects devices have
+    fn load(&mut self, address: u16) -> u8 {
+        self.check_address(address);
+        match self.memory_map.read(address) {
+            Some(value) => value,
+            None => self.peek(address),
+        }
+    }
+
+    fn fetch(&mut self) -> u8 {
+        let opcode = self.load(self.pc);
+        self.bus_cycle(CycleKind::Fetch, self.pc, opcode, fetch_t_states(opcode));
+        opcode
+    }
+
+    fn m(&mut self) -> u8 {
+        self.read_byte(self.hl.both())
+    }
+
+    fn set_m(&mut self, value: u8) {
+        self.write_byte(self.hl.both(), value);
+    }
+
+    fn read_byte(&mut self, address: u16) -> u8 {
+        let value = self.load(address);
+        self.bus_cycle(CycleKind::MemoryRead, address, value, 3);
+        value
     }  
 
-    fn read_bytes(&self, address: u16) -> u16 {
-        ((self.read_byte(address + 1) as u16) << 8) | self.read_byte(address) as u16
+    fn read_bytes(&mut self, address: u16) -> u16 {
+        let lsb = self.read_byte(address);
+        ((self.read_byte(address.wrapping_add(1)) as u16) << 8) | lsb as u16
     }
 
-    fn read_next_instruction_byte(&self) -> u8 {
-        self.read_byte(self.pc + 1)
+    // during INTA the operands come from the bus as well, the PC stays put
+    fn read_next_instruction_byte(&mut self) -> u8 {
+        if let Some(operand) = self.decoded_operand {
+            return operand as u8;
+        }
+
+        match self.bus_instruction {
+            Some(bytes) => {
+                let pc = self.pc.wrapping_add(instruction_length(bytes[0]));
+                self.bus_cycle(CycleKind::MemoryRead, pc, bytes[1], 3);
+                bytes[1]
+            },
+            None => self.read_byte(self.pc.wrapping_add(1)),
+        }
     }
 
-    fn read_next_instruction_bytes(&self) -> u16 {
-        self.read_bytes(self.pc + 1)
+    fn read_next_instruction_bytes(&mut self) -> u16 {
+        if let Some(operand) = self.decoded_operand {
+            return operand;
+        }
+
+        match self.bus_instruction {
+            Some(bytes) => {
+                let pc = self.pc.wrapping_add(instruction_length(bytes[0]));
+                self.bus_cycle(CycleKind::MemoryRead, pc, bytes[1], 3);
+                self.bus_cycle(CycleKind::MemoryRead, pc, bytes[2], 3);
+                (bytes[2] as u16) << 8 | bytes[1] as u16
+            },
+            None => self.read_bytes(self.pc.wrapping_add(1)),
+        }
     }
 
     fn write_byte(&mut self, address: u16, value: u8) {
-        let mut stored_value = self.memory[address as usize];
-        stored_value = value;
+        self.put(address, value);
+        self.bus_cycle(CycleKind::MemoryWrite, address, value, 3);
+    }
+
+    // a write through the devices and banks, the counterpart of `load`
+    fn put(&mut self, address: u16, value: u8) {
+        if self.memory_map.write(address, value) {
+            return;
+        }
+        match self.decode_address(address) {
+            Some(index) if index >= self.rom_size => self.store(index, value),
+            Some(_) => (),
+            None => self.raise(CpuError::BusFault { address }),
+        }
+    }
+
+    fn store(&mut self, index: usize, value: u8) {
+        self.memory[index] = value;
+        if let Some(cache) = &mut self.block_cache {
+            cache.invalidate(index as u16);
+        }
     }
 
     fn write_bytes(&mut self, address: u16, value: u16) {
         self.write_byte(address, value as u8);
-        self.write_byte(address, (value >> 8) as u8);
+        self.write_byte(address.wrapping_add(1), (value >> 8) as u8);
+    }
+
+    fn input(&mut self, state: &mut dyn IOState, port: u8) -> u8 {
+        let value = state.input(port).unwrap_or_else(|error| {
+            self.raise(error.into());
+            0xff
+        });
+        self.bus_cycle(CycleKind::InputRead, (port as u16) << 8 | port as u16, value, 3);
+        value
+    }
+
+    // a port that switches banks goes no further
+    fn output(&mut self, state: &mut dyn IOState, port: u8, value: u8) {
+        if !self.memory_map.output(port, value) {
+            if let Err(error) = state.output(port, value) {
+                self.raise(error.into());
+            }
+        }
+        self.bus_cycle(CycleKind::OutputWrite, (port as u16) << 8 | port as u16, value, 3);
+    }
+
+    // pads the instruction with internal cycles up to its documented timing
+    // and returns the T-states of all its machine cycles
+    fn complete_instruction(&mut self, cycles: u64) -> u64 {
+        if !self.record_cycles {
+            return cycles;
+        }
+
+        let bus_t_states: u64 = self.machine_cycles.iter()
+            .map(|cycle| (cycle.t_states - cycle.wait_states) as u64)
+            .sum();
+
+        if cycles > bus_t_states {
+            self.bus_cycle(CycleKind::Internal, self.pc, 0, (cycles - bus_t_states) as u8);
+        }
+
+        self.machine_cycles.iter()
+            .map(|cycle| cycle.t_states as u64)
+            .sum()
     }
 
     // single register instructions
@@ -282,12 +769,14 @@ impl State8080 {
     fn inr(&mut self, operand: u8) -> u8 {
         let result = operand.wrapping_add(1);
         self.flags.set_all_but_carry(result);
+        self.flags.aux_carry = result & 0xf == 0;
         result 
     }
 
     fn dec(&mut self, operand: u8) -> u8 {
         let result = operand.wrapping_sub(1);
         self.flags.set_all_but_carry(result);
+        self.flags.aux_carry = result & 0xf != 0xf;
         result
     }
 
@@ -377,20 +866,40 @@ impl State8080 {
     // register pair instructions
 
     fn push(&mut self, operand: u16) {
-        self.sp -= 2;
-        self.write_bytes(self.sp, operand);
+        if self.sp < 2 {
+            self.raise(CpuError::StackWrap { sp: self.sp });
+        }
+        self.sp = self.sp.wrapping_sub(2);
+        self.stack_write(self.sp.wrapping_add(1), (operand >> 8) as u8);
+        self.stack_write(self.sp, operand as u8);
     }
 
     fn pop(&mut self) -> u16 {
-        self.sp += 2;
-        self.read_bytes(self.sp - 2)
+        if self.sp > 0xfffd {
+            self.raise(CpuError::StackWrap { sp: self.sp });
+        }
+        let lsb = self.stack_read(self.sp);
+        let msb = self.stack_read(self.sp.wrapping_add(1));
+        self.sp = self.sp.wrapping_add(2);
+        (msb as u16) << 8 | lsb as u16
+    }
+
+    fn stack_read(&mut self, address: u16) -> u8 {
+        let value = self.load(address);
+        self.bus_cycle(CycleKind::StackRead, address, value, 3);
+        value
+    }
+
+    fn stack_write(&mut self, address: u16, value: u8) {
+        self.put(address, value);
+        self.bus_cycle(CycleKind::StackWrite, address, value, 3);
     }
 
     fn dad(&mut self, operand: u16) {
         let result = (self.hl.both() as u32)
             .wrapping_add(operand as u32);
 
-        self.flags.set_carry(result as u16);
+        self.flags.carry = result > 0xffff;
         *self.hl.both_mut() = result as u16;
     }
 
@@ -399,6 +908,13 @@ impl State8080 {
 
         *self.hl.both_mut() = self.pop();
         self.push(tmp);
+        self.call_stack.exchange(self.pc, self.sp, tmp);
+    }
+
+    // POP of a register pair, which may take a return address off the stack
+    fn pop_register(&mut self) -> u16 {
+        self.call_stack.pop(self.pc, self.sp);
+        self.pop()
     }
 
     // immediate iinstructions
@@ -432,18 +948,239 @@ impl State8080 {
     }
 
     fn call(&mut self, adr: u16) {
-        self.push(self.pc + 3);
+        let return_address = self.pc.wrapping_add(3);
+        self.push(return_address);
+        self.enter_frame(FrameKind::Call, adr, return_address);
         self.pc = adr;
     }
 
     fn ret(&mut self) {
+        let (address, sp) = (self.pc, self.sp);
         self.pc = self.pop();
+        self.call_stack.leave(address, sp, self.pc);
+    }
+
+    fn rst(&mut self, n: u16) {
+        let return_address = self.pc.wrapping_add(1);
+        self.push(return_address);
+        self.enter_frame(FrameKind::Rst, 8 * n, return_address);
+        self.pc = 8 * n;
+    }
+
+    // calls supplied during INTA are entered from the interrupted PC
+    fn enter_frame(&mut self, kind: FrameKind, target: u16, return_address: u16) {
+        let (kind, call_site) = match self.bus_instruction {
+            Some(_) => (FrameKind::Interrupt, return_address),
+            None => (kind, self.pc),
+        };
+        self.call_stack.enter(kind, call_site, target, return_address, self.sp);
     }
     
 
-    pub fn emulate(&mut self, state: &mut dyn IOState) -> u64 {
-        let opcode = self.read_byte(self.pc);
+    // what the last instruction emulated did, bus accesses included
+    pub fn last_step(&self) -> StepRecord {
+        StepRecord::from_machine_cycles(&self.machine_cycles, self.acknowledged_interrupt, self.pc)
+    }
+
+    // steps until the condition is met, the core halts or an illegal opcode
+    // is reached, returning why it stopped and the cycles and instructions
+    // spent
+    pub fn run(&mut self, mut until: RunUntil, state: &mut dyn IOState) -> Result<(StopReason, u64, u64), CpuError> {
+        let mut checks = 0;
+        let mut spent = 0;
+        let mut reason = StopReason::BudgetExhausted;
+
+        // the condition is checked after every instruction, so before every
+        // one but the first
+        let result = self.drive(state, &mut |cpu, cycles| {
+            spent = cycles;
+            checks += 1;
+            if checks == 1 {
+                return false;
+            }
+            if cpu.halted {
+                reason = StopReason::Halted;
+                return true;
+            }
+            let stop = match until {
+                RunUntil::Cycles(budget) => cycles >= budget,
+                RunUntil::Pc(pc) => cpu.pc == pc,
+                RunUntil::Predicate(ref mut predicate) => predicate(cpu),
+            };
+            if stop {
+                if let RunUntil::Pc(_) | RunUntil::Predicate(_) = until {
+                    reason = StopReason::Breakpoint(cpu.pc);
+                }
+            }
+            stop
+        });
+
+        match result {
+            Ok((cycles, instructions)) => Ok((reason, cycles, instructions)),
+            // asked before the illegal opcode too
+            Err(CpuError::IllegalOpcode { address, opcode }) => Ok((StopReason::IllegalOpcode { address, opcode }, spent, checks - 1)),
+            Err(error) => Err(error),
+        }
+    }
+
+    // runs at least `budget` cycles and returns the cycles and instructions
+    // executed, through cached blocks when they are enabled
+    pub fn run_for(&mut self, budget: u64, state: &mut dyn IOState) -> Result<(u64, u64), CpuError> {
+        self.drive(state, &mut |_, cycles| cycles >= budget)
+    }
+
+    // executes instructions until `stop`, asked with the cycles spent so far
+    // before each of them, holds
+    fn drive(&mut self, state: &mut dyn IOState, stop: &mut dyn FnMut(&Self, u64) -> bool) -> Result<(u64, u64), CpuError> {
+        let mut cycles = 0;
+        let mut instructions = 0;
+
+        while !stop(self, cycles) {
+            let cacheable = self.unobserved() && !self.interrupt_due() && !self.halted;
+            let block = match self.block_cache.as_mut() {
+                Some(cache) if cacheable => {
+                    cache.take_invalidated();
+                    cache.block(self.pc, &self.memory)
+                },
+                _ => None,
+            };
+
+            let block = match block {
+                Some(block) => block,
+                None => {
+                    cycles += self.emulate(state)?;
+                    instructions += 1;
+                    continue;
+                },
+            };
+
+            // the interpreter takes over between instructions wherever it
+            // would act differently: an interrupt to acknowledge, a halt, a
+            // write to the block or an instruction leaving it
+            for (index, instruction) in block.instructions.iter().enumerate() {
+                if self.pc != instruction.address || self.interrupt_due() || self.halted {
+                    break;
+                }
+                if index > 0 && stop(self, cycles) {
+                    return Ok((cycles, instructions));
+                }
 
+                cycles += self.execute_decoded(instruction, state)?;
+                instructions += 1;
+
+                if self.block_cache.as_mut().is_none_or(BlockCache::take_invalidated) {
+                    break;
+                }
+            }
+        }
+
+        Ok((cycles, instructions))
+    }
+
+    // observers need the machine cycles of every instruction, and blocks
+    // are decoded from the RAM alone
+    fn unobserved(&self) -> bool {
+        self.memory_map.is_empty()
+            && self.bus_monitor.is_none()
+            && self.profiler.is_none()
+            && self.coverage.is_none()
+            && self.lint.is_none()
+    }
+
+    // an interrupt will be acknowledged instead of the next instruction
+    pub fn interrupt_due(&self) -> bool {
+        self.interupts_enabled && !self.interrupt_delay && self.pending_interrupt.is_some()
+    }
+
+    // what emulate does for the instruction at the PC when there is no
+    // interrupt to acknowledge, skipping the fetch and the operand reads
+    pub fn execute_decoded(&mut self, instruction: &DecodedInstruction, state: &mut dyn IOState) -> Result<u64, CpuError> {
+        self.machine_cycles.clear();
+        self.acknowledged_interrupt = None;
+        self.interrupt_delay = false;
+
+        self.record_cycles = false;
+        self.decoded_operand = Some(instruction.operand);
+        let cycles = self.execute(instruction.opcode, state);
+        self.decoded_operand = None;
+        self.record_cycles = true;
+
+        match self.fault.take() {
+            Some(error) => Err(error),
+            None => Ok(cycles),
+        }
+    }
+
+    // the instruction runs to completion, errors raised on the way are
+    // returned afterwards with the core state as the instruction left it
+    pub fn emulate(&mut self, state: &mut dyn IOState) -> Result<u64, CpuError> {
+        let address = self.pc;
+        let cycles = self.emulate_instruction(state);
+
+        if let Some(profiler) = &mut self.profiler {
+            profiler.record(address, cycles, self.call_stack.frames());
+        }
+        if let Some(coverage) = &mut self.coverage {
+            coverage.record(&self.machine_cycles);
+        }
+        if let Some(lint) = &mut self.lint {
+            lint.record(address, &self.machine_cycles, self.call_stack.frames());
+        }
+
+        match self.fault.take() {
+            Some(error) => Err(error),
+            None => Ok(cycles),
+        }
+    }
+
+    fn emulate_instruction(&mut self, state: &mut dyn IOState) -> u64 {
+        self.machine_cycles.clear();
+        self.acknowledged_interrupt = None;
+
+        if self.interupts_enabled && !self.interrupt_delay {
+            if let Some(interrupt) = self.pending_interrupt.take() {
+                return self.acknowledge_interrupt(interrupt, state);
+            }
+        }
+        self.interrupt_delay = false;
+
+        if self.halted {
+            return 4;
+        }
+
+        let opcode = self.fetch();
+        self.execute(opcode, state)
+    }
+
+    // INTA: the instruction is read from the data bus instead of memory and
+    // takes its usual cycles, 11 for an RST and 17 for a CALL
+    fn acknowledge_interrupt(&mut self, interrupt: Interrupt, state: &mut dyn IOState) -> u64 {
+        let bytes = match interrupt {
+            Interrupt::Rst(_) => [interrupt.opcode(), 0, 0],
+            Interrupt::Instruction(bytes) => bytes,
+        };
+
+        self.bus_cycle(CycleKind::InterruptAck, self.pc, bytes[0], fetch_t_states(bytes[0]));
+        self.acknowledged_interrupt = Some(interrupt);
+        self.interupts_enabled = false;
+        self.halted = false;
+
+        if is_illegal(bytes[0]) {
+            self.raise(CpuError::IllegalOpcode { address: self.pc, opcode: bytes[0] });
+            return self.complete_instruction(0);
+        }
+
+        // the PC is not incremented during INTA, rewind it so the instruction
+        // advancing it or pushing its return address leaves it unchanged
+        self.pc = self.pc.wrapping_sub(instruction_length(bytes[0]));
+        self.bus_instruction = Some(bytes);
+        let cycles = self.execute(bytes[0], state);
+        self.bus_instruction = None;
+
+        cycles
+    }
+
+    fn execute(&mut self, opcode: u8, state: &mut dyn IOState) -> u64 {
         let (op_size, cycles) = match opcode {
             // NOP
             0x00 | 0x20 => (1, 4),
@@ -480,7 +1217,7 @@ impl State8080 {
             // RLC
             0x07 => {
                 let prev_bit7: u8 = self.a & (1 << 7);
-                self.a <<= 1;
+                self.a = self.a.rotate_left(1);
                 self.flags.carry = prev_bit7 != 0;
                 (1, 4)
             },
@@ -501,12 +1238,12 @@ impl State8080 {
             },
             // INR C
             0x0c => {
-                *self.bc.msb_mut() = self.inr(self.bc.msb());
+                *self.bc.lsb_mut() = self.inr(self.bc.lsb());
                 (1 ,5)
             },
             // DCR C
             0x0d => {
-                *self.bc.msb_mut() = self.dec(self.bc.msb());
+                *self.bc.lsb_mut() = self.dec(self.bc.lsb());
                 (1, 5)
             },
             // MVI C,D8
@@ -594,9 +1331,9 @@ impl State8080 {
             },
             // RAR
             0x1f => {
-                let bit0: u8 = self.a * 1;
+                let bit0: u8 = self.a & 1;
                 self.a >>= 1;
-                self.a |= bit0 << 7;
+                self.a |= (self.flags.carry as u8) << 7;
                 self.flags.carry = bit0 != 0;
                 (1, 4)
             },
@@ -607,7 +1344,8 @@ impl State8080 {
             },
             // SHLD adr
             0x22 => {
-                self.write_bytes(self.read_next_instruction_bytes(), self.hl.both());
+                let adr = self.read_next_instruction_bytes();
+                self.write_bytes(adr, self.hl.both());
                 (3, 16)
             },
             // INX H
@@ -638,12 +1376,13 @@ impl State8080 {
             // DAD H
             0x29 => {
                 self.dad(self.hl.both());
-                (3, 10)
+                (1, 10)
             },
             // LHLD adr
             0x2a => {
-                *self.hl.both_mut() = self.read_bytes(self.read_next_instruction_bytes());
-                (3, 10)
+                let adr = self.read_next_instruction_bytes();
+                *self.hl.both_mut() = self.read_bytes(adr);
+                (3, 16)
             },
             // DCX H
             0x2b => {
@@ -672,11 +1411,13 @@ impl State8080 {
             // LXI SP, D16
             0x31 => {
                 self.sp = self.read_next_instruction_bytes();
+                self.call_stack.set_stack_pointer(self.pc, self.sp);
                 (3, 10)
             },
             // STA adr
             0x32 => {
-                self.write_byte(self.read_next_instruction_bytes(), self.a);
+                let adr = self.read_next_instruction_bytes();
+                self.write_byte(adr, self.a);
                 (3, 13)
             },
             // INX SP
@@ -686,17 +1427,22 @@ impl State8080 {
             },
             // INR M
             0x34 => {
-                *self.m_mut() = self.inr(self.m());
+                let value = self.m();
+                let result = self.inr(value);
+                self.set_m(result);
                 (1, 10)
             }
             // DCR M
             0x35 => {
-                *self.m_mut() = self.dec(self.m());
+                let value = self.m();
+                let result = self.dec(value);
+                self.set_m(result);
                 (1, 10)
             },
             // MVI M, D8
             0x36 => {
-                *self.m_mut() = self.read_next_instruction_byte();
+                let value = self.read_next_instruction_byte();
+                self.set_m(value);
                 (2, 10)
             },
             // STC
@@ -707,12 +1453,13 @@ impl State8080 {
             // DAD SP
             0x39 => {
                 self.dad(self.sp);
-                (3, 10)
+                (1, 10)
 
             },
             // LDA adr
             0x3a => {
-                self.a = self.read_byte(self.read_next_instruction_bytes());
+                let adr = self.read_next_instruction_bytes();
+                self.a = self.read_byte(adr);
                 (3, 13)
             },
             0x3b => {
@@ -981,42 +1728,43 @@ impl State8080 {
             },
             // MOV M. B
             0x70 => {
-                *self.m_mut() = self.bc.msb();
+                self.set_m(self.bc.msb());
                 (1, 7)
             },
             // MOV M. C
             0x71 => {
-                *self.m_mut() = self.bc.lsb();
+                self.set_m(self.bc.lsb());
                 (1, 7)
             },
             // MOV M. D
             0x72 => {
-                *self.m_mut() = self.de.msb();
+                self.set_m(self.de.msb());
                 (1, 7)
             },
             // MOV M. E
             0x73 => {
-                *self.m_mut() = self.de.lsb();
+                self.set_m(self.de.lsb());
                 (1, 7)
             },
             // MOV M. H
             0x74 => {
-                *self.m_mut() = self.hl.msb();
-                (1, 5)
+                self.set_m(self.hl.msb());
+                (1, 7)
             },
             // MOV M. L
             0x75 => {
-                *self.m_mut() = self.hl.lsb();
+                self.set_m(self.hl.lsb());
                 (1, 7)
             },
             // HLT
             0x76 => {
-                println!("going into hlt");
-                std::process::exit(0);
+                self.bus_cycle(CycleKind::HaltAck, self.pc.wrapping_add(1), 0, 3);
+                self.halted = true;
+                (1, 7)
             },
             // MOV M. A
             0x77 => {
-                *self.m_mut() = self.a;
+                self.set_m(self.a);
                 (1, 7)
             },
             // MOV A. B
@@ -1091,8 +1839,9 @@ impl State8080 {
             },
             // ADD M
             0x86 => {
-                self.add(self.m());
-                (1, 4)
+                let value = self.m();
+                self.add(value);
+                (1, 7)
             },
             // ADD A
             0x87 => {
@@ -1116,7 +1865,7 @@ impl State8080 {
             },
             // ADC E
             0x8b => {
-                self.adc(self.bc.lsb());
+                self.adc(self.de.lsb());
                 (1, 4)
             },
             // ADC H
@@ -1131,8 +1880,9 @@ impl State8080 {
             },
             // ADC M
             0x8e => {
-                self.adc(self.m());
-                (1, 4)
+                let value = self.m();
+                self.adc(value);
+                (1, 7)
             },
             // ADC A
             0x8f => {
@@ -1171,8 +1921,9 @@ impl State8080 {
             },
             // SUB M
             0x96 => {
-                self.sub(self.m());
-                (1, 4)
+                let value = self.m();
+                self.sub(value);
+                (1, 7)
             },
             // SUB A
             0x97 => {
@@ -1211,8 +1962,9 @@ impl State8080 {
             },
             // SBB M
             0x9e => {
-                self.sbb(self.m());
-                (1, 4)
+                let value = self.m();
+                self.sbb(value);
+                (1, 7)
             },
             // SBB A
             0x9f => {
@@ -1251,8 +2003,9 @@ impl State8080 {
             },
             // ANA M 
             0xa6 => {
-                self.and(self.m());
-                (1, 4)
+                let value = self.m();
+                self.and(value);
+                (1, 7)
             },
             // ANA A 
             0xa7 => {
@@ -1291,8 +2044,9 @@ impl State8080 {
             },
             // XRA M
             0xae => {
-                self.xor(self.m());
-                (1, 4)
+                let value = self.m();
+                self.xor(value);
+                (1, 7)
             },
             // XRA A
             0xaf => {
@@ -1331,8 +2085,9 @@ impl State8080 {
             },
             // ORA M
             0xb6 => {
-                self.or(self.m());
-                (1, 4)
+                let value = self.m();
+                self.or(value);
+                (1, 7)
             },
             // ORA A
             0xb7 => {
@@ -1371,7 +2126,8 @@ impl State8080 {
             },
             // CMP M
             0xbe => {
-                self.cmp(self.m());
+                let value = self.m();
+                self.cmp(value);
                 (1, 7)
             },
             // CMP A
@@ -1391,29 +2147,32 @@ impl State8080 {
             },
             // POP B
             0xc1 => {
-                *self.bc.both_mut() = self.pop();
+                *self.bc.both_mut() = self.pop_register();
                 (1, 10)
             },
             // JNZ adr
             0xc2 => {
+                let adr = self.read_next_instruction_bytes();
                 if self.flags.zero {
                     (3, 10)
                 } else {
-                    self.jmp(self.read_next_instruction_bytes());
+                    self.jmp(adr);
                     (0, 10)
                 }
             },
             // JMP adr
             0xc3 => {
-                self.jmp(self.read_next_instruction_bytes());
+                let adr = self.read_next_instruction_bytes();
+                self.jmp(adr);
                 (0, 10)
             },
             // CNZ adr
             0xc4 => {
+                let adr = self.read_next_instruction_bytes();
                 if self.flags.zero {
                     (3, 11)
                 } else {
-                    self.call(self.read_next_instruction_bytes());
+                    self.call(adr);
                     (0, 17)
                 } 
             },
@@ -1424,9 +2183,15 @@ impl State8080 {
             }
             // ADI D8,
             0xc6 => {
-                self.add(self.read_next_instruction_byte());
+                let value = self.read_next_instruction_byte();
+                self.add(value);
                 (2, 7)
             },
+            // RST 0
+            0xc7 => {
+                self.rst(0);
+                (0, 11)
+            },
             // RZ
             0xc8 => {
                 if self.flags.zero {
@@ -1443,8 +2208,9 @@ impl State8080 {
             },
             // JZ adr
             0xca => {
+                let adr = self.read_next_instruction_bytes();
                 if self.flags.zero {
-                    self.jmp(self.read_next_instruction_bytes());
+                    self.jmp(adr);
                     (0, 10)
                 } else {
                     (3, 10)
@@ -1452,8 +2218,9 @@ impl State8080 {
             },
             // CZ adr
             0xcc => {
+                let adr = self.read_next_instruction_bytes();
                 if self.flags.zero {
-                    self.call(self.read_next_instruction_bytes());
+                    self.call(adr);
                     (0, 17)
                 } else {
                     (3, 11)
@@ -1461,17 +2228,24 @@ impl State8080 {
             },
             // CALL adr
             0xcd => {
-                self.call(self.read_next_instruction_bytes());
+                let adr = self.read_next_instruction_bytes();
+                self.call(adr);
                 (0, 17)
             },
             // ACI D8
             0xce => {
-                self.adc(self.read_next_instruction_byte());
+                let value = self.read_next_instruction_byte();
+                self.adc(value);
                 (2, 7)
             },
+            // RST 1
+            0xcf => {
+                self.rst(1);
+                (0, 11)
+            },
             // RNC
             0xd0 => {
-                if self.flags.zero {
+                if self.flags.carry {
                     (1, 5)
                 } else {
                     self.ret();
@@ -1480,28 +2254,32 @@ impl State8080 {
             },
             // POP D
             0xd1 => {
-                *self.de.both_mut() = self.pop();
+                *self.de.both_mut() = self.pop_register();
                 (1, 10)
             },
             // JNC adr
             0xd2 => {
+                let adr = self.read_next_instruction_bytes();
                 if self.flags.carry {
                     (3, 10)
                 } else {
-                    self.jmp(self.read_next_instruction_bytes());
-                    (0, 17)
+                    self.jmp(adr);
+                    (0, 10)
                 }
             },
             // OUT D8
             0xd3 => {
-                unimplemented!("0xd3; OUT d8 not set");
+                let port = self.read_next_instruction_byte();
+                self.output(state, port, self.a);
+                (2, 10)
             },
             // CNC adr
             0xd4 => {
+                let adr = self.read_next_instruction_bytes();
                 if self.flags.carry {
                     (3, 11)
                 } else {
-                    self.call(self.read_next_instruction_bytes());
+                    self.call(adr);
                     (0, 17)
                 }
             },
@@ -1512,9 +2290,15 @@ impl State8080 {
             },
             // SUI D8
             0xd6 => {
-                self.sub(self.read_next_instruction_byte());
+                let value = self.read_next_instruction_byte();
+                self.sub(value);
                 (2, 7)
             },
+            // RST 2
+            0xd7 => {
+                self.rst(2);
+                (0, 11)
+            },
             // RC
             0xd8 => {
                 if self.flags.carry {
@@ -1526,8 +2310,9 @@ impl State8080 {
             },
             // JC adr
             0xda => {
+                let adr = self.read_next_instruction_bytes();
                 if self.flags.carry {
-                    self.jmp(self.read_next_instruction_bytes());
+                    self.jmp(adr);
                     (0, 10)
                 } else {
                     (3, 10)
@@ -1535,44 +2320,71 @@ impl State8080 {
             },
             // IN D8
             0xdb => {
-                unimplemented!("0xdb; IN D8 not set");
+                let port = self.read_next_instruction_byte();
+                self.a = self.input(state, port);
+                (2, 10)
             },
             // CC adr
             0xdc => {
+                let adr = self.read_next_instruction_bytes();
                 if self.flags.carry {
-                    self.call(self.read_next_instruction_bytes());
+                    self.call(adr);
                     (0, 17)
                 } else {
-                    (1, 5)
+                    (3, 11)
                 }
             },
 
               // SBI D8
             0xde => {
-                self.sbb(self.read_next_instruction_byte());
+                let value = self.read_next_instruction_byte();
+                self.sbb(value);
                 (2, 7)
             }
+            // RST 3
+            0xdf => {
+                self.rst(3);
+                (0, 11)
+            }
+            // RPO
+            0xe0 => {
+                if self.flags.parity {
+                    (1, 5)
+                } else {
+                    self.ret();
+                    (0, 11)
+                }
+            }
             // POP H
             0xe1 => {
-                *self.hl.both_mut() = self.pop();
+                *self.hl.both_mut() = self.pop_register();
                 (1, 10)
             }
             // JPO adr
             0xe2 => {
+                let adr = self.read_next_instruction_bytes();
                 if self.flags.parity {
                     (3, 10)
                 } else {
-                    self.jmp(self.read_next_instruction_bytes());
+                    self.jmp(adr);
                     (0, 10)
                 }
             }
             // XTHL
             0xe3 => {
-                let tmp = self.hl.both();
-                *self.hl.both_mut() = self.pop();
-                self.push(tmp);
+                self.xthl();
                 (1, 18)
             }
+            // CPO adr
+            0xe4 => {
+                let adr = self.read_next_instruction_bytes();
+                if self.flags.parity {
+                    (3, 11)
+                } else {
+                    self.call(adr);
+                    (0, 17)
+                }
+            }
             // PUSH H
             0xe5 => {
                 self.push(self.hl.both());
@@ -1583,14 +2395,29 @@ impl State8080 {
                 self.ani();
                 (2, 7)
             }
+            // RST 4
+            0xe7 => {
+                self.rst(4);
+                (0, 11)
+            }
+            // RPE
+            0xe8 => {
+                if self.flags.parity {
+                    self.ret();
+                    (0, 11)
+                } else {
+                    (1, 5)
+                }
+            }
             0xe9 => {
                 self.jmp(self.hl.both());
                 (0, 5)
             }
             // JPE adr
             0xea => {
+                let adr = self.read_next_instruction_bytes();
                 if self.flags.parity {
-                    self.jmp(self.read_next_instruction_bytes());
+                    self.jmp(adr);
                     (0, 10)
                 } else {
                     (3, 10)
@@ -1601,21 +2428,52 @@ impl State8080 {
                 let tmp = self.de.both();
                 *self.de.both_mut() = self.hl.both();
                 *self.hl.both_mut() = tmp;
-                (1, 5)
+                (1, 4)
+            }
+            // CPE adr
+            0xec => {
+                let adr = self.read_next_instruction_bytes();
+                if self.flags.parity {
+                    self.call(adr);
+                    (0, 17)
+                } else {
+                    (3, 11)
+                }
+            }
+            // XRI D8
+            0xee => {
+                let value = self.read_next_instruction_byte();
+                self.xor(value);
+                (2, 7)
+            }
+            // RST 5
+            0xef => {
+                self.rst(5);
+                (0, 11)
+            }
+            // RP
+            0xf0 => {
+                if self.flags.sign {
+                    (1, 5)
+                } else {
+                    self.ret();
+                    (0, 11)
+                }
             }
             // POP AF
             0xf1 => {
-                let pop = self.pop();
+                let pop = self.pop_register();
                 self.flags.set_with_psw(pop as u8);
                 self.a = (pop >> 8) as u8;
                 (1, 10)
             }
             // JP adr
             0xf2 => {
+                let adr = self.read_next_instruction_bytes();
                 if self.flags.sign {
                     (3, 10)
                 } else {
-                    self.jmp(self.read_next_instruction_bytes());
+                    self.jmp(adr);
                     (0, 10)
                 }
             }
@@ -1624,6 +2482,16 @@ impl State8080 {
                 self.interupts_enabled = false;
                 (1, 4)
             }
+            // CP adr
+            0xf4 => {
+                let adr = self.read_next_instruction_bytes();
+                if self.flags.sign {
+                    (3, 11)
+                } else {
+                    self.call(adr);
+                    (0, 17)
+                }
+            }
             // PUSH AF
             0xf5 => {
                 let af = (self.a as u16) << 8 | self.flags.get_psw() as u16;
@@ -1632,13 +2500,35 @@ impl State8080 {
             }
             // ORI d8
             0xf6 => {
-                self.or(self.read_next_instruction_byte());
+                let value = self.read_next_instruction_byte();
+                self.or(value);
                 (2, 7)
             }
+            // RST 6
+            0xf7 => {
+                self.rst(6);
+                (0, 11)
+            }
+            // RM
+            0xf8 => {
+                if self.flags.sign {
+                    self.ret();
+                    (0, 11)
+                } else {
+                    (1, 5)
+                }
+            }
+            // SPHL
+            0xf9 => {
+                self.sp = self.hl.both();
+                self.call_stack.set_stack_pointer(self.pc, self.sp);
+                (1, 5)
+            }
             // JM adr
             0xfa => {
+                let adr = self.read_next_instruction_bytes();
                 if self.flags.sign {
-                    self.jmp(self.read_next_instruction_bytes());
+                    self.jmp(adr);
                     (0, 10)
                 } else {
                     (3, 10)
@@ -1647,19 +2537,96 @@ impl State8080 {
             // EI
             0xfb => {
                 self.interupts_enabled = true;
+                self.interrupt_delay = true;
                 (1, 4)
             }
+            // CM adr
+            0xfc => {
+                let adr = self.read_next_instruction_bytes();
+                if self.flags.sign {
+                    self.call(adr);
+                    (0, 17)
+                } else {
+                    (3, 11)
+                }
+            }
             // CPI D8
             0xfe => {
-                self.cmp(self.read_next_instruction_byte());
+                let value = self.read_next_instruction_byte();
+                self.cmp(value);
                 (2, 7)
             },
+            // RST 7
+            0xff => {
+                self.rst(7);
+                (0, 11)
+            },
             
-            _ => panic!("unimplemented instruction {}", opcode),
+            _ => {
+                self.raise(CpuError::IllegalOpcode { address: self.pc, opcode });
+                (0, 0)
+            },
         };
 
-        self.pc += op_size;
+        self.pc = self.pc.wrapping_add(op_size);
 
-        cycles
+        self.complete_instruction(cycles)
+    }
+}
+
+impl Cpu for State8080 {
+    fn step(&mut self, state: &mut dyn IOState) -> Result<u64, CpuError> {
+        self.emulate(state)
+    }
+
+    fn run_for(&mut self, budget: u64, state: &mut dyn IOState) -> Result<(u64, u64), CpuError> {
+        self.run_for(budget, state)
+    }
+
+    fn interrupt(&mut self, interrupt: Interrupt) {
+        self.pending_interrupt = Some(interrupt);
+    }
+
+    fn reset(&mut self) {
+        self.pc = 0;
+        self.interupts_enabled = false;
+        self.interrupt_delay = false;
+        self.pending_interrupt = None;
+        self.halted = false;
+        self.call_stack.clear();
+        if let Some(lint) = &mut self.lint {
+            lint.reset();
+        }
+    }
+
+    fn registers(&self) -> Registers {
+        Registers {
+            a: self.a,
+            flags: self.flags.get_psw(),
+            b: self.bc.msb(),
+            c: self.bc.lsb(),
+            d: self.de.msb(),
+            e: self.de.lsb(),
+            h: self.hl.msb(),
+            l: self.hl.lsb(),
+            sp: self.sp,
+            pc: self.pc,
+        }
+    }
+
+    fn halted(&self) -> bool {
+        self.halted
+    }
+
+    fn memory(&self) -> &[u8] {
+        &self.memory
+    }
+
+    fn peek(&self, address: u16) -> u8 {
+        State8080::peek(self, address)
+    }
+
+    fn backtrace(&self) -> Backtrace {
+        self.backtrace()
     }
 }
@@ -0,0 +1,702 @@
+use std::collections::{HashMap, HashSet};
+use std::convert::TryFrom;
+use std::fs;
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{TcpListener, ToSocketAddrs};
+use std::path::Path;
+use std::sync::mpsc::{self, Receiver, Sender, TryRecvError};
+use std::thread;
+
+use crate::call_stack::BacktraceFrame;
+use crate::cpu::instruction_length;
+use crate::json::Json;
+use crate::listing::ListingMap;
+use crate::processor::Cpu;
+use crate::space_invader::{GameState, RAM_END, ROM_SIZE};
+
+// instructions run between checks for requests while the program runs
+const SLICE: usize = 4096;
+const THREAD_ID: i64 = 1;
+
+const REGISTERS: i64 = 1;
+const FLAGS: i64 = 2;
+const MEMORY: i64 = 3;
+// references of the memory ranges start here
+const RANGES: i64 = 100;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Run {
+    Continue,
+    StepIn,
+    // runs until the call at the start returned
+    StepOver { return_address: u16, depth: usize },
+    StepOut { depth: usize },
+}
+
+// a Debug Adapter Protocol server for one client, driving the invaders
+// machine an instruction at a time like the gdb stub
+pub struct DapServer {
+    requests: Receiver<Json>,
+    output: Box<dyn Write + Send>,
+    sequence: i64,
+    listing: ListingMap,
+    // by the source path the client gave
+    source_breakpoints: HashMap<String, Vec<u16>>,
+    instruction_breakpoints: Vec<u16>,
+    breakpoints: HashSet<u16>,
+    // start and length of the ranges shown under Memory
+    memory: Vec<(u16, u32)>,
+    stop_on_entry: bool,
+    running: Option<Run>,
+    // set when the program resumes, a breakpoint at the PC does not stop it
+    resumed: bool,
+    done: bool,
+}
+
+impl DapServer {
+    // messages on stdin and stdout
+    pub fn stdio() -> Self {
+        let (sender, requests) = mpsc::channel();
+        thread::spawn(move || read_messages(BufReader::new(io::stdin()), sender));
+        Self::new(requests, Box::new(io::stdout()))
+    }
+
+    // waits for a client on `address`
+    pub fn accept<A: ToSocketAddrs>(address: A) -> io::Result<Self> {
+        let listener = TcpListener::bind(address)?;
+        let (stream, _) = listener.accept()?;
+        let input = stream.try_clone()?;
+
+        let (sender, requests) = mpsc::channel();
+        thread::spawn(move || read_messages(BufReader::new(input), sender));
+        Ok(Self::new(requests, Box::new(stream)))
+    }
+
+    fn new(requests: Receiver<Json>, output: Box<dyn Write + Send>) -> Self {
+        Self {
+            requests,
+            output,
+            sequence: 0,
+            listing: ListingMap::default(),
+            source_breakpoints: HashMap::new(),
+            instruction_breakpoints: Vec::new(),
+            breakpoints: HashSet::new(),
+            memory: vec![(ROM_SIZE as u16, (RAM_END - ROM_SIZE) as u32)],
+            stop_on_entry: false,
+            running: None,
+            resumed: false,
+            done: false,
+        }
+    }
+
+    // serves the client until it disconnects, `frame` is called for every
+    // frame the machine completes while running
+    pub fn serve(&mut self, game: &mut GameState, frame: &mut dyn FnMut(&mut GameState)) -> io::Result<()> {
+        while !self.done {
+            if self.running.is_none() {
+                match self.requests.recv() {
+                    Ok(request) => self.handle(game, &request)?,
+                    Err(_) => break,
+                }
+                continue;
+            }
+
+            loop {
+                match self.requests.try_recv() {
+                    Ok(request) => self.handle(game, &request)?,
+                    Err(TryRecvError::Empty) => break,
+                    Err(TryRecvError::Disconnected) => return Ok(()),
+                }
+            }
+            self.run(game, frame)?;
+        }
+        Ok(())
+    }
+
+    fn handle(&mut self, game: &mut GameState, request: &Json) -> io::Result<()> {
+        let command = request.get("command").and_then(Json::as_str).unwrap_or("");
+        let arguments = request.get("arguments").cloned().unwrap_or(Json::Null);
+
+        let body = match command {
+            "initialize" => Ok(Json::object(vec![
+                ("supportsConfigurationDoneRequest", true.into()),
+                ("supportsInstructionBreakpoints", true.into()),
+                ("supportsReadMemoryRequest", true.into()),
+            ])),
+            "launch" | "attach" => self.launch(game, &arguments),
+            "setBreakpoints" => Ok(self.set_breakpoints(&arguments)),
+            "setInstructionBreakpoints" => Ok(self.set_instruction_breakpoints(&arguments)),
+            "setExceptionBreakpoints" => Ok(Json::object(vec![])),
+            "configurationDone" => Ok(Json::object(vec![])),
+            "threads" => Ok(Json::object(vec![(
+                "threads",
+                vec![Json::object(vec![("id", THREAD_ID.into()), ("name", "8080".into())])].into(),
+            )])),
+            "stackTrace" => Ok(self.stack_trace(game)),
+            "scopes" => Ok(Json::object(vec![("scopes", vec![
+                scope("Registers", REGISTERS),
+                scope("Flags", FLAGS),
+                scope("Memory", MEMORY),
+            ].into())])),
+            "variables" => self.variables(game, &arguments),
+            "readMemory" => read_memory(game, &arguments),
+            "continue" => {
+                self.resume(Run::Continue);
+                Ok(Json::object(vec![("allThreadsContinued", true.into())]))
+            },
+            "stepIn" => {
+                self.resume(Run::StepIn);
+                Ok(Json::object(vec![]))
+            },
+            "next" => {
+                let pc = game.cpu().pc();
+                let opcode = game.cpu().peek(pc);
+                let is_call = opcode == 0xcd || opcode & 0xc7 == 0xc4 || opcode & 0xc7 == 0xc7;
+
+                self.resume(if is_call {
+                    let return_address = pc.wrapping_add(instruction_length(opcode));
+                    Run::StepOver { return_address, depth: game.cpu().call_stack().frames().len() }
+                } else {
+                    Run::StepIn
+                });
+                Ok(Json::object(vec![]))
+            },
+            "stepOut" => {
+                self.resume(Run::StepOut { depth: game.cpu().call_stack().frames().len() });
+                Ok(Json::object(vec![]))
+            },
+            "pause" => Ok(Json::object(vec![])),
+            "disconnect" | "terminate" => {
+                self.done = true;
+                Ok(Json::object(vec![]))
+            },
+            _ => Err(format!("{} is not supported", command)),
+        };
+
+        self.respond(request, command, body)?;
+
+        match command {
+            "initialize" => self.event("initialized", Json::object(vec![])),
+            "configurationDone" if self.stop_on_entry => self.stopped("entry", None),
+            "configurationDone" => {
+                self.resume(Run::Continue);
+                Ok(())
+            },
+            "pause" if self.running.is_some() => self.stopped("pause", None),
+            "disconnect" | "terminate" => self.event("terminated", Json::object(vec![])),
+            _ => Ok(()),
+        }
+    }
+
+    // program: a binary loaded at origin (0) over the ROM, entry: the PC to
+    // start at, listing: the assembler listing of the program, memory: the
+    // ranges to show as [{ "start": 8192, "length": 256 }], stopOnEntry
+    fn launch(&mut self, game: &mut GameState, arguments: &Json) -> Result<Json, String> {
+        let number = |key: &str| arguments.get(key).and_then(Json::as_i64);
+
+        if let Some(path) = arguments.get("program").and_then(Json::as_str) {
+            let program = fs::read(path).map_err(|error| format!("{}: {}", path, error))?;
+            let origin = number("origin").unwrap_or(0) as u16;
+            for (i, &byte) in program.iter().enumerate() {
+                game.cpu_mut().poke(origin.wrapping_add(i as u16), byte);
+            }
+
+            let mut registers = Cpu::registers(game.cpu());
+            registers.pc = number("entry").map_or(origin, |entry| entry as u16);
+            game.cpu_mut().set_registers(registers);
+        }
+
+        if let Some(path) = arguments.get("listing").and_then(Json::as_str) {
+            self.listing = ListingMap::load(Path::new(path)).map_err(|error| format!("{}: {}", path, error))?;
+        }
+
+        if let Some(ranges) = arguments.get("memory").and_then(Json::as_array) {
+            self.memory = ranges.iter()
+                .filter_map(|range| {
+                    let start = range.get("start").and_then(Json::as_i64)?;
+                    let length = range.get("length").and_then(Json::as_i64)?;
+                    Some((start as u16, length.clamp(1, 0x10000) as u32))
+                })
+                .collect();
+        }
+
+        self.stop_on_entry = arguments.get("stopOnEntry").and_then(Json::as_bool).unwrap_or(false);
+        Ok(Json::object(vec![]))
+    }
+
+    fn set_breakpoints(&mut self, arguments: &Json) -> Json {
+        let path = arguments.get("source").and_then(|source| source.get("path")).and_then(Json::as_str).unwrap_or("");
+        let requested = arguments.get("breakpoints").and_then(Json::as_array).unwrap_or(&[]);
+
+        let mut addresses = Vec::new();
+        let breakpoints = requested.iter()
+            .map(|breakpoint| {
+                let line = breakpoint.get("line").and_then(Json::as_i64).unwrap_or(0) as u32;
+
+                match self.listing.line_address(Path::new(path), line) {
+                    Some(source) => {
+                        addresses.push(source.address);
+                        Json::object(vec![
+                            ("verified", true.into()),
+                            ("line", (source.line as i64).into()),
+                            ("instructionReference", format!("0x{:04x}", source.address).into()),
+                        ])
+                    },
+                    None => Json::object(vec![
+                        ("verified", false.into()),
+                        ("message", "no code for this line in the listing".into()),
+                    ]),
+                }
+            })
+            .collect::<Vec<_>>();
+
+        self.source_breakpoints.insert(path.to_string(), addresses);
+        self.update_breakpoints();
+        Json::object(vec![("breakpoints", breakpoints.into())])
+    }
+
+    fn set_instruction_breakpoints(&mut self, arguments: &Json) -> Json {
+        let requested = arguments.get("breakpoints").and_then(Json::as_array).unwrap_or(&[]);
+
+        self.instruction_breakpoints = requested.iter()
+            .filter_map(|breakpoint| {
+                let reference = breakpoint.get("instructionReference").and_then(Json::as_str)?;
+                let offset = breakpoint.get("offset").and_then(Json::as_i64).unwrap_or(0);
+                Some(parse_address(reference)?.wrapping_add(offset as u16))
+            })
+            .collect();
+        self.update_breakpoints();
+
+        let breakpoints = self.instruction_breakpoints.iter()
+            .map(|_| Json::object(vec![("verified", true.into())]))
+            .collect::<Vec<_>>();
+        Json::object(vec![("breakpoints", breakpoints.into())])
+    }
+
+    fn update_breakpoints(&mut self) {
+        self.breakpoints = self.source_breakpoints.values().flatten()
+            .chain(&self.instruction_breakpoints)
+            .copied()
+            .collect();
+    }
+
+    fn stack_trace(&self, game: &GameState) -> Json {
+        let backtrace = game.backtrace();
+
+        let frames = backtrace.frames.iter().enumerate()
+            .map(|(id, frame)| {
+                let mut members = vec![
+                    ("id", (id as i64).into()),
+                    ("name", frame_name(frame).into()),
+                    ("instructionPointerReference", format!("0x{:04x}", frame.address).into()),
+                    ("column", 0i64.into()),
+                ];
+
+                match self.listing.source(frame.address) {
+                    Some(source) => {
+                        let name = source.file.file_name().map(|name| name.to_string_lossy().into_owned()).unwrap_or_default();
+                        members.push(("line", (source.line as i64).into()));
+                        members.push(("source", Json::object(vec![
+                            ("name", name.into()),
+                            ("path", source.file.to_string_lossy().into_owned().into()),
+                        ])));
+                    },
+                    None => members.push(("line", 0i64.into())),
+                }
+                Json::object(members)
+            })
+            .collect::<Vec<_>>();
+
+        let total = frames.len() as i64;
+        Json::object(vec![("stackFrames", frames.into()), ("totalFrames", total.into())])
+    }
+
+    fn variables(&self, game: &GameState, arguments: &Json) -> Result<Json, String> {
+        let reference = arguments.get("variablesReference").and_then(Json::as_i64).unwrap_or(0);
+        let registers = Cpu::registers(game.cpu());
+        let pair = |high: u8, low: u8| (high as u16) << 8 | low as u16;
+
+        let variables = match reference {
+            REGISTERS => vec![
+                byte_variable("a", registers.a),
+                byte_variable("b", registers.b),
+                byte_variable("c", registers.c),
+                byte_variable("d", registers.d),
+                byte_variable("e", registers.e),
+                byte_variable("h", registers.h),
+                byte_variable("l", registers.l),
+                word_variable("bc", pair(registers.b, registers.c)),
+                word_variable("de", pair(registers.d, registers.e)),
+                word_variable("hl", pair(registers.h, registers.l)),
+                word_variable("sp", registers.sp),
+                word_variable("pc", registers.pc),
+            ],
+            FLAGS => [("s", 0x80), ("z", 0x40), ("ac", 0x10), ("p", 0x04), ("cy", 0x01)].iter()
+                .map(|&(name, mask)| variable(name, if registers.flags & mask != 0 { "1" } else { "0" }.to_string(), 0))
+                .collect(),
+            MEMORY => self.memory.iter().enumerate()
+                .map(|(i, &(start, length))| {
+                    let name = format!("{:04x}-{:04x}", start, start.wrapping_add((length - 1) as u16));
+                    let mut range = variable(&name, format!("{} bytes", length), RANGES + i as i64);
+                    if let Json::Object(members) = &mut range {
+                        members.push(("memoryReference".to_string(), format!("0x{:04x}", start).into()));
+                    }
+                    range
+                })
+                .collect(),
+            _ => match reference.checked_sub(RANGES).and_then(|index| self.memory.get(usize::try_from(index).ok()?)) {
+                Some(&(start, length)) => (0..length).step_by(16)
+                    .map(|offset| {
+                        let address = start.wrapping_add(offset as u16);
+                        let bytes = (0..16.min(length - offset))
+                            .map(|i| format!("{:02x}", game.cpu().peek(address.wrapping_add(i as u16))))
+                            .collect::<Vec<_>>()
+                            .join(" ");
+                        variable(&format!("{:04x}", address), bytes, 0)
+                    })
+                    .collect(),
+                None => return Err(format!("unknown variables reference {}", reference)),
+            },
+        };
+
+        Ok(Json::object(vec![("variables", variables.into())]))
+    }
+
+    fn resume(&mut self, run: Run) {
+        self.running = Some(run);
+        self.resumed = true;
+    }
+
+    // runs a slice of the program, stopping it where the client asked to
+    fn run(&mut self, game: &mut GameState, frame: &mut dyn FnMut(&mut GameState)) -> io::Result<()> {
+        let run = match self.running {
+            Some(run) => run,
+            None => return Ok(()),
+        };
+
+        for _ in 0..SLICE {
+            let pc = game.cpu().pc();
+            let depth = game.cpu().call_stack().frames().len();
+
+            if !self.resumed && self.breakpoints.contains(&pc) {
+                return self.stopped("breakpoint", None);
+            }
+            match run {
+                Run::StepOver { return_address, depth: call_depth } if pc == return_address && depth <= call_depth => {
+                    return self.stopped("step", None);
+                },
+                Run::StepOut { depth: call_depth } if depth < call_depth => return self.stopped("step", None),
+                _ => (),
+            }
+
+            self.resumed = false;
+            match game.step() {
+                Ok(true) => frame(game),
+                Ok(false) => (),
+                Err(error) => return self.stopped("exception", Some(error.to_string())),
+            }
+
+            if run == Run::StepIn {
+                return self.stopped("step", None);
+            }
+        }
+        Ok(())
+    }
+
+    fn stopped(&mut self, reason: &str, description: Option<String>) -> io::Result<()> {
+        self.running = None;
+
+        let mut body = vec![
+            ("reason", reason.into()),
+            ("threadId", THREAD_ID.into()),
+            ("allThreadsStopped", true.into()),
+        ];
+        if let Some(description) = description {
+            body.push(("text", description.into()));
+        }
+        self.event("stopped", Json::object(body))
+    }
+
+    fn respond(&mut self, request: &Json, command: &str, body: Result<Json, String>) -> io::Result<()> {
+        let request_sequence = request.get("seq").and_then(Json::as_i64).unwrap_or(0);
+        let mut members = vec![
+            ("type", "response".into()),
+            ("request_seq", request_sequence.into()),
+            ("command", command.into()),
+        ];
+
+        match body {
+            Ok(body) => {
+                members.push(("success", true.into()));
+                members.push(("body", body));
+            },
+            Err(message) => {
+                members.push(("success", false.into()));
+                members.push(("message", message.into()));
+            },
+        }
+        self.send(members)
+    }
+
+    fn event(&mut self, event: &str, body: Json) -> io::Result<()> {
+        self.send(vec![("type", "event".into()), ("event", event.into()), ("body", body)])
+    }
+
+    fn send(&mut self, mut members: Vec<(&str, Json)>) -> io::Result<()> {
+        self.sequence += 1;
+        members.insert(0, ("seq", self.sequence.into()));
+
+        let message = Json::object(members).to_string();
+        write!(self.output, "Content-Length: {}\r\n\r\n{}", message.len(), message)?;
+        self.output.flush()
+    }
+}
+
+// Content-Length framed messages, until the input ends or is not the protocol
+fn read_messages<R: BufRead>(mut input: R, requests: Sender<Json>) {
+    loop {
+        let mut length = None;
+        loop {
+            let mut header = String::new();
+            match input.read_line(&mut header) {
+                Ok(0) | Err(_) => return,
+                Ok(_) => (),
+            }
+
+            let header = header.trim();
+            if header.is_empty() {
+                break;
+            }
+            if let Some(value) = header.strip_prefix("Content-Length:") {
+                length = value.trim().parse::<usize>().ok();
+            }
+        }
+
+        let mut content = vec![0; match length {
+            Some(length) => length,
+            None => return,
+        }];
+        if input.read_exact(&mut content).is_err() {
+            return;
+        }
+
+        let message = match Json::parse(&String::from_utf8_lossy(&content)) {
+            Ok(message) => message,
+            Err(_) => continue,
+        };
+        if requests.send(message).is_err() {
+            return;
+        }
+    }
+}
+
+fn read_memory(game: &GameState, arguments: &Json) -> Result<Json, String> {
+    let reference = arguments.get("memoryReference").and_then(Json::as_str).unwrap_or("");
+    let start = parse_address(reference).ok_or_else(|| format!("bad memory reference {}", reference))?;
+    let offset = arguments.get("offset").and_then(Json::as_i64).unwrap_or(0);
+    let count = arguments.get("count").and_then(Json::as_i64).unwrap_or(0).clamp(0, 0x10000);
+
+    let address = start.wrapping_add(offset as u16);
+    let bytes = (0..count).map(|i| game.cpu().peek(address.wrapping_add(i as u16))).collect::<Vec<_>>();
+
+    Ok(Json::object(vec![
+        ("address", format!("0x{:04x}", address).into()),
+        ("data", base64(&bytes).into()),
+    ]))
+}
+
+// 0x1234, 1234h or decimal
+fn parse_address(reference: &str) -> Option<u16> {
+    if let Some(hex) = reference.strip_prefix("0x") {
+        u16::from_str_radix(hex, 16).ok()
+    } else if let Some(hex) = reference.strip_suffix('h') {
+        u16::from_str_radix(hex, 16).ok()
+    } else {
+        reference.parse().ok()
+    }
+}
+
+fn frame_name(frame: &BacktraceFrame) -> String {
+    match (&frame.name, frame.function) {
+        (Some(name), _) => name.clone(),
+        (None, Some(function)) => format!("sub_{:04x}", function),
+        (None, None) => "(reset)".to_string(),
+    }
+}
+
+fn scope(name: &str, reference: i64) -> Json {
+    Json::object(vec![
+        ("name", name.into()),
+        ("variablesReference", reference.into()),
+        ("expensive", false.into()),
+    ])
+}
+
+fn variable(name: &str, value: String, reference: i64) -> Json {
+    Json::object(vec![
+        ("name", name.into()),
+        ("value", value.into()),
+        ("variablesReference", reference.into()),
+    ])
+}
+
+fn byte_variable(name: &str, value: u8) -> Json {
+    variable(name, format!("0x{:02x}", value), 0)
+}
+
+fn word_variable(name: &str, value: u16) -> Json {
+    variable(name, format!("0x{:04x}", value), 0)
+}
+
+fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+
+    for chunk in bytes.chunks(3) {
+        let group = (chunk[0] as u32) << 16
+            | (*chunk.get(1).unwrap_or(&0) as u32) << 8
+            | *chunk.get(2).unwrap_or(&0) as u32;
+
+        for i in 0..4 {
+            if i <= chunk.len() {
+                encoded.push(ALPHABET[(group >> (18 - 6 * i) & 0x3f) as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // what the server writes, handed over to the client a write at a time
+    struct Channel(Sender<Vec<u8>>);
+
+    impl Write for Channel {
+        fn write(&mut self, bytes: &[u8]) -> io::Result<usize> {
+            self.0.send(bytes.to_vec()).map_err(|_| io::Error::from(io::ErrorKind::BrokenPipe))?;
+            Ok(bytes.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    // a client talking to a server on the invaders machine in a thread
+    struct Client {
+        requests: Sender<Json>,
+        output: Receiver<Vec<u8>>,
+        received: Vec<u8>,
+        sequence: i64,
+        server: thread::JoinHandle<io::Result<()>>,
+    }
+
+    impl Client {
+        fn new() -> Self {
+            let (requests, receiver) = mpsc::channel();
+            let (sender, output) = mpsc::channel();
+            let server = thread::spawn(move || {
+                let mut game = GameState::new_game();
+                DapServer::new(receiver, Box::new(Channel(sender))).serve(&mut game, &mut |_| ())
+            });
+            Self { requests, output, received: Vec::new(), sequence: 0, server }
+        }
+
+        fn request(&mut self, command: &str, arguments: Json) {
+            self.sequence += 1;
+            self.requests.send(Json::object(vec![
+                ("seq", self.sequence.into()),
+                ("type", "request".into()),
+                ("command", command.into()),
+                ("arguments", arguments),
+            ])).unwrap();
+        }
+
+        // the next message, with its Content-Length header checked
+        fn message(&mut self) -> Json {
+            loop {
+                let text = String::from_utf8_lossy(&self.received).into_owned();
+                if let Some(end) = text.find("\r\n\r\n") {
+                    let length: usize = text[..end].strip_prefix("Content-Length: ").unwrap().parse().unwrap();
+                    if self.received.len() >= end + 4 + length {
+                        let message = self.received.drain(..end + 4 + length).skip(end + 4).collect::<Vec<_>>();
+                        return Json::parse(&String::from_utf8(message).unwrap()).unwrap();
+                    }
+                }
+                self.received.extend(self.output.recv().unwrap());
+            }
+        }
+
+        // the body of the response to the last request, which succeeded
+        fn response(&mut self, command: &str) -> Json {
+            let response = self.message();
+            assert_eq!(response.get("type").and_then(Json::as_str), Some("response"));
+            assert_eq!(response.get("command").and_then(Json::as_str), Some(command));
+            assert_eq!(response.get("request_seq").and_then(Json::as_i64), Some(self.sequence));
+            assert_eq!(response.get("success").and_then(Json::as_bool), Some(true), "{}", response);
+            response.get("body").unwrap().clone()
+        }
+
+        fn event(&mut self, event: &str) -> Json {
+            let message = self.message();
+            assert_eq!(message.get("type").and_then(Json::as_str), Some("event"));
+            assert_eq!(message.get("event").and_then(Json::as_str), Some(event), "{}", message);
+            message.get("body").unwrap().clone()
+        }
+    }
+
+    fn string<'a>(value: &'a Json, key: &str) -> Option<&'a str> {
+        value.get(key).and_then(Json::as_str)
+    }
+
+    #[test]
+    fn a_session_stops_at_an_instruction_breakpoint_and_shows_the_machine() {
+        let mut client = Client::new();
+
+        client.request("initialize", Json::object(vec![("adapterID", "rust-8080".into())]));
+        let capabilities = client.response("initialize");
+        assert_eq!(capabilities.get("supportsInstructionBreakpoints"), Some(&Json::Bool(true)));
+        client.event("initialized");
+
+        client.request("setInstructionBreakpoints", Json::object(vec![(
+            "breakpoints",
+            vec![Json::object(vec![("instructionReference", "0x18d4".into())])].into(),
+        )]));
+        let breakpoints = client.response("setInstructionBreakpoints");
+        assert_eq!(breakpoints.to_string(), r#"{"breakpoints":[{"verified":true}]}"#);
+
+        client.request("continue", Json::object(vec![("threadId", THREAD_ID.into())]));
+        client.response("continue");
+        let stopped = client.event("stopped");
+        assert_eq!(string(&stopped, "reason"), Some("breakpoint"));
+        assert_eq!(stopped.get("threadId").and_then(Json::as_i64), Some(THREAD_ID));
+
+        client.request("stackTrace", Json::object(vec![("threadId", THREAD_ID.into())]));
+        let trace = client.response("stackTrace");
+        let frames = trace.get("stackFrames").and_then(Json::as_array).unwrap();
+        assert_eq!(frames.len(), 1);
+        assert_eq!(string(&frames[0], "name"), Some("(reset)"));
+        assert_eq!(string(&frames[0], "instructionPointerReference"), Some("0x18d4"));
+
+        client.request("variables", Json::object(vec![("variablesReference", REGISTERS.into())]));
+        let registers = client.response("variables");
+        let pc = registers.get("variables").and_then(Json::as_array).unwrap().iter()
+            .find(|variable| string(variable, "name") == Some("pc"))
+            .and_then(|variable| string(variable, "value"));
+        assert_eq!(pc, Some("0x18d4"));
+
+        client.request("variables", Json::object(vec![("variablesReference", 99i64.into())]));
+        let response = client.message();
+        assert_eq!(response.get("success"), Some(&Json::Bool(false)));
+        assert_eq!(string(&response, "message"), Some("unknown variables reference 99"));
+
+        client.request("disconnect", Json::object(vec![]));
+        client.response("disconnect");
+        client.event("terminated");
+        client.server.join().unwrap().unwrap();
+    }
+}
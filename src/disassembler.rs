@@ -0,0 +1,200 @@
+use std::collections::HashSet;
+
+use crate::cpu::{instruction_length, is_illegal};
+use crate::symbols::SymbolTable;
+
+pub const REGISTERS: [&str; 8] = ["b", "c", "d", "e", "h", "l", "m", "a"];
+pub const PAIRS: [&str; 4] = ["b", "d", "h", "sp"];
+// PUSH and POP name the last pair by the PSW
+pub const STACK_PAIRS: [&str; 4] = ["b", "d", "h", "psw"];
+pub const CONDITIONS: [&str; 8] = ["nz", "z", "nc", "c", "po", "pe", "p", "m"];
+pub const ALU: [&str; 8] = ["add", "adc", "sub", "sbb", "ana", "xra", "ora", "cmp"];
+pub const ALU_IMMEDIATE: [&str; 8] = ["adi", "aci", "sui", "sbi", "ani", "xri", "ori", "cpi"];
+pub const ACCUMULATOR: [&str; 8] = ["rlc", "rrc", "ral", "rar", "daa", "cma", "stc", "cmc"];
+
+// undocumented opcodes, they are shown as data so the text assembles back
+// to the same bytes
+pub fn is_undocumented(opcode: u8) -> bool {
+    is_illegal(opcode) || opcode == 0x20
+}
+
+// bytes the instruction at `opcode` takes in a listing, one for the
+// undocumented opcodes shown as data
+pub fn length(opcode: u8) -> u16 {
+    if is_undocumented(opcode) {
+        1
+    } else {
+        instruction_length(opcode)
+    }
+}
+
+// an 8 bit value the way the assembler reads it, e.g. 3eh or 0c3h
+pub fn byte(value: u8) -> String {
+    intel_hex(format!("{:02x}", value))
+}
+
+pub fn word(value: u16) -> String {
+    intel_hex(format!("{:04x}", value))
+}
+
+fn intel_hex(digits: String) -> String {
+    if digits.starts_with(|digit: char| digit.is_ascii_alphabetic()) {
+        format!("0{}h", digits)
+    } else {
+        format!("{}h", digits)
+    }
+}
+
+// Intel mnemonics for `opcode` with its immediate data or address
+pub fn disassemble(opcode: u8, operand: u16) -> String {
+    if is_undocumented(opcode) {
+        return format!("db {}", byte(opcode));
+    }
+
+    let destination = REGISTERS[(opcode >> 3 & 7) as usize];
+    let source = REGISTERS[(opcode & 7) as usize];
+    let pair = PAIRS[(opcode >> 4 & 3) as usize];
+    let condition = CONDITIONS[(opcode >> 3 & 7) as usize];
+    let data = byte(operand as u8);
+    let address = word(operand);
+
+    match opcode {
+        0x00 => "nop".to_string(),
+        0x76 => "hlt".to_string(),
+        0x40..=0x7f => format!("mov {},{}", destination, source),
+        0x80..=0xbf => format!("{} {}", ALU[(opcode >> 3 & 7) as usize], source),
+        0x22 => format!("shld {}", address),
+        0x2a => format!("lhld {}", address),
+        0x32 => format!("sta {}", address),
+        0x3a => format!("lda {}", address),
+        0x02 | 0x12 => format!("stax {}", pair),
+        0x0a | 0x1a => format!("ldax {}", pair),
+        0xc3 => format!("jmp {}", address),
+        0xcd => format!("call {}", address),
+        0xc9 => "ret".to_string(),
+        0xe9 => "pchl".to_string(),
+        0xf9 => "sphl".to_string(),
+        0xe3 => "xthl".to_string(),
+        0xeb => "xchg".to_string(),
+        0xd3 => format!("out {}", data),
+        0xdb => format!("in {}", data),
+        0xf3 => "di".to_string(),
+        0xfb => "ei".to_string(),
+        _ => match opcode & 0xc7 {
+            0x01 if opcode & 8 == 0 => format!("lxi {},{}", pair, address),
+            0x01 => format!("dad {}", pair),
+            0x03 if opcode & 8 == 0 => format!("inx {}", pair),
+            0x03 => format!("dcx {}", pair),
+            0x04 => format!("inr {}", destination),
+            0x05 => format!("dcr {}", destination),
+            0x06 => format!("mvi {},{}", destination, data),
+            0x07 => ACCUMULATOR[(opcode >> 3 & 7) as usize].to_string(),
+            0xc0 => format!("r{}", condition),
+            0xc1 if opcode & 8 == 0 => format!("pop {}", STACK_PAIRS[(opcode >> 4 & 3) as usize]),
+            0xc5 if opcode & 8 == 0 => format!("push {}", STACK_PAIRS[(opcode >> 4 & 3) as usize]),
+            0xc2 => format!("j{} {}", condition, address),
+            0xc4 => format!("c{} {}", condition, address),
+            0xc6 => format!("{} {}", ALU_IMMEDIATE[(opcode >> 3 & 7) as usize], data),
+            0xc7 => format!("rst {}", opcode >> 3 & 7),
+            _ => format!("db {}", byte(opcode)),
+        },
+    }
+}
+
+// the instruction at `address` read through `peek`, with its length; the
+// addresses it jumps to or reads and writes are shown by their labels, the
+// value LXI loads only when a name is exactly at it as it may not be an
+// address at all
+pub fn disassemble_at(peek: &dyn Fn(u16) -> u8, address: u16, symbols: &SymbolTable) -> (String, u16) {
+    let opcode = peek(address);
+    let length = length(opcode);
+    let operand = match length {
+        2 => peek(address.wrapping_add(1)) as u16,
+        3 => (peek(address.wrapping_add(2)) as u16) << 8 | peek(address.wrapping_add(1)) as u16,
+        _ => 0,
+    };
+    let text = disassemble(opcode, operand);
+
+    let label = match opcode & 0xcf {
+        _ if length != 3 => None,
+        0x01 => symbols.name(operand).map(str::to_string),
+        _ => symbols.label(operand),
+    };
+    match (label, text.strip_suffix(&word(operand))) {
+        (Some(label), Some(instruction)) => (format!("{}{}", instruction, label), length),
+        _ => (text, length),
+    }
+}
+
+// an address `lines` instructions before `address` from where decoding
+// lands on it, code can not be decoded backwards reliably otherwise
+pub fn start_before(peek: &dyn Fn(u16) -> u8, address: u16, lines: u16) -> u16 {
+    for distance in (1..=lines * 3).rev() {
+        let start = address.wrapping_sub(distance);
+        let mut at = start;
+        let mut count = 0;
+
+        while at.wrapping_sub(start) < distance {
+            at = at.wrapping_add(length(peek(at)));
+            count += 1;
+        }
+        if at == address && count <= lines {
+            return start;
+        }
+    }
+    address
+}
+
+// the whole of `rom` as source the assembler turns back into the same
+// bytes, with the labels of `symbols` where instructions start and an EQU
+// for every other label it uses
+pub fn source(rom: &[u8], origin: u16, symbols: &SymbolTable) -> String {
+    let peek = |address: u16| rom.get(address.wrapping_sub(origin) as usize).copied().unwrap_or(0);
+    let mut starts = HashSet::new();
+    let mut at = 0;
+    while at < rom.len() {
+        let length = length(rom[at]) as usize;
+        if at + length <= rom.len() {
+            starts.insert(origin.wrapping_add(at as u16));
+        }
+        at += length;
+    }
+
+    let mut text = String::new();
+    for (address, name) in symbols.labels().filter(|(address, _)| !starts.contains(address)) {
+        text += &format!("{:<15} equ {}\n", name, word(address));
+    }
+    if !text.is_empty() {
+        text += "\n";
+    }
+    text += &format!("        org {}\n", word(origin));
+
+    let mut at = 0;
+    while at < rom.len() {
+        let address = origin.wrapping_add(at as u16);
+        let (instruction, length) = disassemble_at(&peek, address, symbols);
+
+        // an instruction cut off by the end of the ROM stays data
+        if at + length as usize > rom.len() {
+            for (offset, &value) in rom[at..].iter().enumerate() {
+                let address = address.wrapping_add(offset as u16);
+                text += &format!("        {:<20}; {:04x}\n", format!("db {}", byte(value)), address);
+            }
+            break;
+        }
+        let name = symbols.name(address);
+        let comment = symbols.comment(address);
+        if name.is_some() || comment.is_some() {
+            text += "\n";
+        }
+        if let Some(comment) = comment {
+            text += &format!("; {}\n", comment);
+        }
+        if let Some(name) = name {
+            text += &format!("{}:\n", name);
+        }
+        text += &format!("        {:<20}; {:04x}\n", instruction, address);
+        at += length as usize;
+    }
+    text
+}
@@ -0,0 +1,97 @@
+use std::error::Error;
+use std::fmt;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MachineError {
+    UnmappedPort { port: u8, write: bool },
+}
+
+impl fmt::Display for MachineError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            MachineError::UnmappedPort { port, write: false } => {
+                write!(f, "port {} is not readable", port)
+            },
+            MachineError::UnmappedPort { port, write: true } => {
+                write!(f, "port {} is not writable", port)
+            },
+        }
+    }
+}
+
+impl Error for MachineError {}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CpuError {
+    IllegalOpcode { address: u16, opcode: u8 },
+    // access outside the memory the core has
+    BusFault { address: u16 },
+    // PUSH below 0 or POP above 0xffff
+    StackWrap { sp: u16 },
+    Machine(MachineError),
+}
+
+impl fmt::Display for CpuError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            CpuError::IllegalOpcode { address, opcode } => {
+                write!(f, "illegal opcode {:02x} at {:04x}", opcode, address)
+            },
+            CpuError::BusFault { address } => write!(f, "bus fault at {:04x}", address),
+            CpuError::StackWrap { sp } => write!(f, "stack wrapped around with sp={:04x}", sp),
+            CpuError::Machine(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl Error for CpuError {}
+
+impl From<MachineError> for CpuError {
+    fn from(error: MachineError) -> Self {
+        CpuError::Machine(error)
+    }
+}
+
+// what a machine does when the program touches a port nothing is wired to
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum UnmappedPortPolicy {
+    // reads float to 0xff, writes go nowhere
+    OpenBus,
+    // like open bus, but reports the access on stderr
+    Log,
+    Error,
+}
+
+impl UnmappedPortPolicy {
+    // the names machine descriptions and --unmapped-ports use
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "open_bus" => Some(UnmappedPortPolicy::OpenBus),
+            "log" => Some(UnmappedPortPolicy::Log),
+            "error" => Some(UnmappedPortPolicy::Error),
+            _ => None,
+        }
+    }
+
+    pub fn read(self, port: u8) -> Result<u8, MachineError> {
+        match self {
+            UnmappedPortPolicy::OpenBus => Ok(0xff),
+            UnmappedPortPolicy::Log => {
+                eprintln!("read from unmapped port {}", port);
+                Ok(0xff)
+            },
+            UnmappedPortPolicy::Error => Err(MachineError::UnmappedPort { port, write: false }),
+        }
+    }
+
+    pub fn write(self, port: u8, value: u8) -> Result<(), MachineError> {
+        match self {
+            UnmappedPortPolicy::OpenBus => Ok(()),
+            UnmappedPortPolicy::Log => {
+                eprintln!("write of {:02x} to unmapped port {}", value, port);
+                Ok(())
+            },
+            UnmappedPortPolicy::Error => Err(MachineError::UnmappedPort { port, write: true }),
+        }
+    }
+}
@@ -0,0 +1,680 @@
+use std::collections::HashSet;
+use std::io::{self, ErrorKind, Read, Write};
+use std::net::{TcpListener, TcpStream, ToSocketAddrs};
+
+use crate::bus::CycleKind;
+use crate::cpu::{instruction_length, State8080};
+use crate::error::CpuError;
+use crate::hex;
+use crate::history::History;
+use crate::processor::{Cpu, Registers};
+use crate::space_invader::GameState;
+
+// signals in stop replies
+const SIGINT: u8 = 2;
+const SIGILL: u8 = 4;
+const SIGTRAP: u8 = 5;
+const SIGSEGV: u8 = 11;
+
+// instructions run between checks for a Ctrl-C from the client
+const POLL_INTERVAL: u64 = 1024;
+
+// registers in the order of the 8080's encoding, then the PSW, SP and PC;
+// `g` sends them as bytes, SP and PC little endian
+const TARGET_XML: &str = r#"<?xml version="1.0"?>
+<!DOCTYPE target SYSTEM "gdb-target.dtd">
+<target version="1.0">
+  <feature name="org.rust-8080.i8080">
+    <reg name="b" bitsize="8" type="uint8"/>
+    <reg name="c" bitsize="8" type="uint8"/>
+    <reg name="d" bitsize="8" type="uint8"/>
+    <reg name="e" bitsize="8" type="uint8"/>
+    <reg name="h" bitsize="8" type="uint8"/>
+    <reg name="l" bitsize="8" type="uint8"/>
+    <reg name="a" bitsize="8" type="uint8"/>
+    <reg name="f" bitsize="8" type="uint8"/>
+    <reg name="sp" bitsize="16" type="data_ptr"/>
+    <reg name="pc" bitsize="16" type="code_ptr"/>
+  </feature>
+</target>
+"#;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SessionEnd {
+    // the client detached or went away, the machine keeps running
+    Detached,
+    Killed,
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+enum WatchKind {
+    Write,
+    Read,
+    Access,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Watchpoint {
+    kind: WatchKind,
+    address: u16,
+    length: u16,
+}
+
+impl Watchpoint {
+    fn covers(&self, address: u16) -> bool {
+        address.wrapping_sub(self.address) < self.length
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Stop {
+    Signal(u8),
+    Breakpoint { hardware: bool },
+    Watch { kind: WatchKind, address: u16 },
+    // going back reached the oldest snapshot
+    HistoryBegin,
+}
+
+impl Stop {
+    fn reply(self) -> String {
+        match self {
+            Stop::Signal(signal) => format!("S{:02x}", signal),
+            Stop::Breakpoint { hardware: false } => format!("T{:02x}swbreak:;", SIGTRAP),
+            Stop::Breakpoint { hardware: true } => format!("T{:02x}hwbreak:;", SIGTRAP),
+            Stop::Watch { kind, address } => {
+                let name = match kind {
+                    WatchKind::Write => "watch",
+                    WatchKind::Read => "rwatch",
+                    WatchKind::Access => "awatch",
+                };
+                format!("T{:02x}{}:{:04x};", SIGTRAP, name, address)
+            },
+            Stop::HistoryBegin => format!("T{:02x}replaylog:begin;", SIGTRAP),
+        }
+    }
+}
+
+fn fault_signal(error: CpuError) -> u8 {
+    match error {
+        CpuError::IllegalOpcode { .. } => SIGILL,
+        _ => SIGSEGV,
+    }
+}
+
+// a GDB remote serial protocol server for one client, driving the invaders
+// machine an instruction at a time so the video interrupts keep their timing
+pub struct GdbStub {
+    stream: TcpStream,
+    software_breakpoints: HashSet<u16>,
+    hardware_breakpoints: HashSet<u16>,
+    watchpoints: Vec<Watchpoint>,
+    history: History,
+}
+
+impl GdbStub {
+    // waits for a client on `address`
+    pub fn accept<A: ToSocketAddrs>(address: A) -> io::Result<Self> {
+        let listener = TcpListener::bind(address)?;
+        let (stream, _) = listener.accept()?;
+        Self::new(stream)
+    }
+
+    fn new(stream: TcpStream) -> io::Result<Self> {
+        stream.set_nodelay(true)?;
+
+        Ok(Self {
+            stream,
+            software_breakpoints: HashSet::new(),
+            hardware_breakpoints: HashSet::new(),
+            watchpoints: Vec::new(),
+            history: History::new(),
+        })
+    }
+
+    // serves the client until it detaches or kills the machine, `frame` is
+    // called for every frame the machine completes while running
+    pub fn serve(&mut self, game: &mut GameState, frame: &mut dyn FnMut(&mut GameState)) -> io::Result<SessionEnd> {
+        loop {
+            let packet = match self.read_packet()? {
+                Some(packet) => packet,
+                None => return Ok(SessionEnd::Detached),
+            };
+
+            let reply = match packet.as_bytes().first() {
+                Some(b'?') => Stop::Signal(SIGTRAP).reply(),
+                Some(b'c') => self.resume(game, &packet[1..], false, frame)?.reply(),
+                Some(b's') => self.resume(game, &packet[1..], true, frame)?.reply(),
+                Some(b'b') if packet == "bs" => self.step_back(game).reply(),
+                Some(b'b') if packet == "bc" => self.continue_back(game).reply(),
+                Some(b'D') => {
+                    self.send("OK")?;
+                    return Ok(SessionEnd::Detached);
+                },
+                Some(b'k') => return Ok(SessionEnd::Killed),
+                _ if packet.starts_with("qRcmd,") => self.monitor(game, &packet["qRcmd,".len()..]),
+                _ => {
+                    let reply = self.query(game.cpu_mut(), &packet).unwrap_or_else(|| "E01".to_string());
+                    if matches!(packet.as_bytes().first(), Some(b'G' | b'P' | b'M')) && reply == "OK" {
+                        self.history.edited(game);
+                    }
+                    reply
+                },
+            };
+            self.send(&reply)?;
+        }
+    }
+
+    // packets that do not run the machine, none when malformed
+    fn query(&mut self, cpu: &mut State8080, packet: &str) -> Option<String> {
+        let command = packet.get(..1)?;
+        let arguments = &packet[1..];
+
+        let reply = match command {
+            "g" => hex(&register_bytes(&Cpu::registers(cpu))),
+            "G" => {
+                cpu.set_registers(registers_from_bytes(&hex::bytes(arguments)?)?);
+                "OK".to_string()
+            },
+            "p" => {
+                let bytes = register_bytes(&Cpu::registers(cpu));
+                let (offset, size) = register_slot(usize::from_str_radix(arguments, 16).ok()?)?;
+                hex(&bytes[offset..offset + size])
+            },
+            "P" => {
+                let (number, value) = split(arguments, '=')?;
+                let (offset, size) = register_slot(usize::from_str_radix(number, 16).ok()?)?;
+                let value = hex::bytes(value)?;
+                let mut bytes = register_bytes(&Cpu::registers(cpu));
+                bytes.get_mut(offset..offset + size)?.copy_from_slice(value.get(..size)?);
+                cpu.set_registers(registers_from_bytes(&bytes)?);
+                "OK".to_string()
+            },
+            "m" => {
+                let (address, length) = address_length(arguments)?;
+                let bytes = (0..length).map(|i| cpu.peek(address.wrapping_add(i))).collect::<Vec<_>>();
+                hex(&bytes)
+            },
+            "M" => {
+                let (range, data) = split(arguments, ':')?;
+                let (address, length) = address_length(range)?;
+                let data = hex::bytes(data)?;
+                if data.len() != length as usize {
+                    return None;
+                }
+                for (i, &byte) in data.iter().enumerate() {
+                    cpu.poke(address.wrapping_add(i as u16), byte);
+                }
+                "OK".to_string()
+            },
+            "Z" | "z" => self.breakpoint(command == "Z", arguments)?,
+            "H" => "OK".to_string(),
+            "q" => self.general_query(arguments),
+            // binary writes, vCont and everything else fall back to the
+            // basic packets
+            _ => String::new(),
+        };
+        Some(reply)
+    }
+
+    fn general_query(&self, query: &str) -> String {
+        if query.starts_with("Supported") {
+            return "PacketSize=1000;qXfer:features:read+;swbreak+;hwbreak+;ReverseStep+;ReverseContinue+".to_string();
+        }
+        if let Some(range) = query.strip_prefix("Xfer:features:read:target.xml:") {
+            return match address_length(range) {
+                Some((offset, length)) => {
+                    let xml = TARGET_XML.as_bytes();
+                    let start = (offset as usize).min(xml.len());
+                    let end = (start + length as usize).min(xml.len());
+                    let more = if end < xml.len() { "m" } else { "l" };
+                    format!("{}{}", more, String::from_utf8_lossy(&xml[start..end]))
+                },
+                None => "E01".to_string(),
+            };
+        }
+
+        match query {
+            "Attached" => "1".to_string(),
+            "C" => "QC1".to_string(),
+            "fThreadInfo" => "m1".to_string(),
+            "sThreadInfo" => "l".to_string(),
+            _ => String::new(),
+        }
+    }
+
+    // Z/z type,address,kind: 0 software and 1 hardware breakpoints, 2 write,
+    // 3 read and 4 access watchpoints
+    fn breakpoint(&mut self, insert: bool, arguments: &str) -> Option<String> {
+        let (kind, range) = split(arguments, ',')?;
+        let (address, length) = address_length(range)?;
+
+        let watch = match kind {
+            "0" | "1" => {
+                let breakpoints = if kind == "0" { &mut self.software_breakpoints } else { &mut self.hardware_breakpoints };
+                if insert {
+                    breakpoints.insert(address);
+                } else {
+                    breakpoints.remove(&address);
+                }
+                return Some("OK".to_string());
+            },
+            "2" => WatchKind::Write,
+            "3" => WatchKind::Read,
+            "4" => WatchKind::Access,
+            _ => return Some(String::new()),
+        };
+
+        let watchpoint = Watchpoint { kind: watch, address, length: length.max(1) };
+        if insert {
+            self.watchpoints.push(watchpoint);
+        } else {
+            self.watchpoints.retain(|&existing| existing != watchpoint);
+        }
+        Some("OK".to_string())
+    }
+
+    // c and s with an optional address to resume at, a breakpoint at the
+    // resume address does not stop the first instruction
+    fn resume(
+        &mut self,
+        game: &mut GameState,
+        address: &str,
+        single_step: bool,
+        frame: &mut dyn FnMut(&mut GameState),
+    ) -> io::Result<Stop> {
+        if let Ok(address) = u16::from_str_radix(address, 16) {
+            let mut registers = Cpu::registers(game.cpu());
+            registers.pc = address;
+            game.cpu_mut().set_registers(registers);
+            self.history.edited(game);
+        }
+
+        self.stream.set_nonblocking(true)?;
+        let stop = self.run(game, single_step, frame);
+        self.stream.set_nonblocking(false)?;
+        stop
+    }
+
+    fn run(&mut self, game: &mut GameState, single_step: bool, frame: &mut dyn FnMut(&mut GameState)) -> io::Result<Stop> {
+        let mut executed = 0;
+
+        loop {
+            let pc = game.cpu().pc();
+            if executed > 0 {
+                if self.software_breakpoints.contains(&pc) {
+                    return Ok(Stop::Breakpoint { hardware: false });
+                }
+                if self.hardware_breakpoints.contains(&pc) {
+                    return Ok(Stop::Breakpoint { hardware: true });
+                }
+                if executed % POLL_INTERVAL == 0 && self.interrupted()? {
+                    return Ok(Stop::Signal(SIGINT));
+                }
+            }
+
+            let opcode = game.cpu().peek(pc);
+            self.history.record(game);
+            let completed_frame = match game.step() {
+                Ok(completed_frame) => completed_frame,
+                Err(error) => return Ok(Stop::Signal(fault_signal(error))),
+            };
+            executed += 1;
+
+            if completed_frame {
+                frame(game);
+            }
+            if let Some(stop) = self.watched_access(game.cpu(), pc, opcode) {
+                return Ok(stop);
+            }
+            if single_step {
+                return Ok(Stop::Signal(SIGTRAP));
+            }
+        }
+    }
+
+    fn step_back(&mut self, game: &mut GameState) -> Stop {
+        if self.history.step_back(game) {
+            Stop::Signal(SIGTRAP)
+        } else {
+            Stop::HistoryBegin
+        }
+    }
+
+    // back to the last instruction at a breakpoint or with a watched
+    // access, stopping before it ran
+    fn continue_back(&mut self, game: &mut GameState) -> Stop {
+        let mut stop = Stop::HistoryBegin;
+        let found = self.history.find_back(game, &mut |game, pc| {
+            let hit = if self.software_breakpoints.contains(&pc) {
+                Some(Stop::Breakpoint { hardware: false })
+            } else if self.hardware_breakpoints.contains(&pc) {
+                Some(Stop::Breakpoint { hardware: true })
+            } else {
+                self.watched_access(game.cpu(), pc, game.cpu().peek(pc))
+            };
+            if let Some(hit) = hit {
+                stop = hit;
+            }
+            hit.is_some()
+        });
+
+        let instruction = match found {
+            Some(instruction) => instruction,
+            None => {
+                stop = Stop::HistoryBegin;
+                self.history.oldest().unwrap_or(game.instructions())
+            },
+        };
+        self.history.travel(game, instruction);
+        stop
+    }
+
+    // `monitor` commands, the output goes back hex encoded
+    fn monitor(&mut self, game: &mut GameState, command: &str) -> String {
+        let command = match hex::bytes(command).and_then(|bytes| String::from_utf8(bytes).ok()) {
+            Some(command) => command,
+            None => return "E01".to_string(),
+        };
+        let mut words = command.split_whitespace();
+
+        let output = match (words.next(), words.next().map(|address| u16::from_str_radix(address, 16))) {
+            (Some("who-wrote"), Some(Ok(address))) => match self.history.last_write(game, address) {
+                Some(write) => format!(
+                    "{:04x} was written with {:02x} by the instruction at {:04x}, {} instructions ago\n",
+                    write.address,
+                    write.value,
+                    write.pc,
+                    game.instructions() - write.instruction,
+                ),
+                None => format!("{:04x} was not written since instruction {}\n", address, self.history.oldest().unwrap_or(game.instructions())),
+            },
+            (Some("reset"), None) => {
+                game.reset();
+                // running back across the reset would undo it
+                self.history.edited(game);
+                "reset to 0000\n".to_string()
+            },
+            _ => "commands: who-wrote <hex address>, reset\n".to_string(),
+        };
+        hex(output.as_bytes())
+    }
+
+    // the first watched data access of the last instruction, the opcode and
+    // operand reads at `pc` do not count
+    fn watched_access(&self, cpu: &State8080, pc: u16, opcode: u8) -> Option<Stop> {
+        if self.watchpoints.is_empty() {
+            return None;
+        }
+        let length = instruction_length(opcode);
+
+        for cycle in cpu.machine_cycles() {
+            let write = match cycle.kind {
+                CycleKind::MemoryRead | CycleKind::StackRead => false,
+                CycleKind::MemoryWrite | CycleKind::StackWrite => true,
+                _ => continue,
+            };
+            if !write && cycle.address.wrapping_sub(pc) < length {
+                continue;
+            }
+
+            let hit = self.watchpoints.iter().find(|watchpoint| {
+                watchpoint.covers(cycle.address) && match watchpoint.kind {
+                    WatchKind::Write => write,
+                    WatchKind::Read => !write,
+                    WatchKind::Access => true,
+                }
+            });
+            if let Some(watchpoint) = hit {
+                return Some(Stop::Watch { kind: watchpoint.kind, address: cycle.address });
+            }
+        }
+        None
+    }
+
+    // whether the client sent a Ctrl-C, other bytes while running are acks
+    fn interrupted(&mut self) -> io::Result<bool> {
+        let mut byte = [0];
+        loop {
+            match self.stream.read(&mut byte) {
+                Ok(0) => return Ok(true),
+                Ok(_) if byte[0] == 0x03 => return Ok(true),
+                Ok(_) => (),
+                Err(error) if error.kind() == ErrorKind::WouldBlock => return Ok(false),
+                Err(error) => return Err(error),
+            }
+        }
+    }
+
+    fn read_byte(&mut self) -> io::Result<Option<u8>> {
+        let mut byte = [0];
+        match self.stream.read(&mut byte)? {
+            0 => Ok(None),
+            _ => Ok(Some(byte[0])),
+        }
+    }
+
+    // the next packet with a good checksum, acknowledging it; none once the
+    // client disconnected
+    fn read_packet(&mut self) -> io::Result<Option<String>> {
+        loop {
+            // skip acks and a Ctrl-C sent while the machine was stopped
+            match self.read_byte()? {
+                Some(b'$') => (),
+                Some(_) => continue,
+                None => return Ok(None),
+            }
+
+            let mut data = Vec::new();
+            loop {
+                match self.read_byte()? {
+                    Some(b'#') => break,
+                    Some(b'}') => match self.read_byte()? {
+                        Some(byte) => data.push(byte ^ 0x20),
+                        None => return Ok(None),
+                    },
+                    Some(byte) => data.push(byte),
+                    None => return Ok(None),
+                }
+            }
+
+            let mut checksum = [0; 2];
+            self.stream.read_exact(&mut checksum)?;
+            let expected = std::str::from_utf8(&checksum).ok().and_then(|digits| u8::from_str_radix(digits, 16).ok());
+
+            if expected == Some(checksum_of(&data)) {
+                self.stream.write_all(b"+")?;
+                return Ok(Some(String::from_utf8_lossy(&data).into_owned()));
+            }
+            self.stream.write_all(b"-")?;
+        }
+    }
+
+    fn send(&mut self, reply: &str) -> io::Result<()> {
+        let mut data = Vec::with_capacity(reply.len());
+        for &byte in reply.as_bytes() {
+            if let b'#' | b'$' | b'}' | b'*' = byte {
+                data.extend_from_slice(&[b'}', byte ^ 0x20]);
+            } else {
+                data.push(byte);
+            }
+        }
+
+        let mut packet = Vec::with_capacity(data.len() + 4);
+        packet.push(b'$');
+        packet.extend_from_slice(&data);
+        packet.extend_from_slice(format!("#{:02x}", checksum_of(&data)).as_bytes());
+        self.stream.write_all(&packet)
+    }
+}
+
+fn checksum_of(data: &[u8]) -> u8 {
+    data.iter().fold(0u8, |sum, &byte| sum.wrapping_add(byte))
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+fn split(arguments: &str, separator: char) -> Option<(&str, &str)> {
+    let at = arguments.find(separator)?;
+    Some((&arguments[..at], &arguments[at + 1..]))
+}
+
+// addr,length in hex
+fn address_length(arguments: &str) -> Option<(u16, u16)> {
+    let (address, length) = split(arguments, ',')?;
+    let address = u16::from_str_radix(address, 16).ok()?;
+    let length = u32::from_str_radix(length, 16).ok()?;
+    Some((address, length.min(0xffff) as u16))
+}
+
+// offset and size of register `number` in the `g` packet
+fn register_slot(number: usize) -> Option<(usize, usize)> {
+    match number {
+        0..=7 => Some((number, 1)),
+        8 | 9 => Some((8 + 2 * (number - 8), 2)),
+        _ => None,
+    }
+}
+
+fn register_bytes(registers: &Registers) -> [u8; 12] {
+    let [sp_low, sp_high] = registers.sp.to_le_bytes();
+    let [pc_low, pc_high] = registers.pc.to_le_bytes();
+    [
+        registers.b, registers.c, registers.d, registers.e, registers.h, registers.l,
+        registers.a, registers.flags, sp_low, sp_high, pc_low, pc_high,
+    ]
+}
+
+fn registers_from_bytes(bytes: &[u8]) -> Option<Registers> {
+    if bytes.len() < 12 {
+        return None;
+    }
+    Some(Registers {
+        b: bytes[0],
+        c: bytes[1],
+        d: bytes[2],
+        e: bytes[3],
+        h: bytes[4],
+        l: bytes[5],
+        a: bytes[6],
+        flags: bytes[7],
+        sp: u16::from_le_bytes([bytes[8], bytes[9]]),
+        pc: u16::from_le_bytes([bytes[10], bytes[11]]),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    fn packet(data: &str) -> Vec<u8> {
+        format!("${}#{:02x}", data, checksum_of(data.as_bytes())).into_bytes()
+    }
+
+    // sends the packets to a stub serving the invaders machine and collects
+    // what comes back until the session ends
+    fn session(packets: &[&str]) -> (SessionEnd, String) {
+        exchange(packets.iter().flat_map(|data| packet(data)).collect())
+    }
+
+    // the replies of a session ended by a kill, without acks and checksums
+    fn replies(packets: &[&str]) -> Vec<String> {
+        let mut packets = packets.to_vec();
+        packets.push("k");
+        let (end, replies) = session(&packets);
+        assert_eq!(end, SessionEnd::Killed);
+        payloads(&replies)
+    }
+
+    fn payloads(replies: &str) -> Vec<String> {
+        replies.split('$').skip(1).map(|reply| reply[..reply.find('#').unwrap()].to_string()).collect()
+    }
+
+    // the bytes as the client sends them, packets and anything in between
+    fn exchange(packets: Vec<u8>) -> (SessionEnd, String) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let mut client = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+        let (stream, _) = listener.accept().unwrap();
+
+        let replies = thread::spawn(move || {
+            client.write_all(&packets).unwrap();
+            let mut replies = String::new();
+            client.read_to_string(&mut replies).unwrap();
+            replies
+        });
+
+        let mut game = GameState::new_game();
+        let end = GdbStub::new(stream).unwrap().serve(&mut game, &mut |_| ()).unwrap();
+        (end, replies.join().unwrap())
+    }
+
+    #[test]
+    fn an_empty_packet_is_answered_with_an_error() {
+        let (end, replies) = session(&["", "k"]);
+
+        assert_eq!(end, SessionEnd::Killed);
+        assert_eq!(replies, "+$E01#a6+");
+    }
+
+    #[test]
+    fn g_and_capital_g_read_and_write_all_registers() {
+        // B to L, A, the flags, SP 2000h and PC 0040h
+        let replies = replies(&["g", "G01020304050607d500200040", "g"]);
+
+        assert_eq!(replies, ["000000000000000000000000", "OK", "01020304050607d500200040"]);
+    }
+
+    #[test]
+    fn p_and_capital_p_read_and_write_one_register() {
+        let replies = replies(&["P6=42", "p6", "P9=3412", "p9", "pa"]);
+
+        assert_eq!(replies, ["OK", "42", "OK", "3412", "E01"]);
+    }
+
+    #[test]
+    fn m_and_capital_m_read_and_write_memory() {
+        let replies = replies(&["m0,6", "M2000,3:aabbcc", "m2000,3", "M2000,2:aa"]);
+
+        assert_eq!(replies, ["000000c3d418", "OK", "aabbcc", "E01"]);
+    }
+
+    #[test]
+    fn breakpoints_stop_before_their_instruction() {
+        let replies = replies(&["Z0,3,1", "c", "p9", "z0,3,1", "Z1,18d4,1", "c", "p9"]);
+
+        assert_eq!(replies, ["OK", "T05swbreak:;", "0300", "OK", "OK", "T05hwbreak:;", "d418"]);
+    }
+
+    #[test]
+    fn watchpoints_stop_after_the_access_and_name_its_address() {
+        // sta 2100h, lda 2101h, lxi h,2102h and mov a,m in RAM
+        let replies = replies(&[
+            "M2000,a:3200213a01212102217e",
+            "Z2,2100,1", "c2000",
+            "Z3,2101,1", "c",
+            "Z4,2102,1", "c", "p9",
+        ]);
+
+        assert_eq!(replies, ["OK", "OK", "T05watch:2100;", "OK", "T05rwatch:2101;", "OK", "T05awatch:2102;", "0a20"]);
+    }
+
+    #[test]
+    fn s_runs_one_instruction() {
+        let replies = replies(&["s", "p9", "s", "s", "s", "p9"]);
+
+        assert_eq!(replies, ["S05", "0100", "S05", "S05", "S05", "d418"]);
+    }
+
+    #[test]
+    fn ctrl_c_interrupts_a_continue() {
+        let mut bytes = packet("c");
+        bytes.push(0x03);
+        bytes.extend(packet("k"));
+        let (end, replies) = exchange(bytes);
+
+        assert_eq!(end, SessionEnd::Killed);
+        assert_eq!(payloads(&replies), ["S02"]);
+    }
+}
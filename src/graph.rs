@@ -0,0 +1,215 @@
+use std::collections::BTreeSet;
+use std::fmt::Write;
+
+use crate::analysis::{Analysis, ReferenceKind};
+use crate::disassembler::{self, CONDITIONS};
+use crate::recompiler::{flow, Flow};
+
+// Graphviz for the basic blocks of `function`: the condition a jump or
+// return is taken on labels its edge, calls go to the routine called and
+// returns to one exit
+pub fn control_flow(analysis: &Analysis, rom: &[u8], function: u16) -> String {
+    let symbols = &analysis.symbols;
+    let peek = |address: u16| rom.get(address.wrapping_sub(analysis.origin) as usize).copied().unwrap_or(0);
+    let blocks = analysis.basic_blocks(rom, function);
+    let starts = blocks.iter().map(|block| block.start()).collect::<BTreeSet<_>>();
+
+    let mut dot = String::new();
+    writeln!(dot, "digraph \"{}\" {{", escape(&symbols.describe(function))).unwrap();
+    writeln!(dot, "    node [shape=box, fontname=\"monospace\"];").unwrap();
+
+    // routines called and code jumped to outside the function
+    let mut others = BTreeSet::new();
+    let mut returns = false;
+    let mut indirect = false;
+
+    for block in &blocks {
+        let mut label = String::new();
+        if let Some(name) = symbols.name(block.start()) {
+            label += &format!("{}:\\l", escape(name));
+        }
+        for &address in &block.instructions {
+            let (text, _) = disassembler::disassemble_at(&peek, address, symbols);
+            label += &format!("{:04x}  {}\\l", address, escape(&text));
+        }
+        writeln!(dot, "    b_{:04x} [label=\"{}\"];", block.start(), label).unwrap();
+
+        let (opcode, operand, length) = match analysis.decode(rom, block.last()) {
+            Some(instruction) => instruction,
+            None => continue,
+        };
+        let next = block.last().wrapping_add(length);
+        let condition = CONDITIONS[(opcode >> 3 & 7) as usize];
+        let conditional = opcode & 0xc7 == 0xc0 || opcode & 0xc7 == 0xc2 || opcode & 0xc7 == 0xc4;
+
+        let mut node = |target: u16| {
+            if starts.contains(&target) {
+                format!("b_{:04x}", target)
+            } else {
+                others.insert(target);
+                format!("x_{:04x}", target)
+            }
+        };
+        match flow(opcode, operand) {
+            Flow::Call(target) => {
+                let label = if conditional { condition } else { "call" };
+                writeln!(dot, "    b_{:04x} -> {} [style=dashed, label=\"{}\"];", block.start(), node(target), label).unwrap();
+            },
+            Flow::Return | Flow::ConditionalReturn => {
+                let label = if conditional { format!(" [label=\"{}\"]", condition) } else { String::new() };
+                writeln!(dot, "    b_{:04x} -> exit{};", block.start(), label).unwrap();
+                returns = true;
+            },
+            Flow::Indirect if block.successors.is_empty() => {
+                writeln!(dot, "    b_{:04x} -> indirect;", block.start()).unwrap();
+                indirect = true;
+            },
+            _ => (),
+        }
+        for &successor in &block.successors {
+            let label = match flow(opcode, operand) {
+                Flow::ConditionalJump(target) if successor == target && successor != next => format!(" [label=\"{}\"]", condition),
+                Flow::Indirect => " [label=\"table\"]".to_string(),
+                _ if successor != next => " [label=\"jmp\"]".to_string(),
+                _ => String::new(),
+            };
+            writeln!(dot, "    b_{:04x} -> {}{};", block.start(), node(successor), label).unwrap();
+        }
+    }
+
+    for other in others {
+        writeln!(dot, "    x_{:04x} [label=\"{}\", shape=ellipse];", other, escape(&symbols.describe(other))).unwrap();
+    }
+    if returns {
+        writeln!(dot, "    exit [label=\"ret\", shape=oval];").unwrap();
+    }
+    if indirect {
+        writeln!(dot, "    indirect [label=\"pchl\", shape=oval];").unwrap();
+    }
+    writeln!(dot, "}}").unwrap();
+    dot
+}
+
+// Graphviz for which function calls which, the entries in bold and jumps
+// from one function into another dashed
+pub fn call_graph(analysis: &Analysis) -> String {
+    let symbols = &analysis.symbols;
+    let mut dot = String::new();
+    writeln!(dot, "digraph calls {{").unwrap();
+    writeln!(dot, "    node [shape=box, fontname=\"monospace\"];").unwrap();
+
+    let mut edges = BTreeSet::new();
+    let mut nodes = analysis.functions.clone();
+    for reference in &analysis.references {
+        let caller = match reference.function {
+            Some(caller) => caller,
+            None => continue,
+        };
+        let edge = match reference.kind {
+            ReferenceKind::Call => (caller, reference.to, false),
+            ReferenceKind::Jump if analysis.functions.contains(&reference.to) && reference.to != caller => (caller, reference.to, true),
+            _ => continue,
+        };
+        nodes.insert(reference.to);
+        edges.insert(edge);
+    }
+
+    for node in nodes {
+        let style = if analysis.entries.contains(&node) { ", style=bold" } else { "" };
+        writeln!(dot, "    f_{:04x} [label=\"{}\"{}];", node, escape(&symbols.describe(node)), style).unwrap();
+    }
+    for (caller, callee, jump) in edges {
+        let style = if jump { " [style=dashed]" } else { "" };
+        writeln!(dot, "    f_{:04x} -> f_{:04x}{};", caller, callee, style).unwrap();
+    }
+    writeln!(dot, "}}").unwrap();
+    dot
+}
+
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis;
+    use crate::assembler;
+    use crate::symbols::SymbolTable;
+
+    // a loop with a conditional return, a call and a jump into another
+    // routine
+    const PROGRAM: &str = "
+            org 0
+    start:  call count
+            jmp done
+    count:  mvi b,10
+    again:  dcr b
+            rz
+            call done
+            jnz again
+            ret
+    done:   hlt
+    ";
+
+    fn analysis() -> (Vec<u8>, Analysis) {
+        let (_, rom) = assembler::assemble(PROGRAM).unwrap().image.binary();
+        let names = SymbolTable::parse("0000 start\n0006 count\n0008 again\n0011 done\n").unwrap();
+        let analysis = analysis::analyze(&rom, 0, &[0], &names);
+        (rom, analysis)
+    }
+
+    #[test]
+    fn blocks_end_at_calls_branches_and_conditional_returns() {
+        let (rom, analysis) = analysis();
+        let blocks = analysis.basic_blocks(&rom, 0x0006).into_iter()
+            .map(|block| (block.instructions, block.successors))
+            .collect::<Vec<_>>();
+        assert_eq!(blocks, [
+            (vec![0x0006], vec![0x0008]),
+            (vec![0x0008, 0x0009], vec![0x000a]),
+            (vec![0x000a], vec![0x000d]),
+            (vec![0x000d], vec![0x0008, 0x0010]),
+            (vec![0x0010], vec![]),
+        ]);
+    }
+
+    #[test]
+    fn control_flow_edges_are_labelled_with_their_conditions() {
+        let (rom, analysis) = analysis();
+        assert_eq!(control_flow(&analysis, &rom, 0x0006), r#"digraph "0006 count" {
+    node [shape=box, fontname="monospace"];
+    b_0006 [label="count:\l0006  mvi b,0ah\l"];
+    b_0006 -> b_0008;
+    b_0008 [label="again:\l0008  dcr b\l0009  rz\l"];
+    b_0008 -> exit [label="z"];
+    b_0008 -> b_000a;
+    b_000a [label="000a  call done\l"];
+    b_000a -> x_0011 [style=dashed, label="call"];
+    b_000a -> b_000d;
+    b_000d [label="000d  jnz again\l"];
+    b_000d -> b_0008 [label="nz"];
+    b_000d -> b_0010;
+    b_0010 [label="0010  ret\l"];
+    b_0010 -> exit;
+    x_0011 [label="0011 done", shape=ellipse];
+    exit [label="ret", shape=oval];
+}
+"#);
+    }
+
+    #[test]
+    fn call_graph_dashes_jumps_into_other_functions() {
+        let (_, analysis) = analysis();
+        assert_eq!(call_graph(&analysis), r#"digraph calls {
+    node [shape=box, fontname="monospace"];
+    f_0000 [label="0000 start", style=bold];
+    f_0006 [label="0006 count"];
+    f_0011 [label="0011 done"];
+    f_0000 -> f_0006;
+    f_0000 -> f_0011 [style=dashed];
+    f_0006 -> f_0011;
+}
+"#);
+    }
+}
@@ -0,0 +1,22 @@
+// hex as it appears on command lines, in manifests and object files and on
+// the GDB wire
+
+// an address, with or without 0x in front
+pub fn address(text: &str) -> Option<u16> {
+    u16::from_str_radix(text.strip_prefix("0x").unwrap_or(text), 16).ok()
+}
+
+// a port or a byte, with or without 0x in front
+pub fn byte(text: &str) -> Option<u8> {
+    u8::from_str_radix(text.strip_prefix("0x").unwrap_or(text), 16).ok()
+}
+
+// two digits per byte
+pub fn bytes(digits: &str) -> Option<Vec<u8>> {
+    if !digits.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..digits.len()).step_by(2)
+        .map(|i| u8::from_str_radix(digits.get(i..i + 2)?, 16).ok())
+        .collect()
+}
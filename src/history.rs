@@ -0,0 +1,294 @@
+use std::collections::VecDeque;
+
+use crate::bus::CycleKind;
+use crate::space_invader::{GameState, Snapshot};
+
+// instructions between snapshots, the most a step back replays
+pub const SNAPSHOT_INTERVAL: u64 = 100_000;
+// snapshots kept, about 16 KiB each
+pub const SNAPSHOT_CAPACITY: usize = 1000;
+
+// an instruction that wrote to memory
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Write {
+    // instructions run before it, the position `travel` takes
+    pub instruction: u64,
+    pub pc: u16,
+    pub address: u16,
+    pub value: u8,
+}
+
+// snapshots of the machine taken while a debugger steps it, the program
+// goes back by restoring the nearest one before and running on to the
+// instruction, which repeats the past as the inputs are in the snapshots
+pub struct History {
+    // oldest first, by the instructions run
+    snapshots: VecDeque<Snapshot>,
+    interval: u64,
+    capacity: usize,
+}
+
+impl History {
+    pub fn new() -> Self {
+        Self::with_interval(SNAPSHOT_INTERVAL, SNAPSHOT_CAPACITY)
+    }
+
+    pub fn with_interval(interval: u64, capacity: usize) -> Self {
+        Self {
+            snapshots: VecDeque::with_capacity(capacity),
+            interval: interval.max(1),
+            capacity: capacity.max(1),
+        }
+    }
+
+    // called before every instruction the debugger runs, takes a snapshot
+    // once `interval` instructions passed since the last one
+    pub fn record(&mut self, game: &GameState) {
+        let now = game.instructions();
+        let index = self.snapshots.partition_point(|snapshot| snapshot.instructions() <= now);
+
+        let due = match index.checked_sub(1).map(|previous| &self.snapshots[previous]) {
+            Some(previous) => now - previous.instructions() >= self.interval,
+            None => true,
+        };
+        if due {
+            self.insert(index, game.snapshot());
+        }
+    }
+
+    // the debugger changed the machine, the snapshots after it are of a
+    // past that can not be repeated anymore
+    pub fn edited(&mut self, game: &GameState) {
+        let now = game.instructions();
+        while self.snapshots.back().is_some_and(|snapshot| snapshot.instructions() >= now) {
+            self.snapshots.pop_back();
+        }
+        let index = self.snapshots.len();
+        self.insert(index, game.snapshot());
+    }
+
+    // the earliest instruction the machine can go back to
+    pub fn oldest(&self) -> Option<u64> {
+        self.snapshots.front().map(Snapshot::instructions)
+    }
+
+    // puts the machine where it was after `instruction` instructions, false
+    // when that is before the oldest snapshot
+    pub fn travel(&self, game: &mut GameState, instruction: u64) -> bool {
+        let index = self.snapshots.partition_point(|snapshot| snapshot.instructions() <= instruction);
+        let snapshot = match index.checked_sub(1) {
+            Some(index) => &self.snapshots[index],
+            None => return false,
+        };
+
+        replay(game, |game| {
+            // going forward from the present is faster when no snapshot is
+            // closer
+            let now = game.instructions();
+            if instruction < now || snapshot.instructions() > now {
+                game.restore(snapshot);
+            }
+            run_to(game, instruction, &mut |_, _| ());
+        });
+        true
+    }
+
+    // one instruction back, false at the oldest snapshot
+    pub fn step_back(&self, game: &mut GameState) -> bool {
+        match game.instructions().checked_sub(1) {
+            Some(previous) => self.travel(game, previous),
+            None => false,
+        }
+    }
+
+    // the last instruction before the present that `hit` holds for, called
+    // with the machine after each instruction and the address it ran at;
+    // the machine is left at an arbitrary point in between
+    pub fn find_back(&self, game: &mut GameState, hit: &mut dyn FnMut(&GameState, u16) -> bool) -> Option<u64> {
+        let mut end = game.instructions();
+
+        replay(game, |game| {
+            for snapshot in self.snapshots.iter().rev() {
+                if snapshot.instructions() >= end {
+                    continue;
+                }
+
+                game.restore(snapshot);
+                let mut found = None;
+                run_to(game, end, &mut |game, pc| {
+                    if hit(game, pc) {
+                        found = Some(game.instructions() - 1);
+                    }
+                });
+                if found.is_some() {
+                    return found;
+                }
+                end = snapshot.instructions();
+            }
+            None
+        })
+    }
+
+    // the last instruction that wrote to `address`, the machine stays where
+    // it is
+    pub fn last_write(&self, game: &mut GameState, address: u16) -> Option<Write> {
+        let present = game.snapshot();
+        let size = game.cpu().memory().len();
+        let mut write = None;
+
+        self.find_back(game, &mut |game, pc| {
+            let cycle = game.cpu().machine_cycles().iter().rev().find(|cycle| {
+                matches!(cycle.kind, CycleKind::MemoryWrite | CycleKind::StackWrite)
+                    && cycle.address as usize % size == address as usize % size
+            });
+            if let Some(cycle) = cycle {
+                write = Some(Write { instruction: game.instructions() - 1, pc, address: cycle.address, value: cycle.data });
+            }
+            cycle.is_some()
+        });
+
+        game.restore(&present);
+        write
+    }
+
+    fn insert(&mut self, index: usize, snapshot: Snapshot) {
+        self.snapshots.insert(index, snapshot);
+        if self.snapshots.len() > self.capacity {
+            self.snapshots.pop_front();
+        }
+    }
+}
+
+// runs the machine up to `instruction`, faults were reported when the
+// instructions first ran
+fn run_to(game: &mut GameState, instruction: u64, after: &mut dyn FnMut(&GameState, u16)) {
+    while game.instructions() < instruction {
+        let pc = game.cpu().pc();
+        let _ = game.step();
+        after(game, pc);
+    }
+}
+
+// the instruments would count the replayed instructions twice
+fn replay<T>(game: &mut GameState, run: impl FnOnce(&mut GameState) -> T) -> T {
+    let cpu = game.cpu_mut();
+    let monitor = cpu.take_bus_monitor();
+    let profiler = cpu.take_profiler();
+    let coverage = cpu.take_coverage();
+    let lint = cpu.take_lint();
+
+    let result = run(game);
+
+    let cpu = game.cpu_mut();
+    if let Some(monitor) = monitor {
+        cpu.set_bus_monitor(monitor);
+    }
+    if let Some(profiler) = profiler {
+        cpu.set_profiler(profiler);
+    }
+    if let Some(coverage) = coverage {
+        cpu.set_coverage(coverage);
+    }
+    if let Some(lint) = lint {
+        cpu.set_lint(lint);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::processor::{Cpu, Registers};
+
+    const INSTRUCTIONS: u64 = 40_000;
+
+    // the registers and RAM after each of `instructions`, running forward
+    // from power on with a history recorded the way a debugger does
+    fn run(history: &mut History, instructions: &[u64]) -> (GameState, Vec<(Registers, Vec<u8>)>) {
+        let mut game = GameState::new_game();
+        let mut states = Vec::new();
+
+        while game.instructions() < INSTRUCTIONS {
+            if instructions.contains(&game.instructions()) {
+                states.push((Cpu::registers(game.cpu()), game.cpu().memory().to_vec()));
+            }
+            history.record(&game);
+            game.step().unwrap();
+        }
+        (game, states)
+    }
+
+    #[test]
+    fn travel_repeats_the_registers_and_ram_of_earlier_instructions() {
+        // across snapshots and the video interrupts of the first frames
+        let instructions = [0, 1, 999, 1000, 17_000, 25_001, INSTRUCTIONS - 1];
+        let mut history = History::with_interval(1000, 100);
+        let (mut game, states) = run(&mut history, &instructions);
+
+        for (&instruction, (registers, memory)) in instructions.iter().zip(&states).rev() {
+            assert!(history.travel(&mut game, instruction));
+            assert_eq!(game.instructions(), instruction);
+            assert_eq!(Cpu::registers(game.cpu()), *registers, "after {} instructions", instruction);
+            assert!(game.cpu().memory() == &memory[..], "RAM after {} instructions", instruction);
+        }
+    }
+
+    #[test]
+    fn step_back_goes_one_instruction_back_until_the_oldest_snapshot() {
+        let mut history = History::with_interval(1000, 3);
+        let (mut game, states) = run(&mut history, &[INSTRUCTIONS - 1]);
+
+        assert!(history.step_back(&mut game));
+        assert_eq!(game.instructions(), INSTRUCTIONS - 1);
+        assert_eq!(Cpu::registers(game.cpu()), states[0].0);
+
+        let oldest = history.oldest().unwrap();
+        assert_eq!(oldest, INSTRUCTIONS - 3000);
+        assert!(history.travel(&mut game, oldest));
+        assert!(!history.step_back(&mut game));
+    }
+
+    #[test]
+    fn find_back_stops_at_the_last_instruction_that_hits() {
+        let mut history = History::with_interval(1000, 100);
+        let (mut game, _) = run(&mut history, &[]);
+
+        // the address some late instruction ran at, and the last time it ran
+        let mut pcs = Vec::new();
+        let mut forward = GameState::new_game();
+        while forward.instructions() < INSTRUCTIONS {
+            pcs.push(forward.cpu().pc());
+            forward.step().unwrap();
+        }
+        let target = pcs[INSTRUCTIONS as usize - 2000];
+        let last = pcs.iter().rposition(|&pc| pc == target).map(|index| index as u64);
+
+        let found = history.find_back(&mut game, &mut |_, pc| pc == target);
+        assert_eq!(found, last);
+    }
+
+    #[test]
+    fn last_write_finds_the_store_and_leaves_the_machine_where_it_is() {
+        let mut game = GameState::new_game();
+        let mut history = History::new();
+        // mvi a,42h; sta 2100h; mvi a,43h; sta 2100h; then NOPs in RAM
+        for (i, &byte) in [0x3e, 0x42, 0x32, 0x00, 0x21, 0x3e, 0x43, 0x32, 0x00, 0x21].iter().enumerate() {
+            game.cpu_mut().poke(0x2000 + i as u16, byte);
+        }
+        let mut registers = Cpu::registers(game.cpu());
+        registers.pc = 0x2000;
+        game.cpu_mut().set_registers(registers);
+        history.edited(&game);
+
+        for _ in 0..10 {
+            history.record(&game);
+            game.step().unwrap();
+        }
+
+        let write = history.last_write(&mut game, 0x2100);
+        assert_eq!(write, Some(Write { instruction: 3, pc: 0x2007, address: 0x2100, value: 0x43 }));
+        assert_eq!(game.instructions(), 10);
+        assert_eq!(game.cpu().pc(), 0x2010);
+        assert_eq!(history.last_write(&mut game, 0x2101), None);
+    }
+}
@@ -0,0 +1,99 @@
+// bytes placed at addresses of the 64 KiB address space, as assemblers and
+// linkers build a program, with the addresses nothing was placed at
+pub struct Image {
+    bytes: Vec<Option<u8>>,
+    // where the program starts, when it says
+    pub entry: Option<u16>,
+}
+
+// bytes of a data record in Intel HEX output
+const HEX_RECORD_SIZE: usize = 16;
+
+impl Image {
+    pub fn new() -> Self {
+        Self { bytes: vec![None; 0x10000], entry: None }
+    }
+
+    // every byte of a memory from address 0, for dumps
+    pub fn from_memory(memory: &[u8]) -> Self {
+        let mut image = Self::new();
+        for (address, &byte) in memory.iter().take(0x10000).enumerate() {
+            image.place(address as u16, byte);
+        }
+        image
+    }
+
+    // false when something was placed at the address before
+    pub fn place(&mut self, address: u16, value: u8) -> bool {
+        let slot = &mut self.bytes[address as usize];
+        let free = slot.is_none();
+        *slot = Some(value);
+        free
+    }
+
+    // the lowest and the highest address with a byte
+    pub fn range(&self) -> Option<(u16, u16)> {
+        let first = self.bytes.iter().position(Option::is_some)?;
+        let last = self.bytes.iter().rposition(Option::is_some)?;
+        Some((first as u16, last as u16))
+    }
+
+    // runs of consecutive bytes with the address they start at
+    pub fn chunks(&self) -> Vec<(u16, Vec<u8>)> {
+        let mut chunks: Vec<(u16, Vec<u8>)> = Vec::new();
+
+        for (address, byte) in self.bytes.iter().enumerate() {
+            let byte = match byte {
+                Some(byte) => *byte,
+                None => continue,
+            };
+            match chunks.last_mut() {
+                Some((start, bytes)) if *start as usize + bytes.len() == address => bytes.push(byte),
+                _ => chunks.push((address as u16, vec![byte])),
+            }
+        }
+        chunks
+    }
+
+    // the bytes from the lowest to the highest address and the address they
+    // start at, the gaps are zero
+    pub fn binary(&self) -> (u16, Vec<u8>) {
+        match self.range() {
+            Some((first, last)) => {
+                let bytes = self.bytes[first as usize..=last as usize].iter()
+                    .map(|byte| byte.unwrap_or(0))
+                    .collect();
+                (first, bytes)
+            },
+            None => (0, Vec::new()),
+        }
+    }
+
+    // Intel HEX records of the bytes, with a start address record when the
+    // entry is known
+    pub fn intel_hex(&self) -> String {
+        let mut hex = String::new();
+
+        for (start, bytes) in self.chunks() {
+            for (i, data) in bytes.chunks(HEX_RECORD_SIZE).enumerate() {
+                let address = start.wrapping_add((i * HEX_RECORD_SIZE) as u16);
+                hex += &hex_record(address, 0x00, data);
+            }
+        }
+
+        if let Some(entry) = self.entry {
+            hex += &hex_record(0, 0x03, &[0, 0, (entry >> 8) as u8, entry as u8]);
+        }
+        hex += &hex_record(0, 0x01, &[]);
+        hex
+    }
+}
+
+fn hex_record(address: u16, kind: u8, data: &[u8]) -> String {
+    let mut bytes = vec![data.len() as u8, (address >> 8) as u8, address as u8, kind];
+    bytes.extend_from_slice(data);
+    let checksum = bytes.iter().fold(0u8, |sum, &byte| sum.wrapping_add(byte)).wrapping_neg();
+
+    let digits = bytes.iter().map(|byte| format!("{:02X}", byte)).collect::<String>();
+    format!(":{}{:02X}\n", digits, checksum)
+}
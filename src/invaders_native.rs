@@ -0,0 +1,15699 @@
+// Recompiled from an 8080 ROM by `rust-8080 recompile src/invaders.rom src/invaders_native.rs`, do not edit.
+
+use crate::native::{Machine, Routine, Stop, AUX_CARRY, CARRY, PARITY, SIGN, SZP, ZERO};
+
+pub fn routine(address: u16) -> Option<Routine> {
+    match address {
+        0x0000 => Some(sub_0000),
+        0x0008 => Some(sub_0008),
+        0x0010 => Some(sub_0010),
+        0x0018 => Some(sub_0018),
+        0x0020 => Some(sub_0020),
+        0x0028 => Some(sub_0028),
+        0x0030 => Some(sub_0030),
+        0x0038 => Some(sub_0038),
+        0x00b1 => Some(sub_00b1),
+        0x00d7 => Some(sub_00d7),
+        0x0100 => Some(sub_0100),
+        0x013b => Some(sub_013b),
+        0x0141 => Some(sub_0141),
+        0x017a => Some(sub_017a),
+        0x01a1 => Some(sub_01a1),
+        0x01c0 => Some(sub_01c0),
+        0x01cf => Some(sub_01cf),
+        0x01d9 => Some(sub_01d9),
+        0x01e4 => Some(sub_01e4),
+        0x01e6 => Some(sub_01e6),
+        0x01ef => Some(sub_01ef),
+        0x01f5 => Some(sub_01f5),
+        0x0209 => Some(sub_0209),
+        0x020e => Some(sub_020e),
+        0x0213 => Some(sub_0213),
+        0x021a => Some(sub_021a),
+        0x0248 => Some(sub_0248),
+        0x024b => Some(sub_024b),
+        0x0878 => Some(sub_0878),
+        0x0886 => Some(sub_0886),
+        0x088d => Some(sub_088d),
+        0x08d1 => Some(sub_08d1),
+        0x08d8 => Some(sub_08d8),
+        0x08f3 => Some(sub_08f3),
+        0x08ff => Some(sub_08ff),
+        0x0913 => Some(sub_0913),
+        0x092e => Some(sub_092e),
+        0x0935 => Some(sub_0935),
+        0x097c => Some(sub_097c),
+        0x0988 => Some(sub_0988),
+        0x09b2 => Some(sub_09b2),
+        0x09c5 => Some(sub_09c5),
+        0x09ca => Some(sub_09ca),
+        0x09d6 => Some(sub_09d6),
+        0x0a3c => Some(sub_0a3c),
+        0x0a59 => Some(sub_0a59),
+        0x0a5f => Some(sub_0a5f),
+        0x0a80 => Some(sub_0a80),
+        0x0a93 => Some(sub_0a93),
+        0x0ab1 => Some(sub_0ab1),
+        0x0ab6 => Some(sub_0ab6),
+        0x0abf => Some(sub_0abf),
+        0x0acf => Some(sub_0acf),
+        0x0ae2 => Some(sub_0ae2),
+        0x0bf1 => Some(sub_0bf1),
+        0x1424 => Some(sub_1424),
+        0x1439 => Some(sub_1439),
+        0x1474 => Some(sub_1474),
+        0x147c => Some(sub_147c),
+        0x14cb => Some(sub_14cb),
+        0x14d8 => Some(sub_14d8),
+        0x1554 => Some(sub_1554),
+        0x1562 => Some(sub_1562),
+        0x156f => Some(sub_156f),
+        0x1581 => Some(sub_1581),
+        0x1590 => Some(sub_1590),
+        0x15c5 => Some(sub_15c5),
+        0x15d3 => Some(sub_15d3),
+        0x15f3 => Some(sub_15f3),
+        0x1611 => Some(sub_1611),
+        0x1618 => Some(sub_1618),
+        0x170e => Some(sub_170e),
+        0x172c => Some(sub_172c),
+        0x1740 => Some(sub_1740),
+        0x176d => Some(sub_176d),
+        0x1775 => Some(sub_1775),
+        0x17c0 => Some(sub_17c0),
+        0x17cd => Some(sub_17cd),
+        0x1804 => Some(sub_1804),
+        0x1815 => Some(sub_1815),
+        0x183a => Some(sub_183a),
+        0x1844 => Some(sub_1844),
+        0x184c => Some(sub_184c),
+        0x1856 => Some(sub_1856),
+        0x189e => Some(sub_189e),
+        0x18e7 => Some(sub_18e7),
+        0x18f1 => Some(sub_18f1),
+        0x18fa => Some(sub_18fa),
+        0x1904 => Some(sub_1904),
+        0x190a => Some(sub_190a),
+        0x1910 => Some(sub_1910),
+        0x191a => Some(sub_191a),
+        0x1925 => Some(sub_1925),
+        0x192b => Some(sub_192b),
+        0x1931 => Some(sub_1931),
+        0x193c => Some(sub_193c),
+        0x1947 => Some(sub_1947),
+        0x1950 => Some(sub_1950),
+        0x1956 => Some(sub_1956),
+        0x1979 => Some(sub_1979),
+        0x1982 => Some(sub_1982),
+        0x1988 => Some(sub_1988),
+        0x19d1 => Some(sub_19d1),
+        0x19d7 => Some(sub_19d7),
+        0x19dc => Some(sub_19dc),
+        0x19e6 => Some(sub_19e6),
+        0x19fa => Some(sub_19fa),
+        0x1a32 => Some(sub_1a32),
+        0x1a3b => Some(sub_1a3b),
+        0x1a5c => Some(sub_1a5c),
+        0x1a69 => Some(sub_1a69),
+        0x1a7f => Some(sub_1a7f),
+        0x1a8b => Some(sub_1a8b),
+        _ => None,
+    }
+}
+
+fn sub_0000(m: &mut Machine) -> Result<(), Stop> {
+    loop {
+        match m.pc {
+            0x0000 => {
+                // 0000  nop
+                m.tick(4, 0x0001)?;
+                // 0001  nop
+                m.tick(4, 0x0002)?;
+                // 0002  nop
+                m.tick(4, 0x0003)?;
+                // 0003  jmp 18d4h
+                m.pc = 0x18d4;
+                m.tick(10, 0x18d4)?;
+            },
+            0x0aea => {
+                // 0aea  xra a
+                let value = m.a;
+                m.a ^= value;
+                m.flags = SZP[m.a as usize] | if m.a > 0xf { AUX_CARRY } else { 0 };
+                m.tick(4, 0x0aeb)?;
+                // 0aeb  out 03h
+                m.output(0x03, m.a)?;
+                m.tick(10, 0x0aed)?;
+                // 0aed  out 05h
+                m.output(0x05, m.a)?;
+                m.tick(10, 0x0aef)?;
+                // 0aef  call 1982h
+                m.push(0x0af2)?;
+                m.pc = 0x1982;
+                m.tick(17, 0x1982)?;
+                m.call(0x0af2)?;
+                m.pc = 0x0af2;
+            },
+            // 0af2  ei
+            0x0af2 => m.interpret()?,
+            0x0af6 => {
+                // 0af6  lda 20ech
+                m.a = m.read(0x20ec)?;
+                m.tick(13, 0x0af9)?;
+                // 0af9  ana a
+                let value = m.a;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(4, 0x0afa)?;
+                // 0afa  lxi h,3017h
+                m.set_hl(0x3017);
+                m.tick(10, 0x0afd)?;
+                // 0afd  mvi c,04h
+                m.c = 0x04;
+                m.tick(7, 0x0aff)?;
+                // 0aff  jnz 0be8h
+                m.pc = if m.flags & ZERO == 0 { 0x0be8 } else { 0x0b02 };
+                m.tick(10, m.pc)?;
+            },
+            0x0b02 => {
+                // 0b02  lxi d,1cfah
+                m.set_de(0x1cfa);
+                m.tick(10, 0x0b05)?;
+                // 0b05  call 0a93h
+                m.push(0x0b08)?;
+                m.pc = 0x0a93;
+                m.tick(17, 0x0a93)?;
+                m.call(0x0b08)?;
+                // 0b08  lxi d,1dafh
+                m.set_de(0x1daf);
+                m.tick(10, 0x0b0b)?;
+                m.pc = 0x0b0b;
+            },
+            0x0b0b => {
+                // 0b0b  call 0acfh
+                m.push(0x0b0e)?;
+                m.pc = 0x0acf;
+                m.tick(17, 0x0acf)?;
+                m.call(0x0b0e)?;
+                // 0b0e  call 0ab1h
+                m.push(0x0b11)?;
+                m.pc = 0x0ab1;
+                m.tick(17, 0x0ab1)?;
+                m.call(0x0b11)?;
+                // 0b11  call 1815h
+                m.push(0x0b14)?;
+                m.pc = 0x1815;
+                m.tick(17, 0x1815)?;
+                m.call(0x0b14)?;
+                // 0b14  call 0ab6h
+                m.push(0x0b17)?;
+                m.pc = 0x0ab6;
+                m.tick(17, 0x0ab6)?;
+                m.call(0x0b17)?;
+                // 0b17  lda 20ech
+                m.a = m.read(0x20ec)?;
+                m.tick(13, 0x0b1a)?;
+                // 0b1a  ana a
+                let value = m.a;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(4, 0x0b1b)?;
+                // 0b1b  jnz 0b4ah
+                m.pc = if m.flags & ZERO == 0 { 0x0b4a } else { 0x0b1e };
+                m.tick(10, m.pc)?;
+            },
+            0x0b1e => {
+                // 0b1e  lxi d,1a95h
+                m.set_de(0x1a95);
+                m.tick(10, 0x0b21)?;
+                // 0b21  call 0ae2h
+                m.push(0x0b24)?;
+                m.pc = 0x0ae2;
+                m.tick(17, 0x0ae2)?;
+                m.call(0x0b24)?;
+                // 0b24  call 0a80h
+                m.push(0x0b27)?;
+                m.pc = 0x0a80;
+                m.tick(17, 0x0a80)?;
+                m.call(0x0b27)?;
+                // 0b27  lxi d,1bb0h
+                m.set_de(0x1bb0);
+                m.tick(10, 0x0b2a)?;
+                // 0b2a  call 0ae2h
+                m.push(0x0b2d)?;
+                m.pc = 0x0ae2;
+                m.tick(17, 0x0ae2)?;
+                m.call(0x0b2d)?;
+                // 0b2d  call 0a80h
+                m.push(0x0b30)?;
+                m.pc = 0x0a80;
+                m.tick(17, 0x0a80)?;
+                m.call(0x0b30)?;
+                // 0b30  call 0ab1h
+                m.push(0x0b33)?;
+                m.pc = 0x0ab1;
+                m.tick(17, 0x0ab1)?;
+                m.call(0x0b33)?;
+                // 0b33  lxi d,1fc9h
+                m.set_de(0x1fc9);
+                m.tick(10, 0x0b36)?;
+                // 0b36  call 0ae2h
+                m.push(0x0b39)?;
+                m.pc = 0x0ae2;
+                m.tick(17, 0x0ae2)?;
+                m.call(0x0b39)?;
+                // 0b39  call 0a80h
+                m.push(0x0b3c)?;
+                m.pc = 0x0a80;
+                m.tick(17, 0x0a80)?;
+                m.call(0x0b3c)?;
+                // 0b3c  call 0ab1h
+                m.push(0x0b3f)?;
+                m.pc = 0x0ab1;
+                m.tick(17, 0x0ab1)?;
+                m.call(0x0b3f)?;
+                // 0b3f  lxi h,33b7h
+                m.set_hl(0x33b7);
+                m.tick(10, 0x0b42)?;
+                // 0b42  mvi b,0ah
+                m.b = 0x0a;
+                m.tick(7, 0x0b44)?;
+                // 0b44  call 14cbh
+                m.push(0x0b47)?;
+                m.pc = 0x14cb;
+                m.tick(17, 0x14cb)?;
+                m.call(0x0b47)?;
+                // 0b47  call 0ab6h
+                m.push(0x0b4a)?;
+                m.pc = 0x0ab6;
+                m.tick(17, 0x0ab6)?;
+                m.call(0x0b4a)?;
+                m.pc = 0x0b4a;
+            },
+            0x0b4a => {
+                // 0b4a  call 09d6h
+                m.push(0x0b4d)?;
+                m.pc = 0x09d6;
+                m.tick(17, 0x09d6)?;
+                m.call(0x0b4d)?;
+                // 0b4d  lda 21ffh
+                m.a = m.read(0x21ff)?;
+                m.tick(13, 0x0b50)?;
+                // 0b50  ana a
+                let value = m.a;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(4, 0x0b51)?;
+                // 0b51  jnz 0b5dh
+                m.pc = if m.flags & ZERO == 0 { 0x0b5d } else { 0x0b54 };
+                m.tick(10, m.pc)?;
+            },
+            0x0b54 => {
+                // 0b54  call 08d1h
+                m.push(0x0b57)?;
+                m.pc = 0x08d1;
+                m.tick(17, 0x08d1)?;
+                m.call(0x0b57)?;
+                // 0b57  sta 21ffh
+                m.write(0x21ff, m.a)?;
+                m.tick(13, 0x0b5a)?;
+                // 0b5a  call 1a7fh
+                m.push(0x0b5d)?;
+                m.pc = 0x1a7f;
+                m.tick(17, 0x1a7f)?;
+                m.call(0x0b5d)?;
+                m.pc = 0x0b5d;
+            },
+            0x0b5d => {
+                // 0b5d  call 01e4h
+                m.push(0x0b60)?;
+                m.pc = 0x01e4;
+                m.tick(17, 0x01e4)?;
+                m.call(0x0b60)?;
+                // 0b60  call 01c0h
+                m.push(0x0b63)?;
+                m.pc = 0x01c0;
+                m.tick(17, 0x01c0)?;
+                m.call(0x0b63)?;
+                // 0b63  call 01efh
+                m.push(0x0b66)?;
+                m.pc = 0x01ef;
+                m.tick(17, 0x01ef)?;
+                m.call(0x0b66)?;
+                // 0b66  call 021ah
+                m.push(0x0b69)?;
+                m.pc = 0x021a;
+                m.tick(17, 0x021a)?;
+                m.call(0x0b69)?;
+                // 0b69  mvi a,01h
+                m.a = 0x01;
+                m.tick(7, 0x0b6b)?;
+                // 0b6b  sta 20c1h
+                m.write(0x20c1, m.a)?;
+                m.tick(13, 0x0b6e)?;
+                // 0b6e  call 01cfh
+                m.push(0x0b71)?;
+                m.pc = 0x01cf;
+                m.tick(17, 0x01cf)?;
+                m.call(0x0b71)?;
+                m.pc = 0x0b71;
+            },
+            0x0b71 => {
+                // 0b71  call 1618h
+                m.push(0x0b74)?;
+                m.pc = 0x1618;
+                m.tick(17, 0x1618)?;
+                m.call(0x0b74)?;
+                // 0b74  call 0bf1h
+                m.push(0x0b77)?;
+                m.pc = 0x0bf1;
+                m.tick(17, 0x0bf1)?;
+                m.call(0x0b77)?;
+                // 0b77  out 06h
+                m.output(0x06, m.a)?;
+                m.tick(10, 0x0b79)?;
+                // 0b79  call 0a59h
+                m.push(0x0b7c)?;
+                m.pc = 0x0a59;
+                m.tick(17, 0x0a59)?;
+                m.call(0x0b7c)?;
+                // 0b7c  jz 0b71h
+                m.pc = if m.flags & ZERO != 0 { 0x0b71 } else { 0x0b7f };
+                m.tick(10, m.pc)?;
+            },
+            0x0b7f => {
+                // 0b7f  xra a
+                let value = m.a;
+                m.a ^= value;
+                m.flags = SZP[m.a as usize] | if m.a > 0xf { AUX_CARRY } else { 0 };
+                m.tick(4, 0x0b80)?;
+                // 0b80  sta 2025h
+                m.write(0x2025, m.a)?;
+                m.tick(13, 0x0b83)?;
+                m.pc = 0x0b83;
+            },
+            0x0b83 => {
+                // 0b83  call 0a59h
+                m.push(0x0b86)?;
+                m.pc = 0x0a59;
+                m.tick(17, 0x0a59)?;
+                m.call(0x0b86)?;
+                // 0b86  jnz 0b83h
+                m.pc = if m.flags & ZERO == 0 { 0x0b83 } else { 0x0b89 };
+                m.tick(10, m.pc)?;
+            },
+            0x0b89 => {
+                // 0b89  xra a
+                let value = m.a;
+                m.a ^= value;
+                m.flags = SZP[m.a as usize] | if m.a > 0xf { AUX_CARRY } else { 0 };
+                m.tick(4, 0x0b8a)?;
+                // 0b8a  sta 20c1h
+                m.write(0x20c1, m.a)?;
+                m.tick(13, 0x0b8d)?;
+                // 0b8d  call 0ab1h
+                m.push(0x0b90)?;
+                m.pc = 0x0ab1;
+                m.tick(17, 0x0ab1)?;
+                m.call(0x0b90)?;
+                // 0b90  call 1988h
+                m.push(0x0b93)?;
+                m.pc = 0x1988;
+                m.tick(17, 0x1988)?;
+                m.call(0x0b93)?;
+                // 0b93  mvi c,0ch
+                m.c = 0x0c;
+                m.tick(7, 0x0b95)?;
+                // 0b95  lxi h,2c11h
+                m.set_hl(0x2c11);
+                m.tick(10, 0x0b98)?;
+                // 0b98  lxi d,1f90h
+                m.set_de(0x1f90);
+                m.tick(10, 0x0b9b)?;
+                // 0b9b  call 08f3h
+                m.push(0x0b9e)?;
+                m.pc = 0x08f3;
+                m.tick(17, 0x08f3)?;
+                m.call(0x0b9e)?;
+                // 0b9e  lda 20ech
+                m.a = m.read(0x20ec)?;
+                m.tick(13, 0x0ba1)?;
+                // 0ba1  cpi 00h
+                let value: u8 = 0x00;
+                let result = (m.a as u16).wrapping_sub(value as u16);
+                m.flags = SZP[result as u8 as usize] | (result > 0xff) as u8 | if (m.a & 0xf).wrapping_sub(value & 0xf) > 0xf { AUX_CARRY } else { 0 };
+                m.tick(7, 0x0ba3)?;
+                // 0ba3  jnz 0baeh
+                m.pc = if m.flags & ZERO == 0 { 0x0bae } else { 0x0ba6 };
+                m.tick(10, m.pc)?;
+            },
+            0x0ba6 => {
+                // 0ba6  lxi h,3311h
+                m.set_hl(0x3311);
+                m.tick(10, 0x0ba9)?;
+                // 0ba9  mvi a,02h
+                m.a = 0x02;
+                m.tick(7, 0x0bab)?;
+                // 0bab  call 08ffh
+                m.push(0x0bae)?;
+                m.pc = 0x08ff;
+                m.tick(17, 0x08ff)?;
+                m.call(0x0bae)?;
+                m.pc = 0x0bae;
+            },
+            0x0bae => {
+                // 0bae  lxi b,1f9ch
+                m.set_bc(0x1f9c);
+                m.tick(10, 0x0bb1)?;
+                // 0bb1  call 1856h
+                m.push(0x0bb4)?;
+                m.pc = 0x1856;
+                m.tick(17, 0x1856)?;
+                m.call(0x0bb4)?;
+                // 0bb4  call 184ch
+                m.push(0x0bb7)?;
+                m.pc = 0x184c;
+                m.tick(17, 0x184c)?;
+                m.call(0x0bb7)?;
+                // 0bb7  in 02h
+                m.a = m.input(0x02)?;
+                m.tick(10, 0x0bb9)?;
+                // 0bb9  rlc
+                m.flags = (m.flags & !CARRY) | (m.a >> 7);
+                m.a = m.a.rotate_left(1);
+                m.tick(4, 0x0bba)?;
+                // 0bba  jc 0bc3h
+                m.pc = if m.flags & CARRY != 0 { 0x0bc3 } else { 0x0bbd };
+                m.tick(10, m.pc)?;
+            },
+            0x0bbd => {
+                // 0bbd  lxi b,1fa0h
+                m.set_bc(0x1fa0);
+                m.tick(10, 0x0bc0)?;
+                // 0bc0  call 183ah
+                m.push(0x0bc3)?;
+                m.pc = 0x183a;
+                m.tick(17, 0x183a)?;
+                m.call(0x0bc3)?;
+                m.pc = 0x0bc3;
+            },
+            0x0bc3 => {
+                // 0bc3  call 0ab6h
+                m.push(0x0bc6)?;
+                m.pc = 0x0ab6;
+                m.tick(17, 0x0ab6)?;
+                m.call(0x0bc6)?;
+                // 0bc6  lda 20ech
+                m.a = m.read(0x20ec)?;
+                m.tick(13, 0x0bc9)?;
+                // 0bc9  cpi 00h
+                let value: u8 = 0x00;
+                let result = (m.a as u16).wrapping_sub(value as u16);
+                m.flags = SZP[result as u8 as usize] | (result > 0xff) as u8 | if (m.a & 0xf).wrapping_sub(value & 0xf) > 0xf { AUX_CARRY } else { 0 };
+                m.tick(7, 0x0bcb)?;
+                // 0bcb  jnz 0bdah
+                m.pc = if m.flags & ZERO == 0 { 0x0bda } else { 0x0bce };
+                m.tick(10, m.pc)?;
+            },
+            0x0bce => {
+                // 0bce  lxi d,1fd5h
+                m.set_de(0x1fd5);
+                m.tick(10, 0x0bd1)?;
+                // 0bd1  call 0ae2h
+                m.push(0x0bd4)?;
+                m.pc = 0x0ae2;
+                m.tick(17, 0x0ae2)?;
+                m.call(0x0bd4)?;
+                // 0bd4  call 0a80h
+                m.push(0x0bd7)?;
+                m.pc = 0x0a80;
+                m.tick(17, 0x0a80)?;
+                m.call(0x0bd7)?;
+                // 0bd7  call 189eh
+                m.push(0x0bda)?;
+                m.pc = 0x189e;
+                m.tick(17, 0x189e)?;
+                m.call(0x0bda)?;
+                m.pc = 0x0bda;
+            },
+            0x0bda => {
+                // 0bda  lxi h,20ech
+                m.set_hl(0x20ec);
+                m.tick(10, 0x0bdd)?;
+                // 0bdd  mov a,m
+                m.a = m.read(m.hl())?;
+                m.tick(7, 0x0bde)?;
+                // 0bde  inr a
+                let value = m.a;
+                let value = value.wrapping_add(1);
+                m.flags = SZP[value as usize] | (m.flags & CARRY) | if value & 0xf == 0 { AUX_CARRY } else { 0 };
+                m.a = value;
+                m.tick(5, 0x0bdf)?;
+                // 0bdf  ani 01h
+                let value: u8 = 0x01;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(7, 0x0be1)?;
+                // 0be1  mov m,a
+                m.write(m.hl(), m.a)?;
+                m.tick(7, 0x0be2)?;
+                // 0be2  call 09d6h
+                m.push(0x0be5)?;
+                m.pc = 0x09d6;
+                m.tick(17, 0x09d6)?;
+                m.call(0x0be5)?;
+                // 0be5  jmp 18dfh
+                m.pc = 0x18df;
+                m.tick(10, 0x18df)?;
+            },
+            0x0be8 => {
+                // 0be8  lxi d,1dabh
+                m.set_de(0x1dab);
+                m.tick(10, 0x0beb)?;
+                // 0beb  call 0a93h
+                m.push(0x0bee)?;
+                m.pc = 0x0a93;
+                m.tick(17, 0x0a93)?;
+                m.call(0x0bee)?;
+                // 0bee  jmp 0b0bh
+                m.pc = 0x0b0b;
+                m.tick(10, 0x0b0b)?;
+            },
+            0x18d4 => {
+                // 18d4  lxi sp,2400h
+                m.sp = 0x2400;
+                m.tick(10, 0x18d7)?;
+                // 18d7  mvi b,00h
+                m.b = 0x00;
+                m.tick(7, 0x18d9)?;
+                // 18d9  call 01e6h
+                m.push(0x18dc)?;
+                m.pc = 0x01e6;
+                m.tick(17, 0x01e6)?;
+                m.call(0x18dc)?;
+                // 18dc  call 1956h
+                m.push(0x18df)?;
+                m.pc = 0x1956;
+                m.tick(17, 0x1956)?;
+                m.call(0x18df)?;
+                m.pc = 0x18df;
+            },
+            0x18df => {
+                // 18df  mvi a,08h
+                m.a = 0x08;
+                m.tick(7, 0x18e1)?;
+                // 18e1  sta 20cfh
+                m.write(0x20cf, m.a)?;
+                m.tick(13, 0x18e4)?;
+                // 18e4  jmp 0aeah
+                m.pc = 0x0aea;
+                m.tick(10, 0x0aea)?;
+            },
+            _ => return Ok(()),
+        }
+    }
+}
+
+fn sub_0008(m: &mut Machine) -> Result<(), Stop> {
+    loop {
+        match m.pc {
+            0x0008 => {
+                // 0008  push psw
+                m.push((m.a as u16) << 8 | m.flags as u16)?;
+                m.tick(11, 0x0009)?;
+                // 0009  push b
+                m.push(m.bc())?;
+                m.tick(11, 0x000a)?;
+                // 000a  push d
+                m.push(m.de())?;
+                m.tick(11, 0x000b)?;
+                // 000b  push h
+                m.push(m.hl())?;
+                m.tick(11, 0x000c)?;
+                // 000c  jmp 008ch
+                m.pc = 0x008c;
+                m.tick(10, 0x008c)?;
+            },
+            0x0082 => {
+                // 0082  pop h
+                let value = m.pop()?;
+                m.set_hl(value);
+                m.tick(10, 0x0083)?;
+                // 0083  pop d
+                let value = m.pop()?;
+                m.set_de(value);
+                m.tick(10, 0x0084)?;
+                // 0084  pop b
+                let value = m.pop()?;
+                m.set_bc(value);
+                m.tick(10, 0x0085)?;
+                // 0085  pop psw
+                let value = m.pop()?;
+                m.a = (value >> 8) as u8;
+                m.flags = value as u8 & (SIGN | ZERO | AUX_CARRY | PARITY | CARRY);
+                m.tick(10, 0x0086)?;
+                m.pc = 0x0086;
+            },
+            // 0086  ei
+            0x0086 => m.interpret()?,
+            0x008c => {
+                // 008c  xra a
+                let value = m.a;
+                m.a ^= value;
+                m.flags = SZP[m.a as usize] | if m.a > 0xf { AUX_CARRY } else { 0 };
+                m.tick(4, 0x008d)?;
+                // 008d  sta 2072h
+                m.write(0x2072, m.a)?;
+                m.tick(13, 0x0090)?;
+                // 0090  lda 20e9h
+                m.a = m.read(0x20e9)?;
+                m.tick(13, 0x0093)?;
+                // 0093  ana a
+                let value = m.a;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(4, 0x0094)?;
+                // 0094  jz 0082h
+                m.pc = if m.flags & ZERO != 0 { 0x0082 } else { 0x0097 };
+                m.tick(10, m.pc)?;
+            },
+            0x0097 => {
+                // 0097  lda 20efh
+                m.a = m.read(0x20ef)?;
+                m.tick(13, 0x009a)?;
+                // 009a  ana a
+                let value = m.a;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(4, 0x009b)?;
+                // 009b  jnz 00a5h
+                m.pc = if m.flags & ZERO == 0 { 0x00a5 } else { 0x009e };
+                m.tick(10, m.pc)?;
+            },
+            0x009e => {
+                // 009e  lda 20c1h
+                m.a = m.read(0x20c1)?;
+                m.tick(13, 0x00a1)?;
+                // 00a1  rrc
+                m.flags = (m.flags & !CARRY) | (m.a & 1);
+                m.a = m.a.rotate_right(1);
+                m.tick(4, 0x00a2)?;
+                // 00a2  jnc 0082h
+                m.pc = if m.flags & CARRY == 0 { 0x0082 } else { 0x00a5 };
+                m.tick(10, m.pc)?;
+            },
+            0x00a5 => {
+                // 00a5  lxi h,2020h
+                m.set_hl(0x2020);
+                m.tick(10, 0x00a8)?;
+                // 00a8  call 024bh
+                m.push(0x00ab)?;
+                m.pc = 0x024b;
+                m.tick(17, 0x024b)?;
+                m.call(0x00ab)?;
+                // 00ab  call 0141h
+                m.push(0x00ae)?;
+                m.pc = 0x0141;
+                m.tick(17, 0x0141)?;
+                m.call(0x00ae)?;
+                // 00ae  jmp 0082h
+                m.pc = 0x0082;
+                m.tick(10, 0x0082)?;
+            },
+            _ => return Ok(()),
+        }
+    }
+}
+
+fn sub_0010(m: &mut Machine) -> Result<(), Stop> {
+    loop {
+        match m.pc {
+            0x0010 => {
+                // 0010  push psw
+                m.push((m.a as u16) << 8 | m.flags as u16)?;
+                m.tick(11, 0x0011)?;
+                // 0011  push b
+                m.push(m.bc())?;
+                m.tick(11, 0x0012)?;
+                // 0012  push d
+                m.push(m.de())?;
+                m.tick(11, 0x0013)?;
+                // 0013  push h
+                m.push(m.hl())?;
+                m.tick(11, 0x0014)?;
+                // 0014  mvi a,80h
+                m.a = 0x80;
+                m.tick(7, 0x0016)?;
+                // 0016  sta 2072h
+                m.write(0x2072, m.a)?;
+                m.tick(13, 0x0019)?;
+                // 0019  lxi h,20c0h
+                m.set_hl(0x20c0);
+                m.tick(10, 0x001c)?;
+                // 001c  dcr m
+                let value = m.read(m.hl())?;
+                let value = value.wrapping_sub(1);
+                m.flags = SZP[value as usize] | (m.flags & CARRY) | if value & 0xf != 0xf { AUX_CARRY } else { 0 };
+                m.write(m.hl(), value)?;
+                m.tick(10, 0x001d)?;
+                // 001d  call 17cdh
+                m.push(0x0020)?;
+                m.pc = 0x17cd;
+                m.tick(17, 0x17cd)?;
+                m.call(0x0020)?;
+                // 0020  in 01h
+                m.a = m.input(0x01)?;
+                m.tick(10, 0x0022)?;
+                // 0022  rrc
+                m.flags = (m.flags & !CARRY) | (m.a & 1);
+                m.a = m.a.rotate_right(1);
+                m.tick(4, 0x0023)?;
+                // 0023  jc 0067h
+                m.pc = if m.flags & CARRY != 0 { 0x0067 } else { 0x0026 };
+                m.tick(10, m.pc)?;
+            },
+            0x0026 => {
+                // 0026  lda 20eah
+                m.a = m.read(0x20ea)?;
+                m.tick(13, 0x0029)?;
+                // 0029  ana a
+                let value = m.a;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(4, 0x002a)?;
+                // 002a  jz 0042h
+                m.pc = if m.flags & ZERO != 0 { 0x0042 } else { 0x002d };
+                m.tick(10, m.pc)?;
+            },
+            0x002d => {
+                // 002d  lda 20ebh
+                m.a = m.read(0x20eb)?;
+                m.tick(13, 0x0030)?;
+                // 0030  cpi 99h
+                let value: u8 = 0x99;
+                let result = (m.a as u16).wrapping_sub(value as u16);
+                m.flags = SZP[result as u8 as usize] | (result > 0xff) as u8 | if (m.a & 0xf).wrapping_sub(value & 0xf) > 0xf { AUX_CARRY } else { 0 };
+                m.tick(7, 0x0032)?;
+                // 0032  jz 003eh
+                m.pc = if m.flags & ZERO != 0 { 0x003e } else { 0x0035 };
+                m.tick(10, m.pc)?;
+            },
+            0x0035 => {
+                // 0035  adi 01h
+                let value: u8 = 0x01;
+                let result = m.a as u16 + value as u16;
+                m.flags = SZP[result as u8 as usize] | (result >> 8) as u8 | if (m.a & 0xf) + (value & 0xf) > 0xf { AUX_CARRY } else { 0 };
+                m.a = result as u8;
+                m.tick(7, 0x0037)?;
+                // 0037  daa
+                m.daa();
+                m.tick(4, 0x0038)?;
+                // 0038  sta 20ebh
+                m.write(0x20eb, m.a)?;
+                m.tick(13, 0x003b)?;
+                // 003b  call 1947h
+                m.push(0x003e)?;
+                m.pc = 0x1947;
+                m.tick(17, 0x1947)?;
+                m.call(0x003e)?;
+                m.pc = 0x003e;
+            },
+            0x003e => {
+                // 003e  xra a
+                let value = m.a;
+                m.a ^= value;
+                m.flags = SZP[m.a as usize] | if m.a > 0xf { AUX_CARRY } else { 0 };
+                m.tick(4, 0x003f)?;
+                m.pc = 0x003f;
+            },
+            0x003f => {
+                // 003f  sta 20eah
+                m.write(0x20ea, m.a)?;
+                m.tick(13, 0x0042)?;
+                m.pc = 0x0042;
+            },
+            0x0042 => {
+                // 0042  lda 20e9h
+                m.a = m.read(0x20e9)?;
+                m.tick(13, 0x0045)?;
+                // 0045  ana a
+                let value = m.a;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(4, 0x0046)?;
+                // 0046  jz 0082h
+                m.pc = if m.flags & ZERO != 0 { 0x0082 } else { 0x0049 };
+                m.tick(10, m.pc)?;
+            },
+            0x0049 => {
+                // 0049  lda 20efh
+                m.a = m.read(0x20ef)?;
+                m.tick(13, 0x004c)?;
+                // 004c  ana a
+                let value = m.a;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(4, 0x004d)?;
+                // 004d  jnz 006fh
+                m.pc = if m.flags & ZERO == 0 { 0x006f } else { 0x0050 };
+                m.tick(10, m.pc)?;
+            },
+            0x0050 => {
+                // 0050  lda 20ebh
+                m.a = m.read(0x20eb)?;
+                m.tick(13, 0x0053)?;
+                // 0053  ana a
+                let value = m.a;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(4, 0x0054)?;
+                // 0054  jnz 005dh
+                m.pc = if m.flags & ZERO == 0 { 0x005d } else { 0x0057 };
+                m.tick(10, m.pc)?;
+            },
+            0x0057 => {
+                // 0057  call 0abfh
+                m.push(0x005a)?;
+                m.pc = 0x0abf;
+                m.tick(17, 0x0abf)?;
+                m.call(0x005a)?;
+                // 005a  jmp 0082h
+                m.pc = 0x0082;
+                m.tick(10, 0x0082)?;
+            },
+            0x005d => {
+                // 005d  lda 2093h
+                m.a = m.read(0x2093)?;
+                m.tick(13, 0x0060)?;
+                // 0060  ana a
+                let value = m.a;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(4, 0x0061)?;
+                // 0061  jnz 0082h
+                m.pc = if m.flags & ZERO == 0 { 0x0082 } else { 0x0064 };
+                m.tick(10, m.pc)?;
+            },
+            0x0064 => {
+                // 0064  jmp 0765h
+                m.pc = 0x0765;
+                m.tick(10, 0x0765)?;
+            },
+            0x0067 => {
+                // 0067  mvi a,01h
+                m.a = 0x01;
+                m.tick(7, 0x0069)?;
+                // 0069  sta 20eah
+                m.write(0x20ea, m.a)?;
+                m.tick(13, 0x006c)?;
+                // 006c  jmp 003fh
+                m.pc = 0x003f;
+                m.tick(10, 0x003f)?;
+            },
+            0x006f => {
+                // 006f  call 1740h
+                m.push(0x0072)?;
+                m.pc = 0x1740;
+                m.tick(17, 0x1740)?;
+                m.call(0x0072)?;
+                // 0072  lda 2032h
+                m.a = m.read(0x2032)?;
+                m.tick(13, 0x0075)?;
+                // 0075  sta 2080h
+                m.write(0x2080, m.a)?;
+                m.tick(13, 0x0078)?;
+                // 0078  call 0100h
+                m.push(0x007b)?;
+                m.pc = 0x0100;
+                m.tick(17, 0x0100)?;
+                m.call(0x007b)?;
+                // 007b  call 0248h
+                m.push(0x007e)?;
+                m.pc = 0x0248;
+                m.tick(17, 0x0248)?;
+                m.call(0x007e)?;
+                // 007e  call 0913h
+                m.push(0x0081)?;
+                m.pc = 0x0913;
+                m.tick(17, 0x0913)?;
+                m.call(0x0081)?;
+                // 0081  nop
+                m.tick(4, 0x0082)?;
+                m.pc = 0x0082;
+            },
+            0x0082 => {
+                // 0082  pop h
+                let value = m.pop()?;
+                m.set_hl(value);
+                m.tick(10, 0x0083)?;
+                // 0083  pop d
+                let value = m.pop()?;
+                m.set_de(value);
+                m.tick(10, 0x0084)?;
+                // 0084  pop b
+                let value = m.pop()?;
+                m.set_bc(value);
+                m.tick(10, 0x0085)?;
+                // 0085  pop psw
+                let value = m.pop()?;
+                m.a = (value >> 8) as u8;
+                m.flags = value as u8 & (SIGN | ZERO | AUX_CARRY | PARITY | CARRY);
+                m.tick(10, 0x0086)?;
+                m.pc = 0x0086;
+            },
+            // 0086  ei
+            0x0086 => m.interpret()?,
+            0x0765 => {
+                // 0765  mvi a,01h
+                m.a = 0x01;
+                m.tick(7, 0x0767)?;
+                // 0767  sta 2093h
+                m.write(0x2093, m.a)?;
+                m.tick(13, 0x076a)?;
+                // 076a  lxi sp,2400h
+                m.sp = 0x2400;
+                m.tick(10, 0x076d)?;
+                m.pc = 0x076d;
+            },
+            // 076d  ei
+            0x076d => m.interpret()?,
+            0x0771 => {
+                // 0771  call 09d6h
+                m.push(0x0774)?;
+                m.pc = 0x09d6;
+                m.tick(17, 0x09d6)?;
+                m.call(0x0774)?;
+                // 0774  lxi h,3013h
+                m.set_hl(0x3013);
+                m.tick(10, 0x0777)?;
+                // 0777  lxi d,1ff3h
+                m.set_de(0x1ff3);
+                m.tick(10, 0x077a)?;
+                // 077a  mvi c,04h
+                m.c = 0x04;
+                m.tick(7, 0x077c)?;
+                // 077c  call 08f3h
+                m.push(0x077f)?;
+                m.pc = 0x08f3;
+                m.tick(17, 0x08f3)?;
+                m.call(0x077f)?;
+                m.pc = 0x077f;
+            },
+            0x077f => {
+                // 077f  lda 20ebh
+                m.a = m.read(0x20eb)?;
+                m.tick(13, 0x0782)?;
+                // 0782  dcr a
+                let value = m.a;
+                let value = value.wrapping_sub(1);
+                m.flags = SZP[value as usize] | (m.flags & CARRY) | if value & 0xf != 0xf { AUX_CARRY } else { 0 };
+                m.a = value;
+                m.tick(5, 0x0783)?;
+                // 0783  lxi h,2810h
+                m.set_hl(0x2810);
+                m.tick(10, 0x0786)?;
+                // 0786  mvi c,14h
+                m.c = 0x14;
+                m.tick(7, 0x0788)?;
+                // 0788  jnz 0857h
+                m.pc = if m.flags & ZERO == 0 { 0x0857 } else { 0x078b };
+                m.tick(10, m.pc)?;
+            },
+            0x078b => {
+                // 078b  lxi d,1acfh
+                m.set_de(0x1acf);
+                m.tick(10, 0x078e)?;
+                // 078e  call 08f3h
+                m.push(0x0791)?;
+                m.pc = 0x08f3;
+                m.tick(17, 0x08f3)?;
+                m.call(0x0791)?;
+                // 0791  in 01h
+                m.a = m.input(0x01)?;
+                m.tick(10, 0x0793)?;
+                // 0793  ani 04h
+                let value: u8 = 0x04;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(7, 0x0795)?;
+                // 0795  jz 077fh
+                m.pc = if m.flags & ZERO != 0 { 0x077f } else { 0x0798 };
+                m.tick(10, m.pc)?;
+            },
+            0x0798 => {
+                // 0798  mvi b,99h
+                m.b = 0x99;
+                m.tick(7, 0x079a)?;
+                // 079a  xra a
+                let value = m.a;
+                m.a ^= value;
+                m.flags = SZP[m.a as usize] | if m.a > 0xf { AUX_CARRY } else { 0 };
+                m.tick(4, 0x079b)?;
+                m.pc = 0x079b;
+            },
+            0x079b => {
+                // 079b  sta 20ceh
+                m.write(0x20ce, m.a)?;
+                m.tick(13, 0x079e)?;
+                // 079e  lda 20ebh
+                m.a = m.read(0x20eb)?;
+                m.tick(13, 0x07a1)?;
+                // 07a1  add b
+                let value = m.b;
+                let result = m.a as u16 + value as u16;
+                m.flags = SZP[result as u8 as usize] | (result >> 8) as u8 | if (m.a & 0xf) + (value & 0xf) > 0xf { AUX_CARRY } else { 0 };
+                m.a = result as u8;
+                m.tick(4, 0x07a2)?;
+                // 07a2  daa
+                m.daa();
+                m.tick(4, 0x07a3)?;
+                // 07a3  sta 20ebh
+                m.write(0x20eb, m.a)?;
+                m.tick(13, 0x07a6)?;
+                // 07a6  call 1947h
+                m.push(0x07a9)?;
+                m.pc = 0x1947;
+                m.tick(17, 0x1947)?;
+                m.call(0x07a9)?;
+                // 07a9  lxi h,0000h
+                m.set_hl(0x0000);
+                m.tick(10, 0x07ac)?;
+                // 07ac  shld 20f8h
+                m.write(0x20f8, m.l)?;
+                m.write(0x20f9, m.h)?;
+                m.tick(16, 0x07af)?;
+                // 07af  shld 20fch
+                m.write(0x20fc, m.l)?;
+                m.write(0x20fd, m.h)?;
+                m.tick(16, 0x07b2)?;
+                // 07b2  call 1925h
+                m.push(0x07b5)?;
+                m.pc = 0x1925;
+                m.tick(17, 0x1925)?;
+                m.call(0x07b5)?;
+                // 07b5  call 192bh
+                m.push(0x07b8)?;
+                m.pc = 0x192b;
+                m.tick(17, 0x192b)?;
+                m.call(0x07b8)?;
+                // 07b8  call 19d7h
+                m.push(0x07bb)?;
+                m.pc = 0x19d7;
+                m.tick(17, 0x19d7)?;
+                m.call(0x07bb)?;
+                // 07bb  lxi h,0101h
+                m.set_hl(0x0101);
+                m.tick(10, 0x07be)?;
+                // 07be  mov a,h
+                m.a = m.h;
+                m.tick(5, 0x07bf)?;
+                // 07bf  sta 20efh
+                m.write(0x20ef, m.a)?;
+                m.tick(13, 0x07c2)?;
+                // 07c2  shld 20e7h
+                m.write(0x20e7, m.l)?;
+                m.write(0x20e8, m.h)?;
+                m.tick(16, 0x07c5)?;
+                // 07c5  shld 20e5h
+                m.write(0x20e5, m.l)?;
+                m.write(0x20e6, m.h)?;
+                m.tick(16, 0x07c8)?;
+                // 07c8  call 1956h
+                m.push(0x07cb)?;
+                m.pc = 0x1956;
+                m.tick(17, 0x1956)?;
+                m.call(0x07cb)?;
+                // 07cb  call 01efh
+                m.push(0x07ce)?;
+                m.pc = 0x01ef;
+                m.tick(17, 0x01ef)?;
+                m.call(0x07ce)?;
+                // 07ce  call 01f5h
+                m.push(0x07d1)?;
+                m.pc = 0x01f5;
+                m.tick(17, 0x01f5)?;
+                m.call(0x07d1)?;
+                // 07d1  call 08d1h
+                m.push(0x07d4)?;
+                m.pc = 0x08d1;
+                m.tick(17, 0x08d1)?;
+                m.call(0x07d4)?;
+                // 07d4  sta 21ffh
+                m.write(0x21ff, m.a)?;
+                m.tick(13, 0x07d7)?;
+                // 07d7  sta 22ffh
+                m.write(0x22ff, m.a)?;
+                m.tick(13, 0x07da)?;
+                // 07da  call 00d7h
+                m.push(0x07dd)?;
+                m.pc = 0x00d7;
+                m.tick(17, 0x00d7)?;
+                m.call(0x07dd)?;
+                // 07dd  xra a
+                let value = m.a;
+                m.a ^= value;
+                m.flags = SZP[m.a as usize] | if m.a > 0xf { AUX_CARRY } else { 0 };
+                m.tick(4, 0x07de)?;
+                // 07de  sta 21feh
+                m.write(0x21fe, m.a)?;
+                m.tick(13, 0x07e1)?;
+                // 07e1  sta 22feh
+                m.write(0x22fe, m.a)?;
+                m.tick(13, 0x07e4)?;
+                // 07e4  call 01c0h
+                m.push(0x07e7)?;
+                m.pc = 0x01c0;
+                m.tick(17, 0x01c0)?;
+                m.call(0x07e7)?;
+                // 07e7  call 1904h
+                m.push(0x07ea)?;
+                m.pc = 0x1904;
+                m.tick(17, 0x1904)?;
+                m.call(0x07ea)?;
+                // 07ea  lxi h,3878h
+                m.set_hl(0x3878);
+                m.tick(10, 0x07ed)?;
+                // 07ed  shld 21fch
+                m.write(0x21fc, m.l)?;
+                m.write(0x21fd, m.h)?;
+                m.tick(16, 0x07f0)?;
+                // 07f0  shld 22fch
+                m.write(0x22fc, m.l)?;
+                m.write(0x22fd, m.h)?;
+                m.tick(16, 0x07f3)?;
+                // 07f3  call 01e4h
+                m.push(0x07f6)?;
+                m.pc = 0x01e4;
+                m.tick(17, 0x01e4)?;
+                m.call(0x07f6)?;
+                // 07f6  call 1a7fh
+                m.push(0x07f9)?;
+                m.pc = 0x1a7f;
+                m.tick(17, 0x1a7f)?;
+                m.call(0x07f9)?;
+                // 07f9  call 088dh
+                m.push(0x07fc)?;
+                m.pc = 0x088d;
+                m.tick(17, 0x088d)?;
+                m.call(0x07fc)?;
+                // 07fc  call 09d6h
+                m.push(0x07ff)?;
+                m.pc = 0x09d6;
+                m.tick(17, 0x09d6)?;
+                m.call(0x07ff)?;
+                // 07ff  nop
+                m.tick(4, 0x0800)?;
+                // 0800  xra a
+                let value = m.a;
+                m.a ^= value;
+                m.flags = SZP[m.a as usize] | if m.a > 0xf { AUX_CARRY } else { 0 };
+                m.tick(4, 0x0801)?;
+                // 0801  sta 20c1h
+                m.write(0x20c1, m.a)?;
+                m.tick(13, 0x0804)?;
+                m.pc = 0x0804;
+            },
+            0x0804 => {
+                // 0804  call 01cfh
+                m.push(0x0807)?;
+                m.pc = 0x01cf;
+                m.tick(17, 0x01cf)?;
+                m.call(0x0807)?;
+                // 0807  lda 2067h
+                m.a = m.read(0x2067)?;
+                m.tick(13, 0x080a)?;
+                // 080a  rrc
+                m.flags = (m.flags & !CARRY) | (m.a & 1);
+                m.a = m.a.rotate_right(1);
+                m.tick(4, 0x080b)?;
+                // 080b  jc 0872h
+                m.pc = if m.flags & CARRY != 0 { 0x0872 } else { 0x080e };
+                m.tick(10, m.pc)?;
+            },
+            0x080e => {
+                // 080e  call 0213h
+                m.push(0x0811)?;
+                m.pc = 0x0213;
+                m.tick(17, 0x0213)?;
+                m.call(0x0811)?;
+                // 0811  call 01cfh
+                m.push(0x0814)?;
+                m.pc = 0x01cf;
+                m.tick(17, 0x01cf)?;
+                m.call(0x0814)?;
+                m.pc = 0x0814;
+            },
+            0x0814 => {
+                // 0814  call 00b1h
+                m.push(0x0817)?;
+                m.pc = 0x00b1;
+                m.tick(17, 0x00b1)?;
+                m.call(0x0817)?;
+                // 0817  call 19d1h
+                m.push(0x081a)?;
+                m.pc = 0x19d1;
+                m.tick(17, 0x19d1)?;
+                m.call(0x081a)?;
+                // 081a  mvi b,20h
+                m.b = 0x20;
+                m.tick(7, 0x081c)?;
+                // 081c  call 18fah
+                m.push(0x081f)?;
+                m.pc = 0x18fa;
+                m.tick(17, 0x18fa)?;
+                m.call(0x081f)?;
+                m.pc = 0x081f;
+            },
+            0x081f => {
+                // 081f  call 1618h
+                m.push(0x0822)?;
+                m.pc = 0x1618;
+                m.tick(17, 0x1618)?;
+                m.call(0x0822)?;
+                // 0822  call 190ah
+                m.push(0x0825)?;
+                m.pc = 0x190a;
+                m.tick(17, 0x190a)?;
+                m.call(0x0825)?;
+                // 0825  call 15f3h
+                m.push(0x0828)?;
+                m.pc = 0x15f3;
+                m.tick(17, 0x15f3)?;
+                m.call(0x0828)?;
+                // 0828  call 0988h
+                m.push(0x082b)?;
+                m.pc = 0x0988;
+                m.tick(17, 0x0988)?;
+                m.call(0x082b)?;
+                // 082b  lda 2082h
+                m.a = m.read(0x2082)?;
+                m.tick(13, 0x082e)?;
+                // 082e  ana a
+                let value = m.a;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(4, 0x082f)?;
+                // 082f  jz 09efh
+                m.pc = if m.flags & ZERO != 0 { 0x09ef } else { 0x0832 };
+                m.tick(10, m.pc)?;
+            },
+            0x0832 => {
+                // 0832  call 170eh
+                m.push(0x0835)?;
+                m.pc = 0x170e;
+                m.tick(17, 0x170e)?;
+                m.call(0x0835)?;
+                // 0835  call 0935h
+                m.push(0x0838)?;
+                m.pc = 0x0935;
+                m.tick(17, 0x0935)?;
+                m.call(0x0838)?;
+                // 0838  call 08d8h
+                m.push(0x083b)?;
+                m.pc = 0x08d8;
+                m.tick(17, 0x08d8)?;
+                m.call(0x083b)?;
+                // 083b  call 172ch
+                m.push(0x083e)?;
+                m.pc = 0x172c;
+                m.tick(17, 0x172c)?;
+                m.call(0x083e)?;
+                // 083e  call 0a59h
+                m.push(0x0841)?;
+                m.pc = 0x0a59;
+                m.tick(17, 0x0a59)?;
+                m.call(0x0841)?;
+                // 0841  jz 0849h
+                m.pc = if m.flags & ZERO != 0 { 0x0849 } else { 0x0844 };
+                m.tick(10, m.pc)?;
+            },
+            0x0844 => {
+                // 0844  mvi b,04h
+                m.b = 0x04;
+                m.tick(7, 0x0846)?;
+                // 0846  call 18fah
+                m.push(0x0849)?;
+                m.pc = 0x18fa;
+                m.tick(17, 0x18fa)?;
+                m.call(0x0849)?;
+                m.pc = 0x0849;
+            },
+            0x0849 => {
+                // 0849  call 1775h
+                m.push(0x084c)?;
+                m.pc = 0x1775;
+                m.tick(17, 0x1775)?;
+                m.call(0x084c)?;
+                // 084c  out 06h
+                m.output(0x06, m.a)?;
+                m.tick(10, 0x084e)?;
+                // 084e  call 1804h
+                m.push(0x0851)?;
+                m.pc = 0x1804;
+                m.tick(17, 0x1804)?;
+                m.call(0x0851)?;
+                // 0851  jmp 081fh
+                m.pc = 0x081f;
+                m.tick(10, 0x081f)?;
+            },
+            0x0857 => {
+                // 0857  lxi d,1abah
+                m.set_de(0x1aba);
+                m.tick(10, 0x085a)?;
+                // 085a  call 08f3h
+                m.push(0x085d)?;
+                m.pc = 0x08f3;
+                m.tick(17, 0x08f3)?;
+                m.call(0x085d)?;
+                // 085d  mvi b,98h
+                m.b = 0x98;
+                m.tick(7, 0x085f)?;
+                // 085f  in 01h
+                m.a = m.input(0x01)?;
+                m.tick(10, 0x0861)?;
+                // 0861  rrc
+                m.flags = (m.flags & !CARRY) | (m.a & 1);
+                m.a = m.a.rotate_right(1);
+                m.tick(4, 0x0862)?;
+                // 0862  rrc
+                m.flags = (m.flags & !CARRY) | (m.a & 1);
+                m.a = m.a.rotate_right(1);
+                m.tick(4, 0x0863)?;
+                // 0863  jc 086dh
+                m.pc = if m.flags & CARRY != 0 { 0x086d } else { 0x0866 };
+                m.tick(10, m.pc)?;
+            },
+            0x0866 => {
+                // 0866  rrc
+                m.flags = (m.flags & !CARRY) | (m.a & 1);
+                m.a = m.a.rotate_right(1);
+                m.tick(4, 0x0867)?;
+                // 0867  jc 0798h
+                m.pc = if m.flags & CARRY != 0 { 0x0798 } else { 0x086a };
+                m.tick(10, m.pc)?;
+            },
+            0x086a => {
+                // 086a  jmp 077fh
+                m.pc = 0x077f;
+                m.tick(10, 0x077f)?;
+            },
+            0x086d => {
+                // 086d  mvi a,01h
+                m.a = 0x01;
+                m.tick(7, 0x086f)?;
+                // 086f  jmp 079bh
+                m.pc = 0x079b;
+                m.tick(10, 0x079b)?;
+            },
+            0x0872 => {
+                // 0872  call 021ah
+                m.push(0x0875)?;
+                m.pc = 0x021a;
+                m.tick(17, 0x021a)?;
+                m.call(0x0875)?;
+                // 0875  jmp 0814h
+                m.pc = 0x0814;
+                m.tick(10, 0x0814)?;
+            },
+            0x09ef => {
+                // 09ef  call 0a3ch
+                m.push(0x09f2)?;
+                m.pc = 0x0a3c;
+                m.tick(17, 0x0a3c)?;
+                m.call(0x09f2)?;
+                // 09f2  xra a
+                let value = m.a;
+                m.a ^= value;
+                m.flags = SZP[m.a as usize] | if m.a > 0xf { AUX_CARRY } else { 0 };
+                m.tick(4, 0x09f3)?;
+                // 09f3  sta 20e9h
+                m.write(0x20e9, m.a)?;
+                m.tick(13, 0x09f6)?;
+                // 09f6  call 09d6h
+                m.push(0x09f9)?;
+                m.pc = 0x09d6;
+                m.tick(17, 0x09d6)?;
+                m.call(0x09f9)?;
+                // 09f9  lda 2067h
+                m.a = m.read(0x2067)?;
+                m.tick(13, 0x09fc)?;
+                // 09fc  push psw
+                m.push((m.a as u16) << 8 | m.flags as u16)?;
+                m.tick(11, 0x09fd)?;
+                // 09fd  call 01e4h
+                m.push(0x0a00)?;
+                m.pc = 0x01e4;
+                m.tick(17, 0x01e4)?;
+                m.call(0x0a00)?;
+                // 0a00  pop psw
+                let value = m.pop()?;
+                m.a = (value >> 8) as u8;
+                m.flags = value as u8 & (SIGN | ZERO | AUX_CARRY | PARITY | CARRY);
+                m.tick(10, 0x0a01)?;
+                // 0a01  sta 2067h
+                m.write(0x2067, m.a)?;
+                m.tick(13, 0x0a04)?;
+                // 0a04  lda 2067h
+                m.a = m.read(0x2067)?;
+                m.tick(13, 0x0a07)?;
+                // 0a07  mov h,a
+                m.h = m.a;
+                m.tick(5, 0x0a08)?;
+                // 0a08  push h
+                m.push(m.hl())?;
+                m.tick(11, 0x0a09)?;
+                // 0a09  mvi l,0feh
+                m.l = 0xfe;
+                m.tick(7, 0x0a0b)?;
+                // 0a0b  mov a,m
+                m.a = m.read(m.hl())?;
+                m.tick(7, 0x0a0c)?;
+                // 0a0c  ani 07h
+                let value: u8 = 0x07;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(7, 0x0a0e)?;
+                // 0a0e  inr a
+                let value = m.a;
+                let value = value.wrapping_add(1);
+                m.flags = SZP[value as usize] | (m.flags & CARRY) | if value & 0xf == 0 { AUX_CARRY } else { 0 };
+                m.a = value;
+                m.tick(5, 0x0a0f)?;
+                // 0a0f  mov m,a
+                m.write(m.hl(), m.a)?;
+                m.tick(7, 0x0a10)?;
+                // 0a10  lxi h,1da2h
+                m.set_hl(0x1da2);
+                m.tick(10, 0x0a13)?;
+                m.pc = 0x0a13;
+            },
+            0x0a13 => {
+                // 0a13  inx h
+                m.set_hl(m.hl().wrapping_add(1));
+                m.tick(5, 0x0a14)?;
+                // 0a14  dcr a
+                let value = m.a;
+                let value = value.wrapping_sub(1);
+                m.flags = SZP[value as usize] | (m.flags & CARRY) | if value & 0xf != 0xf { AUX_CARRY } else { 0 };
+                m.a = value;
+                m.tick(5, 0x0a15)?;
+                // 0a15  jnz 0a13h
+                m.pc = if m.flags & ZERO == 0 { 0x0a13 } else { 0x0a18 };
+                m.tick(10, m.pc)?;
+            },
+            0x0a18 => {
+                // 0a18  mov a,m
+                m.a = m.read(m.hl())?;
+                m.tick(7, 0x0a19)?;
+                // 0a19  pop h
+                let value = m.pop()?;
+                m.set_hl(value);
+                m.tick(10, 0x0a1a)?;
+                // 0a1a  mvi l,0fch
+                m.l = 0xfc;
+                m.tick(7, 0x0a1c)?;
+                // 0a1c  mov m,a
+                m.write(m.hl(), m.a)?;
+                m.tick(7, 0x0a1d)?;
+                // 0a1d  inx h
+                m.set_hl(m.hl().wrapping_add(1));
+                m.tick(5, 0x0a1e)?;
+                // 0a1e  mvi m,38h
+                m.write(m.hl(), 0x38)?;
+                m.tick(10, 0x0a20)?;
+                // 0a20  mov a,h
+                m.a = m.h;
+                m.tick(5, 0x0a21)?;
+                // 0a21  rrc
+                m.flags = (m.flags & !CARRY) | (m.a & 1);
+                m.a = m.a.rotate_right(1);
+                m.tick(4, 0x0a22)?;
+                // 0a22  jc 0a33h
+                m.pc = if m.flags & CARRY != 0 { 0x0a33 } else { 0x0a25 };
+                m.tick(10, m.pc)?;
+            },
+            0x0a25 => {
+                // 0a25  mvi a,21h
+                m.a = 0x21;
+                m.tick(7, 0x0a27)?;
+                // 0a27  sta 2098h
+                m.write(0x2098, m.a)?;
+                m.tick(13, 0x0a2a)?;
+                // 0a2a  call 01f5h
+                m.push(0x0a2d)?;
+                m.pc = 0x01f5;
+                m.tick(17, 0x01f5)?;
+                m.call(0x0a2d)?;
+                // 0a2d  call 1904h
+                m.push(0x0a30)?;
+                m.pc = 0x1904;
+                m.tick(17, 0x1904)?;
+                m.call(0x0a30)?;
+                // 0a30  jmp 0804h
+                m.pc = 0x0804;
+                m.tick(10, 0x0804)?;
+            },
+            0x0a33 => {
+                // 0a33  call 01efh
+                m.push(0x0a36)?;
+                m.pc = 0x01ef;
+                m.tick(17, 0x01ef)?;
+                m.call(0x0a36)?;
+                // 0a36  call 01c0h
+                m.push(0x0a39)?;
+                m.pc = 0x01c0;
+                m.tick(17, 0x01c0)?;
+                m.call(0x0a39)?;
+                // 0a39  jmp 0804h
+                m.pc = 0x0804;
+                m.tick(10, 0x0804)?;
+            },
+            _ => return Ok(()),
+        }
+    }
+}
+
+fn sub_0018(m: &mut Machine) -> Result<(), Stop> {
+    loop {
+        match m.pc {
+            0x0018 => {
+                // 0018  db 20h
+                m.tick(4, 0x0019)?;
+                // 0019  lxi h,20c0h
+                m.set_hl(0x20c0);
+                m.tick(10, 0x001c)?;
+                // 001c  dcr m
+                let value = m.read(m.hl())?;
+                let value = value.wrapping_sub(1);
+                m.flags = SZP[value as usize] | (m.flags & CARRY) | if value & 0xf != 0xf { AUX_CARRY } else { 0 };
+                m.write(m.hl(), value)?;
+                m.tick(10, 0x001d)?;
+                // 001d  call 17cdh
+                m.push(0x0020)?;
+                m.pc = 0x17cd;
+                m.tick(17, 0x17cd)?;
+                m.call(0x0020)?;
+                // 0020  in 01h
+                m.a = m.input(0x01)?;
+                m.tick(10, 0x0022)?;
+                // 0022  rrc
+                m.flags = (m.flags & !CARRY) | (m.a & 1);
+                m.a = m.a.rotate_right(1);
+                m.tick(4, 0x0023)?;
+                // 0023  jc 0067h
+                m.pc = if m.flags & CARRY != 0 { 0x0067 } else { 0x0026 };
+                m.tick(10, m.pc)?;
+            },
+            0x0026 => {
+                // 0026  lda 20eah
+                m.a = m.read(0x20ea)?;
+                m.tick(13, 0x0029)?;
+                // 0029  ana a
+                let value = m.a;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(4, 0x002a)?;
+                // 002a  jz 0042h
+                m.pc = if m.flags & ZERO != 0 { 0x0042 } else { 0x002d };
+                m.tick(10, m.pc)?;
+            },
+            0x002d => {
+                // 002d  lda 20ebh
+                m.a = m.read(0x20eb)?;
+                m.tick(13, 0x0030)?;
+                // 0030  cpi 99h
+                let value: u8 = 0x99;
+                let result = (m.a as u16).wrapping_sub(value as u16);
+                m.flags = SZP[result as u8 as usize] | (result > 0xff) as u8 | if (m.a & 0xf).wrapping_sub(value & 0xf) > 0xf { AUX_CARRY } else { 0 };
+                m.tick(7, 0x0032)?;
+                // 0032  jz 003eh
+                m.pc = if m.flags & ZERO != 0 { 0x003e } else { 0x0035 };
+                m.tick(10, m.pc)?;
+            },
+            0x0035 => {
+                // 0035  adi 01h
+                let value: u8 = 0x01;
+                let result = m.a as u16 + value as u16;
+                m.flags = SZP[result as u8 as usize] | (result >> 8) as u8 | if (m.a & 0xf) + (value & 0xf) > 0xf { AUX_CARRY } else { 0 };
+                m.a = result as u8;
+                m.tick(7, 0x0037)?;
+                // 0037  daa
+                m.daa();
+                m.tick(4, 0x0038)?;
+                // 0038  sta 20ebh
+                m.write(0x20eb, m.a)?;
+                m.tick(13, 0x003b)?;
+                // 003b  call 1947h
+                m.push(0x003e)?;
+                m.pc = 0x1947;
+                m.tick(17, 0x1947)?;
+                m.call(0x003e)?;
+                m.pc = 0x003e;
+            },
+            0x003e => {
+                // 003e  xra a
+                let value = m.a;
+                m.a ^= value;
+                m.flags = SZP[m.a as usize] | if m.a > 0xf { AUX_CARRY } else { 0 };
+                m.tick(4, 0x003f)?;
+                m.pc = 0x003f;
+            },
+            0x003f => {
+                // 003f  sta 20eah
+                m.write(0x20ea, m.a)?;
+                m.tick(13, 0x0042)?;
+                m.pc = 0x0042;
+            },
+            0x0042 => {
+                // 0042  lda 20e9h
+                m.a = m.read(0x20e9)?;
+                m.tick(13, 0x0045)?;
+                // 0045  ana a
+                let value = m.a;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(4, 0x0046)?;
+                // 0046  jz 0082h
+                m.pc = if m.flags & ZERO != 0 { 0x0082 } else { 0x0049 };
+                m.tick(10, m.pc)?;
+            },
+            0x0049 => {
+                // 0049  lda 20efh
+                m.a = m.read(0x20ef)?;
+                m.tick(13, 0x004c)?;
+                // 004c  ana a
+                let value = m.a;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(4, 0x004d)?;
+                // 004d  jnz 006fh
+                m.pc = if m.flags & ZERO == 0 { 0x006f } else { 0x0050 };
+                m.tick(10, m.pc)?;
+            },
+            0x0050 => {
+                // 0050  lda 20ebh
+                m.a = m.read(0x20eb)?;
+                m.tick(13, 0x0053)?;
+                // 0053  ana a
+                let value = m.a;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(4, 0x0054)?;
+                // 0054  jnz 005dh
+                m.pc = if m.flags & ZERO == 0 { 0x005d } else { 0x0057 };
+                m.tick(10, m.pc)?;
+            },
+            0x0057 => {
+                // 0057  call 0abfh
+                m.push(0x005a)?;
+                m.pc = 0x0abf;
+                m.tick(17, 0x0abf)?;
+                m.call(0x005a)?;
+                // 005a  jmp 0082h
+                m.pc = 0x0082;
+                m.tick(10, 0x0082)?;
+            },
+            0x005d => {
+                // 005d  lda 2093h
+                m.a = m.read(0x2093)?;
+                m.tick(13, 0x0060)?;
+                // 0060  ana a
+                let value = m.a;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(4, 0x0061)?;
+                // 0061  jnz 0082h
+                m.pc = if m.flags & ZERO == 0 { 0x0082 } else { 0x0064 };
+                m.tick(10, m.pc)?;
+            },
+            0x0064 => {
+                // 0064  jmp 0765h
+                m.pc = 0x0765;
+                m.tick(10, 0x0765)?;
+            },
+            0x0067 => {
+                // 0067  mvi a,01h
+                m.a = 0x01;
+                m.tick(7, 0x0069)?;
+                // 0069  sta 20eah
+                m.write(0x20ea, m.a)?;
+                m.tick(13, 0x006c)?;
+                // 006c  jmp 003fh
+                m.pc = 0x003f;
+                m.tick(10, 0x003f)?;
+            },
+            0x006f => {
+                // 006f  call 1740h
+                m.push(0x0072)?;
+                m.pc = 0x1740;
+                m.tick(17, 0x1740)?;
+                m.call(0x0072)?;
+                // 0072  lda 2032h
+                m.a = m.read(0x2032)?;
+                m.tick(13, 0x0075)?;
+                // 0075  sta 2080h
+                m.write(0x2080, m.a)?;
+                m.tick(13, 0x0078)?;
+                // 0078  call 0100h
+                m.push(0x007b)?;
+                m.pc = 0x0100;
+                m.tick(17, 0x0100)?;
+                m.call(0x007b)?;
+                // 007b  call 0248h
+                m.push(0x007e)?;
+                m.pc = 0x0248;
+                m.tick(17, 0x0248)?;
+                m.call(0x007e)?;
+                // 007e  call 0913h
+                m.push(0x0081)?;
+                m.pc = 0x0913;
+                m.tick(17, 0x0913)?;
+                m.call(0x0081)?;
+                // 0081  nop
+                m.tick(4, 0x0082)?;
+                m.pc = 0x0082;
+            },
+            0x0082 => {
+                // 0082  pop h
+                let value = m.pop()?;
+                m.set_hl(value);
+                m.tick(10, 0x0083)?;
+                // 0083  pop d
+                let value = m.pop()?;
+                m.set_de(value);
+                m.tick(10, 0x0084)?;
+                // 0084  pop b
+                let value = m.pop()?;
+                m.set_bc(value);
+                m.tick(10, 0x0085)?;
+                // 0085  pop psw
+                let value = m.pop()?;
+                m.a = (value >> 8) as u8;
+                m.flags = value as u8 & (SIGN | ZERO | AUX_CARRY | PARITY | CARRY);
+                m.tick(10, 0x0086)?;
+                m.pc = 0x0086;
+            },
+            // 0086  ei
+            0x0086 => m.interpret()?,
+            0x0765 => {
+                // 0765  mvi a,01h
+                m.a = 0x01;
+                m.tick(7, 0x0767)?;
+                // 0767  sta 2093h
+                m.write(0x2093, m.a)?;
+                m.tick(13, 0x076a)?;
+                // 076a  lxi sp,2400h
+                m.sp = 0x2400;
+                m.tick(10, 0x076d)?;
+                m.pc = 0x076d;
+            },
+            // 076d  ei
+            0x076d => m.interpret()?,
+            0x0771 => {
+                // 0771  call 09d6h
+                m.push(0x0774)?;
+                m.pc = 0x09d6;
+                m.tick(17, 0x09d6)?;
+                m.call(0x0774)?;
+                // 0774  lxi h,3013h
+                m.set_hl(0x3013);
+                m.tick(10, 0x0777)?;
+                // 0777  lxi d,1ff3h
+                m.set_de(0x1ff3);
+                m.tick(10, 0x077a)?;
+                // 077a  mvi c,04h
+                m.c = 0x04;
+                m.tick(7, 0x077c)?;
+                // 077c  call 08f3h
+                m.push(0x077f)?;
+                m.pc = 0x08f3;
+                m.tick(17, 0x08f3)?;
+                m.call(0x077f)?;
+                m.pc = 0x077f;
+            },
+            0x077f => {
+                // 077f  lda 20ebh
+                m.a = m.read(0x20eb)?;
+                m.tick(13, 0x0782)?;
+                // 0782  dcr a
+                let value = m.a;
+                let value = value.wrapping_sub(1);
+                m.flags = SZP[value as usize] | (m.flags & CARRY) | if value & 0xf != 0xf { AUX_CARRY } else { 0 };
+                m.a = value;
+                m.tick(5, 0x0783)?;
+                // 0783  lxi h,2810h
+                m.set_hl(0x2810);
+                m.tick(10, 0x0786)?;
+                // 0786  mvi c,14h
+                m.c = 0x14;
+                m.tick(7, 0x0788)?;
+                // 0788  jnz 0857h
+                m.pc = if m.flags & ZERO == 0 { 0x0857 } else { 0x078b };
+                m.tick(10, m.pc)?;
+            },
+            0x078b => {
+                // 078b  lxi d,1acfh
+                m.set_de(0x1acf);
+                m.tick(10, 0x078e)?;
+                // 078e  call 08f3h
+                m.push(0x0791)?;
+                m.pc = 0x08f3;
+                m.tick(17, 0x08f3)?;
+                m.call(0x0791)?;
+                // 0791  in 01h
+                m.a = m.input(0x01)?;
+                m.tick(10, 0x0793)?;
+                // 0793  ani 04h
+                let value: u8 = 0x04;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(7, 0x0795)?;
+                // 0795  jz 077fh
+                m.pc = if m.flags & ZERO != 0 { 0x077f } else { 0x0798 };
+                m.tick(10, m.pc)?;
+            },
+            0x0798 => {
+                // 0798  mvi b,99h
+                m.b = 0x99;
+                m.tick(7, 0x079a)?;
+                // 079a  xra a
+                let value = m.a;
+                m.a ^= value;
+                m.flags = SZP[m.a as usize] | if m.a > 0xf { AUX_CARRY } else { 0 };
+                m.tick(4, 0x079b)?;
+                m.pc = 0x079b;
+            },
+            0x079b => {
+                // 079b  sta 20ceh
+                m.write(0x20ce, m.a)?;
+                m.tick(13, 0x079e)?;
+                // 079e  lda 20ebh
+                m.a = m.read(0x20eb)?;
+                m.tick(13, 0x07a1)?;
+                // 07a1  add b
+                let value = m.b;
+                let result = m.a as u16 + value as u16;
+                m.flags = SZP[result as u8 as usize] | (result >> 8) as u8 | if (m.a & 0xf) + (value & 0xf) > 0xf { AUX_CARRY } else { 0 };
+                m.a = result as u8;
+                m.tick(4, 0x07a2)?;
+                // 07a2  daa
+                m.daa();
+                m.tick(4, 0x07a3)?;
+                // 07a3  sta 20ebh
+                m.write(0x20eb, m.a)?;
+                m.tick(13, 0x07a6)?;
+                // 07a6  call 1947h
+                m.push(0x07a9)?;
+                m.pc = 0x1947;
+                m.tick(17, 0x1947)?;
+                m.call(0x07a9)?;
+                // 07a9  lxi h,0000h
+                m.set_hl(0x0000);
+                m.tick(10, 0x07ac)?;
+                // 07ac  shld 20f8h
+                m.write(0x20f8, m.l)?;
+                m.write(0x20f9, m.h)?;
+                m.tick(16, 0x07af)?;
+                // 07af  shld 20fch
+                m.write(0x20fc, m.l)?;
+                m.write(0x20fd, m.h)?;
+                m.tick(16, 0x07b2)?;
+                // 07b2  call 1925h
+                m.push(0x07b5)?;
+                m.pc = 0x1925;
+                m.tick(17, 0x1925)?;
+                m.call(0x07b5)?;
+                // 07b5  call 192bh
+                m.push(0x07b8)?;
+                m.pc = 0x192b;
+                m.tick(17, 0x192b)?;
+                m.call(0x07b8)?;
+                // 07b8  call 19d7h
+                m.push(0x07bb)?;
+                m.pc = 0x19d7;
+                m.tick(17, 0x19d7)?;
+                m.call(0x07bb)?;
+                // 07bb  lxi h,0101h
+                m.set_hl(0x0101);
+                m.tick(10, 0x07be)?;
+                // 07be  mov a,h
+                m.a = m.h;
+                m.tick(5, 0x07bf)?;
+                // 07bf  sta 20efh
+                m.write(0x20ef, m.a)?;
+                m.tick(13, 0x07c2)?;
+                // 07c2  shld 20e7h
+                m.write(0x20e7, m.l)?;
+                m.write(0x20e8, m.h)?;
+                m.tick(16, 0x07c5)?;
+                // 07c5  shld 20e5h
+                m.write(0x20e5, m.l)?;
+                m.write(0x20e6, m.h)?;
+                m.tick(16, 0x07c8)?;
+                // 07c8  call 1956h
+                m.push(0x07cb)?;
+                m.pc = 0x1956;
+                m.tick(17, 0x1956)?;
+                m.call(0x07cb)?;
+                // 07cb  call 01efh
+                m.push(0x07ce)?;
+                m.pc = 0x01ef;
+                m.tick(17, 0x01ef)?;
+                m.call(0x07ce)?;
+                // 07ce  call 01f5h
+                m.push(0x07d1)?;
+                m.pc = 0x01f5;
+                m.tick(17, 0x01f5)?;
+                m.call(0x07d1)?;
+                // 07d1  call 08d1h
+                m.push(0x07d4)?;
+                m.pc = 0x08d1;
+                m.tick(17, 0x08d1)?;
+                m.call(0x07d4)?;
+                // 07d4  sta 21ffh
+                m.write(0x21ff, m.a)?;
+                m.tick(13, 0x07d7)?;
+                // 07d7  sta 22ffh
+                m.write(0x22ff, m.a)?;
+                m.tick(13, 0x07da)?;
+                // 07da  call 00d7h
+                m.push(0x07dd)?;
+                m.pc = 0x00d7;
+                m.tick(17, 0x00d7)?;
+                m.call(0x07dd)?;
+                // 07dd  xra a
+                let value = m.a;
+                m.a ^= value;
+                m.flags = SZP[m.a as usize] | if m.a > 0xf { AUX_CARRY } else { 0 };
+                m.tick(4, 0x07de)?;
+                // 07de  sta 21feh
+                m.write(0x21fe, m.a)?;
+                m.tick(13, 0x07e1)?;
+                // 07e1  sta 22feh
+                m.write(0x22fe, m.a)?;
+                m.tick(13, 0x07e4)?;
+                // 07e4  call 01c0h
+                m.push(0x07e7)?;
+                m.pc = 0x01c0;
+                m.tick(17, 0x01c0)?;
+                m.call(0x07e7)?;
+                // 07e7  call 1904h
+                m.push(0x07ea)?;
+                m.pc = 0x1904;
+                m.tick(17, 0x1904)?;
+                m.call(0x07ea)?;
+                // 07ea  lxi h,3878h
+                m.set_hl(0x3878);
+                m.tick(10, 0x07ed)?;
+                // 07ed  shld 21fch
+                m.write(0x21fc, m.l)?;
+                m.write(0x21fd, m.h)?;
+                m.tick(16, 0x07f0)?;
+                // 07f0  shld 22fch
+                m.write(0x22fc, m.l)?;
+                m.write(0x22fd, m.h)?;
+                m.tick(16, 0x07f3)?;
+                // 07f3  call 01e4h
+                m.push(0x07f6)?;
+                m.pc = 0x01e4;
+                m.tick(17, 0x01e4)?;
+                m.call(0x07f6)?;
+                // 07f6  call 1a7fh
+                m.push(0x07f9)?;
+                m.pc = 0x1a7f;
+                m.tick(17, 0x1a7f)?;
+                m.call(0x07f9)?;
+                // 07f9  call 088dh
+                m.push(0x07fc)?;
+                m.pc = 0x088d;
+                m.tick(17, 0x088d)?;
+                m.call(0x07fc)?;
+                // 07fc  call 09d6h
+                m.push(0x07ff)?;
+                m.pc = 0x09d6;
+                m.tick(17, 0x09d6)?;
+                m.call(0x07ff)?;
+                // 07ff  nop
+                m.tick(4, 0x0800)?;
+                // 0800  xra a
+                let value = m.a;
+                m.a ^= value;
+                m.flags = SZP[m.a as usize] | if m.a > 0xf { AUX_CARRY } else { 0 };
+                m.tick(4, 0x0801)?;
+                // 0801  sta 20c1h
+                m.write(0x20c1, m.a)?;
+                m.tick(13, 0x0804)?;
+                m.pc = 0x0804;
+            },
+            0x0804 => {
+                // 0804  call 01cfh
+                m.push(0x0807)?;
+                m.pc = 0x01cf;
+                m.tick(17, 0x01cf)?;
+                m.call(0x0807)?;
+                // 0807  lda 2067h
+                m.a = m.read(0x2067)?;
+                m.tick(13, 0x080a)?;
+                // 080a  rrc
+                m.flags = (m.flags & !CARRY) | (m.a & 1);
+                m.a = m.a.rotate_right(1);
+                m.tick(4, 0x080b)?;
+                // 080b  jc 0872h
+                m.pc = if m.flags & CARRY != 0 { 0x0872 } else { 0x080e };
+                m.tick(10, m.pc)?;
+            },
+            0x080e => {
+                // 080e  call 0213h
+                m.push(0x0811)?;
+                m.pc = 0x0213;
+                m.tick(17, 0x0213)?;
+                m.call(0x0811)?;
+                // 0811  call 01cfh
+                m.push(0x0814)?;
+                m.pc = 0x01cf;
+                m.tick(17, 0x01cf)?;
+                m.call(0x0814)?;
+                m.pc = 0x0814;
+            },
+            0x0814 => {
+                // 0814  call 00b1h
+                m.push(0x0817)?;
+                m.pc = 0x00b1;
+                m.tick(17, 0x00b1)?;
+                m.call(0x0817)?;
+                // 0817  call 19d1h
+                m.push(0x081a)?;
+                m.pc = 0x19d1;
+                m.tick(17, 0x19d1)?;
+                m.call(0x081a)?;
+                // 081a  mvi b,20h
+                m.b = 0x20;
+                m.tick(7, 0x081c)?;
+                // 081c  call 18fah
+                m.push(0x081f)?;
+                m.pc = 0x18fa;
+                m.tick(17, 0x18fa)?;
+                m.call(0x081f)?;
+                m.pc = 0x081f;
+            },
+            0x081f => {
+                // 081f  call 1618h
+                m.push(0x0822)?;
+                m.pc = 0x1618;
+                m.tick(17, 0x1618)?;
+                m.call(0x0822)?;
+                // 0822  call 190ah
+                m.push(0x0825)?;
+                m.pc = 0x190a;
+                m.tick(17, 0x190a)?;
+                m.call(0x0825)?;
+                // 0825  call 15f3h
+                m.push(0x0828)?;
+                m.pc = 0x15f3;
+                m.tick(17, 0x15f3)?;
+                m.call(0x0828)?;
+                // 0828  call 0988h
+                m.push(0x082b)?;
+                m.pc = 0x0988;
+                m.tick(17, 0x0988)?;
+                m.call(0x082b)?;
+                // 082b  lda 2082h
+                m.a = m.read(0x2082)?;
+                m.tick(13, 0x082e)?;
+                // 082e  ana a
+                let value = m.a;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(4, 0x082f)?;
+                // 082f  jz 09efh
+                m.pc = if m.flags & ZERO != 0 { 0x09ef } else { 0x0832 };
+                m.tick(10, m.pc)?;
+            },
+            0x0832 => {
+                // 0832  call 170eh
+                m.push(0x0835)?;
+                m.pc = 0x170e;
+                m.tick(17, 0x170e)?;
+                m.call(0x0835)?;
+                // 0835  call 0935h
+                m.push(0x0838)?;
+                m.pc = 0x0935;
+                m.tick(17, 0x0935)?;
+                m.call(0x0838)?;
+                // 0838  call 08d8h
+                m.push(0x083b)?;
+                m.pc = 0x08d8;
+                m.tick(17, 0x08d8)?;
+                m.call(0x083b)?;
+                // 083b  call 172ch
+                m.push(0x083e)?;
+                m.pc = 0x172c;
+                m.tick(17, 0x172c)?;
+                m.call(0x083e)?;
+                // 083e  call 0a59h
+                m.push(0x0841)?;
+                m.pc = 0x0a59;
+                m.tick(17, 0x0a59)?;
+                m.call(0x0841)?;
+                // 0841  jz 0849h
+                m.pc = if m.flags & ZERO != 0 { 0x0849 } else { 0x0844 };
+                m.tick(10, m.pc)?;
+            },
+            0x0844 => {
+                // 0844  mvi b,04h
+                m.b = 0x04;
+                m.tick(7, 0x0846)?;
+                // 0846  call 18fah
+                m.push(0x0849)?;
+                m.pc = 0x18fa;
+                m.tick(17, 0x18fa)?;
+                m.call(0x0849)?;
+                m.pc = 0x0849;
+            },
+            0x0849 => {
+                // 0849  call 1775h
+                m.push(0x084c)?;
+                m.pc = 0x1775;
+                m.tick(17, 0x1775)?;
+                m.call(0x084c)?;
+                // 084c  out 06h
+                m.output(0x06, m.a)?;
+                m.tick(10, 0x084e)?;
+                // 084e  call 1804h
+                m.push(0x0851)?;
+                m.pc = 0x1804;
+                m.tick(17, 0x1804)?;
+                m.call(0x0851)?;
+                // 0851  jmp 081fh
+                m.pc = 0x081f;
+                m.tick(10, 0x081f)?;
+            },
+            0x0857 => {
+                // 0857  lxi d,1abah
+                m.set_de(0x1aba);
+                m.tick(10, 0x085a)?;
+                // 085a  call 08f3h
+                m.push(0x085d)?;
+                m.pc = 0x08f3;
+                m.tick(17, 0x08f3)?;
+                m.call(0x085d)?;
+                // 085d  mvi b,98h
+                m.b = 0x98;
+                m.tick(7, 0x085f)?;
+                // 085f  in 01h
+                m.a = m.input(0x01)?;
+                m.tick(10, 0x0861)?;
+                // 0861  rrc
+                m.flags = (m.flags & !CARRY) | (m.a & 1);
+                m.a = m.a.rotate_right(1);
+                m.tick(4, 0x0862)?;
+                // 0862  rrc
+                m.flags = (m.flags & !CARRY) | (m.a & 1);
+                m.a = m.a.rotate_right(1);
+                m.tick(4, 0x0863)?;
+                // 0863  jc 086dh
+                m.pc = if m.flags & CARRY != 0 { 0x086d } else { 0x0866 };
+                m.tick(10, m.pc)?;
+            },
+            0x0866 => {
+                // 0866  rrc
+                m.flags = (m.flags & !CARRY) | (m.a & 1);
+                m.a = m.a.rotate_right(1);
+                m.tick(4, 0x0867)?;
+                // 0867  jc 0798h
+                m.pc = if m.flags & CARRY != 0 { 0x0798 } else { 0x086a };
+                m.tick(10, m.pc)?;
+            },
+            0x086a => {
+                // 086a  jmp 077fh
+                m.pc = 0x077f;
+                m.tick(10, 0x077f)?;
+            },
+            0x086d => {
+                // 086d  mvi a,01h
+                m.a = 0x01;
+                m.tick(7, 0x086f)?;
+                // 086f  jmp 079bh
+                m.pc = 0x079b;
+                m.tick(10, 0x079b)?;
+            },
+            0x0872 => {
+                // 0872  call 021ah
+                m.push(0x0875)?;
+                m.pc = 0x021a;
+                m.tick(17, 0x021a)?;
+                m.call(0x0875)?;
+                // 0875  jmp 0814h
+                m.pc = 0x0814;
+                m.tick(10, 0x0814)?;
+            },
+            0x09ef => {
+                // 09ef  call 0a3ch
+                m.push(0x09f2)?;
+                m.pc = 0x0a3c;
+                m.tick(17, 0x0a3c)?;
+                m.call(0x09f2)?;
+                // 09f2  xra a
+                let value = m.a;
+                m.a ^= value;
+                m.flags = SZP[m.a as usize] | if m.a > 0xf { AUX_CARRY } else { 0 };
+                m.tick(4, 0x09f3)?;
+                // 09f3  sta 20e9h
+                m.write(0x20e9, m.a)?;
+                m.tick(13, 0x09f6)?;
+                // 09f6  call 09d6h
+                m.push(0x09f9)?;
+                m.pc = 0x09d6;
+                m.tick(17, 0x09d6)?;
+                m.call(0x09f9)?;
+                // 09f9  lda 2067h
+                m.a = m.read(0x2067)?;
+                m.tick(13, 0x09fc)?;
+                // 09fc  push psw
+                m.push((m.a as u16) << 8 | m.flags as u16)?;
+                m.tick(11, 0x09fd)?;
+                // 09fd  call 01e4h
+                m.push(0x0a00)?;
+                m.pc = 0x01e4;
+                m.tick(17, 0x01e4)?;
+                m.call(0x0a00)?;
+                // 0a00  pop psw
+                let value = m.pop()?;
+                m.a = (value >> 8) as u8;
+                m.flags = value as u8 & (SIGN | ZERO | AUX_CARRY | PARITY | CARRY);
+                m.tick(10, 0x0a01)?;
+                // 0a01  sta 2067h
+                m.write(0x2067, m.a)?;
+                m.tick(13, 0x0a04)?;
+                // 0a04  lda 2067h
+                m.a = m.read(0x2067)?;
+                m.tick(13, 0x0a07)?;
+                // 0a07  mov h,a
+                m.h = m.a;
+                m.tick(5, 0x0a08)?;
+                // 0a08  push h
+                m.push(m.hl())?;
+                m.tick(11, 0x0a09)?;
+                // 0a09  mvi l,0feh
+                m.l = 0xfe;
+                m.tick(7, 0x0a0b)?;
+                // 0a0b  mov a,m
+                m.a = m.read(m.hl())?;
+                m.tick(7, 0x0a0c)?;
+                // 0a0c  ani 07h
+                let value: u8 = 0x07;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(7, 0x0a0e)?;
+                // 0a0e  inr a
+                let value = m.a;
+                let value = value.wrapping_add(1);
+                m.flags = SZP[value as usize] | (m.flags & CARRY) | if value & 0xf == 0 { AUX_CARRY } else { 0 };
+                m.a = value;
+                m.tick(5, 0x0a0f)?;
+                // 0a0f  mov m,a
+                m.write(m.hl(), m.a)?;
+                m.tick(7, 0x0a10)?;
+                // 0a10  lxi h,1da2h
+                m.set_hl(0x1da2);
+                m.tick(10, 0x0a13)?;
+                m.pc = 0x0a13;
+            },
+            0x0a13 => {
+                // 0a13  inx h
+                m.set_hl(m.hl().wrapping_add(1));
+                m.tick(5, 0x0a14)?;
+                // 0a14  dcr a
+                let value = m.a;
+                let value = value.wrapping_sub(1);
+                m.flags = SZP[value as usize] | (m.flags & CARRY) | if value & 0xf != 0xf { AUX_CARRY } else { 0 };
+                m.a = value;
+                m.tick(5, 0x0a15)?;
+                // 0a15  jnz 0a13h
+                m.pc = if m.flags & ZERO == 0 { 0x0a13 } else { 0x0a18 };
+                m.tick(10, m.pc)?;
+            },
+            0x0a18 => {
+                // 0a18  mov a,m
+                m.a = m.read(m.hl())?;
+                m.tick(7, 0x0a19)?;
+                // 0a19  pop h
+                let value = m.pop()?;
+                m.set_hl(value);
+                m.tick(10, 0x0a1a)?;
+                // 0a1a  mvi l,0fch
+                m.l = 0xfc;
+                m.tick(7, 0x0a1c)?;
+                // 0a1c  mov m,a
+                m.write(m.hl(), m.a)?;
+                m.tick(7, 0x0a1d)?;
+                // 0a1d  inx h
+                m.set_hl(m.hl().wrapping_add(1));
+                m.tick(5, 0x0a1e)?;
+                // 0a1e  mvi m,38h
+                m.write(m.hl(), 0x38)?;
+                m.tick(10, 0x0a20)?;
+                // 0a20  mov a,h
+                m.a = m.h;
+                m.tick(5, 0x0a21)?;
+                // 0a21  rrc
+                m.flags = (m.flags & !CARRY) | (m.a & 1);
+                m.a = m.a.rotate_right(1);
+                m.tick(4, 0x0a22)?;
+                // 0a22  jc 0a33h
+                m.pc = if m.flags & CARRY != 0 { 0x0a33 } else { 0x0a25 };
+                m.tick(10, m.pc)?;
+            },
+            0x0a25 => {
+                // 0a25  mvi a,21h
+                m.a = 0x21;
+                m.tick(7, 0x0a27)?;
+                // 0a27  sta 2098h
+                m.write(0x2098, m.a)?;
+                m.tick(13, 0x0a2a)?;
+                // 0a2a  call 01f5h
+                m.push(0x0a2d)?;
+                m.pc = 0x01f5;
+                m.tick(17, 0x01f5)?;
+                m.call(0x0a2d)?;
+                // 0a2d  call 1904h
+                m.push(0x0a30)?;
+                m.pc = 0x1904;
+                m.tick(17, 0x1904)?;
+                m.call(0x0a30)?;
+                // 0a30  jmp 0804h
+                m.pc = 0x0804;
+                m.tick(10, 0x0804)?;
+            },
+            0x0a33 => {
+                // 0a33  call 01efh
+                m.push(0x0a36)?;
+                m.pc = 0x01ef;
+                m.tick(17, 0x01ef)?;
+                m.call(0x0a36)?;
+                // 0a36  call 01c0h
+                m.push(0x0a39)?;
+                m.pc = 0x01c0;
+                m.tick(17, 0x01c0)?;
+                m.call(0x0a39)?;
+                // 0a39  jmp 0804h
+                m.pc = 0x0804;
+                m.tick(10, 0x0804)?;
+            },
+            _ => return Ok(()),
+        }
+    }
+}
+
+fn sub_0020(m: &mut Machine) -> Result<(), Stop> {
+    loop {
+        match m.pc {
+            0x0020 => {
+                // 0020  in 01h
+                m.a = m.input(0x01)?;
+                m.tick(10, 0x0022)?;
+                // 0022  rrc
+                m.flags = (m.flags & !CARRY) | (m.a & 1);
+                m.a = m.a.rotate_right(1);
+                m.tick(4, 0x0023)?;
+                // 0023  jc 0067h
+                m.pc = if m.flags & CARRY != 0 { 0x0067 } else { 0x0026 };
+                m.tick(10, m.pc)?;
+            },
+            0x0026 => {
+                // 0026  lda 20eah
+                m.a = m.read(0x20ea)?;
+                m.tick(13, 0x0029)?;
+                // 0029  ana a
+                let value = m.a;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(4, 0x002a)?;
+                // 002a  jz 0042h
+                m.pc = if m.flags & ZERO != 0 { 0x0042 } else { 0x002d };
+                m.tick(10, m.pc)?;
+            },
+            0x002d => {
+                // 002d  lda 20ebh
+                m.a = m.read(0x20eb)?;
+                m.tick(13, 0x0030)?;
+                // 0030  cpi 99h
+                let value: u8 = 0x99;
+                let result = (m.a as u16).wrapping_sub(value as u16);
+                m.flags = SZP[result as u8 as usize] | (result > 0xff) as u8 | if (m.a & 0xf).wrapping_sub(value & 0xf) > 0xf { AUX_CARRY } else { 0 };
+                m.tick(7, 0x0032)?;
+                // 0032  jz 003eh
+                m.pc = if m.flags & ZERO != 0 { 0x003e } else { 0x0035 };
+                m.tick(10, m.pc)?;
+            },
+            0x0035 => {
+                // 0035  adi 01h
+                let value: u8 = 0x01;
+                let result = m.a as u16 + value as u16;
+                m.flags = SZP[result as u8 as usize] | (result >> 8) as u8 | if (m.a & 0xf) + (value & 0xf) > 0xf { AUX_CARRY } else { 0 };
+                m.a = result as u8;
+                m.tick(7, 0x0037)?;
+                // 0037  daa
+                m.daa();
+                m.tick(4, 0x0038)?;
+                // 0038  sta 20ebh
+                m.write(0x20eb, m.a)?;
+                m.tick(13, 0x003b)?;
+                // 003b  call 1947h
+                m.push(0x003e)?;
+                m.pc = 0x1947;
+                m.tick(17, 0x1947)?;
+                m.call(0x003e)?;
+                m.pc = 0x003e;
+            },
+            0x003e => {
+                // 003e  xra a
+                let value = m.a;
+                m.a ^= value;
+                m.flags = SZP[m.a as usize] | if m.a > 0xf { AUX_CARRY } else { 0 };
+                m.tick(4, 0x003f)?;
+                m.pc = 0x003f;
+            },
+            0x003f => {
+                // 003f  sta 20eah
+                m.write(0x20ea, m.a)?;
+                m.tick(13, 0x0042)?;
+                m.pc = 0x0042;
+            },
+            0x0042 => {
+                // 0042  lda 20e9h
+                m.a = m.read(0x20e9)?;
+                m.tick(13, 0x0045)?;
+                // 0045  ana a
+                let value = m.a;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(4, 0x0046)?;
+                // 0046  jz 0082h
+                m.pc = if m.flags & ZERO != 0 { 0x0082 } else { 0x0049 };
+                m.tick(10, m.pc)?;
+            },
+            0x0049 => {
+                // 0049  lda 20efh
+                m.a = m.read(0x20ef)?;
+                m.tick(13, 0x004c)?;
+                // 004c  ana a
+                let value = m.a;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(4, 0x004d)?;
+                // 004d  jnz 006fh
+                m.pc = if m.flags & ZERO == 0 { 0x006f } else { 0x0050 };
+                m.tick(10, m.pc)?;
+            },
+            0x0050 => {
+                // 0050  lda 20ebh
+                m.a = m.read(0x20eb)?;
+                m.tick(13, 0x0053)?;
+                // 0053  ana a
+                let value = m.a;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(4, 0x0054)?;
+                // 0054  jnz 005dh
+                m.pc = if m.flags & ZERO == 0 { 0x005d } else { 0x0057 };
+                m.tick(10, m.pc)?;
+            },
+            0x0057 => {
+                // 0057  call 0abfh
+                m.push(0x005a)?;
+                m.pc = 0x0abf;
+                m.tick(17, 0x0abf)?;
+                m.call(0x005a)?;
+                // 005a  jmp 0082h
+                m.pc = 0x0082;
+                m.tick(10, 0x0082)?;
+            },
+            0x005d => {
+                // 005d  lda 2093h
+                m.a = m.read(0x2093)?;
+                m.tick(13, 0x0060)?;
+                // 0060  ana a
+                let value = m.a;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(4, 0x0061)?;
+                // 0061  jnz 0082h
+                m.pc = if m.flags & ZERO == 0 { 0x0082 } else { 0x0064 };
+                m.tick(10, m.pc)?;
+            },
+            0x0064 => {
+                // 0064  jmp 0765h
+                m.pc = 0x0765;
+                m.tick(10, 0x0765)?;
+            },
+            0x0067 => {
+                // 0067  mvi a,01h
+                m.a = 0x01;
+                m.tick(7, 0x0069)?;
+                // 0069  sta 20eah
+                m.write(0x20ea, m.a)?;
+                m.tick(13, 0x006c)?;
+                // 006c  jmp 003fh
+                m.pc = 0x003f;
+                m.tick(10, 0x003f)?;
+            },
+            0x006f => {
+                // 006f  call 1740h
+                m.push(0x0072)?;
+                m.pc = 0x1740;
+                m.tick(17, 0x1740)?;
+                m.call(0x0072)?;
+                // 0072  lda 2032h
+                m.a = m.read(0x2032)?;
+                m.tick(13, 0x0075)?;
+                // 0075  sta 2080h
+                m.write(0x2080, m.a)?;
+                m.tick(13, 0x0078)?;
+                // 0078  call 0100h
+                m.push(0x007b)?;
+                m.pc = 0x0100;
+                m.tick(17, 0x0100)?;
+                m.call(0x007b)?;
+                // 007b  call 0248h
+                m.push(0x007e)?;
+                m.pc = 0x0248;
+                m.tick(17, 0x0248)?;
+                m.call(0x007e)?;
+                // 007e  call 0913h
+                m.push(0x0081)?;
+                m.pc = 0x0913;
+                m.tick(17, 0x0913)?;
+                m.call(0x0081)?;
+                // 0081  nop
+                m.tick(4, 0x0082)?;
+                m.pc = 0x0082;
+            },
+            0x0082 => {
+                // 0082  pop h
+                let value = m.pop()?;
+                m.set_hl(value);
+                m.tick(10, 0x0083)?;
+                // 0083  pop d
+                let value = m.pop()?;
+                m.set_de(value);
+                m.tick(10, 0x0084)?;
+                // 0084  pop b
+                let value = m.pop()?;
+                m.set_bc(value);
+                m.tick(10, 0x0085)?;
+                // 0085  pop psw
+                let value = m.pop()?;
+                m.a = (value >> 8) as u8;
+                m.flags = value as u8 & (SIGN | ZERO | AUX_CARRY | PARITY | CARRY);
+                m.tick(10, 0x0086)?;
+                m.pc = 0x0086;
+            },
+            // 0086  ei
+            0x0086 => m.interpret()?,
+            0x0765 => {
+                // 0765  mvi a,01h
+                m.a = 0x01;
+                m.tick(7, 0x0767)?;
+                // 0767  sta 2093h
+                m.write(0x2093, m.a)?;
+                m.tick(13, 0x076a)?;
+                // 076a  lxi sp,2400h
+                m.sp = 0x2400;
+                m.tick(10, 0x076d)?;
+                m.pc = 0x076d;
+            },
+            // 076d  ei
+            0x076d => m.interpret()?,
+            0x0771 => {
+                // 0771  call 09d6h
+                m.push(0x0774)?;
+                m.pc = 0x09d6;
+                m.tick(17, 0x09d6)?;
+                m.call(0x0774)?;
+                // 0774  lxi h,3013h
+                m.set_hl(0x3013);
+                m.tick(10, 0x0777)?;
+                // 0777  lxi d,1ff3h
+                m.set_de(0x1ff3);
+                m.tick(10, 0x077a)?;
+                // 077a  mvi c,04h
+                m.c = 0x04;
+                m.tick(7, 0x077c)?;
+                // 077c  call 08f3h
+                m.push(0x077f)?;
+                m.pc = 0x08f3;
+                m.tick(17, 0x08f3)?;
+                m.call(0x077f)?;
+                m.pc = 0x077f;
+            },
+            0x077f => {
+                // 077f  lda 20ebh
+                m.a = m.read(0x20eb)?;
+                m.tick(13, 0x0782)?;
+                // 0782  dcr a
+                let value = m.a;
+                let value = value.wrapping_sub(1);
+                m.flags = SZP[value as usize] | (m.flags & CARRY) | if value & 0xf != 0xf { AUX_CARRY } else { 0 };
+                m.a = value;
+                m.tick(5, 0x0783)?;
+                // 0783  lxi h,2810h
+                m.set_hl(0x2810);
+                m.tick(10, 0x0786)?;
+                // 0786  mvi c,14h
+                m.c = 0x14;
+                m.tick(7, 0x0788)?;
+                // 0788  jnz 0857h
+                m.pc = if m.flags & ZERO == 0 { 0x0857 } else { 0x078b };
+                m.tick(10, m.pc)?;
+            },
+            0x078b => {
+                // 078b  lxi d,1acfh
+                m.set_de(0x1acf);
+                m.tick(10, 0x078e)?;
+                // 078e  call 08f3h
+                m.push(0x0791)?;
+                m.pc = 0x08f3;
+                m.tick(17, 0x08f3)?;
+                m.call(0x0791)?;
+                // 0791  in 01h
+                m.a = m.input(0x01)?;
+                m.tick(10, 0x0793)?;
+                // 0793  ani 04h
+                let value: u8 = 0x04;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(7, 0x0795)?;
+                // 0795  jz 077fh
+                m.pc = if m.flags & ZERO != 0 { 0x077f } else { 0x0798 };
+                m.tick(10, m.pc)?;
+            },
+            0x0798 => {
+                // 0798  mvi b,99h
+                m.b = 0x99;
+                m.tick(7, 0x079a)?;
+                // 079a  xra a
+                let value = m.a;
+                m.a ^= value;
+                m.flags = SZP[m.a as usize] | if m.a > 0xf { AUX_CARRY } else { 0 };
+                m.tick(4, 0x079b)?;
+                m.pc = 0x079b;
+            },
+            0x079b => {
+                // 079b  sta 20ceh
+                m.write(0x20ce, m.a)?;
+                m.tick(13, 0x079e)?;
+                // 079e  lda 20ebh
+                m.a = m.read(0x20eb)?;
+                m.tick(13, 0x07a1)?;
+                // 07a1  add b
+                let value = m.b;
+                let result = m.a as u16 + value as u16;
+                m.flags = SZP[result as u8 as usize] | (result >> 8) as u8 | if (m.a & 0xf) + (value & 0xf) > 0xf { AUX_CARRY } else { 0 };
+                m.a = result as u8;
+                m.tick(4, 0x07a2)?;
+                // 07a2  daa
+                m.daa();
+                m.tick(4, 0x07a3)?;
+                // 07a3  sta 20ebh
+                m.write(0x20eb, m.a)?;
+                m.tick(13, 0x07a6)?;
+                // 07a6  call 1947h
+                m.push(0x07a9)?;
+                m.pc = 0x1947;
+                m.tick(17, 0x1947)?;
+                m.call(0x07a9)?;
+                // 07a9  lxi h,0000h
+                m.set_hl(0x0000);
+                m.tick(10, 0x07ac)?;
+                // 07ac  shld 20f8h
+                m.write(0x20f8, m.l)?;
+                m.write(0x20f9, m.h)?;
+                m.tick(16, 0x07af)?;
+                // 07af  shld 20fch
+                m.write(0x20fc, m.l)?;
+                m.write(0x20fd, m.h)?;
+                m.tick(16, 0x07b2)?;
+                // 07b2  call 1925h
+                m.push(0x07b5)?;
+                m.pc = 0x1925;
+                m.tick(17, 0x1925)?;
+                m.call(0x07b5)?;
+                // 07b5  call 192bh
+                m.push(0x07b8)?;
+                m.pc = 0x192b;
+                m.tick(17, 0x192b)?;
+                m.call(0x07b8)?;
+                // 07b8  call 19d7h
+                m.push(0x07bb)?;
+                m.pc = 0x19d7;
+                m.tick(17, 0x19d7)?;
+                m.call(0x07bb)?;
+                // 07bb  lxi h,0101h
+                m.set_hl(0x0101);
+                m.tick(10, 0x07be)?;
+                // 07be  mov a,h
+                m.a = m.h;
+                m.tick(5, 0x07bf)?;
+                // 07bf  sta 20efh
+                m.write(0x20ef, m.a)?;
+                m.tick(13, 0x07c2)?;
+                // 07c2  shld 20e7h
+                m.write(0x20e7, m.l)?;
+                m.write(0x20e8, m.h)?;
+                m.tick(16, 0x07c5)?;
+                // 07c5  shld 20e5h
+                m.write(0x20e5, m.l)?;
+                m.write(0x20e6, m.h)?;
+                m.tick(16, 0x07c8)?;
+                // 07c8  call 1956h
+                m.push(0x07cb)?;
+                m.pc = 0x1956;
+                m.tick(17, 0x1956)?;
+                m.call(0x07cb)?;
+                // 07cb  call 01efh
+                m.push(0x07ce)?;
+                m.pc = 0x01ef;
+                m.tick(17, 0x01ef)?;
+                m.call(0x07ce)?;
+                // 07ce  call 01f5h
+                m.push(0x07d1)?;
+                m.pc = 0x01f5;
+                m.tick(17, 0x01f5)?;
+                m.call(0x07d1)?;
+                // 07d1  call 08d1h
+                m.push(0x07d4)?;
+                m.pc = 0x08d1;
+                m.tick(17, 0x08d1)?;
+                m.call(0x07d4)?;
+                // 07d4  sta 21ffh
+                m.write(0x21ff, m.a)?;
+                m.tick(13, 0x07d7)?;
+                // 07d7  sta 22ffh
+                m.write(0x22ff, m.a)?;
+                m.tick(13, 0x07da)?;
+                // 07da  call 00d7h
+                m.push(0x07dd)?;
+                m.pc = 0x00d7;
+                m.tick(17, 0x00d7)?;
+                m.call(0x07dd)?;
+                // 07dd  xra a
+                let value = m.a;
+                m.a ^= value;
+                m.flags = SZP[m.a as usize] | if m.a > 0xf { AUX_CARRY } else { 0 };
+                m.tick(4, 0x07de)?;
+                // 07de  sta 21feh
+                m.write(0x21fe, m.a)?;
+                m.tick(13, 0x07e1)?;
+                // 07e1  sta 22feh
+                m.write(0x22fe, m.a)?;
+                m.tick(13, 0x07e4)?;
+                // 07e4  call 01c0h
+                m.push(0x07e7)?;
+                m.pc = 0x01c0;
+                m.tick(17, 0x01c0)?;
+                m.call(0x07e7)?;
+                // 07e7  call 1904h
+                m.push(0x07ea)?;
+                m.pc = 0x1904;
+                m.tick(17, 0x1904)?;
+                m.call(0x07ea)?;
+                // 07ea  lxi h,3878h
+                m.set_hl(0x3878);
+                m.tick(10, 0x07ed)?;
+                // 07ed  shld 21fch
+                m.write(0x21fc, m.l)?;
+                m.write(0x21fd, m.h)?;
+                m.tick(16, 0x07f0)?;
+                // 07f0  shld 22fch
+                m.write(0x22fc, m.l)?;
+                m.write(0x22fd, m.h)?;
+                m.tick(16, 0x07f3)?;
+                // 07f3  call 01e4h
+                m.push(0x07f6)?;
+                m.pc = 0x01e4;
+                m.tick(17, 0x01e4)?;
+                m.call(0x07f6)?;
+                // 07f6  call 1a7fh
+                m.push(0x07f9)?;
+                m.pc = 0x1a7f;
+                m.tick(17, 0x1a7f)?;
+                m.call(0x07f9)?;
+                // 07f9  call 088dh
+                m.push(0x07fc)?;
+                m.pc = 0x088d;
+                m.tick(17, 0x088d)?;
+                m.call(0x07fc)?;
+                // 07fc  call 09d6h
+                m.push(0x07ff)?;
+                m.pc = 0x09d6;
+                m.tick(17, 0x09d6)?;
+                m.call(0x07ff)?;
+                // 07ff  nop
+                m.tick(4, 0x0800)?;
+                // 0800  xra a
+                let value = m.a;
+                m.a ^= value;
+                m.flags = SZP[m.a as usize] | if m.a > 0xf { AUX_CARRY } else { 0 };
+                m.tick(4, 0x0801)?;
+                // 0801  sta 20c1h
+                m.write(0x20c1, m.a)?;
+                m.tick(13, 0x0804)?;
+                m.pc = 0x0804;
+            },
+            0x0804 => {
+                // 0804  call 01cfh
+                m.push(0x0807)?;
+                m.pc = 0x01cf;
+                m.tick(17, 0x01cf)?;
+                m.call(0x0807)?;
+                // 0807  lda 2067h
+                m.a = m.read(0x2067)?;
+                m.tick(13, 0x080a)?;
+                // 080a  rrc
+                m.flags = (m.flags & !CARRY) | (m.a & 1);
+                m.a = m.a.rotate_right(1);
+                m.tick(4, 0x080b)?;
+                // 080b  jc 0872h
+                m.pc = if m.flags & CARRY != 0 { 0x0872 } else { 0x080e };
+                m.tick(10, m.pc)?;
+            },
+            0x080e => {
+                // 080e  call 0213h
+                m.push(0x0811)?;
+                m.pc = 0x0213;
+                m.tick(17, 0x0213)?;
+                m.call(0x0811)?;
+                // 0811  call 01cfh
+                m.push(0x0814)?;
+                m.pc = 0x01cf;
+                m.tick(17, 0x01cf)?;
+                m.call(0x0814)?;
+                m.pc = 0x0814;
+            },
+            0x0814 => {
+                // 0814  call 00b1h
+                m.push(0x0817)?;
+                m.pc = 0x00b1;
+                m.tick(17, 0x00b1)?;
+                m.call(0x0817)?;
+                // 0817  call 19d1h
+                m.push(0x081a)?;
+                m.pc = 0x19d1;
+                m.tick(17, 0x19d1)?;
+                m.call(0x081a)?;
+                // 081a  mvi b,20h
+                m.b = 0x20;
+                m.tick(7, 0x081c)?;
+                // 081c  call 18fah
+                m.push(0x081f)?;
+                m.pc = 0x18fa;
+                m.tick(17, 0x18fa)?;
+                m.call(0x081f)?;
+                m.pc = 0x081f;
+            },
+            0x081f => {
+                // 081f  call 1618h
+                m.push(0x0822)?;
+                m.pc = 0x1618;
+                m.tick(17, 0x1618)?;
+                m.call(0x0822)?;
+                // 0822  call 190ah
+                m.push(0x0825)?;
+                m.pc = 0x190a;
+                m.tick(17, 0x190a)?;
+                m.call(0x0825)?;
+                // 0825  call 15f3h
+                m.push(0x0828)?;
+                m.pc = 0x15f3;
+                m.tick(17, 0x15f3)?;
+                m.call(0x0828)?;
+                // 0828  call 0988h
+                m.push(0x082b)?;
+                m.pc = 0x0988;
+                m.tick(17, 0x0988)?;
+                m.call(0x082b)?;
+                // 082b  lda 2082h
+                m.a = m.read(0x2082)?;
+                m.tick(13, 0x082e)?;
+                // 082e  ana a
+                let value = m.a;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(4, 0x082f)?;
+                // 082f  jz 09efh
+                m.pc = if m.flags & ZERO != 0 { 0x09ef } else { 0x0832 };
+                m.tick(10, m.pc)?;
+            },
+            0x0832 => {
+                // 0832  call 170eh
+                m.push(0x0835)?;
+                m.pc = 0x170e;
+                m.tick(17, 0x170e)?;
+                m.call(0x0835)?;
+                // 0835  call 0935h
+                m.push(0x0838)?;
+                m.pc = 0x0935;
+                m.tick(17, 0x0935)?;
+                m.call(0x0838)?;
+                // 0838  call 08d8h
+                m.push(0x083b)?;
+                m.pc = 0x08d8;
+                m.tick(17, 0x08d8)?;
+                m.call(0x083b)?;
+                // 083b  call 172ch
+                m.push(0x083e)?;
+                m.pc = 0x172c;
+                m.tick(17, 0x172c)?;
+                m.call(0x083e)?;
+                // 083e  call 0a59h
+                m.push(0x0841)?;
+                m.pc = 0x0a59;
+                m.tick(17, 0x0a59)?;
+                m.call(0x0841)?;
+                // 0841  jz 0849h
+                m.pc = if m.flags & ZERO != 0 { 0x0849 } else { 0x0844 };
+                m.tick(10, m.pc)?;
+            },
+            0x0844 => {
+                // 0844  mvi b,04h
+                m.b = 0x04;
+                m.tick(7, 0x0846)?;
+                // 0846  call 18fah
+                m.push(0x0849)?;
+                m.pc = 0x18fa;
+                m.tick(17, 0x18fa)?;
+                m.call(0x0849)?;
+                m.pc = 0x0849;
+            },
+            0x0849 => {
+                // 0849  call 1775h
+                m.push(0x084c)?;
+                m.pc = 0x1775;
+                m.tick(17, 0x1775)?;
+                m.call(0x084c)?;
+                // 084c  out 06h
+                m.output(0x06, m.a)?;
+                m.tick(10, 0x084e)?;
+                // 084e  call 1804h
+                m.push(0x0851)?;
+                m.pc = 0x1804;
+                m.tick(17, 0x1804)?;
+                m.call(0x0851)?;
+                // 0851  jmp 081fh
+                m.pc = 0x081f;
+                m.tick(10, 0x081f)?;
+            },
+            0x0857 => {
+                // 0857  lxi d,1abah
+                m.set_de(0x1aba);
+                m.tick(10, 0x085a)?;
+                // 085a  call 08f3h
+                m.push(0x085d)?;
+                m.pc = 0x08f3;
+                m.tick(17, 0x08f3)?;
+                m.call(0x085d)?;
+                // 085d  mvi b,98h
+                m.b = 0x98;
+                m.tick(7, 0x085f)?;
+                // 085f  in 01h
+                m.a = m.input(0x01)?;
+                m.tick(10, 0x0861)?;
+                // 0861  rrc
+                m.flags = (m.flags & !CARRY) | (m.a & 1);
+                m.a = m.a.rotate_right(1);
+                m.tick(4, 0x0862)?;
+                // 0862  rrc
+                m.flags = (m.flags & !CARRY) | (m.a & 1);
+                m.a = m.a.rotate_right(1);
+                m.tick(4, 0x0863)?;
+                // 0863  jc 086dh
+                m.pc = if m.flags & CARRY != 0 { 0x086d } else { 0x0866 };
+                m.tick(10, m.pc)?;
+            },
+            0x0866 => {
+                // 0866  rrc
+                m.flags = (m.flags & !CARRY) | (m.a & 1);
+                m.a = m.a.rotate_right(1);
+                m.tick(4, 0x0867)?;
+                // 0867  jc 0798h
+                m.pc = if m.flags & CARRY != 0 { 0x0798 } else { 0x086a };
+                m.tick(10, m.pc)?;
+            },
+            0x086a => {
+                // 086a  jmp 077fh
+                m.pc = 0x077f;
+                m.tick(10, 0x077f)?;
+            },
+            0x086d => {
+                // 086d  mvi a,01h
+                m.a = 0x01;
+                m.tick(7, 0x086f)?;
+                // 086f  jmp 079bh
+                m.pc = 0x079b;
+                m.tick(10, 0x079b)?;
+            },
+            0x0872 => {
+                // 0872  call 021ah
+                m.push(0x0875)?;
+                m.pc = 0x021a;
+                m.tick(17, 0x021a)?;
+                m.call(0x0875)?;
+                // 0875  jmp 0814h
+                m.pc = 0x0814;
+                m.tick(10, 0x0814)?;
+            },
+            0x09ef => {
+                // 09ef  call 0a3ch
+                m.push(0x09f2)?;
+                m.pc = 0x0a3c;
+                m.tick(17, 0x0a3c)?;
+                m.call(0x09f2)?;
+                // 09f2  xra a
+                let value = m.a;
+                m.a ^= value;
+                m.flags = SZP[m.a as usize] | if m.a > 0xf { AUX_CARRY } else { 0 };
+                m.tick(4, 0x09f3)?;
+                // 09f3  sta 20e9h
+                m.write(0x20e9, m.a)?;
+                m.tick(13, 0x09f6)?;
+                // 09f6  call 09d6h
+                m.push(0x09f9)?;
+                m.pc = 0x09d6;
+                m.tick(17, 0x09d6)?;
+                m.call(0x09f9)?;
+                // 09f9  lda 2067h
+                m.a = m.read(0x2067)?;
+                m.tick(13, 0x09fc)?;
+                // 09fc  push psw
+                m.push((m.a as u16) << 8 | m.flags as u16)?;
+                m.tick(11, 0x09fd)?;
+                // 09fd  call 01e4h
+                m.push(0x0a00)?;
+                m.pc = 0x01e4;
+                m.tick(17, 0x01e4)?;
+                m.call(0x0a00)?;
+                // 0a00  pop psw
+                let value = m.pop()?;
+                m.a = (value >> 8) as u8;
+                m.flags = value as u8 & (SIGN | ZERO | AUX_CARRY | PARITY | CARRY);
+                m.tick(10, 0x0a01)?;
+                // 0a01  sta 2067h
+                m.write(0x2067, m.a)?;
+                m.tick(13, 0x0a04)?;
+                // 0a04  lda 2067h
+                m.a = m.read(0x2067)?;
+                m.tick(13, 0x0a07)?;
+                // 0a07  mov h,a
+                m.h = m.a;
+                m.tick(5, 0x0a08)?;
+                // 0a08  push h
+                m.push(m.hl())?;
+                m.tick(11, 0x0a09)?;
+                // 0a09  mvi l,0feh
+                m.l = 0xfe;
+                m.tick(7, 0x0a0b)?;
+                // 0a0b  mov a,m
+                m.a = m.read(m.hl())?;
+                m.tick(7, 0x0a0c)?;
+                // 0a0c  ani 07h
+                let value: u8 = 0x07;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(7, 0x0a0e)?;
+                // 0a0e  inr a
+                let value = m.a;
+                let value = value.wrapping_add(1);
+                m.flags = SZP[value as usize] | (m.flags & CARRY) | if value & 0xf == 0 { AUX_CARRY } else { 0 };
+                m.a = value;
+                m.tick(5, 0x0a0f)?;
+                // 0a0f  mov m,a
+                m.write(m.hl(), m.a)?;
+                m.tick(7, 0x0a10)?;
+                // 0a10  lxi h,1da2h
+                m.set_hl(0x1da2);
+                m.tick(10, 0x0a13)?;
+                m.pc = 0x0a13;
+            },
+            0x0a13 => {
+                // 0a13  inx h
+                m.set_hl(m.hl().wrapping_add(1));
+                m.tick(5, 0x0a14)?;
+                // 0a14  dcr a
+                let value = m.a;
+                let value = value.wrapping_sub(1);
+                m.flags = SZP[value as usize] | (m.flags & CARRY) | if value & 0xf != 0xf { AUX_CARRY } else { 0 };
+                m.a = value;
+                m.tick(5, 0x0a15)?;
+                // 0a15  jnz 0a13h
+                m.pc = if m.flags & ZERO == 0 { 0x0a13 } else { 0x0a18 };
+                m.tick(10, m.pc)?;
+            },
+            0x0a18 => {
+                // 0a18  mov a,m
+                m.a = m.read(m.hl())?;
+                m.tick(7, 0x0a19)?;
+                // 0a19  pop h
+                let value = m.pop()?;
+                m.set_hl(value);
+                m.tick(10, 0x0a1a)?;
+                // 0a1a  mvi l,0fch
+                m.l = 0xfc;
+                m.tick(7, 0x0a1c)?;
+                // 0a1c  mov m,a
+                m.write(m.hl(), m.a)?;
+                m.tick(7, 0x0a1d)?;
+                // 0a1d  inx h
+                m.set_hl(m.hl().wrapping_add(1));
+                m.tick(5, 0x0a1e)?;
+                // 0a1e  mvi m,38h
+                m.write(m.hl(), 0x38)?;
+                m.tick(10, 0x0a20)?;
+                // 0a20  mov a,h
+                m.a = m.h;
+                m.tick(5, 0x0a21)?;
+                // 0a21  rrc
+                m.flags = (m.flags & !CARRY) | (m.a & 1);
+                m.a = m.a.rotate_right(1);
+                m.tick(4, 0x0a22)?;
+                // 0a22  jc 0a33h
+                m.pc = if m.flags & CARRY != 0 { 0x0a33 } else { 0x0a25 };
+                m.tick(10, m.pc)?;
+            },
+            0x0a25 => {
+                // 0a25  mvi a,21h
+                m.a = 0x21;
+                m.tick(7, 0x0a27)?;
+                // 0a27  sta 2098h
+                m.write(0x2098, m.a)?;
+                m.tick(13, 0x0a2a)?;
+                // 0a2a  call 01f5h
+                m.push(0x0a2d)?;
+                m.pc = 0x01f5;
+                m.tick(17, 0x01f5)?;
+                m.call(0x0a2d)?;
+                // 0a2d  call 1904h
+                m.push(0x0a30)?;
+                m.pc = 0x1904;
+                m.tick(17, 0x1904)?;
+                m.call(0x0a30)?;
+                // 0a30  jmp 0804h
+                m.pc = 0x0804;
+                m.tick(10, 0x0804)?;
+            },
+            0x0a33 => {
+                // 0a33  call 01efh
+                m.push(0x0a36)?;
+                m.pc = 0x01ef;
+                m.tick(17, 0x01ef)?;
+                m.call(0x0a36)?;
+                // 0a36  call 01c0h
+                m.push(0x0a39)?;
+                m.pc = 0x01c0;
+                m.tick(17, 0x01c0)?;
+                m.call(0x0a39)?;
+                // 0a39  jmp 0804h
+                m.pc = 0x0804;
+                m.tick(10, 0x0804)?;
+            },
+            _ => return Ok(()),
+        }
+    }
+}
+
+fn sub_0028(m: &mut Machine) -> Result<(), Stop> {
+    loop {
+        match m.pc {
+            0x0028 => {
+                // 0028  db 20h
+                m.tick(4, 0x0029)?;
+                // 0029  ana a
+                let value = m.a;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(4, 0x002a)?;
+                // 002a  jz 0042h
+                m.pc = if m.flags & ZERO != 0 { 0x0042 } else { 0x002d };
+                m.tick(10, m.pc)?;
+            },
+            0x002d => {
+                // 002d  lda 20ebh
+                m.a = m.read(0x20eb)?;
+                m.tick(13, 0x0030)?;
+                // 0030  cpi 99h
+                let value: u8 = 0x99;
+                let result = (m.a as u16).wrapping_sub(value as u16);
+                m.flags = SZP[result as u8 as usize] | (result > 0xff) as u8 | if (m.a & 0xf).wrapping_sub(value & 0xf) > 0xf { AUX_CARRY } else { 0 };
+                m.tick(7, 0x0032)?;
+                // 0032  jz 003eh
+                m.pc = if m.flags & ZERO != 0 { 0x003e } else { 0x0035 };
+                m.tick(10, m.pc)?;
+            },
+            0x0035 => {
+                // 0035  adi 01h
+                let value: u8 = 0x01;
+                let result = m.a as u16 + value as u16;
+                m.flags = SZP[result as u8 as usize] | (result >> 8) as u8 | if (m.a & 0xf) + (value & 0xf) > 0xf { AUX_CARRY } else { 0 };
+                m.a = result as u8;
+                m.tick(7, 0x0037)?;
+                // 0037  daa
+                m.daa();
+                m.tick(4, 0x0038)?;
+                // 0038  sta 20ebh
+                m.write(0x20eb, m.a)?;
+                m.tick(13, 0x003b)?;
+                // 003b  call 1947h
+                m.push(0x003e)?;
+                m.pc = 0x1947;
+                m.tick(17, 0x1947)?;
+                m.call(0x003e)?;
+                m.pc = 0x003e;
+            },
+            0x003e => {
+                // 003e  xra a
+                let value = m.a;
+                m.a ^= value;
+                m.flags = SZP[m.a as usize] | if m.a > 0xf { AUX_CARRY } else { 0 };
+                m.tick(4, 0x003f)?;
+                // 003f  sta 20eah
+                m.write(0x20ea, m.a)?;
+                m.tick(13, 0x0042)?;
+                m.pc = 0x0042;
+            },
+            0x0042 => {
+                // 0042  lda 20e9h
+                m.a = m.read(0x20e9)?;
+                m.tick(13, 0x0045)?;
+                // 0045  ana a
+                let value = m.a;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(4, 0x0046)?;
+                // 0046  jz 0082h
+                m.pc = if m.flags & ZERO != 0 { 0x0082 } else { 0x0049 };
+                m.tick(10, m.pc)?;
+            },
+            0x0049 => {
+                // 0049  lda 20efh
+                m.a = m.read(0x20ef)?;
+                m.tick(13, 0x004c)?;
+                // 004c  ana a
+                let value = m.a;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(4, 0x004d)?;
+                // 004d  jnz 006fh
+                m.pc = if m.flags & ZERO == 0 { 0x006f } else { 0x0050 };
+                m.tick(10, m.pc)?;
+            },
+            0x0050 => {
+                // 0050  lda 20ebh
+                m.a = m.read(0x20eb)?;
+                m.tick(13, 0x0053)?;
+                // 0053  ana a
+                let value = m.a;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(4, 0x0054)?;
+                // 0054  jnz 005dh
+                m.pc = if m.flags & ZERO == 0 { 0x005d } else { 0x0057 };
+                m.tick(10, m.pc)?;
+            },
+            0x0057 => {
+                // 0057  call 0abfh
+                m.push(0x005a)?;
+                m.pc = 0x0abf;
+                m.tick(17, 0x0abf)?;
+                m.call(0x005a)?;
+                // 005a  jmp 0082h
+                m.pc = 0x0082;
+                m.tick(10, 0x0082)?;
+            },
+            0x005d => {
+                // 005d  lda 2093h
+                m.a = m.read(0x2093)?;
+                m.tick(13, 0x0060)?;
+                // 0060  ana a
+                let value = m.a;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(4, 0x0061)?;
+                // 0061  jnz 0082h
+                m.pc = if m.flags & ZERO == 0 { 0x0082 } else { 0x0064 };
+                m.tick(10, m.pc)?;
+            },
+            0x0064 => {
+                // 0064  jmp 0765h
+                m.pc = 0x0765;
+                m.tick(10, 0x0765)?;
+            },
+            0x006f => {
+                // 006f  call 1740h
+                m.push(0x0072)?;
+                m.pc = 0x1740;
+                m.tick(17, 0x1740)?;
+                m.call(0x0072)?;
+                // 0072  lda 2032h
+                m.a = m.read(0x2032)?;
+                m.tick(13, 0x0075)?;
+                // 0075  sta 2080h
+                m.write(0x2080, m.a)?;
+                m.tick(13, 0x0078)?;
+                // 0078  call 0100h
+                m.push(0x007b)?;
+                m.pc = 0x0100;
+                m.tick(17, 0x0100)?;
+                m.call(0x007b)?;
+                // 007b  call 0248h
+                m.push(0x007e)?;
+                m.pc = 0x0248;
+                m.tick(17, 0x0248)?;
+                m.call(0x007e)?;
+                // 007e  call 0913h
+                m.push(0x0081)?;
+                m.pc = 0x0913;
+                m.tick(17, 0x0913)?;
+                m.call(0x0081)?;
+                // 0081  nop
+                m.tick(4, 0x0082)?;
+                m.pc = 0x0082;
+            },
+            0x0082 => {
+                // 0082  pop h
+                let value = m.pop()?;
+                m.set_hl(value);
+                m.tick(10, 0x0083)?;
+                // 0083  pop d
+                let value = m.pop()?;
+                m.set_de(value);
+                m.tick(10, 0x0084)?;
+                // 0084  pop b
+                let value = m.pop()?;
+                m.set_bc(value);
+                m.tick(10, 0x0085)?;
+                // 0085  pop psw
+                let value = m.pop()?;
+                m.a = (value >> 8) as u8;
+                m.flags = value as u8 & (SIGN | ZERO | AUX_CARRY | PARITY | CARRY);
+                m.tick(10, 0x0086)?;
+                m.pc = 0x0086;
+            },
+            // 0086  ei
+            0x0086 => m.interpret()?,
+            0x0765 => {
+                // 0765  mvi a,01h
+                m.a = 0x01;
+                m.tick(7, 0x0767)?;
+                // 0767  sta 2093h
+                m.write(0x2093, m.a)?;
+                m.tick(13, 0x076a)?;
+                // 076a  lxi sp,2400h
+                m.sp = 0x2400;
+                m.tick(10, 0x076d)?;
+                m.pc = 0x076d;
+            },
+            // 076d  ei
+            0x076d => m.interpret()?,
+            0x0771 => {
+                // 0771  call 09d6h
+                m.push(0x0774)?;
+                m.pc = 0x09d6;
+                m.tick(17, 0x09d6)?;
+                m.call(0x0774)?;
+                // 0774  lxi h,3013h
+                m.set_hl(0x3013);
+                m.tick(10, 0x0777)?;
+                // 0777  lxi d,1ff3h
+                m.set_de(0x1ff3);
+                m.tick(10, 0x077a)?;
+                // 077a  mvi c,04h
+                m.c = 0x04;
+                m.tick(7, 0x077c)?;
+                // 077c  call 08f3h
+                m.push(0x077f)?;
+                m.pc = 0x08f3;
+                m.tick(17, 0x08f3)?;
+                m.call(0x077f)?;
+                m.pc = 0x077f;
+            },
+            0x077f => {
+                // 077f  lda 20ebh
+                m.a = m.read(0x20eb)?;
+                m.tick(13, 0x0782)?;
+                // 0782  dcr a
+                let value = m.a;
+                let value = value.wrapping_sub(1);
+                m.flags = SZP[value as usize] | (m.flags & CARRY) | if value & 0xf != 0xf { AUX_CARRY } else { 0 };
+                m.a = value;
+                m.tick(5, 0x0783)?;
+                // 0783  lxi h,2810h
+                m.set_hl(0x2810);
+                m.tick(10, 0x0786)?;
+                // 0786  mvi c,14h
+                m.c = 0x14;
+                m.tick(7, 0x0788)?;
+                // 0788  jnz 0857h
+                m.pc = if m.flags & ZERO == 0 { 0x0857 } else { 0x078b };
+                m.tick(10, m.pc)?;
+            },
+            0x078b => {
+                // 078b  lxi d,1acfh
+                m.set_de(0x1acf);
+                m.tick(10, 0x078e)?;
+                // 078e  call 08f3h
+                m.push(0x0791)?;
+                m.pc = 0x08f3;
+                m.tick(17, 0x08f3)?;
+                m.call(0x0791)?;
+                // 0791  in 01h
+                m.a = m.input(0x01)?;
+                m.tick(10, 0x0793)?;
+                // 0793  ani 04h
+                let value: u8 = 0x04;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(7, 0x0795)?;
+                // 0795  jz 077fh
+                m.pc = if m.flags & ZERO != 0 { 0x077f } else { 0x0798 };
+                m.tick(10, m.pc)?;
+            },
+            0x0798 => {
+                // 0798  mvi b,99h
+                m.b = 0x99;
+                m.tick(7, 0x079a)?;
+                // 079a  xra a
+                let value = m.a;
+                m.a ^= value;
+                m.flags = SZP[m.a as usize] | if m.a > 0xf { AUX_CARRY } else { 0 };
+                m.tick(4, 0x079b)?;
+                m.pc = 0x079b;
+            },
+            0x079b => {
+                // 079b  sta 20ceh
+                m.write(0x20ce, m.a)?;
+                m.tick(13, 0x079e)?;
+                // 079e  lda 20ebh
+                m.a = m.read(0x20eb)?;
+                m.tick(13, 0x07a1)?;
+                // 07a1  add b
+                let value = m.b;
+                let result = m.a as u16 + value as u16;
+                m.flags = SZP[result as u8 as usize] | (result >> 8) as u8 | if (m.a & 0xf) + (value & 0xf) > 0xf { AUX_CARRY } else { 0 };
+                m.a = result as u8;
+                m.tick(4, 0x07a2)?;
+                // 07a2  daa
+                m.daa();
+                m.tick(4, 0x07a3)?;
+                // 07a3  sta 20ebh
+                m.write(0x20eb, m.a)?;
+                m.tick(13, 0x07a6)?;
+                // 07a6  call 1947h
+                m.push(0x07a9)?;
+                m.pc = 0x1947;
+                m.tick(17, 0x1947)?;
+                m.call(0x07a9)?;
+                // 07a9  lxi h,0000h
+                m.set_hl(0x0000);
+                m.tick(10, 0x07ac)?;
+                // 07ac  shld 20f8h
+                m.write(0x20f8, m.l)?;
+                m.write(0x20f9, m.h)?;
+                m.tick(16, 0x07af)?;
+                // 07af  shld 20fch
+                m.write(0x20fc, m.l)?;
+                m.write(0x20fd, m.h)?;
+                m.tick(16, 0x07b2)?;
+                // 07b2  call 1925h
+                m.push(0x07b5)?;
+                m.pc = 0x1925;
+                m.tick(17, 0x1925)?;
+                m.call(0x07b5)?;
+                // 07b5  call 192bh
+                m.push(0x07b8)?;
+                m.pc = 0x192b;
+                m.tick(17, 0x192b)?;
+                m.call(0x07b8)?;
+                // 07b8  call 19d7h
+                m.push(0x07bb)?;
+                m.pc = 0x19d7;
+                m.tick(17, 0x19d7)?;
+                m.call(0x07bb)?;
+                // 07bb  lxi h,0101h
+                m.set_hl(0x0101);
+                m.tick(10, 0x07be)?;
+                // 07be  mov a,h
+                m.a = m.h;
+                m.tick(5, 0x07bf)?;
+                // 07bf  sta 20efh
+                m.write(0x20ef, m.a)?;
+                m.tick(13, 0x07c2)?;
+                // 07c2  shld 20e7h
+                m.write(0x20e7, m.l)?;
+                m.write(0x20e8, m.h)?;
+                m.tick(16, 0x07c5)?;
+                // 07c5  shld 20e5h
+                m.write(0x20e5, m.l)?;
+                m.write(0x20e6, m.h)?;
+                m.tick(16, 0x07c8)?;
+                // 07c8  call 1956h
+                m.push(0x07cb)?;
+                m.pc = 0x1956;
+                m.tick(17, 0x1956)?;
+                m.call(0x07cb)?;
+                // 07cb  call 01efh
+                m.push(0x07ce)?;
+                m.pc = 0x01ef;
+                m.tick(17, 0x01ef)?;
+                m.call(0x07ce)?;
+                // 07ce  call 01f5h
+                m.push(0x07d1)?;
+                m.pc = 0x01f5;
+                m.tick(17, 0x01f5)?;
+                m.call(0x07d1)?;
+                // 07d1  call 08d1h
+                m.push(0x07d4)?;
+                m.pc = 0x08d1;
+                m.tick(17, 0x08d1)?;
+                m.call(0x07d4)?;
+                // 07d4  sta 21ffh
+                m.write(0x21ff, m.a)?;
+                m.tick(13, 0x07d7)?;
+                // 07d7  sta 22ffh
+                m.write(0x22ff, m.a)?;
+                m.tick(13, 0x07da)?;
+                // 07da  call 00d7h
+                m.push(0x07dd)?;
+                m.pc = 0x00d7;
+                m.tick(17, 0x00d7)?;
+                m.call(0x07dd)?;
+                // 07dd  xra a
+                let value = m.a;
+                m.a ^= value;
+                m.flags = SZP[m.a as usize] | if m.a > 0xf { AUX_CARRY } else { 0 };
+                m.tick(4, 0x07de)?;
+                // 07de  sta 21feh
+                m.write(0x21fe, m.a)?;
+                m.tick(13, 0x07e1)?;
+                // 07e1  sta 22feh
+                m.write(0x22fe, m.a)?;
+                m.tick(13, 0x07e4)?;
+                // 07e4  call 01c0h
+                m.push(0x07e7)?;
+                m.pc = 0x01c0;
+                m.tick(17, 0x01c0)?;
+                m.call(0x07e7)?;
+                // 07e7  call 1904h
+                m.push(0x07ea)?;
+                m.pc = 0x1904;
+                m.tick(17, 0x1904)?;
+                m.call(0x07ea)?;
+                // 07ea  lxi h,3878h
+                m.set_hl(0x3878);
+                m.tick(10, 0x07ed)?;
+                // 07ed  shld 21fch
+                m.write(0x21fc, m.l)?;
+                m.write(0x21fd, m.h)?;
+                m.tick(16, 0x07f0)?;
+                // 07f0  shld 22fch
+                m.write(0x22fc, m.l)?;
+                m.write(0x22fd, m.h)?;
+                m.tick(16, 0x07f3)?;
+                // 07f3  call 01e4h
+                m.push(0x07f6)?;
+                m.pc = 0x01e4;
+                m.tick(17, 0x01e4)?;
+                m.call(0x07f6)?;
+                // 07f6  call 1a7fh
+                m.push(0x07f9)?;
+                m.pc = 0x1a7f;
+                m.tick(17, 0x1a7f)?;
+                m.call(0x07f9)?;
+                // 07f9  call 088dh
+                m.push(0x07fc)?;
+                m.pc = 0x088d;
+                m.tick(17, 0x088d)?;
+                m.call(0x07fc)?;
+                // 07fc  call 09d6h
+                m.push(0x07ff)?;
+                m.pc = 0x09d6;
+                m.tick(17, 0x09d6)?;
+                m.call(0x07ff)?;
+                // 07ff  nop
+                m.tick(4, 0x0800)?;
+                // 0800  xra a
+                let value = m.a;
+                m.a ^= value;
+                m.flags = SZP[m.a as usize] | if m.a > 0xf { AUX_CARRY } else { 0 };
+                m.tick(4, 0x0801)?;
+                // 0801  sta 20c1h
+                m.write(0x20c1, m.a)?;
+                m.tick(13, 0x0804)?;
+                m.pc = 0x0804;
+            },
+            0x0804 => {
+                // 0804  call 01cfh
+                m.push(0x0807)?;
+                m.pc = 0x01cf;
+                m.tick(17, 0x01cf)?;
+                m.call(0x0807)?;
+                // 0807  lda 2067h
+                m.a = m.read(0x2067)?;
+                m.tick(13, 0x080a)?;
+                // 080a  rrc
+                m.flags = (m.flags & !CARRY) | (m.a & 1);
+                m.a = m.a.rotate_right(1);
+                m.tick(4, 0x080b)?;
+                // 080b  jc 0872h
+                m.pc = if m.flags & CARRY != 0 { 0x0872 } else { 0x080e };
+                m.tick(10, m.pc)?;
+            },
+            0x080e => {
+                // 080e  call 0213h
+                m.push(0x0811)?;
+                m.pc = 0x0213;
+                m.tick(17, 0x0213)?;
+                m.call(0x0811)?;
+                // 0811  call 01cfh
+                m.push(0x0814)?;
+                m.pc = 0x01cf;
+                m.tick(17, 0x01cf)?;
+                m.call(0x0814)?;
+                m.pc = 0x0814;
+            },
+            0x0814 => {
+                // 0814  call 00b1h
+                m.push(0x0817)?;
+                m.pc = 0x00b1;
+                m.tick(17, 0x00b1)?;
+                m.call(0x0817)?;
+                // 0817  call 19d1h
+                m.push(0x081a)?;
+                m.pc = 0x19d1;
+                m.tick(17, 0x19d1)?;
+                m.call(0x081a)?;
+                // 081a  mvi b,20h
+                m.b = 0x20;
+                m.tick(7, 0x081c)?;
+                // 081c  call 18fah
+                m.push(0x081f)?;
+                m.pc = 0x18fa;
+                m.tick(17, 0x18fa)?;
+                m.call(0x081f)?;
+                m.pc = 0x081f;
+            },
+            0x081f => {
+                // 081f  call 1618h
+                m.push(0x0822)?;
+                m.pc = 0x1618;
+                m.tick(17, 0x1618)?;
+                m.call(0x0822)?;
+                // 0822  call 190ah
+                m.push(0x0825)?;
+                m.pc = 0x190a;
+                m.tick(17, 0x190a)?;
+                m.call(0x0825)?;
+                // 0825  call 15f3h
+                m.push(0x0828)?;
+                m.pc = 0x15f3;
+                m.tick(17, 0x15f3)?;
+                m.call(0x0828)?;
+                // 0828  call 0988h
+                m.push(0x082b)?;
+                m.pc = 0x0988;
+                m.tick(17, 0x0988)?;
+                m.call(0x082b)?;
+                // 082b  lda 2082h
+                m.a = m.read(0x2082)?;
+                m.tick(13, 0x082e)?;
+                // 082e  ana a
+                let value = m.a;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(4, 0x082f)?;
+                // 082f  jz 09efh
+                m.pc = if m.flags & ZERO != 0 { 0x09ef } else { 0x0832 };
+                m.tick(10, m.pc)?;
+            },
+            0x0832 => {
+                // 0832  call 170eh
+                m.push(0x0835)?;
+                m.pc = 0x170e;
+                m.tick(17, 0x170e)?;
+                m.call(0x0835)?;
+                // 0835  call 0935h
+                m.push(0x0838)?;
+                m.pc = 0x0935;
+                m.tick(17, 0x0935)?;
+                m.call(0x0838)?;
+                // 0838  call 08d8h
+                m.push(0x083b)?;
+                m.pc = 0x08d8;
+                m.tick(17, 0x08d8)?;
+                m.call(0x083b)?;
+                // 083b  call 172ch
+                m.push(0x083e)?;
+                m.pc = 0x172c;
+                m.tick(17, 0x172c)?;
+                m.call(0x083e)?;
+                // 083e  call 0a59h
+                m.push(0x0841)?;
+                m.pc = 0x0a59;
+                m.tick(17, 0x0a59)?;
+                m.call(0x0841)?;
+                // 0841  jz 0849h
+                m.pc = if m.flags & ZERO != 0 { 0x0849 } else { 0x0844 };
+                m.tick(10, m.pc)?;
+            },
+            0x0844 => {
+                // 0844  mvi b,04h
+                m.b = 0x04;
+                m.tick(7, 0x0846)?;
+                // 0846  call 18fah
+                m.push(0x0849)?;
+                m.pc = 0x18fa;
+                m.tick(17, 0x18fa)?;
+                m.call(0x0849)?;
+                m.pc = 0x0849;
+            },
+            0x0849 => {
+                // 0849  call 1775h
+                m.push(0x084c)?;
+                m.pc = 0x1775;
+                m.tick(17, 0x1775)?;
+                m.call(0x084c)?;
+                // 084c  out 06h
+                m.output(0x06, m.a)?;
+                m.tick(10, 0x084e)?;
+                // 084e  call 1804h
+                m.push(0x0851)?;
+                m.pc = 0x1804;
+                m.tick(17, 0x1804)?;
+                m.call(0x0851)?;
+                // 0851  jmp 081fh
+                m.pc = 0x081f;
+                m.tick(10, 0x081f)?;
+            },
+            0x0857 => {
+                // 0857  lxi d,1abah
+                m.set_de(0x1aba);
+                m.tick(10, 0x085a)?;
+                // 085a  call 08f3h
+                m.push(0x085d)?;
+                m.pc = 0x08f3;
+                m.tick(17, 0x08f3)?;
+                m.call(0x085d)?;
+                // 085d  mvi b,98h
+                m.b = 0x98;
+                m.tick(7, 0x085f)?;
+                // 085f  in 01h
+                m.a = m.input(0x01)?;
+                m.tick(10, 0x0861)?;
+                // 0861  rrc
+                m.flags = (m.flags & !CARRY) | (m.a & 1);
+                m.a = m.a.rotate_right(1);
+                m.tick(4, 0x0862)?;
+                // 0862  rrc
+                m.flags = (m.flags & !CARRY) | (m.a & 1);
+                m.a = m.a.rotate_right(1);
+                m.tick(4, 0x0863)?;
+                // 0863  jc 086dh
+                m.pc = if m.flags & CARRY != 0 { 0x086d } else { 0x0866 };
+                m.tick(10, m.pc)?;
+            },
+            0x0866 => {
+                // 0866  rrc
+                m.flags = (m.flags & !CARRY) | (m.a & 1);
+                m.a = m.a.rotate_right(1);
+                m.tick(4, 0x0867)?;
+                // 0867  jc 0798h
+                m.pc = if m.flags & CARRY != 0 { 0x0798 } else { 0x086a };
+                m.tick(10, m.pc)?;
+            },
+            0x086a => {
+                // 086a  jmp 077fh
+                m.pc = 0x077f;
+                m.tick(10, 0x077f)?;
+            },
+            0x086d => {
+                // 086d  mvi a,01h
+                m.a = 0x01;
+                m.tick(7, 0x086f)?;
+                // 086f  jmp 079bh
+                m.pc = 0x079b;
+                m.tick(10, 0x079b)?;
+            },
+            0x0872 => {
+                // 0872  call 021ah
+                m.push(0x0875)?;
+                m.pc = 0x021a;
+                m.tick(17, 0x021a)?;
+                m.call(0x0875)?;
+                // 0875  jmp 0814h
+                m.pc = 0x0814;
+                m.tick(10, 0x0814)?;
+            },
+            0x09ef => {
+                // 09ef  call 0a3ch
+                m.push(0x09f2)?;
+                m.pc = 0x0a3c;
+                m.tick(17, 0x0a3c)?;
+                m.call(0x09f2)?;
+                // 09f2  xra a
+                let value = m.a;
+                m.a ^= value;
+                m.flags = SZP[m.a as usize] | if m.a > 0xf { AUX_CARRY } else { 0 };
+                m.tick(4, 0x09f3)?;
+                // 09f3  sta 20e9h
+                m.write(0x20e9, m.a)?;
+                m.tick(13, 0x09f6)?;
+                // 09f6  call 09d6h
+                m.push(0x09f9)?;
+                m.pc = 0x09d6;
+                m.tick(17, 0x09d6)?;
+                m.call(0x09f9)?;
+                // 09f9  lda 2067h
+                m.a = m.read(0x2067)?;
+                m.tick(13, 0x09fc)?;
+                // 09fc  push psw
+                m.push((m.a as u16) << 8 | m.flags as u16)?;
+                m.tick(11, 0x09fd)?;
+                // 09fd  call 01e4h
+                m.push(0x0a00)?;
+                m.pc = 0x01e4;
+                m.tick(17, 0x01e4)?;
+                m.call(0x0a00)?;
+                // 0a00  pop psw
+                let value = m.pop()?;
+                m.a = (value >> 8) as u8;
+                m.flags = value as u8 & (SIGN | ZERO | AUX_CARRY | PARITY | CARRY);
+                m.tick(10, 0x0a01)?;
+                // 0a01  sta 2067h
+                m.write(0x2067, m.a)?;
+                m.tick(13, 0x0a04)?;
+                // 0a04  lda 2067h
+                m.a = m.read(0x2067)?;
+                m.tick(13, 0x0a07)?;
+                // 0a07  mov h,a
+                m.h = m.a;
+                m.tick(5, 0x0a08)?;
+                // 0a08  push h
+                m.push(m.hl())?;
+                m.tick(11, 0x0a09)?;
+                // 0a09  mvi l,0feh
+                m.l = 0xfe;
+                m.tick(7, 0x0a0b)?;
+                // 0a0b  mov a,m
+                m.a = m.read(m.hl())?;
+                m.tick(7, 0x0a0c)?;
+                // 0a0c  ani 07h
+                let value: u8 = 0x07;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(7, 0x0a0e)?;
+                // 0a0e  inr a
+                let value = m.a;
+                let value = value.wrapping_add(1);
+                m.flags = SZP[value as usize] | (m.flags & CARRY) | if value & 0xf == 0 { AUX_CARRY } else { 0 };
+                m.a = value;
+                m.tick(5, 0x0a0f)?;
+                // 0a0f  mov m,a
+                m.write(m.hl(), m.a)?;
+                m.tick(7, 0x0a10)?;
+                // 0a10  lxi h,1da2h
+                m.set_hl(0x1da2);
+                m.tick(10, 0x0a13)?;
+                m.pc = 0x0a13;
+            },
+            0x0a13 => {
+                // 0a13  inx h
+                m.set_hl(m.hl().wrapping_add(1));
+                m.tick(5, 0x0a14)?;
+                // 0a14  dcr a
+                let value = m.a;
+                let value = value.wrapping_sub(1);
+                m.flags = SZP[value as usize] | (m.flags & CARRY) | if value & 0xf != 0xf { AUX_CARRY } else { 0 };
+                m.a = value;
+                m.tick(5, 0x0a15)?;
+                // 0a15  jnz 0a13h
+                m.pc = if m.flags & ZERO == 0 { 0x0a13 } else { 0x0a18 };
+                m.tick(10, m.pc)?;
+            },
+            0x0a18 => {
+                // 0a18  mov a,m
+                m.a = m.read(m.hl())?;
+                m.tick(7, 0x0a19)?;
+                // 0a19  pop h
+                let value = m.pop()?;
+                m.set_hl(value);
+                m.tick(10, 0x0a1a)?;
+                // 0a1a  mvi l,0fch
+                m.l = 0xfc;
+                m.tick(7, 0x0a1c)?;
+                // 0a1c  mov m,a
+                m.write(m.hl(), m.a)?;
+                m.tick(7, 0x0a1d)?;
+                // 0a1d  inx h
+                m.set_hl(m.hl().wrapping_add(1));
+                m.tick(5, 0x0a1e)?;
+                // 0a1e  mvi m,38h
+                m.write(m.hl(), 0x38)?;
+                m.tick(10, 0x0a20)?;
+                // 0a20  mov a,h
+                m.a = m.h;
+                m.tick(5, 0x0a21)?;
+                // 0a21  rrc
+                m.flags = (m.flags & !CARRY) | (m.a & 1);
+                m.a = m.a.rotate_right(1);
+                m.tick(4, 0x0a22)?;
+                // 0a22  jc 0a33h
+                m.pc = if m.flags & CARRY != 0 { 0x0a33 } else { 0x0a25 };
+                m.tick(10, m.pc)?;
+            },
+            0x0a25 => {
+                // 0a25  mvi a,21h
+                m.a = 0x21;
+                m.tick(7, 0x0a27)?;
+                // 0a27  sta 2098h
+                m.write(0x2098, m.a)?;
+                m.tick(13, 0x0a2a)?;
+                // 0a2a  call 01f5h
+                m.push(0x0a2d)?;
+                m.pc = 0x01f5;
+                m.tick(17, 0x01f5)?;
+                m.call(0x0a2d)?;
+                // 0a2d  call 1904h
+                m.push(0x0a30)?;
+                m.pc = 0x1904;
+                m.tick(17, 0x1904)?;
+                m.call(0x0a30)?;
+                // 0a30  jmp 0804h
+                m.pc = 0x0804;
+                m.tick(10, 0x0804)?;
+            },
+            0x0a33 => {
+                // 0a33  call 01efh
+                m.push(0x0a36)?;
+                m.pc = 0x01ef;
+                m.tick(17, 0x01ef)?;
+                m.call(0x0a36)?;
+                // 0a36  call 01c0h
+                m.push(0x0a39)?;
+                m.pc = 0x01c0;
+                m.tick(17, 0x01c0)?;
+                m.call(0x0a39)?;
+                // 0a39  jmp 0804h
+                m.pc = 0x0804;
+                m.tick(10, 0x0804)?;
+            },
+            _ => return Ok(()),
+        }
+    }
+}
+
+fn sub_0030(m: &mut Machine) -> Result<(), Stop> {
+    loop {
+        match m.pc {
+            0x0030 => {
+                // 0030  cpi 99h
+                let value: u8 = 0x99;
+                let result = (m.a as u16).wrapping_sub(value as u16);
+                m.flags = SZP[result as u8 as usize] | (result > 0xff) as u8 | if (m.a & 0xf).wrapping_sub(value & 0xf) > 0xf { AUX_CARRY } else { 0 };
+                m.tick(7, 0x0032)?;
+                // 0032  jz 003eh
+                m.pc = if m.flags & ZERO != 0 { 0x003e } else { 0x0035 };
+                m.tick(10, m.pc)?;
+            },
+            0x0035 => {
+                // 0035  adi 01h
+                let value: u8 = 0x01;
+                let result = m.a as u16 + value as u16;
+                m.flags = SZP[result as u8 as usize] | (result >> 8) as u8 | if (m.a & 0xf) + (value & 0xf) > 0xf { AUX_CARRY } else { 0 };
+                m.a = result as u8;
+                m.tick(7, 0x0037)?;
+                // 0037  daa
+                m.daa();
+                m.tick(4, 0x0038)?;
+                // 0038  sta 20ebh
+                m.write(0x20eb, m.a)?;
+                m.tick(13, 0x003b)?;
+                // 003b  call 1947h
+                m.push(0x003e)?;
+                m.pc = 0x1947;
+                m.tick(17, 0x1947)?;
+                m.call(0x003e)?;
+                m.pc = 0x003e;
+            },
+            0x003e => {
+                // 003e  xra a
+                let value = m.a;
+                m.a ^= value;
+                m.flags = SZP[m.a as usize] | if m.a > 0xf { AUX_CARRY } else { 0 };
+                m.tick(4, 0x003f)?;
+                // 003f  sta 20eah
+                m.write(0x20ea, m.a)?;
+                m.tick(13, 0x0042)?;
+                // 0042  lda 20e9h
+                m.a = m.read(0x20e9)?;
+                m.tick(13, 0x0045)?;
+                // 0045  ana a
+                let value = m.a;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(4, 0x0046)?;
+                // 0046  jz 0082h
+                m.pc = if m.flags & ZERO != 0 { 0x0082 } else { 0x0049 };
+                m.tick(10, m.pc)?;
+            },
+            0x0049 => {
+                // 0049  lda 20efh
+                m.a = m.read(0x20ef)?;
+                m.tick(13, 0x004c)?;
+                // 004c  ana a
+                let value = m.a;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(4, 0x004d)?;
+                // 004d  jnz 006fh
+                m.pc = if m.flags & ZERO == 0 { 0x006f } else { 0x0050 };
+                m.tick(10, m.pc)?;
+            },
+            0x0050 => {
+                // 0050  lda 20ebh
+                m.a = m.read(0x20eb)?;
+                m.tick(13, 0x0053)?;
+                // 0053  ana a
+                let value = m.a;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(4, 0x0054)?;
+                // 0054  jnz 005dh
+                m.pc = if m.flags & ZERO == 0 { 0x005d } else { 0x0057 };
+                m.tick(10, m.pc)?;
+            },
+            0x0057 => {
+                // 0057  call 0abfh
+                m.push(0x005a)?;
+                m.pc = 0x0abf;
+                m.tick(17, 0x0abf)?;
+                m.call(0x005a)?;
+                // 005a  jmp 0082h
+                m.pc = 0x0082;
+                m.tick(10, 0x0082)?;
+            },
+            0x005d => {
+                // 005d  lda 2093h
+                m.a = m.read(0x2093)?;
+                m.tick(13, 0x0060)?;
+                // 0060  ana a
+                let value = m.a;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(4, 0x0061)?;
+                // 0061  jnz 0082h
+                m.pc = if m.flags & ZERO == 0 { 0x0082 } else { 0x0064 };
+                m.tick(10, m.pc)?;
+            },
+            0x0064 => {
+                // 0064  jmp 0765h
+                m.pc = 0x0765;
+                m.tick(10, 0x0765)?;
+            },
+            0x006f => {
+                // 006f  call 1740h
+                m.push(0x0072)?;
+                m.pc = 0x1740;
+                m.tick(17, 0x1740)?;
+                m.call(0x0072)?;
+                // 0072  lda 2032h
+                m.a = m.read(0x2032)?;
+                m.tick(13, 0x0075)?;
+                // 0075  sta 2080h
+                m.write(0x2080, m.a)?;
+                m.tick(13, 0x0078)?;
+                // 0078  call 0100h
+                m.push(0x007b)?;
+                m.pc = 0x0100;
+                m.tick(17, 0x0100)?;
+                m.call(0x007b)?;
+                // 007b  call 0248h
+                m.push(0x007e)?;
+                m.pc = 0x0248;
+                m.tick(17, 0x0248)?;
+                m.call(0x007e)?;
+                // 007e  call 0913h
+                m.push(0x0081)?;
+                m.pc = 0x0913;
+                m.tick(17, 0x0913)?;
+                m.call(0x0081)?;
+                // 0081  nop
+                m.tick(4, 0x0082)?;
+                m.pc = 0x0082;
+            },
+            0x0082 => {
+                // 0082  pop h
+                let value = m.pop()?;
+                m.set_hl(value);
+                m.tick(10, 0x0083)?;
+                // 0083  pop d
+                let value = m.pop()?;
+                m.set_de(value);
+                m.tick(10, 0x0084)?;
+                // 0084  pop b
+                let value = m.pop()?;
+                m.set_bc(value);
+                m.tick(10, 0x0085)?;
+                // 0085  pop psw
+                let value = m.pop()?;
+                m.a = (value >> 8) as u8;
+                m.flags = value as u8 & (SIGN | ZERO | AUX_CARRY | PARITY | CARRY);
+                m.tick(10, 0x0086)?;
+                m.pc = 0x0086;
+            },
+            // 0086  ei
+            0x0086 => m.interpret()?,
+            0x0765 => {
+                // 0765  mvi a,01h
+                m.a = 0x01;
+                m.tick(7, 0x0767)?;
+                // 0767  sta 2093h
+                m.write(0x2093, m.a)?;
+                m.tick(13, 0x076a)?;
+                // 076a  lxi sp,2400h
+                m.sp = 0x2400;
+                m.tick(10, 0x076d)?;
+                m.pc = 0x076d;
+            },
+            // 076d  ei
+            0x076d => m.interpret()?,
+            0x0771 => {
+                // 0771  call 09d6h
+                m.push(0x0774)?;
+                m.pc = 0x09d6;
+                m.tick(17, 0x09d6)?;
+                m.call(0x0774)?;
+                // 0774  lxi h,3013h
+                m.set_hl(0x3013);
+                m.tick(10, 0x0777)?;
+                // 0777  lxi d,1ff3h
+                m.set_de(0x1ff3);
+                m.tick(10, 0x077a)?;
+                // 077a  mvi c,04h
+                m.c = 0x04;
+                m.tick(7, 0x077c)?;
+                // 077c  call 08f3h
+                m.push(0x077f)?;
+                m.pc = 0x08f3;
+                m.tick(17, 0x08f3)?;
+                m.call(0x077f)?;
+                m.pc = 0x077f;
+            },
+            0x077f => {
+                // 077f  lda 20ebh
+                m.a = m.read(0x20eb)?;
+                m.tick(13, 0x0782)?;
+                // 0782  dcr a
+                let value = m.a;
+                let value = value.wrapping_sub(1);
+                m.flags = SZP[value as usize] | (m.flags & CARRY) | if value & 0xf != 0xf { AUX_CARRY } else { 0 };
+                m.a = value;
+                m.tick(5, 0x0783)?;
+                // 0783  lxi h,2810h
+                m.set_hl(0x2810);
+                m.tick(10, 0x0786)?;
+                // 0786  mvi c,14h
+                m.c = 0x14;
+                m.tick(7, 0x0788)?;
+                // 0788  jnz 0857h
+                m.pc = if m.flags & ZERO == 0 { 0x0857 } else { 0x078b };
+                m.tick(10, m.pc)?;
+            },
+            0x078b => {
+                // 078b  lxi d,1acfh
+                m.set_de(0x1acf);
+                m.tick(10, 0x078e)?;
+                // 078e  call 08f3h
+                m.push(0x0791)?;
+                m.pc = 0x08f3;
+                m.tick(17, 0x08f3)?;
+                m.call(0x0791)?;
+                // 0791  in 01h
+                m.a = m.input(0x01)?;
+                m.tick(10, 0x0793)?;
+                // 0793  ani 04h
+                let value: u8 = 0x04;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(7, 0x0795)?;
+                // 0795  jz 077fh
+                m.pc = if m.flags & ZERO != 0 { 0x077f } else { 0x0798 };
+                m.tick(10, m.pc)?;
+            },
+            0x0798 => {
+                // 0798  mvi b,99h
+                m.b = 0x99;
+                m.tick(7, 0x079a)?;
+                // 079a  xra a
+                let value = m.a;
+                m.a ^= value;
+                m.flags = SZP[m.a as usize] | if m.a > 0xf { AUX_CARRY } else { 0 };
+                m.tick(4, 0x079b)?;
+                m.pc = 0x079b;
+            },
+            0x079b => {
+                // 079b  sta 20ceh
+                m.write(0x20ce, m.a)?;
+                m.tick(13, 0x079e)?;
+                // 079e  lda 20ebh
+                m.a = m.read(0x20eb)?;
+                m.tick(13, 0x07a1)?;
+                // 07a1  add b
+                let value = m.b;
+                let result = m.a as u16 + value as u16;
+                m.flags = SZP[result as u8 as usize] | (result >> 8) as u8 | if (m.a & 0xf) + (value & 0xf) > 0xf { AUX_CARRY } else { 0 };
+                m.a = result as u8;
+                m.tick(4, 0x07a2)?;
+                // 07a2  daa
+                m.daa();
+                m.tick(4, 0x07a3)?;
+                // 07a3  sta 20ebh
+                m.write(0x20eb, m.a)?;
+                m.tick(13, 0x07a6)?;
+                // 07a6  call 1947h
+                m.push(0x07a9)?;
+                m.pc = 0x1947;
+                m.tick(17, 0x1947)?;
+                m.call(0x07a9)?;
+                // 07a9  lxi h,0000h
+                m.set_hl(0x0000);
+                m.tick(10, 0x07ac)?;
+                // 07ac  shld 20f8h
+                m.write(0x20f8, m.l)?;
+                m.write(0x20f9, m.h)?;
+                m.tick(16, 0x07af)?;
+                // 07af  shld 20fch
+                m.write(0x20fc, m.l)?;
+                m.write(0x20fd, m.h)?;
+                m.tick(16, 0x07b2)?;
+                // 07b2  call 1925h
+                m.push(0x07b5)?;
+                m.pc = 0x1925;
+                m.tick(17, 0x1925)?;
+                m.call(0x07b5)?;
+                // 07b5  call 192bh
+                m.push(0x07b8)?;
+                m.pc = 0x192b;
+                m.tick(17, 0x192b)?;
+                m.call(0x07b8)?;
+                // 07b8  call 19d7h
+                m.push(0x07bb)?;
+                m.pc = 0x19d7;
+                m.tick(17, 0x19d7)?;
+                m.call(0x07bb)?;
+                // 07bb  lxi h,0101h
+                m.set_hl(0x0101);
+                m.tick(10, 0x07be)?;
+                // 07be  mov a,h
+                m.a = m.h;
+                m.tick(5, 0x07bf)?;
+                // 07bf  sta 20efh
+                m.write(0x20ef, m.a)?;
+                m.tick(13, 0x07c2)?;
+                // 07c2  shld 20e7h
+                m.write(0x20e7, m.l)?;
+                m.write(0x20e8, m.h)?;
+                m.tick(16, 0x07c5)?;
+                // 07c5  shld 20e5h
+                m.write(0x20e5, m.l)?;
+                m.write(0x20e6, m.h)?;
+                m.tick(16, 0x07c8)?;
+                // 07c8  call 1956h
+                m.push(0x07cb)?;
+                m.pc = 0x1956;
+                m.tick(17, 0x1956)?;
+                m.call(0x07cb)?;
+                // 07cb  call 01efh
+                m.push(0x07ce)?;
+                m.pc = 0x01ef;
+                m.tick(17, 0x01ef)?;
+                m.call(0x07ce)?;
+                // 07ce  call 01f5h
+                m.push(0x07d1)?;
+                m.pc = 0x01f5;
+                m.tick(17, 0x01f5)?;
+                m.call(0x07d1)?;
+                // 07d1  call 08d1h
+                m.push(0x07d4)?;
+                m.pc = 0x08d1;
+                m.tick(17, 0x08d1)?;
+                m.call(0x07d4)?;
+                // 07d4  sta 21ffh
+                m.write(0x21ff, m.a)?;
+                m.tick(13, 0x07d7)?;
+                // 07d7  sta 22ffh
+                m.write(0x22ff, m.a)?;
+                m.tick(13, 0x07da)?;
+                // 07da  call 00d7h
+                m.push(0x07dd)?;
+                m.pc = 0x00d7;
+                m.tick(17, 0x00d7)?;
+                m.call(0x07dd)?;
+                // 07dd  xra a
+                let value = m.a;
+                m.a ^= value;
+                m.flags = SZP[m.a as usize] | if m.a > 0xf { AUX_CARRY } else { 0 };
+                m.tick(4, 0x07de)?;
+                // 07de  sta 21feh
+                m.write(0x21fe, m.a)?;
+                m.tick(13, 0x07e1)?;
+                // 07e1  sta 22feh
+                m.write(0x22fe, m.a)?;
+                m.tick(13, 0x07e4)?;
+                // 07e4  call 01c0h
+                m.push(0x07e7)?;
+                m.pc = 0x01c0;
+                m.tick(17, 0x01c0)?;
+                m.call(0x07e7)?;
+                // 07e7  call 1904h
+                m.push(0x07ea)?;
+                m.pc = 0x1904;
+                m.tick(17, 0x1904)?;
+                m.call(0x07ea)?;
+                // 07ea  lxi h,3878h
+                m.set_hl(0x3878);
+                m.tick(10, 0x07ed)?;
+                // 07ed  shld 21fch
+                m.write(0x21fc, m.l)?;
+                m.write(0x21fd, m.h)?;
+                m.tick(16, 0x07f0)?;
+                // 07f0  shld 22fch
+                m.write(0x22fc, m.l)?;
+                m.write(0x22fd, m.h)?;
+                m.tick(16, 0x07f3)?;
+                // 07f3  call 01e4h
+                m.push(0x07f6)?;
+                m.pc = 0x01e4;
+                m.tick(17, 0x01e4)?;
+                m.call(0x07f6)?;
+                // 07f6  call 1a7fh
+                m.push(0x07f9)?;
+                m.pc = 0x1a7f;
+                m.tick(17, 0x1a7f)?;
+                m.call(0x07f9)?;
+                // 07f9  call 088dh
+                m.push(0x07fc)?;
+                m.pc = 0x088d;
+                m.tick(17, 0x088d)?;
+                m.call(0x07fc)?;
+                // 07fc  call 09d6h
+                m.push(0x07ff)?;
+                m.pc = 0x09d6;
+                m.tick(17, 0x09d6)?;
+                m.call(0x07ff)?;
+                // 07ff  nop
+                m.tick(4, 0x0800)?;
+                // 0800  xra a
+                let value = m.a;
+                m.a ^= value;
+                m.flags = SZP[m.a as usize] | if m.a > 0xf { AUX_CARRY } else { 0 };
+                m.tick(4, 0x0801)?;
+                // 0801  sta 20c1h
+                m.write(0x20c1, m.a)?;
+                m.tick(13, 0x0804)?;
+                m.pc = 0x0804;
+            },
+            0x0804 => {
+                // 0804  call 01cfh
+                m.push(0x0807)?;
+                m.pc = 0x01cf;
+                m.tick(17, 0x01cf)?;
+                m.call(0x0807)?;
+                // 0807  lda 2067h
+                m.a = m.read(0x2067)?;
+                m.tick(13, 0x080a)?;
+                // 080a  rrc
+                m.flags = (m.flags & !CARRY) | (m.a & 1);
+                m.a = m.a.rotate_right(1);
+                m.tick(4, 0x080b)?;
+                // 080b  jc 0872h
+                m.pc = if m.flags & CARRY != 0 { 0x0872 } else { 0x080e };
+                m.tick(10, m.pc)?;
+            },
+            0x080e => {
+                // 080e  call 0213h
+                m.push(0x0811)?;
+                m.pc = 0x0213;
+                m.tick(17, 0x0213)?;
+                m.call(0x0811)?;
+                // 0811  call 01cfh
+                m.push(0x0814)?;
+                m.pc = 0x01cf;
+                m.tick(17, 0x01cf)?;
+                m.call(0x0814)?;
+                m.pc = 0x0814;
+            },
+            0x0814 => {
+                // 0814  call 00b1h
+                m.push(0x0817)?;
+                m.pc = 0x00b1;
+                m.tick(17, 0x00b1)?;
+                m.call(0x0817)?;
+                // 0817  call 19d1h
+                m.push(0x081a)?;
+                m.pc = 0x19d1;
+                m.tick(17, 0x19d1)?;
+                m.call(0x081a)?;
+                // 081a  mvi b,20h
+                m.b = 0x20;
+                m.tick(7, 0x081c)?;
+                // 081c  call 18fah
+                m.push(0x081f)?;
+                m.pc = 0x18fa;
+                m.tick(17, 0x18fa)?;
+                m.call(0x081f)?;
+                m.pc = 0x081f;
+            },
+            0x081f => {
+                // 081f  call 1618h
+                m.push(0x0822)?;
+                m.pc = 0x1618;
+                m.tick(17, 0x1618)?;
+                m.call(0x0822)?;
+                // 0822  call 190ah
+                m.push(0x0825)?;
+                m.pc = 0x190a;
+                m.tick(17, 0x190a)?;
+                m.call(0x0825)?;
+                // 0825  call 15f3h
+                m.push(0x0828)?;
+                m.pc = 0x15f3;
+                m.tick(17, 0x15f3)?;
+                m.call(0x0828)?;
+                // 0828  call 0988h
+                m.push(0x082b)?;
+                m.pc = 0x0988;
+                m.tick(17, 0x0988)?;
+                m.call(0x082b)?;
+                // 082b  lda 2082h
+                m.a = m.read(0x2082)?;
+                m.tick(13, 0x082e)?;
+                // 082e  ana a
+                let value = m.a;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(4, 0x082f)?;
+                // 082f  jz 09efh
+                m.pc = if m.flags & ZERO != 0 { 0x09ef } else { 0x0832 };
+                m.tick(10, m.pc)?;
+            },
+            0x0832 => {
+                // 0832  call 170eh
+                m.push(0x0835)?;
+                m.pc = 0x170e;
+                m.tick(17, 0x170e)?;
+                m.call(0x0835)?;
+                // 0835  call 0935h
+                m.push(0x0838)?;
+                m.pc = 0x0935;
+                m.tick(17, 0x0935)?;
+                m.call(0x0838)?;
+                // 0838  call 08d8h
+                m.push(0x083b)?;
+                m.pc = 0x08d8;
+                m.tick(17, 0x08d8)?;
+                m.call(0x083b)?;
+                // 083b  call 172ch
+                m.push(0x083e)?;
+                m.pc = 0x172c;
+                m.tick(17, 0x172c)?;
+                m.call(0x083e)?;
+                // 083e  call 0a59h
+                m.push(0x0841)?;
+                m.pc = 0x0a59;
+                m.tick(17, 0x0a59)?;
+                m.call(0x0841)?;
+                // 0841  jz 0849h
+                m.pc = if m.flags & ZERO != 0 { 0x0849 } else { 0x0844 };
+                m.tick(10, m.pc)?;
+            },
+            0x0844 => {
+                // 0844  mvi b,04h
+                m.b = 0x04;
+                m.tick(7, 0x0846)?;
+                // 0846  call 18fah
+                m.push(0x0849)?;
+                m.pc = 0x18fa;
+                m.tick(17, 0x18fa)?;
+                m.call(0x0849)?;
+                m.pc = 0x0849;
+            },
+            0x0849 => {
+                // 0849  call 1775h
+                m.push(0x084c)?;
+                m.pc = 0x1775;
+                m.tick(17, 0x1775)?;
+                m.call(0x084c)?;
+                // 084c  out 06h
+                m.output(0x06, m.a)?;
+                m.tick(10, 0x084e)?;
+                // 084e  call 1804h
+                m.push(0x0851)?;
+                m.pc = 0x1804;
+                m.tick(17, 0x1804)?;
+                m.call(0x0851)?;
+                // 0851  jmp 081fh
+                m.pc = 0x081f;
+                m.tick(10, 0x081f)?;
+            },
+            0x0857 => {
+                // 0857  lxi d,1abah
+                m.set_de(0x1aba);
+                m.tick(10, 0x085a)?;
+                // 085a  call 08f3h
+                m.push(0x085d)?;
+                m.pc = 0x08f3;
+                m.tick(17, 0x08f3)?;
+                m.call(0x085d)?;
+                // 085d  mvi b,98h
+                m.b = 0x98;
+                m.tick(7, 0x085f)?;
+                // 085f  in 01h
+                m.a = m.input(0x01)?;
+                m.tick(10, 0x0861)?;
+                // 0861  rrc
+                m.flags = (m.flags & !CARRY) | (m.a & 1);
+                m.a = m.a.rotate_right(1);
+                m.tick(4, 0x0862)?;
+                // 0862  rrc
+                m.flags = (m.flags & !CARRY) | (m.a & 1);
+                m.a = m.a.rotate_right(1);
+                m.tick(4, 0x0863)?;
+                // 0863  jc 086dh
+                m.pc = if m.flags & CARRY != 0 { 0x086d } else { 0x0866 };
+                m.tick(10, m.pc)?;
+            },
+            0x0866 => {
+                // 0866  rrc
+                m.flags = (m.flags & !CARRY) | (m.a & 1);
+                m.a = m.a.rotate_right(1);
+                m.tick(4, 0x0867)?;
+                // 0867  jc 0798h
+                m.pc = if m.flags & CARRY != 0 { 0x0798 } else { 0x086a };
+                m.tick(10, m.pc)?;
+            },
+            0x086a => {
+                // 086a  jmp 077fh
+                m.pc = 0x077f;
+                m.tick(10, 0x077f)?;
+            },
+            0x086d => {
+                // 086d  mvi a,01h
+                m.a = 0x01;
+                m.tick(7, 0x086f)?;
+                // 086f  jmp 079bh
+                m.pc = 0x079b;
+                m.tick(10, 0x079b)?;
+            },
+            0x0872 => {
+                // 0872  call 021ah
+                m.push(0x0875)?;
+                m.pc = 0x021a;
+                m.tick(17, 0x021a)?;
+                m.call(0x0875)?;
+                // 0875  jmp 0814h
+                m.pc = 0x0814;
+                m.tick(10, 0x0814)?;
+            },
+            0x09ef => {
+                // 09ef  call 0a3ch
+                m.push(0x09f2)?;
+                m.pc = 0x0a3c;
+                m.tick(17, 0x0a3c)?;
+                m.call(0x09f2)?;
+                // 09f2  xra a
+                let value = m.a;
+                m.a ^= value;
+                m.flags = SZP[m.a as usize] | if m.a > 0xf { AUX_CARRY } else { 0 };
+                m.tick(4, 0x09f3)?;
+                // 09f3  sta 20e9h
+                m.write(0x20e9, m.a)?;
+                m.tick(13, 0x09f6)?;
+                // 09f6  call 09d6h
+                m.push(0x09f9)?;
+                m.pc = 0x09d6;
+                m.tick(17, 0x09d6)?;
+                m.call(0x09f9)?;
+                // 09f9  lda 2067h
+                m.a = m.read(0x2067)?;
+                m.tick(13, 0x09fc)?;
+                // 09fc  push psw
+                m.push((m.a as u16) << 8 | m.flags as u16)?;
+                m.tick(11, 0x09fd)?;
+                // 09fd  call 01e4h
+                m.push(0x0a00)?;
+                m.pc = 0x01e4;
+                m.tick(17, 0x01e4)?;
+                m.call(0x0a00)?;
+                // 0a00  pop psw
+                let value = m.pop()?;
+                m.a = (value >> 8) as u8;
+                m.flags = value as u8 & (SIGN | ZERO | AUX_CARRY | PARITY | CARRY);
+                m.tick(10, 0x0a01)?;
+                // 0a01  sta 2067h
+                m.write(0x2067, m.a)?;
+                m.tick(13, 0x0a04)?;
+                // 0a04  lda 2067h
+                m.a = m.read(0x2067)?;
+                m.tick(13, 0x0a07)?;
+                // 0a07  mov h,a
+                m.h = m.a;
+                m.tick(5, 0x0a08)?;
+                // 0a08  push h
+                m.push(m.hl())?;
+                m.tick(11, 0x0a09)?;
+                // 0a09  mvi l,0feh
+                m.l = 0xfe;
+                m.tick(7, 0x0a0b)?;
+                // 0a0b  mov a,m
+                m.a = m.read(m.hl())?;
+                m.tick(7, 0x0a0c)?;
+                // 0a0c  ani 07h
+                let value: u8 = 0x07;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(7, 0x0a0e)?;
+                // 0a0e  inr a
+                let value = m.a;
+                let value = value.wrapping_add(1);
+                m.flags = SZP[value as usize] | (m.flags & CARRY) | if value & 0xf == 0 { AUX_CARRY } else { 0 };
+                m.a = value;
+                m.tick(5, 0x0a0f)?;
+                // 0a0f  mov m,a
+                m.write(m.hl(), m.a)?;
+                m.tick(7, 0x0a10)?;
+                // 0a10  lxi h,1da2h
+                m.set_hl(0x1da2);
+                m.tick(10, 0x0a13)?;
+                m.pc = 0x0a13;
+            },
+            0x0a13 => {
+                // 0a13  inx h
+                m.set_hl(m.hl().wrapping_add(1));
+                m.tick(5, 0x0a14)?;
+                // 0a14  dcr a
+                let value = m.a;
+                let value = value.wrapping_sub(1);
+                m.flags = SZP[value as usize] | (m.flags & CARRY) | if value & 0xf != 0xf { AUX_CARRY } else { 0 };
+                m.a = value;
+                m.tick(5, 0x0a15)?;
+                // 0a15  jnz 0a13h
+                m.pc = if m.flags & ZERO == 0 { 0x0a13 } else { 0x0a18 };
+                m.tick(10, m.pc)?;
+            },
+            0x0a18 => {
+                // 0a18  mov a,m
+                m.a = m.read(m.hl())?;
+                m.tick(7, 0x0a19)?;
+                // 0a19  pop h
+                let value = m.pop()?;
+                m.set_hl(value);
+                m.tick(10, 0x0a1a)?;
+                // 0a1a  mvi l,0fch
+                m.l = 0xfc;
+                m.tick(7, 0x0a1c)?;
+                // 0a1c  mov m,a
+                m.write(m.hl(), m.a)?;
+                m.tick(7, 0x0a1d)?;
+                // 0a1d  inx h
+                m.set_hl(m.hl().wrapping_add(1));
+                m.tick(5, 0x0a1e)?;
+                // 0a1e  mvi m,38h
+                m.write(m.hl(), 0x38)?;
+                m.tick(10, 0x0a20)?;
+                // 0a20  mov a,h
+                m.a = m.h;
+                m.tick(5, 0x0a21)?;
+                // 0a21  rrc
+                m.flags = (m.flags & !CARRY) | (m.a & 1);
+                m.a = m.a.rotate_right(1);
+                m.tick(4, 0x0a22)?;
+                // 0a22  jc 0a33h
+                m.pc = if m.flags & CARRY != 0 { 0x0a33 } else { 0x0a25 };
+                m.tick(10, m.pc)?;
+            },
+            0x0a25 => {
+                // 0a25  mvi a,21h
+                m.a = 0x21;
+                m.tick(7, 0x0a27)?;
+                // 0a27  sta 2098h
+                m.write(0x2098, m.a)?;
+                m.tick(13, 0x0a2a)?;
+                // 0a2a  call 01f5h
+                m.push(0x0a2d)?;
+                m.pc = 0x01f5;
+                m.tick(17, 0x01f5)?;
+                m.call(0x0a2d)?;
+                // 0a2d  call 1904h
+                m.push(0x0a30)?;
+                m.pc = 0x1904;
+                m.tick(17, 0x1904)?;
+                m.call(0x0a30)?;
+                // 0a30  jmp 0804h
+                m.pc = 0x0804;
+                m.tick(10, 0x0804)?;
+            },
+            0x0a33 => {
+                // 0a33  call 01efh
+                m.push(0x0a36)?;
+                m.pc = 0x01ef;
+                m.tick(17, 0x01ef)?;
+                m.call(0x0a36)?;
+                // 0a36  call 01c0h
+                m.push(0x0a39)?;
+                m.pc = 0x01c0;
+                m.tick(17, 0x01c0)?;
+                m.call(0x0a39)?;
+                // 0a39  jmp 0804h
+                m.pc = 0x0804;
+                m.tick(10, 0x0804)?;
+            },
+            _ => return Ok(()),
+        }
+    }
+}
+
+fn sub_0038(m: &mut Machine) -> Result<(), Stop> {
+    loop {
+        match m.pc {
+            0x0038 => {
+                // 0038  sta 20ebh
+                m.write(0x20eb, m.a)?;
+                m.tick(13, 0x003b)?;
+                // 003b  call 1947h
+                m.push(0x003e)?;
+                m.pc = 0x1947;
+                m.tick(17, 0x1947)?;
+                m.call(0x003e)?;
+                // 003e  xra a
+                let value = m.a;
+                m.a ^= value;
+                m.flags = SZP[m.a as usize] | if m.a > 0xf { AUX_CARRY } else { 0 };
+                m.tick(4, 0x003f)?;
+                // 003f  sta 20eah
+                m.write(0x20ea, m.a)?;
+                m.tick(13, 0x0042)?;
+                // 0042  lda 20e9h
+                m.a = m.read(0x20e9)?;
+                m.tick(13, 0x0045)?;
+                // 0045  ana a
+                let value = m.a;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(4, 0x0046)?;
+                // 0046  jz 0082h
+                m.pc = if m.flags & ZERO != 0 { 0x0082 } else { 0x0049 };
+                m.tick(10, m.pc)?;
+            },
+            0x0049 => {
+                // 0049  lda 20efh
+                m.a = m.read(0x20ef)?;
+                m.tick(13, 0x004c)?;
+                // 004c  ana a
+                let value = m.a;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(4, 0x004d)?;
+                // 004d  jnz 006fh
+                m.pc = if m.flags & ZERO == 0 { 0x006f } else { 0x0050 };
+                m.tick(10, m.pc)?;
+            },
+            0x0050 => {
+                // 0050  lda 20ebh
+                m.a = m.read(0x20eb)?;
+                m.tick(13, 0x0053)?;
+                // 0053  ana a
+                let value = m.a;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(4, 0x0054)?;
+                // 0054  jnz 005dh
+                m.pc = if m.flags & ZERO == 0 { 0x005d } else { 0x0057 };
+                m.tick(10, m.pc)?;
+            },
+            0x0057 => {
+                // 0057  call 0abfh
+                m.push(0x005a)?;
+                m.pc = 0x0abf;
+                m.tick(17, 0x0abf)?;
+                m.call(0x005a)?;
+                // 005a  jmp 0082h
+                m.pc = 0x0082;
+                m.tick(10, 0x0082)?;
+            },
+            0x005d => {
+                // 005d  lda 2093h
+                m.a = m.read(0x2093)?;
+                m.tick(13, 0x0060)?;
+                // 0060  ana a
+                let value = m.a;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(4, 0x0061)?;
+                // 0061  jnz 0082h
+                m.pc = if m.flags & ZERO == 0 { 0x0082 } else { 0x0064 };
+                m.tick(10, m.pc)?;
+            },
+            0x0064 => {
+                // 0064  jmp 0765h
+                m.pc = 0x0765;
+                m.tick(10, 0x0765)?;
+            },
+            0x006f => {
+                // 006f  call 1740h
+                m.push(0x0072)?;
+                m.pc = 0x1740;
+                m.tick(17, 0x1740)?;
+                m.call(0x0072)?;
+                // 0072  lda 2032h
+                m.a = m.read(0x2032)?;
+                m.tick(13, 0x0075)?;
+                // 0075  sta 2080h
+                m.write(0x2080, m.a)?;
+                m.tick(13, 0x0078)?;
+                // 0078  call 0100h
+                m.push(0x007b)?;
+                m.pc = 0x0100;
+                m.tick(17, 0x0100)?;
+                m.call(0x007b)?;
+                // 007b  call 0248h
+                m.push(0x007e)?;
+                m.pc = 0x0248;
+                m.tick(17, 0x0248)?;
+                m.call(0x007e)?;
+                // 007e  call 0913h
+                m.push(0x0081)?;
+                m.pc = 0x0913;
+                m.tick(17, 0x0913)?;
+                m.call(0x0081)?;
+                // 0081  nop
+                m.tick(4, 0x0082)?;
+                m.pc = 0x0082;
+            },
+            0x0082 => {
+                // 0082  pop h
+                let value = m.pop()?;
+                m.set_hl(value);
+                m.tick(10, 0x0083)?;
+                // 0083  pop d
+                let value = m.pop()?;
+                m.set_de(value);
+                m.tick(10, 0x0084)?;
+                // 0084  pop b
+                let value = m.pop()?;
+                m.set_bc(value);
+                m.tick(10, 0x0085)?;
+                // 0085  pop psw
+                let value = m.pop()?;
+                m.a = (value >> 8) as u8;
+                m.flags = value as u8 & (SIGN | ZERO | AUX_CARRY | PARITY | CARRY);
+                m.tick(10, 0x0086)?;
+                m.pc = 0x0086;
+            },
+            // 0086  ei
+            0x0086 => m.interpret()?,
+            0x0765 => {
+                // 0765  mvi a,01h
+                m.a = 0x01;
+                m.tick(7, 0x0767)?;
+                // 0767  sta 2093h
+                m.write(0x2093, m.a)?;
+                m.tick(13, 0x076a)?;
+                // 076a  lxi sp,2400h
+                m.sp = 0x2400;
+                m.tick(10, 0x076d)?;
+                m.pc = 0x076d;
+            },
+            // 076d  ei
+            0x076d => m.interpret()?,
+            0x0771 => {
+                // 0771  call 09d6h
+                m.push(0x0774)?;
+                m.pc = 0x09d6;
+                m.tick(17, 0x09d6)?;
+                m.call(0x0774)?;
+                // 0774  lxi h,3013h
+                m.set_hl(0x3013);
+                m.tick(10, 0x0777)?;
+                // 0777  lxi d,1ff3h
+                m.set_de(0x1ff3);
+                m.tick(10, 0x077a)?;
+                // 077a  mvi c,04h
+                m.c = 0x04;
+                m.tick(7, 0x077c)?;
+                // 077c  call 08f3h
+                m.push(0x077f)?;
+                m.pc = 0x08f3;
+                m.tick(17, 0x08f3)?;
+                m.call(0x077f)?;
+                m.pc = 0x077f;
+            },
+            0x077f => {
+                // 077f  lda 20ebh
+                m.a = m.read(0x20eb)?;
+                m.tick(13, 0x0782)?;
+                // 0782  dcr a
+                let value = m.a;
+                let value = value.wrapping_sub(1);
+                m.flags = SZP[value as usize] | (m.flags & CARRY) | if value & 0xf != 0xf { AUX_CARRY } else { 0 };
+                m.a = value;
+                m.tick(5, 0x0783)?;
+                // 0783  lxi h,2810h
+                m.set_hl(0x2810);
+                m.tick(10, 0x0786)?;
+                // 0786  mvi c,14h
+                m.c = 0x14;
+                m.tick(7, 0x0788)?;
+                // 0788  jnz 0857h
+                m.pc = if m.flags & ZERO == 0 { 0x0857 } else { 0x078b };
+                m.tick(10, m.pc)?;
+            },
+            0x078b => {
+                // 078b  lxi d,1acfh
+                m.set_de(0x1acf);
+                m.tick(10, 0x078e)?;
+                // 078e  call 08f3h
+                m.push(0x0791)?;
+                m.pc = 0x08f3;
+                m.tick(17, 0x08f3)?;
+                m.call(0x0791)?;
+                // 0791  in 01h
+                m.a = m.input(0x01)?;
+                m.tick(10, 0x0793)?;
+                // 0793  ani 04h
+                let value: u8 = 0x04;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(7, 0x0795)?;
+                // 0795  jz 077fh
+                m.pc = if m.flags & ZERO != 0 { 0x077f } else { 0x0798 };
+                m.tick(10, m.pc)?;
+            },
+            0x0798 => {
+                // 0798  mvi b,99h
+                m.b = 0x99;
+                m.tick(7, 0x079a)?;
+                // 079a  xra a
+                let value = m.a;
+                m.a ^= value;
+                m.flags = SZP[m.a as usize] | if m.a > 0xf { AUX_CARRY } else { 0 };
+                m.tick(4, 0x079b)?;
+                m.pc = 0x079b;
+            },
+            0x079b => {
+                // 079b  sta 20ceh
+                m.write(0x20ce, m.a)?;
+                m.tick(13, 0x079e)?;
+                // 079e  lda 20ebh
+                m.a = m.read(0x20eb)?;
+                m.tick(13, 0x07a1)?;
+                // 07a1  add b
+                let value = m.b;
+                let result = m.a as u16 + value as u16;
+                m.flags = SZP[result as u8 as usize] | (result >> 8) as u8 | if (m.a & 0xf) + (value & 0xf) > 0xf { AUX_CARRY } else { 0 };
+                m.a = result as u8;
+                m.tick(4, 0x07a2)?;
+                // 07a2  daa
+                m.daa();
+                m.tick(4, 0x07a3)?;
+                // 07a3  sta 20ebh
+                m.write(0x20eb, m.a)?;
+                m.tick(13, 0x07a6)?;
+                // 07a6  call 1947h
+                m.push(0x07a9)?;
+                m.pc = 0x1947;
+                m.tick(17, 0x1947)?;
+                m.call(0x07a9)?;
+                // 07a9  lxi h,0000h
+                m.set_hl(0x0000);
+                m.tick(10, 0x07ac)?;
+                // 07ac  shld 20f8h
+                m.write(0x20f8, m.l)?;
+                m.write(0x20f9, m.h)?;
+                m.tick(16, 0x07af)?;
+                // 07af  shld 20fch
+                m.write(0x20fc, m.l)?;
+                m.write(0x20fd, m.h)?;
+                m.tick(16, 0x07b2)?;
+                // 07b2  call 1925h
+                m.push(0x07b5)?;
+                m.pc = 0x1925;
+                m.tick(17, 0x1925)?;
+                m.call(0x07b5)?;
+                // 07b5  call 192bh
+                m.push(0x07b8)?;
+                m.pc = 0x192b;
+                m.tick(17, 0x192b)?;
+                m.call(0x07b8)?;
+                // 07b8  call 19d7h
+                m.push(0x07bb)?;
+                m.pc = 0x19d7;
+                m.tick(17, 0x19d7)?;
+                m.call(0x07bb)?;
+                // 07bb  lxi h,0101h
+                m.set_hl(0x0101);
+                m.tick(10, 0x07be)?;
+                // 07be  mov a,h
+                m.a = m.h;
+                m.tick(5, 0x07bf)?;
+                // 07bf  sta 20efh
+                m.write(0x20ef, m.a)?;
+                m.tick(13, 0x07c2)?;
+                // 07c2  shld 20e7h
+                m.write(0x20e7, m.l)?;
+                m.write(0x20e8, m.h)?;
+                m.tick(16, 0x07c5)?;
+                // 07c5  shld 20e5h
+                m.write(0x20e5, m.l)?;
+                m.write(0x20e6, m.h)?;
+                m.tick(16, 0x07c8)?;
+                // 07c8  call 1956h
+                m.push(0x07cb)?;
+                m.pc = 0x1956;
+                m.tick(17, 0x1956)?;
+                m.call(0x07cb)?;
+                // 07cb  call 01efh
+                m.push(0x07ce)?;
+                m.pc = 0x01ef;
+                m.tick(17, 0x01ef)?;
+                m.call(0x07ce)?;
+                // 07ce  call 01f5h
+                m.push(0x07d1)?;
+                m.pc = 0x01f5;
+                m.tick(17, 0x01f5)?;
+                m.call(0x07d1)?;
+                // 07d1  call 08d1h
+                m.push(0x07d4)?;
+                m.pc = 0x08d1;
+                m.tick(17, 0x08d1)?;
+                m.call(0x07d4)?;
+                // 07d4  sta 21ffh
+                m.write(0x21ff, m.a)?;
+                m.tick(13, 0x07d7)?;
+                // 07d7  sta 22ffh
+                m.write(0x22ff, m.a)?;
+                m.tick(13, 0x07da)?;
+                // 07da  call 00d7h
+                m.push(0x07dd)?;
+                m.pc = 0x00d7;
+                m.tick(17, 0x00d7)?;
+                m.call(0x07dd)?;
+                // 07dd  xra a
+                let value = m.a;
+                m.a ^= value;
+                m.flags = SZP[m.a as usize] | if m.a > 0xf { AUX_CARRY } else { 0 };
+                m.tick(4, 0x07de)?;
+                // 07de  sta 21feh
+                m.write(0x21fe, m.a)?;
+                m.tick(13, 0x07e1)?;
+                // 07e1  sta 22feh
+                m.write(0x22fe, m.a)?;
+                m.tick(13, 0x07e4)?;
+                // 07e4  call 01c0h
+                m.push(0x07e7)?;
+                m.pc = 0x01c0;
+                m.tick(17, 0x01c0)?;
+                m.call(0x07e7)?;
+                // 07e7  call 1904h
+                m.push(0x07ea)?;
+                m.pc = 0x1904;
+                m.tick(17, 0x1904)?;
+                m.call(0x07ea)?;
+                // 07ea  lxi h,3878h
+                m.set_hl(0x3878);
+                m.tick(10, 0x07ed)?;
+                // 07ed  shld 21fch
+                m.write(0x21fc, m.l)?;
+                m.write(0x21fd, m.h)?;
+                m.tick(16, 0x07f0)?;
+                // 07f0  shld 22fch
+                m.write(0x22fc, m.l)?;
+                m.write(0x22fd, m.h)?;
+                m.tick(16, 0x07f3)?;
+                // 07f3  call 01e4h
+                m.push(0x07f6)?;
+                m.pc = 0x01e4;
+                m.tick(17, 0x01e4)?;
+                m.call(0x07f6)?;
+                // 07f6  call 1a7fh
+                m.push(0x07f9)?;
+                m.pc = 0x1a7f;
+                m.tick(17, 0x1a7f)?;
+                m.call(0x07f9)?;
+                // 07f9  call 088dh
+                m.push(0x07fc)?;
+                m.pc = 0x088d;
+                m.tick(17, 0x088d)?;
+                m.call(0x07fc)?;
+                // 07fc  call 09d6h
+                m.push(0x07ff)?;
+                m.pc = 0x09d6;
+                m.tick(17, 0x09d6)?;
+                m.call(0x07ff)?;
+                // 07ff  nop
+                m.tick(4, 0x0800)?;
+                // 0800  xra a
+                let value = m.a;
+                m.a ^= value;
+                m.flags = SZP[m.a as usize] | if m.a > 0xf { AUX_CARRY } else { 0 };
+                m.tick(4, 0x0801)?;
+                // 0801  sta 20c1h
+                m.write(0x20c1, m.a)?;
+                m.tick(13, 0x0804)?;
+                m.pc = 0x0804;
+            },
+            0x0804 => {
+                // 0804  call 01cfh
+                m.push(0x0807)?;
+                m.pc = 0x01cf;
+                m.tick(17, 0x01cf)?;
+                m.call(0x0807)?;
+                // 0807  lda 2067h
+                m.a = m.read(0x2067)?;
+                m.tick(13, 0x080a)?;
+                // 080a  rrc
+                m.flags = (m.flags & !CARRY) | (m.a & 1);
+                m.a = m.a.rotate_right(1);
+                m.tick(4, 0x080b)?;
+                // 080b  jc 0872h
+                m.pc = if m.flags & CARRY != 0 { 0x0872 } else { 0x080e };
+                m.tick(10, m.pc)?;
+            },
+            0x080e => {
+                // 080e  call 0213h
+                m.push(0x0811)?;
+                m.pc = 0x0213;
+                m.tick(17, 0x0213)?;
+                m.call(0x0811)?;
+                // 0811  call 01cfh
+                m.push(0x0814)?;
+                m.pc = 0x01cf;
+                m.tick(17, 0x01cf)?;
+                m.call(0x0814)?;
+                m.pc = 0x0814;
+            },
+            0x0814 => {
+                // 0814  call 00b1h
+                m.push(0x0817)?;
+                m.pc = 0x00b1;
+                m.tick(17, 0x00b1)?;
+                m.call(0x0817)?;
+                // 0817  call 19d1h
+                m.push(0x081a)?;
+                m.pc = 0x19d1;
+                m.tick(17, 0x19d1)?;
+                m.call(0x081a)?;
+                // 081a  mvi b,20h
+                m.b = 0x20;
+                m.tick(7, 0x081c)?;
+                // 081c  call 18fah
+                m.push(0x081f)?;
+                m.pc = 0x18fa;
+                m.tick(17, 0x18fa)?;
+                m.call(0x081f)?;
+                m.pc = 0x081f;
+            },
+            0x081f => {
+                // 081f  call 1618h
+                m.push(0x0822)?;
+                m.pc = 0x1618;
+                m.tick(17, 0x1618)?;
+                m.call(0x0822)?;
+                // 0822  call 190ah
+                m.push(0x0825)?;
+                m.pc = 0x190a;
+                m.tick(17, 0x190a)?;
+                m.call(0x0825)?;
+                // 0825  call 15f3h
+                m.push(0x0828)?;
+                m.pc = 0x15f3;
+                m.tick(17, 0x15f3)?;
+                m.call(0x0828)?;
+                // 0828  call 0988h
+                m.push(0x082b)?;
+                m.pc = 0x0988;
+                m.tick(17, 0x0988)?;
+                m.call(0x082b)?;
+                // 082b  lda 2082h
+                m.a = m.read(0x2082)?;
+                m.tick(13, 0x082e)?;
+                // 082e  ana a
+                let value = m.a;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(4, 0x082f)?;
+                // 082f  jz 09efh
+                m.pc = if m.flags & ZERO != 0 { 0x09ef } else { 0x0832 };
+                m.tick(10, m.pc)?;
+            },
+            0x0832 => {
+                // 0832  call 170eh
+                m.push(0x0835)?;
+                m.pc = 0x170e;
+                m.tick(17, 0x170e)?;
+                m.call(0x0835)?;
+                // 0835  call 0935h
+                m.push(0x0838)?;
+                m.pc = 0x0935;
+                m.tick(17, 0x0935)?;
+                m.call(0x0838)?;
+                // 0838  call 08d8h
+                m.push(0x083b)?;
+                m.pc = 0x08d8;
+                m.tick(17, 0x08d8)?;
+                m.call(0x083b)?;
+                // 083b  call 172ch
+                m.push(0x083e)?;
+                m.pc = 0x172c;
+                m.tick(17, 0x172c)?;
+                m.call(0x083e)?;
+                // 083e  call 0a59h
+                m.push(0x0841)?;
+                m.pc = 0x0a59;
+                m.tick(17, 0x0a59)?;
+                m.call(0x0841)?;
+                // 0841  jz 0849h
+                m.pc = if m.flags & ZERO != 0 { 0x0849 } else { 0x0844 };
+                m.tick(10, m.pc)?;
+            },
+            0x0844 => {
+                // 0844  mvi b,04h
+                m.b = 0x04;
+                m.tick(7, 0x0846)?;
+                // 0846  call 18fah
+                m.push(0x0849)?;
+                m.pc = 0x18fa;
+                m.tick(17, 0x18fa)?;
+                m.call(0x0849)?;
+                m.pc = 0x0849;
+            },
+            0x0849 => {
+                // 0849  call 1775h
+                m.push(0x084c)?;
+                m.pc = 0x1775;
+                m.tick(17, 0x1775)?;
+                m.call(0x084c)?;
+                // 084c  out 06h
+                m.output(0x06, m.a)?;
+                m.tick(10, 0x084e)?;
+                // 084e  call 1804h
+                m.push(0x0851)?;
+                m.pc = 0x1804;
+                m.tick(17, 0x1804)?;
+                m.call(0x0851)?;
+                // 0851  jmp 081fh
+                m.pc = 0x081f;
+                m.tick(10, 0x081f)?;
+            },
+            0x0857 => {
+                // 0857  lxi d,1abah
+                m.set_de(0x1aba);
+                m.tick(10, 0x085a)?;
+                // 085a  call 08f3h
+                m.push(0x085d)?;
+                m.pc = 0x08f3;
+                m.tick(17, 0x08f3)?;
+                m.call(0x085d)?;
+                // 085d  mvi b,98h
+                m.b = 0x98;
+                m.tick(7, 0x085f)?;
+                // 085f  in 01h
+                m.a = m.input(0x01)?;
+                m.tick(10, 0x0861)?;
+                // 0861  rrc
+                m.flags = (m.flags & !CARRY) | (m.a & 1);
+                m.a = m.a.rotate_right(1);
+                m.tick(4, 0x0862)?;
+                // 0862  rrc
+                m.flags = (m.flags & !CARRY) | (m.a & 1);
+                m.a = m.a.rotate_right(1);
+                m.tick(4, 0x0863)?;
+                // 0863  jc 086dh
+                m.pc = if m.flags & CARRY != 0 { 0x086d } else { 0x0866 };
+                m.tick(10, m.pc)?;
+            },
+            0x0866 => {
+                // 0866  rrc
+                m.flags = (m.flags & !CARRY) | (m.a & 1);
+                m.a = m.a.rotate_right(1);
+                m.tick(4, 0x0867)?;
+                // 0867  jc 0798h
+                m.pc = if m.flags & CARRY != 0 { 0x0798 } else { 0x086a };
+                m.tick(10, m.pc)?;
+            },
+            0x086a => {
+                // 086a  jmp 077fh
+                m.pc = 0x077f;
+                m.tick(10, 0x077f)?;
+            },
+            0x086d => {
+                // 086d  mvi a,01h
+                m.a = 0x01;
+                m.tick(7, 0x086f)?;
+                // 086f  jmp 079bh
+                m.pc = 0x079b;
+                m.tick(10, 0x079b)?;
+            },
+            0x0872 => {
+                // 0872  call 021ah
+                m.push(0x0875)?;
+                m.pc = 0x021a;
+                m.tick(17, 0x021a)?;
+                m.call(0x0875)?;
+                // 0875  jmp 0814h
+                m.pc = 0x0814;
+                m.tick(10, 0x0814)?;
+            },
+            0x09ef => {
+                // 09ef  call 0a3ch
+                m.push(0x09f2)?;
+                m.pc = 0x0a3c;
+                m.tick(17, 0x0a3c)?;
+                m.call(0x09f2)?;
+                // 09f2  xra a
+                let value = m.a;
+                m.a ^= value;
+                m.flags = SZP[m.a as usize] | if m.a > 0xf { AUX_CARRY } else { 0 };
+                m.tick(4, 0x09f3)?;
+                // 09f3  sta 20e9h
+                m.write(0x20e9, m.a)?;
+                m.tick(13, 0x09f6)?;
+                // 09f6  call 09d6h
+                m.push(0x09f9)?;
+                m.pc = 0x09d6;
+                m.tick(17, 0x09d6)?;
+                m.call(0x09f9)?;
+                // 09f9  lda 2067h
+                m.a = m.read(0x2067)?;
+                m.tick(13, 0x09fc)?;
+                // 09fc  push psw
+                m.push((m.a as u16) << 8 | m.flags as u16)?;
+                m.tick(11, 0x09fd)?;
+                // 09fd  call 01e4h
+                m.push(0x0a00)?;
+                m.pc = 0x01e4;
+                m.tick(17, 0x01e4)?;
+                m.call(0x0a00)?;
+                // 0a00  pop psw
+                let value = m.pop()?;
+                m.a = (value >> 8) as u8;
+                m.flags = value as u8 & (SIGN | ZERO | AUX_CARRY | PARITY | CARRY);
+                m.tick(10, 0x0a01)?;
+                // 0a01  sta 2067h
+                m.write(0x2067, m.a)?;
+                m.tick(13, 0x0a04)?;
+                // 0a04  lda 2067h
+                m.a = m.read(0x2067)?;
+                m.tick(13, 0x0a07)?;
+                // 0a07  mov h,a
+                m.h = m.a;
+                m.tick(5, 0x0a08)?;
+                // 0a08  push h
+                m.push(m.hl())?;
+                m.tick(11, 0x0a09)?;
+                // 0a09  mvi l,0feh
+                m.l = 0xfe;
+                m.tick(7, 0x0a0b)?;
+                // 0a0b  mov a,m
+                m.a = m.read(m.hl())?;
+                m.tick(7, 0x0a0c)?;
+                // 0a0c  ani 07h
+                let value: u8 = 0x07;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(7, 0x0a0e)?;
+                // 0a0e  inr a
+                let value = m.a;
+                let value = value.wrapping_add(1);
+                m.flags = SZP[value as usize] | (m.flags & CARRY) | if value & 0xf == 0 { AUX_CARRY } else { 0 };
+                m.a = value;
+                m.tick(5, 0x0a0f)?;
+                // 0a0f  mov m,a
+                m.write(m.hl(), m.a)?;
+                m.tick(7, 0x0a10)?;
+                // 0a10  lxi h,1da2h
+                m.set_hl(0x1da2);
+                m.tick(10, 0x0a13)?;
+                m.pc = 0x0a13;
+            },
+            0x0a13 => {
+                // 0a13  inx h
+                m.set_hl(m.hl().wrapping_add(1));
+                m.tick(5, 0x0a14)?;
+                // 0a14  dcr a
+                let value = m.a;
+                let value = value.wrapping_sub(1);
+                m.flags = SZP[value as usize] | (m.flags & CARRY) | if value & 0xf != 0xf { AUX_CARRY } else { 0 };
+                m.a = value;
+                m.tick(5, 0x0a15)?;
+                // 0a15  jnz 0a13h
+                m.pc = if m.flags & ZERO == 0 { 0x0a13 } else { 0x0a18 };
+                m.tick(10, m.pc)?;
+            },
+            0x0a18 => {
+                // 0a18  mov a,m
+                m.a = m.read(m.hl())?;
+                m.tick(7, 0x0a19)?;
+                // 0a19  pop h
+                let value = m.pop()?;
+                m.set_hl(value);
+                m.tick(10, 0x0a1a)?;
+                // 0a1a  mvi l,0fch
+                m.l = 0xfc;
+                m.tick(7, 0x0a1c)?;
+                // 0a1c  mov m,a
+                m.write(m.hl(), m.a)?;
+                m.tick(7, 0x0a1d)?;
+                // 0a1d  inx h
+                m.set_hl(m.hl().wrapping_add(1));
+                m.tick(5, 0x0a1e)?;
+                // 0a1e  mvi m,38h
+                m.write(m.hl(), 0x38)?;
+                m.tick(10, 0x0a20)?;
+                // 0a20  mov a,h
+                m.a = m.h;
+                m.tick(5, 0x0a21)?;
+                // 0a21  rrc
+                m.flags = (m.flags & !CARRY) | (m.a & 1);
+                m.a = m.a.rotate_right(1);
+                m.tick(4, 0x0a22)?;
+                // 0a22  jc 0a33h
+                m.pc = if m.flags & CARRY != 0 { 0x0a33 } else { 0x0a25 };
+                m.tick(10, m.pc)?;
+            },
+            0x0a25 => {
+                // 0a25  mvi a,21h
+                m.a = 0x21;
+                m.tick(7, 0x0a27)?;
+                // 0a27  sta 2098h
+                m.write(0x2098, m.a)?;
+                m.tick(13, 0x0a2a)?;
+                // 0a2a  call 01f5h
+                m.push(0x0a2d)?;
+                m.pc = 0x01f5;
+                m.tick(17, 0x01f5)?;
+                m.call(0x0a2d)?;
+                // 0a2d  call 1904h
+                m.push(0x0a30)?;
+                m.pc = 0x1904;
+                m.tick(17, 0x1904)?;
+                m.call(0x0a30)?;
+                // 0a30  jmp 0804h
+                m.pc = 0x0804;
+                m.tick(10, 0x0804)?;
+            },
+            0x0a33 => {
+                // 0a33  call 01efh
+                m.push(0x0a36)?;
+                m.pc = 0x01ef;
+                m.tick(17, 0x01ef)?;
+                m.call(0x0a36)?;
+                // 0a36  call 01c0h
+                m.push(0x0a39)?;
+                m.pc = 0x01c0;
+                m.tick(17, 0x01c0)?;
+                m.call(0x0a39)?;
+                // 0a39  jmp 0804h
+                m.pc = 0x0804;
+                m.tick(10, 0x0804)?;
+            },
+            _ => return Ok(()),
+        }
+    }
+}
+
+fn sub_00b1(m: &mut Machine) -> Result<(), Stop> {
+    loop {
+        match m.pc {
+            0x00b1 => {
+                // 00b1  call 0886h
+                m.push(0x00b4)?;
+                m.pc = 0x0886;
+                m.tick(17, 0x0886)?;
+                m.call(0x00b4)?;
+                // 00b4  push h
+                m.push(m.hl())?;
+                m.tick(11, 0x00b5)?;
+                // 00b5  mov a,m
+                m.a = m.read(m.hl())?;
+                m.tick(7, 0x00b6)?;
+                // 00b6  inx h
+                m.set_hl(m.hl().wrapping_add(1));
+                m.tick(5, 0x00b7)?;
+                // 00b7  mov h,m
+                m.h = m.read(m.hl())?;
+                m.tick(7, 0x00b8)?;
+                // 00b8  mov l,a
+                m.l = m.a;
+                m.tick(5, 0x00b9)?;
+                // 00b9  shld 2009h
+                m.write(0x2009, m.l)?;
+                m.write(0x200a, m.h)?;
+                m.tick(16, 0x00bc)?;
+                // 00bc  shld 200bh
+                m.write(0x200b, m.l)?;
+                m.write(0x200c, m.h)?;
+                m.tick(16, 0x00bf)?;
+                // 00bf  pop h
+                let value = m.pop()?;
+                m.set_hl(value);
+                m.tick(10, 0x00c0)?;
+                // 00c0  dcx h
+                m.set_hl(m.hl().wrapping_sub(1));
+                m.tick(5, 0x00c1)?;
+                // 00c1  mov a,m
+                m.a = m.read(m.hl())?;
+                m.tick(7, 0x00c2)?;
+                // 00c2  cpi 03h
+                let value: u8 = 0x03;
+                let result = (m.a as u16).wrapping_sub(value as u16);
+                m.flags = SZP[result as u8 as usize] | (result > 0xff) as u8 | if (m.a & 0xf).wrapping_sub(value & 0xf) > 0xf { AUX_CARRY } else { 0 };
+                m.tick(7, 0x00c4)?;
+                // 00c4  jnz 00c8h
+                m.pc = if m.flags & ZERO == 0 { 0x00c8 } else { 0x00c7 };
+                m.tick(10, m.pc)?;
+            },
+            0x00c7 => {
+                // 00c7  dcr a
+                let value = m.a;
+                let value = value.wrapping_sub(1);
+                m.flags = SZP[value as usize] | (m.flags & CARRY) | if value & 0xf != 0xf { AUX_CARRY } else { 0 };
+                m.a = value;
+                m.tick(5, 0x00c8)?;
+                m.pc = 0x00c8;
+            },
+            0x00c8 => {
+                // 00c8  sta 2008h
+                m.write(0x2008, m.a)?;
+                m.tick(13, 0x00cb)?;
+                // 00cb  cpi 0feh
+                let value: u8 = 0xfe;
+                let result = (m.a as u16).wrapping_sub(value as u16);
+                m.flags = SZP[result as u8 as usize] | (result > 0xff) as u8 | if (m.a & 0xf).wrapping_sub(value & 0xf) > 0xf { AUX_CARRY } else { 0 };
+                m.tick(7, 0x00cd)?;
+                // 00cd  mvi a,00h
+                m.a = 0x00;
+                m.tick(7, 0x00cf)?;
+                // 00cf  jnz 00d3h
+                m.pc = if m.flags & ZERO == 0 { 0x00d3 } else { 0x00d2 };
+                m.tick(10, m.pc)?;
+            },
+            0x00d2 => {
+                // 00d2  inr a
+                let value = m.a;
+                let value = value.wrapping_add(1);
+                m.flags = SZP[value as usize] | (m.flags & CARRY) | if value & 0xf == 0 { AUX_CARRY } else { 0 };
+                m.a = value;
+                m.tick(5, 0x00d3)?;
+                m.pc = 0x00d3;
+            },
+            0x00d3 => {
+                // 00d3  sta 200dh
+                m.write(0x200d, m.a)?;
+                m.tick(13, 0x00d6)?;
+                // 00d6  ret
+                let target = m.pop()?;
+                m.pc = target;
+                m.tick(10, target)?;
+                return Ok(());
+            },
+            _ => return Ok(()),
+        }
+    }
+}
+
+fn sub_00d7(m: &mut Machine) -> Result<(), Stop> {
+    loop {
+        match m.pc {
+            0x00d7 => {
+                // 00d7  mvi a,02h
+                m.a = 0x02;
+                m.tick(7, 0x00d9)?;
+                // 00d9  sta 21fbh
+                m.write(0x21fb, m.a)?;
+                m.tick(13, 0x00dc)?;
+                // 00dc  sta 22fbh
+                m.write(0x22fb, m.a)?;
+                m.tick(13, 0x00df)?;
+                // 00df  jmp 08e4h
+                m.pc = 0x08e4;
+                m.tick(10, 0x08e4)?;
+            },
+            0x08e4 => {
+                // 08e4  lda 20ceh
+                m.a = m.read(0x20ce)?;
+                m.tick(13, 0x08e7)?;
+                // 08e7  ana a
+                let value = m.a;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(4, 0x08e8)?;
+                // 08e8  rnz
+                if m.flags & ZERO == 0 {
+                    let target = m.pop()?;
+                    m.pc = target;
+                    m.tick(11, target)?;
+                    return Ok(());
+                }
+                m.tick(5, 0x08e9)?;
+                // 08e9  lxi h,391ch
+                m.set_hl(0x391c);
+                m.tick(10, 0x08ec)?;
+                // 08ec  mvi b,20h
+                m.b = 0x20;
+                m.tick(7, 0x08ee)?;
+                // 08ee  jmp 14cbh
+                m.pc = 0x14cb;
+                m.tick(10, 0x14cb)?;
+            },
+            0x14cb => {
+                // 14cb  xra a
+                let value = m.a;
+                m.a ^= value;
+                m.flags = SZP[m.a as usize] | if m.a > 0xf { AUX_CARRY } else { 0 };
+                m.tick(4, 0x14cc)?;
+                m.pc = 0x14cc;
+            },
+            0x14cc => {
+                // 14cc  push b
+                m.push(m.bc())?;
+                m.tick(11, 0x14cd)?;
+                // 14cd  mov m,a
+                m.write(m.hl(), m.a)?;
+                m.tick(7, 0x14ce)?;
+                // 14ce  lxi b,0020h
+                m.set_bc(0x0020);
+                m.tick(10, 0x14d1)?;
+                // 14d1  dad b
+                let result = m.hl() as u32 + m.bc() as u32;
+                m.flags = (m.flags & !CARRY) | (result >> 16) as u8;
+                m.set_hl(result as u16);
+                m.tick(10, 0x14d2)?;
+                // 14d2  pop b
+                let value = m.pop()?;
+                m.set_bc(value);
+                m.tick(10, 0x14d3)?;
+                // 14d3  dcr b
+                let value = m.b;
+                let value = value.wrapping_sub(1);
+                m.flags = SZP[value as usize] | (m.flags & CARRY) | if value & 0xf != 0xf { AUX_CARRY } else { 0 };
+                m.b = value;
+                m.tick(5, 0x14d4)?;
+                // 14d4  jnz 14cch
+                m.pc = if m.flags & ZERO == 0 { 0x14cc } else { 0x14d7 };
+                m.tick(10, m.pc)?;
+            },
+            0x14d7 => {
+                // 14d7  ret
+                let target = m.pop()?;
+                m.pc = target;
+                m.tick(10, target)?;
+                return Ok(());
+            },
+            _ => return Ok(()),
+        }
+    }
+}
+
+fn sub_0100(m: &mut Machine) -> Result<(), Stop> {
+    loop {
+        match m.pc {
+            0x0100 => {
+                // 0100  lxi h,2002h
+                m.set_hl(0x2002);
+                m.tick(10, 0x0103)?;
+                // 0103  mov a,m
+                m.a = m.read(m.hl())?;
+                m.tick(7, 0x0104)?;
+                // 0104  ana a
+                let value = m.a;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(4, 0x0105)?;
+                // 0105  jnz 1538h
+                m.pc = if m.flags & ZERO == 0 { 0x1538 } else { 0x0108 };
+                m.tick(10, m.pc)?;
+            },
+            0x0108 => {
+                // 0108  push h
+                m.push(m.hl())?;
+                m.tick(11, 0x0109)?;
+                // 0109  lda 2006h
+                m.a = m.read(0x2006)?;
+                m.tick(13, 0x010c)?;
+                // 010c  mov l,a
+                m.l = m.a;
+                m.tick(5, 0x010d)?;
+                // 010d  lda 2067h
+                m.a = m.read(0x2067)?;
+                m.tick(13, 0x0110)?;
+                // 0110  mov h,a
+                m.h = m.a;
+                m.tick(5, 0x0111)?;
+                // 0111  mov a,m
+                m.a = m.read(m.hl())?;
+                m.tick(7, 0x0112)?;
+                // 0112  ana a
+                let value = m.a;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(4, 0x0113)?;
+                // 0113  pop h
+                let value = m.pop()?;
+                m.set_hl(value);
+                m.tick(10, 0x0114)?;
+                // 0114  jz 0136h
+                m.pc = if m.flags & ZERO != 0 { 0x0136 } else { 0x0117 };
+                m.tick(10, m.pc)?;
+            },
+            0x0117 => {
+                // 0117  inx h
+                m.set_hl(m.hl().wrapping_add(1));
+                m.tick(5, 0x0118)?;
+                // 0118  inx h
+                m.set_hl(m.hl().wrapping_add(1));
+                m.tick(5, 0x0119)?;
+                // 0119  mov a,m
+                m.a = m.read(m.hl())?;
+                m.tick(7, 0x011a)?;
+                // 011a  inx h
+                m.set_hl(m.hl().wrapping_add(1));
+                m.tick(5, 0x011b)?;
+                // 011b  mov b,m
+                m.b = m.read(m.hl())?;
+                m.tick(7, 0x011c)?;
+                // 011c  ani 0feh
+                let value: u8 = 0xfe;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(7, 0x011e)?;
+                // 011e  rlc
+                m.flags = (m.flags & !CARRY) | (m.a >> 7);
+                m.a = m.a.rotate_left(1);
+                m.tick(4, 0x011f)?;
+                // 011f  rlc
+                m.flags = (m.flags & !CARRY) | (m.a >> 7);
+                m.a = m.a.rotate_left(1);
+                m.tick(4, 0x0120)?;
+                // 0120  rlc
+                m.flags = (m.flags & !CARRY) | (m.a >> 7);
+                m.a = m.a.rotate_left(1);
+                m.tick(4, 0x0121)?;
+                // 0121  mov e,a
+                m.e = m.a;
+                m.tick(5, 0x0122)?;
+                // 0122  mvi d,00h
+                m.d = 0x00;
+                m.tick(7, 0x0124)?;
+                // 0124  lxi h,1c00h
+                m.set_hl(0x1c00);
+                m.tick(10, 0x0127)?;
+                // 0127  dad d
+                let result = m.hl() as u32 + m.de() as u32;
+                m.flags = (m.flags & !CARRY) | (result >> 16) as u8;
+                m.set_hl(result as u16);
+                m.tick(10, 0x0128)?;
+                // 0128  xchg
+                std::mem::swap(&mut m.d, &mut m.h);
+                std::mem::swap(&mut m.e, &mut m.l);
+                m.tick(4, 0x0129)?;
+                // 0129  mov a,b
+                m.a = m.b;
+                m.tick(5, 0x012a)?;
+                // 012a  ana a
+                let value = m.a;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(4, 0x012b)?;
+                // 012b  cnz 013bh
+                if m.flags & ZERO == 0 {
+                    m.push(0x012e)?;
+                    m.pc = 0x013b;
+                    m.tick(17, 0x013b)?;
+                    m.call(0x012e)?;
+                } else {
+                    m.tick(11, 0x012e)?;
+                }
+                // 012e  lhld 200bh
+                m.l = m.read(0x200b)?;
+                m.h = m.read(0x200c)?;
+                m.tick(16, 0x0131)?;
+                // 0131  mvi b,10h
+                m.b = 0x10;
+                m.tick(7, 0x0133)?;
+                // 0133  call 15d3h
+                m.push(0x0136)?;
+                m.pc = 0x15d3;
+                m.tick(17, 0x15d3)?;
+                m.call(0x0136)?;
+                m.pc = 0x0136;
+            },
+            0x0136 => {
+                // 0136  xra a
+                let value = m.a;
+                m.a ^= value;
+                m.flags = SZP[m.a as usize] | if m.a > 0xf { AUX_CARRY } else { 0 };
+                m.tick(4, 0x0137)?;
+                // 0137  sta 2000h
+                m.write(0x2000, m.a)?;
+                m.tick(13, 0x013a)?;
+                // 013a  ret
+                let target = m.pop()?;
+                m.pc = target;
+                m.tick(10, target)?;
+                return Ok(());
+            },
+            0x1538 => {
+                // 1538  lxi h,2003h
+                m.set_hl(0x2003);
+                m.tick(10, 0x153b)?;
+                // 153b  dcr m
+                let value = m.read(m.hl())?;
+                let value = value.wrapping_sub(1);
+                m.flags = SZP[value as usize] | (m.flags & CARRY) | if value & 0xf != 0xf { AUX_CARRY } else { 0 };
+                m.write(m.hl(), value)?;
+                m.tick(10, 0x153c)?;
+                // 153c  rnz
+                if m.flags & ZERO == 0 {
+                    let target = m.pop()?;
+                    m.pc = target;
+                    m.tick(11, target)?;
+                    return Ok(());
+                }
+                m.tick(5, 0x153d)?;
+                // 153d  lhld 2064h
+                m.l = m.read(0x2064)?;
+                m.h = m.read(0x2065)?;
+                m.tick(16, 0x1540)?;
+                // 1540  mvi b,10h
+                m.b = 0x10;
+                m.tick(7, 0x1542)?;
+                // 1542  call 1424h
+                m.push(0x1545)?;
+                m.pc = 0x1424;
+                m.tick(17, 0x1424)?;
+                m.call(0x1545)?;
+                // 1545  mvi a,04h
+                m.a = 0x04;
+                m.tick(7, 0x1547)?;
+                // 1547  sta 2025h
+                m.write(0x2025, m.a)?;
+                m.tick(13, 0x154a)?;
+                // 154a  xra a
+                let value = m.a;
+                m.a ^= value;
+                m.flags = SZP[m.a as usize] | if m.a > 0xf { AUX_CARRY } else { 0 };
+                m.tick(4, 0x154b)?;
+                // 154b  sta 2002h
+                m.write(0x2002, m.a)?;
+                m.tick(13, 0x154e)?;
+                // 154e  mvi b,0f7h
+                m.b = 0xf7;
+                m.tick(7, 0x1550)?;
+                // 1550  jmp 19dch
+                m.pc = 0x19dc;
+                m.tick(10, 0x19dc)?;
+            },
+            0x19dc => {
+                // 19dc  lda 2094h
+                m.a = m.read(0x2094)?;
+                m.tick(13, 0x19df)?;
+                // 19df  ana b
+                let value = m.b;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(4, 0x19e0)?;
+                // 19e0  sta 2094h
+                m.write(0x2094, m.a)?;
+                m.tick(13, 0x19e3)?;
+                // 19e3  out 03h
+                m.output(0x03, m.a)?;
+                m.tick(10, 0x19e5)?;
+                // 19e5  ret
+                let target = m.pop()?;
+                m.pc = target;
+                m.tick(10, target)?;
+                return Ok(());
+            },
+            _ => return Ok(()),
+        }
+    }
+}
+
+fn sub_013b(m: &mut Machine) -> Result<(), Stop> {
+    // 013b  lxi h,0030h
+    m.set_hl(0x0030);
+    m.tick(10, 0x013e)?;
+    // 013e  dad d
+    let result = m.hl() as u32 + m.de() as u32;
+    m.flags = (m.flags & !CARRY) | (result >> 16) as u8;
+    m.set_hl(result as u16);
+    m.tick(10, 0x013f)?;
+    // 013f  xchg
+    std::mem::swap(&mut m.d, &mut m.h);
+    std::mem::swap(&mut m.e, &mut m.l);
+    m.tick(4, 0x0140)?;
+    // 0140  ret
+    let target = m.pop()?;
+    m.pc = target;
+    m.tick(10, target)?;
+    Ok(())
+}
+
+fn sub_0141(m: &mut Machine) -> Result<(), Stop> {
+    loop {
+        match m.pc {
+            0x0141 => {
+                // 0141  lda 2068h
+                m.a = m.read(0x2068)?;
+                m.tick(13, 0x0144)?;
+                // 0144  ana a
+                let value = m.a;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(4, 0x0145)?;
+                // 0145  rz
+                if m.flags & ZERO != 0 {
+                    let target = m.pop()?;
+                    m.pc = target;
+                    m.tick(11, target)?;
+                    return Ok(());
+                }
+                m.tick(5, 0x0146)?;
+                // 0146  lda 2000h
+                m.a = m.read(0x2000)?;
+                m.tick(13, 0x0149)?;
+                // 0149  ana a
+                let value = m.a;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(4, 0x014a)?;
+                // 014a  rnz
+                if m.flags & ZERO == 0 {
+                    let target = m.pop()?;
+                    m.pc = target;
+                    m.tick(11, target)?;
+                    return Ok(());
+                }
+                m.tick(5, 0x014b)?;
+                // 014b  lda 2067h
+                m.a = m.read(0x2067)?;
+                m.tick(13, 0x014e)?;
+                // 014e  mov h,a
+                m.h = m.a;
+                m.tick(5, 0x014f)?;
+                // 014f  lda 2006h
+                m.a = m.read(0x2006)?;
+                m.tick(13, 0x0152)?;
+                // 0152  mvi d,02h
+                m.d = 0x02;
+                m.tick(7, 0x0154)?;
+                m.pc = 0x0154;
+            },
+            0x0154 => {
+                // 0154  inr a
+                let value = m.a;
+                let value = value.wrapping_add(1);
+                m.flags = SZP[value as usize] | (m.flags & CARRY) | if value & 0xf == 0 { AUX_CARRY } else { 0 };
+                m.a = value;
+                m.tick(5, 0x0155)?;
+                // 0155  cpi 37h
+                let value: u8 = 0x37;
+                let result = (m.a as u16).wrapping_sub(value as u16);
+                m.flags = SZP[result as u8 as usize] | (result > 0xff) as u8 | if (m.a & 0xf).wrapping_sub(value & 0xf) > 0xf { AUX_CARRY } else { 0 };
+                m.tick(7, 0x0157)?;
+                // 0157  cz 01a1h
+                if m.flags & ZERO != 0 {
+                    m.push(0x015a)?;
+                    m.pc = 0x01a1;
+                    m.tick(17, 0x01a1)?;
+                    m.call(0x015a)?;
+                } else {
+                    m.tick(11, 0x015a)?;
+                }
+                // 015a  mov l,a
+                m.l = m.a;
+                m.tick(5, 0x015b)?;
+                // 015b  mov b,m
+                m.b = m.read(m.hl())?;
+                m.tick(7, 0x015c)?;
+                // 015c  dcr b
+                let value = m.b;
+                let value = value.wrapping_sub(1);
+                m.flags = SZP[value as usize] | (m.flags & CARRY) | if value & 0xf != 0xf { AUX_CARRY } else { 0 };
+                m.b = value;
+                m.tick(5, 0x015d)?;
+                // 015d  jnz 0154h
+                m.pc = if m.flags & ZERO == 0 { 0x0154 } else { 0x0160 };
+                m.tick(10, m.pc)?;
+            },
+            0x0160 => {
+                // 0160  sta 2006h
+                m.write(0x2006, m.a)?;
+                m.tick(13, 0x0163)?;
+                // 0163  call 017ah
+                m.push(0x0166)?;
+                m.pc = 0x017a;
+                m.tick(17, 0x017a)?;
+                m.call(0x0166)?;
+                // 0166  mov h,c
+                m.h = m.c;
+                m.tick(5, 0x0167)?;
+                // 0167  shld 200bh
+                m.write(0x200b, m.l)?;
+                m.write(0x200c, m.h)?;
+                m.tick(16, 0x016a)?;
+                // 016a  mov a,l
+                m.a = m.l;
+                m.tick(5, 0x016b)?;
+                // 016b  cpi 28h
+                let value: u8 = 0x28;
+                let result = (m.a as u16).wrapping_sub(value as u16);
+                m.flags = SZP[result as u8 as usize] | (result > 0xff) as u8 | if (m.a & 0xf).wrapping_sub(value & 0xf) > 0xf { AUX_CARRY } else { 0 };
+                m.tick(7, 0x016d)?;
+                // 016d  jc 1971h
+                m.pc = if m.flags & CARRY != 0 { 0x1971 } else { 0x0170 };
+                m.tick(10, m.pc)?;
+            },
+            0x0170 => {
+                // 0170  mov a,d
+                m.a = m.d;
+                m.tick(5, 0x0171)?;
+                // 0171  sta 2004h
+                m.write(0x2004, m.a)?;
+                m.tick(13, 0x0174)?;
+                // 0174  mvi a,01h
+                m.a = 0x01;
+                m.tick(7, 0x0176)?;
+                // 0176  sta 2000h
+                m.write(0x2000, m.a)?;
+                m.tick(13, 0x0179)?;
+                // 0179  ret
+                let target = m.pop()?;
+                m.pc = target;
+                m.tick(10, target)?;
+                return Ok(());
+            },
+            0x02ed => {
+                // 02ed  lda 2067h
+                m.a = m.read(0x2067)?;
+                m.tick(13, 0x02f0)?;
+                // 02f0  push psw
+                m.push((m.a as u16) << 8 | m.flags as u16)?;
+                m.tick(11, 0x02f1)?;
+                // 02f1  rrc
+                m.flags = (m.flags & !CARRY) | (m.a & 1);
+                m.a = m.a.rotate_right(1);
+                m.tick(4, 0x02f2)?;
+                // 02f2  jc 0332h
+                m.pc = if m.flags & CARRY != 0 { 0x0332 } else { 0x02f5 };
+                m.tick(10, m.pc)?;
+            },
+            0x02f5 => {
+                // 02f5  call 020eh
+                m.push(0x02f8)?;
+                m.pc = 0x020e;
+                m.tick(17, 0x020e)?;
+                m.call(0x02f8)?;
+                m.pc = 0x02f8;
+            },
+            0x02f8 => {
+                // 02f8  call 0878h
+                m.push(0x02fb)?;
+                m.pc = 0x0878;
+                m.tick(17, 0x0878)?;
+                m.call(0x02fb)?;
+                // 02fb  mov m,e
+                m.write(m.hl(), m.e)?;
+                m.tick(7, 0x02fc)?;
+                // 02fc  inx h
+                m.set_hl(m.hl().wrapping_add(1));
+                m.tick(5, 0x02fd)?;
+                // 02fd  mov m,d
+                m.write(m.hl(), m.d)?;
+                m.tick(7, 0x02fe)?;
+                // 02fe  dcx h
+                m.set_hl(m.hl().wrapping_sub(1));
+                m.tick(5, 0x02ff)?;
+                // 02ff  dcx h
+                m.set_hl(m.hl().wrapping_sub(1));
+                m.tick(5, 0x0300)?;
+                // 0300  mov m,b
+                m.write(m.hl(), m.b)?;
+                m.tick(7, 0x0301)?;
+                // 0301  nop
+                m.tick(4, 0x0302)?;
+                // 0302  call 01e4h
+                m.push(0x0305)?;
+                m.pc = 0x01e4;
+                m.tick(17, 0x01e4)?;
+                m.call(0x0305)?;
+                // 0305  pop psw
+                let value = m.pop()?;
+                m.a = (value >> 8) as u8;
+                m.flags = value as u8 & (SIGN | ZERO | AUX_CARRY | PARITY | CARRY);
+                m.tick(10, 0x0306)?;
+                // 0306  rrc
+                m.flags = (m.flags & !CARRY) | (m.a & 1);
+                m.a = m.a.rotate_right(1);
+                m.tick(4, 0x0307)?;
+                // 0307  mvi a,21h
+                m.a = 0x21;
+                m.tick(7, 0x0309)?;
+                // 0309  mvi b,00h
+                m.b = 0x00;
+                m.tick(7, 0x030b)?;
+                // 030b  jnc 0312h
+                m.pc = if m.flags & CARRY == 0 { 0x0312 } else { 0x030e };
+                m.tick(10, m.pc)?;
+            },
+            0x030e => {
+                // 030e  mvi b,20h
+                m.b = 0x20;
+                m.tick(7, 0x0310)?;
+                // 0310  mvi a,22h
+                m.a = 0x22;
+                m.tick(7, 0x0312)?;
+                m.pc = 0x0312;
+            },
+            0x0312 => {
+                // 0312  sta 2067h
+                m.write(0x2067, m.a)?;
+                m.tick(13, 0x0315)?;
+                // 0315  call 0ab6h
+                m.push(0x0318)?;
+                m.pc = 0x0ab6;
+                m.tick(17, 0x0ab6)?;
+                m.call(0x0318)?;
+                // 0318  xra a
+                let value = m.a;
+                m.a ^= value;
+                m.flags = SZP[m.a as usize] | if m.a > 0xf { AUX_CARRY } else { 0 };
+                m.tick(4, 0x0319)?;
+                // 0319  sta 2011h
+                m.write(0x2011, m.a)?;
+                m.tick(13, 0x031c)?;
+                // 031c  mov a,b
+                m.a = m.b;
+                m.tick(5, 0x031d)?;
+                // 031d  out 05h
+                m.output(0x05, m.a)?;
+                m.tick(10, 0x031f)?;
+                // 031f  inr a
+                let value = m.a;
+                let value = value.wrapping_add(1);
+                m.flags = SZP[value as usize] | (m.flags & CARRY) | if value & 0xf == 0 { AUX_CARRY } else { 0 };
+                m.a = value;
+                m.tick(5, 0x0320)?;
+                // 0320  sta 2098h
+                m.write(0x2098, m.a)?;
+                m.tick(13, 0x0323)?;
+                // 0323  call 09d6h
+                m.push(0x0326)?;
+                m.pc = 0x09d6;
+                m.tick(17, 0x09d6)?;
+                m.call(0x0326)?;
+                // 0326  call 1a7fh
+                m.push(0x0329)?;
+                m.pc = 0x1a7f;
+                m.tick(17, 0x1a7f)?;
+                m.call(0x0329)?;
+                // 0329  jmp 07f9h
+                m.pc = 0x07f9;
+                m.tick(10, 0x07f9)?;
+            },
+            0x0332 => {
+                // 0332  call 0209h
+                m.push(0x0335)?;
+                m.pc = 0x0209;
+                m.tick(17, 0x0209)?;
+                m.call(0x0335)?;
+                // 0335  jmp 02f8h
+                m.pc = 0x02f8;
+                m.tick(10, 0x02f8)?;
+            },
+            0x07f9 => {
+                // 07f9  call 088dh
+                m.push(0x07fc)?;
+                m.pc = 0x088d;
+                m.tick(17, 0x088d)?;
+                m.call(0x07fc)?;
+                // 07fc  call 09d6h
+                m.push(0x07ff)?;
+                m.pc = 0x09d6;
+                m.tick(17, 0x09d6)?;
+                m.call(0x07ff)?;
+                // 07ff  nop
+                m.tick(4, 0x0800)?;
+                // 0800  xra a
+                let value = m.a;
+                m.a ^= value;
+                m.flags = SZP[m.a as usize] | if m.a > 0xf { AUX_CARRY } else { 0 };
+                m.tick(4, 0x0801)?;
+                // 0801  sta 20c1h
+                m.write(0x20c1, m.a)?;
+                m.tick(13, 0x0804)?;
+                m.pc = 0x0804;
+            },
+            0x0804 => {
+                // 0804  call 01cfh
+                m.push(0x0807)?;
+                m.pc = 0x01cf;
+                m.tick(17, 0x01cf)?;
+                m.call(0x0807)?;
+                // 0807  lda 2067h
+                m.a = m.read(0x2067)?;
+                m.tick(13, 0x080a)?;
+                // 080a  rrc
+                m.flags = (m.flags & !CARRY) | (m.a & 1);
+                m.a = m.a.rotate_right(1);
+                m.tick(4, 0x080b)?;
+                // 080b  jc 0872h
+                m.pc = if m.flags & CARRY != 0 { 0x0872 } else { 0x080e };
+                m.tick(10, m.pc)?;
+            },
+            0x080e => {
+                // 080e  call 0213h
+                m.push(0x0811)?;
+                m.pc = 0x0213;
+                m.tick(17, 0x0213)?;
+                m.call(0x0811)?;
+                // 0811  call 01cfh
+                m.push(0x0814)?;
+                m.pc = 0x01cf;
+                m.tick(17, 0x01cf)?;
+                m.call(0x0814)?;
+                m.pc = 0x0814;
+            },
+            0x0814 => {
+                // 0814  call 00b1h
+                m.push(0x0817)?;
+                m.pc = 0x00b1;
+                m.tick(17, 0x00b1)?;
+                m.call(0x0817)?;
+                // 0817  call 19d1h
+                m.push(0x081a)?;
+                m.pc = 0x19d1;
+                m.tick(17, 0x19d1)?;
+                m.call(0x081a)?;
+                // 081a  mvi b,20h
+                m.b = 0x20;
+                m.tick(7, 0x081c)?;
+                // 081c  call 18fah
+                m.push(0x081f)?;
+                m.pc = 0x18fa;
+                m.tick(17, 0x18fa)?;
+                m.call(0x081f)?;
+                m.pc = 0x081f;
+            },
+            0x081f => {
+                // 081f  call 1618h
+                m.push(0x0822)?;
+                m.pc = 0x1618;
+                m.tick(17, 0x1618)?;
+                m.call(0x0822)?;
+                // 0822  call 190ah
+                m.push(0x0825)?;
+                m.pc = 0x190a;
+                m.tick(17, 0x190a)?;
+                m.call(0x0825)?;
+                // 0825  call 15f3h
+                m.push(0x0828)?;
+                m.pc = 0x15f3;
+                m.tick(17, 0x15f3)?;
+                m.call(0x0828)?;
+                // 0828  call 0988h
+                m.push(0x082b)?;
+                m.pc = 0x0988;
+                m.tick(17, 0x0988)?;
+                m.call(0x082b)?;
+                // 082b  lda 2082h
+                m.a = m.read(0x2082)?;
+                m.tick(13, 0x082e)?;
+                // 082e  ana a
+                let value = m.a;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(4, 0x082f)?;
+                // 082f  jz 09efh
+                m.pc = if m.flags & ZERO != 0 { 0x09ef } else { 0x0832 };
+                m.tick(10, m.pc)?;
+            },
+            0x0832 => {
+                // 0832  call 170eh
+                m.push(0x0835)?;
+                m.pc = 0x170e;
+                m.tick(17, 0x170e)?;
+                m.call(0x0835)?;
+                // 0835  call 0935h
+                m.push(0x0838)?;
+                m.pc = 0x0935;
+                m.tick(17, 0x0935)?;
+                m.call(0x0838)?;
+                // 0838  call 08d8h
+                m.push(0x083b)?;
+                m.pc = 0x08d8;
+                m.tick(17, 0x08d8)?;
+                m.call(0x083b)?;
+                // 083b  call 172ch
+                m.push(0x083e)?;
+                m.pc = 0x172c;
+                m.tick(17, 0x172c)?;
+                m.call(0x083e)?;
+                // 083e  call 0a59h
+                m.push(0x0841)?;
+                m.pc = 0x0a59;
+                m.tick(17, 0x0a59)?;
+                m.call(0x0841)?;
+                // 0841  jz 0849h
+                m.pc = if m.flags & ZERO != 0 { 0x0849 } else { 0x0844 };
+                m.tick(10, m.pc)?;
+            },
+            0x0844 => {
+                // 0844  mvi b,04h
+                m.b = 0x04;
+                m.tick(7, 0x0846)?;
+                // 0846  call 18fah
+                m.push(0x0849)?;
+                m.pc = 0x18fa;
+                m.tick(17, 0x18fa)?;
+                m.call(0x0849)?;
+                m.pc = 0x0849;
+            },
+            0x0849 => {
+                // 0849  call 1775h
+                m.push(0x084c)?;
+                m.pc = 0x1775;
+                m.tick(17, 0x1775)?;
+                m.call(0x084c)?;
+                // 084c  out 06h
+                m.output(0x06, m.a)?;
+                m.tick(10, 0x084e)?;
+                // 084e  call 1804h
+                m.push(0x0851)?;
+                m.pc = 0x1804;
+                m.tick(17, 0x1804)?;
+                m.call(0x0851)?;
+                // 0851  jmp 081fh
+                m.pc = 0x081f;
+                m.tick(10, 0x081f)?;
+            },
+            0x0872 => {
+                // 0872  call 021ah
+                m.push(0x0875)?;
+                m.pc = 0x021a;
+                m.tick(17, 0x021a)?;
+                m.call(0x0875)?;
+                // 0875  jmp 0814h
+                m.pc = 0x0814;
+                m.tick(10, 0x0814)?;
+            },
+            0x09ef => {
+                // 09ef  call 0a3ch
+                m.push(0x09f2)?;
+                m.pc = 0x0a3c;
+                m.tick(17, 0x0a3c)?;
+                m.call(0x09f2)?;
+                // 09f2  xra a
+                let value = m.a;
+                m.a ^= value;
+                m.flags = SZP[m.a as usize] | if m.a > 0xf { AUX_CARRY } else { 0 };
+                m.tick(4, 0x09f3)?;
+                // 09f3  sta 20e9h
+                m.write(0x20e9, m.a)?;
+                m.tick(13, 0x09f6)?;
+                // 09f6  call 09d6h
+                m.push(0x09f9)?;
+                m.pc = 0x09d6;
+                m.tick(17, 0x09d6)?;
+                m.call(0x09f9)?;
+                // 09f9  lda 2067h
+                m.a = m.read(0x2067)?;
+                m.tick(13, 0x09fc)?;
+                // 09fc  push psw
+                m.push((m.a as u16) << 8 | m.flags as u16)?;
+                m.tick(11, 0x09fd)?;
+                // 09fd  call 01e4h
+                m.push(0x0a00)?;
+                m.pc = 0x01e4;
+                m.tick(17, 0x01e4)?;
+                m.call(0x0a00)?;
+                // 0a00  pop psw
+                let value = m.pop()?;
+                m.a = (value >> 8) as u8;
+                m.flags = value as u8 & (SIGN | ZERO | AUX_CARRY | PARITY | CARRY);
+                m.tick(10, 0x0a01)?;
+                // 0a01  sta 2067h
+                m.write(0x2067, m.a)?;
+                m.tick(13, 0x0a04)?;
+                // 0a04  lda 2067h
+                m.a = m.read(0x2067)?;
+                m.tick(13, 0x0a07)?;
+                // 0a07  mov h,a
+                m.h = m.a;
+                m.tick(5, 0x0a08)?;
+                // 0a08  push h
+                m.push(m.hl())?;
+                m.tick(11, 0x0a09)?;
+                // 0a09  mvi l,0feh
+                m.l = 0xfe;
+                m.tick(7, 0x0a0b)?;
+                // 0a0b  mov a,m
+                m.a = m.read(m.hl())?;
+                m.tick(7, 0x0a0c)?;
+                // 0a0c  ani 07h
+                let value: u8 = 0x07;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(7, 0x0a0e)?;
+                // 0a0e  inr a
+                let value = m.a;
+                let value = value.wrapping_add(1);
+                m.flags = SZP[value as usize] | (m.flags & CARRY) | if value & 0xf == 0 { AUX_CARRY } else { 0 };
+                m.a = value;
+                m.tick(5, 0x0a0f)?;
+                // 0a0f  mov m,a
+                m.write(m.hl(), m.a)?;
+                m.tick(7, 0x0a10)?;
+                // 0a10  lxi h,1da2h
+                m.set_hl(0x1da2);
+                m.tick(10, 0x0a13)?;
+                m.pc = 0x0a13;
+            },
+            0x0a13 => {
+                // 0a13  inx h
+                m.set_hl(m.hl().wrapping_add(1));
+                m.tick(5, 0x0a14)?;
+                // 0a14  dcr a
+                let value = m.a;
+                let value = value.wrapping_sub(1);
+                m.flags = SZP[value as usize] | (m.flags & CARRY) | if value & 0xf != 0xf { AUX_CARRY } else { 0 };
+                m.a = value;
+                m.tick(5, 0x0a15)?;
+                // 0a15  jnz 0a13h
+                m.pc = if m.flags & ZERO == 0 { 0x0a13 } else { 0x0a18 };
+                m.tick(10, m.pc)?;
+            },
+            0x0a18 => {
+                // 0a18  mov a,m
+                m.a = m.read(m.hl())?;
+                m.tick(7, 0x0a19)?;
+                // 0a19  pop h
+                let value = m.pop()?;
+                m.set_hl(value);
+                m.tick(10, 0x0a1a)?;
+                // 0a1a  mvi l,0fch
+                m.l = 0xfc;
+                m.tick(7, 0x0a1c)?;
+                // 0a1c  mov m,a
+                m.write(m.hl(), m.a)?;
+                m.tick(7, 0x0a1d)?;
+                // 0a1d  inx h
+                m.set_hl(m.hl().wrapping_add(1));
+                m.tick(5, 0x0a1e)?;
+                // 0a1e  mvi m,38h
+                m.write(m.hl(), 0x38)?;
+                m.tick(10, 0x0a20)?;
+                // 0a20  mov a,h
+                m.a = m.h;
+                m.tick(5, 0x0a21)?;
+                // 0a21  rrc
+                m.flags = (m.flags & !CARRY) | (m.a & 1);
+                m.a = m.a.rotate_right(1);
+                m.tick(4, 0x0a22)?;
+                // 0a22  jc 0a33h
+                m.pc = if m.flags & CARRY != 0 { 0x0a33 } else { 0x0a25 };
+                m.tick(10, m.pc)?;
+            },
+            0x0a25 => {
+                // 0a25  mvi a,21h
+                m.a = 0x21;
+                m.tick(7, 0x0a27)?;
+                // 0a27  sta 2098h
+                m.write(0x2098, m.a)?;
+                m.tick(13, 0x0a2a)?;
+                // 0a2a  call 01f5h
+                m.push(0x0a2d)?;
+                m.pc = 0x01f5;
+                m.tick(17, 0x01f5)?;
+                m.call(0x0a2d)?;
+                // 0a2d  call 1904h
+                m.push(0x0a30)?;
+                m.pc = 0x1904;
+                m.tick(17, 0x1904)?;
+                m.call(0x0a30)?;
+                // 0a30  jmp 0804h
+                m.pc = 0x0804;
+                m.tick(10, 0x0804)?;
+            },
+            0x0a33 => {
+                // 0a33  call 01efh
+                m.push(0x0a36)?;
+                m.pc = 0x01ef;
+                m.tick(17, 0x01ef)?;
+                m.call(0x0a36)?;
+                // 0a36  call 01c0h
+                m.push(0x0a39)?;
+                m.pc = 0x01c0;
+                m.tick(17, 0x01c0)?;
+                m.call(0x0a39)?;
+                // 0a39  jmp 0804h
+                m.pc = 0x0804;
+                m.tick(10, 0x0804)?;
+            },
+            0x0aea => {
+                // 0aea  xra a
+                let value = m.a;
+                m.a ^= value;
+                m.flags = SZP[m.a as usize] | if m.a > 0xf { AUX_CARRY } else { 0 };
+                m.tick(4, 0x0aeb)?;
+                // 0aeb  out 03h
+                m.output(0x03, m.a)?;
+                m.tick(10, 0x0aed)?;
+                // 0aed  out 05h
+                m.output(0x05, m.a)?;
+                m.tick(10, 0x0aef)?;
+                // 0aef  call 1982h
+                m.push(0x0af2)?;
+                m.pc = 0x1982;
+                m.tick(17, 0x1982)?;
+                m.call(0x0af2)?;
+                m.pc = 0x0af2;
+            },
+            // 0af2  ei
+            0x0af2 => m.interpret()?,
+            0x0af6 => {
+                // 0af6  lda 20ech
+                m.a = m.read(0x20ec)?;
+                m.tick(13, 0x0af9)?;
+                // 0af9  ana a
+                let value = m.a;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(4, 0x0afa)?;
+                // 0afa  lxi h,3017h
+                m.set_hl(0x3017);
+                m.tick(10, 0x0afd)?;
+                // 0afd  mvi c,04h
+                m.c = 0x04;
+                m.tick(7, 0x0aff)?;
+                // 0aff  jnz 0be8h
+                m.pc = if m.flags & ZERO == 0 { 0x0be8 } else { 0x0b02 };
+                m.tick(10, m.pc)?;
+            },
+            0x0b02 => {
+                // 0b02  lxi d,1cfah
+                m.set_de(0x1cfa);
+                m.tick(10, 0x0b05)?;
+                // 0b05  call 0a93h
+                m.push(0x0b08)?;
+                m.pc = 0x0a93;
+                m.tick(17, 0x0a93)?;
+                m.call(0x0b08)?;
+                // 0b08  lxi d,1dafh
+                m.set_de(0x1daf);
+                m.tick(10, 0x0b0b)?;
+                m.pc = 0x0b0b;
+            },
+            0x0b0b => {
+                // 0b0b  call 0acfh
+                m.push(0x0b0e)?;
+                m.pc = 0x0acf;
+                m.tick(17, 0x0acf)?;
+                m.call(0x0b0e)?;
+                // 0b0e  call 0ab1h
+                m.push(0x0b11)?;
+                m.pc = 0x0ab1;
+                m.tick(17, 0x0ab1)?;
+                m.call(0x0b11)?;
+                // 0b11  call 1815h
+                m.push(0x0b14)?;
+                m.pc = 0x1815;
+                m.tick(17, 0x1815)?;
+                m.call(0x0b14)?;
+                // 0b14  call 0ab6h
+                m.push(0x0b17)?;
+                m.pc = 0x0ab6;
+                m.tick(17, 0x0ab6)?;
+                m.call(0x0b17)?;
+                // 0b17  lda 20ech
+                m.a = m.read(0x20ec)?;
+                m.tick(13, 0x0b1a)?;
+                // 0b1a  ana a
+                let value = m.a;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(4, 0x0b1b)?;
+                // 0b1b  jnz 0b4ah
+                m.pc = if m.flags & ZERO == 0 { 0x0b4a } else { 0x0b1e };
+                m.tick(10, m.pc)?;
+            },
+            0x0b1e => {
+                // 0b1e  lxi d,1a95h
+                m.set_de(0x1a95);
+                m.tick(10, 0x0b21)?;
+                // 0b21  call 0ae2h
+                m.push(0x0b24)?;
+                m.pc = 0x0ae2;
+                m.tick(17, 0x0ae2)?;
+                m.call(0x0b24)?;
+                // 0b24  call 0a80h
+                m.push(0x0b27)?;
+                m.pc = 0x0a80;
+                m.tick(17, 0x0a80)?;
+                m.call(0x0b27)?;
+                // 0b27  lxi d,1bb0h
+                m.set_de(0x1bb0);
+                m.tick(10, 0x0b2a)?;
+                // 0b2a  call 0ae2h
+                m.push(0x0b2d)?;
+                m.pc = 0x0ae2;
+                m.tick(17, 0x0ae2)?;
+                m.call(0x0b2d)?;
+                // 0b2d  call 0a80h
+                m.push(0x0b30)?;
+                m.pc = 0x0a80;
+                m.tick(17, 0x0a80)?;
+                m.call(0x0b30)?;
+                // 0b30  call 0ab1h
+                m.push(0x0b33)?;
+                m.pc = 0x0ab1;
+                m.tick(17, 0x0ab1)?;
+                m.call(0x0b33)?;
+                // 0b33  lxi d,1fc9h
+                m.set_de(0x1fc9);
+                m.tick(10, 0x0b36)?;
+                // 0b36  call 0ae2h
+                m.push(0x0b39)?;
+                m.pc = 0x0ae2;
+                m.tick(17, 0x0ae2)?;
+                m.call(0x0b39)?;
+                // 0b39  call 0a80h
+                m.push(0x0b3c)?;
+                m.pc = 0x0a80;
+                m.tick(17, 0x0a80)?;
+                m.call(0x0b3c)?;
+                // 0b3c  call 0ab1h
+                m.push(0x0b3f)?;
+                m.pc = 0x0ab1;
+                m.tick(17, 0x0ab1)?;
+                m.call(0x0b3f)?;
+                // 0b3f  lxi h,33b7h
+                m.set_hl(0x33b7);
+                m.tick(10, 0x0b42)?;
+                // 0b42  mvi b,0ah
+                m.b = 0x0a;
+                m.tick(7, 0x0b44)?;
+                // 0b44  call 14cbh
+                m.push(0x0b47)?;
+                m.pc = 0x14cb;
+                m.tick(17, 0x14cb)?;
+                m.call(0x0b47)?;
+                // 0b47  call 0ab6h
+                m.push(0x0b4a)?;
+                m.pc = 0x0ab6;
+                m.tick(17, 0x0ab6)?;
+                m.call(0x0b4a)?;
+                m.pc = 0x0b4a;
+            },
+            0x0b4a => {
+                // 0b4a  call 09d6h
+                m.push(0x0b4d)?;
+                m.pc = 0x09d6;
+                m.tick(17, 0x09d6)?;
+                m.call(0x0b4d)?;
+                // 0b4d  lda 21ffh
+                m.a = m.read(0x21ff)?;
+                m.tick(13, 0x0b50)?;
+                // 0b50  ana a
+                let value = m.a;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(4, 0x0b51)?;
+                // 0b51  jnz 0b5dh
+                m.pc = if m.flags & ZERO == 0 { 0x0b5d } else { 0x0b54 };
+                m.tick(10, m.pc)?;
+            },
+            0x0b54 => {
+                // 0b54  call 08d1h
+                m.push(0x0b57)?;
+                m.pc = 0x08d1;
+                m.tick(17, 0x08d1)?;
+                m.call(0x0b57)?;
+                // 0b57  sta 21ffh
+                m.write(0x21ff, m.a)?;
+                m.tick(13, 0x0b5a)?;
+                // 0b5a  call 1a7fh
+                m.push(0x0b5d)?;
+                m.pc = 0x1a7f;
+                m.tick(17, 0x1a7f)?;
+                m.call(0x0b5d)?;
+                m.pc = 0x0b5d;
+            },
+            0x0b5d => {
+                // 0b5d  call 01e4h
+                m.push(0x0b60)?;
+                m.pc = 0x01e4;
+                m.tick(17, 0x01e4)?;
+                m.call(0x0b60)?;
+                // 0b60  call 01c0h
+                m.push(0x0b63)?;
+                m.pc = 0x01c0;
+                m.tick(17, 0x01c0)?;
+                m.call(0x0b63)?;
+                // 0b63  call 01efh
+                m.push(0x0b66)?;
+                m.pc = 0x01ef;
+                m.tick(17, 0x01ef)?;
+                m.call(0x0b66)?;
+                // 0b66  call 021ah
+                m.push(0x0b69)?;
+                m.pc = 0x021a;
+                m.tick(17, 0x021a)?;
+                m.call(0x0b69)?;
+                // 0b69  mvi a,01h
+                m.a = 0x01;
+                m.tick(7, 0x0b6b)?;
+                // 0b6b  sta 20c1h
+                m.write(0x20c1, m.a)?;
+                m.tick(13, 0x0b6e)?;
+                // 0b6e  call 01cfh
+                m.push(0x0b71)?;
+                m.pc = 0x01cf;
+                m.tick(17, 0x01cf)?;
+                m.call(0x0b71)?;
+                m.pc = 0x0b71;
+            },
+            0x0b71 => {
+                // 0b71  call 1618h
+                m.push(0x0b74)?;
+                m.pc = 0x1618;
+                m.tick(17, 0x1618)?;
+                m.call(0x0b74)?;
+                // 0b74  call 0bf1h
+                m.push(0x0b77)?;
+                m.pc = 0x0bf1;
+                m.tick(17, 0x0bf1)?;
+                m.call(0x0b77)?;
+                // 0b77  out 06h
+                m.output(0x06, m.a)?;
+                m.tick(10, 0x0b79)?;
+                // 0b79  call 0a59h
+                m.push(0x0b7c)?;
+                m.pc = 0x0a59;
+                m.tick(17, 0x0a59)?;
+                m.call(0x0b7c)?;
+                // 0b7c  jz 0b71h
+                m.pc = if m.flags & ZERO != 0 { 0x0b71 } else { 0x0b7f };
+                m.tick(10, m.pc)?;
+            },
+            0x0b7f => {
+                // 0b7f  xra a
+                let value = m.a;
+                m.a ^= value;
+                m.flags = SZP[m.a as usize] | if m.a > 0xf { AUX_CARRY } else { 0 };
+                m.tick(4, 0x0b80)?;
+                // 0b80  sta 2025h
+                m.write(0x2025, m.a)?;
+                m.tick(13, 0x0b83)?;
+                m.pc = 0x0b83;
+            },
+            0x0b83 => {
+                // 0b83  call 0a59h
+                m.push(0x0b86)?;
+                m.pc = 0x0a59;
+                m.tick(17, 0x0a59)?;
+                m.call(0x0b86)?;
+                // 0b86  jnz 0b83h
+                m.pc = if m.flags & ZERO == 0 { 0x0b83 } else { 0x0b89 };
+                m.tick(10, m.pc)?;
+            },
+            0x0b89 => {
+                // 0b89  xra a
+                let value = m.a;
+                m.a ^= value;
+                m.flags = SZP[m.a as usize] | if m.a > 0xf { AUX_CARRY } else { 0 };
+                m.tick(4, 0x0b8a)?;
+                // 0b8a  sta 20c1h
+                m.write(0x20c1, m.a)?;
+                m.tick(13, 0x0b8d)?;
+                // 0b8d  call 0ab1h
+                m.push(0x0b90)?;
+                m.pc = 0x0ab1;
+                m.tick(17, 0x0ab1)?;
+                m.call(0x0b90)?;
+                // 0b90  call 1988h
+                m.push(0x0b93)?;
+                m.pc = 0x1988;
+                m.tick(17, 0x1988)?;
+                m.call(0x0b93)?;
+                // 0b93  mvi c,0ch
+                m.c = 0x0c;
+                m.tick(7, 0x0b95)?;
+                // 0b95  lxi h,2c11h
+                m.set_hl(0x2c11);
+                m.tick(10, 0x0b98)?;
+                // 0b98  lxi d,1f90h
+                m.set_de(0x1f90);
+                m.tick(10, 0x0b9b)?;
+                // 0b9b  call 08f3h
+                m.push(0x0b9e)?;
+                m.pc = 0x08f3;
+                m.tick(17, 0x08f3)?;
+                m.call(0x0b9e)?;
+                // 0b9e  lda 20ech
+                m.a = m.read(0x20ec)?;
+                m.tick(13, 0x0ba1)?;
+                // 0ba1  cpi 00h
+                let value: u8 = 0x00;
+                let result = (m.a as u16).wrapping_sub(value as u16);
+                m.flags = SZP[result as u8 as usize] | (result > 0xff) as u8 | if (m.a & 0xf).wrapping_sub(value & 0xf) > 0xf { AUX_CARRY } else { 0 };
+                m.tick(7, 0x0ba3)?;
+                // 0ba3  jnz 0baeh
+                m.pc = if m.flags & ZERO == 0 { 0x0bae } else { 0x0ba6 };
+                m.tick(10, m.pc)?;
+            },
+            0x0ba6 => {
+                // 0ba6  lxi h,3311h
+                m.set_hl(0x3311);
+                m.tick(10, 0x0ba9)?;
+                // 0ba9  mvi a,02h
+                m.a = 0x02;
+                m.tick(7, 0x0bab)?;
+                // 0bab  call 08ffh
+                m.push(0x0bae)?;
+                m.pc = 0x08ff;
+                m.tick(17, 0x08ff)?;
+                m.call(0x0bae)?;
+                m.pc = 0x0bae;
+            },
+            0x0bae => {
+                // 0bae  lxi b,1f9ch
+                m.set_bc(0x1f9c);
+                m.tick(10, 0x0bb1)?;
+                // 0bb1  call 1856h
+                m.push(0x0bb4)?;
+                m.pc = 0x1856;
+                m.tick(17, 0x1856)?;
+                m.call(0x0bb4)?;
+                // 0bb4  call 184ch
+                m.push(0x0bb7)?;
+                m.pc = 0x184c;
+                m.tick(17, 0x184c)?;
+                m.call(0x0bb7)?;
+                // 0bb7  in 02h
+                m.a = m.input(0x02)?;
+                m.tick(10, 0x0bb9)?;
+                // 0bb9  rlc
+                m.flags = (m.flags & !CARRY) | (m.a >> 7);
+                m.a = m.a.rotate_left(1);
+                m.tick(4, 0x0bba)?;
+                // 0bba  jc 0bc3h
+                m.pc = if m.flags & CARRY != 0 { 0x0bc3 } else { 0x0bbd };
+                m.tick(10, m.pc)?;
+            },
+            0x0bbd => {
+                // 0bbd  lxi b,1fa0h
+                m.set_bc(0x1fa0);
+                m.tick(10, 0x0bc0)?;
+                // 0bc0  call 183ah
+                m.push(0x0bc3)?;
+                m.pc = 0x183a;
+                m.tick(17, 0x183a)?;
+                m.call(0x0bc3)?;
+                m.pc = 0x0bc3;
+            },
+            0x0bc3 => {
+                // 0bc3  call 0ab6h
+                m.push(0x0bc6)?;
+                m.pc = 0x0ab6;
+                m.tick(17, 0x0ab6)?;
+                m.call(0x0bc6)?;
+                // 0bc6  lda 20ech
+                m.a = m.read(0x20ec)?;
+                m.tick(13, 0x0bc9)?;
+                // 0bc9  cpi 00h
+                let value: u8 = 0x00;
+                let result = (m.a as u16).wrapping_sub(value as u16);
+                m.flags = SZP[result as u8 as usize] | (result > 0xff) as u8 | if (m.a & 0xf).wrapping_sub(value & 0xf) > 0xf { AUX_CARRY } else { 0 };
+                m.tick(7, 0x0bcb)?;
+                // 0bcb  jnz 0bdah
+                m.pc = if m.flags & ZERO == 0 { 0x0bda } else { 0x0bce };
+                m.tick(10, m.pc)?;
+            },
+            0x0bce => {
+                // 0bce  lxi d,1fd5h
+                m.set_de(0x1fd5);
+                m.tick(10, 0x0bd1)?;
+                // 0bd1  call 0ae2h
+                m.push(0x0bd4)?;
+                m.pc = 0x0ae2;
+                m.tick(17, 0x0ae2)?;
+                m.call(0x0bd4)?;
+                // 0bd4  call 0a80h
+                m.push(0x0bd7)?;
+                m.pc = 0x0a80;
+                m.tick(17, 0x0a80)?;
+                m.call(0x0bd7)?;
+                // 0bd7  call 189eh
+                m.push(0x0bda)?;
+                m.pc = 0x189e;
+                m.tick(17, 0x189e)?;
+                m.call(0x0bda)?;
+                m.pc = 0x0bda;
+            },
+            0x0bda => {
+                // 0bda  lxi h,20ech
+                m.set_hl(0x20ec);
+                m.tick(10, 0x0bdd)?;
+                // 0bdd  mov a,m
+                m.a = m.read(m.hl())?;
+                m.tick(7, 0x0bde)?;
+                // 0bde  inr a
+                let value = m.a;
+                let value = value.wrapping_add(1);
+                m.flags = SZP[value as usize] | (m.flags & CARRY) | if value & 0xf == 0 { AUX_CARRY } else { 0 };
+                m.a = value;
+                m.tick(5, 0x0bdf)?;
+                // 0bdf  ani 01h
+                let value: u8 = 0x01;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(7, 0x0be1)?;
+                // 0be1  mov m,a
+                m.write(m.hl(), m.a)?;
+                m.tick(7, 0x0be2)?;
+                // 0be2  call 09d6h
+                m.push(0x0be5)?;
+                m.pc = 0x09d6;
+                m.tick(17, 0x09d6)?;
+                m.call(0x0be5)?;
+                // 0be5  jmp 18dfh
+                m.pc = 0x18df;
+                m.tick(10, 0x18df)?;
+            },
+            0x0be8 => {
+                // 0be8  lxi d,1dabh
+                m.set_de(0x1dab);
+                m.tick(10, 0x0beb)?;
+                // 0beb  call 0a93h
+                m.push(0x0bee)?;
+                m.pc = 0x0a93;
+                m.tick(17, 0x0a93)?;
+                m.call(0x0bee)?;
+                // 0bee  jmp 0b0bh
+                m.pc = 0x0b0b;
+                m.tick(10, 0x0b0b)?;
+            },
+            0x1671 => {
+                // 1671  call 1910h
+                m.push(0x1674)?;
+                m.pc = 0x1910;
+                m.tick(17, 0x1910)?;
+                m.call(0x1674)?;
+                // 1674  mvi m,00h
+                m.write(m.hl(), 0x00)?;
+                m.tick(10, 0x1676)?;
+                // 1676  call 09cah
+                m.push(0x1679)?;
+                m.pc = 0x09ca;
+                m.tick(17, 0x09ca)?;
+                m.call(0x1679)?;
+                // 1679  inx h
+                m.set_hl(m.hl().wrapping_add(1));
+                m.tick(5, 0x167a)?;
+                // 167a  lxi d,20f5h
+                m.set_de(0x20f5);
+                m.tick(10, 0x167d)?;
+                // 167d  ldax d
+                m.a = m.read(m.de())?;
+                m.tick(7, 0x167e)?;
+                // 167e  cmp m
+                let value = m.read(m.hl())?;
+                let result = (m.a as u16).wrapping_sub(value as u16);
+                m.flags = SZP[result as u8 as usize] | (result > 0xff) as u8 | if (m.a & 0xf).wrapping_sub(value & 0xf) > 0xf { AUX_CARRY } else { 0 };
+                m.tick(7, 0x167f)?;
+                // 167f  dcx d
+                m.set_de(m.de().wrapping_sub(1));
+                m.tick(5, 0x1680)?;
+                // 1680  dcx h
+                m.set_hl(m.hl().wrapping_sub(1));
+                m.tick(5, 0x1681)?;
+                // 1681  ldax d
+                m.a = m.read(m.de())?;
+                m.tick(7, 0x1682)?;
+                // 1682  jz 168bh
+                m.pc = if m.flags & ZERO != 0 { 0x168b } else { 0x1685 };
+                m.tick(10, m.pc)?;
+            },
+            0x1685 => {
+                // 1685  jnc 1698h
+                m.pc = if m.flags & CARRY == 0 { 0x1698 } else { 0x1688 };
+                m.tick(10, m.pc)?;
+            },
+            0x1688 => {
+                // 1688  jmp 168fh
+                m.pc = 0x168f;
+                m.tick(10, 0x168f)?;
+            },
+            0x168b => {
+                // 168b  cmp m
+                let value = m.read(m.hl())?;
+                let result = (m.a as u16).wrapping_sub(value as u16);
+                m.flags = SZP[result as u8 as usize] | (result > 0xff) as u8 | if (m.a & 0xf).wrapping_sub(value & 0xf) > 0xf { AUX_CARRY } else { 0 };
+                m.tick(7, 0x168c)?;
+                // 168c  jnc 1698h
+                m.pc = if m.flags & CARRY == 0 { 0x1698 } else { 0x168f };
+                m.tick(10, m.pc)?;
+            },
+            0x168f => {
+                // 168f  mov a,m
+                m.a = m.read(m.hl())?;
+                m.tick(7, 0x1690)?;
+                // 1690  stax d
+                m.write(m.de(), m.a)?;
+                m.tick(7, 0x1691)?;
+                // 1691  inx d
+                m.set_de(m.de().wrapping_add(1));
+                m.tick(5, 0x1692)?;
+                // 1692  inx h
+                m.set_hl(m.hl().wrapping_add(1));
+                m.tick(5, 0x1693)?;
+                // 1693  mov a,m
+                m.a = m.read(m.hl())?;
+                m.tick(7, 0x1694)?;
+                // 1694  stax d
+                m.write(m.de(), m.a)?;
+                m.tick(7, 0x1695)?;
+                // 1695  call 1950h
+                m.push(0x1698)?;
+                m.pc = 0x1950;
+                m.tick(17, 0x1950)?;
+                m.call(0x1698)?;
+                m.pc = 0x1698;
+            },
+            0x1698 => {
+                // 1698  lda 20ceh
+                m.a = m.read(0x20ce)?;
+                m.tick(13, 0x169b)?;
+                // 169b  ana a
+                let value = m.a;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(4, 0x169c)?;
+                // 169c  jz 16c9h
+                m.pc = if m.flags & ZERO != 0 { 0x16c9 } else { 0x169f };
+                m.tick(10, m.pc)?;
+            },
+            0x169f => {
+                // 169f  lxi h,2803h
+                m.set_hl(0x2803);
+                m.tick(10, 0x16a2)?;
+                // 16a2  lxi d,1aa6h
+                m.set_de(0x1aa6);
+                m.tick(10, 0x16a5)?;
+                // 16a5  mvi c,14h
+                m.c = 0x14;
+                m.tick(7, 0x16a7)?;
+                // 16a7  call 0a93h
+                m.push(0x16aa)?;
+                m.pc = 0x0a93;
+                m.tick(17, 0x0a93)?;
+                m.call(0x16aa)?;
+                // 16aa  dcr h
+                let value = m.h;
+                let value = value.wrapping_sub(1);
+                m.flags = SZP[value as usize] | (m.flags & CARRY) | if value & 0xf != 0xf { AUX_CARRY } else { 0 };
+                m.h = value;
+                m.tick(5, 0x16ab)?;
+                // 16ab  dcr h
+                let value = m.h;
+                let value = value.wrapping_sub(1);
+                m.flags = SZP[value as usize] | (m.flags & CARRY) | if value & 0xf != 0xf { AUX_CARRY } else { 0 };
+                m.h = value;
+                m.tick(5, 0x16ac)?;
+                // 16ac  mvi b,1bh
+                m.b = 0x1b;
+                m.tick(7, 0x16ae)?;
+                // 16ae  lda 2067h
+                m.a = m.read(0x2067)?;
+                m.tick(13, 0x16b1)?;
+                // 16b1  rrc
+                m.flags = (m.flags & !CARRY) | (m.a & 1);
+                m.a = m.a.rotate_right(1);
+                m.tick(4, 0x16b2)?;
+                // 16b2  jc 16b7h
+                m.pc = if m.flags & CARRY != 0 { 0x16b7 } else { 0x16b5 };
+                m.tick(10, m.pc)?;
+            },
+            0x16b5 => {
+                // 16b5  mvi b,1ch
+                m.b = 0x1c;
+                m.tick(7, 0x16b7)?;
+                m.pc = 0x16b7;
+            },
+            0x16b7 => {
+                // 16b7  mov a,b
+                m.a = m.b;
+                m.tick(5, 0x16b8)?;
+                // 16b8  call 08ffh
+                m.push(0x16bb)?;
+                m.pc = 0x08ff;
+                m.tick(17, 0x08ff)?;
+                m.call(0x16bb)?;
+                // 16bb  call 0ab1h
+                m.push(0x16be)?;
+                m.pc = 0x0ab1;
+                m.tick(17, 0x0ab1)?;
+                m.call(0x16be)?;
+                // 16be  call 18e7h
+                m.push(0x16c1)?;
+                m.pc = 0x18e7;
+                m.tick(17, 0x18e7)?;
+                m.call(0x16c1)?;
+                // 16c1  mov a,m
+                m.a = m.read(m.hl())?;
+                m.tick(7, 0x16c2)?;
+                // 16c2  ana a
+                let value = m.a;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(4, 0x16c3)?;
+                // 16c3  jz 16c9h
+                m.pc = if m.flags & ZERO != 0 { 0x16c9 } else { 0x16c6 };
+                m.tick(10, m.pc)?;
+            },
+            0x16c6 => {
+                // 16c6  jmp 02edh
+                m.pc = 0x02ed;
+                m.tick(10, 0x02ed)?;
+            },
+            0x16c9 => {
+                // 16c9  lxi h,2d18h
+                m.set_hl(0x2d18);
+                m.tick(10, 0x16cc)?;
+                // 16cc  lxi d,1aa6h
+                m.set_de(0x1aa6);
+                m.tick(10, 0x16cf)?;
+                // 16cf  mvi c,0ah
+                m.c = 0x0a;
+                m.tick(7, 0x16d1)?;
+                // 16d1  call 0a93h
+                m.push(0x16d4)?;
+                m.pc = 0x0a93;
+                m.tick(17, 0x0a93)?;
+                m.call(0x16d4)?;
+                // 16d4  call 0ab6h
+                m.push(0x16d7)?;
+                m.pc = 0x0ab6;
+                m.tick(17, 0x0ab6)?;
+                m.call(0x16d7)?;
+                // 16d7  call 09d6h
+                m.push(0x16da)?;
+                m.pc = 0x09d6;
+                m.tick(17, 0x09d6)?;
+                m.call(0x16da)?;
+                // 16da  xra a
+                let value = m.a;
+                m.a ^= value;
+                m.flags = SZP[m.a as usize] | if m.a > 0xf { AUX_CARRY } else { 0 };
+                m.tick(4, 0x16db)?;
+                // 16db  sta 20efh
+                m.write(0x20ef, m.a)?;
+                m.tick(13, 0x16de)?;
+                // 16de  out 05h
+                m.output(0x05, m.a)?;
+                m.tick(10, 0x16e0)?;
+                // 16e0  call 19d1h
+                m.push(0x16e3)?;
+                m.pc = 0x19d1;
+                m.tick(17, 0x19d1)?;
+                m.call(0x16e3)?;
+                // 16e3  jmp 0b89h
+                m.pc = 0x0b89;
+                m.tick(10, 0x0b89)?;
+            },
+            0x16e6 => {
+                // 16e6  lxi sp,2400h
+                m.sp = 0x2400;
+                m.tick(10, 0x16e9)?;
+                m.pc = 0x16e9;
+            },
+            // 16e9  ei
+            0x16e9 => m.interpret()?,
+            0x16eb => {
+                // 16eb  sta 2015h
+                m.write(0x2015, m.a)?;
+                m.tick(13, 0x16ee)?;
+                m.pc = 0x16ee;
+            },
+            0x16ee => {
+                // 16ee  call 14d8h
+                m.push(0x16f1)?;
+                m.pc = 0x14d8;
+                m.tick(17, 0x14d8)?;
+                m.call(0x16f1)?;
+                // 16f1  mvi b,04h
+                m.b = 0x04;
+                m.tick(7, 0x16f3)?;
+                // 16f3  call 18fah
+                m.push(0x16f6)?;
+                m.pc = 0x18fa;
+                m.tick(17, 0x18fa)?;
+                m.call(0x16f6)?;
+                // 16f6  call 0a59h
+                m.push(0x16f9)?;
+                m.pc = 0x0a59;
+                m.tick(17, 0x0a59)?;
+                m.call(0x16f9)?;
+                // 16f9  jnz 16eeh
+                m.pc = if m.flags & ZERO == 0 { 0x16ee } else { 0x16fc };
+                m.tick(10, m.pc)?;
+            },
+            0x16fc => {
+                // 16fc  call 19d7h
+                m.push(0x16ff)?;
+                m.pc = 0x19d7;
+                m.tick(17, 0x19d7)?;
+                m.call(0x16ff)?;
+                // 16ff  lxi h,2701h
+                m.set_hl(0x2701);
+                m.tick(10, 0x1702)?;
+                // 1702  call 19fah
+                m.push(0x1705)?;
+                m.pc = 0x19fa;
+                m.tick(17, 0x19fa)?;
+                m.call(0x1705)?;
+                // 1705  xra a
+                let value = m.a;
+                m.a ^= value;
+                m.flags = SZP[m.a as usize] | if m.a > 0xf { AUX_CARRY } else { 0 };
+                m.tick(4, 0x1706)?;
+                // 1706  call 1a8bh
+                m.push(0x1709)?;
+                m.pc = 0x1a8b;
+                m.tick(17, 0x1a8b)?;
+                m.call(0x1709)?;
+                // 1709  mvi b,0fbh
+                m.b = 0xfb;
+                m.tick(7, 0x170b)?;
+                // 170b  jmp 196bh
+                m.pc = 0x196b;
+                m.tick(10, 0x196b)?;
+            },
+            0x18df => {
+                // 18df  mvi a,08h
+                m.a = 0x08;
+                m.tick(7, 0x18e1)?;
+                // 18e1  sta 20cfh
+                m.write(0x20cf, m.a)?;
+                m.tick(13, 0x18e4)?;
+                // 18e4  jmp 0aeah
+                m.pc = 0x0aea;
+                m.tick(10, 0x0aea)?;
+            },
+            0x196b => {
+                // 196b  call 19dch
+                m.push(0x196e)?;
+                m.pc = 0x19dc;
+                m.tick(17, 0x19dc)?;
+                m.call(0x196e)?;
+                // 196e  jmp 1671h
+                m.pc = 0x1671;
+                m.tick(10, 0x1671)?;
+            },
+            0x1971 => {
+                // 1971  mvi a,01h
+                m.a = 0x01;
+                m.tick(7, 0x1973)?;
+                // 1973  sta 206dh
+                m.write(0x206d, m.a)?;
+                m.tick(13, 0x1976)?;
+                // 1976  jmp 16e6h
+                m.pc = 0x16e6;
+                m.tick(10, 0x16e6)?;
+            },
+            _ => return Ok(()),
+        }
+    }
+}
+
+fn sub_017a(m: &mut Machine) -> Result<(), Stop> {
+    loop {
+        match m.pc {
+            0x017a => {
+                // 017a  mvi d,00h
+                m.d = 0x00;
+                m.tick(7, 0x017c)?;
+                // 017c  mov a,l
+                m.a = m.l;
+                m.tick(5, 0x017d)?;
+                // 017d  lxi h,2009h
+                m.set_hl(0x2009);
+                m.tick(10, 0x0180)?;
+                // 0180  mov b,m
+                m.b = m.read(m.hl())?;
+                m.tick(7, 0x0181)?;
+                // 0181  inx h
+                m.set_hl(m.hl().wrapping_add(1));
+                m.tick(5, 0x0182)?;
+                // 0182  mov c,m
+                m.c = m.read(m.hl())?;
+                m.tick(7, 0x0183)?;
+                m.pc = 0x0183;
+            },
+            0x0183 => {
+                // 0183  cpi 0bh
+                let value: u8 = 0x0b;
+                let result = (m.a as u16).wrapping_sub(value as u16);
+                m.flags = SZP[result as u8 as usize] | (result > 0xff) as u8 | if (m.a & 0xf).wrapping_sub(value & 0xf) > 0xf { AUX_CARRY } else { 0 };
+                m.tick(7, 0x0185)?;
+                // 0185  jm 0194h
+                m.pc = if m.flags & SIGN != 0 { 0x0194 } else { 0x0188 };
+                m.tick(10, m.pc)?;
+            },
+            0x0188 => {
+                // 0188  sbi 0bh
+                let value: u8 = 0x0b;
+                let carry = m.flags & CARRY;
+                let result = (m.a as u16).wrapping_sub(value as u16).wrapping_sub(carry as u16);
+                m.flags = SZP[result as u8 as usize] | (result > 0xff) as u8 | if (m.a & 0xf).wrapping_sub(value & 0xf).wrapping_sub(carry) > 0xf { AUX_CARRY } else { 0 };
+                m.a = result as u8;
+                m.tick(7, 0x018a)?;
+                // 018a  mov e,a
+                m.e = m.a;
+                m.tick(5, 0x018b)?;
+                // 018b  mov a,b
+                m.a = m.b;
+                m.tick(5, 0x018c)?;
+                // 018c  adi 10h
+                let value: u8 = 0x10;
+                let result = m.a as u16 + value as u16;
+                m.flags = SZP[result as u8 as usize] | (result >> 8) as u8 | if (m.a & 0xf) + (value & 0xf) > 0xf { AUX_CARRY } else { 0 };
+                m.a = result as u8;
+                m.tick(7, 0x018e)?;
+                // 018e  mov b,a
+                m.b = m.a;
+                m.tick(5, 0x018f)?;
+                // 018f  mov a,e
+                m.a = m.e;
+                m.tick(5, 0x0190)?;
+                // 0190  inr d
+                let value = m.d;
+                let value = value.wrapping_add(1);
+                m.flags = SZP[value as usize] | (m.flags & CARRY) | if value & 0xf == 0 { AUX_CARRY } else { 0 };
+                m.d = value;
+                m.tick(5, 0x0191)?;
+                // 0191  jmp 0183h
+                m.pc = 0x0183;
+                m.tick(10, 0x0183)?;
+            },
+            0x0194 => {
+                // 0194  mov l,b
+                m.l = m.b;
+                m.tick(5, 0x0195)?;
+                m.pc = 0x0195;
+            },
+            0x0195 => {
+                // 0195  ana a
+                let value = m.a;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(4, 0x0196)?;
+                // 0196  rz
+                if m.flags & ZERO != 0 {
+                    let target = m.pop()?;
+                    m.pc = target;
+                    m.tick(11, target)?;
+                    return Ok(());
+                }
+                m.tick(5, 0x0197)?;
+                // 0197  mov e,a
+                m.e = m.a;
+                m.tick(5, 0x0198)?;
+                // 0198  mov a,c
+                m.a = m.c;
+                m.tick(5, 0x0199)?;
+                // 0199  adi 10h
+                let value: u8 = 0x10;
+                let result = m.a as u16 + value as u16;
+                m.flags = SZP[result as u8 as usize] | (result >> 8) as u8 | if (m.a & 0xf) + (value & 0xf) > 0xf { AUX_CARRY } else { 0 };
+                m.a = result as u8;
+                m.tick(7, 0x019b)?;
+                // 019b  mov c,a
+                m.c = m.a;
+                m.tick(5, 0x019c)?;
+                // 019c  mov a,e
+                m.a = m.e;
+                m.tick(5, 0x019d)?;
+                // 019d  dcr a
+                let value = m.a;
+                let value = value.wrapping_sub(1);
+                m.flags = SZP[value as usize] | (m.flags & CARRY) | if value & 0xf != 0xf { AUX_CARRY } else { 0 };
+                m.a = value;
+                m.tick(5, 0x019e)?;
+                // 019e  jmp 0195h
+                m.pc = 0x0195;
+                m.tick(10, 0x0195)?;
+            },
+            _ => return Ok(()),
+        }
+    }
+}
+
+fn sub_01a1(m: &mut Machine) -> Result<(), Stop> {
+    loop {
+        match m.pc {
+            0x01a1 => {
+                // 01a1  dcr d
+                let value = m.d;
+                let value = value.wrapping_sub(1);
+                m.flags = SZP[value as usize] | (m.flags & CARRY) | if value & 0xf != 0xf { AUX_CARRY } else { 0 };
+                m.d = value;
+                m.tick(5, 0x01a2)?;
+                // 01a2  jz 01cdh
+                m.pc = if m.flags & ZERO != 0 { 0x01cd } else { 0x01a5 };
+                m.tick(10, m.pc)?;
+            },
+            0x01a5 => {
+                // 01a5  lxi h,2006h
+                m.set_hl(0x2006);
+                m.tick(10, 0x01a8)?;
+                // 01a8  mvi m,00h
+                m.write(m.hl(), 0x00)?;
+                m.tick(10, 0x01aa)?;
+                // 01aa  inx h
+                m.set_hl(m.hl().wrapping_add(1));
+                m.tick(5, 0x01ab)?;
+                // 01ab  mov c,m
+                m.c = m.read(m.hl())?;
+                m.tick(7, 0x01ac)?;
+                // 01ac  mvi m,00h
+                m.write(m.hl(), 0x00)?;
+                m.tick(10, 0x01ae)?;
+                // 01ae  call 01d9h
+                m.push(0x01b1)?;
+                m.pc = 0x01d9;
+                m.tick(17, 0x01d9)?;
+                m.call(0x01b1)?;
+                // 01b1  lxi h,2005h
+                m.set_hl(0x2005);
+                m.tick(10, 0x01b4)?;
+                // 01b4  mov a,m
+                m.a = m.read(m.hl())?;
+                m.tick(7, 0x01b5)?;
+                // 01b5  inr a
+                let value = m.a;
+                let value = value.wrapping_add(1);
+                m.flags = SZP[value as usize] | (m.flags & CARRY) | if value & 0xf == 0 { AUX_CARRY } else { 0 };
+                m.a = value;
+                m.tick(5, 0x01b6)?;
+                // 01b6  ani 01h
+                let value: u8 = 0x01;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(7, 0x01b8)?;
+                // 01b8  mov m,a
+                m.write(m.hl(), m.a)?;
+                m.tick(7, 0x01b9)?;
+                // 01b9  xra a
+                let value = m.a;
+                m.a ^= value;
+                m.flags = SZP[m.a as usize] | if m.a > 0xf { AUX_CARRY } else { 0 };
+                m.tick(4, 0x01ba)?;
+                // 01ba  lxi h,2067h
+                m.set_hl(0x2067);
+                m.tick(10, 0x01bd)?;
+                // 01bd  mov h,m
+                m.h = m.read(m.hl())?;
+                m.tick(7, 0x01be)?;
+                // 01be  ret
+                let target = m.pop()?;
+                m.pc = target;
+                m.tick(10, target)?;
+                return Ok(());
+            },
+            0x01cd => {
+                // 01cd  pop h
+                let value = m.pop()?;
+                m.set_hl(value);
+                m.tick(10, 0x01ce)?;
+                // 01ce  ret
+                let target = m.pop()?;
+                m.pc = target;
+                m.tick(10, target)?;
+                return Ok(());
+            },
+            _ => return Ok(()),
+        }
+    }
+}
+
+fn sub_01c0(m: &mut Machine) -> Result<(), Stop> {
+    loop {
+        match m.pc {
+            0x01c0 => {
+                // 01c0  lxi h,2100h
+                m.set_hl(0x2100);
+                m.tick(10, 0x01c3)?;
+                // 01c3  mvi b,37h
+                m.b = 0x37;
+                m.tick(7, 0x01c5)?;
+                m.pc = 0x01c5;
+            },
+            0x01c5 => {
+                // 01c5  mvi m,01h
+                m.write(m.hl(), 0x01)?;
+                m.tick(10, 0x01c7)?;
+                // 01c7  inx h
+                m.set_hl(m.hl().wrapping_add(1));
+                m.tick(5, 0x01c8)?;
+                // 01c8  dcr b
+                let value = m.b;
+                let value = value.wrapping_sub(1);
+                m.flags = SZP[value as usize] | (m.flags & CARRY) | if value & 0xf != 0xf { AUX_CARRY } else { 0 };
+                m.b = value;
+                m.tick(5, 0x01c9)?;
+                // 01c9  jnz 01c5h
+                m.pc = if m.flags & ZERO == 0 { 0x01c5 } else { 0x01cc };
+                m.tick(10, m.pc)?;
+            },
+            0x01cc => {
+                // 01cc  ret
+                let target = m.pop()?;
+                m.pc = target;
+                m.tick(10, target)?;
+                return Ok(());
+            },
+            _ => return Ok(()),
+        }
+    }
+}
+
+fn sub_01cf(m: &mut Machine) -> Result<(), Stop> {
+    loop {
+        match m.pc {
+            0x01cf => {
+                // 01cf  mvi a,01h
+                m.a = 0x01;
+                m.tick(7, 0x01d1)?;
+                // 01d1  mvi b,0e0h
+                m.b = 0xe0;
+                m.tick(7, 0x01d3)?;
+                // 01d3  lxi h,2402h
+                m.set_hl(0x2402);
+                m.tick(10, 0x01d6)?;
+                // 01d6  jmp 14cch
+                m.pc = 0x14cc;
+                m.tick(10, 0x14cc)?;
+            },
+            0x14cc => {
+                // 14cc  push b
+                m.push(m.bc())?;
+                m.tick(11, 0x14cd)?;
+                // 14cd  mov m,a
+                m.write(m.hl(), m.a)?;
+                m.tick(7, 0x14ce)?;
+                // 14ce  lxi b,0020h
+                m.set_bc(0x0020);
+                m.tick(10, 0x14d1)?;
+                // 14d1  dad b
+                let result = m.hl() as u32 + m.bc() as u32;
+                m.flags = (m.flags & !CARRY) | (result >> 16) as u8;
+                m.set_hl(result as u16);
+                m.tick(10, 0x14d2)?;
+                // 14d2  pop b
+                let value = m.pop()?;
+                m.set_bc(value);
+                m.tick(10, 0x14d3)?;
+                // 14d3  dcr b
+                let value = m.b;
+                let value = value.wrapping_sub(1);
+                m.flags = SZP[value as usize] | (m.flags & CARRY) | if value & 0xf != 0xf { AUX_CARRY } else { 0 };
+                m.b = value;
+                m.tick(5, 0x14d4)?;
+                // 14d4  jnz 14cch
+                m.pc = if m.flags & ZERO == 0 { 0x14cc } else { 0x14d7 };
+                m.tick(10, m.pc)?;
+            },
+            0x14d7 => {
+                // 14d7  ret
+                let target = m.pop()?;
+                m.pc = target;
+                m.tick(10, target)?;
+                return Ok(());
+            },
+            _ => return Ok(()),
+        }
+    }
+}
+
+fn sub_01d9(m: &mut Machine) -> Result<(), Stop> {
+    // 01d9  inx h
+    m.set_hl(m.hl().wrapping_add(1));
+    m.tick(5, 0x01da)?;
+    // 01da  mov b,m
+    m.b = m.read(m.hl())?;
+    m.tick(7, 0x01db)?;
+    // 01db  inx h
+    m.set_hl(m.hl().wrapping_add(1));
+    m.tick(5, 0x01dc)?;
+    // 01dc  mov a,c
+    m.a = m.c;
+    m.tick(5, 0x01dd)?;
+    // 01dd  add m
+    let value = m.read(m.hl())?;
+    let result = m.a as u16 + value as u16;
+    m.flags = SZP[result as u8 as usize] | (result >> 8) as u8 | if (m.a & 0xf) + (value & 0xf) > 0xf { AUX_CARRY } else { 0 };
+    m.a = result as u8;
+    m.tick(7, 0x01de)?;
+    // 01de  mov m,a
+    m.write(m.hl(), m.a)?;
+    m.tick(7, 0x01df)?;
+    // 01df  inx h
+    m.set_hl(m.hl().wrapping_add(1));
+    m.tick(5, 0x01e0)?;
+    // 01e0  mov a,b
+    m.a = m.b;
+    m.tick(5, 0x01e1)?;
+    // 01e1  add m
+    let value = m.read(m.hl())?;
+    let result = m.a as u16 + value as u16;
+    m.flags = SZP[result as u8 as usize] | (result >> 8) as u8 | if (m.a & 0xf) + (value & 0xf) > 0xf { AUX_CARRY } else { 0 };
+    m.a = result as u8;
+    m.tick(7, 0x01e2)?;
+    // 01e2  mov m,a
+    m.write(m.hl(), m.a)?;
+    m.tick(7, 0x01e3)?;
+    // 01e3  ret
+    let target = m.pop()?;
+    m.pc = target;
+    m.tick(10, target)?;
+    Ok(())
+}
+
+fn sub_01e4(m: &mut Machine) -> Result<(), Stop> {
+    loop {
+        match m.pc {
+            0x01e4 => {
+                // 01e4  mvi b,0c0h
+                m.b = 0xc0;
+                m.tick(7, 0x01e6)?;
+                // 01e6  lxi d,1b00h
+                m.set_de(0x1b00);
+                m.tick(10, 0x01e9)?;
+                // 01e9  lxi h,2000h
+                m.set_hl(0x2000);
+                m.tick(10, 0x01ec)?;
+                // 01ec  jmp 1a32h
+                m.pc = 0x1a32;
+                m.tick(10, 0x1a32)?;
+            },
+            0x1a32 => {
+                // 1a32  ldax d
+                m.a = m.read(m.de())?;
+                m.tick(7, 0x1a33)?;
+                // 1a33  mov m,a
+                m.write(m.hl(), m.a)?;
+                m.tick(7, 0x1a34)?;
+                // 1a34  inx h
+                m.set_hl(m.hl().wrapping_add(1));
+                m.tick(5, 0x1a35)?;
+                // 1a35  inx d
+                m.set_de(m.de().wrapping_add(1));
+                m.tick(5, 0x1a36)?;
+                // 1a36  dcr b
+                let value = m.b;
+                let value = value.wrapping_sub(1);
+                m.flags = SZP[value as usize] | (m.flags & CARRY) | if value & 0xf != 0xf { AUX_CARRY } else { 0 };
+                m.b = value;
+                m.tick(5, 0x1a37)?;
+                // 1a37  jnz 1a32h
+                m.pc = if m.flags & ZERO == 0 { 0x1a32 } else { 0x1a3a };
+                m.tick(10, m.pc)?;
+            },
+            0x1a3a => {
+                // 1a3a  ret
+                let target = m.pop()?;
+                m.pc = target;
+                m.tick(10, target)?;
+                return Ok(());
+            },
+            _ => return Ok(()),
+        }
+    }
+}
+
+fn sub_01e6(m: &mut Machine) -> Result<(), Stop> {
+    loop {
+        match m.pc {
+            0x01e6 => {
+                // 01e6  lxi d,1b00h
+                m.set_de(0x1b00);
+                m.tick(10, 0x01e9)?;
+                // 01e9  lxi h,2000h
+                m.set_hl(0x2000);
+                m.tick(10, 0x01ec)?;
+                // 01ec  jmp 1a32h
+                m.pc = 0x1a32;
+                m.tick(10, 0x1a32)?;
+            },
+            0x1a32 => {
+                // 1a32  ldax d
+                m.a = m.read(m.de())?;
+                m.tick(7, 0x1a33)?;
+                // 1a33  mov m,a
+                m.write(m.hl(), m.a)?;
+                m.tick(7, 0x1a34)?;
+                // 1a34  inx h
+                m.set_hl(m.hl().wrapping_add(1));
+                m.tick(5, 0x1a35)?;
+                // 1a35  inx d
+                m.set_de(m.de().wrapping_add(1));
+                m.tick(5, 0x1a36)?;
+                // 1a36  dcr b
+                let value = m.b;
+                let value = value.wrapping_sub(1);
+                m.flags = SZP[value as usize] | (m.flags & CARRY) | if value & 0xf != 0xf { AUX_CARRY } else { 0 };
+                m.b = value;
+                m.tick(5, 0x1a37)?;
+                // 1a37  jnz 1a32h
+                m.pc = if m.flags & ZERO == 0 { 0x1a32 } else { 0x1a3a };
+                m.tick(10, m.pc)?;
+            },
+            0x1a3a => {
+                // 1a3a  ret
+                let target = m.pop()?;
+                m.pc = target;
+                m.tick(10, target)?;
+                return Ok(());
+            },
+            _ => return Ok(()),
+        }
+    }
+}
+
+fn sub_01ef(m: &mut Machine) -> Result<(), Stop> {
+    loop {
+        match m.pc {
+            0x01ef => {
+                // 01ef  lxi h,2142h
+                m.set_hl(0x2142);
+                m.tick(10, 0x01f2)?;
+                // 01f2  jmp 01f8h
+                m.pc = 0x01f8;
+                m.tick(10, 0x01f8)?;
+            },
+            0x01f8 => {
+                // 01f8  mvi c,04h
+                m.c = 0x04;
+                m.tick(7, 0x01fa)?;
+                // 01fa  lxi d,1d20h
+                m.set_de(0x1d20);
+                m.tick(10, 0x01fd)?;
+                m.pc = 0x01fd;
+            },
+            0x01fd => {
+                // 01fd  push d
+                m.push(m.de())?;
+                m.tick(11, 0x01fe)?;
+                // 01fe  mvi b,2ch
+                m.b = 0x2c;
+                m.tick(7, 0x0200)?;
+                // 0200  call 1a32h
+                m.push(0x0203)?;
+                m.pc = 0x1a32;
+                m.tick(17, 0x1a32)?;
+                m.call(0x0203)?;
+                // 0203  pop d
+                let value = m.pop()?;
+                m.set_de(value);
+                m.tick(10, 0x0204)?;
+                // 0204  dcr c
+                let value = m.c;
+                let value = value.wrapping_sub(1);
+                m.flags = SZP[value as usize] | (m.flags & CARRY) | if value & 0xf != 0xf { AUX_CARRY } else { 0 };
+                m.c = value;
+                m.tick(5, 0x0205)?;
+                // 0205  jnz 01fdh
+                m.pc = if m.flags & ZERO == 0 { 0x01fd } else { 0x0208 };
+                m.tick(10, m.pc)?;
+            },
+            0x0208 => {
+                // 0208  ret
+                let target = m.pop()?;
+                m.pc = target;
+                m.tick(10, target)?;
+                return Ok(());
+            },
+            _ => return Ok(()),
+        }
+    }
+}
+
+fn sub_01f5(m: &mut Machine) -> Result<(), Stop> {
+    loop {
+        match m.pc {
+            0x01f5 => {
+                // 01f5  lxi h,2242h
+                m.set_hl(0x2242);
+                m.tick(10, 0x01f8)?;
+                // 01f8  mvi c,04h
+                m.c = 0x04;
+                m.tick(7, 0x01fa)?;
+                // 01fa  lxi d,1d20h
+                m.set_de(0x1d20);
+                m.tick(10, 0x01fd)?;
+                m.pc = 0x01fd;
+            },
+            0x01fd => {
+                // 01fd  push d
+                m.push(m.de())?;
+                m.tick(11, 0x01fe)?;
+                // 01fe  mvi b,2ch
+                m.b = 0x2c;
+                m.tick(7, 0x0200)?;
+                // 0200  call 1a32h
+                m.push(0x0203)?;
+                m.pc = 0x1a32;
+                m.tick(17, 0x1a32)?;
+                m.call(0x0203)?;
+                // 0203  pop d
+                let value = m.pop()?;
+                m.set_de(value);
+                m.tick(10, 0x0204)?;
+                // 0204  dcr c
+                let value = m.c;
+                let value = value.wrapping_sub(1);
+                m.flags = SZP[value as usize] | (m.flags & CARRY) | if value & 0xf != 0xf { AUX_CARRY } else { 0 };
+                m.c = value;
+                m.tick(5, 0x0205)?;
+                // 0205  jnz 01fdh
+                m.pc = if m.flags & ZERO == 0 { 0x01fd } else { 0x0208 };
+                m.tick(10, m.pc)?;
+            },
+            0x0208 => {
+                // 0208  ret
+                let target = m.pop()?;
+                m.pc = target;
+                m.tick(10, target)?;
+                return Ok(());
+            },
+            _ => return Ok(()),
+        }
+    }
+}
+
+fn sub_0209(m: &mut Machine) -> Result<(), Stop> {
+    loop {
+        match m.pc {
+            0x0209 => {
+                // 0209  mvi a,01h
+                m.a = 0x01;
+                m.tick(7, 0x020b)?;
+                // 020b  jmp 021bh
+                m.pc = 0x021b;
+                m.tick(10, 0x021b)?;
+            },
+            0x021b => {
+                // 021b  lxi d,2142h
+                m.set_de(0x2142);
+                m.tick(10, 0x021e)?;
+                // 021e  sta 2081h
+                m.write(0x2081, m.a)?;
+                m.tick(13, 0x0221)?;
+                // 0221  lxi b,1602h
+                m.set_bc(0x1602);
+                m.tick(10, 0x0224)?;
+                // 0224  lxi h,2806h
+                m.set_hl(0x2806);
+                m.tick(10, 0x0227)?;
+                // 0227  mvi a,04h
+                m.a = 0x04;
+                m.tick(7, 0x0229)?;
+                m.pc = 0x0229;
+            },
+            0x0229 => {
+                // 0229  push psw
+                m.push((m.a as u16) << 8 | m.flags as u16)?;
+                m.tick(11, 0x022a)?;
+                // 022a  push b
+                m.push(m.bc())?;
+                m.tick(11, 0x022b)?;
+                // 022b  lda 2081h
+                m.a = m.read(0x2081)?;
+                m.tick(13, 0x022e)?;
+                // 022e  ana a
+                let value = m.a;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(4, 0x022f)?;
+                // 022f  jnz 0242h
+                m.pc = if m.flags & ZERO == 0 { 0x0242 } else { 0x0232 };
+                m.tick(10, m.pc)?;
+            },
+            0x0232 => {
+                // 0232  call 1a69h
+                m.push(0x0235)?;
+                m.pc = 0x1a69;
+                m.tick(17, 0x1a69)?;
+                m.call(0x0235)?;
+                m.pc = 0x0235;
+            },
+            0x0235 => {
+                // 0235  pop b
+                let value = m.pop()?;
+                m.set_bc(value);
+                m.tick(10, 0x0236)?;
+                // 0236  pop psw
+                let value = m.pop()?;
+                m.a = (value >> 8) as u8;
+                m.flags = value as u8 & (SIGN | ZERO | AUX_CARRY | PARITY | CARRY);
+                m.tick(10, 0x0237)?;
+                // 0237  dcr a
+                let value = m.a;
+                let value = value.wrapping_sub(1);
+                m.flags = SZP[value as usize] | (m.flags & CARRY) | if value & 0xf != 0xf { AUX_CARRY } else { 0 };
+                m.a = value;
+                m.tick(5, 0x0238)?;
+                // 0238  rz
+                if m.flags & ZERO != 0 {
+                    let target = m.pop()?;
+                    m.pc = target;
+                    m.tick(11, target)?;
+                    return Ok(());
+                }
+                m.tick(5, 0x0239)?;
+                // 0239  push d
+                m.push(m.de())?;
+                m.tick(11, 0x023a)?;
+                // 023a  lxi d,02e0h
+                m.set_de(0x02e0);
+                m.tick(10, 0x023d)?;
+                // 023d  dad d
+                let result = m.hl() as u32 + m.de() as u32;
+                m.flags = (m.flags & !CARRY) | (result >> 16) as u8;
+                m.set_hl(result as u16);
+                m.tick(10, 0x023e)?;
+                // 023e  pop d
+                let value = m.pop()?;
+                m.set_de(value);
+                m.tick(10, 0x023f)?;
+                // 023f  jmp 0229h
+                m.pc = 0x0229;
+                m.tick(10, 0x0229)?;
+            },
+            0x0242 => {
+                // 0242  call 147ch
+                m.push(0x0245)?;
+                m.pc = 0x147c;
+                m.tick(17, 0x147c)?;
+                m.call(0x0245)?;
+                // 0245  jmp 0235h
+                m.pc = 0x0235;
+                m.tick(10, 0x0235)?;
+            },
+            _ => return Ok(()),
+        }
+    }
+}
+
+fn sub_020e(m: &mut Machine) -> Result<(), Stop> {
+    loop {
+        match m.pc {
+            0x020e => {
+                // 020e  mvi a,01h
+                m.a = 0x01;
+                m.tick(7, 0x0210)?;
+                // 0210  jmp 0214h
+                m.pc = 0x0214;
+                m.tick(10, 0x0214)?;
+            },
+            0x0214 => {
+                // 0214  lxi d,2242h
+                m.set_de(0x2242);
+                m.tick(10, 0x0217)?;
+                // 0217  jmp 021eh
+                m.pc = 0x021e;
+                m.tick(10, 0x021e)?;
+            },
+            0x021e => {
+                // 021e  sta 2081h
+                m.write(0x2081, m.a)?;
+                m.tick(13, 0x0221)?;
+                // 0221  lxi b,1602h
+                m.set_bc(0x1602);
+                m.tick(10, 0x0224)?;
+                // 0224  lxi h,2806h
+                m.set_hl(0x2806);
+                m.tick(10, 0x0227)?;
+                // 0227  mvi a,04h
+                m.a = 0x04;
+                m.tick(7, 0x0229)?;
+                m.pc = 0x0229;
+            },
+            0x0229 => {
+                // 0229  push psw
+                m.push((m.a as u16) << 8 | m.flags as u16)?;
+                m.tick(11, 0x022a)?;
+                // 022a  push b
+                m.push(m.bc())?;
+                m.tick(11, 0x022b)?;
+                // 022b  lda 2081h
+                m.a = m.read(0x2081)?;
+                m.tick(13, 0x022e)?;
+                // 022e  ana a
+                let value = m.a;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(4, 0x022f)?;
+                // 022f  jnz 0242h
+                m.pc = if m.flags & ZERO == 0 { 0x0242 } else { 0x0232 };
+                m.tick(10, m.pc)?;
+            },
+            0x0232 => {
+                // 0232  call 1a69h
+                m.push(0x0235)?;
+                m.pc = 0x1a69;
+                m.tick(17, 0x1a69)?;
+                m.call(0x0235)?;
+                m.pc = 0x0235;
+            },
+            0x0235 => {
+                // 0235  pop b
+                let value = m.pop()?;
+                m.set_bc(value);
+                m.tick(10, 0x0236)?;
+                // 0236  pop psw
+                let value = m.pop()?;
+                m.a = (value >> 8) as u8;
+                m.flags = value as u8 & (SIGN | ZERO | AUX_CARRY | PARITY | CARRY);
+                m.tick(10, 0x0237)?;
+                // 0237  dcr a
+                let value = m.a;
+                let value = value.wrapping_sub(1);
+                m.flags = SZP[value as usize] | (m.flags & CARRY) | if value & 0xf != 0xf { AUX_CARRY } else { 0 };
+                m.a = value;
+                m.tick(5, 0x0238)?;
+                // 0238  rz
+                if m.flags & ZERO != 0 {
+                    let target = m.pop()?;
+                    m.pc = target;
+                    m.tick(11, target)?;
+                    return Ok(());
+                }
+                m.tick(5, 0x0239)?;
+                // 0239  push d
+                m.push(m.de())?;
+                m.tick(11, 0x023a)?;
+                // 023a  lxi d,02e0h
+                m.set_de(0x02e0);
+                m.tick(10, 0x023d)?;
+                // 023d  dad d
+                let result = m.hl() as u32 + m.de() as u32;
+                m.flags = (m.flags & !CARRY) | (result >> 16) as u8;
+                m.set_hl(result as u16);
+                m.tick(10, 0x023e)?;
+                // 023e  pop d
+                let value = m.pop()?;
+                m.set_de(value);
+                m.tick(10, 0x023f)?;
+                // 023f  jmp 0229h
+                m.pc = 0x0229;
+                m.tick(10, 0x0229)?;
+            },
+            0x0242 => {
+                // 0242  call 147ch
+                m.push(0x0245)?;
+                m.pc = 0x147c;
+                m.tick(17, 0x147c)?;
+                m.call(0x0245)?;
+                // 0245  jmp 0235h
+                m.pc = 0x0235;
+                m.tick(10, 0x0235)?;
+            },
+            _ => return Ok(()),
+        }
+    }
+}
+
+fn sub_0213(m: &mut Machine) -> Result<(), Stop> {
+    loop {
+        match m.pc {
+            0x0213 => {
+                // 0213  xra a
+                let value = m.a;
+                m.a ^= value;
+                m.flags = SZP[m.a as usize] | if m.a > 0xf { AUX_CARRY } else { 0 };
+                m.tick(4, 0x0214)?;
+                // 0214  lxi d,2242h
+                m.set_de(0x2242);
+                m.tick(10, 0x0217)?;
+                // 0217  jmp 021eh
+                m.pc = 0x021e;
+                m.tick(10, 0x021e)?;
+            },
+            0x021e => {
+                // 021e  sta 2081h
+                m.write(0x2081, m.a)?;
+                m.tick(13, 0x0221)?;
+                // 0221  lxi b,1602h
+                m.set_bc(0x1602);
+                m.tick(10, 0x0224)?;
+                // 0224  lxi h,2806h
+                m.set_hl(0x2806);
+                m.tick(10, 0x0227)?;
+                // 0227  mvi a,04h
+                m.a = 0x04;
+                m.tick(7, 0x0229)?;
+                m.pc = 0x0229;
+            },
+            0x0229 => {
+                // 0229  push psw
+                m.push((m.a as u16) << 8 | m.flags as u16)?;
+                m.tick(11, 0x022a)?;
+                // 022a  push b
+                m.push(m.bc())?;
+                m.tick(11, 0x022b)?;
+                // 022b  lda 2081h
+                m.a = m.read(0x2081)?;
+                m.tick(13, 0x022e)?;
+                // 022e  ana a
+                let value = m.a;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(4, 0x022f)?;
+                // 022f  jnz 0242h
+                m.pc = if m.flags & ZERO == 0 { 0x0242 } else { 0x0232 };
+                m.tick(10, m.pc)?;
+            },
+            0x0232 => {
+                // 0232  call 1a69h
+                m.push(0x0235)?;
+                m.pc = 0x1a69;
+                m.tick(17, 0x1a69)?;
+                m.call(0x0235)?;
+                m.pc = 0x0235;
+            },
+            0x0235 => {
+                // 0235  pop b
+                let value = m.pop()?;
+                m.set_bc(value);
+                m.tick(10, 0x0236)?;
+                // 0236  pop psw
+                let value = m.pop()?;
+                m.a = (value >> 8) as u8;
+                m.flags = value as u8 & (SIGN | ZERO | AUX_CARRY | PARITY | CARRY);
+                m.tick(10, 0x0237)?;
+                // 0237  dcr a
+                let value = m.a;
+                let value = value.wrapping_sub(1);
+                m.flags = SZP[value as usize] | (m.flags & CARRY) | if value & 0xf != 0xf { AUX_CARRY } else { 0 };
+                m.a = value;
+                m.tick(5, 0x0238)?;
+                // 0238  rz
+                if m.flags & ZERO != 0 {
+                    let target = m.pop()?;
+                    m.pc = target;
+                    m.tick(11, target)?;
+                    return Ok(());
+                }
+                m.tick(5, 0x0239)?;
+                // 0239  push d
+                m.push(m.de())?;
+                m.tick(11, 0x023a)?;
+                // 023a  lxi d,02e0h
+                m.set_de(0x02e0);
+                m.tick(10, 0x023d)?;
+                // 023d  dad d
+                let result = m.hl() as u32 + m.de() as u32;
+                m.flags = (m.flags & !CARRY) | (result >> 16) as u8;
+                m.set_hl(result as u16);
+                m.tick(10, 0x023e)?;
+                // 023e  pop d
+                let value = m.pop()?;
+                m.set_de(value);
+                m.tick(10, 0x023f)?;
+                // 023f  jmp 0229h
+                m.pc = 0x0229;
+                m.tick(10, 0x0229)?;
+            },
+            0x0242 => {
+                // 0242  call 147ch
+                m.push(0x0245)?;
+                m.pc = 0x147c;
+                m.tick(17, 0x147c)?;
+                m.call(0x0245)?;
+                // 0245  jmp 0235h
+                m.pc = 0x0235;
+                m.tick(10, 0x0235)?;
+            },
+            _ => return Ok(()),
+        }
+    }
+}
+
+fn sub_021a(m: &mut Machine) -> Result<(), Stop> {
+    loop {
+        match m.pc {
+            0x021a => {
+                // 021a  xra a
+                let value = m.a;
+                m.a ^= value;
+                m.flags = SZP[m.a as usize] | if m.a > 0xf { AUX_CARRY } else { 0 };
+                m.tick(4, 0x021b)?;
+                // 021b  lxi d,2142h
+                m.set_de(0x2142);
+                m.tick(10, 0x021e)?;
+                // 021e  sta 2081h
+                m.write(0x2081, m.a)?;
+                m.tick(13, 0x0221)?;
+                // 0221  lxi b,1602h
+                m.set_bc(0x1602);
+                m.tick(10, 0x0224)?;
+                // 0224  lxi h,2806h
+                m.set_hl(0x2806);
+                m.tick(10, 0x0227)?;
+                // 0227  mvi a,04h
+                m.a = 0x04;
+                m.tick(7, 0x0229)?;
+                m.pc = 0x0229;
+            },
+            0x0229 => {
+                // 0229  push psw
+                m.push((m.a as u16) << 8 | m.flags as u16)?;
+                m.tick(11, 0x022a)?;
+                // 022a  push b
+                m.push(m.bc())?;
+                m.tick(11, 0x022b)?;
+                // 022b  lda 2081h
+                m.a = m.read(0x2081)?;
+                m.tick(13, 0x022e)?;
+                // 022e  ana a
+                let value = m.a;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(4, 0x022f)?;
+                // 022f  jnz 0242h
+                m.pc = if m.flags & ZERO == 0 { 0x0242 } else { 0x0232 };
+                m.tick(10, m.pc)?;
+            },
+            0x0232 => {
+                // 0232  call 1a69h
+                m.push(0x0235)?;
+                m.pc = 0x1a69;
+                m.tick(17, 0x1a69)?;
+                m.call(0x0235)?;
+                m.pc = 0x0235;
+            },
+            0x0235 => {
+                // 0235  pop b
+                let value = m.pop()?;
+                m.set_bc(value);
+                m.tick(10, 0x0236)?;
+                // 0236  pop psw
+                let value = m.pop()?;
+                m.a = (value >> 8) as u8;
+                m.flags = value as u8 & (SIGN | ZERO | AUX_CARRY | PARITY | CARRY);
+                m.tick(10, 0x0237)?;
+                // 0237  dcr a
+                let value = m.a;
+                let value = value.wrapping_sub(1);
+                m.flags = SZP[value as usize] | (m.flags & CARRY) | if value & 0xf != 0xf { AUX_CARRY } else { 0 };
+                m.a = value;
+                m.tick(5, 0x0238)?;
+                // 0238  rz
+                if m.flags & ZERO != 0 {
+                    let target = m.pop()?;
+                    m.pc = target;
+                    m.tick(11, target)?;
+                    return Ok(());
+                }
+                m.tick(5, 0x0239)?;
+                // 0239  push d
+                m.push(m.de())?;
+                m.tick(11, 0x023a)?;
+                // 023a  lxi d,02e0h
+                m.set_de(0x02e0);
+                m.tick(10, 0x023d)?;
+                // 023d  dad d
+                let result = m.hl() as u32 + m.de() as u32;
+                m.flags = (m.flags & !CARRY) | (result >> 16) as u8;
+                m.set_hl(result as u16);
+                m.tick(10, 0x023e)?;
+                // 023e  pop d
+                let value = m.pop()?;
+                m.set_de(value);
+                m.tick(10, 0x023f)?;
+                // 023f  jmp 0229h
+                m.pc = 0x0229;
+                m.tick(10, 0x0229)?;
+            },
+            0x0242 => {
+                // 0242  call 147ch
+                m.push(0x0245)?;
+                m.pc = 0x147c;
+                m.tick(17, 0x147c)?;
+                m.call(0x0245)?;
+                // 0245  jmp 0235h
+                m.pc = 0x0235;
+                m.tick(10, 0x0235)?;
+            },
+            _ => return Ok(()),
+        }
+    }
+}
+
+fn sub_0248(m: &mut Machine) -> Result<(), Stop> {
+    loop {
+        match m.pc {
+            0x0248 => {
+                // 0248  lxi h,2010h
+                m.set_hl(0x2010);
+                m.tick(10, 0x024b)?;
+                m.pc = 0x024b;
+            },
+            0x024b => {
+                // 024b  mov a,m
+                m.a = m.read(m.hl())?;
+                m.tick(7, 0x024c)?;
+                // 024c  cpi 0ffh
+                let value: u8 = 0xff;
+                let result = (m.a as u16).wrapping_sub(value as u16);
+                m.flags = SZP[result as u8 as usize] | (result > 0xff) as u8 | if (m.a & 0xf).wrapping_sub(value & 0xf) > 0xf { AUX_CARRY } else { 0 };
+                m.tick(7, 0x024e)?;
+                // 024e  rz
+                if m.flags & ZERO != 0 {
+                    let target = m.pop()?;
+                    m.pc = target;
+                    m.tick(11, target)?;
+                    return Ok(());
+                }
+                m.tick(5, 0x024f)?;
+                // 024f  cpi 0feh
+                let value: u8 = 0xfe;
+                let result = (m.a as u16).wrapping_sub(value as u16);
+                m.flags = SZP[result as u8 as usize] | (result > 0xff) as u8 | if (m.a & 0xf).wrapping_sub(value & 0xf) > 0xf { AUX_CARRY } else { 0 };
+                m.tick(7, 0x0251)?;
+                // 0251  jz 0281h
+                m.pc = if m.flags & ZERO != 0 { 0x0281 } else { 0x0254 };
+                m.tick(10, m.pc)?;
+            },
+            0x0254 => {
+                // 0254  inx h
+                m.set_hl(m.hl().wrapping_add(1));
+                m.tick(5, 0x0255)?;
+                // 0255  mov b,m
+                m.b = m.read(m.hl())?;
+                m.tick(7, 0x0256)?;
+                // 0256  mov c,a
+                m.c = m.a;
+                m.tick(5, 0x0257)?;
+                // 0257  ora b
+                let value = m.b;
+                m.a |= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(4, 0x0258)?;
+                // 0258  mov a,c
+                m.a = m.c;
+                m.tick(5, 0x0259)?;
+                // 0259  jnz 0277h
+                m.pc = if m.flags & ZERO == 0 { 0x0277 } else { 0x025c };
+                m.tick(10, m.pc)?;
+            },
+            0x025c => {
+                // 025c  inx h
+                m.set_hl(m.hl().wrapping_add(1));
+                m.tick(5, 0x025d)?;
+                // 025d  mov a,m
+                m.a = m.read(m.hl())?;
+                m.tick(7, 0x025e)?;
+                // 025e  ana a
+                let value = m.a;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(4, 0x025f)?;
+                // 025f  jnz 0288h
+                m.pc = if m.flags & ZERO == 0 { 0x0288 } else { 0x0262 };
+                m.tick(10, m.pc)?;
+            },
+            0x0262 => {
+                // 0262  inx h
+                m.set_hl(m.hl().wrapping_add(1));
+                m.tick(5, 0x0263)?;
+                // 0263  mov e,m
+                m.e = m.read(m.hl())?;
+                m.tick(7, 0x0264)?;
+                // 0264  inx h
+                m.set_hl(m.hl().wrapping_add(1));
+                m.tick(5, 0x0265)?;
+                // 0265  mov d,m
+                m.d = m.read(m.hl())?;
+                m.tick(7, 0x0266)?;
+                // 0266  push h
+                m.push(m.hl())?;
+                m.tick(11, 0x0267)?;
+                // 0267  xchg
+                std::mem::swap(&mut m.d, &mut m.h);
+                std::mem::swap(&mut m.e, &mut m.l);
+                m.tick(4, 0x0268)?;
+                // 0268  push h
+                m.push(m.hl())?;
+                m.tick(11, 0x0269)?;
+                // 0269  lxi h,026fh
+                m.set_hl(0x026f);
+                m.tick(10, 0x026c)?;
+                // 026c  xthl
+                let value = m.pop()?;
+                let hl = m.hl();
+                m.set_hl(value);
+                m.push(hl)?;
+                m.tick(18, 0x026d)?;
+                // 026d  push d
+                m.push(m.de())?;
+                m.tick(11, 0x026e)?;
+                // 026e  pchl
+                m.pc = m.hl();
+                m.tick(5, m.pc)?;
+            },
+            0x0277 => {
+                // 0277  dcr b
+                let value = m.b;
+                let value = value.wrapping_sub(1);
+                m.flags = SZP[value as usize] | (m.flags & CARRY) | if value & 0xf != 0xf { AUX_CARRY } else { 0 };
+                m.b = value;
+                m.tick(5, 0x0278)?;
+                // 0278  inr b
+                let value = m.b;
+                let value = value.wrapping_add(1);
+                m.flags = SZP[value as usize] | (m.flags & CARRY) | if value & 0xf == 0 { AUX_CARRY } else { 0 };
+                m.b = value;
+                m.tick(5, 0x0279)?;
+                // 0279  jnz 027dh
+                m.pc = if m.flags & ZERO == 0 { 0x027d } else { 0x027c };
+                m.tick(10, m.pc)?;
+            },
+            0x027c => {
+                // 027c  dcr a
+                let value = m.a;
+                let value = value.wrapping_sub(1);
+                m.flags = SZP[value as usize] | (m.flags & CARRY) | if value & 0xf != 0xf { AUX_CARRY } else { 0 };
+                m.a = value;
+                m.tick(5, 0x027d)?;
+                m.pc = 0x027d;
+            },
+            0x027d => {
+                // 027d  dcr b
+                let value = m.b;
+                let value = value.wrapping_sub(1);
+                m.flags = SZP[value as usize] | (m.flags & CARRY) | if value & 0xf != 0xf { AUX_CARRY } else { 0 };
+                m.b = value;
+                m.tick(5, 0x027e)?;
+                // 027e  mov m,b
+                m.write(m.hl(), m.b)?;
+                m.tick(7, 0x027f)?;
+                // 027f  dcx h
+                m.set_hl(m.hl().wrapping_sub(1));
+                m.tick(5, 0x0280)?;
+                // 0280  mov m,a
+                m.write(m.hl(), m.a)?;
+                m.tick(7, 0x0281)?;
+                m.pc = 0x0281;
+            },
+            0x0281 => {
+                // 0281  lxi d,0010h
+                m.set_de(0x0010);
+                m.tick(10, 0x0284)?;
+                // 0284  dad d
+                let result = m.hl() as u32 + m.de() as u32;
+                m.flags = (m.flags & !CARRY) | (result >> 16) as u8;
+                m.set_hl(result as u16);
+                m.tick(10, 0x0285)?;
+                // 0285  jmp 024bh
+                m.pc = 0x024b;
+                m.tick(10, 0x024b)?;
+            },
+            0x0288 => {
+                // 0288  dcr m
+                let value = m.read(m.hl())?;
+                let value = value.wrapping_sub(1);
+                m.flags = SZP[value as usize] | (m.flags & CARRY) | if value & 0xf != 0xf { AUX_CARRY } else { 0 };
+                m.write(m.hl(), value)?;
+                m.tick(10, 0x0289)?;
+                // 0289  dcx h
+                m.set_hl(m.hl().wrapping_sub(1));
+                m.tick(5, 0x028a)?;
+                // 028a  dcx h
+                m.set_hl(m.hl().wrapping_sub(1));
+                m.tick(5, 0x028b)?;
+                // 028b  jmp 0281h
+                m.pc = 0x0281;
+                m.tick(10, 0x0281)?;
+            },
+            _ => return Ok(()),
+        }
+    }
+}
+
+fn sub_024b(m: &mut Machine) -> Result<(), Stop> {
+    loop {
+        match m.pc {
+            0x024b => {
+                // 024b  mov a,m
+                m.a = m.read(m.hl())?;
+                m.tick(7, 0x024c)?;
+                // 024c  cpi 0ffh
+                let value: u8 = 0xff;
+                let result = (m.a as u16).wrapping_sub(value as u16);
+                m.flags = SZP[result as u8 as usize] | (result > 0xff) as u8 | if (m.a & 0xf).wrapping_sub(value & 0xf) > 0xf { AUX_CARRY } else { 0 };
+                m.tick(7, 0x024e)?;
+                // 024e  rz
+                if m.flags & ZERO != 0 {
+                    let target = m.pop()?;
+                    m.pc = target;
+                    m.tick(11, target)?;
+                    return Ok(());
+                }
+                m.tick(5, 0x024f)?;
+                // 024f  cpi 0feh
+                let value: u8 = 0xfe;
+                let result = (m.a as u16).wrapping_sub(value as u16);
+                m.flags = SZP[result as u8 as usize] | (result > 0xff) as u8 | if (m.a & 0xf).wrapping_sub(value & 0xf) > 0xf { AUX_CARRY } else { 0 };
+                m.tick(7, 0x0251)?;
+                // 0251  jz 0281h
+                m.pc = if m.flags & ZERO != 0 { 0x0281 } else { 0x0254 };
+                m.tick(10, m.pc)?;
+            },
+            0x0254 => {
+                // 0254  inx h
+                m.set_hl(m.hl().wrapping_add(1));
+                m.tick(5, 0x0255)?;
+                // 0255  mov b,m
+                m.b = m.read(m.hl())?;
+                m.tick(7, 0x0256)?;
+                // 0256  mov c,a
+                m.c = m.a;
+                m.tick(5, 0x0257)?;
+                // 0257  ora b
+                let value = m.b;
+                m.a |= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(4, 0x0258)?;
+                // 0258  mov a,c
+                m.a = m.c;
+                m.tick(5, 0x0259)?;
+                // 0259  jnz 0277h
+                m.pc = if m.flags & ZERO == 0 { 0x0277 } else { 0x025c };
+                m.tick(10, m.pc)?;
+            },
+            0x025c => {
+                // 025c  inx h
+                m.set_hl(m.hl().wrapping_add(1));
+                m.tick(5, 0x025d)?;
+                // 025d  mov a,m
+                m.a = m.read(m.hl())?;
+                m.tick(7, 0x025e)?;
+                // 025e  ana a
+                let value = m.a;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(4, 0x025f)?;
+                // 025f  jnz 0288h
+                m.pc = if m.flags & ZERO == 0 { 0x0288 } else { 0x0262 };
+                m.tick(10, m.pc)?;
+            },
+            0x0262 => {
+                // 0262  inx h
+                m.set_hl(m.hl().wrapping_add(1));
+                m.tick(5, 0x0263)?;
+                // 0263  mov e,m
+                m.e = m.read(m.hl())?;
+                m.tick(7, 0x0264)?;
+                // 0264  inx h
+                m.set_hl(m.hl().wrapping_add(1));
+                m.tick(5, 0x0265)?;
+                // 0265  mov d,m
+                m.d = m.read(m.hl())?;
+                m.tick(7, 0x0266)?;
+                // 0266  push h
+                m.push(m.hl())?;
+                m.tick(11, 0x0267)?;
+                // 0267  xchg
+                std::mem::swap(&mut m.d, &mut m.h);
+                std::mem::swap(&mut m.e, &mut m.l);
+                m.tick(4, 0x0268)?;
+                // 0268  push h
+                m.push(m.hl())?;
+                m.tick(11, 0x0269)?;
+                // 0269  lxi h,026fh
+                m.set_hl(0x026f);
+                m.tick(10, 0x026c)?;
+                // 026c  xthl
+                let value = m.pop()?;
+                let hl = m.hl();
+                m.set_hl(value);
+                m.push(hl)?;
+                m.tick(18, 0x026d)?;
+                // 026d  push d
+                m.push(m.de())?;
+                m.tick(11, 0x026e)?;
+                // 026e  pchl
+                m.pc = m.hl();
+                m.tick(5, m.pc)?;
+            },
+            0x0277 => {
+                // 0277  dcr b
+                let value = m.b;
+                let value = value.wrapping_sub(1);
+                m.flags = SZP[value as usize] | (m.flags & CARRY) | if value & 0xf != 0xf { AUX_CARRY } else { 0 };
+                m.b = value;
+                m.tick(5, 0x0278)?;
+                // 0278  inr b
+                let value = m.b;
+                let value = value.wrapping_add(1);
+                m.flags = SZP[value as usize] | (m.flags & CARRY) | if value & 0xf == 0 { AUX_CARRY } else { 0 };
+                m.b = value;
+                m.tick(5, 0x0279)?;
+                // 0279  jnz 027dh
+                m.pc = if m.flags & ZERO == 0 { 0x027d } else { 0x027c };
+                m.tick(10, m.pc)?;
+            },
+            0x027c => {
+                // 027c  dcr a
+                let value = m.a;
+                let value = value.wrapping_sub(1);
+                m.flags = SZP[value as usize] | (m.flags & CARRY) | if value & 0xf != 0xf { AUX_CARRY } else { 0 };
+                m.a = value;
+                m.tick(5, 0x027d)?;
+                m.pc = 0x027d;
+            },
+            0x027d => {
+                // 027d  dcr b
+                let value = m.b;
+                let value = value.wrapping_sub(1);
+                m.flags = SZP[value as usize] | (m.flags & CARRY) | if value & 0xf != 0xf { AUX_CARRY } else { 0 };
+                m.b = value;
+                m.tick(5, 0x027e)?;
+                // 027e  mov m,b
+                m.write(m.hl(), m.b)?;
+                m.tick(7, 0x027f)?;
+                // 027f  dcx h
+                m.set_hl(m.hl().wrapping_sub(1));
+                m.tick(5, 0x0280)?;
+                // 0280  mov m,a
+                m.write(m.hl(), m.a)?;
+                m.tick(7, 0x0281)?;
+                m.pc = 0x0281;
+            },
+            0x0281 => {
+                // 0281  lxi d,0010h
+                m.set_de(0x0010);
+                m.tick(10, 0x0284)?;
+                // 0284  dad d
+                let result = m.hl() as u32 + m.de() as u32;
+                m.flags = (m.flags & !CARRY) | (result >> 16) as u8;
+                m.set_hl(result as u16);
+                m.tick(10, 0x0285)?;
+                // 0285  jmp 024bh
+                m.pc = 0x024b;
+                m.tick(10, 0x024b)?;
+            },
+            0x0288 => {
+                // 0288  dcr m
+                let value = m.read(m.hl())?;
+                let value = value.wrapping_sub(1);
+                m.flags = SZP[value as usize] | (m.flags & CARRY) | if value & 0xf != 0xf { AUX_CARRY } else { 0 };
+                m.write(m.hl(), value)?;
+                m.tick(10, 0x0289)?;
+                // 0289  dcx h
+                m.set_hl(m.hl().wrapping_sub(1));
+                m.tick(5, 0x028a)?;
+                // 028a  dcx h
+                m.set_hl(m.hl().wrapping_sub(1));
+                m.tick(5, 0x028b)?;
+                // 028b  jmp 0281h
+                m.pc = 0x0281;
+                m.tick(10, 0x0281)?;
+            },
+            _ => return Ok(()),
+        }
+    }
+}
+
+fn sub_0878(m: &mut Machine) -> Result<(), Stop> {
+    loop {
+        match m.pc {
+            0x0878 => {
+                // 0878  lda 2008h
+                m.a = m.read(0x2008)?;
+                m.tick(13, 0x087b)?;
+                // 087b  mov b,a
+                m.b = m.a;
+                m.tick(5, 0x087c)?;
+                // 087c  lhld 2009h
+                m.l = m.read(0x2009)?;
+                m.h = m.read(0x200a)?;
+                m.tick(16, 0x087f)?;
+                // 087f  xchg
+                std::mem::swap(&mut m.d, &mut m.h);
+                std::mem::swap(&mut m.e, &mut m.l);
+                m.tick(4, 0x0880)?;
+                // 0880  jmp 0886h
+                m.pc = 0x0886;
+                m.tick(10, 0x0886)?;
+            },
+            0x0886 => {
+                // 0886  lda 2067h
+                m.a = m.read(0x2067)?;
+                m.tick(13, 0x0889)?;
+                // 0889  mov h,a
+                m.h = m.a;
+                m.tick(5, 0x088a)?;
+                // 088a  mvi l,0fch
+                m.l = 0xfc;
+                m.tick(7, 0x088c)?;
+                // 088c  ret
+                let target = m.pop()?;
+                m.pc = target;
+                m.tick(10, target)?;
+                return Ok(());
+            },
+            _ => return Ok(()),
+        }
+    }
+}
+
+fn sub_0886(m: &mut Machine) -> Result<(), Stop> {
+    // 0886  lda 2067h
+    m.a = m.read(0x2067)?;
+    m.tick(13, 0x0889)?;
+    // 0889  mov h,a
+    m.h = m.a;
+    m.tick(5, 0x088a)?;
+    // 088a  mvi l,0fch
+    m.l = 0xfc;
+    m.tick(7, 0x088c)?;
+    // 088c  ret
+    let target = m.pop()?;
+    m.pc = target;
+    m.tick(10, target)?;
+    Ok(())
+}
+
+fn sub_088d(m: &mut Machine) -> Result<(), Stop> {
+    loop {
+        match m.pc {
+            0x088d => {
+                // 088d  lxi h,2b11h
+                m.set_hl(0x2b11);
+                m.tick(10, 0x0890)?;
+                // 0890  lxi d,1b70h
+                m.set_de(0x1b70);
+                m.tick(10, 0x0893)?;
+                // 0893  mvi c,0eh
+                m.c = 0x0e;
+                m.tick(7, 0x0895)?;
+                // 0895  call 08f3h
+                m.push(0x0898)?;
+                m.pc = 0x08f3;
+                m.tick(17, 0x08f3)?;
+                m.call(0x0898)?;
+                // 0898  lda 2067h
+                m.a = m.read(0x2067)?;
+                m.tick(13, 0x089b)?;
+                // 089b  rrc
+                m.flags = (m.flags & !CARRY) | (m.a & 1);
+                m.a = m.a.rotate_right(1);
+                m.tick(4, 0x089c)?;
+                // 089c  mvi a,1ch
+                m.a = 0x1c;
+                m.tick(7, 0x089e)?;
+                // 089e  lxi h,3711h
+                m.set_hl(0x3711);
+                m.tick(10, 0x08a1)?;
+                // 08a1  cnc 08ffh
+                if m.flags & CARRY == 0 {
+                    m.push(0x08a4)?;
+                    m.pc = 0x08ff;
+                    m.tick(17, 0x08ff)?;
+                    m.call(0x08a4)?;
+                } else {
+                    m.tick(11, 0x08a4)?;
+                }
+                // 08a4  mvi a,0b0h
+                m.a = 0xb0;
+                m.tick(7, 0x08a6)?;
+                // 08a6  sta 20c0h
+                m.write(0x20c0, m.a)?;
+                m.tick(13, 0x08a9)?;
+                m.pc = 0x08a9;
+            },
+            0x08a9 => {
+                // 08a9  lda 20c0h
+                m.a = m.read(0x20c0)?;
+                m.tick(13, 0x08ac)?;
+                // 08ac  ana a
+                let value = m.a;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(4, 0x08ad)?;
+                // 08ad  rz
+                if m.flags & ZERO != 0 {
+                    let target = m.pop()?;
+                    m.pc = target;
+                    m.tick(11, target)?;
+                    return Ok(());
+                }
+                m.tick(5, 0x08ae)?;
+                // 08ae  ani 04h
+                let value: u8 = 0x04;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(7, 0x08b0)?;
+                // 08b0  jnz 08bch
+                m.pc = if m.flags & ZERO == 0 { 0x08bc } else { 0x08b3 };
+                m.tick(10, m.pc)?;
+            },
+            0x08b3 => {
+                // 08b3  call 09cah
+                m.push(0x08b6)?;
+                m.pc = 0x09ca;
+                m.tick(17, 0x09ca)?;
+                m.call(0x08b6)?;
+                // 08b6  call 1931h
+                m.push(0x08b9)?;
+                m.pc = 0x1931;
+                m.tick(17, 0x1931)?;
+                m.call(0x08b9)?;
+                // 08b9  jmp 08a9h
+                m.pc = 0x08a9;
+                m.tick(10, 0x08a9)?;
+            },
+            0x08bc => {
+                // 08bc  mvi b,20h
+                m.b = 0x20;
+                m.tick(7, 0x08be)?;
+                // 08be  lxi h,271ch
+                m.set_hl(0x271c);
+                m.tick(10, 0x08c1)?;
+                // 08c1  lda 2067h
+                m.a = m.read(0x2067)?;
+                m.tick(13, 0x08c4)?;
+                // 08c4  rrc
+                m.flags = (m.flags & !CARRY) | (m.a & 1);
+                m.a = m.a.rotate_right(1);
+                m.tick(4, 0x08c5)?;
+                // 08c5  jc 08cbh
+                m.pc = if m.flags & CARRY != 0 { 0x08cb } else { 0x08c8 };
+                m.tick(10, m.pc)?;
+            },
+            0x08c8 => {
+                // 08c8  lxi h,391ch
+                m.set_hl(0x391c);
+                m.tick(10, 0x08cb)?;
+                m.pc = 0x08cb;
+            },
+            0x08cb => {
+                // 08cb  call 14cbh
+                m.push(0x08ce)?;
+                m.pc = 0x14cb;
+                m.tick(17, 0x14cb)?;
+                m.call(0x08ce)?;
+                // 08ce  jmp 08a9h
+                m.pc = 0x08a9;
+                m.tick(10, 0x08a9)?;
+            },
+            _ => return Ok(()),
+        }
+    }
+}
+
+fn sub_08d1(m: &mut Machine) -> Result<(), Stop> {
+    // 08d1  in 02h
+    m.a = m.input(0x02)?;
+    m.tick(10, 0x08d3)?;
+    // 08d3  ani 03h
+    let value: u8 = 0x03;
+    m.a &= value;
+    m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+    m.tick(7, 0x08d5)?;
+    // 08d5  adi 03h
+    let value: u8 = 0x03;
+    let result = m.a as u16 + value as u16;
+    m.flags = SZP[result as u8 as usize] | (result >> 8) as u8 | if (m.a & 0xf) + (value & 0xf) > 0xf { AUX_CARRY } else { 0 };
+    m.a = result as u8;
+    m.tick(7, 0x08d7)?;
+    // 08d7  ret
+    let target = m.pop()?;
+    m.pc = target;
+    m.tick(10, target)?;
+    Ok(())
+}
+
+fn sub_08d8(m: &mut Machine) -> Result<(), Stop> {
+    // 08d8  lda 2082h
+    m.a = m.read(0x2082)?;
+    m.tick(13, 0x08db)?;
+    // 08db  cpi 09h
+    let value: u8 = 0x09;
+    let result = (m.a as u16).wrapping_sub(value as u16);
+    m.flags = SZP[result as u8 as usize] | (result > 0xff) as u8 | if (m.a & 0xf).wrapping_sub(value & 0xf) > 0xf { AUX_CARRY } else { 0 };
+    m.tick(7, 0x08dd)?;
+    // 08dd  rnc
+    if m.flags & CARRY == 0 {
+        let target = m.pop()?;
+        m.pc = target;
+        m.tick(11, target)?;
+        return Ok(());
+    }
+    m.tick(5, 0x08de)?;
+    // 08de  mvi a,0fbh
+    m.a = 0xfb;
+    m.tick(7, 0x08e0)?;
+    // 08e0  sta 207eh
+    m.write(0x207e, m.a)?;
+    m.tick(13, 0x08e3)?;
+    // 08e3  ret
+    let target = m.pop()?;
+    m.pc = target;
+    m.tick(10, target)?;
+    Ok(())
+}
+
+fn sub_08f3(m: &mut Machine) -> Result<(), Stop> {
+    loop {
+        match m.pc {
+            0x08f3 => {
+                // 08f3  ldax d
+                m.a = m.read(m.de())?;
+                m.tick(7, 0x08f4)?;
+                // 08f4  push d
+                m.push(m.de())?;
+                m.tick(11, 0x08f5)?;
+                // 08f5  call 08ffh
+                m.push(0x08f8)?;
+                m.pc = 0x08ff;
+                m.tick(17, 0x08ff)?;
+                m.call(0x08f8)?;
+                // 08f8  pop d
+                let value = m.pop()?;
+                m.set_de(value);
+                m.tick(10, 0x08f9)?;
+                // 08f9  inx d
+                m.set_de(m.de().wrapping_add(1));
+                m.tick(5, 0x08fa)?;
+                // 08fa  dcr c
+                let value = m.c;
+                let value = value.wrapping_sub(1);
+                m.flags = SZP[value as usize] | (m.flags & CARRY) | if value & 0xf != 0xf { AUX_CARRY } else { 0 };
+                m.c = value;
+                m.tick(5, 0x08fb)?;
+                // 08fb  jnz 08f3h
+                m.pc = if m.flags & ZERO == 0 { 0x08f3 } else { 0x08fe };
+                m.tick(10, m.pc)?;
+            },
+            0x08fe => {
+                // 08fe  ret
+                let target = m.pop()?;
+                m.pc = target;
+                m.tick(10, target)?;
+                return Ok(());
+            },
+            _ => return Ok(()),
+        }
+    }
+}
+
+fn sub_08ff(m: &mut Machine) -> Result<(), Stop> {
+    loop {
+        match m.pc {
+            0x08ff => {
+                // 08ff  lxi d,1e00h
+                m.set_de(0x1e00);
+                m.tick(10, 0x0902)?;
+                // 0902  push h
+                m.push(m.hl())?;
+                m.tick(11, 0x0903)?;
+                // 0903  mvi h,00h
+                m.h = 0x00;
+                m.tick(7, 0x0905)?;
+                // 0905  mov l,a
+                m.l = m.a;
+                m.tick(5, 0x0906)?;
+                // 0906  dad h
+                let result = m.hl() as u32 + m.hl() as u32;
+                m.flags = (m.flags & !CARRY) | (result >> 16) as u8;
+                m.set_hl(result as u16);
+                m.tick(10, 0x0907)?;
+                // 0907  dad h
+                let result = m.hl() as u32 + m.hl() as u32;
+                m.flags = (m.flags & !CARRY) | (result >> 16) as u8;
+                m.set_hl(result as u16);
+                m.tick(10, 0x0908)?;
+                // 0908  dad h
+                let result = m.hl() as u32 + m.hl() as u32;
+                m.flags = (m.flags & !CARRY) | (result >> 16) as u8;
+                m.set_hl(result as u16);
+                m.tick(10, 0x0909)?;
+                // 0909  dad d
+                let result = m.hl() as u32 + m.de() as u32;
+                m.flags = (m.flags & !CARRY) | (result >> 16) as u8;
+                m.set_hl(result as u16);
+                m.tick(10, 0x090a)?;
+                // 090a  xchg
+                std::mem::swap(&mut m.d, &mut m.h);
+                std::mem::swap(&mut m.e, &mut m.l);
+                m.tick(4, 0x090b)?;
+                // 090b  pop h
+                let value = m.pop()?;
+                m.set_hl(value);
+                m.tick(10, 0x090c)?;
+                // 090c  mvi b,08h
+                m.b = 0x08;
+                m.tick(7, 0x090e)?;
+                // 090e  out 06h
+                m.output(0x06, m.a)?;
+                m.tick(10, 0x0910)?;
+                // 0910  jmp 1439h
+                m.pc = 0x1439;
+                m.tick(10, 0x1439)?;
+            },
+            0x1439 => {
+                // 1439  push b
+                m.push(m.bc())?;
+                m.tick(11, 0x143a)?;
+                // 143a  ldax d
+                m.a = m.read(m.de())?;
+                m.tick(7, 0x143b)?;
+                // 143b  mov m,a
+                m.write(m.hl(), m.a)?;
+                m.tick(7, 0x143c)?;
+                // 143c  inx d
+                m.set_de(m.de().wrapping_add(1));
+                m.tick(5, 0x143d)?;
+                // 143d  lxi b,0020h
+                m.set_bc(0x0020);
+                m.tick(10, 0x1440)?;
+                // 1440  dad b
+                let result = m.hl() as u32 + m.bc() as u32;
+                m.flags = (m.flags & !CARRY) | (result >> 16) as u8;
+                m.set_hl(result as u16);
+                m.tick(10, 0x1441)?;
+                // 1441  pop b
+                let value = m.pop()?;
+                m.set_bc(value);
+                m.tick(10, 0x1442)?;
+                // 1442  dcr b
+                let value = m.b;
+                let value = value.wrapping_sub(1);
+                m.flags = SZP[value as usize] | (m.flags & CARRY) | if value & 0xf != 0xf { AUX_CARRY } else { 0 };
+                m.b = value;
+                m.tick(5, 0x1443)?;
+                // 1443  jnz 1439h
+                m.pc = if m.flags & ZERO == 0 { 0x1439 } else { 0x1446 };
+                m.tick(10, m.pc)?;
+            },
+            0x1446 => {
+                // 1446  ret
+                let target = m.pop()?;
+                m.pc = target;
+                m.tick(10, target)?;
+                return Ok(());
+            },
+            _ => return Ok(()),
+        }
+    }
+}
+
+fn sub_0913(m: &mut Machine) -> Result<(), Stop> {
+    loop {
+        match m.pc {
+            0x0913 => {
+                // 0913  lda 2009h
+                m.a = m.read(0x2009)?;
+                m.tick(13, 0x0916)?;
+                // 0916  cpi 78h
+                let value: u8 = 0x78;
+                let result = (m.a as u16).wrapping_sub(value as u16);
+                m.flags = SZP[result as u8 as usize] | (result > 0xff) as u8 | if (m.a & 0xf).wrapping_sub(value & 0xf) > 0xf { AUX_CARRY } else { 0 };
+                m.tick(7, 0x0918)?;
+                // 0918  rnc
+                if m.flags & CARRY == 0 {
+                    let target = m.pop()?;
+                    m.pc = target;
+                    m.tick(11, target)?;
+                    return Ok(());
+                }
+                m.tick(5, 0x0919)?;
+                // 0919  lhld 2091h
+                m.l = m.read(0x2091)?;
+                m.h = m.read(0x2092)?;
+                m.tick(16, 0x091c)?;
+                // 091c  mov a,l
+                m.a = m.l;
+                m.tick(5, 0x091d)?;
+                // 091d  ora h
+                let value = m.h;
+                m.a |= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(4, 0x091e)?;
+                // 091e  jnz 0929h
+                m.pc = if m.flags & ZERO == 0 { 0x0929 } else { 0x0921 };
+                m.tick(10, m.pc)?;
+            },
+            0x0921 => {
+                // 0921  lxi h,0600h
+                m.set_hl(0x0600);
+                m.tick(10, 0x0924)?;
+                // 0924  mvi a,01h
+                m.a = 0x01;
+                m.tick(7, 0x0926)?;
+                // 0926  sta 2083h
+                m.write(0x2083, m.a)?;
+                m.tick(13, 0x0929)?;
+                m.pc = 0x0929;
+            },
+            0x0929 => {
+                // 0929  dcx h
+                m.set_hl(m.hl().wrapping_sub(1));
+                m.tick(5, 0x092a)?;
+                // 092a  shld 2091h
+                m.write(0x2091, m.l)?;
+                m.write(0x2092, m.h)?;
+                m.tick(16, 0x092d)?;
+                // 092d  ret
+                let target = m.pop()?;
+                m.pc = target;
+                m.tick(10, target)?;
+                return Ok(());
+            },
+            _ => return Ok(()),
+        }
+    }
+}
+
+fn sub_092e(m: &mut Machine) -> Result<(), Stop> {
+    // 092e  call 1611h
+    m.push(0x0931)?;
+    m.pc = 0x1611;
+    m.tick(17, 0x1611)?;
+    m.call(0x0931)?;
+    // 0931  mvi l,0ffh
+    m.l = 0xff;
+    m.tick(7, 0x0933)?;
+    // 0933  mov a,m
+    m.a = m.read(m.hl())?;
+    m.tick(7, 0x0934)?;
+    // 0934  ret
+    let target = m.pop()?;
+    m.pc = target;
+    m.tick(10, target)?;
+    Ok(())
+}
+
+fn sub_0935(m: &mut Machine) -> Result<(), Stop> {
+    loop {
+        match m.pc {
+            0x0935 => {
+                // 0935  call 1910h
+                m.push(0x0938)?;
+                m.pc = 0x1910;
+                m.tick(17, 0x1910)?;
+                m.call(0x0938)?;
+                // 0938  dcx h
+                m.set_hl(m.hl().wrapping_sub(1));
+                m.tick(5, 0x0939)?;
+                // 0939  dcx h
+                m.set_hl(m.hl().wrapping_sub(1));
+                m.tick(5, 0x093a)?;
+                // 093a  mov a,m
+                m.a = m.read(m.hl())?;
+                m.tick(7, 0x093b)?;
+                // 093b  ana a
+                let value = m.a;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(4, 0x093c)?;
+                // 093c  rz
+                if m.flags & ZERO != 0 {
+                    let target = m.pop()?;
+                    m.pc = target;
+                    m.tick(11, target)?;
+                    return Ok(());
+                }
+                m.tick(5, 0x093d)?;
+                // 093d  mvi b,15h
+                m.b = 0x15;
+                m.tick(7, 0x093f)?;
+                // 093f  in 02h
+                m.a = m.input(0x02)?;
+                m.tick(10, 0x0941)?;
+                // 0941  ani 08h
+                let value: u8 = 0x08;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(7, 0x0943)?;
+                // 0943  jz 0948h
+                m.pc = if m.flags & ZERO != 0 { 0x0948 } else { 0x0946 };
+                m.tick(10, m.pc)?;
+            },
+            0x0946 => {
+                // 0946  mvi b,10h
+                m.b = 0x10;
+                m.tick(7, 0x0948)?;
+                m.pc = 0x0948;
+            },
+            0x0948 => {
+                // 0948  call 09cah
+                m.push(0x094b)?;
+                m.pc = 0x09ca;
+                m.tick(17, 0x09ca)?;
+                m.call(0x094b)?;
+                // 094b  inx h
+                m.set_hl(m.hl().wrapping_add(1));
+                m.tick(5, 0x094c)?;
+                // 094c  mov a,m
+                m.a = m.read(m.hl())?;
+                m.tick(7, 0x094d)?;
+                // 094d  cmp b
+                let value = m.b;
+                let result = (m.a as u16).wrapping_sub(value as u16);
+                m.flags = SZP[result as u8 as usize] | (result > 0xff) as u8 | if (m.a & 0xf).wrapping_sub(value & 0xf) > 0xf { AUX_CARRY } else { 0 };
+                m.tick(4, 0x094e)?;
+                // 094e  rc
+                if m.flags & CARRY != 0 {
+                    let target = m.pop()?;
+                    m.pc = target;
+                    m.tick(11, target)?;
+                    return Ok(());
+                }
+                m.tick(5, 0x094f)?;
+                // 094f  call 092eh
+                m.push(0x0952)?;
+                m.pc = 0x092e;
+                m.tick(17, 0x092e)?;
+                m.call(0x0952)?;
+                // 0952  inr m
+                let value = m.read(m.hl())?;
+                let value = value.wrapping_add(1);
+                m.flags = SZP[value as usize] | (m.flags & CARRY) | if value & 0xf == 0 { AUX_CARRY } else { 0 };
+                m.write(m.hl(), value)?;
+                m.tick(10, 0x0953)?;
+                // 0953  mov a,m
+                m.a = m.read(m.hl())?;
+                m.tick(7, 0x0954)?;
+                // 0954  push psw
+                m.push((m.a as u16) << 8 | m.flags as u16)?;
+                m.tick(11, 0x0955)?;
+                // 0955  lxi h,2501h
+                m.set_hl(0x2501);
+                m.tick(10, 0x0958)?;
+                m.pc = 0x0958;
+            },
+            0x0958 => {
+                // 0958  inr h
+                let value = m.h;
+                let value = value.wrapping_add(1);
+                m.flags = SZP[value as usize] | (m.flags & CARRY) | if value & 0xf == 0 { AUX_CARRY } else { 0 };
+                m.h = value;
+                m.tick(5, 0x0959)?;
+                // 0959  inr h
+                let value = m.h;
+                let value = value.wrapping_add(1);
+                m.flags = SZP[value as usize] | (m.flags & CARRY) | if value & 0xf == 0 { AUX_CARRY } else { 0 };
+                m.h = value;
+                m.tick(5, 0x095a)?;
+                // 095a  dcr a
+                let value = m.a;
+                let value = value.wrapping_sub(1);
+                m.flags = SZP[value as usize] | (m.flags & CARRY) | if value & 0xf != 0xf { AUX_CARRY } else { 0 };
+                m.a = value;
+                m.tick(5, 0x095b)?;
+                // 095b  jnz 0958h
+                m.pc = if m.flags & ZERO == 0 { 0x0958 } else { 0x095e };
+                m.tick(10, m.pc)?;
+            },
+            0x095e => {
+                // 095e  mvi b,10h
+                m.b = 0x10;
+                m.tick(7, 0x0960)?;
+                // 0960  lxi d,1c60h
+                m.set_de(0x1c60);
+                m.tick(10, 0x0963)?;
+                // 0963  call 1439h
+                m.push(0x0966)?;
+                m.pc = 0x1439;
+                m.tick(17, 0x1439)?;
+                m.call(0x0966)?;
+                // 0966  pop psw
+                let value = m.pop()?;
+                m.a = (value >> 8) as u8;
+                m.flags = value as u8 & (SIGN | ZERO | AUX_CARRY | PARITY | CARRY);
+                m.tick(10, 0x0967)?;
+                // 0967  inr a
+                let value = m.a;
+                let value = value.wrapping_add(1);
+                m.flags = SZP[value as usize] | (m.flags & CARRY) | if value & 0xf == 0 { AUX_CARRY } else { 0 };
+                m.a = value;
+                m.tick(5, 0x0968)?;
+                // 0968  call 1a8bh
+                m.push(0x096b)?;
+                m.pc = 0x1a8b;
+                m.tick(17, 0x1a8b)?;
+                m.call(0x096b)?;
+                // 096b  call 1910h
+                m.push(0x096e)?;
+                m.pc = 0x1910;
+                m.tick(17, 0x1910)?;
+                m.call(0x096e)?;
+                // 096e  dcx h
+                m.set_hl(m.hl().wrapping_sub(1));
+                m.tick(5, 0x096f)?;
+                // 096f  dcx h
+                m.set_hl(m.hl().wrapping_sub(1));
+                m.tick(5, 0x0970)?;
+                // 0970  mvi m,00h
+                m.write(m.hl(), 0x00)?;
+                m.tick(10, 0x0972)?;
+                // 0972  mvi a,0ffh
+                m.a = 0xff;
+                m.tick(7, 0x0974)?;
+                // 0974  sta 2099h
+                m.write(0x2099, m.a)?;
+                m.tick(13, 0x0977)?;
+                // 0977  mvi b,10h
+                m.b = 0x10;
+                m.tick(7, 0x0979)?;
+                // 0979  jmp 18fah
+                m.pc = 0x18fa;
+                m.tick(10, 0x18fa)?;
+            },
+            0x18fa => {
+                // 18fa  lda 2094h
+                m.a = m.read(0x2094)?;
+                m.tick(13, 0x18fd)?;
+                // 18fd  ora b
+                let value = m.b;
+                m.a |= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(4, 0x18fe)?;
+                // 18fe  sta 2094h
+                m.write(0x2094, m.a)?;
+                m.tick(13, 0x1901)?;
+                // 1901  out 03h
+                m.output(0x03, m.a)?;
+                m.tick(10, 0x1903)?;
+                // 1903  ret
+                let target = m.pop()?;
+                m.pc = target;
+                m.tick(10, target)?;
+                return Ok(());
+            },
+            _ => return Ok(()),
+        }
+    }
+}
+
+fn sub_097c(m: &mut Machine) -> Result<(), Stop> {
+    // 097c  lxi h,1da0h
+    m.set_hl(0x1da0);
+    m.tick(10, 0x097f)?;
+    // 097f  cpi 02h
+    let value: u8 = 0x02;
+    let result = (m.a as u16).wrapping_sub(value as u16);
+    m.flags = SZP[result as u8 as usize] | (result > 0xff) as u8 | if (m.a & 0xf).wrapping_sub(value & 0xf) > 0xf { AUX_CARRY } else { 0 };
+    m.tick(7, 0x0981)?;
+    // 0981  rc
+    if m.flags & CARRY != 0 {
+        let target = m.pop()?;
+        m.pc = target;
+        m.tick(11, target)?;
+        return Ok(());
+    }
+    m.tick(5, 0x0982)?;
+    // 0982  inx h
+    m.set_hl(m.hl().wrapping_add(1));
+    m.tick(5, 0x0983)?;
+    // 0983  cpi 04h
+    let value: u8 = 0x04;
+    let result = (m.a as u16).wrapping_sub(value as u16);
+    m.flags = SZP[result as u8 as usize] | (result > 0xff) as u8 | if (m.a & 0xf).wrapping_sub(value & 0xf) > 0xf { AUX_CARRY } else { 0 };
+    m.tick(7, 0x0985)?;
+    // 0985  rc
+    if m.flags & CARRY != 0 {
+        let target = m.pop()?;
+        m.pc = target;
+        m.tick(11, target)?;
+        return Ok(());
+    }
+    m.tick(5, 0x0986)?;
+    // 0986  inx h
+    m.set_hl(m.hl().wrapping_add(1));
+    m.tick(5, 0x0987)?;
+    // 0987  ret
+    let target = m.pop()?;
+    m.pc = target;
+    m.tick(10, target)?;
+    Ok(())
+}
+
+fn sub_0988(m: &mut Machine) -> Result<(), Stop> {
+    loop {
+        match m.pc {
+            0x0988 => {
+                // 0988  call 09cah
+                m.push(0x098b)?;
+                m.pc = 0x09ca;
+                m.tick(17, 0x09ca)?;
+                m.call(0x098b)?;
+                // 098b  lda 20f1h
+                m.a = m.read(0x20f1)?;
+                m.tick(13, 0x098e)?;
+                // 098e  ana a
+                let value = m.a;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(4, 0x098f)?;
+                // 098f  rz
+                if m.flags & ZERO != 0 {
+                    let target = m.pop()?;
+                    m.pc = target;
+                    m.tick(11, target)?;
+                    return Ok(());
+                }
+                m.tick(5, 0x0990)?;
+                // 0990  xra a
+                let value = m.a;
+                m.a ^= value;
+                m.flags = SZP[m.a as usize] | if m.a > 0xf { AUX_CARRY } else { 0 };
+                m.tick(4, 0x0991)?;
+                // 0991  sta 20f1h
+                m.write(0x20f1, m.a)?;
+                m.tick(13, 0x0994)?;
+                // 0994  push h
+                m.push(m.hl())?;
+                m.tick(11, 0x0995)?;
+                // 0995  lhld 20f2h
+                m.l = m.read(0x20f2)?;
+                m.h = m.read(0x20f3)?;
+                m.tick(16, 0x0998)?;
+                // 0998  xchg
+                std::mem::swap(&mut m.d, &mut m.h);
+                std::mem::swap(&mut m.e, &mut m.l);
+                m.tick(4, 0x0999)?;
+                // 0999  pop h
+                let value = m.pop()?;
+                m.set_hl(value);
+                m.tick(10, 0x099a)?;
+                // 099a  mov a,m
+                m.a = m.read(m.hl())?;
+                m.tick(7, 0x099b)?;
+                // 099b  add e
+                let value = m.e;
+                let result = m.a as u16 + value as u16;
+                m.flags = SZP[result as u8 as usize] | (result >> 8) as u8 | if (m.a & 0xf) + (value & 0xf) > 0xf { AUX_CARRY } else { 0 };
+                m.a = result as u8;
+                m.tick(4, 0x099c)?;
+                // 099c  daa
+                m.daa();
+                m.tick(4, 0x099d)?;
+                // 099d  mov m,a
+                m.write(m.hl(), m.a)?;
+                m.tick(7, 0x099e)?;
+                // 099e  mov e,a
+                m.e = m.a;
+                m.tick(5, 0x099f)?;
+                // 099f  inx h
+                m.set_hl(m.hl().wrapping_add(1));
+                m.tick(5, 0x09a0)?;
+                // 09a0  mov a,m
+                m.a = m.read(m.hl())?;
+                m.tick(7, 0x09a1)?;
+                // 09a1  adc d
+                let value = m.d;
+                let carry = m.flags & CARRY;
+                let result = m.a as u16 + value as u16 + carry as u16;
+                m.flags = SZP[result as u8 as usize] | (result >> 8) as u8 | if (m.a & 0xf) + (value & 0xf) + carry > 0xf { AUX_CARRY } else { 0 };
+                m.a = result as u8;
+                m.tick(4, 0x09a2)?;
+                // 09a2  daa
+                m.daa();
+                m.tick(4, 0x09a3)?;
+                // 09a3  mov m,a
+                m.write(m.hl(), m.a)?;
+                m.tick(7, 0x09a4)?;
+                // 09a4  mov d,a
+                m.d = m.a;
+                m.tick(5, 0x09a5)?;
+                // 09a5  inx h
+                m.set_hl(m.hl().wrapping_add(1));
+                m.tick(5, 0x09a6)?;
+                // 09a6  mov a,m
+                m.a = m.read(m.hl())?;
+                m.tick(7, 0x09a7)?;
+                // 09a7  inx h
+                m.set_hl(m.hl().wrapping_add(1));
+                m.tick(5, 0x09a8)?;
+                // 09a8  mov h,m
+                m.h = m.read(m.hl())?;
+                m.tick(7, 0x09a9)?;
+                // 09a9  mov l,a
+                m.l = m.a;
+                m.tick(5, 0x09aa)?;
+                // 09aa  jmp 09adh
+                m.pc = 0x09ad;
+                m.tick(10, 0x09ad)?;
+            },
+            0x09ad => {
+                // 09ad  mov a,d
+                m.a = m.d;
+                m.tick(5, 0x09ae)?;
+                // 09ae  call 09b2h
+                m.push(0x09b1)?;
+                m.pc = 0x09b2;
+                m.tick(17, 0x09b2)?;
+                m.call(0x09b1)?;
+                // 09b1  mov a,e
+                m.a = m.e;
+                m.tick(5, 0x09b2)?;
+                // 09b2  push d
+                m.push(m.de())?;
+                m.tick(11, 0x09b3)?;
+                // 09b3  push psw
+                m.push((m.a as u16) << 8 | m.flags as u16)?;
+                m.tick(11, 0x09b4)?;
+                // 09b4  rrc
+                m.flags = (m.flags & !CARRY) | (m.a & 1);
+                m.a = m.a.rotate_right(1);
+                m.tick(4, 0x09b5)?;
+                // 09b5  rrc
+                m.flags = (m.flags & !CARRY) | (m.a & 1);
+                m.a = m.a.rotate_right(1);
+                m.tick(4, 0x09b6)?;
+                // 09b6  rrc
+                m.flags = (m.flags & !CARRY) | (m.a & 1);
+                m.a = m.a.rotate_right(1);
+                m.tick(4, 0x09b7)?;
+                // 09b7  rrc
+                m.flags = (m.flags & !CARRY) | (m.a & 1);
+                m.a = m.a.rotate_right(1);
+                m.tick(4, 0x09b8)?;
+                // 09b8  ani 0fh
+                let value: u8 = 0x0f;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(7, 0x09ba)?;
+                // 09ba  call 09c5h
+                m.push(0x09bd)?;
+                m.pc = 0x09c5;
+                m.tick(17, 0x09c5)?;
+                m.call(0x09bd)?;
+                // 09bd  pop psw
+                let value = m.pop()?;
+                m.a = (value >> 8) as u8;
+                m.flags = value as u8 & (SIGN | ZERO | AUX_CARRY | PARITY | CARRY);
+                m.tick(10, 0x09be)?;
+                // 09be  ani 0fh
+                let value: u8 = 0x0f;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(7, 0x09c0)?;
+                // 09c0  call 09c5h
+                m.push(0x09c3)?;
+                m.pc = 0x09c5;
+                m.tick(17, 0x09c5)?;
+                m.call(0x09c3)?;
+                // 09c3  pop d
+                let value = m.pop()?;
+                m.set_de(value);
+                m.tick(10, 0x09c4)?;
+                // 09c4  ret
+                let target = m.pop()?;
+                m.pc = target;
+                m.tick(10, target)?;
+                return Ok(());
+            },
+            _ => return Ok(()),
+        }
+    }
+}
+
+fn sub_09b2(m: &mut Machine) -> Result<(), Stop> {
+    // 09b2  push d
+    m.push(m.de())?;
+    m.tick(11, 0x09b3)?;
+    // 09b3  push psw
+    m.push((m.a as u16) << 8 | m.flags as u16)?;
+    m.tick(11, 0x09b4)?;
+    // 09b4  rrc
+    m.flags = (m.flags & !CARRY) | (m.a & 1);
+    m.a = m.a.rotate_right(1);
+    m.tick(4, 0x09b5)?;
+    // 09b5  rrc
+    m.flags = (m.flags & !CARRY) | (m.a & 1);
+    m.a = m.a.rotate_right(1);
+    m.tick(4, 0x09b6)?;
+    // 09b6  rrc
+    m.flags = (m.flags & !CARRY) | (m.a & 1);
+    m.a = m.a.rotate_right(1);
+    m.tick(4, 0x09b7)?;
+    // 09b7  rrc
+    m.flags = (m.flags & !CARRY) | (m.a & 1);
+    m.a = m.a.rotate_right(1);
+    m.tick(4, 0x09b8)?;
+    // 09b8  ani 0fh
+    let value: u8 = 0x0f;
+    m.a &= value;
+    m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+    m.tick(7, 0x09ba)?;
+    // 09ba  call 09c5h
+    m.push(0x09bd)?;
+    m.pc = 0x09c5;
+    m.tick(17, 0x09c5)?;
+    m.call(0x09bd)?;
+    // 09bd  pop psw
+    let value = m.pop()?;
+    m.a = (value >> 8) as u8;
+    m.flags = value as u8 & (SIGN | ZERO | AUX_CARRY | PARITY | CARRY);
+    m.tick(10, 0x09be)?;
+    // 09be  ani 0fh
+    let value: u8 = 0x0f;
+    m.a &= value;
+    m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+    m.tick(7, 0x09c0)?;
+    // 09c0  call 09c5h
+    m.push(0x09c3)?;
+    m.pc = 0x09c5;
+    m.tick(17, 0x09c5)?;
+    m.call(0x09c3)?;
+    // 09c3  pop d
+    let value = m.pop()?;
+    m.set_de(value);
+    m.tick(10, 0x09c4)?;
+    // 09c4  ret
+    let target = m.pop()?;
+    m.pc = target;
+    m.tick(10, target)?;
+    Ok(())
+}
+
+fn sub_09c5(m: &mut Machine) -> Result<(), Stop> {
+    loop {
+        match m.pc {
+            0x08ff => {
+                // 08ff  lxi d,1e00h
+                m.set_de(0x1e00);
+                m.tick(10, 0x0902)?;
+                // 0902  push h
+                m.push(m.hl())?;
+                m.tick(11, 0x0903)?;
+                // 0903  mvi h,00h
+                m.h = 0x00;
+                m.tick(7, 0x0905)?;
+                // 0905  mov l,a
+                m.l = m.a;
+                m.tick(5, 0x0906)?;
+                // 0906  dad h
+                let result = m.hl() as u32 + m.hl() as u32;
+                m.flags = (m.flags & !CARRY) | (result >> 16) as u8;
+                m.set_hl(result as u16);
+                m.tick(10, 0x0907)?;
+                // 0907  dad h
+                let result = m.hl() as u32 + m.hl() as u32;
+                m.flags = (m.flags & !CARRY) | (result >> 16) as u8;
+                m.set_hl(result as u16);
+                m.tick(10, 0x0908)?;
+                // 0908  dad h
+                let result = m.hl() as u32 + m.hl() as u32;
+                m.flags = (m.flags & !CARRY) | (result >> 16) as u8;
+                m.set_hl(result as u16);
+                m.tick(10, 0x0909)?;
+                // 0909  dad d
+                let result = m.hl() as u32 + m.de() as u32;
+                m.flags = (m.flags & !CARRY) | (result >> 16) as u8;
+                m.set_hl(result as u16);
+                m.tick(10, 0x090a)?;
+                // 090a  xchg
+                std::mem::swap(&mut m.d, &mut m.h);
+                std::mem::swap(&mut m.e, &mut m.l);
+                m.tick(4, 0x090b)?;
+                // 090b  pop h
+                let value = m.pop()?;
+                m.set_hl(value);
+                m.tick(10, 0x090c)?;
+                // 090c  mvi b,08h
+                m.b = 0x08;
+                m.tick(7, 0x090e)?;
+                // 090e  out 06h
+                m.output(0x06, m.a)?;
+                m.tick(10, 0x0910)?;
+                // 0910  jmp 1439h
+                m.pc = 0x1439;
+                m.tick(10, 0x1439)?;
+            },
+            0x09c5 => {
+                // 09c5  adi 1ah
+                let value: u8 = 0x1a;
+                let result = m.a as u16 + value as u16;
+                m.flags = SZP[result as u8 as usize] | (result >> 8) as u8 | if (m.a & 0xf) + (value & 0xf) > 0xf { AUX_CARRY } else { 0 };
+                m.a = result as u8;
+                m.tick(7, 0x09c7)?;
+                // 09c7  jmp 08ffh
+                m.pc = 0x08ff;
+                m.tick(10, 0x08ff)?;
+            },
+            0x1439 => {
+                // 1439  push b
+                m.push(m.bc())?;
+                m.tick(11, 0x143a)?;
+                // 143a  ldax d
+                m.a = m.read(m.de())?;
+                m.tick(7, 0x143b)?;
+                // 143b  mov m,a
+                m.write(m.hl(), m.a)?;
+                m.tick(7, 0x143c)?;
+                // 143c  inx d
+                m.set_de(m.de().wrapping_add(1));
+                m.tick(5, 0x143d)?;
+                // 143d  lxi b,0020h
+                m.set_bc(0x0020);
+                m.tick(10, 0x1440)?;
+                // 1440  dad b
+                let result = m.hl() as u32 + m.bc() as u32;
+                m.flags = (m.flags & !CARRY) | (result >> 16) as u8;
+                m.set_hl(result as u16);
+                m.tick(10, 0x1441)?;
+                // 1441  pop b
+                let value = m.pop()?;
+                m.set_bc(value);
+                m.tick(10, 0x1442)?;
+                // 1442  dcr b
+                let value = m.b;
+                let value = value.wrapping_sub(1);
+                m.flags = SZP[value as usize] | (m.flags & CARRY) | if value & 0xf != 0xf { AUX_CARRY } else { 0 };
+                m.b = value;
+                m.tick(5, 0x1443)?;
+                // 1443  jnz 1439h
+                m.pc = if m.flags & ZERO == 0 { 0x1439 } else { 0x1446 };
+                m.tick(10, m.pc)?;
+            },
+            0x1446 => {
+                // 1446  ret
+                let target = m.pop()?;
+                m.pc = target;
+                m.tick(10, target)?;
+                return Ok(());
+            },
+            _ => return Ok(()),
+        }
+    }
+}
+
+fn sub_09ca(m: &mut Machine) -> Result<(), Stop> {
+    // 09ca  lda 2067h
+    m.a = m.read(0x2067)?;
+    m.tick(13, 0x09cd)?;
+    // 09cd  rrc
+    m.flags = (m.flags & !CARRY) | (m.a & 1);
+    m.a = m.a.rotate_right(1);
+    m.tick(4, 0x09ce)?;
+    // 09ce  lxi h,20f8h
+    m.set_hl(0x20f8);
+    m.tick(10, 0x09d1)?;
+    // 09d1  rc
+    if m.flags & CARRY != 0 {
+        let target = m.pop()?;
+        m.pc = target;
+        m.tick(11, target)?;
+        return Ok(());
+    }
+    m.tick(5, 0x09d2)?;
+    // 09d2  lxi h,20fch
+    m.set_hl(0x20fc);
+    m.tick(10, 0x09d5)?;
+    // 09d5  ret
+    let target = m.pop()?;
+    m.pc = target;
+    m.tick(10, target)?;
+    Ok(())
+}
+
+fn sub_09d6(m: &mut Machine) -> Result<(), Stop> {
+    loop {
+        match m.pc {
+            0x09d6 => {
+                // 09d6  lxi h,2402h
+                m.set_hl(0x2402);
+                m.tick(10, 0x09d9)?;
+                m.pc = 0x09d9;
+            },
+            0x09d9 => {
+                // 09d9  mvi m,00h
+                m.write(m.hl(), 0x00)?;
+                m.tick(10, 0x09db)?;
+                // 09db  inx h
+                m.set_hl(m.hl().wrapping_add(1));
+                m.tick(5, 0x09dc)?;
+                // 09dc  mov a,l
+                m.a = m.l;
+                m.tick(5, 0x09dd)?;
+                // 09dd  ani 1fh
+                let value: u8 = 0x1f;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(7, 0x09df)?;
+                // 09df  cpi 1ch
+                let value: u8 = 0x1c;
+                let result = (m.a as u16).wrapping_sub(value as u16);
+                m.flags = SZP[result as u8 as usize] | (result > 0xff) as u8 | if (m.a & 0xf).wrapping_sub(value & 0xf) > 0xf { AUX_CARRY } else { 0 };
+                m.tick(7, 0x09e1)?;
+                // 09e1  jc 09e8h
+                m.pc = if m.flags & CARRY != 0 { 0x09e8 } else { 0x09e4 };
+                m.tick(10, m.pc)?;
+            },
+            0x09e4 => {
+                // 09e4  lxi d,0006h
+                m.set_de(0x0006);
+                m.tick(10, 0x09e7)?;
+                // 09e7  dad d
+                let result = m.hl() as u32 + m.de() as u32;
+                m.flags = (m.flags & !CARRY) | (result >> 16) as u8;
+                m.set_hl(result as u16);
+                m.tick(10, 0x09e8)?;
+                m.pc = 0x09e8;
+            },
+            0x09e8 => {
+                // 09e8  mov a,h
+                m.a = m.h;
+                m.tick(5, 0x09e9)?;
+                // 09e9  cpi 40h
+                let value: u8 = 0x40;
+                let result = (m.a as u16).wrapping_sub(value as u16);
+                m.flags = SZP[result as u8 as usize] | (result > 0xff) as u8 | if (m.a & 0xf).wrapping_sub(value & 0xf) > 0xf { AUX_CARRY } else { 0 };
+                m.tick(7, 0x09eb)?;
+                // 09eb  jc 09d9h
+                m.pc = if m.flags & CARRY != 0 { 0x09d9 } else { 0x09ee };
+                m.tick(10, m.pc)?;
+            },
+            0x09ee => {
+                // 09ee  ret
+                let target = m.pop()?;
+                m.pc = target;
+                m.tick(10, target)?;
+                return Ok(());
+            },
+            _ => return Ok(()),
+        }
+    }
+}
+
+fn sub_0a3c(m: &mut Machine) -> Result<(), Stop> {
+    loop {
+        match m.pc {
+            0x0a3c => {
+                // 0a3c  call 0a59h
+                m.push(0x0a3f)?;
+                m.pc = 0x0a59;
+                m.tick(17, 0x0a59)?;
+                m.call(0x0a3f)?;
+                // 0a3f  jnz 0a52h
+                m.pc = if m.flags & ZERO == 0 { 0x0a52 } else { 0x0a42 };
+                m.tick(10, m.pc)?;
+            },
+            0x0a42 => {
+                // 0a42  mvi a,30h
+                m.a = 0x30;
+                m.tick(7, 0x0a44)?;
+                // 0a44  sta 20c0h
+                m.write(0x20c0, m.a)?;
+                m.tick(13, 0x0a47)?;
+                m.pc = 0x0a47;
+            },
+            0x0a47 => {
+                // 0a47  lda 20c0h
+                m.a = m.read(0x20c0)?;
+                m.tick(13, 0x0a4a)?;
+                // 0a4a  ana a
+                let value = m.a;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(4, 0x0a4b)?;
+                // 0a4b  rz
+                if m.flags & ZERO != 0 {
+                    let target = m.pop()?;
+                    m.pc = target;
+                    m.tick(11, target)?;
+                    return Ok(());
+                }
+                m.tick(5, 0x0a4c)?;
+                // 0a4c  call 0a59h
+                m.push(0x0a4f)?;
+                m.pc = 0x0a59;
+                m.tick(17, 0x0a59)?;
+                m.call(0x0a4f)?;
+                // 0a4f  jz 0a47h
+                m.pc = if m.flags & ZERO != 0 { 0x0a47 } else { 0x0a52 };
+                m.tick(10, m.pc)?;
+            },
+            0x0a52 => {
+                // 0a52  call 0a59h
+                m.push(0x0a55)?;
+                m.pc = 0x0a59;
+                m.tick(17, 0x0a59)?;
+                m.call(0x0a55)?;
+                // 0a55  jnz 0a52h
+                m.pc = if m.flags & ZERO == 0 { 0x0a52 } else { 0x0a58 };
+                m.tick(10, m.pc)?;
+            },
+            0x0a58 => {
+                // 0a58  ret
+                let target = m.pop()?;
+                m.pc = target;
+                m.tick(10, target)?;
+                return Ok(());
+            },
+            _ => return Ok(()),
+        }
+    }
+}
+
+fn sub_0a59(m: &mut Machine) -> Result<(), Stop> {
+    // 0a59  lda 2015h
+    m.a = m.read(0x2015)?;
+    m.tick(13, 0x0a5c)?;
+    // 0a5c  cpi 0ffh
+    let value: u8 = 0xff;
+    let result = (m.a as u16).wrapping_sub(value as u16);
+    m.flags = SZP[result as u8 as usize] | (result > 0xff) as u8 | if (m.a & 0xf).wrapping_sub(value & 0xf) > 0xf { AUX_CARRY } else { 0 };
+    m.tick(7, 0x0a5e)?;
+    // 0a5e  ret
+    let target = m.pop()?;
+    m.pc = target;
+    m.tick(10, target)?;
+    Ok(())
+}
+
+fn sub_0a5f(m: &mut Machine) -> Result<(), Stop> {
+    loop {
+        match m.pc {
+            0x0a5f => {
+                // 0a5f  lda 20efh
+                m.a = m.read(0x20ef)?;
+                m.tick(13, 0x0a62)?;
+                // 0a62  ana a
+                let value = m.a;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(4, 0x0a63)?;
+                // 0a63  jz 0a7ch
+                m.pc = if m.flags & ZERO != 0 { 0x0a7c } else { 0x0a66 };
+                m.tick(10, m.pc)?;
+            },
+            0x0a66 => {
+                // 0a66  mov c,b
+                m.c = m.b;
+                m.tick(5, 0x0a67)?;
+                // 0a67  mvi b,08h
+                m.b = 0x08;
+                m.tick(7, 0x0a69)?;
+                // 0a69  call 18fah
+                m.push(0x0a6c)?;
+                m.pc = 0x18fa;
+                m.tick(17, 0x18fa)?;
+                m.call(0x0a6c)?;
+                // 0a6c  mov b,c
+                m.b = m.c;
+                m.tick(5, 0x0a6d)?;
+                // 0a6d  mov a,b
+                m.a = m.b;
+                m.tick(5, 0x0a6e)?;
+                // 0a6e  call 097ch
+                m.push(0x0a71)?;
+                m.pc = 0x097c;
+                m.tick(17, 0x097c)?;
+                m.call(0x0a71)?;
+                // 0a71  mov a,m
+                m.a = m.read(m.hl())?;
+                m.tick(7, 0x0a72)?;
+                // 0a72  lxi h,20f3h
+                m.set_hl(0x20f3);
+                m.tick(10, 0x0a75)?;
+                // 0a75  mvi m,00h
+                m.write(m.hl(), 0x00)?;
+                m.tick(10, 0x0a77)?;
+                // 0a77  dcx h
+                m.set_hl(m.hl().wrapping_sub(1));
+                m.tick(5, 0x0a78)?;
+                // 0a78  mov m,a
+                m.write(m.hl(), m.a)?;
+                m.tick(7, 0x0a79)?;
+                // 0a79  dcx h
+                m.set_hl(m.hl().wrapping_sub(1));
+                m.tick(5, 0x0a7a)?;
+                // 0a7a  mvi m,01h
+                m.write(m.hl(), 0x01)?;
+                m.tick(10, 0x0a7c)?;
+                m.pc = 0x0a7c;
+            },
+            0x0a7c => {
+                // 0a7c  lxi h,2062h
+                m.set_hl(0x2062);
+                m.tick(10, 0x0a7f)?;
+                // 0a7f  ret
+                let target = m.pop()?;
+                m.pc = target;
+                m.tick(10, target)?;
+                return Ok(());
+            },
+            _ => return Ok(()),
+        }
+    }
+}
+
+fn sub_0a80(m: &mut Machine) -> Result<(), Stop> {
+    loop {
+        match m.pc {
+            0x0a80 => {
+                // 0a80  mvi a,02h
+                m.a = 0x02;
+                m.tick(7, 0x0a82)?;
+                // 0a82  sta 20c1h
+                m.write(0x20c1, m.a)?;
+                m.tick(13, 0x0a85)?;
+                m.pc = 0x0a85;
+            },
+            0x0a85 => {
+                // 0a85  out 06h
+                m.output(0x06, m.a)?;
+                m.tick(10, 0x0a87)?;
+                // 0a87  lda 20cbh
+                m.a = m.read(0x20cb)?;
+                m.tick(13, 0x0a8a)?;
+                // 0a8a  ana a
+                let value = m.a;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(4, 0x0a8b)?;
+                // 0a8b  jz 0a85h
+                m.pc = if m.flags & ZERO != 0 { 0x0a85 } else { 0x0a8e };
+                m.tick(10, m.pc)?;
+            },
+            0x0a8e => {
+                // 0a8e  xra a
+                let value = m.a;
+                m.a ^= value;
+                m.flags = SZP[m.a as usize] | if m.a > 0xf { AUX_CARRY } else { 0 };
+                m.tick(4, 0x0a8f)?;
+                // 0a8f  sta 20c1h
+                m.write(0x20c1, m.a)?;
+                m.tick(13, 0x0a92)?;
+                // 0a92  ret
+                let target = m.pop()?;
+                m.pc = target;
+                m.tick(10, target)?;
+                return Ok(());
+            },
+            _ => return Ok(()),
+        }
+    }
+}
+
+fn sub_0a93(m: &mut Machine) -> Result<(), Stop> {
+    loop {
+        match m.pc {
+            0x0a93 => {
+                // 0a93  push d
+                m.push(m.de())?;
+                m.tick(11, 0x0a94)?;
+                // 0a94  ldax d
+                m.a = m.read(m.de())?;
+                m.tick(7, 0x0a95)?;
+                // 0a95  call 08ffh
+                m.push(0x0a98)?;
+                m.pc = 0x08ff;
+                m.tick(17, 0x08ff)?;
+                m.call(0x0a98)?;
+                // 0a98  pop d
+                let value = m.pop()?;
+                m.set_de(value);
+                m.tick(10, 0x0a99)?;
+                // 0a99  mvi a,07h
+                m.a = 0x07;
+                m.tick(7, 0x0a9b)?;
+                // 0a9b  sta 20c0h
+                m.write(0x20c0, m.a)?;
+                m.tick(13, 0x0a9e)?;
+                m.pc = 0x0a9e;
+            },
+            0x0a9e => {
+                // 0a9e  lda 20c0h
+                m.a = m.read(0x20c0)?;
+                m.tick(13, 0x0aa1)?;
+                // 0aa1  dcr a
+                let value = m.a;
+                let value = value.wrapping_sub(1);
+                m.flags = SZP[value as usize] | (m.flags & CARRY) | if value & 0xf != 0xf { AUX_CARRY } else { 0 };
+                m.a = value;
+                m.tick(5, 0x0aa2)?;
+                // 0aa2  jnz 0a9eh
+                m.pc = if m.flags & ZERO == 0 { 0x0a9e } else { 0x0aa5 };
+                m.tick(10, m.pc)?;
+            },
+            0x0aa5 => {
+                // 0aa5  inx d
+                m.set_de(m.de().wrapping_add(1));
+                m.tick(5, 0x0aa6)?;
+                // 0aa6  dcr c
+                let value = m.c;
+                let value = value.wrapping_sub(1);
+                m.flags = SZP[value as usize] | (m.flags & CARRY) | if value & 0xf != 0xf { AUX_CARRY } else { 0 };
+                m.c = value;
+                m.tick(5, 0x0aa7)?;
+                // 0aa7  jnz 0a93h
+                m.pc = if m.flags & ZERO == 0 { 0x0a93 } else { 0x0aaa };
+                m.tick(10, m.pc)?;
+            },
+            0x0aaa => {
+                // 0aaa  ret
+                let target = m.pop()?;
+                m.pc = target;
+                m.tick(10, target)?;
+                return Ok(());
+            },
+            _ => return Ok(()),
+        }
+    }
+}
+
+fn sub_0ab1(m: &mut Machine) -> Result<(), Stop> {
+    loop {
+        match m.pc {
+            0x0ab1 => {
+                // 0ab1  mvi a,40h
+                m.a = 0x40;
+                m.tick(7, 0x0ab3)?;
+                // 0ab3  jmp 0ad7h
+                m.pc = 0x0ad7;
+                m.tick(10, 0x0ad7)?;
+            },
+            0x0ad7 => {
+                // 0ad7  sta 20c0h
+                m.write(0x20c0, m.a)?;
+                m.tick(13, 0x0ada)?;
+                m.pc = 0x0ada;
+            },
+            0x0ada => {
+                // 0ada  lda 20c0h
+                m.a = m.read(0x20c0)?;
+                m.tick(13, 0x0add)?;
+                // 0add  ana a
+                let value = m.a;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(4, 0x0ade)?;
+                // 0ade  jnz 0adah
+                m.pc = if m.flags & ZERO == 0 { 0x0ada } else { 0x0ae1 };
+                m.tick(10, m.pc)?;
+            },
+            0x0ae1 => {
+                // 0ae1  ret
+                let target = m.pop()?;
+                m.pc = target;
+                m.tick(10, target)?;
+                return Ok(());
+            },
+            _ => return Ok(()),
+        }
+    }
+}
+
+fn sub_0ab6(m: &mut Machine) -> Result<(), Stop> {
+    loop {
+        match m.pc {
+            0x0ab6 => {
+                // 0ab6  mvi a,80h
+                m.a = 0x80;
+                m.tick(7, 0x0ab8)?;
+                // 0ab8  jmp 0ad7h
+                m.pc = 0x0ad7;
+                m.tick(10, 0x0ad7)?;
+            },
+            0x0ad7 => {
+                // 0ad7  sta 20c0h
+                m.write(0x20c0, m.a)?;
+                m.tick(13, 0x0ada)?;
+                m.pc = 0x0ada;
+            },
+            0x0ada => {
+                // 0ada  lda 20c0h
+                m.a = m.read(0x20c0)?;
+                m.tick(13, 0x0add)?;
+                // 0add  ana a
+                let value = m.a;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(4, 0x0ade)?;
+                // 0ade  jnz 0adah
+                m.pc = if m.flags & ZERO == 0 { 0x0ada } else { 0x0ae1 };
+                m.tick(10, m.pc)?;
+            },
+            0x0ae1 => {
+                // 0ae1  ret
+                let target = m.pop()?;
+                m.pc = target;
+                m.tick(10, target)?;
+                return Ok(());
+            },
+            _ => return Ok(()),
+        }
+    }
+}
+
+fn sub_0abf(m: &mut Machine) -> Result<(), Stop> {
+    loop {
+        match m.pc {
+            0x0072 => {
+                // 0072  lda 2032h
+                m.a = m.read(0x2032)?;
+                m.tick(13, 0x0075)?;
+                // 0075  sta 2080h
+                m.write(0x2080, m.a)?;
+                m.tick(13, 0x0078)?;
+                // 0078  call 0100h
+                m.push(0x007b)?;
+                m.pc = 0x0100;
+                m.tick(17, 0x0100)?;
+                m.call(0x007b)?;
+                // 007b  call 0248h
+                m.push(0x007e)?;
+                m.pc = 0x0248;
+                m.tick(17, 0x0248)?;
+                m.call(0x007e)?;
+                // 007e  call 0913h
+                m.push(0x0081)?;
+                m.pc = 0x0913;
+                m.tick(17, 0x0913)?;
+                m.call(0x0081)?;
+                // 0081  nop
+                m.tick(4, 0x0082)?;
+                // 0082  pop h
+                let value = m.pop()?;
+                m.set_hl(value);
+                m.tick(10, 0x0083)?;
+                // 0083  pop d
+                let value = m.pop()?;
+                m.set_de(value);
+                m.tick(10, 0x0084)?;
+                // 0084  pop b
+                let value = m.pop()?;
+                m.set_bc(value);
+                m.tick(10, 0x0085)?;
+                // 0085  pop psw
+                let value = m.pop()?;
+                m.a = (value >> 8) as u8;
+                m.flags = value as u8 & (SIGN | ZERO | AUX_CARRY | PARITY | CARRY);
+                m.tick(10, 0x0086)?;
+                m.pc = 0x0086;
+            },
+            // 0086  ei
+            0x0086 => m.interpret()?,
+            0x024b => {
+                // 024b  mov a,m
+                m.a = m.read(m.hl())?;
+                m.tick(7, 0x024c)?;
+                // 024c  cpi 0ffh
+                let value: u8 = 0xff;
+                let result = (m.a as u16).wrapping_sub(value as u16);
+                m.flags = SZP[result as u8 as usize] | (result > 0xff) as u8 | if (m.a & 0xf).wrapping_sub(value & 0xf) > 0xf { AUX_CARRY } else { 0 };
+                m.tick(7, 0x024e)?;
+                // 024e  rz
+                if m.flags & ZERO != 0 {
+                    let target = m.pop()?;
+                    m.pc = target;
+                    m.tick(11, target)?;
+                    return Ok(());
+                }
+                m.tick(5, 0x024f)?;
+                // 024f  cpi 0feh
+                let value: u8 = 0xfe;
+                let result = (m.a as u16).wrapping_sub(value as u16);
+                m.flags = SZP[result as u8 as usize] | (result > 0xff) as u8 | if (m.a & 0xf).wrapping_sub(value & 0xf) > 0xf { AUX_CARRY } else { 0 };
+                m.tick(7, 0x0251)?;
+                // 0251  jz 0281h
+                m.pc = if m.flags & ZERO != 0 { 0x0281 } else { 0x0254 };
+                m.tick(10, m.pc)?;
+            },
+            0x0254 => {
+                // 0254  inx h
+                m.set_hl(m.hl().wrapping_add(1));
+                m.tick(5, 0x0255)?;
+                // 0255  mov b,m
+                m.b = m.read(m.hl())?;
+                m.tick(7, 0x0256)?;
+                // 0256  mov c,a
+                m.c = m.a;
+                m.tick(5, 0x0257)?;
+                // 0257  ora b
+                let value = m.b;
+                m.a |= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(4, 0x0258)?;
+                // 0258  mov a,c
+                m.a = m.c;
+                m.tick(5, 0x0259)?;
+                // 0259  jnz 0277h
+                m.pc = if m.flags & ZERO == 0 { 0x0277 } else { 0x025c };
+                m.tick(10, m.pc)?;
+            },
+            0x025c => {
+                // 025c  inx h
+                m.set_hl(m.hl().wrapping_add(1));
+                m.tick(5, 0x025d)?;
+                // 025d  mov a,m
+                m.a = m.read(m.hl())?;
+                m.tick(7, 0x025e)?;
+                // 025e  ana a
+                let value = m.a;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(4, 0x025f)?;
+                // 025f  jnz 0288h
+                m.pc = if m.flags & ZERO == 0 { 0x0288 } else { 0x0262 };
+                m.tick(10, m.pc)?;
+            },
+            0x0262 => {
+                // 0262  inx h
+                m.set_hl(m.hl().wrapping_add(1));
+                m.tick(5, 0x0263)?;
+                // 0263  mov e,m
+                m.e = m.read(m.hl())?;
+                m.tick(7, 0x0264)?;
+                // 0264  inx h
+                m.set_hl(m.hl().wrapping_add(1));
+                m.tick(5, 0x0265)?;
+                // 0265  mov d,m
+                m.d = m.read(m.hl())?;
+                m.tick(7, 0x0266)?;
+                // 0266  push h
+                m.push(m.hl())?;
+                m.tick(11, 0x0267)?;
+                // 0267  xchg
+                std::mem::swap(&mut m.d, &mut m.h);
+                std::mem::swap(&mut m.e, &mut m.l);
+                m.tick(4, 0x0268)?;
+                // 0268  push h
+                m.push(m.hl())?;
+                m.tick(11, 0x0269)?;
+                // 0269  lxi h,026fh
+                m.set_hl(0x026f);
+                m.tick(10, 0x026c)?;
+                // 026c  xthl
+                let value = m.pop()?;
+                let hl = m.hl();
+                m.set_hl(value);
+                m.push(hl)?;
+                m.tick(18, 0x026d)?;
+                // 026d  push d
+                m.push(m.de())?;
+                m.tick(11, 0x026e)?;
+                // 026e  pchl
+                m.pc = m.hl();
+                m.tick(5, m.pc)?;
+            },
+            0x0277 => {
+                // 0277  dcr b
+                let value = m.b;
+                let value = value.wrapping_sub(1);
+                m.flags = SZP[value as usize] | (m.flags & CARRY) | if value & 0xf != 0xf { AUX_CARRY } else { 0 };
+                m.b = value;
+                m.tick(5, 0x0278)?;
+                // 0278  inr b
+                let value = m.b;
+                let value = value.wrapping_add(1);
+                m.flags = SZP[value as usize] | (m.flags & CARRY) | if value & 0xf == 0 { AUX_CARRY } else { 0 };
+                m.b = value;
+                m.tick(5, 0x0279)?;
+                // 0279  jnz 027dh
+                m.pc = if m.flags & ZERO == 0 { 0x027d } else { 0x027c };
+                m.tick(10, m.pc)?;
+            },
+            0x027c => {
+                // 027c  dcr a
+                let value = m.a;
+                let value = value.wrapping_sub(1);
+                m.flags = SZP[value as usize] | (m.flags & CARRY) | if value & 0xf != 0xf { AUX_CARRY } else { 0 };
+                m.a = value;
+                m.tick(5, 0x027d)?;
+                m.pc = 0x027d;
+            },
+            0x027d => {
+                // 027d  dcr b
+                let value = m.b;
+                let value = value.wrapping_sub(1);
+                m.flags = SZP[value as usize] | (m.flags & CARRY) | if value & 0xf != 0xf { AUX_CARRY } else { 0 };
+                m.b = value;
+                m.tick(5, 0x027e)?;
+                // 027e  mov m,b
+                m.write(m.hl(), m.b)?;
+                m.tick(7, 0x027f)?;
+                // 027f  dcx h
+                m.set_hl(m.hl().wrapping_sub(1));
+                m.tick(5, 0x0280)?;
+                // 0280  mov m,a
+                m.write(m.hl(), m.a)?;
+                m.tick(7, 0x0281)?;
+                m.pc = 0x0281;
+            },
+            0x0281 => {
+                // 0281  lxi d,0010h
+                m.set_de(0x0010);
+                m.tick(10, 0x0284)?;
+                // 0284  dad d
+                let result = m.hl() as u32 + m.de() as u32;
+                m.flags = (m.flags & !CARRY) | (result >> 16) as u8;
+                m.set_hl(result as u16);
+                m.tick(10, 0x0285)?;
+                // 0285  jmp 024bh
+                m.pc = 0x024b;
+                m.tick(10, 0x024b)?;
+            },
+            0x0288 => {
+                // 0288  dcr m
+                let value = m.read(m.hl())?;
+                let value = value.wrapping_sub(1);
+                m.flags = SZP[value as usize] | (m.flags & CARRY) | if value & 0xf != 0xf { AUX_CARRY } else { 0 };
+                m.write(m.hl(), value)?;
+                m.tick(10, 0x0289)?;
+                // 0289  dcx h
+                m.set_hl(m.hl().wrapping_sub(1));
+                m.tick(5, 0x028a)?;
+                // 028a  dcx h
+                m.set_hl(m.hl().wrapping_sub(1));
+                m.tick(5, 0x028b)?;
+                // 028b  jmp 0281h
+                m.pc = 0x0281;
+                m.tick(10, 0x0281)?;
+            },
+            0x0aab => {
+                // 0aab  lxi h,2050h
+                m.set_hl(0x2050);
+                m.tick(10, 0x0aae)?;
+                // 0aae  jmp 024bh
+                m.pc = 0x024b;
+                m.tick(10, 0x024b)?;
+            },
+            0x0abb => {
+                // 0abb  pop h
+                let value = m.pop()?;
+                m.set_hl(value);
+                m.tick(10, 0x0abc)?;
+                // 0abc  jmp 0072h
+                m.pc = 0x0072;
+                m.tick(10, 0x0072)?;
+            },
+            0x0abf => {
+                // 0abf  lda 20c1h
+                m.a = m.read(0x20c1)?;
+                m.tick(13, 0x0ac2)?;
+                // 0ac2  rrc
+                m.flags = (m.flags & !CARRY) | (m.a & 1);
+                m.a = m.a.rotate_right(1);
+                m.tick(4, 0x0ac3)?;
+                // 0ac3  jc 0abbh
+                m.pc = if m.flags & CARRY != 0 { 0x0abb } else { 0x0ac6 };
+                m.tick(10, m.pc)?;
+            },
+            0x0ac6 => {
+                // 0ac6  rrc
+                m.flags = (m.flags & !CARRY) | (m.a & 1);
+                m.a = m.a.rotate_right(1);
+                m.tick(4, 0x0ac7)?;
+                // 0ac7  jc 1868h
+                m.pc = if m.flags & CARRY != 0 { 0x1868 } else { 0x0aca };
+                m.tick(10, m.pc)?;
+            },
+            0x0aca => {
+                // 0aca  rrc
+                m.flags = (m.flags & !CARRY) | (m.a & 1);
+                m.a = m.a.rotate_right(1);
+                m.tick(4, 0x0acb)?;
+                // 0acb  jc 0aabh
+                m.pc = if m.flags & CARRY != 0 { 0x0aab } else { 0x0ace };
+                m.tick(10, m.pc)?;
+            },
+            0x0ace => {
+                // 0ace  ret
+                let target = m.pop()?;
+                m.pc = target;
+                m.tick(10, target)?;
+                return Ok(());
+            },
+            0x15d3 => {
+                // 15d3  call 1474h
+                m.push(0x15d6)?;
+                m.pc = 0x1474;
+                m.tick(17, 0x1474)?;
+                m.call(0x15d6)?;
+                // 15d6  push h
+                m.push(m.hl())?;
+                m.tick(11, 0x15d7)?;
+                m.pc = 0x15d7;
+            },
+            0x15d7 => {
+                // 15d7  push b
+                m.push(m.bc())?;
+                m.tick(11, 0x15d8)?;
+                // 15d8  push h
+                m.push(m.hl())?;
+                m.tick(11, 0x15d9)?;
+                // 15d9  ldax d
+                m.a = m.read(m.de())?;
+                m.tick(7, 0x15da)?;
+                // 15da  out 04h
+                m.output(0x04, m.a)?;
+                m.tick(10, 0x15dc)?;
+                // 15dc  in 03h
+                m.a = m.input(0x03)?;
+                m.tick(10, 0x15de)?;
+                // 15de  mov m,a
+                m.write(m.hl(), m.a)?;
+                m.tick(7, 0x15df)?;
+                // 15df  inx h
+                m.set_hl(m.hl().wrapping_add(1));
+                m.tick(5, 0x15e0)?;
+                // 15e0  inx d
+                m.set_de(m.de().wrapping_add(1));
+                m.tick(5, 0x15e1)?;
+                // 15e1  xra a
+                let value = m.a;
+                m.a ^= value;
+                m.flags = SZP[m.a as usize] | if m.a > 0xf { AUX_CARRY } else { 0 };
+                m.tick(4, 0x15e2)?;
+                // 15e2  out 04h
+                m.output(0x04, m.a)?;
+                m.tick(10, 0x15e4)?;
+                // 15e4  in 03h
+                m.a = m.input(0x03)?;
+                m.tick(10, 0x15e6)?;
+                // 15e6  mov m,a
+                m.write(m.hl(), m.a)?;
+                m.tick(7, 0x15e7)?;
+                // 15e7  pop h
+                let value = m.pop()?;
+                m.set_hl(value);
+                m.tick(10, 0x15e8)?;
+                // 15e8  lxi b,0020h
+                m.set_bc(0x0020);
+                m.tick(10, 0x15eb)?;
+                // 15eb  dad b
+                let result = m.hl() as u32 + m.bc() as u32;
+                m.flags = (m.flags & !CARRY) | (result >> 16) as u8;
+                m.set_hl(result as u16);
+                m.tick(10, 0x15ec)?;
+                // 15ec  pop b
+                let value = m.pop()?;
+                m.set_bc(value);
+                m.tick(10, 0x15ed)?;
+                // 15ed  dcr b
+                let value = m.b;
+                let value = value.wrapping_sub(1);
+                m.flags = SZP[value as usize] | (m.flags & CARRY) | if value & 0xf != 0xf { AUX_CARRY } else { 0 };
+                m.b = value;
+                m.tick(5, 0x15ee)?;
+                // 15ee  jnz 15d7h
+                m.pc = if m.flags & ZERO == 0 { 0x15d7 } else { 0x15f1 };
+                m.tick(10, m.pc)?;
+            },
+            0x15f1 => {
+                // 15f1  pop h
+                let value = m.pop()?;
+                m.set_hl(value);
+                m.tick(10, 0x15f2)?;
+                // 15f2  ret
+                let target = m.pop()?;
+                m.pc = target;
+                m.tick(10, target)?;
+                return Ok(());
+            },
+            0x1868 => {
+                // 1868  lxi h,20c2h
+                m.set_hl(0x20c2);
+                m.tick(10, 0x186b)?;
+                // 186b  inr m
+                let value = m.read(m.hl())?;
+                let value = value.wrapping_add(1);
+                m.flags = SZP[value as usize] | (m.flags & CARRY) | if value & 0xf == 0 { AUX_CARRY } else { 0 };
+                m.write(m.hl(), value)?;
+                m.tick(10, 0x186c)?;
+                // 186c  inx h
+                m.set_hl(m.hl().wrapping_add(1));
+                m.tick(5, 0x186d)?;
+                // 186d  mov c,m
+                m.c = m.read(m.hl())?;
+                m.tick(7, 0x186e)?;
+                // 186e  call 01d9h
+                m.push(0x1871)?;
+                m.pc = 0x01d9;
+                m.tick(17, 0x01d9)?;
+                m.call(0x1871)?;
+                // 1871  mov b,a
+                m.b = m.a;
+                m.tick(5, 0x1872)?;
+                // 1872  lda 20cah
+                m.a = m.read(0x20ca)?;
+                m.tick(13, 0x1875)?;
+                // 1875  cmp b
+                let value = m.b;
+                let result = (m.a as u16).wrapping_sub(value as u16);
+                m.flags = SZP[result as u8 as usize] | (result > 0xff) as u8 | if (m.a & 0xf).wrapping_sub(value & 0xf) > 0xf { AUX_CARRY } else { 0 };
+                m.tick(4, 0x1876)?;
+                // 1876  jz 1898h
+                m.pc = if m.flags & ZERO != 0 { 0x1898 } else { 0x1879 };
+                m.tick(10, m.pc)?;
+            },
+            0x1879 => {
+                // 1879  lda 20c2h
+                m.a = m.read(0x20c2)?;
+                m.tick(13, 0x187c)?;
+                // 187c  ani 04h
+                let value: u8 = 0x04;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(7, 0x187e)?;
+                // 187e  lhld 20cch
+                m.l = m.read(0x20cc)?;
+                m.h = m.read(0x20cd)?;
+                m.tick(16, 0x1881)?;
+                // 1881  jnz 1888h
+                m.pc = if m.flags & ZERO == 0 { 0x1888 } else { 0x1884 };
+                m.tick(10, m.pc)?;
+            },
+            0x1884 => {
+                // 1884  lxi d,0030h
+                m.set_de(0x0030);
+                m.tick(10, 0x1887)?;
+                // 1887  dad d
+                let result = m.hl() as u32 + m.de() as u32;
+                m.flags = (m.flags & !CARRY) | (result >> 16) as u8;
+                m.set_hl(result as u16);
+                m.tick(10, 0x1888)?;
+                m.pc = 0x1888;
+            },
+            0x1888 => {
+                // 1888  shld 20c7h
+                m.write(0x20c7, m.l)?;
+                m.write(0x20c8, m.h)?;
+                m.tick(16, 0x188b)?;
+                // 188b  lxi h,20c5h
+                m.set_hl(0x20c5);
+                m.tick(10, 0x188e)?;
+                // 188e  call 1a3bh
+                m.push(0x1891)?;
+                m.pc = 0x1a3b;
+                m.tick(17, 0x1a3b)?;
+                m.call(0x1891)?;
+                // 1891  xchg
+                std::mem::swap(&mut m.d, &mut m.h);
+                std::mem::swap(&mut m.e, &mut m.l);
+                m.tick(4, 0x1892)?;
+                // 1892  jmp 15d3h
+                m.pc = 0x15d3;
+                m.tick(10, 0x15d3)?;
+            },
+            0x1898 => {
+                // 1898  mvi a,01h
+                m.a = 0x01;
+                m.tick(7, 0x189a)?;
+                // 189a  sta 20cbh
+                m.write(0x20cb, m.a)?;
+                m.tick(13, 0x189d)?;
+                // 189d  ret
+                let target = m.pop()?;
+                m.pc = target;
+                m.tick(10, target)?;
+                return Ok(());
+            },
+            _ => return Ok(()),
+        }
+    }
+}
+
+fn sub_0acf(m: &mut Machine) -> Result<(), Stop> {
+    loop {
+        match m.pc {
+            0x0a93 => {
+                // 0a93  push d
+                m.push(m.de())?;
+                m.tick(11, 0x0a94)?;
+                // 0a94  ldax d
+                m.a = m.read(m.de())?;
+                m.tick(7, 0x0a95)?;
+                // 0a95  call 08ffh
+                m.push(0x0a98)?;
+                m.pc = 0x08ff;
+                m.tick(17, 0x08ff)?;
+                m.call(0x0a98)?;
+                // 0a98  pop d
+                let value = m.pop()?;
+                m.set_de(value);
+                m.tick(10, 0x0a99)?;
+                // 0a99  mvi a,07h
+                m.a = 0x07;
+                m.tick(7, 0x0a9b)?;
+                // 0a9b  sta 20c0h
+                m.write(0x20c0, m.a)?;
+                m.tick(13, 0x0a9e)?;
+                m.pc = 0x0a9e;
+            },
+            0x0a9e => {
+                // 0a9e  lda 20c0h
+                m.a = m.read(0x20c0)?;
+                m.tick(13, 0x0aa1)?;
+                // 0aa1  dcr a
+                let value = m.a;
+                let value = value.wrapping_sub(1);
+                m.flags = SZP[value as usize] | (m.flags & CARRY) | if value & 0xf != 0xf { AUX_CARRY } else { 0 };
+                m.a = value;
+                m.tick(5, 0x0aa2)?;
+                // 0aa2  jnz 0a9eh
+                m.pc = if m.flags & ZERO == 0 { 0x0a9e } else { 0x0aa5 };
+                m.tick(10, m.pc)?;
+            },
+            0x0aa5 => {
+                // 0aa5  inx d
+                m.set_de(m.de().wrapping_add(1));
+                m.tick(5, 0x0aa6)?;
+                // 0aa6  dcr c
+                let value = m.c;
+                let value = value.wrapping_sub(1);
+                m.flags = SZP[value as usize] | (m.flags & CARRY) | if value & 0xf != 0xf { AUX_CARRY } else { 0 };
+                m.c = value;
+                m.tick(5, 0x0aa7)?;
+                // 0aa7  jnz 0a93h
+                m.pc = if m.flags & ZERO == 0 { 0x0a93 } else { 0x0aaa };
+                m.tick(10, m.pc)?;
+            },
+            0x0aaa => {
+                // 0aaa  ret
+                let target = m.pop()?;
+                m.pc = target;
+                m.tick(10, target)?;
+                return Ok(());
+            },
+            0x0acf => {
+                // 0acf  lxi h,2b14h
+                m.set_hl(0x2b14);
+                m.tick(10, 0x0ad2)?;
+                // 0ad2  mvi c,0fh
+                m.c = 0x0f;
+                m.tick(7, 0x0ad4)?;
+                // 0ad4  jmp 0a93h
+                m.pc = 0x0a93;
+                m.tick(10, 0x0a93)?;
+            },
+            _ => return Ok(()),
+        }
+    }
+}
+
+fn sub_0ae2(m: &mut Machine) -> Result<(), Stop> {
+    loop {
+        match m.pc {
+            0x0ae2 => {
+                // 0ae2  lxi h,20c2h
+                m.set_hl(0x20c2);
+                m.tick(10, 0x0ae5)?;
+                // 0ae5  mvi b,0ch
+                m.b = 0x0c;
+                m.tick(7, 0x0ae7)?;
+                // 0ae7  jmp 1a32h
+                m.pc = 0x1a32;
+                m.tick(10, 0x1a32)?;
+            },
+            0x1a32 => {
+                // 1a32  ldax d
+                m.a = m.read(m.de())?;
+                m.tick(7, 0x1a33)?;
+                // 1a33  mov m,a
+                m.write(m.hl(), m.a)?;
+                m.tick(7, 0x1a34)?;
+                // 1a34  inx h
+                m.set_hl(m.hl().wrapping_add(1));
+                m.tick(5, 0x1a35)?;
+                // 1a35  inx d
+                m.set_de(m.de().wrapping_add(1));
+                m.tick(5, 0x1a36)?;
+                // 1a36  dcr b
+                let value = m.b;
+                let value = value.wrapping_sub(1);
+                m.flags = SZP[value as usize] | (m.flags & CARRY) | if value & 0xf != 0xf { AUX_CARRY } else { 0 };
+                m.b = value;
+                m.tick(5, 0x1a37)?;
+                // 1a37  jnz 1a32h
+                m.pc = if m.flags & ZERO == 0 { 0x1a32 } else { 0x1a3a };
+                m.tick(10, m.pc)?;
+            },
+            0x1a3a => {
+                // 1a3a  ret
+                let target = m.pop()?;
+                m.pc = target;
+                m.tick(10, target)?;
+                return Ok(());
+            },
+            _ => return Ok(()),
+        }
+    }
+}
+
+fn sub_0bf1(m: &mut Machine) -> Result<(), Stop> {
+    loop {
+        match m.pc {
+            0x08f3 => {
+                // 08f3  ldax d
+                m.a = m.read(m.de())?;
+                m.tick(7, 0x08f4)?;
+                // 08f4  push d
+                m.push(m.de())?;
+                m.tick(11, 0x08f5)?;
+                // 08f5  call 08ffh
+                m.push(0x08f8)?;
+                m.pc = 0x08ff;
+                m.tick(17, 0x08ff)?;
+                m.call(0x08f8)?;
+                // 08f8  pop d
+                let value = m.pop()?;
+                m.set_de(value);
+                m.tick(10, 0x08f9)?;
+                // 08f9  inx d
+                m.set_de(m.de().wrapping_add(1));
+                m.tick(5, 0x08fa)?;
+                // 08fa  dcr c
+                let value = m.c;
+                let value = value.wrapping_sub(1);
+                m.flags = SZP[value as usize] | (m.flags & CARRY) | if value & 0xf != 0xf { AUX_CARRY } else { 0 };
+                m.c = value;
+                m.tick(5, 0x08fb)?;
+                // 08fb  jnz 08f3h
+                m.pc = if m.flags & ZERO == 0 { 0x08f3 } else { 0x08fe };
+                m.tick(10, m.pc)?;
+            },
+            0x08fe => {
+                // 08fe  ret
+                let target = m.pop()?;
+                m.pc = target;
+                m.tick(10, target)?;
+                return Ok(());
+            },
+            0x0bf1 => {
+                // 0bf1  call 190ah
+                m.push(0x0bf4)?;
+                m.pc = 0x190a;
+                m.tick(17, 0x190a)?;
+                m.call(0x0bf4)?;
+                // 0bf4  jmp 199ah
+                m.pc = 0x199a;
+                m.tick(10, 0x199a)?;
+            },
+            0x199a => {
+                // 199a  lda 201eh
+                m.a = m.read(0x201e)?;
+                m.tick(13, 0x199d)?;
+                // 199d  ana a
+                let value = m.a;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(4, 0x199e)?;
+                // 199e  jnz 19ach
+                m.pc = if m.flags & ZERO == 0 { 0x19ac } else { 0x19a1 };
+                m.tick(10, m.pc)?;
+            },
+            0x19a1 => {
+                // 19a1  in 01h
+                m.a = m.input(0x01)?;
+                m.tick(10, 0x19a3)?;
+                // 19a3  ani 76h
+                let value: u8 = 0x76;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(7, 0x19a5)?;
+                // 19a5  sui 72h
+                let value: u8 = 0x72;
+                let result = (m.a as u16).wrapping_sub(value as u16);
+                m.flags = SZP[result as u8 as usize] | (result > 0xff) as u8 | if (m.a & 0xf).wrapping_sub(value & 0xf) > 0xf { AUX_CARRY } else { 0 };
+                m.a = result as u8;
+                m.tick(7, 0x19a7)?;
+                // 19a7  rnz
+                if m.flags & ZERO == 0 {
+                    let target = m.pop()?;
+                    m.pc = target;
+                    m.tick(11, target)?;
+                    return Ok(());
+                }
+                m.tick(5, 0x19a8)?;
+                // 19a8  inr a
+                let value = m.a;
+                let value = value.wrapping_add(1);
+                m.flags = SZP[value as usize] | (m.flags & CARRY) | if value & 0xf == 0 { AUX_CARRY } else { 0 };
+                m.a = value;
+                m.tick(5, 0x19a9)?;
+                // 19a9  sta 201eh
+                m.write(0x201e, m.a)?;
+                m.tick(13, 0x19ac)?;
+                m.pc = 0x19ac;
+            },
+            0x19ac => {
+                // 19ac  in 01h
+                m.a = m.input(0x01)?;
+                m.tick(10, 0x19ae)?;
+                // 19ae  ani 76h
+                let value: u8 = 0x76;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(7, 0x19b0)?;
+                // 19b0  cpi 34h
+                let value: u8 = 0x34;
+                let result = (m.a as u16).wrapping_sub(value as u16);
+                m.flags = SZP[result as u8 as usize] | (result > 0xff) as u8 | if (m.a & 0xf).wrapping_sub(value & 0xf) > 0xf { AUX_CARRY } else { 0 };
+                m.tick(7, 0x19b2)?;
+                // 19b2  rnz
+                if m.flags & ZERO == 0 {
+                    let target = m.pop()?;
+                    m.pc = target;
+                    m.tick(11, target)?;
+                    return Ok(());
+                }
+                m.tick(5, 0x19b3)?;
+                // 19b3  lxi h,2e1bh
+                m.set_hl(0x2e1b);
+                m.tick(10, 0x19b6)?;
+                // 19b6  lxi d,0bf7h
+                m.set_de(0x0bf7);
+                m.tick(10, 0x19b9)?;
+                // 19b9  mvi c,09h
+                m.c = 0x09;
+                m.tick(7, 0x19bb)?;
+                // 19bb  jmp 08f3h
+                m.pc = 0x08f3;
+                m.tick(10, 0x08f3)?;
+            },
+            _ => return Ok(()),
+        }
+    }
+}
+
+fn sub_1424(m: &mut Machine) -> Result<(), Stop> {
+    loop {
+        match m.pc {
+            0x1424 => {
+                // 1424  call 1474h
+                m.push(0x1427)?;
+                m.pc = 0x1474;
+                m.tick(17, 0x1474)?;
+                m.call(0x1427)?;
+                m.pc = 0x1427;
+            },
+            0x1427 => {
+                // 1427  push b
+                m.push(m.bc())?;
+                m.tick(11, 0x1428)?;
+                // 1428  push h
+                m.push(m.hl())?;
+                m.tick(11, 0x1429)?;
+                // 1429  xra a
+                let value = m.a;
+                m.a ^= value;
+                m.flags = SZP[m.a as usize] | if m.a > 0xf { AUX_CARRY } else { 0 };
+                m.tick(4, 0x142a)?;
+                // 142a  mov m,a
+                m.write(m.hl(), m.a)?;
+                m.tick(7, 0x142b)?;
+                // 142b  inx h
+                m.set_hl(m.hl().wrapping_add(1));
+                m.tick(5, 0x142c)?;
+                // 142c  mov m,a
+                m.write(m.hl(), m.a)?;
+                m.tick(7, 0x142d)?;
+                // 142d  inx h
+                m.set_hl(m.hl().wrapping_add(1));
+                m.tick(5, 0x142e)?;
+                // 142e  pop h
+                let value = m.pop()?;
+                m.set_hl(value);
+                m.tick(10, 0x142f)?;
+                // 142f  lxi b,0020h
+                m.set_bc(0x0020);
+                m.tick(10, 0x1432)?;
+                // 1432  dad b
+                let result = m.hl() as u32 + m.bc() as u32;
+                m.flags = (m.flags & !CARRY) | (result >> 16) as u8;
+                m.set_hl(result as u16);
+                m.tick(10, 0x1433)?;
+                // 1433  pop b
+                let value = m.pop()?;
+                m.set_bc(value);
+                m.tick(10, 0x1434)?;
+                // 1434  dcr b
+                let value = m.b;
+                let value = value.wrapping_sub(1);
+                m.flags = SZP[value as usize] | (m.flags & CARRY) | if value & 0xf != 0xf { AUX_CARRY } else { 0 };
+                m.b = value;
+                m.tick(5, 0x1435)?;
+                // 1435  jnz 1427h
+                m.pc = if m.flags & ZERO == 0 { 0x1427 } else { 0x1438 };
+                m.tick(10, m.pc)?;
+            },
+            0x1438 => {
+                // 1438  ret
+                let target = m.pop()?;
+                m.pc = target;
+                m.tick(10, target)?;
+                return Ok(());
+            },
+            _ => return Ok(()),
+        }
+    }
+}
+
+fn sub_1439(m: &mut Machine) -> Result<(), Stop> {
+    loop {
+        match m.pc {
+            0x1439 => {
+                // 1439  push b
+                m.push(m.bc())?;
+                m.tick(11, 0x143a)?;
+                // 143a  ldax d
+                m.a = m.read(m.de())?;
+                m.tick(7, 0x143b)?;
+                // 143b  mov m,a
+                m.write(m.hl(), m.a)?;
+                m.tick(7, 0x143c)?;
+                // 143c  inx d
+                m.set_de(m.de().wrapping_add(1));
+                m.tick(5, 0x143d)?;
+                // 143d  lxi b,0020h
+                m.set_bc(0x0020);
+                m.tick(10, 0x1440)?;
+                // 1440  dad b
+                let result = m.hl() as u32 + m.bc() as u32;
+                m.flags = (m.flags & !CARRY) | (result >> 16) as u8;
+                m.set_hl(result as u16);
+                m.tick(10, 0x1441)?;
+                // 1441  pop b
+                let value = m.pop()?;
+                m.set_bc(value);
+                m.tick(10, 0x1442)?;
+                // 1442  dcr b
+                let value = m.b;
+                let value = value.wrapping_sub(1);
+                m.flags = SZP[value as usize] | (m.flags & CARRY) | if value & 0xf != 0xf { AUX_CARRY } else { 0 };
+                m.b = value;
+                m.tick(5, 0x1443)?;
+                // 1443  jnz 1439h
+                m.pc = if m.flags & ZERO == 0 { 0x1439 } else { 0x1446 };
+                m.tick(10, m.pc)?;
+            },
+            0x1446 => {
+                // 1446  ret
+                let target = m.pop()?;
+                m.pc = target;
+                m.tick(10, target)?;
+                return Ok(());
+            },
+            _ => return Ok(()),
+        }
+    }
+}
+
+fn sub_1474(m: &mut Machine) -> Result<(), Stop> {
+    loop {
+        match m.pc {
+            0x1474 => {
+                // 1474  mov a,l
+                m.a = m.l;
+                m.tick(5, 0x1475)?;
+                // 1475  ani 07h
+                let value: u8 = 0x07;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(7, 0x1477)?;
+                // 1477  out 02h
+                m.output(0x02, m.a)?;
+                m.tick(10, 0x1479)?;
+                // 1479  jmp 1a47h
+                m.pc = 0x1a47;
+                m.tick(10, 0x1a47)?;
+            },
+            0x1a47 => {
+                // 1a47  push b
+                m.push(m.bc())?;
+                m.tick(11, 0x1a48)?;
+                // 1a48  mvi b,03h
+                m.b = 0x03;
+                m.tick(7, 0x1a4a)?;
+                m.pc = 0x1a4a;
+            },
+            0x1a4a => {
+                // 1a4a  mov a,h
+                m.a = m.h;
+                m.tick(5, 0x1a4b)?;
+                // 1a4b  rar
+                let carry = m.flags & CARRY;
+                m.flags = (m.flags & !CARRY) | (m.a & 1);
+                m.a = (m.a >> 1) | (carry << 7);
+                m.tick(4, 0x1a4c)?;
+                // 1a4c  mov h,a
+                m.h = m.a;
+                m.tick(5, 0x1a4d)?;
+                // 1a4d  mov a,l
+                m.a = m.l;
+                m.tick(5, 0x1a4e)?;
+                // 1a4e  rar
+                let carry = m.flags & CARRY;
+                m.flags = (m.flags & !CARRY) | (m.a & 1);
+                m.a = (m.a >> 1) | (carry << 7);
+                m.tick(4, 0x1a4f)?;
+                // 1a4f  mov l,a
+                m.l = m.a;
+                m.tick(5, 0x1a50)?;
+                // 1a50  dcr b
+                let value = m.b;
+                let value = value.wrapping_sub(1);
+                m.flags = SZP[value as usize] | (m.flags & CARRY) | if value & 0xf != 0xf { AUX_CARRY } else { 0 };
+                m.b = value;
+                m.tick(5, 0x1a51)?;
+                // 1a51  jnz 1a4ah
+                m.pc = if m.flags & ZERO == 0 { 0x1a4a } else { 0x1a54 };
+                m.tick(10, m.pc)?;
+            },
+            0x1a54 => {
+                // 1a54  mov a,h
+                m.a = m.h;
+                m.tick(5, 0x1a55)?;
+                // 1a55  ani 3fh
+                let value: u8 = 0x3f;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(7, 0x1a57)?;
+                // 1a57  ori 20h
+                let value: u8 = 0x20;
+                m.a |= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(7, 0x1a59)?;
+                // 1a59  mov h,a
+                m.h = m.a;
+                m.tick(5, 0x1a5a)?;
+                // 1a5a  pop b
+                let value = m.pop()?;
+                m.set_bc(value);
+                m.tick(10, 0x1a5b)?;
+                // 1a5b  ret
+                let target = m.pop()?;
+                m.pc = target;
+                m.tick(10, target)?;
+                return Ok(());
+            },
+            _ => return Ok(()),
+        }
+    }
+}
+
+fn sub_147c(m: &mut Machine) -> Result<(), Stop> {
+    loop {
+        match m.pc {
+            0x147c => {
+                // 147c  push b
+                m.push(m.bc())?;
+                m.tick(11, 0x147d)?;
+                // 147d  push h
+                m.push(m.hl())?;
+                m.tick(11, 0x147e)?;
+                m.pc = 0x147e;
+            },
+            0x147e => {
+                // 147e  mov a,m
+                m.a = m.read(m.hl())?;
+                m.tick(7, 0x147f)?;
+                // 147f  stax d
+                m.write(m.de(), m.a)?;
+                m.tick(7, 0x1480)?;
+                // 1480  inx d
+                m.set_de(m.de().wrapping_add(1));
+                m.tick(5, 0x1481)?;
+                // 1481  inx h
+                m.set_hl(m.hl().wrapping_add(1));
+                m.tick(5, 0x1482)?;
+                // 1482  dcr c
+                let value = m.c;
+                let value = value.wrapping_sub(1);
+                m.flags = SZP[value as usize] | (m.flags & CARRY) | if value & 0xf != 0xf { AUX_CARRY } else { 0 };
+                m.c = value;
+                m.tick(5, 0x1483)?;
+                // 1483  jnz 147eh
+                m.pc = if m.flags & ZERO == 0 { 0x147e } else { 0x1486 };
+                m.tick(10, m.pc)?;
+            },
+            0x1486 => {
+                // 1486  pop h
+                let value = m.pop()?;
+                m.set_hl(value);
+                m.tick(10, 0x1487)?;
+                // 1487  lxi b,0020h
+                m.set_bc(0x0020);
+                m.tick(10, 0x148a)?;
+                // 148a  dad b
+                let result = m.hl() as u32 + m.bc() as u32;
+                m.flags = (m.flags & !CARRY) | (result >> 16) as u8;
+                m.set_hl(result as u16);
+                m.tick(10, 0x148b)?;
+                // 148b  pop b
+                let value = m.pop()?;
+                m.set_bc(value);
+                m.tick(10, 0x148c)?;
+                // 148c  dcr b
+                let value = m.b;
+                let value = value.wrapping_sub(1);
+                m.flags = SZP[value as usize] | (m.flags & CARRY) | if value & 0xf != 0xf { AUX_CARRY } else { 0 };
+                m.b = value;
+                m.tick(5, 0x148d)?;
+                // 148d  jnz 147ch
+                m.pc = if m.flags & ZERO == 0 { 0x147c } else { 0x1490 };
+                m.tick(10, m.pc)?;
+            },
+            0x1490 => {
+                // 1490  ret
+                let target = m.pop()?;
+                m.pc = target;
+                m.tick(10, target)?;
+                return Ok(());
+            },
+            _ => return Ok(()),
+        }
+    }
+}
+
+fn sub_14cb(m: &mut Machine) -> Result<(), Stop> {
+    loop {
+        match m.pc {
+            0x14cb => {
+                // 14cb  xra a
+                let value = m.a;
+                m.a ^= value;
+                m.flags = SZP[m.a as usize] | if m.a > 0xf { AUX_CARRY } else { 0 };
+                m.tick(4, 0x14cc)?;
+                m.pc = 0x14cc;
+            },
+            0x14cc => {
+                // 14cc  push b
+                m.push(m.bc())?;
+                m.tick(11, 0x14cd)?;
+                // 14cd  mov m,a
+                m.write(m.hl(), m.a)?;
+                m.tick(7, 0x14ce)?;
+                // 14ce  lxi b,0020h
+                m.set_bc(0x0020);
+                m.tick(10, 0x14d1)?;
+                // 14d1  dad b
+                let result = m.hl() as u32 + m.bc() as u32;
+                m.flags = (m.flags & !CARRY) | (result >> 16) as u8;
+                m.set_hl(result as u16);
+                m.tick(10, 0x14d2)?;
+                // 14d2  pop b
+                let value = m.pop()?;
+                m.set_bc(value);
+                m.tick(10, 0x14d3)?;
+                // 14d3  dcr b
+                let value = m.b;
+                let value = value.wrapping_sub(1);
+                m.flags = SZP[value as usize] | (m.flags & CARRY) | if value & 0xf != 0xf { AUX_CARRY } else { 0 };
+                m.b = value;
+                m.tick(5, 0x14d4)?;
+                // 14d4  jnz 14cch
+                m.pc = if m.flags & ZERO == 0 { 0x14cc } else { 0x14d7 };
+                m.tick(10, m.pc)?;
+            },
+            0x14d7 => {
+                // 14d7  ret
+                let target = m.pop()?;
+                m.pc = target;
+                m.tick(10, target)?;
+                return Ok(());
+            },
+            _ => return Ok(()),
+        }
+    }
+}
+
+fn sub_14d8(m: &mut Machine) -> Result<(), Stop> {
+    loop {
+        match m.pc {
+            0x14d8 => {
+                // 14d8  lda 2025h
+                m.a = m.read(0x2025)?;
+                m.tick(13, 0x14db)?;
+                // 14db  cpi 05h
+                let value: u8 = 0x05;
+                let result = (m.a as u16).wrapping_sub(value as u16);
+                m.flags = SZP[result as u8 as usize] | (result > 0xff) as u8 | if (m.a & 0xf).wrapping_sub(value & 0xf) > 0xf { AUX_CARRY } else { 0 };
+                m.tick(7, 0x14dd)?;
+                // 14dd  rz
+                if m.flags & ZERO != 0 {
+                    let target = m.pop()?;
+                    m.pc = target;
+                    m.tick(11, target)?;
+                    return Ok(());
+                }
+                m.tick(5, 0x14de)?;
+                // 14de  cpi 02h
+                let value: u8 = 0x02;
+                let result = (m.a as u16).wrapping_sub(value as u16);
+                m.flags = SZP[result as u8 as usize] | (result > 0xff) as u8 | if (m.a & 0xf).wrapping_sub(value & 0xf) > 0xf { AUX_CARRY } else { 0 };
+                m.tick(7, 0x14e0)?;
+                // 14e0  rnz
+                if m.flags & ZERO == 0 {
+                    let target = m.pop()?;
+                    m.pc = target;
+                    m.tick(11, target)?;
+                    return Ok(());
+                }
+                m.tick(5, 0x14e1)?;
+                // 14e1  lda 2029h
+                m.a = m.read(0x2029)?;
+                m.tick(13, 0x14e4)?;
+                // 14e4  cpi 0d8h
+                let value: u8 = 0xd8;
+                let result = (m.a as u16).wrapping_sub(value as u16);
+                m.flags = SZP[result as u8 as usize] | (result > 0xff) as u8 | if (m.a & 0xf).wrapping_sub(value & 0xf) > 0xf { AUX_CARRY } else { 0 };
+                m.tick(7, 0x14e6)?;
+                // 14e6  mov b,a
+                m.b = m.a;
+                m.tick(5, 0x14e7)?;
+                // 14e7  jnc 1530h
+                m.pc = if m.flags & CARRY == 0 { 0x1530 } else { 0x14ea };
+                m.tick(10, m.pc)?;
+            },
+            0x14ea => {
+                // 14ea  lda 2002h
+                m.a = m.read(0x2002)?;
+                m.tick(13, 0x14ed)?;
+                // 14ed  ana a
+                let value = m.a;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(4, 0x14ee)?;
+                // 14ee  rz
+                if m.flags & ZERO != 0 {
+                    let target = m.pop()?;
+                    m.pc = target;
+                    m.tick(11, target)?;
+                    return Ok(());
+                }
+                m.tick(5, 0x14ef)?;
+                // 14ef  mov a,b
+                m.a = m.b;
+                m.tick(5, 0x14f0)?;
+                // 14f0  cpi 0ceh
+                let value: u8 = 0xce;
+                let result = (m.a as u16).wrapping_sub(value as u16);
+                m.flags = SZP[result as u8 as usize] | (result > 0xff) as u8 | if (m.a & 0xf).wrapping_sub(value & 0xf) > 0xf { AUX_CARRY } else { 0 };
+                m.tick(7, 0x14f2)?;
+                // 14f2  jnc 1579h
+                m.pc = if m.flags & CARRY == 0 { 0x1579 } else { 0x14f5 };
+                m.tick(10, m.pc)?;
+            },
+            0x14f5 => {
+                // 14f5  adi 06h
+                let value: u8 = 0x06;
+                let result = m.a as u16 + value as u16;
+                m.flags = SZP[result as u8 as usize] | (result >> 8) as u8 | if (m.a & 0xf) + (value & 0xf) > 0xf { AUX_CARRY } else { 0 };
+                m.a = result as u8;
+                m.tick(7, 0x14f7)?;
+                // 14f7  mov b,a
+                m.b = m.a;
+                m.tick(5, 0x14f8)?;
+                // 14f8  lda 2009h
+                m.a = m.read(0x2009)?;
+                m.tick(13, 0x14fb)?;
+                // 14fb  cpi 90h
+                let value: u8 = 0x90;
+                let result = (m.a as u16).wrapping_sub(value as u16);
+                m.flags = SZP[result as u8 as usize] | (result > 0xff) as u8 | if (m.a & 0xf).wrapping_sub(value & 0xf) > 0xf { AUX_CARRY } else { 0 };
+                m.tick(7, 0x14fd)?;
+                // 14fd  jnc 1504h
+                m.pc = if m.flags & CARRY == 0 { 0x1504 } else { 0x1500 };
+                m.tick(10, m.pc)?;
+            },
+            0x1500 => {
+                // 1500  cmp b
+                let value = m.b;
+                let result = (m.a as u16).wrapping_sub(value as u16);
+                m.flags = SZP[result as u8 as usize] | (result > 0xff) as u8 | if (m.a & 0xf).wrapping_sub(value & 0xf) > 0xf { AUX_CARRY } else { 0 };
+                m.tick(4, 0x1501)?;
+                // 1501  jnc 1530h
+                m.pc = if m.flags & CARRY == 0 { 0x1530 } else { 0x1504 };
+                m.tick(10, m.pc)?;
+            },
+            0x1504 => {
+                // 1504  mov l,b
+                m.l = m.b;
+                m.tick(5, 0x1505)?;
+                // 1505  call 1562h
+                m.push(0x1508)?;
+                m.pc = 0x1562;
+                m.tick(17, 0x1562)?;
+                m.call(0x1508)?;
+                // 1508  lda 202ah
+                m.a = m.read(0x202a)?;
+                m.tick(13, 0x150b)?;
+                // 150b  mov h,a
+                m.h = m.a;
+                m.tick(5, 0x150c)?;
+                // 150c  call 156fh
+                m.push(0x150f)?;
+                m.pc = 0x156f;
+                m.tick(17, 0x156f)?;
+                m.call(0x150f)?;
+                // 150f  shld 2064h
+                m.write(0x2064, m.l)?;
+                m.write(0x2065, m.h)?;
+                m.tick(16, 0x1512)?;
+                // 1512  mvi a,05h
+                m.a = 0x05;
+                m.tick(7, 0x1514)?;
+                // 1514  sta 2025h
+                m.write(0x2025, m.a)?;
+                m.tick(13, 0x1517)?;
+                // 1517  call 1581h
+                m.push(0x151a)?;
+                m.pc = 0x1581;
+                m.tick(17, 0x1581)?;
+                m.call(0x151a)?;
+                // 151a  mov a,m
+                m.a = m.read(m.hl())?;
+                m.tick(7, 0x151b)?;
+                // 151b  ana a
+                let value = m.a;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(4, 0x151c)?;
+                // 151c  jz 1530h
+                m.pc = if m.flags & ZERO != 0 { 0x1530 } else { 0x151f };
+                m.tick(10, m.pc)?;
+            },
+            0x151f => {
+                // 151f  mvi m,00h
+                m.write(m.hl(), 0x00)?;
+                m.tick(10, 0x1521)?;
+                // 1521  call 0a5fh
+                m.push(0x1524)?;
+                m.pc = 0x0a5f;
+                m.tick(17, 0x0a5f)?;
+                m.call(0x1524)?;
+                // 1524  call 1a3bh
+                m.push(0x1527)?;
+                m.pc = 0x1a3b;
+                m.tick(17, 0x1a3b)?;
+                m.call(0x1527)?;
+                // 1527  call 15d3h
+                m.push(0x152a)?;
+                m.pc = 0x15d3;
+                m.tick(17, 0x15d3)?;
+                m.call(0x152a)?;
+                // 152a  mvi a,10h
+                m.a = 0x10;
+                m.tick(7, 0x152c)?;
+                // 152c  sta 2003h
+                m.write(0x2003, m.a)?;
+                m.tick(13, 0x152f)?;
+                // 152f  ret
+                let target = m.pop()?;
+                m.pc = target;
+                m.tick(10, target)?;
+                return Ok(());
+            },
+            0x1530 => {
+                // 1530  mvi a,03h
+                m.a = 0x03;
+                m.tick(7, 0x1532)?;
+                // 1532  sta 2025h
+                m.write(0x2025, m.a)?;
+                m.tick(13, 0x1535)?;
+                // 1535  jmp 154ah
+                m.pc = 0x154a;
+                m.tick(10, 0x154a)?;
+            },
+            0x1545 => {
+                // 1545  mvi a,04h
+                m.a = 0x04;
+                m.tick(7, 0x1547)?;
+                // 1547  sta 2025h
+                m.write(0x2025, m.a)?;
+                m.tick(13, 0x154a)?;
+                m.pc = 0x154a;
+            },
+            0x154a => {
+                // 154a  xra a
+                let value = m.a;
+                m.a ^= value;
+                m.flags = SZP[m.a as usize] | if m.a > 0xf { AUX_CARRY } else { 0 };
+                m.tick(4, 0x154b)?;
+                // 154b  sta 2002h
+                m.write(0x2002, m.a)?;
+                m.tick(13, 0x154e)?;
+                // 154e  mvi b,0f7h
+                m.b = 0xf7;
+                m.tick(7, 0x1550)?;
+                // 1550  jmp 19dch
+                m.pc = 0x19dc;
+                m.tick(10, 0x19dc)?;
+            },
+            0x1579 => {
+                // 1579  mvi a,01h
+                m.a = 0x01;
+                m.tick(7, 0x157b)?;
+                // 157b  sta 2085h
+                m.write(0x2085, m.a)?;
+                m.tick(13, 0x157e)?;
+                // 157e  jmp 1545h
+                m.pc = 0x1545;
+                m.tick(10, 0x1545)?;
+            },
+            0x19dc => {
+                // 19dc  lda 2094h
+                m.a = m.read(0x2094)?;
+                m.tick(13, 0x19df)?;
+                // 19df  ana b
+                let value = m.b;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(4, 0x19e0)?;
+                // 19e0  sta 2094h
+                m.write(0x2094, m.a)?;
+                m.tick(13, 0x19e3)?;
+                // 19e3  out 03h
+                m.output(0x03, m.a)?;
+                m.tick(10, 0x19e5)?;
+                // 19e5  ret
+                let target = m.pop()?;
+                m.pc = target;
+                m.tick(10, target)?;
+                return Ok(());
+            },
+            _ => return Ok(()),
+        }
+    }
+}
+
+fn sub_1554(m: &mut Machine) -> Result<(), Stop> {
+    loop {
+        match m.pc {
+            0x1554 => {
+                // 1554  mvi c,00h
+                m.c = 0x00;
+                m.tick(7, 0x1556)?;
+                // 1556  cmp h
+                let value = m.h;
+                let result = (m.a as u16).wrapping_sub(value as u16);
+                m.flags = SZP[result as u8 as usize] | (result > 0xff) as u8 | if (m.a & 0xf).wrapping_sub(value & 0xf) > 0xf { AUX_CARRY } else { 0 };
+                m.tick(4, 0x1557)?;
+                // 1557  cnc 1590h
+                if m.flags & CARRY == 0 {
+                    m.push(0x155a)?;
+                    m.pc = 0x1590;
+                    m.tick(17, 0x1590)?;
+                    m.call(0x155a)?;
+                } else {
+                    m.tick(11, 0x155a)?;
+                }
+                m.pc = 0x155a;
+            },
+            0x155a => {
+                // 155a  cmp h
+                let value = m.h;
+                let result = (m.a as u16).wrapping_sub(value as u16);
+                m.flags = SZP[result as u8 as usize] | (result > 0xff) as u8 | if (m.a & 0xf).wrapping_sub(value & 0xf) > 0xf { AUX_CARRY } else { 0 };
+                m.tick(4, 0x155b)?;
+                // 155b  rnc
+                if m.flags & CARRY == 0 {
+                    let target = m.pop()?;
+                    m.pc = target;
+                    m.tick(11, target)?;
+                    return Ok(());
+                }
+                m.tick(5, 0x155c)?;
+                // 155c  adi 10h
+                let value: u8 = 0x10;
+                let result = m.a as u16 + value as u16;
+                m.flags = SZP[result as u8 as usize] | (result >> 8) as u8 | if (m.a & 0xf) + (value & 0xf) > 0xf { AUX_CARRY } else { 0 };
+                m.a = result as u8;
+                m.tick(7, 0x155e)?;
+                // 155e  inr c
+                let value = m.c;
+                let value = value.wrapping_add(1);
+                m.flags = SZP[value as usize] | (m.flags & CARRY) | if value & 0xf == 0 { AUX_CARRY } else { 0 };
+                m.c = value;
+                m.tick(5, 0x155f)?;
+                // 155f  jmp 155ah
+                m.pc = 0x155a;
+                m.tick(10, 0x155a)?;
+            },
+            _ => return Ok(()),
+        }
+    }
+}
+
+fn sub_1562(m: &mut Machine) -> Result<(), Stop> {
+    // 1562  lda 2009h
+    m.a = m.read(0x2009)?;
+    m.tick(13, 0x1565)?;
+    // 1565  mov h,l
+    m.h = m.l;
+    m.tick(5, 0x1566)?;
+    // 1566  call 1554h
+    m.push(0x1569)?;
+    m.pc = 0x1554;
+    m.tick(17, 0x1554)?;
+    m.call(0x1569)?;
+    // 1569  mov b,c
+    m.b = m.c;
+    m.tick(5, 0x156a)?;
+    // 156a  dcr b
+    let value = m.b;
+    let value = value.wrapping_sub(1);
+    m.flags = SZP[value as usize] | (m.flags & CARRY) | if value & 0xf != 0xf { AUX_CARRY } else { 0 };
+    m.b = value;
+    m.tick(5, 0x156b)?;
+    // 156b  sbi 10h
+    let value: u8 = 0x10;
+    let carry = m.flags & CARRY;
+    let result = (m.a as u16).wrapping_sub(value as u16).wrapping_sub(carry as u16);
+    m.flags = SZP[result as u8 as usize] | (result > 0xff) as u8 | if (m.a & 0xf).wrapping_sub(value & 0xf).wrapping_sub(carry) > 0xf { AUX_CARRY } else { 0 };
+    m.a = result as u8;
+    m.tick(7, 0x156d)?;
+    // 156d  mov l,a
+    m.l = m.a;
+    m.tick(5, 0x156e)?;
+    // 156e  ret
+    let target = m.pop()?;
+    m.pc = target;
+    m.tick(10, target)?;
+    Ok(())
+}
+
+fn sub_156f(m: &mut Machine) -> Result<(), Stop> {
+    // 156f  lda 200ah
+    m.a = m.read(0x200a)?;
+    m.tick(13, 0x1572)?;
+    // 1572  call 1554h
+    m.push(0x1575)?;
+    m.pc = 0x1554;
+    m.tick(17, 0x1554)?;
+    m.call(0x1575)?;
+    // 1575  sbi 10h
+    let value: u8 = 0x10;
+    let carry = m.flags & CARRY;
+    let result = (m.a as u16).wrapping_sub(value as u16).wrapping_sub(carry as u16);
+    m.flags = SZP[result as u8 as usize] | (result > 0xff) as u8 | if (m.a & 0xf).wrapping_sub(value & 0xf).wrapping_sub(carry) > 0xf { AUX_CARRY } else { 0 };
+    m.a = result as u8;
+    m.tick(7, 0x1577)?;
+    // 1577  mov h,a
+    m.h = m.a;
+    m.tick(5, 0x1578)?;
+    // 1578  ret
+    let target = m.pop()?;
+    m.pc = target;
+    m.tick(10, target)?;
+    Ok(())
+}
+
+fn sub_1581(m: &mut Machine) -> Result<(), Stop> {
+    // 1581  mov a,b
+    m.a = m.b;
+    m.tick(5, 0x1582)?;
+    // 1582  rlc
+    m.flags = (m.flags & !CARRY) | (m.a >> 7);
+    m.a = m.a.rotate_left(1);
+    m.tick(4, 0x1583)?;
+    // 1583  rlc
+    m.flags = (m.flags & !CARRY) | (m.a >> 7);
+    m.a = m.a.rotate_left(1);
+    m.tick(4, 0x1584)?;
+    // 1584  rlc
+    m.flags = (m.flags & !CARRY) | (m.a >> 7);
+    m.a = m.a.rotate_left(1);
+    m.tick(4, 0x1585)?;
+    // 1585  add b
+    let value = m.b;
+    let result = m.a as u16 + value as u16;
+    m.flags = SZP[result as u8 as usize] | (result >> 8) as u8 | if (m.a & 0xf) + (value & 0xf) > 0xf { AUX_CARRY } else { 0 };
+    m.a = result as u8;
+    m.tick(4, 0x1586)?;
+    // 1586  add b
+    let value = m.b;
+    let result = m.a as u16 + value as u16;
+    m.flags = SZP[result as u8 as usize] | (result >> 8) as u8 | if (m.a & 0xf) + (value & 0xf) > 0xf { AUX_CARRY } else { 0 };
+    m.a = result as u8;
+    m.tick(4, 0x1587)?;
+    // 1587  add b
+    let value = m.b;
+    let result = m.a as u16 + value as u16;
+    m.flags = SZP[result as u8 as usize] | (result >> 8) as u8 | if (m.a & 0xf) + (value & 0xf) > 0xf { AUX_CARRY } else { 0 };
+    m.a = result as u8;
+    m.tick(4, 0x1588)?;
+    // 1588  add c
+    let value = m.c;
+    let result = m.a as u16 + value as u16;
+    m.flags = SZP[result as u8 as usize] | (result >> 8) as u8 | if (m.a & 0xf) + (value & 0xf) > 0xf { AUX_CARRY } else { 0 };
+    m.a = result as u8;
+    m.tick(4, 0x1589)?;
+    // 1589  dcr a
+    let value = m.a;
+    let value = value.wrapping_sub(1);
+    m.flags = SZP[value as usize] | (m.flags & CARRY) | if value & 0xf != 0xf { AUX_CARRY } else { 0 };
+    m.a = value;
+    m.tick(5, 0x158a)?;
+    // 158a  mov l,a
+    m.l = m.a;
+    m.tick(5, 0x158b)?;
+    // 158b  lda 2067h
+    m.a = m.read(0x2067)?;
+    m.tick(13, 0x158e)?;
+    // 158e  mov h,a
+    m.h = m.a;
+    m.tick(5, 0x158f)?;
+    // 158f  ret
+    let target = m.pop()?;
+    m.pc = target;
+    m.tick(10, target)?;
+    Ok(())
+}
+
+fn sub_1590(m: &mut Machine) -> Result<(), Stop> {
+    loop {
+        match m.pc {
+            0x1590 => {
+                // 1590  inr c
+                let value = m.c;
+                let value = value.wrapping_add(1);
+                m.flags = SZP[value as usize] | (m.flags & CARRY) | if value & 0xf == 0 { AUX_CARRY } else { 0 };
+                m.c = value;
+                m.tick(5, 0x1591)?;
+                // 1591  adi 10h
+                let value: u8 = 0x10;
+                let result = m.a as u16 + value as u16;
+                m.flags = SZP[result as u8 as usize] | (result >> 8) as u8 | if (m.a & 0xf) + (value & 0xf) > 0xf { AUX_CARRY } else { 0 };
+                m.a = result as u8;
+                m.tick(7, 0x1593)?;
+                // 1593  jm 1590h
+                m.pc = if m.flags & SIGN != 0 { 0x1590 } else { 0x1596 };
+                m.tick(10, m.pc)?;
+            },
+            0x1596 => {
+                // 1596  ret
+                let target = m.pop()?;
+                m.pc = target;
+                m.tick(10, target)?;
+                return Ok(());
+            },
+            _ => return Ok(()),
+        }
+    }
+}
+
+fn sub_15c5(m: &mut Machine) -> Result<(), Stop> {
+    loop {
+        match m.pc {
+            0x15c5 => {
+                // 15c5  mvi b,17h
+                m.b = 0x17;
+                m.tick(7, 0x15c7)?;
+                m.pc = 0x15c7;
+            },
+            0x15c7 => {
+                // 15c7  mov a,m
+                m.a = m.read(m.hl())?;
+                m.tick(7, 0x15c8)?;
+                // 15c8  ana a
+                let value = m.a;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(4, 0x15c9)?;
+                // 15c9  jnz 166bh
+                m.pc = if m.flags & ZERO == 0 { 0x166b } else { 0x15cc };
+                m.tick(10, m.pc)?;
+            },
+            0x15cc => {
+                // 15cc  inx h
+                m.set_hl(m.hl().wrapping_add(1));
+                m.tick(5, 0x15cd)?;
+                // 15cd  dcr b
+                let value = m.b;
+                let value = value.wrapping_sub(1);
+                m.flags = SZP[value as usize] | (m.flags & CARRY) | if value & 0xf != 0xf { AUX_CARRY } else { 0 };
+                m.b = value;
+                m.tick(5, 0x15ce)?;
+                // 15ce  jnz 15c7h
+                m.pc = if m.flags & ZERO == 0 { 0x15c7 } else { 0x15d1 };
+                m.tick(10, m.pc)?;
+            },
+            0x15d1 => {
+                // 15d1  ret
+                let target = m.pop()?;
+                m.pc = target;
+                m.tick(10, target)?;
+                return Ok(());
+            },
+            0x166b => {
+                // 166b  stc
+                m.flags |= CARRY;
+                m.tick(4, 0x166c)?;
+                // 166c  ret
+                let target = m.pop()?;
+                m.pc = target;
+                m.tick(10, target)?;
+                return Ok(());
+            },
+            _ => return Ok(()),
+        }
+    }
+}
+
+fn sub_15d3(m: &mut Machine) -> Result<(), Stop> {
+    loop {
+        match m.pc {
+            0x15d3 => {
+                // 15d3  call 1474h
+                m.push(0x15d6)?;
+                m.pc = 0x1474;
+                m.tick(17, 0x1474)?;
+                m.call(0x15d6)?;
+                // 15d6  push h
+                m.push(m.hl())?;
+                m.tick(11, 0x15d7)?;
+                m.pc = 0x15d7;
+            },
+            0x15d7 => {
+                // 15d7  push b
+                m.push(m.bc())?;
+                m.tick(11, 0x15d8)?;
+                // 15d8  push h
+                m.push(m.hl())?;
+                m.tick(11, 0x15d9)?;
+                // 15d9  ldax d
+                m.a = m.read(m.de())?;
+                m.tick(7, 0x15da)?;
+                // 15da  out 04h
+                m.output(0x04, m.a)?;
+                m.tick(10, 0x15dc)?;
+                // 15dc  in 03h
+                m.a = m.input(0x03)?;
+                m.tick(10, 0x15de)?;
+                // 15de  mov m,a
+                m.write(m.hl(), m.a)?;
+                m.tick(7, 0x15df)?;
+                // 15df  inx h
+                m.set_hl(m.hl().wrapping_add(1));
+                m.tick(5, 0x15e0)?;
+                // 15e0  inx d
+                m.set_de(m.de().wrapping_add(1));
+                m.tick(5, 0x15e1)?;
+                // 15e1  xra a
+                let value = m.a;
+                m.a ^= value;
+                m.flags = SZP[m.a as usize] | if m.a > 0xf { AUX_CARRY } else { 0 };
+                m.tick(4, 0x15e2)?;
+                // 15e2  out 04h
+                m.output(0x04, m.a)?;
+                m.tick(10, 0x15e4)?;
+                // 15e4  in 03h
+                m.a = m.input(0x03)?;
+                m.tick(10, 0x15e6)?;
+                // 15e6  mov m,a
+                m.write(m.hl(), m.a)?;
+                m.tick(7, 0x15e7)?;
+                // 15e7  pop h
+                let value = m.pop()?;
+                m.set_hl(value);
+                m.tick(10, 0x15e8)?;
+                // 15e8  lxi b,0020h
+                m.set_bc(0x0020);
+                m.tick(10, 0x15eb)?;
+                // 15eb  dad b
+                let result = m.hl() as u32 + m.bc() as u32;
+                m.flags = (m.flags & !CARRY) | (result >> 16) as u8;
+                m.set_hl(result as u16);
+                m.tick(10, 0x15ec)?;
+                // 15ec  pop b
+                let value = m.pop()?;
+                m.set_bc(value);
+                m.tick(10, 0x15ed)?;
+                // 15ed  dcr b
+                let value = m.b;
+                let value = value.wrapping_sub(1);
+                m.flags = SZP[value as usize] | (m.flags & CARRY) | if value & 0xf != 0xf { AUX_CARRY } else { 0 };
+                m.b = value;
+                m.tick(5, 0x15ee)?;
+                // 15ee  jnz 15d7h
+                m.pc = if m.flags & ZERO == 0 { 0x15d7 } else { 0x15f1 };
+                m.tick(10, m.pc)?;
+            },
+            0x15f1 => {
+                // 15f1  pop h
+                let value = m.pop()?;
+                m.set_hl(value);
+                m.tick(10, 0x15f2)?;
+                // 15f2  ret
+                let target = m.pop()?;
+                m.pc = target;
+                m.tick(10, target)?;
+                return Ok(());
+            },
+            _ => return Ok(()),
+        }
+    }
+}
+
+fn sub_15f3(m: &mut Machine) -> Result<(), Stop> {
+    loop {
+        match m.pc {
+            0x15f3 => {
+                // 15f3  call 1611h
+                m.push(0x15f6)?;
+                m.pc = 0x1611;
+                m.tick(17, 0x1611)?;
+                m.call(0x15f6)?;
+                // 15f6  lxi b,3700h
+                m.set_bc(0x3700);
+                m.tick(10, 0x15f9)?;
+                m.pc = 0x15f9;
+            },
+            0x15f9 => {
+                // 15f9  mov a,m
+                m.a = m.read(m.hl())?;
+                m.tick(7, 0x15fa)?;
+                // 15fa  ana a
+                let value = m.a;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(4, 0x15fb)?;
+                // 15fb  jz 15ffh
+                m.pc = if m.flags & ZERO != 0 { 0x15ff } else { 0x15fe };
+                m.tick(10, m.pc)?;
+            },
+            0x15fe => {
+                // 15fe  inr c
+                let value = m.c;
+                let value = value.wrapping_add(1);
+                m.flags = SZP[value as usize] | (m.flags & CARRY) | if value & 0xf == 0 { AUX_CARRY } else { 0 };
+                m.c = value;
+                m.tick(5, 0x15ff)?;
+                m.pc = 0x15ff;
+            },
+            0x15ff => {
+                // 15ff  inx h
+                m.set_hl(m.hl().wrapping_add(1));
+                m.tick(5, 0x1600)?;
+                // 1600  dcr b
+                let value = m.b;
+                let value = value.wrapping_sub(1);
+                m.flags = SZP[value as usize] | (m.flags & CARRY) | if value & 0xf != 0xf { AUX_CARRY } else { 0 };
+                m.b = value;
+                m.tick(5, 0x1601)?;
+                // 1601  jnz 15f9h
+                m.pc = if m.flags & ZERO == 0 { 0x15f9 } else { 0x1604 };
+                m.tick(10, m.pc)?;
+            },
+            0x1604 => {
+                // 1604  mov a,c
+                m.a = m.c;
+                m.tick(5, 0x1605)?;
+                // 1605  sta 2082h
+                m.write(0x2082, m.a)?;
+                m.tick(13, 0x1608)?;
+                // 1608  cpi 01h
+                let value: u8 = 0x01;
+                let result = (m.a as u16).wrapping_sub(value as u16);
+                m.flags = SZP[result as u8 as usize] | (result > 0xff) as u8 | if (m.a & 0xf).wrapping_sub(value & 0xf) > 0xf { AUX_CARRY } else { 0 };
+                m.tick(7, 0x160a)?;
+                // 160a  rnz
+                if m.flags & ZERO == 0 {
+                    let target = m.pop()?;
+                    m.pc = target;
+                    m.tick(11, target)?;
+                    return Ok(());
+                }
+                m.tick(5, 0x160b)?;
+                // 160b  lxi h,206bh
+                m.set_hl(0x206b);
+                m.tick(10, 0x160e)?;
+                // 160e  mvi m,01h
+                m.write(m.hl(), 0x01)?;
+                m.tick(10, 0x1610)?;
+                // 1610  ret
+                let target = m.pop()?;
+                m.pc = target;
+                m.tick(10, target)?;
+                return Ok(());
+            },
+            _ => return Ok(()),
+        }
+    }
+}
+
+fn sub_1611(m: &mut Machine) -> Result<(), Stop> {
+    // 1611  mvi l,00h
+    m.l = 0x00;
+    m.tick(7, 0x1613)?;
+    // 1613  lda 2067h
+    m.a = m.read(0x2067)?;
+    m.tick(13, 0x1616)?;
+    // 1616  mov h,a
+    m.h = m.a;
+    m.tick(5, 0x1617)?;
+    // 1617  ret
+    let target = m.pop()?;
+    m.pc = target;
+    m.tick(10, target)?;
+    Ok(())
+}
+
+fn sub_1618(m: &mut Machine) -> Result<(), Stop> {
+    loop {
+        match m.pc {
+            0x1618 => {
+                // 1618  lda 2015h
+                m.a = m.read(0x2015)?;
+                m.tick(13, 0x161b)?;
+                // 161b  cpi 0ffh
+                let value: u8 = 0xff;
+                let result = (m.a as u16).wrapping_sub(value as u16);
+                m.flags = SZP[result as u8 as usize] | (result > 0xff) as u8 | if (m.a & 0xf).wrapping_sub(value & 0xf) > 0xf { AUX_CARRY } else { 0 };
+                m.tick(7, 0x161d)?;
+                // 161d  rnz
+                if m.flags & ZERO == 0 {
+                    let target = m.pop()?;
+                    m.pc = target;
+                    m.tick(11, target)?;
+                    return Ok(());
+                }
+                m.tick(5, 0x161e)?;
+                // 161e  lxi h,2010h
+                m.set_hl(0x2010);
+                m.tick(10, 0x1621)?;
+                // 1621  mov a,m
+                m.a = m.read(m.hl())?;
+                m.tick(7, 0x1622)?;
+                // 1622  inx h
+                m.set_hl(m.hl().wrapping_add(1));
+                m.tick(5, 0x1623)?;
+                // 1623  mov b,m
+                m.b = m.read(m.hl())?;
+                m.tick(7, 0x1624)?;
+                // 1624  ora b
+                let value = m.b;
+                m.a |= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(4, 0x1625)?;
+                // 1625  rnz
+                if m.flags & ZERO == 0 {
+                    let target = m.pop()?;
+                    m.pc = target;
+                    m.tick(11, target)?;
+                    return Ok(());
+                }
+                m.tick(5, 0x1626)?;
+                // 1626  lda 2025h
+                m.a = m.read(0x2025)?;
+                m.tick(13, 0x1629)?;
+                // 1629  ana a
+                let value = m.a;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(4, 0x162a)?;
+                // 162a  rnz
+                if m.flags & ZERO == 0 {
+                    let target = m.pop()?;
+                    m.pc = target;
+                    m.tick(11, target)?;
+                    return Ok(());
+                }
+                m.tick(5, 0x162b)?;
+                // 162b  lda 20efh
+                m.a = m.read(0x20ef)?;
+                m.tick(13, 0x162e)?;
+                // 162e  ana a
+                let value = m.a;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(4, 0x162f)?;
+                // 162f  jz 1652h
+                m.pc = if m.flags & ZERO != 0 { 0x1652 } else { 0x1632 };
+                m.tick(10, m.pc)?;
+            },
+            0x1632 => {
+                // 1632  lda 202dh
+                m.a = m.read(0x202d)?;
+                m.tick(13, 0x1635)?;
+                // 1635  ana a
+                let value = m.a;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(4, 0x1636)?;
+                // 1636  jnz 1648h
+                m.pc = if m.flags & ZERO == 0 { 0x1648 } else { 0x1639 };
+                m.tick(10, m.pc)?;
+            },
+            0x1639 => {
+                // 1639  call 17c0h
+                m.push(0x163c)?;
+                m.pc = 0x17c0;
+                m.tick(17, 0x17c0)?;
+                m.call(0x163c)?;
+                // 163c  ani 10h
+                let value: u8 = 0x10;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(7, 0x163e)?;
+                // 163e  rz
+                if m.flags & ZERO != 0 {
+                    let target = m.pop()?;
+                    m.pc = target;
+                    m.tick(11, target)?;
+                    return Ok(());
+                }
+                m.tick(5, 0x163f)?;
+                // 163f  mvi a,01h
+                m.a = 0x01;
+                m.tick(7, 0x1641)?;
+                // 1641  sta 2025h
+                m.write(0x2025, m.a)?;
+                m.tick(13, 0x1644)?;
+                // 1644  sta 202dh
+                m.write(0x202d, m.a)?;
+                m.tick(13, 0x1647)?;
+                // 1647  ret
+                let target = m.pop()?;
+                m.pc = target;
+                m.tick(10, target)?;
+                return Ok(());
+            },
+            0x1648 => {
+                // 1648  call 17c0h
+                m.push(0x164b)?;
+                m.pc = 0x17c0;
+                m.tick(17, 0x17c0)?;
+                m.call(0x164b)?;
+                // 164b  ani 10h
+                let value: u8 = 0x10;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(7, 0x164d)?;
+                // 164d  rnz
+                if m.flags & ZERO == 0 {
+                    let target = m.pop()?;
+                    m.pc = target;
+                    m.tick(11, target)?;
+                    return Ok(());
+                }
+                m.tick(5, 0x164e)?;
+                // 164e  sta 202dh
+                m.write(0x202d, m.a)?;
+                m.tick(13, 0x1651)?;
+                // 1651  ret
+                let target = m.pop()?;
+                m.pc = target;
+                m.tick(10, target)?;
+                return Ok(());
+            },
+            0x1652 => {
+                // 1652  lxi h,2025h
+                m.set_hl(0x2025);
+                m.tick(10, 0x1655)?;
+                // 1655  mvi m,01h
+                m.write(m.hl(), 0x01)?;
+                m.tick(10, 0x1657)?;
+                // 1657  lhld 20edh
+                m.l = m.read(0x20ed)?;
+                m.h = m.read(0x20ee)?;
+                m.tick(16, 0x165a)?;
+                // 165a  inx h
+                m.set_hl(m.hl().wrapping_add(1));
+                m.tick(5, 0x165b)?;
+                // 165b  mov a,l
+                m.a = m.l;
+                m.tick(5, 0x165c)?;
+                // 165c  cpi 7eh
+                let value: u8 = 0x7e;
+                let result = (m.a as u16).wrapping_sub(value as u16);
+                m.flags = SZP[result as u8 as usize] | (result > 0xff) as u8 | if (m.a & 0xf).wrapping_sub(value & 0xf) > 0xf { AUX_CARRY } else { 0 };
+                m.tick(7, 0x165e)?;
+                // 165e  jc 1663h
+                m.pc = if m.flags & CARRY != 0 { 0x1663 } else { 0x1661 };
+                m.tick(10, m.pc)?;
+            },
+            0x1661 => {
+                // 1661  mvi l,74h
+                m.l = 0x74;
+                m.tick(7, 0x1663)?;
+                m.pc = 0x1663;
+            },
+            0x1663 => {
+                // 1663  shld 20edh
+                m.write(0x20ed, m.l)?;
+                m.write(0x20ee, m.h)?;
+                m.tick(16, 0x1666)?;
+                // 1666  mov a,m
+                m.a = m.read(m.hl())?;
+                m.tick(7, 0x1667)?;
+                // 1667  sta 201dh
+                m.write(0x201d, m.a)?;
+                m.tick(13, 0x166a)?;
+                // 166a  ret
+                let target = m.pop()?;
+                m.pc = target;
+                m.tick(10, target)?;
+                return Ok(());
+            },
+            _ => return Ok(()),
+        }
+    }
+}
+
+fn sub_170e(m: &mut Machine) -> Result<(), Stop> {
+    loop {
+        match m.pc {
+            0x170e => {
+                // 170e  call 09cah
+                m.push(0x1711)?;
+                m.pc = 0x09ca;
+                m.tick(17, 0x09ca)?;
+                m.call(0x1711)?;
+                // 1711  inx h
+                m.set_hl(m.hl().wrapping_add(1));
+                m.tick(5, 0x1712)?;
+                // 1712  mov a,m
+                m.a = m.read(m.hl())?;
+                m.tick(7, 0x1713)?;
+                // 1713  lxi d,1cb8h
+                m.set_de(0x1cb8);
+                m.tick(10, 0x1716)?;
+                // 1716  lxi h,1aa1h
+                m.set_hl(0x1aa1);
+                m.tick(10, 0x1719)?;
+                // 1719  mvi c,04h
+                m.c = 0x04;
+                m.tick(7, 0x171b)?;
+                // 171b  mov b,a
+                m.b = m.a;
+                m.tick(5, 0x171c)?;
+                m.pc = 0x171c;
+            },
+            0x171c => {
+                // 171c  ldax d
+                m.a = m.read(m.de())?;
+                m.tick(7, 0x171d)?;
+                // 171d  cmp b
+                let value = m.b;
+                let result = (m.a as u16).wrapping_sub(value as u16);
+                m.flags = SZP[result as u8 as usize] | (result > 0xff) as u8 | if (m.a & 0xf).wrapping_sub(value & 0xf) > 0xf { AUX_CARRY } else { 0 };
+                m.tick(4, 0x171e)?;
+                // 171e  jnc 1727h
+                m.pc = if m.flags & CARRY == 0 { 0x1727 } else { 0x1721 };
+                m.tick(10, m.pc)?;
+            },
+            0x1721 => {
+                // 1721  inx h
+                m.set_hl(m.hl().wrapping_add(1));
+                m.tick(5, 0x1722)?;
+                // 1722  inx d
+                m.set_de(m.de().wrapping_add(1));
+                m.tick(5, 0x1723)?;
+                // 1723  dcr c
+                let value = m.c;
+                let value = value.wrapping_sub(1);
+                m.flags = SZP[value as usize] | (m.flags & CARRY) | if value & 0xf != 0xf { AUX_CARRY } else { 0 };
+                m.c = value;
+                m.tick(5, 0x1724)?;
+                // 1724  jnz 171ch
+                m.pc = if m.flags & ZERO == 0 { 0x171c } else { 0x1727 };
+                m.tick(10, m.pc)?;
+            },
+            0x1727 => {
+                // 1727  mov a,m
+                m.a = m.read(m.hl())?;
+                m.tick(7, 0x1728)?;
+                // 1728  sta 20cfh
+                m.write(0x20cf, m.a)?;
+                m.tick(13, 0x172b)?;
+                // 172b  ret
+                let target = m.pop()?;
+                m.pc = target;
+                m.tick(10, target)?;
+                return Ok(());
+            },
+            _ => return Ok(()),
+        }
+    }
+}
+
+fn sub_172c(m: &mut Machine) -> Result<(), Stop> {
+    loop {
+        match m.pc {
+            0x172c => {
+                // 172c  lda 2025h
+                m.a = m.read(0x2025)?;
+                m.tick(13, 0x172f)?;
+                // 172f  cpi 00h
+                let value: u8 = 0x00;
+                let result = (m.a as u16).wrapping_sub(value as u16);
+                m.flags = SZP[result as u8 as usize] | (result > 0xff) as u8 | if (m.a & 0xf).wrapping_sub(value & 0xf) > 0xf { AUX_CARRY } else { 0 };
+                m.tick(7, 0x1731)?;
+                // 1731  jnz 1739h
+                m.pc = if m.flags & ZERO == 0 { 0x1739 } else { 0x1734 };
+                m.tick(10, m.pc)?;
+            },
+            0x1734 => {
+                // 1734  mvi b,0fdh
+                m.b = 0xfd;
+                m.tick(7, 0x1736)?;
+                // 1736  jmp 19dch
+                m.pc = 0x19dc;
+                m.tick(10, 0x19dc)?;
+            },
+            0x1739 => {
+                // 1739  mvi b,02h
+                m.b = 0x02;
+                m.tick(7, 0x173b)?;
+                // 173b  jmp 18fah
+                m.pc = 0x18fa;
+                m.tick(10, 0x18fa)?;
+            },
+            0x18fa => {
+                // 18fa  lda 2094h
+                m.a = m.read(0x2094)?;
+                m.tick(13, 0x18fd)?;
+                // 18fd  ora b
+                let value = m.b;
+                m.a |= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(4, 0x18fe)?;
+                // 18fe  sta 2094h
+                m.write(0x2094, m.a)?;
+                m.tick(13, 0x1901)?;
+                // 1901  out 03h
+                m.output(0x03, m.a)?;
+                m.tick(10, 0x1903)?;
+                // 1903  ret
+                let target = m.pop()?;
+                m.pc = target;
+                m.tick(10, target)?;
+                return Ok(());
+            },
+            0x19dc => {
+                // 19dc  lda 2094h
+                m.a = m.read(0x2094)?;
+                m.tick(13, 0x19df)?;
+                // 19df  ana b
+                let value = m.b;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(4, 0x19e0)?;
+                // 19e0  sta 2094h
+                m.write(0x2094, m.a)?;
+                m.tick(13, 0x19e3)?;
+                // 19e3  out 03h
+                m.output(0x03, m.a)?;
+                m.tick(10, 0x19e5)?;
+                // 19e5  ret
+                let target = m.pop()?;
+                m.pc = target;
+                m.tick(10, target)?;
+                return Ok(());
+            },
+            _ => return Ok(()),
+        }
+    }
+}
+
+fn sub_1740(m: &mut Machine) -> Result<(), Stop> {
+    loop {
+        match m.pc {
+            0x1740 => {
+                // 1740  lxi h,209bh
+                m.set_hl(0x209b);
+                m.tick(10, 0x1743)?;
+                // 1743  dcr m
+                let value = m.read(m.hl())?;
+                let value = value.wrapping_sub(1);
+                m.flags = SZP[value as usize] | (m.flags & CARRY) | if value & 0xf != 0xf { AUX_CARRY } else { 0 };
+                m.write(m.hl(), value)?;
+                m.tick(10, 0x1744)?;
+                // 1744  cz 176dh
+                if m.flags & ZERO != 0 {
+                    m.push(0x1747)?;
+                    m.pc = 0x176d;
+                    m.tick(17, 0x176d)?;
+                    m.call(0x1747)?;
+                } else {
+                    m.tick(11, 0x1747)?;
+                }
+                // 1747  lda 2068h
+                m.a = m.read(0x2068)?;
+                m.tick(13, 0x174a)?;
+                // 174a  ana a
+                let value = m.a;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(4, 0x174b)?;
+                // 174b  jz 176dh
+                m.pc = if m.flags & ZERO != 0 { 0x176d } else { 0x174e };
+                m.tick(10, m.pc)?;
+            },
+            0x174e => {
+                // 174e  lxi h,2096h
+                m.set_hl(0x2096);
+                m.tick(10, 0x1751)?;
+                // 1751  dcr m
+                let value = m.read(m.hl())?;
+                let value = value.wrapping_sub(1);
+                m.flags = SZP[value as usize] | (m.flags & CARRY) | if value & 0xf != 0xf { AUX_CARRY } else { 0 };
+                m.write(m.hl(), value)?;
+                m.tick(10, 0x1752)?;
+                // 1752  rnz
+                if m.flags & ZERO == 0 {
+                    let target = m.pop()?;
+                    m.pc = target;
+                    m.tick(11, target)?;
+                    return Ok(());
+                }
+                m.tick(5, 0x1753)?;
+                // 1753  lxi h,2098h
+                m.set_hl(0x2098);
+                m.tick(10, 0x1756)?;
+                // 1756  mov a,m
+                m.a = m.read(m.hl())?;
+                m.tick(7, 0x1757)?;
+                // 1757  out 05h
+                m.output(0x05, m.a)?;
+                m.tick(10, 0x1759)?;
+                // 1759  lda 2082h
+                m.a = m.read(0x2082)?;
+                m.tick(13, 0x175c)?;
+                // 175c  ana a
+                let value = m.a;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(4, 0x175d)?;
+                // 175d  jz 176dh
+                m.pc = if m.flags & ZERO != 0 { 0x176d } else { 0x1760 };
+                m.tick(10, m.pc)?;
+            },
+            0x1760 => {
+                // 1760  dcx h
+                m.set_hl(m.hl().wrapping_sub(1));
+                m.tick(5, 0x1761)?;
+                // 1761  mov a,m
+                m.a = m.read(m.hl())?;
+                m.tick(7, 0x1762)?;
+                // 1762  dcx h
+                m.set_hl(m.hl().wrapping_sub(1));
+                m.tick(5, 0x1763)?;
+                // 1763  mov m,a
+                m.write(m.hl(), m.a)?;
+                m.tick(7, 0x1764)?;
+                // 1764  dcx h
+                m.set_hl(m.hl().wrapping_sub(1));
+                m.tick(5, 0x1765)?;
+                // 1765  mvi m,01h
+                m.write(m.hl(), 0x01)?;
+                m.tick(10, 0x1767)?;
+                // 1767  mvi a,04h
+                m.a = 0x04;
+                m.tick(7, 0x1769)?;
+                // 1769  sta 209bh
+                m.write(0x209b, m.a)?;
+                m.tick(13, 0x176c)?;
+                // 176c  ret
+                let target = m.pop()?;
+                m.pc = target;
+                m.tick(10, target)?;
+                return Ok(());
+            },
+            0x176d => {
+                // 176d  lda 2098h
+                m.a = m.read(0x2098)?;
+                m.tick(13, 0x1770)?;
+                // 1770  ani 30h
+                let value: u8 = 0x30;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(7, 0x1772)?;
+                // 1772  out 05h
+                m.output(0x05, m.a)?;
+                m.tick(10, 0x1774)?;
+                // 1774  ret
+                let target = m.pop()?;
+                m.pc = target;
+                m.tick(10, target)?;
+                return Ok(());
+            },
+            _ => return Ok(()),
+        }
+    }
+}
+
+fn sub_176d(m: &mut Machine) -> Result<(), Stop> {
+    // 176d  lda 2098h
+    m.a = m.read(0x2098)?;
+    m.tick(13, 0x1770)?;
+    // 1770  ani 30h
+    let value: u8 = 0x30;
+    m.a &= value;
+    m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+    m.tick(7, 0x1772)?;
+    // 1772  out 05h
+    m.output(0x05, m.a)?;
+    m.tick(10, 0x1774)?;
+    // 1774  ret
+    let target = m.pop()?;
+    m.pc = target;
+    m.tick(10, target)?;
+    Ok(())
+}
+
+fn sub_1775(m: &mut Machine) -> Result<(), Stop> {
+    loop {
+        match m.pc {
+            0x1775 => {
+                // 1775  lda 2095h
+                m.a = m.read(0x2095)?;
+                m.tick(13, 0x1778)?;
+                // 1778  ana a
+                let value = m.a;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(4, 0x1779)?;
+                // 1779  jz 17aah
+                m.pc = if m.flags & ZERO != 0 { 0x17aa } else { 0x177c };
+                m.tick(10, m.pc)?;
+            },
+            0x177c => {
+                // 177c  lxi h,1a11h
+                m.set_hl(0x1a11);
+                m.tick(10, 0x177f)?;
+                // 177f  lxi d,1a21h
+                m.set_de(0x1a21);
+                m.tick(10, 0x1782)?;
+                // 1782  lda 2082h
+                m.a = m.read(0x2082)?;
+                m.tick(13, 0x1785)?;
+                m.pc = 0x1785;
+            },
+            0x1785 => {
+                // 1785  cmp m
+                let value = m.read(m.hl())?;
+                let result = (m.a as u16).wrapping_sub(value as u16);
+                m.flags = SZP[result as u8 as usize] | (result > 0xff) as u8 | if (m.a & 0xf).wrapping_sub(value & 0xf) > 0xf { AUX_CARRY } else { 0 };
+                m.tick(7, 0x1786)?;
+                // 1786  jnc 178eh
+                m.pc = if m.flags & CARRY == 0 { 0x178e } else { 0x1789 };
+                m.tick(10, m.pc)?;
+            },
+            0x1789 => {
+                // 1789  inx h
+                m.set_hl(m.hl().wrapping_add(1));
+                m.tick(5, 0x178a)?;
+                // 178a  inx d
+                m.set_de(m.de().wrapping_add(1));
+                m.tick(5, 0x178b)?;
+                // 178b  jmp 1785h
+                m.pc = 0x1785;
+                m.tick(10, 0x1785)?;
+            },
+            0x178e => {
+                // 178e  ldax d
+                m.a = m.read(m.de())?;
+                m.tick(7, 0x178f)?;
+                // 178f  sta 2097h
+                m.write(0x2097, m.a)?;
+                m.tick(13, 0x1792)?;
+                // 1792  lxi h,2098h
+                m.set_hl(0x2098);
+                m.tick(10, 0x1795)?;
+                // 1795  mov a,m
+                m.a = m.read(m.hl())?;
+                m.tick(7, 0x1796)?;
+                // 1796  ani 30h
+                let value: u8 = 0x30;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(7, 0x1798)?;
+                // 1798  mov b,a
+                m.b = m.a;
+                m.tick(5, 0x1799)?;
+                // 1799  mov a,m
+                m.a = m.read(m.hl())?;
+                m.tick(7, 0x179a)?;
+                // 179a  ani 0fh
+                let value: u8 = 0x0f;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(7, 0x179c)?;
+                // 179c  rlc
+                m.flags = (m.flags & !CARRY) | (m.a >> 7);
+                m.a = m.a.rotate_left(1);
+                m.tick(4, 0x179d)?;
+                // 179d  cpi 10h
+                let value: u8 = 0x10;
+                let result = (m.a as u16).wrapping_sub(value as u16);
+                m.flags = SZP[result as u8 as usize] | (result > 0xff) as u8 | if (m.a & 0xf).wrapping_sub(value & 0xf) > 0xf { AUX_CARRY } else { 0 };
+                m.tick(7, 0x179f)?;
+                // 179f  jnz 17a4h
+                m.pc = if m.flags & ZERO == 0 { 0x17a4 } else { 0x17a2 };
+                m.tick(10, m.pc)?;
+            },
+            0x17a2 => {
+                // 17a2  mvi a,01h
+                m.a = 0x01;
+                m.tick(7, 0x17a4)?;
+                m.pc = 0x17a4;
+            },
+            0x17a4 => {
+                // 17a4  ora b
+                let value = m.b;
+                m.a |= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(4, 0x17a5)?;
+                // 17a5  mov m,a
+                m.write(m.hl(), m.a)?;
+                m.tick(7, 0x17a6)?;
+                // 17a6  xra a
+                let value = m.a;
+                m.a ^= value;
+                m.flags = SZP[m.a as usize] | if m.a > 0xf { AUX_CARRY } else { 0 };
+                m.tick(4, 0x17a7)?;
+                // 17a7  sta 2095h
+                m.write(0x2095, m.a)?;
+                m.tick(13, 0x17aa)?;
+                m.pc = 0x17aa;
+            },
+            0x17aa => {
+                // 17aa  lxi h,2099h
+                m.set_hl(0x2099);
+                m.tick(10, 0x17ad)?;
+                // 17ad  dcr m
+                let value = m.read(m.hl())?;
+                let value = value.wrapping_sub(1);
+                m.flags = SZP[value as usize] | (m.flags & CARRY) | if value & 0xf != 0xf { AUX_CARRY } else { 0 };
+                m.write(m.hl(), value)?;
+                m.tick(10, 0x17ae)?;
+                // 17ae  rnz
+                if m.flags & ZERO == 0 {
+                    let target = m.pop()?;
+                    m.pc = target;
+                    m.tick(11, target)?;
+                    return Ok(());
+                }
+                m.tick(5, 0x17af)?;
+                // 17af  mvi b,0efh
+                m.b = 0xef;
+                m.tick(7, 0x17b1)?;
+                // 17b1  jmp 19dch
+                m.pc = 0x19dc;
+                m.tick(10, 0x19dc)?;
+            },
+            0x19dc => {
+                // 19dc  lda 2094h
+                m.a = m.read(0x2094)?;
+                m.tick(13, 0x19df)?;
+                // 19df  ana b
+                let value = m.b;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(4, 0x19e0)?;
+                // 19e0  sta 2094h
+                m.write(0x2094, m.a)?;
+                m.tick(13, 0x19e3)?;
+                // 19e3  out 03h
+                m.output(0x03, m.a)?;
+                m.tick(10, 0x19e5)?;
+                // 19e5  ret
+                let target = m.pop()?;
+                m.pc = target;
+                m.tick(10, target)?;
+                return Ok(());
+            },
+            _ => return Ok(()),
+        }
+    }
+}
+
+fn sub_17c0(m: &mut Machine) -> Result<(), Stop> {
+    loop {
+        match m.pc {
+            0x17c0 => {
+                // 17c0  lda 2067h
+                m.a = m.read(0x2067)?;
+                m.tick(13, 0x17c3)?;
+                // 17c3  rrc
+                m.flags = (m.flags & !CARRY) | (m.a & 1);
+                m.a = m.a.rotate_right(1);
+                m.tick(4, 0x17c4)?;
+                // 17c4  jnc 17cah
+                m.pc = if m.flags & CARRY == 0 { 0x17ca } else { 0x17c7 };
+                m.tick(10, m.pc)?;
+            },
+            0x17c7 => {
+                // 17c7  in 01h
+                m.a = m.input(0x01)?;
+                m.tick(10, 0x17c9)?;
+                // 17c9  ret
+                let target = m.pop()?;
+                m.pc = target;
+                m.tick(10, target)?;
+                return Ok(());
+            },
+            0x17ca => {
+                // 17ca  in 02h
+                m.a = m.input(0x02)?;
+                m.tick(10, 0x17cc)?;
+                // 17cc  ret
+                let target = m.pop()?;
+                m.pc = target;
+                m.tick(10, target)?;
+                return Ok(());
+            },
+            _ => return Ok(()),
+        }
+    }
+}
+
+fn sub_17cd(m: &mut Machine) -> Result<(), Stop> {
+    loop {
+        match m.pc {
+            0x0aea => {
+                // 0aea  xra a
+                let value = m.a;
+                m.a ^= value;
+                m.flags = SZP[m.a as usize] | if m.a > 0xf { AUX_CARRY } else { 0 };
+                m.tick(4, 0x0aeb)?;
+                // 0aeb  out 03h
+                m.output(0x03, m.a)?;
+                m.tick(10, 0x0aed)?;
+                // 0aed  out 05h
+                m.output(0x05, m.a)?;
+                m.tick(10, 0x0aef)?;
+                // 0aef  call 1982h
+                m.push(0x0af2)?;
+                m.pc = 0x1982;
+                m.tick(17, 0x1982)?;
+                m.call(0x0af2)?;
+                m.pc = 0x0af2;
+            },
+            // 0af2  ei
+            0x0af2 => m.interpret()?,
+            0x0af6 => {
+                // 0af6  lda 20ech
+                m.a = m.read(0x20ec)?;
+                m.tick(13, 0x0af9)?;
+                // 0af9  ana a
+                let value = m.a;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(4, 0x0afa)?;
+                // 0afa  lxi h,3017h
+                m.set_hl(0x3017);
+                m.tick(10, 0x0afd)?;
+                // 0afd  mvi c,04h
+                m.c = 0x04;
+                m.tick(7, 0x0aff)?;
+                // 0aff  jnz 0be8h
+                m.pc = if m.flags & ZERO == 0 { 0x0be8 } else { 0x0b02 };
+                m.tick(10, m.pc)?;
+            },
+            0x0b02 => {
+                // 0b02  lxi d,1cfah
+                m.set_de(0x1cfa);
+                m.tick(10, 0x0b05)?;
+                // 0b05  call 0a93h
+                m.push(0x0b08)?;
+                m.pc = 0x0a93;
+                m.tick(17, 0x0a93)?;
+                m.call(0x0b08)?;
+                // 0b08  lxi d,1dafh
+                m.set_de(0x1daf);
+                m.tick(10, 0x0b0b)?;
+                m.pc = 0x0b0b;
+            },
+            0x0b0b => {
+                // 0b0b  call 0acfh
+                m.push(0x0b0e)?;
+                m.pc = 0x0acf;
+                m.tick(17, 0x0acf)?;
+                m.call(0x0b0e)?;
+                // 0b0e  call 0ab1h
+                m.push(0x0b11)?;
+                m.pc = 0x0ab1;
+                m.tick(17, 0x0ab1)?;
+                m.call(0x0b11)?;
+                // 0b11  call 1815h
+                m.push(0x0b14)?;
+                m.pc = 0x1815;
+                m.tick(17, 0x1815)?;
+                m.call(0x0b14)?;
+                // 0b14  call 0ab6h
+                m.push(0x0b17)?;
+                m.pc = 0x0ab6;
+                m.tick(17, 0x0ab6)?;
+                m.call(0x0b17)?;
+                // 0b17  lda 20ech
+                m.a = m.read(0x20ec)?;
+                m.tick(13, 0x0b1a)?;
+                // 0b1a  ana a
+                let value = m.a;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(4, 0x0b1b)?;
+                // 0b1b  jnz 0b4ah
+                m.pc = if m.flags & ZERO == 0 { 0x0b4a } else { 0x0b1e };
+                m.tick(10, m.pc)?;
+            },
+            0x0b1e => {
+                // 0b1e  lxi d,1a95h
+                m.set_de(0x1a95);
+                m.tick(10, 0x0b21)?;
+                // 0b21  call 0ae2h
+                m.push(0x0b24)?;
+                m.pc = 0x0ae2;
+                m.tick(17, 0x0ae2)?;
+                m.call(0x0b24)?;
+                // 0b24  call 0a80h
+                m.push(0x0b27)?;
+                m.pc = 0x0a80;
+                m.tick(17, 0x0a80)?;
+                m.call(0x0b27)?;
+                // 0b27  lxi d,1bb0h
+                m.set_de(0x1bb0);
+                m.tick(10, 0x0b2a)?;
+                // 0b2a  call 0ae2h
+                m.push(0x0b2d)?;
+                m.pc = 0x0ae2;
+                m.tick(17, 0x0ae2)?;
+                m.call(0x0b2d)?;
+                // 0b2d  call 0a80h
+                m.push(0x0b30)?;
+                m.pc = 0x0a80;
+                m.tick(17, 0x0a80)?;
+                m.call(0x0b30)?;
+                // 0b30  call 0ab1h
+                m.push(0x0b33)?;
+                m.pc = 0x0ab1;
+                m.tick(17, 0x0ab1)?;
+                m.call(0x0b33)?;
+                // 0b33  lxi d,1fc9h
+                m.set_de(0x1fc9);
+                m.tick(10, 0x0b36)?;
+                // 0b36  call 0ae2h
+                m.push(0x0b39)?;
+                m.pc = 0x0ae2;
+                m.tick(17, 0x0ae2)?;
+                m.call(0x0b39)?;
+                // 0b39  call 0a80h
+                m.push(0x0b3c)?;
+                m.pc = 0x0a80;
+                m.tick(17, 0x0a80)?;
+                m.call(0x0b3c)?;
+                // 0b3c  call 0ab1h
+                m.push(0x0b3f)?;
+                m.pc = 0x0ab1;
+                m.tick(17, 0x0ab1)?;
+                m.call(0x0b3f)?;
+                // 0b3f  lxi h,33b7h
+                m.set_hl(0x33b7);
+                m.tick(10, 0x0b42)?;
+                // 0b42  mvi b,0ah
+                m.b = 0x0a;
+                m.tick(7, 0x0b44)?;
+                // 0b44  call 14cbh
+                m.push(0x0b47)?;
+                m.pc = 0x14cb;
+                m.tick(17, 0x14cb)?;
+                m.call(0x0b47)?;
+                // 0b47  call 0ab6h
+                m.push(0x0b4a)?;
+                m.pc = 0x0ab6;
+                m.tick(17, 0x0ab6)?;
+                m.call(0x0b4a)?;
+                m.pc = 0x0b4a;
+            },
+            0x0b4a => {
+                // 0b4a  call 09d6h
+                m.push(0x0b4d)?;
+                m.pc = 0x09d6;
+                m.tick(17, 0x09d6)?;
+                m.call(0x0b4d)?;
+                // 0b4d  lda 21ffh
+                m.a = m.read(0x21ff)?;
+                m.tick(13, 0x0b50)?;
+                // 0b50  ana a
+                let value = m.a;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(4, 0x0b51)?;
+                // 0b51  jnz 0b5dh
+                m.pc = if m.flags & ZERO == 0 { 0x0b5d } else { 0x0b54 };
+                m.tick(10, m.pc)?;
+            },
+            0x0b54 => {
+                // 0b54  call 08d1h
+                m.push(0x0b57)?;
+                m.pc = 0x08d1;
+                m.tick(17, 0x08d1)?;
+                m.call(0x0b57)?;
+                // 0b57  sta 21ffh
+                m.write(0x21ff, m.a)?;
+                m.tick(13, 0x0b5a)?;
+                // 0b5a  call 1a7fh
+                m.push(0x0b5d)?;
+                m.pc = 0x1a7f;
+                m.tick(17, 0x1a7f)?;
+                m.call(0x0b5d)?;
+                m.pc = 0x0b5d;
+            },
+            0x0b5d => {
+                // 0b5d  call 01e4h
+                m.push(0x0b60)?;
+                m.pc = 0x01e4;
+                m.tick(17, 0x01e4)?;
+                m.call(0x0b60)?;
+                // 0b60  call 01c0h
+                m.push(0x0b63)?;
+                m.pc = 0x01c0;
+                m.tick(17, 0x01c0)?;
+                m.call(0x0b63)?;
+                // 0b63  call 01efh
+                m.push(0x0b66)?;
+                m.pc = 0x01ef;
+                m.tick(17, 0x01ef)?;
+                m.call(0x0b66)?;
+                // 0b66  call 021ah
+                m.push(0x0b69)?;
+                m.pc = 0x021a;
+                m.tick(17, 0x021a)?;
+                m.call(0x0b69)?;
+                // 0b69  mvi a,01h
+                m.a = 0x01;
+                m.tick(7, 0x0b6b)?;
+                // 0b6b  sta 20c1h
+                m.write(0x20c1, m.a)?;
+                m.tick(13, 0x0b6e)?;
+                // 0b6e  call 01cfh
+                m.push(0x0b71)?;
+                m.pc = 0x01cf;
+                m.tick(17, 0x01cf)?;
+                m.call(0x0b71)?;
+                m.pc = 0x0b71;
+            },
+            0x0b71 => {
+                // 0b71  call 1618h
+                m.push(0x0b74)?;
+                m.pc = 0x1618;
+                m.tick(17, 0x1618)?;
+                m.call(0x0b74)?;
+                // 0b74  call 0bf1h
+                m.push(0x0b77)?;
+                m.pc = 0x0bf1;
+                m.tick(17, 0x0bf1)?;
+                m.call(0x0b77)?;
+                // 0b77  out 06h
+                m.output(0x06, m.a)?;
+                m.tick(10, 0x0b79)?;
+                // 0b79  call 0a59h
+                m.push(0x0b7c)?;
+                m.pc = 0x0a59;
+                m.tick(17, 0x0a59)?;
+                m.call(0x0b7c)?;
+                // 0b7c  jz 0b71h
+                m.pc = if m.flags & ZERO != 0 { 0x0b71 } else { 0x0b7f };
+                m.tick(10, m.pc)?;
+            },
+            0x0b7f => {
+                // 0b7f  xra a
+                let value = m.a;
+                m.a ^= value;
+                m.flags = SZP[m.a as usize] | if m.a > 0xf { AUX_CARRY } else { 0 };
+                m.tick(4, 0x0b80)?;
+                // 0b80  sta 2025h
+                m.write(0x2025, m.a)?;
+                m.tick(13, 0x0b83)?;
+                m.pc = 0x0b83;
+            },
+            0x0b83 => {
+                // 0b83  call 0a59h
+                m.push(0x0b86)?;
+                m.pc = 0x0a59;
+                m.tick(17, 0x0a59)?;
+                m.call(0x0b86)?;
+                // 0b86  jnz 0b83h
+                m.pc = if m.flags & ZERO == 0 { 0x0b83 } else { 0x0b89 };
+                m.tick(10, m.pc)?;
+            },
+            0x0b89 => {
+                // 0b89  xra a
+                let value = m.a;
+                m.a ^= value;
+                m.flags = SZP[m.a as usize] | if m.a > 0xf { AUX_CARRY } else { 0 };
+                m.tick(4, 0x0b8a)?;
+                // 0b8a  sta 20c1h
+                m.write(0x20c1, m.a)?;
+                m.tick(13, 0x0b8d)?;
+                // 0b8d  call 0ab1h
+                m.push(0x0b90)?;
+                m.pc = 0x0ab1;
+                m.tick(17, 0x0ab1)?;
+                m.call(0x0b90)?;
+                // 0b90  call 1988h
+                m.push(0x0b93)?;
+                m.pc = 0x1988;
+                m.tick(17, 0x1988)?;
+                m.call(0x0b93)?;
+                // 0b93  mvi c,0ch
+                m.c = 0x0c;
+                m.tick(7, 0x0b95)?;
+                // 0b95  lxi h,2c11h
+                m.set_hl(0x2c11);
+                m.tick(10, 0x0b98)?;
+                // 0b98  lxi d,1f90h
+                m.set_de(0x1f90);
+                m.tick(10, 0x0b9b)?;
+                // 0b9b  call 08f3h
+                m.push(0x0b9e)?;
+                m.pc = 0x08f3;
+                m.tick(17, 0x08f3)?;
+                m.call(0x0b9e)?;
+                // 0b9e  lda 20ech
+                m.a = m.read(0x20ec)?;
+                m.tick(13, 0x0ba1)?;
+                // 0ba1  cpi 00h
+                let value: u8 = 0x00;
+                let result = (m.a as u16).wrapping_sub(value as u16);
+                m.flags = SZP[result as u8 as usize] | (result > 0xff) as u8 | if (m.a & 0xf).wrapping_sub(value & 0xf) > 0xf { AUX_CARRY } else { 0 };
+                m.tick(7, 0x0ba3)?;
+                // 0ba3  jnz 0baeh
+                m.pc = if m.flags & ZERO == 0 { 0x0bae } else { 0x0ba6 };
+                m.tick(10, m.pc)?;
+            },
+            0x0ba6 => {
+                // 0ba6  lxi h,3311h
+                m.set_hl(0x3311);
+                m.tick(10, 0x0ba9)?;
+                // 0ba9  mvi a,02h
+                m.a = 0x02;
+                m.tick(7, 0x0bab)?;
+                // 0bab  call 08ffh
+                m.push(0x0bae)?;
+                m.pc = 0x08ff;
+                m.tick(17, 0x08ff)?;
+                m.call(0x0bae)?;
+                m.pc = 0x0bae;
+            },
+            0x0bae => {
+                // 0bae  lxi b,1f9ch
+                m.set_bc(0x1f9c);
+                m.tick(10, 0x0bb1)?;
+                // 0bb1  call 1856h
+                m.push(0x0bb4)?;
+                m.pc = 0x1856;
+                m.tick(17, 0x1856)?;
+                m.call(0x0bb4)?;
+                // 0bb4  call 184ch
+                m.push(0x0bb7)?;
+                m.pc = 0x184c;
+                m.tick(17, 0x184c)?;
+                m.call(0x0bb7)?;
+                // 0bb7  in 02h
+                m.a = m.input(0x02)?;
+                m.tick(10, 0x0bb9)?;
+                // 0bb9  rlc
+                m.flags = (m.flags & !CARRY) | (m.a >> 7);
+                m.a = m.a.rotate_left(1);
+                m.tick(4, 0x0bba)?;
+                // 0bba  jc 0bc3h
+                m.pc = if m.flags & CARRY != 0 { 0x0bc3 } else { 0x0bbd };
+                m.tick(10, m.pc)?;
+            },
+            0x0bbd => {
+                // 0bbd  lxi b,1fa0h
+                m.set_bc(0x1fa0);
+                m.tick(10, 0x0bc0)?;
+                // 0bc0  call 183ah
+                m.push(0x0bc3)?;
+                m.pc = 0x183a;
+                m.tick(17, 0x183a)?;
+                m.call(0x0bc3)?;
+                m.pc = 0x0bc3;
+            },
+            0x0bc3 => {
+                // 0bc3  call 0ab6h
+                m.push(0x0bc6)?;
+                m.pc = 0x0ab6;
+                m.tick(17, 0x0ab6)?;
+                m.call(0x0bc6)?;
+                // 0bc6  lda 20ech
+                m.a = m.read(0x20ec)?;
+                m.tick(13, 0x0bc9)?;
+                // 0bc9  cpi 00h
+                let value: u8 = 0x00;
+                let result = (m.a as u16).wrapping_sub(value as u16);
+                m.flags = SZP[result as u8 as usize] | (result > 0xff) as u8 | if (m.a & 0xf).wrapping_sub(value & 0xf) > 0xf { AUX_CARRY } else { 0 };
+                m.tick(7, 0x0bcb)?;
+                // 0bcb  jnz 0bdah
+                m.pc = if m.flags & ZERO == 0 { 0x0bda } else { 0x0bce };
+                m.tick(10, m.pc)?;
+            },
+            0x0bce => {
+                // 0bce  lxi d,1fd5h
+                m.set_de(0x1fd5);
+                m.tick(10, 0x0bd1)?;
+                // 0bd1  call 0ae2h
+                m.push(0x0bd4)?;
+                m.pc = 0x0ae2;
+                m.tick(17, 0x0ae2)?;
+                m.call(0x0bd4)?;
+                // 0bd4  call 0a80h
+                m.push(0x0bd7)?;
+                m.pc = 0x0a80;
+                m.tick(17, 0x0a80)?;
+                m.call(0x0bd7)?;
+                // 0bd7  call 189eh
+                m.push(0x0bda)?;
+                m.pc = 0x189e;
+                m.tick(17, 0x189e)?;
+                m.call(0x0bda)?;
+                m.pc = 0x0bda;
+            },
+            0x0bda => {
+                // 0bda  lxi h,20ech
+                m.set_hl(0x20ec);
+                m.tick(10, 0x0bdd)?;
+                // 0bdd  mov a,m
+                m.a = m.read(m.hl())?;
+                m.tick(7, 0x0bde)?;
+                // 0bde  inr a
+                let value = m.a;
+                let value = value.wrapping_add(1);
+                m.flags = SZP[value as usize] | (m.flags & CARRY) | if value & 0xf == 0 { AUX_CARRY } else { 0 };
+                m.a = value;
+                m.tick(5, 0x0bdf)?;
+                // 0bdf  ani 01h
+                let value: u8 = 0x01;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(7, 0x0be1)?;
+                // 0be1  mov m,a
+                m.write(m.hl(), m.a)?;
+                m.tick(7, 0x0be2)?;
+                // 0be2  call 09d6h
+                m.push(0x0be5)?;
+                m.pc = 0x09d6;
+                m.tick(17, 0x09d6)?;
+                m.call(0x0be5)?;
+                // 0be5  jmp 18dfh
+                m.pc = 0x18df;
+                m.tick(10, 0x18df)?;
+            },
+            0x0be8 => {
+                // 0be8  lxi d,1dabh
+                m.set_de(0x1dab);
+                m.tick(10, 0x0beb)?;
+                // 0beb  call 0a93h
+                m.push(0x0bee)?;
+                m.pc = 0x0a93;
+                m.tick(17, 0x0a93)?;
+                m.call(0x0bee)?;
+                // 0bee  jmp 0b0bh
+                m.pc = 0x0b0b;
+                m.tick(10, 0x0b0b)?;
+            },
+            0x16c9 => {
+                // 16c9  lxi h,2d18h
+                m.set_hl(0x2d18);
+                m.tick(10, 0x16cc)?;
+                // 16cc  lxi d,1aa6h
+                m.set_de(0x1aa6);
+                m.tick(10, 0x16cf)?;
+                // 16cf  mvi c,0ah
+                m.c = 0x0a;
+                m.tick(7, 0x16d1)?;
+                // 16d1  call 0a93h
+                m.push(0x16d4)?;
+                m.pc = 0x0a93;
+                m.tick(17, 0x0a93)?;
+                m.call(0x16d4)?;
+                // 16d4  call 0ab6h
+                m.push(0x16d7)?;
+                m.pc = 0x0ab6;
+                m.tick(17, 0x0ab6)?;
+                m.call(0x16d7)?;
+                // 16d7  call 09d6h
+                m.push(0x16da)?;
+                m.pc = 0x09d6;
+                m.tick(17, 0x09d6)?;
+                m.call(0x16da)?;
+                // 16da  xra a
+                let value = m.a;
+                m.a ^= value;
+                m.flags = SZP[m.a as usize] | if m.a > 0xf { AUX_CARRY } else { 0 };
+                m.tick(4, 0x16db)?;
+                // 16db  sta 20efh
+                m.write(0x20ef, m.a)?;
+                m.tick(13, 0x16de)?;
+                // 16de  out 05h
+                m.output(0x05, m.a)?;
+                m.tick(10, 0x16e0)?;
+                // 16e0  call 19d1h
+                m.push(0x16e3)?;
+                m.pc = 0x19d1;
+                m.tick(17, 0x19d1)?;
+                m.call(0x16e3)?;
+                // 16e3  jmp 0b89h
+                m.pc = 0x0b89;
+                m.tick(10, 0x0b89)?;
+            },
+            0x17cd => {
+                // 17cd  in 02h
+                m.a = m.input(0x02)?;
+                m.tick(10, 0x17cf)?;
+                // 17cf  ani 04h
+                let value: u8 = 0x04;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(7, 0x17d1)?;
+                // 17d1  rz
+                if m.flags & ZERO != 0 {
+                    let target = m.pop()?;
+                    m.pc = target;
+                    m.tick(11, target)?;
+                    return Ok(());
+                }
+                m.tick(5, 0x17d2)?;
+                // 17d2  lda 209ah
+                m.a = m.read(0x209a)?;
+                m.tick(13, 0x17d5)?;
+                // 17d5  ana a
+                let value = m.a;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(4, 0x17d6)?;
+                // 17d6  rnz
+                if m.flags & ZERO == 0 {
+                    let target = m.pop()?;
+                    m.pc = target;
+                    m.tick(11, target)?;
+                    return Ok(());
+                }
+                m.tick(5, 0x17d7)?;
+                // 17d7  lxi sp,2400h
+                m.sp = 0x2400;
+                m.tick(10, 0x17da)?;
+                // 17da  mvi b,04h
+                m.b = 0x04;
+                m.tick(7, 0x17dc)?;
+                m.pc = 0x17dc;
+            },
+            0x17dc => {
+                // 17dc  call 09d6h
+                m.push(0x17df)?;
+                m.pc = 0x09d6;
+                m.tick(17, 0x09d6)?;
+                m.call(0x17df)?;
+                // 17df  dcr b
+                let value = m.b;
+                let value = value.wrapping_sub(1);
+                m.flags = SZP[value as usize] | (m.flags & CARRY) | if value & 0xf != 0xf { AUX_CARRY } else { 0 };
+                m.b = value;
+                m.tick(5, 0x17e0)?;
+                // 17e0  jnz 17dch
+                m.pc = if m.flags & ZERO == 0 { 0x17dc } else { 0x17e3 };
+                m.tick(10, m.pc)?;
+            },
+            0x17e3 => {
+                // 17e3  mvi a,01h
+                m.a = 0x01;
+                m.tick(7, 0x17e5)?;
+                // 17e5  sta 209ah
+                m.write(0x209a, m.a)?;
+                m.tick(13, 0x17e8)?;
+                // 17e8  call 19d7h
+                m.push(0x17eb)?;
+                m.pc = 0x19d7;
+                m.tick(17, 0x19d7)?;
+                m.call(0x17eb)?;
+                m.pc = 0x17eb;
+            },
+            // 17eb  ei
+            0x17eb => m.interpret()?,
+            0x17ef => {
+                // 17ef  lxi h,3016h
+                m.set_hl(0x3016);
+                m.tick(10, 0x17f2)?;
+                // 17f2  mvi c,04h
+                m.c = 0x04;
+                m.tick(7, 0x17f4)?;
+                // 17f4  call 0a93h
+                m.push(0x17f7)?;
+                m.pc = 0x0a93;
+                m.tick(17, 0x0a93)?;
+                m.call(0x17f7)?;
+                // 17f7  call 0ab1h
+                m.push(0x17fa)?;
+                m.pc = 0x0ab1;
+                m.tick(17, 0x0ab1)?;
+                m.call(0x17fa)?;
+                // 17fa  xra a
+                let value = m.a;
+                m.a ^= value;
+                m.flags = SZP[m.a as usize] | if m.a > 0xf { AUX_CARRY } else { 0 };
+                m.tick(4, 0x17fb)?;
+                // 17fb  sta 209ah
+                m.write(0x209a, m.a)?;
+                m.tick(13, 0x17fe)?;
+                // 17fe  sta 2093h
+                m.write(0x2093, m.a)?;
+                m.tick(13, 0x1801)?;
+                // 1801  jmp 16c9h
+                m.pc = 0x16c9;
+                m.tick(10, 0x16c9)?;
+            },
+            0x18df => {
+                // 18df  mvi a,08h
+                m.a = 0x08;
+                m.tick(7, 0x18e1)?;
+                // 18e1  sta 20cfh
+                m.write(0x20cf, m.a)?;
+                m.tick(13, 0x18e4)?;
+                // 18e4  jmp 0aeah
+                m.pc = 0x0aea;
+                m.tick(10, 0x0aea)?;
+            },
+            _ => return Ok(()),
+        }
+    }
+}
+
+fn sub_1804(m: &mut Machine) -> Result<(), Stop> {
+    loop {
+        match m.pc {
+            0x0707 => {
+                // 0707  mvi b,0feh
+                m.b = 0xfe;
+                m.tick(7, 0x0709)?;
+                // 0709  jmp 19dch
+                m.pc = 0x19dc;
+                m.tick(10, 0x19dc)?;
+            },
+            0x1804 => {
+                // 1804  lxi h,2084h
+                m.set_hl(0x2084);
+                m.tick(10, 0x1807)?;
+                // 1807  mov a,m
+                m.a = m.read(m.hl())?;
+                m.tick(7, 0x1808)?;
+                // 1808  ana a
+                let value = m.a;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(4, 0x1809)?;
+                // 1809  jz 0707h
+                m.pc = if m.flags & ZERO != 0 { 0x0707 } else { 0x180c };
+                m.tick(10, m.pc)?;
+            },
+            0x180c => {
+                // 180c  inx h
+                m.set_hl(m.hl().wrapping_add(1));
+                m.tick(5, 0x180d)?;
+                // 180d  mov a,m
+                m.a = m.read(m.hl())?;
+                m.tick(7, 0x180e)?;
+                // 180e  ana a
+                let value = m.a;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(4, 0x180f)?;
+                // 180f  rnz
+                if m.flags & ZERO == 0 {
+                    let target = m.pop()?;
+                    m.pc = target;
+                    m.tick(11, target)?;
+                    return Ok(());
+                }
+                m.tick(5, 0x1810)?;
+                // 1810  mvi b,01h
+                m.b = 0x01;
+                m.tick(7, 0x1812)?;
+                // 1812  jmp 18fah
+                m.pc = 0x18fa;
+                m.tick(10, 0x18fa)?;
+            },
+            0x18fa => {
+                // 18fa  lda 2094h
+                m.a = m.read(0x2094)?;
+                m.tick(13, 0x18fd)?;
+                // 18fd  ora b
+                let value = m.b;
+                m.a |= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(4, 0x18fe)?;
+                // 18fe  sta 2094h
+                m.write(0x2094, m.a)?;
+                m.tick(13, 0x1901)?;
+                // 1901  out 03h
+                m.output(0x03, m.a)?;
+                m.tick(10, 0x1903)?;
+                // 1903  ret
+                let target = m.pop()?;
+                m.pc = target;
+                m.tick(10, target)?;
+                return Ok(());
+            },
+            0x19dc => {
+                // 19dc  lda 2094h
+                m.a = m.read(0x2094)?;
+                m.tick(13, 0x19df)?;
+                // 19df  ana b
+                let value = m.b;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(4, 0x19e0)?;
+                // 19e0  sta 2094h
+                m.write(0x2094, m.a)?;
+                m.tick(13, 0x19e3)?;
+                // 19e3  out 03h
+                m.output(0x03, m.a)?;
+                m.tick(10, 0x19e5)?;
+                // 19e5  ret
+                let target = m.pop()?;
+                m.pc = target;
+                m.tick(10, target)?;
+                return Ok(());
+            },
+            _ => return Ok(()),
+        }
+    }
+}
+
+fn sub_1815(m: &mut Machine) -> Result<(), Stop> {
+    loop {
+        match m.pc {
+            0x1815 => {
+                // 1815  lxi h,2810h
+                m.set_hl(0x2810);
+                m.tick(10, 0x1818)?;
+                // 1818  lxi d,1ca3h
+                m.set_de(0x1ca3);
+                m.tick(10, 0x181b)?;
+                // 181b  mvi c,15h
+                m.c = 0x15;
+                m.tick(7, 0x181d)?;
+                // 181d  call 08f3h
+                m.push(0x1820)?;
+                m.pc = 0x08f3;
+                m.tick(17, 0x08f3)?;
+                m.call(0x1820)?;
+                // 1820  mvi a,0ah
+                m.a = 0x0a;
+                m.tick(7, 0x1822)?;
+                // 1822  sta 206ch
+                m.write(0x206c, m.a)?;
+                m.tick(13, 0x1825)?;
+                // 1825  lxi b,1dbeh
+                m.set_bc(0x1dbe);
+                m.tick(10, 0x1828)?;
+                m.pc = 0x1828;
+            },
+            0x1828 => {
+                // 1828  call 1856h
+                m.push(0x182b)?;
+                m.pc = 0x1856;
+                m.tick(17, 0x1856)?;
+                m.call(0x182b)?;
+                // 182b  jc 1837h
+                m.pc = if m.flags & CARRY != 0 { 0x1837 } else { 0x182e };
+                m.tick(10, m.pc)?;
+            },
+            0x182e => {
+                // 182e  call 1844h
+                m.push(0x1831)?;
+                m.pc = 0x1844;
+                m.tick(17, 0x1844)?;
+                m.call(0x1831)?;
+                // 1831  jmp 1828h
+                m.pc = 0x1828;
+                m.tick(10, 0x1828)?;
+            },
+            0x1837 => {
+                // 1837  lxi b,1dcfh
+                m.set_bc(0x1dcf);
+                m.tick(10, 0x183a)?;
+                m.pc = 0x183a;
+            },
+            0x183a => {
+                // 183a  call 1856h
+                m.push(0x183d)?;
+                m.pc = 0x1856;
+                m.tick(17, 0x1856)?;
+                m.call(0x183d)?;
+                // 183d  rc
+                if m.flags & CARRY != 0 {
+                    let target = m.pop()?;
+                    m.pc = target;
+                    m.tick(11, target)?;
+                    return Ok(());
+                }
+                m.tick(5, 0x183e)?;
+                // 183e  call 184ch
+                m.push(0x1841)?;
+                m.pc = 0x184c;
+                m.tick(17, 0x184c)?;
+                m.call(0x1841)?;
+                // 1841  jmp 183ah
+                m.pc = 0x183a;
+                m.tick(10, 0x183a)?;
+            },
+            _ => return Ok(()),
+        }
+    }
+}
+
+fn sub_183a(m: &mut Machine) -> Result<(), Stop> {
+    loop {
+        match m.pc {
+            0x183a => {
+                // 183a  call 1856h
+                m.push(0x183d)?;
+                m.pc = 0x1856;
+                m.tick(17, 0x1856)?;
+                m.call(0x183d)?;
+                // 183d  rc
+                if m.flags & CARRY != 0 {
+                    let target = m.pop()?;
+                    m.pc = target;
+                    m.tick(11, target)?;
+                    return Ok(());
+                }
+                m.tick(5, 0x183e)?;
+                // 183e  call 184ch
+                m.push(0x1841)?;
+                m.pc = 0x184c;
+                m.tick(17, 0x184c)?;
+                m.call(0x1841)?;
+                // 1841  jmp 183ah
+                m.pc = 0x183a;
+                m.tick(10, 0x183a)?;
+            },
+            _ => return Ok(()),
+        }
+    }
+}
+
+fn sub_1844(m: &mut Machine) -> Result<(), Stop> {
+    // 1844  push b
+    m.push(m.bc())?;
+    m.tick(11, 0x1845)?;
+    // 1845  mvi b,10h
+    m.b = 0x10;
+    m.tick(7, 0x1847)?;
+    // 1847  call 1439h
+    m.push(0x184a)?;
+    m.pc = 0x1439;
+    m.tick(17, 0x1439)?;
+    m.call(0x184a)?;
+    // 184a  pop b
+    let value = m.pop()?;
+    m.set_bc(value);
+    m.tick(10, 0x184b)?;
+    // 184b  ret
+    let target = m.pop()?;
+    m.pc = target;
+    m.tick(10, target)?;
+    Ok(())
+}
+
+fn sub_184c(m: &mut Machine) -> Result<(), Stop> {
+    // 184c  push b
+    m.push(m.bc())?;
+    m.tick(11, 0x184d)?;
+    // 184d  lda 206ch
+    m.a = m.read(0x206c)?;
+    m.tick(13, 0x1850)?;
+    // 1850  mov c,a
+    m.c = m.a;
+    m.tick(5, 0x1851)?;
+    // 1851  call 0a93h
+    m.push(0x1854)?;
+    m.pc = 0x0a93;
+    m.tick(17, 0x0a93)?;
+    m.call(0x1854)?;
+    // 1854  pop b
+    let value = m.pop()?;
+    m.set_bc(value);
+    m.tick(10, 0x1855)?;
+    // 1855  ret
+    let target = m.pop()?;
+    m.pc = target;
+    m.tick(10, target)?;
+    Ok(())
+}
+
+fn sub_1856(m: &mut Machine) -> Result<(), Stop> {
+    // 1856  ldax b
+    m.a = m.read(m.bc())?;
+    m.tick(7, 0x1857)?;
+    // 1857  cpi 0ffh
+    let value: u8 = 0xff;
+    let result = (m.a as u16).wrapping_sub(value as u16);
+    m.flags = SZP[result as u8 as usize] | (result > 0xff) as u8 | if (m.a & 0xf).wrapping_sub(value & 0xf) > 0xf { AUX_CARRY } else { 0 };
+    m.tick(7, 0x1859)?;
+    // 1859  stc
+    m.flags |= CARRY;
+    m.tick(4, 0x185a)?;
+    // 185a  rz
+    if m.flags & ZERO != 0 {
+        let target = m.pop()?;
+        m.pc = target;
+        m.tick(11, target)?;
+        return Ok(());
+    }
+    m.tick(5, 0x185b)?;
+    // 185b  mov l,a
+    m.l = m.a;
+    m.tick(5, 0x185c)?;
+    // 185c  inx b
+    m.set_bc(m.bc().wrapping_add(1));
+    m.tick(5, 0x185d)?;
+    // 185d  ldax b
+    m.a = m.read(m.bc())?;
+    m.tick(7, 0x185e)?;
+    // 185e  mov h,a
+    m.h = m.a;
+    m.tick(5, 0x185f)?;
+    // 185f  inx b
+    m.set_bc(m.bc().wrapping_add(1));
+    m.tick(5, 0x1860)?;
+    // 1860  ldax b
+    m.a = m.read(m.bc())?;
+    m.tick(7, 0x1861)?;
+    // 1861  mov e,a
+    m.e = m.a;
+    m.tick(5, 0x1862)?;
+    // 1862  inx b
+    m.set_bc(m.bc().wrapping_add(1));
+    m.tick(5, 0x1863)?;
+    // 1863  ldax b
+    m.a = m.read(m.bc())?;
+    m.tick(7, 0x1864)?;
+    // 1864  mov d,a
+    m.d = m.a;
+    m.tick(5, 0x1865)?;
+    // 1865  inx b
+    m.set_bc(m.bc().wrapping_add(1));
+    m.tick(5, 0x1866)?;
+    // 1866  ana a
+    let value = m.a;
+    m.a &= value;
+    m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+    m.tick(4, 0x1867)?;
+    // 1867  ret
+    let target = m.pop()?;
+    m.pc = target;
+    m.tick(10, target)?;
+    Ok(())
+}
+
+fn sub_189e(m: &mut Machine) -> Result<(), Stop> {
+    loop {
+        match m.pc {
+            0x0ab6 => {
+                // 0ab6  mvi a,80h
+                m.a = 0x80;
+                m.tick(7, 0x0ab8)?;
+                // 0ab8  jmp 0ad7h
+                m.pc = 0x0ad7;
+                m.tick(10, 0x0ad7)?;
+            },
+            0x0ad7 => {
+                // 0ad7  sta 20c0h
+                m.write(0x20c0, m.a)?;
+                m.tick(13, 0x0ada)?;
+                m.pc = 0x0ada;
+            },
+            0x0ada => {
+                // 0ada  lda 20c0h
+                m.a = m.read(0x20c0)?;
+                m.tick(13, 0x0add)?;
+                // 0add  ana a
+                let value = m.a;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(4, 0x0ade)?;
+                // 0ade  jnz 0adah
+                m.pc = if m.flags & ZERO == 0 { 0x0ada } else { 0x0ae1 };
+                m.tick(10, m.pc)?;
+            },
+            0x0ae1 => {
+                // 0ae1  ret
+                let target = m.pop()?;
+                m.pc = target;
+                m.tick(10, target)?;
+                return Ok(());
+            },
+            0x189e => {
+                // 189e  lxi h,2050h
+                m.set_hl(0x2050);
+                m.tick(10, 0x18a1)?;
+                // 18a1  lxi d,1bc0h
+                m.set_de(0x1bc0);
+                m.tick(10, 0x18a4)?;
+                // 18a4  mvi b,10h
+                m.b = 0x10;
+                m.tick(7, 0x18a6)?;
+                // 18a6  call 1a32h
+                m.push(0x18a9)?;
+                m.pc = 0x1a32;
+                m.tick(17, 0x1a32)?;
+                m.call(0x18a9)?;
+                // 18a9  mvi a,02h
+                m.a = 0x02;
+                m.tick(7, 0x18ab)?;
+                // 18ab  sta 2080h
+                m.write(0x2080, m.a)?;
+                m.tick(13, 0x18ae)?;
+                // 18ae  mvi a,0ffh
+                m.a = 0xff;
+                m.tick(7, 0x18b0)?;
+                // 18b0  sta 207eh
+                m.write(0x207e, m.a)?;
+                m.tick(13, 0x18b3)?;
+                // 18b3  mvi a,04h
+                m.a = 0x04;
+                m.tick(7, 0x18b5)?;
+                // 18b5  sta 20c1h
+                m.write(0x20c1, m.a)?;
+                m.tick(13, 0x18b8)?;
+                m.pc = 0x18b8;
+            },
+            0x18b8 => {
+                // 18b8  lda 2055h
+                m.a = m.read(0x2055)?;
+                m.tick(13, 0x18bb)?;
+                // 18bb  ani 01h
+                let value: u8 = 0x01;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(7, 0x18bd)?;
+                // 18bd  jz 18b8h
+                m.pc = if m.flags & ZERO != 0 { 0x18b8 } else { 0x18c0 };
+                m.tick(10, m.pc)?;
+            },
+            0x18c0 => {
+                // 18c0  lda 2055h
+                m.a = m.read(0x2055)?;
+                m.tick(13, 0x18c3)?;
+                // 18c3  ani 01h
+                let value: u8 = 0x01;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(7, 0x18c5)?;
+                // 18c5  jnz 18c0h
+                m.pc = if m.flags & ZERO == 0 { 0x18c0 } else { 0x18c8 };
+                m.tick(10, m.pc)?;
+            },
+            0x18c8 => {
+                // 18c8  lxi h,3311h
+                m.set_hl(0x3311);
+                m.tick(10, 0x18cb)?;
+                // 18cb  mvi a,26h
+                m.a = 0x26;
+                m.tick(7, 0x18cd)?;
+                // 18cd  nop
+                m.tick(4, 0x18ce)?;
+                // 18ce  call 08ffh
+                m.push(0x18d1)?;
+                m.pc = 0x08ff;
+                m.tick(17, 0x08ff)?;
+                m.call(0x18d1)?;
+                // 18d1  jmp 0ab6h
+                m.pc = 0x0ab6;
+                m.tick(10, 0x0ab6)?;
+            },
+            _ => return Ok(()),
+        }
+    }
+}
+
+fn sub_18e7(m: &mut Machine) -> Result<(), Stop> {
+    // 18e7  lda 2067h
+    m.a = m.read(0x2067)?;
+    m.tick(13, 0x18ea)?;
+    // 18ea  lxi h,20e7h
+    m.set_hl(0x20e7);
+    m.tick(10, 0x18ed)?;
+    // 18ed  rrc
+    m.flags = (m.flags & !CARRY) | (m.a & 1);
+    m.a = m.a.rotate_right(1);
+    m.tick(4, 0x18ee)?;
+    // 18ee  rnc
+    if m.flags & CARRY == 0 {
+        let target = m.pop()?;
+        m.pc = target;
+        m.tick(11, target)?;
+        return Ok(());
+    }
+    m.tick(5, 0x18ef)?;
+    // 18ef  inx h
+    m.set_hl(m.hl().wrapping_add(1));
+    m.tick(5, 0x18f0)?;
+    // 18f0  ret
+    let target = m.pop()?;
+    m.pc = target;
+    m.tick(10, target)?;
+    Ok(())
+}
+
+fn sub_18f1(m: &mut Machine) -> Result<(), Stop> {
+    // 18f1  mvi b,02h
+    m.b = 0x02;
+    m.tick(7, 0x18f3)?;
+    // 18f3  lda 2082h
+    m.a = m.read(0x2082)?;
+    m.tick(13, 0x18f6)?;
+    // 18f6  dcr a
+    let value = m.a;
+    let value = value.wrapping_sub(1);
+    m.flags = SZP[value as usize] | (m.flags & CARRY) | if value & 0xf != 0xf { AUX_CARRY } else { 0 };
+    m.a = value;
+    m.tick(5, 0x18f7)?;
+    // 18f7  rnz
+    if m.flags & ZERO == 0 {
+        let target = m.pop()?;
+        m.pc = target;
+        m.tick(11, target)?;
+        return Ok(());
+    }
+    m.tick(5, 0x18f8)?;
+    // 18f8  inr b
+    let value = m.b;
+    let value = value.wrapping_add(1);
+    m.flags = SZP[value as usize] | (m.flags & CARRY) | if value & 0xf == 0 { AUX_CARRY } else { 0 };
+    m.b = value;
+    m.tick(5, 0x18f9)?;
+    // 18f9  ret
+    let target = m.pop()?;
+    m.pc = target;
+    m.tick(10, target)?;
+    Ok(())
+}
+
+fn sub_18fa(m: &mut Machine) -> Result<(), Stop> {
+    // 18fa  lda 2094h
+    m.a = m.read(0x2094)?;
+    m.tick(13, 0x18fd)?;
+    // 18fd  ora b
+    let value = m.b;
+    m.a |= value;
+    m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+    m.tick(4, 0x18fe)?;
+    // 18fe  sta 2094h
+    m.write(0x2094, m.a)?;
+    m.tick(13, 0x1901)?;
+    // 1901  out 03h
+    m.output(0x03, m.a)?;
+    m.tick(10, 0x1903)?;
+    // 1903  ret
+    let target = m.pop()?;
+    m.pc = target;
+    m.tick(10, target)?;
+    Ok(())
+}
+
+fn sub_1904(m: &mut Machine) -> Result<(), Stop> {
+    loop {
+        match m.pc {
+            0x01c3 => {
+                // 01c3  mvi b,37h
+                m.b = 0x37;
+                m.tick(7, 0x01c5)?;
+                m.pc = 0x01c5;
+            },
+            0x01c5 => {
+                // 01c5  mvi m,01h
+                m.write(m.hl(), 0x01)?;
+                m.tick(10, 0x01c7)?;
+                // 01c7  inx h
+                m.set_hl(m.hl().wrapping_add(1));
+                m.tick(5, 0x01c8)?;
+                // 01c8  dcr b
+                let value = m.b;
+                let value = value.wrapping_sub(1);
+                m.flags = SZP[value as usize] | (m.flags & CARRY) | if value & 0xf != 0xf { AUX_CARRY } else { 0 };
+                m.b = value;
+                m.tick(5, 0x01c9)?;
+                // 01c9  jnz 01c5h
+                m.pc = if m.flags & ZERO == 0 { 0x01c5 } else { 0x01cc };
+                m.tick(10, m.pc)?;
+            },
+            0x01cc => {
+                // 01cc  ret
+                let target = m.pop()?;
+                m.pc = target;
+                m.tick(10, target)?;
+                return Ok(());
+            },
+            0x1904 => {
+                // 1904  lxi h,2200h
+                m.set_hl(0x2200);
+                m.tick(10, 0x1907)?;
+                // 1907  jmp 01c3h
+                m.pc = 0x01c3;
+                m.tick(10, 0x01c3)?;
+            },
+            _ => return Ok(()),
+        }
+    }
+}
+
+fn sub_190a(m: &mut Machine) -> Result<(), Stop> {
+    loop {
+        match m.pc {
+            0x1597 => {
+                // 1597  lda 200dh
+                m.a = m.read(0x200d)?;
+                m.tick(13, 0x159a)?;
+                // 159a  ana a
+                let value = m.a;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(4, 0x159b)?;
+                // 159b  jnz 15b7h
+                m.pc = if m.flags & ZERO == 0 { 0x15b7 } else { 0x159e };
+                m.tick(10, m.pc)?;
+            },
+            0x159e => {
+                // 159e  lxi h,3ea4h
+                m.set_hl(0x3ea4);
+                m.tick(10, 0x15a1)?;
+                // 15a1  call 15c5h
+                m.push(0x15a4)?;
+                m.pc = 0x15c5;
+                m.tick(17, 0x15c5)?;
+                m.call(0x15a4)?;
+                // 15a4  rnc
+                if m.flags & CARRY == 0 {
+                    let target = m.pop()?;
+                    m.pc = target;
+                    m.tick(11, target)?;
+                    return Ok(());
+                }
+                m.tick(5, 0x15a5)?;
+                // 15a5  mvi b,0feh
+                m.b = 0xfe;
+                m.tick(7, 0x15a7)?;
+                // 15a7  mvi a,01h
+                m.a = 0x01;
+                m.tick(7, 0x15a9)?;
+                m.pc = 0x15a9;
+            },
+            0x15a9 => {
+                // 15a9  sta 200dh
+                m.write(0x200d, m.a)?;
+                m.tick(13, 0x15ac)?;
+                // 15ac  mov a,b
+                m.a = m.b;
+                m.tick(5, 0x15ad)?;
+                // 15ad  sta 2008h
+                m.write(0x2008, m.a)?;
+                m.tick(13, 0x15b0)?;
+                // 15b0  lda 200eh
+                m.a = m.read(0x200e)?;
+                m.tick(13, 0x15b3)?;
+                // 15b3  sta 2007h
+                m.write(0x2007, m.a)?;
+                m.tick(13, 0x15b6)?;
+                // 15b6  ret
+                let target = m.pop()?;
+                m.pc = target;
+                m.tick(10, target)?;
+                return Ok(());
+            },
+            0x15b7 => {
+                // 15b7  lxi h,2524h
+                m.set_hl(0x2524);
+                m.tick(10, 0x15ba)?;
+                // 15ba  call 15c5h
+                m.push(0x15bd)?;
+                m.pc = 0x15c5;
+                m.tick(17, 0x15c5)?;
+                m.call(0x15bd)?;
+                // 15bd  rnc
+                if m.flags & CARRY == 0 {
+                    let target = m.pop()?;
+                    m.pc = target;
+                    m.tick(11, target)?;
+                    return Ok(());
+                }
+                m.tick(5, 0x15be)?;
+                // 15be  call 18f1h
+                m.push(0x15c1)?;
+                m.pc = 0x18f1;
+                m.tick(17, 0x18f1)?;
+                m.call(0x15c1)?;
+                // 15c1  xra a
+                let value = m.a;
+                m.a ^= value;
+                m.flags = SZP[m.a as usize] | if m.a > 0xf { AUX_CARRY } else { 0 };
+                m.tick(4, 0x15c2)?;
+                // 15c2  jmp 15a9h
+                m.pc = 0x15a9;
+                m.tick(10, 0x15a9)?;
+            },
+            0x190a => {
+                // 190a  call 14d8h
+                m.push(0x190d)?;
+                m.pc = 0x14d8;
+                m.tick(17, 0x14d8)?;
+                m.call(0x190d)?;
+                // 190d  jmp 1597h
+                m.pc = 0x1597;
+                m.tick(10, 0x1597)?;
+            },
+            _ => return Ok(()),
+        }
+    }
+}
+
+fn sub_1910(m: &mut Machine) -> Result<(), Stop> {
+    // 1910  lxi h,20e7h
+    m.set_hl(0x20e7);
+    m.tick(10, 0x1913)?;
+    // 1913  lda 2067h
+    m.a = m.read(0x2067)?;
+    m.tick(13, 0x1916)?;
+    // 1916  rrc
+    m.flags = (m.flags & !CARRY) | (m.a & 1);
+    m.a = m.a.rotate_right(1);
+    m.tick(4, 0x1917)?;
+    // 1917  rc
+    if m.flags & CARRY != 0 {
+        let target = m.pop()?;
+        m.pc = target;
+        m.tick(11, target)?;
+        return Ok(());
+    }
+    m.tick(5, 0x1918)?;
+    // 1918  inx h
+    m.set_hl(m.hl().wrapping_add(1));
+    m.tick(5, 0x1919)?;
+    // 1919  ret
+    let target = m.pop()?;
+    m.pc = target;
+    m.tick(10, target)?;
+    Ok(())
+}
+
+fn sub_191a(m: &mut Machine) -> Result<(), Stop> {
+    loop {
+        match m.pc {
+            0x08f3 => {
+                // 08f3  ldax d
+                m.a = m.read(m.de())?;
+                m.tick(7, 0x08f4)?;
+                // 08f4  push d
+                m.push(m.de())?;
+                m.tick(11, 0x08f5)?;
+                // 08f5  call 08ffh
+                m.push(0x08f8)?;
+                m.pc = 0x08ff;
+                m.tick(17, 0x08ff)?;
+                m.call(0x08f8)?;
+                // 08f8  pop d
+                let value = m.pop()?;
+                m.set_de(value);
+                m.tick(10, 0x08f9)?;
+                // 08f9  inx d
+                m.set_de(m.de().wrapping_add(1));
+                m.tick(5, 0x08fa)?;
+                // 08fa  dcr c
+                let value = m.c;
+                let value = value.wrapping_sub(1);
+                m.flags = SZP[value as usize] | (m.flags & CARRY) | if value & 0xf != 0xf { AUX_CARRY } else { 0 };
+                m.c = value;
+                m.tick(5, 0x08fb)?;
+                // 08fb  jnz 08f3h
+                m.pc = if m.flags & ZERO == 0 { 0x08f3 } else { 0x08fe };
+                m.tick(10, m.pc)?;
+            },
+            0x08fe => {
+                // 08fe  ret
+                let target = m.pop()?;
+                m.pc = target;
+                m.tick(10, target)?;
+                return Ok(());
+            },
+            0x191a => {
+                // 191a  mvi c,1ch
+                m.c = 0x1c;
+                m.tick(7, 0x191c)?;
+                // 191c  lxi h,241eh
+                m.set_hl(0x241e);
+                m.tick(10, 0x191f)?;
+                // 191f  lxi d,1ae4h
+                m.set_de(0x1ae4);
+                m.tick(10, 0x1922)?;
+                // 1922  jmp 08f3h
+                m.pc = 0x08f3;
+                m.tick(10, 0x08f3)?;
+            },
+            _ => return Ok(()),
+        }
+    }
+}
+
+fn sub_1925(m: &mut Machine) -> Result<(), Stop> {
+    loop {
+        match m.pc {
+            0x09ad => {
+                // 09ad  mov a,d
+                m.a = m.d;
+                m.tick(5, 0x09ae)?;
+                // 09ae  call 09b2h
+                m.push(0x09b1)?;
+                m.pc = 0x09b2;
+                m.tick(17, 0x09b2)?;
+                m.call(0x09b1)?;
+                // 09b1  mov a,e
+                m.a = m.e;
+                m.tick(5, 0x09b2)?;
+                // 09b2  push d
+                m.push(m.de())?;
+                m.tick(11, 0x09b3)?;
+                // 09b3  push psw
+                m.push((m.a as u16) << 8 | m.flags as u16)?;
+                m.tick(11, 0x09b4)?;
+                // 09b4  rrc
+                m.flags = (m.flags & !CARRY) | (m.a & 1);
+                m.a = m.a.rotate_right(1);
+                m.tick(4, 0x09b5)?;
+                // 09b5  rrc
+                m.flags = (m.flags & !CARRY) | (m.a & 1);
+                m.a = m.a.rotate_right(1);
+                m.tick(4, 0x09b6)?;
+                // 09b6  rrc
+                m.flags = (m.flags & !CARRY) | (m.a & 1);
+                m.a = m.a.rotate_right(1);
+                m.tick(4, 0x09b7)?;
+                // 09b7  rrc
+                m.flags = (m.flags & !CARRY) | (m.a & 1);
+                m.a = m.a.rotate_right(1);
+                m.tick(4, 0x09b8)?;
+                // 09b8  ani 0fh
+                let value: u8 = 0x0f;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(7, 0x09ba)?;
+                // 09ba  call 09c5h
+                m.push(0x09bd)?;
+                m.pc = 0x09c5;
+                m.tick(17, 0x09c5)?;
+                m.call(0x09bd)?;
+                // 09bd  pop psw
+                let value = m.pop()?;
+                m.a = (value >> 8) as u8;
+                m.flags = value as u8 & (SIGN | ZERO | AUX_CARRY | PARITY | CARRY);
+                m.tick(10, 0x09be)?;
+                // 09be  ani 0fh
+                let value: u8 = 0x0f;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(7, 0x09c0)?;
+                // 09c0  call 09c5h
+                m.push(0x09c3)?;
+                m.pc = 0x09c5;
+                m.tick(17, 0x09c5)?;
+                m.call(0x09c3)?;
+                // 09c3  pop d
+                let value = m.pop()?;
+                m.set_de(value);
+                m.tick(10, 0x09c4)?;
+                // 09c4  ret
+                let target = m.pop()?;
+                m.pc = target;
+                m.tick(10, target)?;
+                return Ok(());
+            },
+            0x1925 => {
+                // 1925  lxi h,20f8h
+                m.set_hl(0x20f8);
+                m.tick(10, 0x1928)?;
+                // 1928  jmp 1931h
+                m.pc = 0x1931;
+                m.tick(10, 0x1931)?;
+            },
+            0x1931 => {
+                // 1931  mov e,m
+                m.e = m.read(m.hl())?;
+                m.tick(7, 0x1932)?;
+                // 1932  inx h
+                m.set_hl(m.hl().wrapping_add(1));
+                m.tick(5, 0x1933)?;
+                // 1933  mov d,m
+                m.d = m.read(m.hl())?;
+                m.tick(7, 0x1934)?;
+                // 1934  inx h
+                m.set_hl(m.hl().wrapping_add(1));
+                m.tick(5, 0x1935)?;
+                // 1935  mov a,m
+                m.a = m.read(m.hl())?;
+                m.tick(7, 0x1936)?;
+                // 1936  inx h
+                m.set_hl(m.hl().wrapping_add(1));
+                m.tick(5, 0x1937)?;
+                // 1937  mov h,m
+                m.h = m.read(m.hl())?;
+                m.tick(7, 0x1938)?;
+                // 1938  mov l,a
+                m.l = m.a;
+                m.tick(5, 0x1939)?;
+                // 1939  jmp 09adh
+                m.pc = 0x09ad;
+                m.tick(10, 0x09ad)?;
+            },
+            _ => return Ok(()),
+        }
+    }
+}
+
+fn sub_192b(m: &mut Machine) -> Result<(), Stop> {
+    loop {
+        match m.pc {
+            0x09ad => {
+                // 09ad  mov a,d
+                m.a = m.d;
+                m.tick(5, 0x09ae)?;
+                // 09ae  call 09b2h
+                m.push(0x09b1)?;
+                m.pc = 0x09b2;
+                m.tick(17, 0x09b2)?;
+                m.call(0x09b1)?;
+                // 09b1  mov a,e
+                m.a = m.e;
+                m.tick(5, 0x09b2)?;
+                // 09b2  push d
+                m.push(m.de())?;
+                m.tick(11, 0x09b3)?;
+                // 09b3  push psw
+                m.push((m.a as u16) << 8 | m.flags as u16)?;
+                m.tick(11, 0x09b4)?;
+                // 09b4  rrc
+                m.flags = (m.flags & !CARRY) | (m.a & 1);
+                m.a = m.a.rotate_right(1);
+                m.tick(4, 0x09b5)?;
+                // 09b5  rrc
+                m.flags = (m.flags & !CARRY) | (m.a & 1);
+                m.a = m.a.rotate_right(1);
+                m.tick(4, 0x09b6)?;
+                // 09b6  rrc
+                m.flags = (m.flags & !CARRY) | (m.a & 1);
+                m.a = m.a.rotate_right(1);
+                m.tick(4, 0x09b7)?;
+                // 09b7  rrc
+                m.flags = (m.flags & !CARRY) | (m.a & 1);
+                m.a = m.a.rotate_right(1);
+                m.tick(4, 0x09b8)?;
+                // 09b8  ani 0fh
+                let value: u8 = 0x0f;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(7, 0x09ba)?;
+                // 09ba  call 09c5h
+                m.push(0x09bd)?;
+                m.pc = 0x09c5;
+                m.tick(17, 0x09c5)?;
+                m.call(0x09bd)?;
+                // 09bd  pop psw
+                let value = m.pop()?;
+                m.a = (value >> 8) as u8;
+                m.flags = value as u8 & (SIGN | ZERO | AUX_CARRY | PARITY | CARRY);
+                m.tick(10, 0x09be)?;
+                // 09be  ani 0fh
+                let value: u8 = 0x0f;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(7, 0x09c0)?;
+                // 09c0  call 09c5h
+                m.push(0x09c3)?;
+                m.pc = 0x09c5;
+                m.tick(17, 0x09c5)?;
+                m.call(0x09c3)?;
+                // 09c3  pop d
+                let value = m.pop()?;
+                m.set_de(value);
+                m.tick(10, 0x09c4)?;
+                // 09c4  ret
+                let target = m.pop()?;
+                m.pc = target;
+                m.tick(10, target)?;
+                return Ok(());
+            },
+            0x192b => {
+                // 192b  lxi h,20fch
+                m.set_hl(0x20fc);
+                m.tick(10, 0x192e)?;
+                // 192e  jmp 1931h
+                m.pc = 0x1931;
+                m.tick(10, 0x1931)?;
+            },
+            0x1931 => {
+                // 1931  mov e,m
+                m.e = m.read(m.hl())?;
+                m.tick(7, 0x1932)?;
+                // 1932  inx h
+                m.set_hl(m.hl().wrapping_add(1));
+                m.tick(5, 0x1933)?;
+                // 1933  mov d,m
+                m.d = m.read(m.hl())?;
+                m.tick(7, 0x1934)?;
+                // 1934  inx h
+                m.set_hl(m.hl().wrapping_add(1));
+                m.tick(5, 0x1935)?;
+                // 1935  mov a,m
+                m.a = m.read(m.hl())?;
+                m.tick(7, 0x1936)?;
+                // 1936  inx h
+                m.set_hl(m.hl().wrapping_add(1));
+                m.tick(5, 0x1937)?;
+                // 1937  mov h,m
+                m.h = m.read(m.hl())?;
+                m.tick(7, 0x1938)?;
+                // 1938  mov l,a
+                m.l = m.a;
+                m.tick(5, 0x1939)?;
+                // 1939  jmp 09adh
+                m.pc = 0x09ad;
+                m.tick(10, 0x09ad)?;
+            },
+            _ => return Ok(()),
+        }
+    }
+}
+
+fn sub_1931(m: &mut Machine) -> Result<(), Stop> {
+    loop {
+        match m.pc {
+            0x09ad => {
+                // 09ad  mov a,d
+                m.a = m.d;
+                m.tick(5, 0x09ae)?;
+                // 09ae  call 09b2h
+                m.push(0x09b1)?;
+                m.pc = 0x09b2;
+                m.tick(17, 0x09b2)?;
+                m.call(0x09b1)?;
+                // 09b1  mov a,e
+                m.a = m.e;
+                m.tick(5, 0x09b2)?;
+                // 09b2  push d
+                m.push(m.de())?;
+                m.tick(11, 0x09b3)?;
+                // 09b3  push psw
+                m.push((m.a as u16) << 8 | m.flags as u16)?;
+                m.tick(11, 0x09b4)?;
+                // 09b4  rrc
+                m.flags = (m.flags & !CARRY) | (m.a & 1);
+                m.a = m.a.rotate_right(1);
+                m.tick(4, 0x09b5)?;
+                // 09b5  rrc
+                m.flags = (m.flags & !CARRY) | (m.a & 1);
+                m.a = m.a.rotate_right(1);
+                m.tick(4, 0x09b6)?;
+                // 09b6  rrc
+                m.flags = (m.flags & !CARRY) | (m.a & 1);
+                m.a = m.a.rotate_right(1);
+                m.tick(4, 0x09b7)?;
+                // 09b7  rrc
+                m.flags = (m.flags & !CARRY) | (m.a & 1);
+                m.a = m.a.rotate_right(1);
+                m.tick(4, 0x09b8)?;
+                // 09b8  ani 0fh
+                let value: u8 = 0x0f;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(7, 0x09ba)?;
+                // 09ba  call 09c5h
+                m.push(0x09bd)?;
+                m.pc = 0x09c5;
+                m.tick(17, 0x09c5)?;
+                m.call(0x09bd)?;
+                // 09bd  pop psw
+                let value = m.pop()?;
+                m.a = (value >> 8) as u8;
+                m.flags = value as u8 & (SIGN | ZERO | AUX_CARRY | PARITY | CARRY);
+                m.tick(10, 0x09be)?;
+                // 09be  ani 0fh
+                let value: u8 = 0x0f;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(7, 0x09c0)?;
+                // 09c0  call 09c5h
+                m.push(0x09c3)?;
+                m.pc = 0x09c5;
+                m.tick(17, 0x09c5)?;
+                m.call(0x09c3)?;
+                // 09c3  pop d
+                let value = m.pop()?;
+                m.set_de(value);
+                m.tick(10, 0x09c4)?;
+                // 09c4  ret
+                let target = m.pop()?;
+                m.pc = target;
+                m.tick(10, target)?;
+                return Ok(());
+            },
+            0x1931 => {
+                // 1931  mov e,m
+                m.e = m.read(m.hl())?;
+                m.tick(7, 0x1932)?;
+                // 1932  inx h
+                m.set_hl(m.hl().wrapping_add(1));
+                m.tick(5, 0x1933)?;
+                // 1933  mov d,m
+                m.d = m.read(m.hl())?;
+                m.tick(7, 0x1934)?;
+                // 1934  inx h
+                m.set_hl(m.hl().wrapping_add(1));
+                m.tick(5, 0x1935)?;
+                // 1935  mov a,m
+                m.a = m.read(m.hl())?;
+                m.tick(7, 0x1936)?;
+                // 1936  inx h
+                m.set_hl(m.hl().wrapping_add(1));
+                m.tick(5, 0x1937)?;
+                // 1937  mov h,m
+                m.h = m.read(m.hl())?;
+                m.tick(7, 0x1938)?;
+                // 1938  mov l,a
+                m.l = m.a;
+                m.tick(5, 0x1939)?;
+                // 1939  jmp 09adh
+                m.pc = 0x09ad;
+                m.tick(10, 0x09ad)?;
+            },
+            _ => return Ok(()),
+        }
+    }
+}
+
+fn sub_193c(m: &mut Machine) -> Result<(), Stop> {
+    loop {
+        match m.pc {
+            0x08f3 => {
+                // 08f3  ldax d
+                m.a = m.read(m.de())?;
+                m.tick(7, 0x08f4)?;
+                // 08f4  push d
+                m.push(m.de())?;
+                m.tick(11, 0x08f5)?;
+                // 08f5  call 08ffh
+                m.push(0x08f8)?;
+                m.pc = 0x08ff;
+                m.tick(17, 0x08ff)?;
+                m.call(0x08f8)?;
+                // 08f8  pop d
+                let value = m.pop()?;
+                m.set_de(value);
+                m.tick(10, 0x08f9)?;
+                // 08f9  inx d
+                m.set_de(m.de().wrapping_add(1));
+                m.tick(5, 0x08fa)?;
+                // 08fa  dcr c
+                let value = m.c;
+                let value = value.wrapping_sub(1);
+                m.flags = SZP[value as usize] | (m.flags & CARRY) | if value & 0xf != 0xf { AUX_CARRY } else { 0 };
+                m.c = value;
+                m.tick(5, 0x08fb)?;
+                // 08fb  jnz 08f3h
+                m.pc = if m.flags & ZERO == 0 { 0x08f3 } else { 0x08fe };
+                m.tick(10, m.pc)?;
+            },
+            0x08fe => {
+                // 08fe  ret
+                let target = m.pop()?;
+                m.pc = target;
+                m.tick(10, target)?;
+                return Ok(());
+            },
+            0x193c => {
+                // 193c  mvi c,07h
+                m.c = 0x07;
+                m.tick(7, 0x193e)?;
+                // 193e  lxi h,3501h
+                m.set_hl(0x3501);
+                m.tick(10, 0x1941)?;
+                // 1941  lxi d,1fa9h
+                m.set_de(0x1fa9);
+                m.tick(10, 0x1944)?;
+                // 1944  jmp 08f3h
+                m.pc = 0x08f3;
+                m.tick(10, 0x08f3)?;
+            },
+            _ => return Ok(()),
+        }
+    }
+}
+
+fn sub_1947(m: &mut Machine) -> Result<(), Stop> {
+    loop {
+        match m.pc {
+            0x09b2 => {
+                // 09b2  push d
+                m.push(m.de())?;
+                m.tick(11, 0x09b3)?;
+                // 09b3  push psw
+                m.push((m.a as u16) << 8 | m.flags as u16)?;
+                m.tick(11, 0x09b4)?;
+                // 09b4  rrc
+                m.flags = (m.flags & !CARRY) | (m.a & 1);
+                m.a = m.a.rotate_right(1);
+                m.tick(4, 0x09b5)?;
+                // 09b5  rrc
+                m.flags = (m.flags & !CARRY) | (m.a & 1);
+                m.a = m.a.rotate_right(1);
+                m.tick(4, 0x09b6)?;
+                // 09b6  rrc
+                m.flags = (m.flags & !CARRY) | (m.a & 1);
+                m.a = m.a.rotate_right(1);
+                m.tick(4, 0x09b7)?;
+                // 09b7  rrc
+                m.flags = (m.flags & !CARRY) | (m.a & 1);
+                m.a = m.a.rotate_right(1);
+                m.tick(4, 0x09b8)?;
+                // 09b8  ani 0fh
+                let value: u8 = 0x0f;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(7, 0x09ba)?;
+                // 09ba  call 09c5h
+                m.push(0x09bd)?;
+                m.pc = 0x09c5;
+                m.tick(17, 0x09c5)?;
+                m.call(0x09bd)?;
+                // 09bd  pop psw
+                let value = m.pop()?;
+                m.a = (value >> 8) as u8;
+                m.flags = value as u8 & (SIGN | ZERO | AUX_CARRY | PARITY | CARRY);
+                m.tick(10, 0x09be)?;
+                // 09be  ani 0fh
+                let value: u8 = 0x0f;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(7, 0x09c0)?;
+                // 09c0  call 09c5h
+                m.push(0x09c3)?;
+                m.pc = 0x09c5;
+                m.tick(17, 0x09c5)?;
+                m.call(0x09c3)?;
+                // 09c3  pop d
+                let value = m.pop()?;
+                m.set_de(value);
+                m.tick(10, 0x09c4)?;
+                // 09c4  ret
+                let target = m.pop()?;
+                m.pc = target;
+                m.tick(10, target)?;
+                return Ok(());
+            },
+            0x1947 => {
+                // 1947  lda 20ebh
+                m.a = m.read(0x20eb)?;
+                m.tick(13, 0x194a)?;
+                // 194a  lxi h,3c01h
+                m.set_hl(0x3c01);
+                m.tick(10, 0x194d)?;
+                // 194d  jmp 09b2h
+                m.pc = 0x09b2;
+                m.tick(10, 0x09b2)?;
+            },
+            _ => return Ok(()),
+        }
+    }
+}
+
+fn sub_1950(m: &mut Machine) -> Result<(), Stop> {
+    loop {
+        match m.pc {
+            0x09ad => {
+                // 09ad  mov a,d
+                m.a = m.d;
+                m.tick(5, 0x09ae)?;
+                // 09ae  call 09b2h
+                m.push(0x09b1)?;
+                m.pc = 0x09b2;
+                m.tick(17, 0x09b2)?;
+                m.call(0x09b1)?;
+                // 09b1  mov a,e
+                m.a = m.e;
+                m.tick(5, 0x09b2)?;
+                // 09b2  push d
+                m.push(m.de())?;
+                m.tick(11, 0x09b3)?;
+                // 09b3  push psw
+                m.push((m.a as u16) << 8 | m.flags as u16)?;
+                m.tick(11, 0x09b4)?;
+                // 09b4  rrc
+                m.flags = (m.flags & !CARRY) | (m.a & 1);
+                m.a = m.a.rotate_right(1);
+                m.tick(4, 0x09b5)?;
+                // 09b5  rrc
+                m.flags = (m.flags & !CARRY) | (m.a & 1);
+                m.a = m.a.rotate_right(1);
+                m.tick(4, 0x09b6)?;
+                // 09b6  rrc
+                m.flags = (m.flags & !CARRY) | (m.a & 1);
+                m.a = m.a.rotate_right(1);
+                m.tick(4, 0x09b7)?;
+                // 09b7  rrc
+                m.flags = (m.flags & !CARRY) | (m.a & 1);
+                m.a = m.a.rotate_right(1);
+                m.tick(4, 0x09b8)?;
+                // 09b8  ani 0fh
+                let value: u8 = 0x0f;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(7, 0x09ba)?;
+                // 09ba  call 09c5h
+                m.push(0x09bd)?;
+                m.pc = 0x09c5;
+                m.tick(17, 0x09c5)?;
+                m.call(0x09bd)?;
+                // 09bd  pop psw
+                let value = m.pop()?;
+                m.a = (value >> 8) as u8;
+                m.flags = value as u8 & (SIGN | ZERO | AUX_CARRY | PARITY | CARRY);
+                m.tick(10, 0x09be)?;
+                // 09be  ani 0fh
+                let value: u8 = 0x0f;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(7, 0x09c0)?;
+                // 09c0  call 09c5h
+                m.push(0x09c3)?;
+                m.pc = 0x09c5;
+                m.tick(17, 0x09c5)?;
+                m.call(0x09c3)?;
+                // 09c3  pop d
+                let value = m.pop()?;
+                m.set_de(value);
+                m.tick(10, 0x09c4)?;
+                // 09c4  ret
+                let target = m.pop()?;
+                m.pc = target;
+                m.tick(10, target)?;
+                return Ok(());
+            },
+            0x1931 => {
+                // 1931  mov e,m
+                m.e = m.read(m.hl())?;
+                m.tick(7, 0x1932)?;
+                // 1932  inx h
+                m.set_hl(m.hl().wrapping_add(1));
+                m.tick(5, 0x1933)?;
+                // 1933  mov d,m
+                m.d = m.read(m.hl())?;
+                m.tick(7, 0x1934)?;
+                // 1934  inx h
+                m.set_hl(m.hl().wrapping_add(1));
+                m.tick(5, 0x1935)?;
+                // 1935  mov a,m
+                m.a = m.read(m.hl())?;
+                m.tick(7, 0x1936)?;
+                // 1936  inx h
+                m.set_hl(m.hl().wrapping_add(1));
+                m.tick(5, 0x1937)?;
+                // 1937  mov h,m
+                m.h = m.read(m.hl())?;
+                m.tick(7, 0x1938)?;
+                // 1938  mov l,a
+                m.l = m.a;
+                m.tick(5, 0x1939)?;
+                // 1939  jmp 09adh
+                m.pc = 0x09ad;
+                m.tick(10, 0x09ad)?;
+            },
+            0x1950 => {
+                // 1950  lxi h,20f4h
+                m.set_hl(0x20f4);
+                m.tick(10, 0x1953)?;
+                // 1953  jmp 1931h
+                m.pc = 0x1931;
+                m.tick(10, 0x1931)?;
+            },
+            _ => return Ok(()),
+        }
+    }
+}
+
+fn sub_1956(m: &mut Machine) -> Result<(), Stop> {
+    loop {
+        match m.pc {
+            0x09b2 => {
+                // 09b2  push d
+                m.push(m.de())?;
+                m.tick(11, 0x09b3)?;
+                // 09b3  push psw
+                m.push((m.a as u16) << 8 | m.flags as u16)?;
+                m.tick(11, 0x09b4)?;
+                // 09b4  rrc
+                m.flags = (m.flags & !CARRY) | (m.a & 1);
+                m.a = m.a.rotate_right(1);
+                m.tick(4, 0x09b5)?;
+                // 09b5  rrc
+                m.flags = (m.flags & !CARRY) | (m.a & 1);
+                m.a = m.a.rotate_right(1);
+                m.tick(4, 0x09b6)?;
+                // 09b6  rrc
+                m.flags = (m.flags & !CARRY) | (m.a & 1);
+                m.a = m.a.rotate_right(1);
+                m.tick(4, 0x09b7)?;
+                // 09b7  rrc
+                m.flags = (m.flags & !CARRY) | (m.a & 1);
+                m.a = m.a.rotate_right(1);
+                m.tick(4, 0x09b8)?;
+                // 09b8  ani 0fh
+                let value: u8 = 0x0f;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(7, 0x09ba)?;
+                // 09ba  call 09c5h
+                m.push(0x09bd)?;
+                m.pc = 0x09c5;
+                m.tick(17, 0x09c5)?;
+                m.call(0x09bd)?;
+                // 09bd  pop psw
+                let value = m.pop()?;
+                m.a = (value >> 8) as u8;
+                m.flags = value as u8 & (SIGN | ZERO | AUX_CARRY | PARITY | CARRY);
+                m.tick(10, 0x09be)?;
+                // 09be  ani 0fh
+                let value: u8 = 0x0f;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(7, 0x09c0)?;
+                // 09c0  call 09c5h
+                m.push(0x09c3)?;
+                m.pc = 0x09c5;
+                m.tick(17, 0x09c5)?;
+                m.call(0x09c3)?;
+                // 09c3  pop d
+                let value = m.pop()?;
+                m.set_de(value);
+                m.tick(10, 0x09c4)?;
+                // 09c4  ret
+                let target = m.pop()?;
+                m.pc = target;
+                m.tick(10, target)?;
+                return Ok(());
+            },
+            0x1947 => {
+                // 1947  lda 20ebh
+                m.a = m.read(0x20eb)?;
+                m.tick(13, 0x194a)?;
+                // 194a  lxi h,3c01h
+                m.set_hl(0x3c01);
+                m.tick(10, 0x194d)?;
+                // 194d  jmp 09b2h
+                m.pc = 0x09b2;
+                m.tick(10, 0x09b2)?;
+            },
+            0x1956 => {
+                // 1956  call 1a5ch
+                m.push(0x1959)?;
+                m.pc = 0x1a5c;
+                m.tick(17, 0x1a5c)?;
+                m.call(0x1959)?;
+                // 1959  call 191ah
+                m.push(0x195c)?;
+                m.pc = 0x191a;
+                m.tick(17, 0x191a)?;
+                m.call(0x195c)?;
+                // 195c  call 1925h
+                m.push(0x195f)?;
+                m.pc = 0x1925;
+                m.tick(17, 0x1925)?;
+                m.call(0x195f)?;
+                // 195f  call 192bh
+                m.push(0x1962)?;
+                m.pc = 0x192b;
+                m.tick(17, 0x192b)?;
+                m.call(0x1962)?;
+                // 1962  call 1950h
+                m.push(0x1965)?;
+                m.pc = 0x1950;
+                m.tick(17, 0x1950)?;
+                m.call(0x1965)?;
+                // 1965  call 193ch
+                m.push(0x1968)?;
+                m.pc = 0x193c;
+                m.tick(17, 0x193c)?;
+                m.call(0x1968)?;
+                // 1968  jmp 1947h
+                m.pc = 0x1947;
+                m.tick(10, 0x1947)?;
+            },
+            _ => return Ok(()),
+        }
+    }
+}
+
+fn sub_1979(m: &mut Machine) -> Result<(), Stop> {
+    loop {
+        match m.pc {
+            0x08f3 => {
+                // 08f3  ldax d
+                m.a = m.read(m.de())?;
+                m.tick(7, 0x08f4)?;
+                // 08f4  push d
+                m.push(m.de())?;
+                m.tick(11, 0x08f5)?;
+                // 08f5  call 08ffh
+                m.push(0x08f8)?;
+                m.pc = 0x08ff;
+                m.tick(17, 0x08ff)?;
+                m.call(0x08f8)?;
+                // 08f8  pop d
+                let value = m.pop()?;
+                m.set_de(value);
+                m.tick(10, 0x08f9)?;
+                // 08f9  inx d
+                m.set_de(m.de().wrapping_add(1));
+                m.tick(5, 0x08fa)?;
+                // 08fa  dcr c
+                let value = m.c;
+                let value = value.wrapping_sub(1);
+                m.flags = SZP[value as usize] | (m.flags & CARRY) | if value & 0xf != 0xf { AUX_CARRY } else { 0 };
+                m.c = value;
+                m.tick(5, 0x08fb)?;
+                // 08fb  jnz 08f3h
+                m.pc = if m.flags & ZERO == 0 { 0x08f3 } else { 0x08fe };
+                m.tick(10, m.pc)?;
+            },
+            0x08fe => {
+                // 08fe  ret
+                let target = m.pop()?;
+                m.pc = target;
+                m.tick(10, target)?;
+                return Ok(());
+            },
+            0x193c => {
+                // 193c  mvi c,07h
+                m.c = 0x07;
+                m.tick(7, 0x193e)?;
+                // 193e  lxi h,3501h
+                m.set_hl(0x3501);
+                m.tick(10, 0x1941)?;
+                // 1941  lxi d,1fa9h
+                m.set_de(0x1fa9);
+                m.tick(10, 0x1944)?;
+                // 1944  jmp 08f3h
+                m.pc = 0x08f3;
+                m.tick(10, 0x08f3)?;
+            },
+            0x1979 => {
+                // 1979  call 19d7h
+                m.push(0x197c)?;
+                m.pc = 0x19d7;
+                m.tick(17, 0x19d7)?;
+                m.call(0x197c)?;
+                // 197c  call 1947h
+                m.push(0x197f)?;
+                m.pc = 0x1947;
+                m.tick(17, 0x1947)?;
+                m.call(0x197f)?;
+                // 197f  jmp 193ch
+                m.pc = 0x193c;
+                m.tick(10, 0x193c)?;
+            },
+            _ => return Ok(()),
+        }
+    }
+}
+
+fn sub_1982(m: &mut Machine) -> Result<(), Stop> {
+    // 1982  sta 20c1h
+    m.write(0x20c1, m.a)?;
+    m.tick(13, 0x1985)?;
+    // 1985  ret
+    let target = m.pop()?;
+    m.pc = target;
+    m.tick(10, target)?;
+    Ok(())
+}
+
+fn sub_1988(m: &mut Machine) -> Result<(), Stop> {
+    loop {
+        match m.pc {
+            0x09d6 => {
+                // 09d6  lxi h,2402h
+                m.set_hl(0x2402);
+                m.tick(10, 0x09d9)?;
+                m.pc = 0x09d9;
+            },
+            0x09d9 => {
+                // 09d9  mvi m,00h
+                m.write(m.hl(), 0x00)?;
+                m.tick(10, 0x09db)?;
+                // 09db  inx h
+                m.set_hl(m.hl().wrapping_add(1));
+                m.tick(5, 0x09dc)?;
+                // 09dc  mov a,l
+                m.a = m.l;
+                m.tick(5, 0x09dd)?;
+                // 09dd  ani 1fh
+                let value: u8 = 0x1f;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(7, 0x09df)?;
+                // 09df  cpi 1ch
+                let value: u8 = 0x1c;
+                let result = (m.a as u16).wrapping_sub(value as u16);
+                m.flags = SZP[result as u8 as usize] | (result > 0xff) as u8 | if (m.a & 0xf).wrapping_sub(value & 0xf) > 0xf { AUX_CARRY } else { 0 };
+                m.tick(7, 0x09e1)?;
+                // 09e1  jc 09e8h
+                m.pc = if m.flags & CARRY != 0 { 0x09e8 } else { 0x09e4 };
+                m.tick(10, m.pc)?;
+            },
+            0x09e4 => {
+                // 09e4  lxi d,0006h
+                m.set_de(0x0006);
+                m.tick(10, 0x09e7)?;
+                // 09e7  dad d
+                let result = m.hl() as u32 + m.de() as u32;
+                m.flags = (m.flags & !CARRY) | (result >> 16) as u8;
+                m.set_hl(result as u16);
+                m.tick(10, 0x09e8)?;
+                m.pc = 0x09e8;
+            },
+            0x09e8 => {
+                // 09e8  mov a,h
+                m.a = m.h;
+                m.tick(5, 0x09e9)?;
+                // 09e9  cpi 40h
+                let value: u8 = 0x40;
+                let result = (m.a as u16).wrapping_sub(value as u16);
+                m.flags = SZP[result as u8 as usize] | (result > 0xff) as u8 | if (m.a & 0xf).wrapping_sub(value & 0xf) > 0xf { AUX_CARRY } else { 0 };
+                m.tick(7, 0x09eb)?;
+                // 09eb  jc 09d9h
+                m.pc = if m.flags & CARRY != 0 { 0x09d9 } else { 0x09ee };
+                m.tick(10, m.pc)?;
+            },
+            0x09ee => {
+                // 09ee  ret
+                let target = m.pop()?;
+                m.pc = target;
+                m.tick(10, target)?;
+                return Ok(());
+            },
+            0x1988 => {
+                // 1988  jmp 09d6h
+                m.pc = 0x09d6;
+                m.tick(10, 0x09d6)?;
+            },
+            _ => return Ok(()),
+        }
+    }
+}
+
+fn sub_19d1(m: &mut Machine) -> Result<(), Stop> {
+    // 19d1  mvi a,01h
+    m.a = 0x01;
+    m.tick(7, 0x19d3)?;
+    // 19d3  sta 20e9h
+    m.write(0x20e9, m.a)?;
+    m.tick(13, 0x19d6)?;
+    // 19d6  ret
+    let target = m.pop()?;
+    m.pc = target;
+    m.tick(10, target)?;
+    Ok(())
+}
+
+fn sub_19d7(m: &mut Machine) -> Result<(), Stop> {
+    loop {
+        match m.pc {
+            0x19d3 => {
+                // 19d3  sta 20e9h
+                m.write(0x20e9, m.a)?;
+                m.tick(13, 0x19d6)?;
+                // 19d6  ret
+                let target = m.pop()?;
+                m.pc = target;
+                m.tick(10, target)?;
+                return Ok(());
+            },
+            0x19d7 => {
+                // 19d7  xra a
+                let value = m.a;
+                m.a ^= value;
+                m.flags = SZP[m.a as usize] | if m.a > 0xf { AUX_CARRY } else { 0 };
+                m.tick(4, 0x19d8)?;
+                // 19d8  jmp 19d3h
+                m.pc = 0x19d3;
+                m.tick(10, 0x19d3)?;
+            },
+            _ => return Ok(()),
+        }
+    }
+}
+
+fn sub_19dc(m: &mut Machine) -> Result<(), Stop> {
+    // 19dc  lda 2094h
+    m.a = m.read(0x2094)?;
+    m.tick(13, 0x19df)?;
+    // 19df  ana b
+    let value = m.b;
+    m.a &= value;
+    m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+    m.tick(4, 0x19e0)?;
+    // 19e0  sta 2094h
+    m.write(0x2094, m.a)?;
+    m.tick(13, 0x19e3)?;
+    // 19e3  out 03h
+    m.output(0x03, m.a)?;
+    m.tick(10, 0x19e5)?;
+    // 19e5  ret
+    let target = m.pop()?;
+    m.pc = target;
+    m.tick(10, target)?;
+    Ok(())
+}
+
+fn sub_19e6(m: &mut Machine) -> Result<(), Stop> {
+    loop {
+        match m.pc {
+            0x19e6 => {
+                // 19e6  lxi h,2701h
+                m.set_hl(0x2701);
+                m.tick(10, 0x19e9)?;
+                // 19e9  jz 19fah
+                m.pc = if m.flags & ZERO != 0 { 0x19fa } else { 0x19ec };
+                m.tick(10, m.pc)?;
+            },
+            0x19ec => {
+                // 19ec  lxi d,1c60h
+                m.set_de(0x1c60);
+                m.tick(10, 0x19ef)?;
+                // 19ef  mvi b,10h
+                m.b = 0x10;
+                m.tick(7, 0x19f1)?;
+                // 19f1  mov c,a
+                m.c = m.a;
+                m.tick(5, 0x19f2)?;
+                // 19f2  call 1439h
+                m.push(0x19f5)?;
+                m.pc = 0x1439;
+                m.tick(17, 0x1439)?;
+                m.call(0x19f5)?;
+                // 19f5  mov a,c
+                m.a = m.c;
+                m.tick(5, 0x19f6)?;
+                // 19f6  dcr a
+                let value = m.a;
+                let value = value.wrapping_sub(1);
+                m.flags = SZP[value as usize] | (m.flags & CARRY) | if value & 0xf != 0xf { AUX_CARRY } else { 0 };
+                m.a = value;
+                m.tick(5, 0x19f7)?;
+                // 19f7  jnz 19ech
+                m.pc = if m.flags & ZERO == 0 { 0x19ec } else { 0x19fa };
+                m.tick(10, m.pc)?;
+            },
+            0x19fa => {
+                // 19fa  mvi b,10h
+                m.b = 0x10;
+                m.tick(7, 0x19fc)?;
+                // 19fc  call 14cbh
+                m.push(0x19ff)?;
+                m.pc = 0x14cb;
+                m.tick(17, 0x14cb)?;
+                m.call(0x19ff)?;
+                // 19ff  mov a,h
+                m.a = m.h;
+                m.tick(5, 0x1a00)?;
+                // 1a00  cpi 35h
+                let value: u8 = 0x35;
+                let result = (m.a as u16).wrapping_sub(value as u16);
+                m.flags = SZP[result as u8 as usize] | (result > 0xff) as u8 | if (m.a & 0xf).wrapping_sub(value & 0xf) > 0xf { AUX_CARRY } else { 0 };
+                m.tick(7, 0x1a02)?;
+                // 1a02  jnz 19fah
+                m.pc = if m.flags & ZERO == 0 { 0x19fa } else { 0x1a05 };
+                m.tick(10, m.pc)?;
+            },
+            0x1a05 => {
+                // 1a05  ret
+                let target = m.pop()?;
+                m.pc = target;
+                m.tick(10, target)?;
+                return Ok(());
+            },
+            _ => return Ok(()),
+        }
+    }
+}
+
+fn sub_19fa(m: &mut Machine) -> Result<(), Stop> {
+    loop {
+        match m.pc {
+            0x19fa => {
+                // 19fa  mvi b,10h
+                m.b = 0x10;
+                m.tick(7, 0x19fc)?;
+                // 19fc  call 14cbh
+                m.push(0x19ff)?;
+                m.pc = 0x14cb;
+                m.tick(17, 0x14cb)?;
+                m.call(0x19ff)?;
+                // 19ff  mov a,h
+                m.a = m.h;
+                m.tick(5, 0x1a00)?;
+                // 1a00  cpi 35h
+                let value: u8 = 0x35;
+                let result = (m.a as u16).wrapping_sub(value as u16);
+                m.flags = SZP[result as u8 as usize] | (result > 0xff) as u8 | if (m.a & 0xf).wrapping_sub(value & 0xf) > 0xf { AUX_CARRY } else { 0 };
+                m.tick(7, 0x1a02)?;
+                // 1a02  jnz 19fah
+                m.pc = if m.flags & ZERO == 0 { 0x19fa } else { 0x1a05 };
+                m.tick(10, m.pc)?;
+            },
+            0x1a05 => {
+                // 1a05  ret
+                let target = m.pop()?;
+                m.pc = target;
+                m.tick(10, target)?;
+                return Ok(());
+            },
+            _ => return Ok(()),
+        }
+    }
+}
+
+fn sub_1a32(m: &mut Machine) -> Result<(), Stop> {
+    loop {
+        match m.pc {
+            0x1a32 => {
+                // 1a32  ldax d
+                m.a = m.read(m.de())?;
+                m.tick(7, 0x1a33)?;
+                // 1a33  mov m,a
+                m.write(m.hl(), m.a)?;
+                m.tick(7, 0x1a34)?;
+                // 1a34  inx h
+                m.set_hl(m.hl().wrapping_add(1));
+                m.tick(5, 0x1a35)?;
+                // 1a35  inx d
+                m.set_de(m.de().wrapping_add(1));
+                m.tick(5, 0x1a36)?;
+                // 1a36  dcr b
+                let value = m.b;
+                let value = value.wrapping_sub(1);
+                m.flags = SZP[value as usize] | (m.flags & CARRY) | if value & 0xf != 0xf { AUX_CARRY } else { 0 };
+                m.b = value;
+                m.tick(5, 0x1a37)?;
+                // 1a37  jnz 1a32h
+                m.pc = if m.flags & ZERO == 0 { 0x1a32 } else { 0x1a3a };
+                m.tick(10, m.pc)?;
+            },
+            0x1a3a => {
+                // 1a3a  ret
+                let target = m.pop()?;
+                m.pc = target;
+                m.tick(10, target)?;
+                return Ok(());
+            },
+            _ => return Ok(()),
+        }
+    }
+}
+
+fn sub_1a3b(m: &mut Machine) -> Result<(), Stop> {
+    // 1a3b  mov e,m
+    m.e = m.read(m.hl())?;
+    m.tick(7, 0x1a3c)?;
+    // 1a3c  inx h
+    m.set_hl(m.hl().wrapping_add(1));
+    m.tick(5, 0x1a3d)?;
+    // 1a3d  mov d,m
+    m.d = m.read(m.hl())?;
+    m.tick(7, 0x1a3e)?;
+    // 1a3e  inx h
+    m.set_hl(m.hl().wrapping_add(1));
+    m.tick(5, 0x1a3f)?;
+    // 1a3f  mov a,m
+    m.a = m.read(m.hl())?;
+    m.tick(7, 0x1a40)?;
+    // 1a40  inx h
+    m.set_hl(m.hl().wrapping_add(1));
+    m.tick(5, 0x1a41)?;
+    // 1a41  mov c,m
+    m.c = m.read(m.hl())?;
+    m.tick(7, 0x1a42)?;
+    // 1a42  inx h
+    m.set_hl(m.hl().wrapping_add(1));
+    m.tick(5, 0x1a43)?;
+    // 1a43  mov b,m
+    m.b = m.read(m.hl())?;
+    m.tick(7, 0x1a44)?;
+    // 1a44  mov h,c
+    m.h = m.c;
+    m.tick(5, 0x1a45)?;
+    // 1a45  mov l,a
+    m.l = m.a;
+    m.tick(5, 0x1a46)?;
+    // 1a46  ret
+    let target = m.pop()?;
+    m.pc = target;
+    m.tick(10, target)?;
+    Ok(())
+}
+
+fn sub_1a5c(m: &mut Machine) -> Result<(), Stop> {
+    loop {
+        match m.pc {
+            0x1a5c => {
+                // 1a5c  lxi h,2400h
+                m.set_hl(0x2400);
+                m.tick(10, 0x1a5f)?;
+                m.pc = 0x1a5f;
+            },
+            0x1a5f => {
+                // 1a5f  mvi m,00h
+                m.write(m.hl(), 0x00)?;
+                m.tick(10, 0x1a61)?;
+                // 1a61  inx h
+                m.set_hl(m.hl().wrapping_add(1));
+                m.tick(5, 0x1a62)?;
+                // 1a62  mov a,h
+                m.a = m.h;
+                m.tick(5, 0x1a63)?;
+                // 1a63  cpi 40h
+                let value: u8 = 0x40;
+                let result = (m.a as u16).wrapping_sub(value as u16);
+                m.flags = SZP[result as u8 as usize] | (result > 0xff) as u8 | if (m.a & 0xf).wrapping_sub(value & 0xf) > 0xf { AUX_CARRY } else { 0 };
+                m.tick(7, 0x1a65)?;
+                // 1a65  jnz 1a5fh
+                m.pc = if m.flags & ZERO == 0 { 0x1a5f } else { 0x1a68 };
+                m.tick(10, m.pc)?;
+            },
+            0x1a68 => {
+                // 1a68  ret
+                let target = m.pop()?;
+                m.pc = target;
+                m.tick(10, target)?;
+                return Ok(());
+            },
+            _ => return Ok(()),
+        }
+    }
+}
+
+fn sub_1a69(m: &mut Machine) -> Result<(), Stop> {
+    loop {
+        match m.pc {
+            0x1a69 => {
+                // 1a69  push b
+                m.push(m.bc())?;
+                m.tick(11, 0x1a6a)?;
+                // 1a6a  push h
+                m.push(m.hl())?;
+                m.tick(11, 0x1a6b)?;
+                m.pc = 0x1a6b;
+            },
+            0x1a6b => {
+                // 1a6b  ldax d
+                m.a = m.read(m.de())?;
+                m.tick(7, 0x1a6c)?;
+                // 1a6c  ora m
+                let value = m.read(m.hl())?;
+                m.a |= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(7, 0x1a6d)?;
+                // 1a6d  mov m,a
+                m.write(m.hl(), m.a)?;
+                m.tick(7, 0x1a6e)?;
+                // 1a6e  inx d
+                m.set_de(m.de().wrapping_add(1));
+                m.tick(5, 0x1a6f)?;
+                // 1a6f  inx h
+                m.set_hl(m.hl().wrapping_add(1));
+                m.tick(5, 0x1a70)?;
+                // 1a70  dcr c
+                let value = m.c;
+                let value = value.wrapping_sub(1);
+                m.flags = SZP[value as usize] | (m.flags & CARRY) | if value & 0xf != 0xf { AUX_CARRY } else { 0 };
+                m.c = value;
+                m.tick(5, 0x1a71)?;
+                // 1a71  jnz 1a6bh
+                m.pc = if m.flags & ZERO == 0 { 0x1a6b } else { 0x1a74 };
+                m.tick(10, m.pc)?;
+            },
+            0x1a74 => {
+                // 1a74  pop h
+                let value = m.pop()?;
+                m.set_hl(value);
+                m.tick(10, 0x1a75)?;
+                // 1a75  lxi b,0020h
+                m.set_bc(0x0020);
+                m.tick(10, 0x1a78)?;
+                // 1a78  dad b
+                let result = m.hl() as u32 + m.bc() as u32;
+                m.flags = (m.flags & !CARRY) | (result >> 16) as u8;
+                m.set_hl(result as u16);
+                m.tick(10, 0x1a79)?;
+                // 1a79  pop b
+                let value = m.pop()?;
+                m.set_bc(value);
+                m.tick(10, 0x1a7a)?;
+                // 1a7a  dcr b
+                let value = m.b;
+                let value = value.wrapping_sub(1);
+                m.flags = SZP[value as usize] | (m.flags & CARRY) | if value & 0xf != 0xf { AUX_CARRY } else { 0 };
+                m.b = value;
+                m.tick(5, 0x1a7b)?;
+                // 1a7b  jnz 1a69h
+                m.pc = if m.flags & ZERO == 0 { 0x1a69 } else { 0x1a7e };
+                m.tick(10, m.pc)?;
+            },
+            0x1a7e => {
+                // 1a7e  ret
+                let target = m.pop()?;
+                m.pc = target;
+                m.tick(10, target)?;
+                return Ok(());
+            },
+            _ => return Ok(()),
+        }
+    }
+}
+
+fn sub_1a7f(m: &mut Machine) -> Result<(), Stop> {
+    loop {
+        match m.pc {
+            0x08ff => {
+                // 08ff  lxi d,1e00h
+                m.set_de(0x1e00);
+                m.tick(10, 0x0902)?;
+                // 0902  push h
+                m.push(m.hl())?;
+                m.tick(11, 0x0903)?;
+                // 0903  mvi h,00h
+                m.h = 0x00;
+                m.tick(7, 0x0905)?;
+                // 0905  mov l,a
+                m.l = m.a;
+                m.tick(5, 0x0906)?;
+                // 0906  dad h
+                let result = m.hl() as u32 + m.hl() as u32;
+                m.flags = (m.flags & !CARRY) | (result >> 16) as u8;
+                m.set_hl(result as u16);
+                m.tick(10, 0x0907)?;
+                // 0907  dad h
+                let result = m.hl() as u32 + m.hl() as u32;
+                m.flags = (m.flags & !CARRY) | (result >> 16) as u8;
+                m.set_hl(result as u16);
+                m.tick(10, 0x0908)?;
+                // 0908  dad h
+                let result = m.hl() as u32 + m.hl() as u32;
+                m.flags = (m.flags & !CARRY) | (result >> 16) as u8;
+                m.set_hl(result as u16);
+                m.tick(10, 0x0909)?;
+                // 0909  dad d
+                let result = m.hl() as u32 + m.de() as u32;
+                m.flags = (m.flags & !CARRY) | (result >> 16) as u8;
+                m.set_hl(result as u16);
+                m.tick(10, 0x090a)?;
+                // 090a  xchg
+                std::mem::swap(&mut m.d, &mut m.h);
+                std::mem::swap(&mut m.e, &mut m.l);
+                m.tick(4, 0x090b)?;
+                // 090b  pop h
+                let value = m.pop()?;
+                m.set_hl(value);
+                m.tick(10, 0x090c)?;
+                // 090c  mvi b,08h
+                m.b = 0x08;
+                m.tick(7, 0x090e)?;
+                // 090e  out 06h
+                m.output(0x06, m.a)?;
+                m.tick(10, 0x0910)?;
+                // 0910  jmp 1439h
+                m.pc = 0x1439;
+                m.tick(10, 0x1439)?;
+            },
+            0x09c5 => {
+                // 09c5  adi 1ah
+                let value: u8 = 0x1a;
+                let result = m.a as u16 + value as u16;
+                m.flags = SZP[result as u8 as usize] | (result >> 8) as u8 | if (m.a & 0xf) + (value & 0xf) > 0xf { AUX_CARRY } else { 0 };
+                m.a = result as u8;
+                m.tick(7, 0x09c7)?;
+                // 09c7  jmp 08ffh
+                m.pc = 0x08ff;
+                m.tick(10, 0x08ff)?;
+            },
+            0x1439 => {
+                // 1439  push b
+                m.push(m.bc())?;
+                m.tick(11, 0x143a)?;
+                // 143a  ldax d
+                m.a = m.read(m.de())?;
+                m.tick(7, 0x143b)?;
+                // 143b  mov m,a
+                m.write(m.hl(), m.a)?;
+                m.tick(7, 0x143c)?;
+                // 143c  inx d
+                m.set_de(m.de().wrapping_add(1));
+                m.tick(5, 0x143d)?;
+                // 143d  lxi b,0020h
+                m.set_bc(0x0020);
+                m.tick(10, 0x1440)?;
+                // 1440  dad b
+                let result = m.hl() as u32 + m.bc() as u32;
+                m.flags = (m.flags & !CARRY) | (result >> 16) as u8;
+                m.set_hl(result as u16);
+                m.tick(10, 0x1441)?;
+                // 1441  pop b
+                let value = m.pop()?;
+                m.set_bc(value);
+                m.tick(10, 0x1442)?;
+                // 1442  dcr b
+                let value = m.b;
+                let value = value.wrapping_sub(1);
+                m.flags = SZP[value as usize] | (m.flags & CARRY) | if value & 0xf != 0xf { AUX_CARRY } else { 0 };
+                m.b = value;
+                m.tick(5, 0x1443)?;
+                // 1443  jnz 1439h
+                m.pc = if m.flags & ZERO == 0 { 0x1439 } else { 0x1446 };
+                m.tick(10, m.pc)?;
+            },
+            0x1446 => {
+                // 1446  ret
+                let target = m.pop()?;
+                m.pc = target;
+                m.tick(10, target)?;
+                return Ok(());
+            },
+            0x1a7f => {
+                // 1a7f  call 092eh
+                m.push(0x1a82)?;
+                m.pc = 0x092e;
+                m.tick(17, 0x092e)?;
+                m.call(0x1a82)?;
+                // 1a82  ana a
+                let value = m.a;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(4, 0x1a83)?;
+                // 1a83  rz
+                if m.flags & ZERO != 0 {
+                    let target = m.pop()?;
+                    m.pc = target;
+                    m.tick(11, target)?;
+                    return Ok(());
+                }
+                m.tick(5, 0x1a84)?;
+                // 1a84  push psw
+                m.push((m.a as u16) << 8 | m.flags as u16)?;
+                m.tick(11, 0x1a85)?;
+                // 1a85  dcr a
+                let value = m.a;
+                let value = value.wrapping_sub(1);
+                m.flags = SZP[value as usize] | (m.flags & CARRY) | if value & 0xf != 0xf { AUX_CARRY } else { 0 };
+                m.a = value;
+                m.tick(5, 0x1a86)?;
+                // 1a86  mov m,a
+                m.write(m.hl(), m.a)?;
+                m.tick(7, 0x1a87)?;
+                // 1a87  call 19e6h
+                m.push(0x1a8a)?;
+                m.pc = 0x19e6;
+                m.tick(17, 0x19e6)?;
+                m.call(0x1a8a)?;
+                // 1a8a  pop psw
+                let value = m.pop()?;
+                m.a = (value >> 8) as u8;
+                m.flags = value as u8 & (SIGN | ZERO | AUX_CARRY | PARITY | CARRY);
+                m.tick(10, 0x1a8b)?;
+                // 1a8b  lxi h,2501h
+                m.set_hl(0x2501);
+                m.tick(10, 0x1a8e)?;
+                // 1a8e  ani 0fh
+                let value: u8 = 0x0f;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(7, 0x1a90)?;
+                // 1a90  jmp 09c5h
+                m.pc = 0x09c5;
+                m.tick(10, 0x09c5)?;
+            },
+            _ => return Ok(()),
+        }
+    }
+}
+
+fn sub_1a8b(m: &mut Machine) -> Result<(), Stop> {
+    loop {
+        match m.pc {
+            0x08ff => {
+                // 08ff  lxi d,1e00h
+                m.set_de(0x1e00);
+                m.tick(10, 0x0902)?;
+                // 0902  push h
+                m.push(m.hl())?;
+                m.tick(11, 0x0903)?;
+                // 0903  mvi h,00h
+                m.h = 0x00;
+                m.tick(7, 0x0905)?;
+                // 0905  mov l,a
+                m.l = m.a;
+                m.tick(5, 0x0906)?;
+                // 0906  dad h
+                let result = m.hl() as u32 + m.hl() as u32;
+                m.flags = (m.flags & !CARRY) | (result >> 16) as u8;
+                m.set_hl(result as u16);
+                m.tick(10, 0x0907)?;
+                // 0907  dad h
+                let result = m.hl() as u32 + m.hl() as u32;
+                m.flags = (m.flags & !CARRY) | (result >> 16) as u8;
+                m.set_hl(result as u16);
+                m.tick(10, 0x0908)?;
+                // 0908  dad h
+                let result = m.hl() as u32 + m.hl() as u32;
+                m.flags = (m.flags & !CARRY) | (result >> 16) as u8;
+                m.set_hl(result as u16);
+                m.tick(10, 0x0909)?;
+                // 0909  dad d
+                let result = m.hl() as u32 + m.de() as u32;
+                m.flags = (m.flags & !CARRY) | (result >> 16) as u8;
+                m.set_hl(result as u16);
+                m.tick(10, 0x090a)?;
+                // 090a  xchg
+                std::mem::swap(&mut m.d, &mut m.h);
+                std::mem::swap(&mut m.e, &mut m.l);
+                m.tick(4, 0x090b)?;
+                // 090b  pop h
+                let value = m.pop()?;
+                m.set_hl(value);
+                m.tick(10, 0x090c)?;
+                // 090c  mvi b,08h
+                m.b = 0x08;
+                m.tick(7, 0x090e)?;
+                // 090e  out 06h
+                m.output(0x06, m.a)?;
+                m.tick(10, 0x0910)?;
+                // 0910  jmp 1439h
+                m.pc = 0x1439;
+                m.tick(10, 0x1439)?;
+            },
+            0x09c5 => {
+                // 09c5  adi 1ah
+                let value: u8 = 0x1a;
+                let result = m.a as u16 + value as u16;
+                m.flags = SZP[result as u8 as usize] | (result >> 8) as u8 | if (m.a & 0xf) + (value & 0xf) > 0xf { AUX_CARRY } else { 0 };
+                m.a = result as u8;
+                m.tick(7, 0x09c7)?;
+                // 09c7  jmp 08ffh
+                m.pc = 0x08ff;
+                m.tick(10, 0x08ff)?;
+            },
+            0x1439 => {
+                // 1439  push b
+                m.push(m.bc())?;
+                m.tick(11, 0x143a)?;
+                // 143a  ldax d
+                m.a = m.read(m.de())?;
+                m.tick(7, 0x143b)?;
+                // 143b  mov m,a
+                m.write(m.hl(), m.a)?;
+                m.tick(7, 0x143c)?;
+                // 143c  inx d
+                m.set_de(m.de().wrapping_add(1));
+                m.tick(5, 0x143d)?;
+                // 143d  lxi b,0020h
+                m.set_bc(0x0020);
+                m.tick(10, 0x1440)?;
+                // 1440  dad b
+                let result = m.hl() as u32 + m.bc() as u32;
+                m.flags = (m.flags & !CARRY) | (result >> 16) as u8;
+                m.set_hl(result as u16);
+                m.tick(10, 0x1441)?;
+                // 1441  pop b
+                let value = m.pop()?;
+                m.set_bc(value);
+                m.tick(10, 0x1442)?;
+                // 1442  dcr b
+                let value = m.b;
+                let value = value.wrapping_sub(1);
+                m.flags = SZP[value as usize] | (m.flags & CARRY) | if value & 0xf != 0xf { AUX_CARRY } else { 0 };
+                m.b = value;
+                m.tick(5, 0x1443)?;
+                // 1443  jnz 1439h
+                m.pc = if m.flags & ZERO == 0 { 0x1439 } else { 0x1446 };
+                m.tick(10, m.pc)?;
+            },
+            0x1446 => {
+                // 1446  ret
+                let target = m.pop()?;
+                m.pc = target;
+                m.tick(10, target)?;
+                return Ok(());
+            },
+            0x1a8b => {
+                // 1a8b  lxi h,2501h
+                m.set_hl(0x2501);
+                m.tick(10, 0x1a8e)?;
+                // 1a8e  ani 0fh
+                let value: u8 = 0x0f;
+                m.a &= value;
+                m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);
+                m.tick(7, 0x1a90)?;
+                // 1a90  jmp 09c5h
+                m.pc = 0x09c5;
+                m.tick(10, 0x09c5)?;
+            },
+            _ => return Ok(()),
+        }
+    }
+}
@@ -0,0 +1,316 @@
+use std::fmt;
+
+// just enough JSON for the debug adapter protocol
+#[derive(Clone, Debug, PartialEq)]
+pub enum Json {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<Json>),
+    // members in the order they were written
+    Object(Vec<(String, Json)>),
+}
+
+impl Json {
+    pub fn object(members: Vec<(&str, Json)>) -> Json {
+        Json::Object(members.into_iter().map(|(key, value)| (key.to_string(), value)).collect())
+    }
+
+    pub fn parse(text: &str) -> Result<Json, String> {
+        let mut parser = Parser { text: text.as_bytes(), at: 0 };
+        let value = parser.value()?;
+        parser.whitespace();
+
+        if parser.at < parser.text.len() {
+            return Err(format!("trailing characters at {}", parser.at));
+        }
+        Ok(value)
+    }
+
+    // member of an object, none for anything else
+    pub fn get(&self, key: &str) -> Option<&Json> {
+        match self {
+            Json::Object(members) => members.iter().find(|(name, _)| name == key).map(|(_, value)| value),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Json::String(string) => Some(string),
+            _ => None,
+        }
+    }
+
+    pub fn as_i64(&self) -> Option<i64> {
+        match *self {
+            Json::Number(number) if number.fract() == 0.0 => Some(number as i64),
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match *self {
+            Json::Bool(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn as_array(&self) -> Option<&[Json]> {
+        match self {
+            Json::Array(values) => Some(values),
+            _ => None,
+        }
+    }
+}
+
+impl From<&str> for Json {
+    fn from(value: &str) -> Self {
+        Json::String(value.to_string())
+    }
+}
+
+impl From<String> for Json {
+    fn from(value: String) -> Self {
+        Json::String(value)
+    }
+}
+
+impl From<bool> for Json {
+    fn from(value: bool) -> Self {
+        Json::Bool(value)
+    }
+}
+
+impl From<i64> for Json {
+    fn from(value: i64) -> Self {
+        Json::Number(value as f64)
+    }
+}
+
+impl From<Vec<Json>> for Json {
+    fn from(values: Vec<Json>) -> Self {
+        Json::Array(values)
+    }
+}
+
+impl fmt::Display for Json {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            Json::Null => write!(f, "null"),
+            Json::Bool(value) => write!(f, "{}", value),
+            Json::Number(number) if number.fract() == 0.0 && number.abs() < 1e15 => write!(f, "{}", *number as i64),
+            Json::Number(number) => write!(f, "{}", number),
+            Json::String(string) => write_string(f, string),
+            Json::Array(values) => {
+                write!(f, "[")?;
+                for (i, value) in values.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{}", value)?;
+                }
+                write!(f, "]")
+            },
+            Json::Object(members) => {
+                write!(f, "{{")?;
+                for (i, (key, value)) in members.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write_string(f, key)?;
+                    write!(f, ":{}", value)?;
+                }
+                write!(f, "}}")
+            },
+        }
+    }
+}
+
+fn write_string(f: &mut fmt::Formatter, string: &str) -> Result<(), fmt::Error> {
+    write!(f, "\"")?;
+    for c in string.chars() {
+        match c {
+            '"' => write!(f, "\\\"")?,
+            '\\' => write!(f, "\\\\")?,
+            '\n' => write!(f, "\\n")?,
+            '\r' => write!(f, "\\r")?,
+            '\t' => write!(f, "\\t")?,
+            c if (c as u32) < 0x20 => write!(f, "\\u{:04x}", c as u32)?,
+            c => write!(f, "{}", c)?,
+        }
+    }
+    write!(f, "\"")
+}
+
+struct Parser<'a> {
+    text: &'a [u8],
+    at: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn whitespace(&mut self) {
+        while let Some(b' ') | Some(b'\t') | Some(b'\n') | Some(b'\r') = self.text.get(self.at) {
+            self.at += 1;
+        }
+    }
+
+    fn expect(&mut self, literal: &str) -> Result<(), String> {
+        if self.text[self.at..].starts_with(literal.as_bytes()) {
+            self.at += literal.len();
+            Ok(())
+        } else {
+            Err(format!("expected {} at {}", literal, self.at))
+        }
+    }
+
+    fn value(&mut self) -> Result<Json, String> {
+        self.whitespace();
+
+        match self.text.get(self.at) {
+            Some(b'n') => self.expect("null").map(|_| Json::Null),
+            Some(b't') => self.expect("true").map(|_| Json::Bool(true)),
+            Some(b'f') => self.expect("false").map(|_| Json::Bool(false)),
+            Some(b'"') => self.string().map(Json::String),
+            Some(b'[') => {
+                self.at += 1;
+                let mut values = Vec::new();
+                self.whitespace();
+                if self.text.get(self.at) == Some(&b']') {
+                    self.at += 1;
+                    return Ok(Json::Array(values));
+                }
+                loop {
+                    values.push(self.value()?);
+                    self.whitespace();
+                    match self.text.get(self.at) {
+                        Some(b',') => self.at += 1,
+                        Some(b']') => {
+                            self.at += 1;
+                            return Ok(Json::Array(values));
+                        },
+                        _ => return Err(format!("expected , or ] at {}", self.at)),
+                    }
+                }
+            },
+            Some(b'{') => {
+                self.at += 1;
+                let mut members = Vec::new();
+                self.whitespace();
+                if self.text.get(self.at) == Some(&b'}') {
+                    self.at += 1;
+                    return Ok(Json::Object(members));
+                }
+                loop {
+                    self.whitespace();
+                    let key = self.string()?;
+                    self.whitespace();
+                    self.expect(":")?;
+                    members.push((key, self.value()?));
+                    self.whitespace();
+                    match self.text.get(self.at) {
+                        Some(b',') => self.at += 1,
+                        Some(b'}') => {
+                            self.at += 1;
+                            return Ok(Json::Object(members));
+                        },
+                        _ => return Err(format!("expected , or }} at {}", self.at)),
+                    }
+                }
+            },
+            Some(_) => self.number(),
+            None => Err("unexpected end".to_string()),
+        }
+    }
+
+    fn number(&mut self) -> Result<Json, String> {
+        let start = self.at;
+        while let Some(b'0'..=b'9') | Some(b'-') | Some(b'+') | Some(b'.') | Some(b'e') | Some(b'E') = self.text.get(self.at) {
+            self.at += 1;
+        }
+
+        std::str::from_utf8(&self.text[start..self.at]).ok()
+            .and_then(|number| number.parse().ok())
+            .map(Json::Number)
+            .ok_or_else(|| format!("bad value at {}", start))
+    }
+
+    fn string(&mut self) -> Result<String, String> {
+        self.expect("\"")?;
+        let mut bytes = Vec::new();
+
+        loop {
+            let byte = *self.text.get(self.at).ok_or("unterminated string")?;
+            self.at += 1;
+
+            match byte {
+                b'"' => break,
+                b'\\' => {
+                    let escape = *self.text.get(self.at).ok_or("unterminated string")?;
+                    self.at += 1;
+                    let unescaped = match escape {
+                        b'n' => '\n',
+                        b'r' => '\r',
+                        b't' => '\t',
+                        b'b' => '\u{8}',
+                        b'f' => '\u{c}',
+                        b'u' => {
+                            let digits = self.text.get(self.at..self.at + 4).ok_or("bad escape")?;
+                            self.at += 4;
+                            let code = std::str::from_utf8(digits).ok()
+                                .and_then(|digits| u32::from_str_radix(digits, 16).ok())
+                                .ok_or("bad escape")?;
+                            // surrogate pairs are not needed by the protocol
+                            std::char::from_u32(code).unwrap_or('\u{fffd}')
+                        },
+                        other => other as char,
+                    };
+                    let mut buffer = [0; 4];
+                    bytes.extend_from_slice(unescaped.encode_utf8(&mut buffer).as_bytes());
+                },
+                byte => bytes.push(byte),
+            }
+        }
+
+        String::from_utf8(bytes).map_err(|error| error.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn values_round_trip_through_text() {
+        let value = Json::object(vec![
+            ("seq", 7i64.into()),
+            ("success", true.into()),
+            ("body", Json::object(vec![
+                ("values", vec![Json::Null, Json::Number(-2.5), "x".into(), Json::Array(Vec::new())].into()),
+                ("empty", Json::object(vec![])),
+            ])),
+        ]);
+        let text = value.to_string();
+
+        assert_eq!(text, r#"{"seq":7,"success":true,"body":{"values":[null,-2.5,"x",[]],"empty":{}}}"#);
+        assert_eq!(Json::parse(&text), Ok(value));
+        assert_eq!(Json::parse(" { \"a\" : [ 1 , 2e3 ] } ").unwrap().get("a").and_then(Json::as_array).map(<[Json]>::len), Some(2));
+    }
+
+    #[test]
+    fn strings_escape_quotes_backslashes_and_control_characters() {
+        let string = Json::from("say \"hi\"\\\n\t\u{1}é");
+
+        assert_eq!(string.to_string(), r#""say \"hi\"\\\n\t\u0001é""#);
+        assert_eq!(Json::parse(&string.to_string()), Ok(string));
+        assert_eq!(Json::parse(r#""\u0041\/\b\f\r""#), Ok(Json::from("A/\u{8}\u{c}\r")));
+    }
+
+    #[test]
+    fn malformed_text_is_an_error() {
+        for text in ["", "[1,", "{\"a\" 1}", "\"open", "\"\\u12\"", "nul", "1 2", "-"] {
+            assert!(Json::parse(text).is_err(), "{}", text);
+        }
+    }
+}
@@ -0,0 +1,227 @@
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use crate::image::Image;
+use crate::object::{Object, RelocationKind, Target, ABSOLUTE};
+
+// a linked program
+pub struct Linked {
+    pub image: Image,
+    // every symbol of every object at its address, by address
+    pub symbols: Vec<(String, u16)>,
+    // where the sections and the objects' parts of them went
+    pub map: String,
+}
+
+// the sections of the objects with the same name go one after the other in
+// the order of the objects; a section goes at its placement or after the one
+// before it, the first at 0
+pub fn link(objects: &[(String, Object)], placements: &[(String, u16)]) -> Result<Linked, String> {
+    // the address of every object's part of every section
+    let mut bases: HashMap<(usize, &str), u32> = HashMap::new();
+    let mut map = String::new();
+    let mut next = 0u32;
+
+    writeln!(map, "section          start end   size").unwrap();
+    for name in section_names(objects) {
+        let start = placements.iter()
+            .find(|(placed, _)| placed == name)
+            .map_or(next, |&(_, address)| address as u32);
+
+        let mut end = start;
+        let mut parts = String::new();
+        for (index, (object_name, object)) in objects.iter().enumerate() {
+            if let Some(section) = object.sections.iter().find(|section| section.name == name) {
+                bases.insert((index, name), end);
+                writeln!(parts, "  {:<15}{:04x}  {:04x}  {:04x}", object_name, end, (end + section.size).max(end + 1) - 1, section.size).unwrap();
+                end += section.size;
+            }
+        }
+        if end > 0x10000 {
+            return Err(format!("section {} at {:04x} is larger than memory", name, start));
+        }
+
+        writeln!(map, "{:<17}{:04x}  {:04x}  {:04x}", name, start, end.max(start + 1) - 1, end - start).unwrap();
+        map += &parts;
+        next = end;
+    }
+    for (index, _) in objects.iter().enumerate() {
+        bases.insert((index, ABSOLUTE), 0);
+    }
+
+    let mut symbols = Vec::new();
+    let mut publics: HashMap<&str, (u16, &str)> = HashMap::new();
+    for (index, (object_name, object)) in objects.iter().enumerate() {
+        for symbol in &object.symbols {
+            let base = *bases.get(&(index, symbol.section.as_str()))
+                .ok_or_else(|| format!("{}: {} is in an unknown section {}", object_name, symbol.name, symbol.section))?;
+            let address = (base + symbol.value as u32) as u16;
+
+            if symbol.public {
+                if let Some((_, other)) = publics.insert(&symbol.name, (address, object_name)) {
+                    return Err(format!("{} is public in {} and {}", symbol.name, other, object_name));
+                }
+            }
+            symbols.push((symbol.name.clone(), address));
+        }
+    }
+    symbols.sort_by(|(a, a_address), (b, b_address)| (a_address, a).cmp(&(b_address, b)));
+
+    let mut image = Image::new();
+    for (index, (object_name, object)) in objects.iter().enumerate() {
+        for section in &object.sections {
+            let base = bases[&(index, section.name.as_str())];
+            for (offset, bytes) in &section.chunks {
+                for (i, &byte) in bytes.iter().enumerate() {
+                    let address = base + *offset as u32 + i as u32;
+                    if address > 0xffff || !image.place(address as u16, byte) {
+                        return Err(format!("{}: {} overlaps at {:04x}", object_name, section.name, address));
+                    }
+                }
+            }
+        }
+
+        for relocation in &object.relocations {
+            let target = match &relocation.target {
+                Target::Section(name) => bases.get(&(index, name.as_str())).copied()
+                    .ok_or_else(|| format!("{}: unknown section {}", object_name, name))? as i64,
+                Target::External(name) => publics.get(name.as_str())
+                    .ok_or_else(|| format!("{}: {} is not public in any object", object_name, name))?.0 as i64,
+            };
+            let value = target + relocation.addend;
+            let base = bases.get(&(index, relocation.section.as_str())).copied()
+                .ok_or_else(|| format!("{}: unknown section {}", object_name, relocation.section))?;
+            let address = (base + relocation.offset as u32) as u16;
+
+            match relocation.kind {
+                RelocationKind::Word => {
+                    if !(-0x10000..=0xffff).contains(&value) {
+                        return Err(format!("{}: {:04x} does not fit in a word", object_name, value));
+                    }
+                    image.place(address, value as u8);
+                    image.place(address.wrapping_add(1), (value >> 8) as u8);
+                },
+                RelocationKind::Low => {
+                    image.place(address, value as u8);
+                },
+                RelocationKind::High => {
+                    image.place(address, (value >> 8) as u8);
+                },
+            }
+        }
+
+        if image.entry.is_none() {
+            if let Some((section, offset)) = &object.entry {
+                let base = bases.get(&(index, section.as_str())).copied().unwrap_or(0);
+                image.entry = Some((base + *offset as u32) as u16);
+            }
+        }
+    }
+
+    if let Some(entry) = image.entry {
+        writeln!(map, "\nentry {:04x}", entry).unwrap();
+    }
+    writeln!(map, "\nsymbols").unwrap();
+    for (name, address) in &symbols {
+        writeln!(map, "{:04x} {}", address, name).unwrap();
+    }
+
+    Ok(Linked { image, symbols, map })
+}
+
+// the relocatable sections in the order they first appear
+fn section_names(objects: &[(String, Object)]) -> Vec<&str> {
+    let mut names: Vec<&str> = Vec::new();
+    for (_, object) in objects {
+        for section in &object.sections {
+            if section.name != ABSOLUTE && !names.contains(&section.name.as_str()) {
+                names.push(&section.name);
+            }
+        }
+    }
+    names
+}
+
+// a line of address and name for every symbol, by address, as `asm` and
+// `link` write them
+pub fn symbol_file(symbols: &[(String, u16)]) -> String {
+    let mut symbols = symbols.iter().collect::<Vec<_>>();
+    symbols.sort_by_key(|&(name, value)| (value, name));
+
+    symbols.iter()
+        .map(|(name, value)| format!("{:04x} {}\n", value, name))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assembler;
+    use crate::object::Relocation;
+
+    // an object as `asm` writes it and `link` reads it back
+    fn object(source: &str) -> Object {
+        let text = assembler::assemble(source).unwrap().object().to_string();
+        Object::parse(&text).unwrap()
+    }
+
+    #[test]
+    fn a_call_to_another_object_goes_to_its_public_address() {
+        let main = object("
+            extrn print
+            public start
+            cseg
+    start:  lxi h,message
+            call print
+            hlt
+            dseg
+    message: db 'hi', 0
+            end start
+        ");
+        let print = object("
+            public print
+            cseg
+    print:  mov a,m
+            ora a
+            rz
+            out 1
+            inx h
+            jmp print
+        ");
+
+        // message moves with data, print is wherever print.obj puts it
+        assert_eq!(main.externs, ["print"]);
+        assert_eq!(main.relocations, [
+            Relocation { section: "code".to_string(), offset: 1, kind: RelocationKind::Word, target: Target::Section("data".to_string()), addend: 0 },
+            Relocation { section: "code".to_string(), offset: 4, kind: RelocationKind::Word, target: Target::External("print".to_string()), addend: 0 },
+        ]);
+
+        let objects = [("main.obj".to_string(), main), ("print.obj".to_string(), print)];
+        let linked = link(&objects, &[("data".to_string(), 0x4000)]).unwrap();
+        let (start, bytes) = linked.image.binary();
+
+        // lxi h,message; call print; hlt; then print right after it
+        assert_eq!(start, 0);
+        assert_eq!(&bytes[..8], &[0x21, 0x00, 0x40, 0xcd, 0x07, 0x00, 0x76, 0x7e]);
+        // jmp print
+        assert_eq!(&bytes[0x0d..0x10], &[0xc3, 0x07, 0x00]);
+        assert_eq!(&bytes[0x4000..], b"hi\0");
+        assert_eq!(linked.image.entry, Some(0));
+        assert_eq!(linked.symbols, [("start".to_string(), 0), ("print".to_string(), 7), ("message".to_string(), 0x4000)]);
+        assert_eq!(linked.map, "\
+section          start end   size
+code             0000  000f  0010
+  main.obj       0000  0006  0007
+  print.obj      0007  000f  0009
+data             4000  4002  0003
+  main.obj       4000  4002  0003
+
+entry 0000
+
+symbols
+0000 start
+0007 print
+4000 message
+");
+    }
+}
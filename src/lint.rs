@@ -0,0 +1,292 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::io::{self, Write};
+
+use crate::bus::{CycleKind, MachineCycle};
+use crate::call_stack::{Frame, FrameKind};
+use crate::cpu::instruction_length;
+
+// the memory and IO map the program is checked against
+#[derive(Clone, Debug, PartialEq)]
+pub struct LintConfig {
+    // ROM from 0, RAM up to `ram_end`
+    pub rom_size: usize,
+    pub ram_end: usize,
+    // start of the video RAM, which the stack must stay below
+    pub video_ram: usize,
+    pub input_ports: Vec<u8>,
+    pub output_ports: Vec<u8>,
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Warning {
+    UninitializedRead { address: u16 },
+    RomWrite { address: u16 },
+    StackInVideoRam { address: u16 },
+    ExecutionOutsideRom { address: u16 },
+    // a byte that has been executed is overwritten
+    SelfModifyingCode { address: u16 },
+    UnmappedPort { port: u8, write: bool },
+    // EI inside a handler that kept running instead of returning
+    InterruptsEnabledInHandler,
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match *self {
+            Warning::UninitializedRead { address } => write!(f, "read of uninitialized RAM at {:04x}", address),
+            Warning::RomWrite { address } => write!(f, "write to ROM at {:04x}", address),
+            Warning::StackInVideoRam { address } => write!(f, "stack access in video RAM at {:04x}", address),
+            Warning::ExecutionOutsideRom { address } => write!(f, "execution outside ROM at {:04x}", address),
+            Warning::SelfModifyingCode { address } => write!(f, "write to executed code at {:04x}", address),
+            Warning::UnmappedPort { port, write: false } => write!(f, "input from unmapped port {}", port),
+            Warning::UnmappedPort { port, write: true } => write!(f, "output to unmapped port {}", port),
+            Warning::InterruptsEnabledInHandler => write!(f, "interrupts enabled inside an interrupt handler"),
+        }
+    }
+}
+
+// every distinct warning once, with where and when it was first seen
+#[derive(Clone, Debug, PartialEq)]
+pub struct Finding {
+    pub warning: Warning,
+    // address of the instruction that caused it
+    pub pc: u16,
+    // instructions executed before it was first seen
+    pub instruction: u64,
+    pub count: u64,
+}
+
+pub struct Lint {
+    config: LintConfig,
+    written: Vec<bool>,
+    executed: Vec<bool>,
+    findings: Vec<Finding>,
+    // index into the findings by warning and instruction address
+    seen: HashMap<(Warning, u16), usize>,
+    instructions: u64,
+    operands: u16,
+    from_bus: bool,
+    // an EI inside a handler and how many interrupt frames were active
+    enabled_in_handler: Option<(u16, usize)>,
+}
+
+impl Lint {
+    pub fn new(config: LintConfig) -> Self {
+        Self {
+            config,
+            written: vec![false; 0x10000],
+            executed: vec![false; 0x10000],
+            findings: Vec::new(),
+            seen: HashMap::new(),
+            instructions: 0,
+            operands: 0,
+            from_bus: false,
+            enabled_in_handler: None,
+        }
+    }
+
+    pub fn findings(&self) -> &[Finding] {
+        &self.findings
+    }
+
+    // RAM counts as uninitialized again, the findings are kept
+    pub fn reset(&mut self) {
+        self.written.iter_mut().for_each(|written| *written = false);
+        self.enabled_in_handler = None;
+    }
+
+    fn warn(&mut self, warning: Warning, pc: u16) {
+        match self.seen.get(&(warning, pc)) {
+            Some(&index) => self.findings[index].count += 1,
+            None => {
+                self.seen.insert((warning, pc), self.findings.len());
+                self.findings.push(Finding { warning, pc, instruction: self.instructions, count: 1 });
+            },
+        }
+    }
+
+    fn in_rom(&self, address: u16) -> bool {
+        (address as usize) < self.config.rom_size
+    }
+
+    // the machine cycles of the instruction at `pc` and the call stack after it
+    pub fn record(&mut self, pc: u16, cycles: &[MachineCycle], frames: &[Frame]) {
+        let handlers = frames.iter().filter(|frame| frame.kind == FrameKind::Interrupt).count();
+
+        if let Some((address, active)) = self.enabled_in_handler.take() {
+            if handlers >= active {
+                self.warn(Warning::InterruptsEnabledInHandler, address);
+            }
+        }
+
+        for cycle in cycles {
+            let address = cycle.address;
+
+            match cycle.kind {
+                CycleKind::Fetch | CycleKind::InterruptAck => {
+                    if cycle.kind == CycleKind::Fetch {
+                        if !self.in_rom(address) {
+                            self.warn(Warning::ExecutionOutsideRom { address }, pc);
+                        }
+                        if cycle.data == 0xfb && handlers > 0 {
+                            self.enabled_in_handler = Some((pc, handlers));
+                        }
+                        self.executed[address as usize] = true;
+                    }
+                    self.operands = instruction_length(cycle.data) - 1;
+                    self.from_bus = cycle.kind == CycleKind::InterruptAck;
+                },
+                CycleKind::MemoryRead if self.operands > 0 && self.from_bus => self.operands -= 1,
+                CycleKind::MemoryRead | CycleKind::StackRead => {
+                    if self.operands > 0 {
+                        self.executed[address as usize] = true;
+                        self.operands -= 1;
+                    }
+                    if !self.in_rom(address) && !self.written[address as usize] {
+                        self.warn(Warning::UninitializedRead { address }, pc);
+                    }
+                    if cycle.kind == CycleKind::StackRead {
+                        self.check_stack(address, pc);
+                    }
+                },
+                CycleKind::MemoryWrite | CycleKind::StackWrite => {
+                    if self.in_rom(address) {
+                        self.warn(Warning::RomWrite { address }, pc);
+                    } else if self.executed[address as usize] {
+                        self.warn(Warning::SelfModifyingCode { address }, pc);
+                    }
+                    if cycle.kind == CycleKind::StackWrite {
+                        self.check_stack(address, pc);
+                    }
+                    self.written[address as usize] = true;
+                },
+                CycleKind::InputRead | CycleKind::OutputWrite => {
+                    let port = address as u8;
+                    let write = cycle.kind == CycleKind::OutputWrite;
+                    let ports = if write { &self.config.output_ports } else { &self.config.input_ports };
+
+                    if !ports.contains(&port) {
+                        self.warn(Warning::UnmappedPort { port, write }, pc);
+                    }
+                },
+                _ => (),
+            }
+        }
+
+        self.instructions += 1;
+    }
+
+    fn check_stack(&mut self, address: u16, pc: u16) {
+        let address_range = self.config.video_ram..self.config.ram_end;
+        if address_range.contains(&(address as usize)) {
+            self.warn(Warning::StackInVideoRam { address }, pc);
+        }
+    }
+
+    // findings in the order they were first seen
+    pub fn write_report(&self, out: &mut dyn Write) -> io::Result<()> {
+        writeln!(out, "{} warnings after {} instructions", self.findings.len(), self.instructions)?;
+
+        for finding in &self.findings {
+            writeln!(
+                out, "{:04x}: {} (first at instruction {}, {} times)",
+                finding.pc, finding.warning, finding.instruction, finding.count,
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assembler;
+    use crate::bench::Console;
+    use crate::cpu::State8080;
+    use crate::processor::{Cpu, Interrupt};
+
+    // ROM below 100h, video RAM from 2400h and a port to read and write
+    fn config() -> LintConfig {
+        LintConfig {
+            rom_size: 0x100,
+            ram_end: 0x4000,
+            video_ram: 0x2400,
+            input_ports: vec![1],
+            output_ports: vec![3],
+        }
+    }
+
+    // the warnings of the program up to its HLT, then of `interrupt` up to
+    // the next one
+    fn warnings(source: &str, interrupt: Option<Interrupt>) -> Vec<Warning> {
+        let assembly = assembler::assemble(source).unwrap();
+        let mut cpu = State8080::new();
+        cpu.load_image(&assembly.image).unwrap();
+        cpu.set_lint(Lint::new(config()));
+        let mut console = Console { output: String::new() };
+        while !cpu.halted() {
+            cpu.emulate(&mut console).unwrap();
+        }
+        if let Some(interrupt) = interrupt {
+            cpu.interrupt(interrupt);
+            cpu.emulate(&mut console).unwrap();
+            while !cpu.halted() {
+                cpu.emulate(&mut console).unwrap();
+            }
+        }
+        cpu.lint().unwrap().findings().iter().map(|finding| finding.warning).collect()
+    }
+
+    #[test]
+    fn a_read_of_ram_nothing_wrote_is_uninitialized() {
+        assert_eq!(warnings("lxi h,2000h\nmvi m,1\nmov a,m\nlda 2001h\nhlt", None),
+            vec![Warning::UninitializedRead { address: 0x2001 }]);
+    }
+
+    #[test]
+    fn a_write_below_the_ram_is_a_rom_write() {
+        assert_eq!(warnings("sta 80h\nhlt", None), vec![Warning::RomWrite { address: 0x80 }]);
+    }
+
+    #[test]
+    fn the_stack_is_kept_out_of_video_ram() {
+        assert_eq!(warnings("lxi sp,2401h\npush b\nhlt", None),
+            vec![Warning::StackInVideoRam { address: 0x2400 }]);
+    }
+
+    #[test]
+    fn code_in_ram_runs_outside_rom() {
+        assert_eq!(warnings("mvi a,76h\nsta 2000h\njmp 2000h", None),
+            vec![Warning::ExecutionOutsideRom { address: 0x2000 }]);
+    }
+
+    #[test]
+    fn a_write_over_executed_code_modifies_it() {
+        assert_eq!(warnings("lxi sp,2100h\nmvi a,0c9h\nsta 2000h\ncall 2000h\nsta 2000h\nhlt", None), vec![
+            Warning::ExecutionOutsideRom { address: 0x2000 },
+            Warning::SelfModifyingCode { address: 0x2000 },
+        ]);
+    }
+
+    #[test]
+    fn ports_the_board_does_not_decode_are_unmapped() {
+        assert_eq!(warnings("in 1\nin 2\nout 3\nout 4\nhlt", None), vec![
+            Warning::UnmappedPort { port: 2, write: false },
+            Warning::UnmappedPort { port: 4, write: true },
+        ]);
+    }
+
+    #[test]
+    fn a_handler_that_enables_interrupts_and_keeps_running_is_reported() {
+        let source = "
+                lxi sp,2100h
+                ei
+                hlt
+                org 8
+                ei
+                hlt
+        ";
+        assert_eq!(warnings(source, Some(Interrupt::Rst(1))), vec![Warning::InterruptsEnabledInHandler]);
+    }
+}
@@ -0,0 +1,104 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+// columns of a listing line: the source line number, the address and the
+// bytes of the code it produced, then the source text
+//
+// ; game.asm
+//     12  0100 3e 01       loop:   mvi a,1
+//
+// a line of "; " and a path starts the lines of that source file, paths are
+// relative to the listing
+pub const LINE_COLUMNS: std::ops::Range<usize> = 0..5;
+pub const ADDRESS_COLUMNS: std::ops::Range<usize> = 7..11;
+pub const BYTES_COLUMNS: std::ops::Range<usize> = 12..24;
+
+// a listing line, `data` is the bytes of the code in hex or another note
+// on what the line produced
+pub fn format_line(line: u32, address: Option<u16>, data: &str, source: &str) -> String {
+    let address = address.map_or_else(|| " ".repeat(ADDRESS_COLUMNS.len()), |address| format!("{:04x}", address));
+    format!(
+        "{:>5}  {} {:<width$}{}",
+        line,
+        address,
+        data,
+        source,
+        width = BYTES_COLUMNS.len(),
+    ).trim_end().to_string()
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SourceLine {
+    pub file: PathBuf,
+    pub line: u32,
+    pub address: u16,
+    // bytes of code on the line, continuation lines included
+    pub length: u16,
+}
+
+// maps between source lines and the addresses of their code
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ListingMap {
+    lines: Vec<SourceLine>,
+}
+
+impl ListingMap {
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let text = fs::read_to_string(path)?;
+        let directory = path.parent().unwrap_or_else(|| Path::new(""));
+        Ok(Self::parse(&text, directory))
+    }
+
+    pub fn parse(text: &str, directory: &Path) -> Self {
+        let mut lines: Vec<SourceLine> = Vec::new();
+        let mut file = PathBuf::new();
+
+        for text in text.lines() {
+            if let Some(path) = text.strip_prefix("; ") {
+                file = directory.join(path.trim());
+                continue;
+            }
+
+            let line = match text.get(LINE_COLUMNS).and_then(|line| line.trim().parse().ok()) {
+                Some(line) => line,
+                None => continue,
+            };
+            let address = match text.get(ADDRESS_COLUMNS).and_then(|address| u16::from_str_radix(address, 16).ok()) {
+                Some(address) => address,
+                None => continue,
+            };
+            let length = text.get(BYTES_COLUMNS).unwrap_or_else(|| text.get(BYTES_COLUMNS.start..).unwrap_or(""))
+                .split_whitespace()
+                .count() as u16;
+
+            // long data continues on lines with the same number
+            match lines.last_mut() {
+                Some(last) if last.line == line && last.file == file => last.length += length,
+                _ => lines.push(SourceLine { file: file.clone(), line, address, length }),
+            }
+        }
+
+        Self { lines }
+    }
+
+    // the first line at or after `line` in `file` that has code
+    pub fn line_address(&self, file: &Path, line: u32) -> Option<&SourceLine> {
+        self.lines.iter()
+            .filter(|source| source.line >= line && same_file(&source.file, file))
+            .min_by_key(|source| source.line)
+    }
+
+    // the line whose code contains `address`
+    pub fn source(&self, address: u16) -> Option<&SourceLine> {
+        self.lines.iter()
+            .find(|source| address.wrapping_sub(source.address) < source.length.max(1))
+    }
+}
+
+fn same_file(a: &Path, b: &Path) -> bool {
+    match (a.canonicalize(), b.canonicalize()) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => a == b || (a.file_name().is_some() && a.file_name() == b.file_name()),
+    }
+}
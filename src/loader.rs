@@ -0,0 +1,305 @@
+use std::convert::TryFrom;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::hex;
+use crate::image::Image;
+
+// where CP/M loads and starts a .COM program
+pub const COM_ORIGIN: u16 = 0x100;
+
+// reads a program in the format its name says: Intel HEX (.hex, .ihx),
+// Motorola S-records (.s19, .s28, .s37, .srec, .mot), CP/M (.com), a
+// manifest of files (.manifest) or the bytes of a ROM at 0
+pub fn load(path: &Path) -> Result<Image, String> {
+    load_from(path, &mut Vec::new())
+}
+
+// `including` holds the manifests being loaded, one that is among them
+// would include itself forever
+fn load_from(path: &Path, including: &mut Vec<PathBuf>) -> Result<Image, String> {
+    let extension = path.extension()
+        .map(|extension| extension.to_string_lossy().to_ascii_lowercase())
+        .unwrap_or_default();
+    let read_text = || fs::read_to_string(path).map_err(|error| format!("{}: {}", path.display(), error));
+    let read = || fs::read(path).map_err(|error| format!("{}: {}", path.display(), error));
+
+    let image = match extension.as_str() {
+        "hex" | "ihx" => intel_hex(&read_text()?),
+        "s19" | "s28" | "s37" | "srec" | "mot" => s_records(&read_text()?),
+        "com" => com(&read()?),
+        "manifest" => {
+            let canonical = fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+            if including.contains(&canonical) {
+                return Err(format!("{} includes itself", path.display()));
+            }
+            including.push(canonical);
+            let image = manifest(&read_text()?, path.parent().unwrap_or_else(|| Path::new("")), including);
+            including.pop();
+            image
+        },
+        _ => raw(&read()?, 0),
+    };
+    image.map_err(|error| format!("{}: {}", path.display(), error))
+}
+
+// records of `:`, the byte count, the address, the type, the data and a
+// checksum in hex; the extended address records move the data above 64 KiB,
+// which an 8080 can not reach
+pub fn intel_hex(text: &str) -> Result<Image, String> {
+    let mut image = Image::new();
+    // from the extended segment and linear address records
+    let mut base = 0u32;
+
+    for (index, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let error = |message: &str| format!("line {}: {}", index + 1, message);
+
+        let record = line.strip_prefix(':')
+            .and_then(hex::bytes)
+            .ok_or_else(|| error("not an Intel HEX record"))?;
+        if record.len() < 5 || record.len() != record[0] as usize + 5 {
+            return Err(error("wrong length"));
+        }
+        if record.iter().fold(0u8, |sum, &byte| sum.wrapping_add(byte)) != 0 {
+            return Err(error("bad checksum"));
+        }
+
+        let address = u16::from_be_bytes([record[1], record[2]]) as u32;
+        let data = &record[4..record.len() - 1];
+        match (record[3], data) {
+            (0x00, _) => place(&mut image, base + address, data).map_err(|message| error(&message))?,
+            (0x01, _) => return Ok(image),
+            (0x02, &[high, low]) => base = (u16::from_be_bytes([high, low]) as u32) << 4,
+            (0x04, &[high, low]) => base = (u16::from_be_bytes([high, low]) as u32) << 16,
+            // CS:IP or a 32 bit address
+            (0x03, &[cs_high, cs_low, ip_high, ip_low]) => {
+                let start = ((u16::from_be_bytes([cs_high, cs_low]) as u32) << 4) + u16::from_be_bytes([ip_high, ip_low]) as u32;
+                image.entry = Some(entry(start).map_err(|message| error(&message))?);
+            },
+            (0x05, &[a, b, c, d]) => {
+                image.entry = Some(entry(u32::from_be_bytes([a, b, c, d])).map_err(|message| error(&message))?);
+            },
+            (kind, _) => return Err(error(&format!("bad record of type {:02x}", kind))),
+        }
+    }
+    Err("no end of file record".to_string())
+}
+
+// records of `S`, the type, the byte count, an address of 2, 3 or 4 bytes,
+// the data and a checksum in hex; S1 to S3 carry data, S7 to S9 the start
+pub fn s_records(text: &str) -> Result<Image, String> {
+    let mut image = Image::new();
+
+    for (index, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let error = |message: &str| format!("line {}: {}", index + 1, message);
+
+        let (kind, record) = match (line.get(..1), line.get(1..2), line.get(2..).and_then(hex::bytes)) {
+            (Some("S"), Some(kind), Some(record)) => (kind, record),
+            _ => return Err(error("not an S-record")),
+        };
+        if record.is_empty() || record.len() != record[0] as usize + 1 {
+            return Err(error("wrong length"));
+        }
+        if record.iter().fold(0u8, |sum, &byte| sum.wrapping_add(byte)) != 0xff {
+            return Err(error("bad checksum"));
+        }
+
+        let address_size = match kind {
+            "0" | "1" | "5" | "9" => 2,
+            "2" | "6" | "8" => 3,
+            "3" | "7" => 4,
+            _ => return Err(error(&format!("bad record type S{}", kind))),
+        };
+        if record.len() < address_size + 2 {
+            return Err(error("wrong length"));
+        }
+        let address = record[1..=address_size].iter().fold(0u32, |address, &byte| address << 8 | byte as u32);
+        let data = &record[address_size + 1..record.len() - 1];
+
+        match kind {
+            "1" | "2" | "3" => place(&mut image, address, data).map_err(|message| error(&message))?,
+            "7" | "8" | "9" => image.entry = Some(entry(address).map_err(|message| error(&message))?),
+            // the header and the record counts
+            _ => (),
+        }
+    }
+    Ok(image)
+}
+
+// a CP/M program, loaded and started at 100h
+pub fn com(program: &[u8]) -> Result<Image, String> {
+    let mut image = raw(program, COM_ORIGIN)?;
+    image.entry = Some(COM_ORIGIN);
+    Ok(image)
+}
+
+pub fn raw(bytes: &[u8], address: u16) -> Result<Image, String> {
+    let mut image = Image::new();
+    place(&mut image, address as u32, bytes)?;
+    Ok(image)
+}
+
+// lines of a file and the address in hex to load its bytes at, or of a file
+// in one of the formats `load` knows that says where it goes, and an
+// `entry` line with the address to start at; paths are relative to the
+// manifest and # starts a comment:
+//
+// invaders.h 0000
+// invaders.g 0800
+// patch.hex
+// entry 0000
+//
+// a manifest that includes itself, directly or through others, is an error
+fn manifest(text: &str, directory: &Path, including: &mut Vec<PathBuf>) -> Result<Image, String> {
+    let mut image = Image::new();
+
+    for (index, line) in text.lines().enumerate() {
+        let line = line.split('#').next().unwrap_or("").trim();
+        let error = |message: String| format!("line {}: {}", index + 1, message);
+
+        let parsed = match line.split_whitespace().collect::<Vec<_>>().as_slice() {
+            [] => continue,
+            ["entry", address] => {
+                image.entry = Some(hex_address(address).map_err(error)?);
+                continue;
+            },
+            [file, address] => {
+                let address = hex_address(address).map_err(error)?;
+                let path = directory.join(file);
+                let bytes = fs::read(&path).map_err(|io_error| error(format!("{}: {}", path.display(), io_error)))?;
+                raw(&bytes, address).map_err(|message| error(format!("{}: {}", file, message)))?
+            },
+            [file] => load_from(&directory.join(file), including).map_err(error)?,
+            _ => return Err(error("expected a file and an address".to_string())),
+        };
+
+        for (start, bytes) in parsed.chunks() {
+            place(&mut image, start as u32, &bytes).map_err(error)?;
+        }
+        if let Some(start) = parsed.entry {
+            image.entry.get_or_insert(start);
+        }
+    }
+    Ok(image)
+}
+
+// puts bytes at an address, none of them may go past 64 KiB or where
+// something was loaded already
+fn place(image: &mut Image, address: u32, bytes: &[u8]) -> Result<(), String> {
+    if address as usize + bytes.len() > 0x10000 {
+        return Err(format!("{} bytes at {:x} go past 64 KiB", bytes.len(), address));
+    }
+    for (i, &byte) in bytes.iter().enumerate() {
+        let address = address as u16 + i as u16;
+        if !image.place(address, byte) {
+            return Err(format!("{:04x} is loaded twice", address));
+        }
+    }
+    Ok(())
+}
+
+fn entry(address: u32) -> Result<u16, String> {
+    u16::try_from(address).map_err(|_| format!("start address {:x} is past 64 KiB", address))
+}
+
+fn hex_address(text: &str) -> Result<u16, String> {
+    hex::address(text).ok_or_else(|| format!("{} is not an address", text))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn manifests_that_include_each_other_are_an_error() {
+        let directory = std::env::temp_dir().join(format!("rust-8080-loader-{}", std::process::id()));
+        fs::create_dir_all(&directory).unwrap();
+        fs::write(directory.join("bytes.bin"), [1, 2, 3]).unwrap();
+        fs::write(directory.join("self.manifest"), "bytes.bin 0000\nself.manifest\n").unwrap();
+        fs::write(directory.join("first.manifest"), "second.manifest\n").unwrap();
+        fs::write(directory.join("second.manifest"), "bytes.bin 0100\nfirst.manifest\n").unwrap();
+
+        let itself = load(&directory.join("self.manifest")).err();
+        let each_other = load(&directory.join("first.manifest")).err();
+        fs::remove_dir_all(&directory).unwrap();
+
+        let name = |file: &str| directory.join(file).display().to_string();
+        assert_eq!(itself, Some(format!("{0}: line 2: {0} includes itself", name("self.manifest"))));
+        assert_eq!(each_other, Some(format!(
+            "{}: line 1: {}: line 2: {} includes itself",
+            name("first.manifest"), name("second.manifest"), name("first.manifest"),
+        )));
+    }
+
+    #[test]
+    fn intel_hex_segments_linear_addresses_and_start_records() {
+        // data at segment 10h, then back at linear 0, and CS:IP 0010:0005
+        let image = intel_hex("\
+:020000020010EC
+:02000000AABB99
+:020000040000FA
+:01020000CC31
+:0400000300100005E4
+:00000001FF
+").unwrap();
+        assert_eq!(image.chunks(), [(0x100, vec![0xaa, 0xbb]), (0x200, vec![0xcc])]);
+        assert_eq!(image.entry, Some(0x105));
+
+        let image = intel_hex(":0400000500001234B1\n:00000001FF\n").unwrap();
+        assert_eq!(image.entry, Some(0x1234));
+
+        // linear address 1 puts the data at 10000h
+        let error = intel_hex(":020000040001F9\n:0100000001FE\n:00000001FF\n").err();
+        assert_eq!(error, Some("line 2: 1 bytes at 10000 go past 64 KiB".to_string()));
+    }
+
+    #[test]
+    fn intel_hex_needs_checksums_and_an_end_of_file_record() {
+        assert_eq!(intel_hex(":02000000AABB98\n:00000001FF\n").err(), Some("line 1: bad checksum".to_string()));
+        assert_eq!(intel_hex(":02000000AABB99\n").err(), Some("no end of file record".to_string()));
+    }
+
+    #[test]
+    fn s_records_with_every_address_size() {
+        let image = s_records("\
+S0060000686472BB
+S10501000102F6
+S20500020003F5
+S307000003000405EC
+S5030002FA
+S9030100FB
+").unwrap();
+        assert_eq!(image.chunks(), [(0x100, vec![1, 2]), (0x200, vec![3]), (0x300, vec![4, 5])]);
+        assert_eq!(image.entry, Some(0x100));
+
+        assert_eq!(s_records("S10501000102F7\n").err(), Some("line 1: bad checksum".to_string()));
+    }
+
+    #[test]
+    fn com_programs_load_and_start_at_100h() {
+        let image = com(&[0xc3, 0x00, 0x01]).unwrap();
+        assert_eq!(image.chunks(), [(COM_ORIGIN, vec![0xc3, 0x00, 0x01])]);
+        assert_eq!(image.entry, Some(COM_ORIGIN));
+    }
+
+    #[test]
+    fn intel_hex_dumps_load_back() {
+        let mut dump = Image::new();
+        for address in (0x10..0x38).chain(0xfff0..=0xffff) {
+            dump.place(address, address as u8 ^ 0x5a);
+        }
+        dump.entry = Some(0x1234);
+
+        let image = intel_hex(&dump.intel_hex()).unwrap();
+        assert_eq!(image.chunks(), dump.chunks());
+        assert_eq!(image.entry, dump.entry);
+    }
+}
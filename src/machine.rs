@@ -0,0 +1,702 @@
+use std::cell::Cell;
+use std::convert::TryFrom;
+use std::fs;
+use std::path::Path;
+use std::rc::Rc;
+
+use minifb::Window;
+
+use crate::call_stack::Backtrace;
+use crate::cpu::State8080;
+use crate::error::{CpuError, MachineError, UnmappedPortPolicy};
+use crate::image::Image;
+use crate::memory_map::{Bank, BankSelect, BankedRegion, Callbacks, Device};
+use crate::png::Crc32;
+use crate::processor::{Cpu, Interrupt};
+use crate::space_invader::IOState;
+use crate::symbols::SymbolTable;
+use crate::toml::Toml;
+use crate::z80::StateZ80;
+
+// a machine wired up by a TOML description instead of code: the core and
+// its clock, ROMs checked against their crc32, RAM and mirrors, banks,
+// devices in front of memory and on the ports, the frame buffer and the interrupts raised on which
+// scanline; machines/invaders.toml describes the invaders board. With the
+// 8080 core everything below the lowest RAM is ROM, the z80 core has a
+// flat 64 KiB without mirrors, banks, memory devices or write protection
+pub struct Machine {
+    name: String,
+    cpu: Box<dyn Cpu>,
+    io_state: PortDevices,
+    video: Option<Video>,
+    // cycles into the frame each interrupt is raised at, in order
+    interrupts: Vec<(u64, Signal)>,
+    cycles_per_frame: u64,
+    frame_rate: u64,
+    // cycles run in the current frame
+    frame_cycles: u64,
+    instr_count: u64,
+    cycles: u64,
+    frames: u64,
+    screen: Vec<u32>,
+}
+
+impl Machine {
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let text = fs::read_to_string(path).map_err(|error| format!("{}: {}", path.display(), error))?;
+        let root = Toml::parse(&text).map_err(|error| format!("{}: {}", path.display(), error))?;
+        let directory = path.parent().unwrap_or_else(|| Path::new("."));
+
+        let top = Section::new("the description".to_string(), &root,
+            &["name", "cpu", "memory", "rom", "ram", "bank", "memory_device", "io", "device", "video", "interrupt"])?;
+        let name = top.string("name")?.unwrap_or("machine").to_string();
+
+        let cpu_section = Section::new("[cpu]".to_string(), root.get("cpu").ok_or("the description has no [cpu]")?,
+            &["type", "clock", "entry", "symbols"])?;
+        let clock = cpu_section.required("clock", cpu_section.integer("clock", 1, 1_000_000_000)?)? as u64;
+        let cpu_type = cpu_section.string("type")?.unwrap_or("8080");
+        let cpu = build_cpu(&root, &cpu_section, directory)?;
+
+        let io = optional_section("[io]", &root, "io", &["unmapped"])?;
+        let unmapped_ports = match io.as_ref().map_or(Ok(None), |io| io.string("unmapped"))? {
+            None => UnmappedPortPolicy::OpenBus,
+            Some(name) => UnmappedPortPolicy::from_name(name)
+                .ok_or_else(|| format!("[io] unmapped is open_bus, log or error, not {}", name))?,
+        };
+        let io_state = PortDevices { devices: port_devices(&root)?, unmapped_ports };
+
+        let video_section = optional_section("[video]", &root, "video",
+            &["address", "width", "height", "rotate", "msb_first", "foreground", "background", "frame_rate", "lines"])?;
+        let video = match &video_section {
+            Some(section) => Some(Video::new(section)?),
+            None => None,
+        };
+        // without video the frame is only what interrupts are timed in
+        let frame_rate = video_section.as_ref().map_or(Ok(None), |video| video.integer("frame_rate", 1, 1000))?.unwrap_or(60) as u64;
+        let lines = match (&video_section, &video) {
+            (Some(section), Some(video)) => section.integer("lines", 1, 0x10000)?.map_or(video.height as u64, |lines| lines as u64),
+            _ => 1,
+        };
+        let cycles_per_frame = clock / frame_rate;
+
+        let mut interrupts = Vec::new();
+        for (index, table) in array_of_tables(&root, "interrupt")?.into_iter().enumerate() {
+            let section = Section::new(format!("[[interrupt]] {}", index + 1), table, &["line", "rst", "instruction", "nmi"])?;
+            let line = section.integer("line", 0, lines as i64 - 1)?.unwrap_or(0) as u64;
+            let nmi = section.boolean("nmi")?.unwrap_or(false);
+            let interrupt = match (section.integer("rst", 0, 7)?, section.bytes("instruction")?, nmi) {
+                (Some(n), None, false) => Signal::Int(Interrupt::Rst(n as u8)),
+                (None, Some(bytes), false) if !bytes.is_empty() && bytes.len() <= 3 => {
+                    let mut instruction = [0; 3];
+                    instruction[..bytes.len()].copy_from_slice(&bytes);
+                    Signal::Int(Interrupt::Instruction(instruction))
+                },
+                (None, None, true) if cpu_type == "z80" => Signal::Nmi,
+                (None, None, true) => return Err(format!("{}: the 8080 has no NMI line", section.name)),
+                _ => return Err(format!("{} needs one of rst, an instruction of 1 to 3 bytes or nmi", section.name)),
+            };
+            interrupts.push((line * cycles_per_frame / lines, interrupt));
+        }
+        interrupts.sort_by_key(|&(cycle, _)| cycle);
+
+        let (width, height) = video.as_ref().map_or((0, 0), Video::screen_size);
+        Ok(Machine {
+            name,
+            cpu,
+            io_state,
+            video,
+            interrupts,
+            cycles_per_frame,
+            frame_rate,
+            frame_cycles: 0,
+            instr_count: 0,
+            cycles: 0,
+            frames: 0,
+            screen: vec![0; width * height],
+        })
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    // width and height of the picture, 0 by 0 without video
+    pub fn screen_size(&self) -> (usize, usize) {
+        self.video.as_ref().map_or((0, 0), Video::screen_size)
+    }
+
+    pub fn screen(&self) -> &[u32] {
+        &self.screen
+    }
+
+    pub fn cycles(&self) -> u64 {
+        self.cycles
+    }
+
+    pub fn instructions(&self) -> u64 {
+        self.instr_count
+    }
+
+    pub fn frames(&self) -> u64 {
+        self.frames
+    }
+
+    pub fn backtrace(&self) -> Backtrace {
+        self.cpu.backtrace()
+    }
+
+    pub fn next_frame(&mut self, window: &mut Window) -> Result<(), CpuError> {
+        self.run_frame()?;
+        let (width, height) = self.screen_size();
+        window.update_with_buffer(&self.screen, width, height)
+            .unwrap_or_else(|e| println!("Error while updating window: {}", e));
+
+        std::thread::sleep(std::time::Duration::from_millis(1000 / self.frame_rate));
+        Ok(())
+    }
+
+    // a frame without a window, raising the interrupts on their lines and
+    // drawing the picture at the end
+    pub fn run_frame(&mut self) -> Result<(), CpuError> {
+        for index in 0..self.interrupts.len() {
+            let (cycle, signal) = self.interrupts[index];
+            self.run_until(cycle)?;
+            match signal {
+                Signal::Int(interrupt) => self.cpu.interrupt(interrupt),
+                Signal::Nmi => {
+                    self.cpu.nmi();
+                },
+            }
+        }
+        self.run_until(self.cycles_per_frame)?;
+
+        // what the last instruction ran past the frame counts for the next
+        self.frame_cycles -= self.cycles_per_frame;
+        self.frames += 1;
+        if let Some(video) = &self.video {
+            video.render(&*self.cpu, &mut self.screen);
+        }
+        Ok(())
+    }
+
+    fn run_until(&mut self, cycle: u64) -> Result<(), CpuError> {
+        if self.frame_cycles < cycle {
+            let (cycles, instructions) = self.cpu.run_for(cycle - self.frame_cycles, &mut self.io_state)?;
+            self.instr_count += instructions;
+            self.cycles += cycles;
+            self.frame_cycles += cycles;
+        }
+        Ok(())
+    }
+}
+
+// what an interrupt source does to the core
+#[derive(Clone, Copy)]
+enum Signal {
+    Int(Interrupt),
+    Nmi,
+}
+
+// the core with the ROMs, RAM and banks of the description loaded
+fn build_cpu(root: &Toml, cpu_section: &Section, directory: &Path) -> Result<Box<dyn Cpu>, String> {
+    let entry = cpu_section.address("entry")?.unwrap_or(0);
+
+    let memory = optional_section("[memory]", root, "memory", &["size", "mirror"])?;
+    let size = memory.as_ref().map_or(Ok(None), |memory| memory.integer("size", 1, 0x10000))?.unwrap_or(0x10000) as usize;
+    let mirror = memory.as_ref().map_or(Ok(None), |memory| memory.boolean("mirror"))?.unwrap_or(false);
+
+    let mut image = Image::new();
+    image.entry = Some(entry);
+    for (index, table) in array_of_tables(root, "rom")?.into_iter().enumerate() {
+        let rom = Section::new(format!("[[rom]] {}", index + 1), table, &["file", "address", "size", "crc32"])?;
+        let file = rom.required("file", rom.string("file")?)?;
+        let address = rom.required("address", rom.address("address")?)?;
+        let bytes = fs::read(directory.join(file)).map_err(|error| format!("{}: {}", file, error))?;
+
+        if let Some(size) = rom.integer("size", 1, 0x10000)? {
+            if bytes.len() != size as usize {
+                return Err(format!("{} has {} bytes instead of {}", file, bytes.len(), size));
+            }
+        }
+        if let Some(expected) = rom.integer("crc32", 0, 0xffff_ffff)? {
+            let mut crc = Crc32::new();
+            crc.update(&bytes);
+            if crc.finish() != expected as u32 {
+                return Err(format!("{} has crc32 {:08x} instead of {:08x}", file, crc.finish(), expected));
+            }
+        }
+        if address as usize + bytes.len() > 0x10000 {
+            return Err(format!("{} bytes of {} at {:04x} go past 64 KiB", bytes.len(), file, address));
+        }
+        for (offset, &byte) in bytes.iter().enumerate() {
+            if !image.place(address + offset as u16, byte) {
+                return Err(format!("{} overlaps another ROM at {:04x}", file, address + offset as u16));
+            }
+        }
+    }
+
+    let mut ram = Vec::new();
+    for (index, table) in array_of_tables(root, "ram")?.into_iter().enumerate() {
+        let section = Section::new(format!("[[ram]] {}", index + 1), table, &["start", "size"])?;
+        let start = section.required("start", section.address("start")?)? as u32;
+        let size = section.required("size", section.integer("size", 1, 0x10000)?)? as u32;
+        if start + size > 0x10000 {
+            return Err(format!("{} goes past 64 KiB", section.name));
+        }
+        if let Some((rom, _)) = image.chunks().into_iter().find(|(rom, bytes)| (*rom as u32) < start + size && start < *rom as u32 + bytes.len() as u32) {
+            return Err(format!("{} overlaps the ROM at {:04x}", section.name, rom));
+        }
+        ram.push((start, size));
+    }
+    ram.sort_unstable();
+
+    let mut regions = Vec::new();
+    for (index, table) in array_of_tables(root, "bank")?.into_iter().enumerate() {
+        let section = Section::new(format!("[[bank]] {}", index + 1), table, &["start", "size", "port", "address", "banks"])?;
+        let start = section.required("start", section.address("start")?)?;
+        let size = section.required("size", section.integer("size", 1, 0x10000)?)? as u32;
+        let select = match (section.integer("port", 0, 0xff)?, section.address("address")?) {
+            (Some(port), None) => BankSelect::Port(port as u8),
+            (None, Some(address)) => BankSelect::Address(address),
+            _ => return Err(format!("{} is switched by either a port or an address", section.name)),
+        };
+        let names = section.table.get("banks").and_then(Toml::as_array)
+            .ok_or_else(|| format!("{} needs banks, \"ram\" or a file for each", section.name))?;
+        let mut banks = Vec::new();
+        for name in names {
+            banks.push(match name.as_str() {
+                Some("ram") => Bank::ram(size as usize),
+                Some(file) => Bank::rom(fs::read(directory.join(file)).map_err(|error| format!("{}: {}", file, error))?),
+                None => return Err(format!("{} banks are \"ram\" or file names", section.name)),
+            });
+        }
+        regions.push(BankedRegion::new(start, size, select, banks).map_err(|error| format!("{}: {}", section.name, error))?);
+    }
+
+    let mut devices = Vec::new();
+    for (index, table) in array_of_tables(root, "memory_device")?.into_iter().enumerate() {
+        let section = Section::new(format!("[[memory_device]] {}", index + 1), table, &["model", "start", "size", "value"])?;
+        let start = section.required("start", section.address("start")?)?;
+        let size = section.integer("size", 1, 0x10000)?.unwrap_or(1) as u32;
+        devices.push((section.name.clone(), start, size, memory_device(&section)?));
+    }
+
+    let symbols = match cpu_section.string("symbols")? {
+        Some(file) => Some(SymbolTable::load(&directory.join(file))?),
+        None => None,
+    };
+
+    match cpu_section.string("type")?.unwrap_or("8080") {
+        "8080" => {
+            let mut cpu = State8080::new();
+            cpu.set_memory_size(size)?;
+            cpu.set_memory_mirroring(mirror);
+            cpu.load_image(&image)?;
+
+            // the core protects one block at the bottom of memory
+            if let Some(&(lowest, _)) = ram.first() {
+                let mut end = lowest;
+                for &(start, size) in &ram {
+                    if start > end {
+                        break;
+                    }
+                    end = end.max(start + size);
+                }
+                if end != size as u32 {
+                    return Err(format!("the 8080 core needs one block of RAM up to the end of its {} bytes of memory, not {:04x} to {:04x}", size, lowest, end));
+                }
+                cpu.protect_rom(lowest as usize);
+            }
+            for region in regions {
+                cpu.memory_map_mut().add_region(region)?;
+            }
+            for (name, start, size, device) in devices {
+                cpu.memory_map_mut().attach(start, size, device).map_err(|error| format!("{}: {}", name, error))?;
+            }
+            if let Some(symbols) = symbols {
+                cpu.call_stack_mut().set_symbols(symbols);
+            }
+            Ok(Box::new(cpu))
+        },
+        "z80" => {
+            if size != 0x10000 || mirror || !regions.is_empty() || !devices.is_empty() {
+                return Err("the z80 core has a flat 64 KiB of memory, without mirrors, banks or memory devices".to_string());
+            }
+            if symbols.is_some() {
+                return Err("the z80 core does not keep a call stack to name".to_string());
+            }
+            let (start, bytes) = image.binary();
+            Ok(Box::new(StateZ80::load_from_rom(&bytes, start as usize, entry)))
+        },
+        other => Err(format!("[cpu] type is 8080 or z80, not {}", other)),
+    }
+}
+
+// the input, latch and sink models of the ports at a range of addresses:
+// an input reads its value everywhere and a latch is a single byte
+fn memory_device(section: &Section) -> Result<Box<dyn Device>, String> {
+    let value = section.port("value")?;
+    let device: Box<dyn Device> = match section.required("model", section.string("model")?)? {
+        "input" => {
+            let value = value.unwrap_or(0xff);
+            Box::new(Callbacks::new(move |_| value, |_, _| ()))
+        },
+        "latch" => {
+            let latch = Rc::new(Cell::new(value.unwrap_or(0)));
+            let written = Rc::clone(&latch);
+            Box::new(Callbacks::new(move |_| latch.get(), move |_, value| written.set(value)))
+        },
+        "sink" => Box::new(Callbacks::new(|_| 0xff, |_, _| ())),
+        model => return Err(format!("{} model is input, latch or sink, not {}", section.name, model)),
+    };
+    Ok(device)
+}
+
+fn port_devices(root: &Toml) -> Result<Vec<PortDevice>, String> {
+    let mut devices: Vec<PortDevice> = Vec::new();
+
+    for (index, table) in array_of_tables(root, "device")?.into_iter().enumerate() {
+        let name = format!("[[device]] {}", index + 1);
+        let model = table.get("model").and_then(Toml::as_str).ok_or_else(|| format!("{} needs a model", name))?;
+        let device = match model {
+            "input" | "latch" | "sink" => {
+                let section = Section::new(name, table, &["model", "port", "value"])?;
+                let port = section.required("port", section.port("port")?)?;
+                let value = section.port("value")?;
+                match model {
+                    "input" => PortDevice::Input { port, value: value.unwrap_or(0xff) },
+                    "latch" => PortDevice::Latch { port, value: value.unwrap_or(0) },
+                    _ => PortDevice::Sink { port },
+                }
+            },
+            "shift_register" => {
+                let section = Section::new(name, table, &["model", "data", "offset", "result"])?;
+                PortDevice::ShiftRegister {
+                    data: section.required("data", section.port("data")?)?,
+                    offset: section.required("offset", section.port("offset")?)?,
+                    result: section.required("result", section.port("result")?)?,
+                    register: 0,
+                    shift: 0,
+                }
+            },
+            _ => return Err(format!("{} model is input, latch, sink or shift_register, not {}", name, model)),
+        };
+
+        for other in &devices {
+            if let Some(port) = device.inputs().iter().find(|port| other.inputs().contains(port)) {
+                return Err(format!("port {} is read from two devices", port));
+            }
+            if let Some(port) = device.outputs().iter().find(|port| other.outputs().contains(port)) {
+                return Err(format!("port {} is written to two devices", port));
+            }
+        }
+        devices.push(device);
+    }
+    Ok(devices)
+}
+
+// the built-in models of what sits on the ports
+#[derive(Clone, Debug)]
+enum PortDevice {
+    // switches or buttons nobody presses, always reading the same
+    Input { port: u8, value: u8 },
+    // reads back what was last written
+    Latch { port: u8, value: u8 },
+    // takes writes and does nothing with them, e.g. sound or a watchdog
+    Sink { port: u8 },
+    // the invaders shifter: bytes written to `data` come in from the top of
+    // 16 bits and `result` reads 8 of them, `offset` bits below the top
+    ShiftRegister { data: u8, offset: u8, result: u8, register: u16, shift: u8 },
+}
+
+impl PortDevice {
+    fn inputs(&self) -> Vec<u8> {
+        match *self {
+            PortDevice::Input { port, .. } | PortDevice::Latch { port, .. } => vec![port],
+            PortDevice::Sink { .. } => Vec::new(),
+            PortDevice::ShiftRegister { result, .. } => vec![result],
+        }
+    }
+
+    fn outputs(&self) -> Vec<u8> {
+        match *self {
+            PortDevice::Input { .. } => Vec::new(),
+            PortDevice::Latch { port, .. } | PortDevice::Sink { port } => vec![port],
+            PortDevice::ShiftRegister { data, offset, .. } => vec![data, offset],
+        }
+    }
+
+    fn input(&self, port: u8) -> Option<u8> {
+        match *self {
+            PortDevice::Input { port: own, value } | PortDevice::Latch { port: own, value } if port == own => Some(value),
+            PortDevice::ShiftRegister { result, register, shift, .. } if port == result => Some((register >> (8 - shift)) as u8),
+            _ => None,
+        }
+    }
+
+    // false when the device is not on the port
+    fn output(&mut self, port: u8, written: u8) -> bool {
+        match self {
+            PortDevice::Latch { port: own, value } if port == *own => *value = written,
+            PortDevice::Sink { port: own } if port == *own => (),
+            PortDevice::ShiftRegister { data, register, .. } if port == *data => {
+                *register = *register >> 8 | (written as u16) << 8;
+            },
+            PortDevice::ShiftRegister { offset, shift, .. } if port == *offset => *shift = written & 0b111,
+            _ => return false,
+        }
+        true
+    }
+}
+
+struct PortDevices {
+    devices: Vec<PortDevice>,
+    unmapped_ports: UnmappedPortPolicy,
+}
+
+impl IOState for PortDevices {
+    fn input(&self, port: u8) -> Result<u8, MachineError> {
+        match self.devices.iter().find_map(|device| device.input(port)) {
+            Some(value) => Ok(value),
+            None => self.unmapped_ports.read(port),
+        }
+    }
+
+    fn output(&mut self, port: u8, value: u8) -> Result<(), MachineError> {
+        if self.devices.iter_mut().any(|device| device.output(port, value)) {
+            return Ok(());
+        }
+        self.unmapped_ports.write(port, value)
+    }
+}
+
+// a one bit a pixel frame buffer, rows of `width` pixels from `address` on
+struct Video {
+    address: usize,
+    width: usize,
+    height: usize,
+    // degrees the picture is turned clockwise on the monitor
+    rotate: u16,
+    // the leftmost pixel of a byte is its top bit instead of its lowest
+    msb_first: bool,
+    foreground: u32,
+    background: u32,
+}
+
+impl Video {
+    fn new(section: &Section) -> Result<Self, String> {
+        let video = Video {
+            address: section.required("address", section.address("address")?)? as usize,
+            width: section.required("width", section.integer("width", 8, 0x1000)?)? as usize,
+            height: section.required("height", section.integer("height", 1, 0x1000)?)? as usize,
+            rotate: section.integer("rotate", 0, 270)?.unwrap_or(0) as u16,
+            msb_first: section.boolean("msb_first")?.unwrap_or(false),
+            foreground: section.integer("foreground", 0, 0xff_ffff)?.unwrap_or(0xff_ffff) as u32,
+            background: section.integer("background", 0, 0xff_ffff)?.unwrap_or(0) as u32,
+        };
+        if !video.rotate.is_multiple_of(90) {
+            return Err("[video] rotate is 0, 90, 180 or 270".to_string());
+        }
+        if !video.width.is_multiple_of(8) {
+            return Err("[video] width is a whole number of bytes".to_string());
+        }
+        if video.address + video.width * video.height / 8 > 0x10000 {
+            return Err(format!("[video] {} by {} at {:04x} goes past 64 KiB", video.width, video.height, video.address));
+        }
+        Ok(video)
+    }
+
+    fn screen_size(&self) -> (usize, usize) {
+        if self.rotate % 180 == 90 {
+            (self.height, self.width)
+        } else {
+            (self.width, self.height)
+        }
+    }
+
+    // the frame buffer is read the way a debugger sees it, through the
+    // banks and devices in front of the RAM
+    fn render(&self, cpu: &dyn Cpu, screen: &mut [u32]) {
+        let (screen_width, _) = self.screen_size();
+
+        for row in 0..self.height {
+            for column in 0..self.width {
+                let pixel = row * self.width + column;
+                let bit = if self.msb_first { 7 - pixel % 8 } else { pixel % 8 };
+                let lit = cpu.peek((self.address + pixel / 8) as u16) & 1 << bit != 0;
+
+                let (x, y) = match self.rotate {
+                    90 => (self.height - 1 - row, column),
+                    180 => (self.width - 1 - column, self.height - 1 - row),
+                    270 => (row, self.width - 1 - column),
+                    _ => (column, row),
+                };
+                screen[x + y * screen_width] = if lit { self.foreground } else { self.background };
+            }
+        }
+    }
+}
+
+// a table of the description, named in errors the way it is written
+struct Section<'a> {
+    name: String,
+    table: &'a Toml,
+}
+
+impl<'a> Section<'a> {
+    // keys other than `known` are mistakes
+    fn new(name: String, table: &'a Toml, known: &[&str]) -> Result<Self, String> {
+        let members = table.as_table().ok_or_else(|| format!("{} is not a table", name))?;
+        if let Some((key, _)) = members.iter().find(|(key, _)| !known.contains(&key.as_str())) {
+            return Err(format!("{} has an unknown key {}", name, key));
+        }
+        Ok(Section { name, table })
+    }
+
+    fn required<T>(&self, key: &str, value: Option<T>) -> Result<T, String> {
+        value.ok_or_else(|| format!("{} needs {}", self.name, key))
+    }
+
+    fn integer(&self, key: &str, min: i64, max: i64) -> Result<Option<i64>, String> {
+        match self.table.get(key) {
+            None => Ok(None),
+            Some(value) => match value.as_integer() {
+                Some(value) if min <= value && value <= max => Ok(Some(value)),
+                _ => Err(format!("{} {} is an integer from {} to {}", self.name, key, min, max)),
+            },
+        }
+    }
+
+    fn address(&self, key: &str) -> Result<Option<u16>, String> {
+        Ok(self.integer(key, 0, 0xffff)?.map(|value| value as u16))
+    }
+
+    fn port(&self, key: &str) -> Result<Option<u8>, String> {
+        Ok(self.integer(key, 0, 0xff)?.map(|value| value as u8))
+    }
+
+    fn string(&self, key: &str) -> Result<Option<&'a str>, String> {
+        match self.table.get(key) {
+            None => Ok(None),
+            Some(value) => value.as_str().map(Some).ok_or_else(|| format!("{} {} is a string", self.name, key)),
+        }
+    }
+
+    fn boolean(&self, key: &str) -> Result<Option<bool>, String> {
+        match self.table.get(key) {
+            None => Ok(None),
+            Some(value) => value.as_bool().map(Some).ok_or_else(|| format!("{} {} is true or false", self.name, key)),
+        }
+    }
+
+    fn bytes(&self, key: &str) -> Result<Option<Vec<u8>>, String> {
+        let values = match self.table.get(key) {
+            None => return Ok(None),
+            Some(value) => value.as_array().ok_or_else(|| format!("{} {} is an array of bytes", self.name, key))?,
+        };
+        values.iter()
+            .map(|value| value.as_integer().and_then(|value| u8::try_from(value).ok()))
+            .collect::<Option<Vec<_>>>()
+            .map(Some)
+            .ok_or_else(|| format!("{} {} is an array of bytes", self.name, key))
+    }
+}
+
+fn optional_section<'a>(name: &str, root: &'a Toml, key: &str, known: &[&str]) -> Result<Option<Section<'a>>, String> {
+    root.get(key).map(|table| Section::new(name.to_string(), table, known)).transpose()
+}
+
+// the tables of a [[key]], none when there are none
+fn array_of_tables<'a>(root: &'a Toml, key: &str) -> Result<Vec<&'a Toml>, String> {
+    match root.get(key) {
+        None => Ok(Vec::new()),
+        Some(Toml::Array(tables)) if tables.iter().all(|table| table.as_table().is_some()) => Ok(tables.iter().collect()),
+        Some(_) => Err(format!("{} is written as [[{}]] tables", key, key)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // a description loaded from a directory of its own with the files it
+    // names
+    fn load(test: &str, description: &str, files: &[(&str, &[u8])]) -> Result<Machine, String> {
+        let directory = std::env::temp_dir().join(format!("rust-8080-machine-{}-{}", std::process::id(), test));
+        fs::create_dir_all(&directory).unwrap();
+        fs::write(directory.join("machine.toml"), description).unwrap();
+        for (name, bytes) in files {
+            fs::write(directory.join(name), bytes).unwrap();
+        }
+        let machine = Machine::load(&directory.join("machine.toml"));
+        fs::remove_dir_all(&directory).unwrap();
+        machine
+    }
+
+    #[test]
+    fn unknown_keys_are_mistakes() {
+        let error = load("unknown", "[cpu]\nclock = 2000000\nspeed = 2\n", &[]).err();
+        assert_eq!(error, Some("[cpu] has an unknown key speed".to_string()));
+
+        let error = load("unknown-top", "[cpu]\nclock = 2000000\n[screen]\n", &[]).err();
+        assert_eq!(error, Some("the description has an unknown key screen".to_string()));
+    }
+
+    #[test]
+    fn roms_are_checked_against_their_crc32() {
+        let rom: &[u8] = &[0x00, 0x76];
+        let mut crc = Crc32::new();
+        crc.update(rom);
+        let description = |expected: u32| format!("[cpu]\nclock = 2000000\n[[rom]]\nfile = \"rom.bin\"\naddress = 0\ncrc32 = 0x{:08x}\n", expected);
+
+        assert!(load("crc", &description(crc.finish()), &[("rom.bin", rom)]).is_ok());
+        let error = load("crc-mismatch", &description(crc.finish() ^ 1), &[("rom.bin", rom)]).err();
+        assert_eq!(error, Some(format!("rom.bin has crc32 {:08x} instead of {:08x}", crc.finish(), crc.finish() ^ 1)));
+    }
+
+    #[test]
+    fn roms_and_ram_do_not_overlap() {
+        let rom: &[u8] = &[0; 0x10];
+        let error = load("rom-ram", "\
+[cpu]
+clock = 2000000
+[[rom]]
+file = \"rom.bin\"
+address = 0
+[[ram]]
+start = 0x0008
+size = 0xfff8
+", &[("rom.bin", rom)]).err();
+        assert_eq!(error, Some("[[ram]] 1 overlaps the ROM at 0000".to_string()));
+
+        let error = load("rom-rom", "\
+[cpu]
+clock = 2000000
+[[rom]]
+file = \"rom.bin\"
+address = 0
+[[rom]]
+file = \"rom.bin\"
+address = 0x000c
+", &[("rom.bin", rom)]).err();
+        assert_eq!(error, Some("rom.bin overlaps another ROM at 000c".to_string()));
+    }
+
+    #[test]
+    fn the_invaders_description_runs_frames() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("machines/invaders.toml");
+        let mut machine = Machine::load(&path).unwrap();
+        assert_eq!(machine.name(), "Space Invaders");
+        assert_eq!(machine.screen_size(), (224, 256));
+
+        for _ in 0..120 {
+            machine.run_frame().unwrap();
+        }
+        assert_eq!(machine.frames(), 120);
+        // 1996800 Hz at 60 frames a second, give or take the last instruction
+        let cycles = 120 * 1_996_800 / 60;
+        assert!((cycles..cycles + 18).contains(&machine.cycles()), "{} cycles", machine.cycles());
+        let background = machine.screen()[0];
+        assert!(machine.screen().iter().any(|&pixel| pixel != background), "the attract mode draws nothing");
+    }
+}
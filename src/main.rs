@@ -1,20 +1,598 @@
+use std::env;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
 use minifb::{Key, Window, WindowOptions};
 
+mod analysis;
+mod assembler;
+mod bench;
+mod block_cache;
+mod bus;
+mod call_stack;
+mod coverage;
 mod cpu;
+mod disassembler;
+mod dap;
+mod error;
+mod gdb;
+mod graph;
+mod hex;
+mod history;
+mod image;
+mod invaders_native;
+mod json;
+mod linker;
+mod lint;
+mod listing;
+mod loader;
+mod machine;
+mod memory_map;
+mod native;
+mod object;
+mod png;
+mod processor;
+mod profiler;
+mod recompiler;
 mod space_invader;
+mod step;
+mod symbols;
+mod toml;
+mod tui;
+mod z80;
+
 
+// a subcommand, given the arguments after its name
+type Command = fn(&[String]) -> Result<(), String>;
 
 fn main() {
+    let args: Vec<String> = env::args().collect();
+
+    let command: Option<Command> = match args.get(1).map(String::as_str) {
+        // recompile <rom> <out.rs> [entry...] writes the ROM as Rust source,
+        // starting from the reset and RST vectors unless entries in hex are given
+        Some("recompile") => Some(recompile),
+        // diff-native <frames> runs the recompiled invaders next to the interpreter
+        Some("diff-native") => Some(diff_native),
+        // bench [--block-cache] [--exerciser <file.com>] measures the core, the
+        // built-in operations exerciser and the machine, a CP/M exerciser only
+        // runs when its image is given
+        Some("bench") => Some(bench),
+        // asm <source> [-o <out>] [--listing <file>] [--symbols <file>] assembles
+        // Intel 8080 source, the output is Intel HEX when its name ends in .hex
+        // and an object file for `link` when it ends in .obj
+        Some("asm") => Some(assemble),
+        // link <object>... -o <out> places the sections of the objects and
+        // resolves the names they share into one program
+        Some("link") => Some(link),
+        // convert <in> <out> reads a program in any format `--rom` takes and
+        // writes it as Intel HEX when the name ends in .hex, as bytes otherwise
+        Some("convert") => Some(convert),
+        // analyze <rom> [entry...] traces the code of a ROM from the entries in
+        // hex, the reset and interrupt vectors by default, and prints it as
+        // annotated source; --db <file> writes what it found for `query`,
+        // --calls <file> the call graph and --cfg <directory> the control flow of
+        // every function for Graphviz
+        Some("analyze") => Some(analyze),
+        // query <db> <question> looks up functions, tables, data, ports and the
+        // references to and from an address or name in an `analyze` database
+        Some("query") => Some(query),
+        // disassemble <rom> [origin] [--symbols <file>] prints the ROM as source
+        // asm reads back, with the names of the symbol file as labels
+        Some("disassemble") => Some(disassemble),
+        // machine <file.toml> runs the machine a description file wires up in a
+        // window; --frames <n> runs n frames without one and --screen <file.png>
+        // writes the picture it ended on
+        Some("machine") => Some(run_machine),
+        // anything else runs the invaders machine with the options below
+        _ => None,
+    };
+    if let Some(command) = command {
+        if let Err(error) = command(&args[2..]) {
+            eprintln!("{}: {}", args[1], error);
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    // --profile <file> writes a callgrind profile on exit and prints a report
+    let profile_path = option(&args, "--profile");
+    // --coverage <prefix> writes <prefix>.txt and a <prefix>.png heatmap on exit
+    let coverage_prefix = option(&args, "--coverage");
+    // --lint <file> writes the suspicious accesses the program made on exit
+    let lint_path = option(&args, "--lint");
+    // --block-cache runs the ROM through pre-decoded basic blocks
+    let block_cache = args.iter().any(|arg| arg == "--block-cache");
+    // --gdb <address> waits for a gdb to connect before running, e.g. --gdb localhost:1234
+    let gdb_address = option(&args, "--gdb");
+    // --dap <stdio|address> serves an editor's debugger on stdio or TCP before running
+    let dap_transport = option(&args, "--dap");
+    // --tui debugs in a full-screen terminal interface instead of a window
+    let tui = args.iter().any(|arg| arg == "--tui");
+    // --rom <file> loads a program over the invaders ROM, as Intel HEX,
+    // S-records, a CP/M .COM, a .manifest of files or raw bytes at 0
+    let rom_path = option(&args, "--rom");
+    // --dump <file> writes the memory on exit, as Intel HEX for a .hex
+    let dump_path = option(&args, "--dump");
+    // --unmapped-ports <open_bus|log|error> sets what reads and writes of
+    // ports the board does not decode do, open bus by default
+    let unmapped_ports = option(&args, "--unmapped-ports");
+    // --symbols <file> names addresses in backtraces, profiles and the
+    // debugger, from a .sym, a `name = address` map or a MAME comment file
+    let symbols_path = option(&args, "--symbols");
+
     let mut invaders_game_state = space_invader::GameState::new_game();
-    let mut window = Window::new(
-        "invaders test",
-        224,
-        256,
-        WindowOptions::default(),
-    ).unwrap();
+    if let Some(path) = rom_path {
+        let loaded = loader::load(Path::new(path)).and_then(|image| invaders_game_state.cpu_mut().load_image(&image));
+        if let Err(error) = loaded {
+            eprintln!("could not load {}: {}", path, error);
+            std::process::exit(1);
+        }
+        // the invaders names mean nothing in another program
+        invaders_game_state.cpu_mut().call_stack_mut().set_symbols(symbols::SymbolTable::new());
+    }
+    if let Some(path) = symbols_path {
+        match symbols::SymbolTable::load(Path::new(path)) {
+            Ok(table) => invaders_game_state.cpu_mut().call_stack_mut().symbols_mut().merge(&table),
+            Err(error) => {
+                eprintln!("could not load symbols {}", error);
+                std::process::exit(1);
+            },
+        }
+    }
+    if let Some(name) = unmapped_ports {
+        match error::UnmappedPortPolicy::from_name(name) {
+            Some(policy) => invaders_game_state.set_unmapped_port_policy(policy),
+            None => {
+                eprintln!("--unmapped-ports is open_bus, log or error, not {}", name);
+                std::process::exit(1);
+            },
+        }
+    }
+    if block_cache {
+        invaders_game_state.cpu_mut().set_block_cache(block_cache::BlockCache::new());
+    }
+    if profile_path.is_some() {
+        invaders_game_state.cpu_mut().set_profiler(profiler::Profiler::new());
+    }
+    if coverage_prefix.is_some() {
+        invaders_game_state.cpu_mut().set_coverage(coverage::Coverage::new());
+    }
+    if lint_path.is_some() {
+        invaders_game_state.cpu_mut().set_lint(lint::Lint::new(lint::LintConfig {
+            rom_size: space_invader::ROM_SIZE,
+            ram_end: space_invader::RAM_END,
+            video_ram: space_invader::VIDEO_RAM,
+            input_ports: space_invader::SpaceInvaderIO::INPUT_PORTS.to_vec(),
+            output_ports: space_invader::SpaceInvaderIO::OUTPUT_PORTS.to_vec(),
+        }));
+    }
+    if tui {
+        if let Err(error) = tui::Tui::new().run(&mut invaders_game_state) {
+            eprintln!("tui: {}", error);
+        }
+    } else {
+        let mut window = Window::new(
+            "invaders test",
+            224,
+            256,
+            WindowOptions::default(),
+        ).unwrap();
+
+        let mut killed = false;
+        if let Some(address) = gdb_address {
+            eprintln!("waiting for gdb on {}", address);
+            let session = gdb::GdbStub::accept(address.as_str()).and_then(|mut stub| {
+                stub.serve(&mut invaders_game_state, &mut |game| {
+                    game.present(&mut window);
+                    std::thread::sleep(std::time::Duration::from_millis(16));
+                })
+            });
+
+            match session {
+                Ok(end) => killed = end == gdb::SessionEnd::Killed,
+                Err(error) => eprintln!("gdb session ended: {}", error),
+            }
+        }
+        if let Some(transport) = dap_transport {
+            let server = if transport == "stdio" {
+                Ok(dap::DapServer::stdio())
+            } else {
+                eprintln!("waiting for a debug adapter client on {}", transport);
+                dap::DapServer::accept(transport.as_str())
+            };
+            let session = server.and_then(|mut server| {
+                server.serve(&mut invaders_game_state, &mut |game| {
+                    game.present(&mut window);
+                    std::thread::sleep(std::time::Duration::from_millis(16));
+                })
+            });
+
+            if let Err(error) = session {
+                eprintln!("debug adapter session ended: {}", error);
+            }
+            // the client decides when the program ends
+            killed = true;
+        }
+
+        while !killed && window.is_open() && !window.is_key_down(Key::Escape) {
+            if let Err(error) = invaders_game_state.next_frame(&mut window) {
+                eprintln!("emulation stopped: {}\nbacktrace:\n{}", error, invaders_game_state.backtrace());
+                break;
+            }
+        }
+    }
+
+    if let Some(path) = profile_path {
+        let object = rom_path.map_or("invaders.rom", String::as_str);
+        if let Err(error) = write_profile(invaders_game_state.cpu(), path, object) {
+            eprintln!("could not write profile to {}: {}", path, error);
+        }
+    }
+    if let Some(prefix) = coverage_prefix {
+        if let Err(error) = write_coverage(invaders_game_state.cpu(), prefix) {
+            eprintln!("could not write coverage to {}: {}", prefix, error);
+        }
+    }
+    if let Some(path) = lint_path {
+        if let Err(error) = write_lint(invaders_game_state.cpu(), path) {
+            eprintln!("could not write lint report to {}: {}", path, error);
+        }
+    }
+    if let Some(path) = dump_path {
+        if let Err(error) = write_dump(invaders_game_state.cpu(), Path::new(path)) {
+            eprintln!("could not write memory to {}: {}", path, error);
+        }
+    }
+}
+
+fn option<'a>(args: &'a [String], name: &str) -> Option<&'a String> {
+    args.iter()
+        .position(|arg| arg == name)
+        .and_then(|i| args.get(i + 1))
+}
+
+// the arguments that are not one of `options` or the value after it
+fn positionals<'a>(args: &'a [String], options: &[&str]) -> Vec<&'a String> {
+    args.iter()
+        .enumerate()
+        .filter(|&(i, arg)| !options.contains(&arg.as_str()) && (i == 0 || !options.contains(&args[i - 1].as_str())))
+        .map(|(_, arg)| arg)
+        .collect()
+}
+
+fn write_profile(cpu: &cpu::State8080, path: &str, object: &str) -> io::Result<()> {
+    let symbols = cpu.call_stack().symbols();
+
+    if let Some(profiler) = cpu.profiler() {
+        profiler.write_report(&mut io::stdout(), symbols)?;
+
+        let mut file = BufWriter::new(File::create(path)?);
+        profiler.write_callgrind(&mut file, object, symbols)?;
+        file.flush()?;
+    }
+    Ok(())
+}
+
+fn write_coverage(cpu: &cpu::State8080, prefix: &str) -> io::Result<()> {
+    if let Some(coverage) = cpu.coverage() {
+        let mut report = BufWriter::new(File::create(format!("{}.txt", prefix))?);
+        coverage.write_report(&mut report, space_invader::ROM_SIZE)?;
+        report.flush()?;
+
+        let mut heatmap = BufWriter::new(File::create(format!("{}.png", prefix))?);
+        coverage.write_png(&mut heatmap)?;
+        heatmap.flush()?;
+    }
+    Ok(())
+}
+
+fn write_lint(cpu: &cpu::State8080, path: &str) -> io::Result<()> {
+    if let Some(lint) = cpu.lint() {
+        let mut report = BufWriter::new(File::create(path)?);
+        lint.write_report(&mut report)?;
+        report.flush()?;
+        // the report is only worth opening when there is something in it
+        if !lint.findings().is_empty() {
+            eprintln!("{} lint warnings written to {}", lint.findings().len(), path);
+        }
+    }
+    Ok(())
+}
+
+fn assemble(args: &[String]) -> Result<(), String> {
+    let source = match args.first() {
+        Some(source) if !source.starts_with("-") => Path::new(source),
+        _ => return Err("usage: asm <source> [-o <out.bin|out.hex|out.obj>] [--listing <file>] [--symbols <file>] [--map <file>] [--place <section>=<address>]...".to_string()),
+    };
+    let assembly = assembler::assemble_file(source).map_err(|error| error.to_string())?;
+
+    if let Some(path) = option(args, "--listing") {
+        let directory = Path::new(path).parent().unwrap_or_else(|| Path::new(""));
+        let directory = if directory.as_os_str().is_empty() { Path::new(".") } else { directory };
+        fs::write(path, assembly.listing(directory)).map_err(|error| format!("{}: {}", path, error))?;
+    }
+
+    let out = option(args, "-o").map(PathBuf::from).unwrap_or_else(|| source.with_extension("bin"));
+    if has_extension(&out, "obj") {
+        fs::write(&out, assembly.object().to_string()).map_err(|error| format!("{}: {}", out.display(), error))?;
+        if let Some(path) = option(args, "--symbols") {
+            fs::write(path, assembly.symbol_file()).map_err(|error| format!("{}: {}", path, error))?;
+        }
+        return Ok(());
+    }
+
+    // a program on its own is linked with nothing else
+    let objects = [(source.display().to_string(), assembly.object().clone())];
+    write_linked(args, &out, &linker::link(&objects, &placements(args)?)?)
+}
+
+// link <object>... -o <out> [--map <file>] [--symbols <file>]
+//     [--place <section>=<address>]...
+fn link(args: &[String]) -> Result<(), String> {
+    let mut paths = Vec::new();
+    let mut rest = args.iter();
+    while let Some(arg) = rest.next() {
+        match arg.as_str() {
+            "-o" | "--map" | "--symbols" | "--place" => {
+                rest.next();
+            },
+            _ => paths.push(arg),
+        }
+    }
+    let out = match option(args, "-o") {
+        Some(out) if !paths.is_empty() => Path::new(out),
+        _ => return Err("usage: link <object>... -o <out.bin|out.hex> [--map <file>] [--symbols <file>] [--place <section>=<address>]...".to_string()),
+    };
+
+    let objects = paths.iter()
+        .map(|path| {
+            let text = fs::read_to_string(path).map_err(|error| format!("{}: {}", path, error))?;
+            let object = object::Object::parse(&text).map_err(|error| format!("{}: {}", path, error))?;
+            Ok((path.to_string(), object))
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+    write_linked(args, out, &linker::link(&objects, &placements(args)?)?)
+}
+
+fn write_linked(args: &[String], out: &Path, linked: &linker::Linked) -> Result<(), String> {
+    let written = if has_extension(out, "hex") {
+        fs::write(out, linked.image.intel_hex())
+    } else {
+        fs::write(out, linked.image.binary().1)
+    };
+    written.map_err(|error| format!("{}: {}", out.display(), error))?;
+
+    if let Some(path) = option(args, "--map") {
+        fs::write(path, &linked.map).map_err(|error| format!("{}: {}", path, error))?;
+    }
+    if let Some(path) = option(args, "--symbols") {
+        fs::write(path, linker::symbol_file(&linked.symbols)).map_err(|error| format!("{}: {}", path, error))?;
+    }
+    Ok(())
+}
+
+fn has_extension(path: &Path, extension: &str) -> bool {
+    path.extension().is_some_and(|candidate| candidate.eq_ignore_ascii_case(extension))
+}
+
+// every --place <section>=<address>, the address in hex
+fn placements(args: &[String]) -> Result<Vec<(String, u16)>, String> {
+    args.windows(2)
+        .filter(|pair| pair[0] == "--place")
+        .map(|pair| {
+            let (section, address) = pair[1].split_once('=').ok_or_else(|| format!("bad placement {}", pair[1]))?;
+            let address = hex::address(address).ok_or_else(|| format!("bad placement {}", pair[1]))?;
+            Ok((section.to_string(), address))
+        })
+        .collect()
+}
+
+// the memory as Intel HEX starting at the pc, or as bytes
+fn write_dump(cpu: &cpu::State8080, path: &Path) -> io::Result<()> {
+    if has_extension(path, "hex") {
+        let mut image = image::Image::from_memory(cpu.memory());
+        image.entry = Some(cpu.pc());
+        fs::write(path, image.intel_hex())
+    } else {
+        fs::write(path, cpu.memory())
+    }
+}
+
+fn convert(args: &[String]) -> Result<(), String> {
+    let (input, out) = match args {
+        [input, out, ..] => (Path::new(input), Path::new(out)),
+        _ => return Err("usage: convert <in> <out.hex|out.bin>".to_string()),
+    };
+    let image = loader::load(input)?;
+
+    // bytes start at the lowest address loaded, Intel HEX keeps the addresses
+    let written = if has_extension(out, "hex") {
+        fs::write(out, image.intel_hex())
+    } else {
+        fs::write(out, image.binary().1)
+    };
+    written.map_err(|error| format!("{}: {}", out.display(), error))
+}
+
+fn run_machine(args: &[String]) -> Result<(), String> {
+    let frames = option(args, "--frames").map(|frames| frames.parse::<u64>().map_err(|_| format!("{} is not a number of frames", frames))).transpose()?;
+    let screen_path = option(args, "--screen");
+    let path = match positionals(args, &["--frames", "--screen"]).as_slice() {
+        [path] => Path::new(path.as_str()),
+        _ => return Err("usage: machine <file.toml> [--frames <n>] [--screen <file.png>]".to_string()),
+    };
+    let mut machine = machine::Machine::load(path)?;
+
+    let stopped = match frames {
+        Some(frames) => {
+            let result = (0..frames).try_for_each(|_| machine.run_frame());
+            println!("{} frames, {} instructions, {} cycles", machine.frames(), machine.instructions(), machine.cycles());
+            result
+        },
+        None => {
+            let (width, height) = machine.screen_size();
+            let mut window = Window::new(machine.name(), width.max(1), height.max(1), WindowOptions::default())
+                .map_err(|error| error.to_string())?;
+            let mut result = Ok(());
+            while result.is_ok() && window.is_open() && !window.is_key_down(Key::Escape) {
+                result = machine.next_frame(&mut window);
+            }
+            result
+        },
+    };
+    if let Err(error) = stopped {
+        return Err(format!("emulation stopped: {}\nbacktrace:\n{}", error, machine.backtrace()));
+    }
+
+    if let Some(path) = screen_path {
+        let (width, height) = machine.screen_size();
+        if width == 0 {
+            return Err("the machine has no video to write".to_string());
+        }
+        let mut pixels = Vec::with_capacity(width * height * 3);
+        for pixel in machine.screen() {
+            pixels.extend_from_slice(&pixel.to_be_bytes()[1..]);
+        }
+        let written = File::create(path).and_then(|file| {
+            let mut out = BufWriter::new(file);
+            png::write_rgb(&mut out, width as u32, height as u32, &pixels)?;
+            out.flush()
+        });
+        written.map_err(|error| format!("{}: {}", path, error))?;
+    }
+    Ok(())
+}
 
-    while window.is_open() && !window.is_key_down(Key::Escape) {
-        invaders_game_state.next_frame(&mut window);
+fn disassemble(args: &[String]) -> Result<(), String> {
+    let usage = "usage: disassemble <rom> [origin] [--symbols <file>]";
+    let symbols = match option(args, "--symbols") {
+        Some(path) => symbols::SymbolTable::load(Path::new(path))?,
+        None => symbols::SymbolTable::new(),
+    };
+    let args = positionals(args, &["--symbols"]);
+
+    let rom_path = args.first().ok_or(usage)?;
+    let origin = match args.get(1) {
+        Some(origin) => hex::address(origin).ok_or_else(|| format!("bad origin {}", origin))?,
+        None => 0,
+    };
+
+    let rom = fs::read(rom_path).map_err(|error| format!("{}: {}", rom_path, error))?;
+    print!("{}", disassembler::source(&rom, origin, &symbols));
+    Ok(())
+}
+
+// analyze <rom> [entry...] [--origin <address>] [--symbols <file>] [--db <file>]
+// [--calls <file>] [--cfg <directory>]
+fn analyze(args: &[String]) -> Result<(), String> {
+    let usage = "usage: analyze <rom> [entry...] [--origin <address>] [--symbols <file>] [--db <file>] [--calls <file>] [--cfg <directory>]";
+    let hex = |text: &str| hex::address(text).ok_or_else(|| format!("{} is not an address", text));
+    let origin = option(args, "--origin").map_or(Ok(0), |origin| hex(origin))?;
+    let symbols = match option(args, "--symbols") {
+        Some(path) => symbols::SymbolTable::load(Path::new(path))?,
+        None => symbols::SymbolTable::new(),
+    };
+    let db_path = option(args, "--db");
+    let calls_path = option(args, "--calls");
+    let cfg_directory = option(args, "--cfg");
+    let args = positionals(args, &["--origin", "--symbols", "--db", "--calls", "--cfg"]);
+
+    let rom_path = args.first().ok_or(usage)?;
+    let rom = fs::read(rom_path).map_err(|error| format!("{}: {}", rom_path, error))?;
+    let mut entries = args[1..].iter().map(|entry| hex(entry)).collect::<Result<Vec<_>, _>>()?;
+    // a program somewhere else starts where it is loaded
+    if entries.is_empty() {
+        entries = if origin == 0 { analysis::VECTORS.to_vec() } else { vec![origin] };
+    }
+
+    let analysis = analysis::analyze(&rom, origin, &entries, &symbols);
+    if let Some(path) = db_path {
+        fs::write(path, analysis.to_string()).map_err(|error| format!("{}: {}", path, error))?;
+    }
+    if let Some(path) = calls_path {
+        fs::write(path, graph::call_graph(&analysis)).map_err(|error| format!("{}: {}", path, error))?;
+    }
+    // a graph for every function, named after it
+    if let Some(directory) = cfg_directory {
+        fs::create_dir_all(directory).map_err(|error| format!("{}: {}", directory, error))?;
+        for &function in &analysis.functions {
+            let name = analysis.symbols.name(function).map_or_else(|| format!("sub_{:04x}", function), str::to_string);
+            let path = Path::new(directory).join(format!("{}.dot", name));
+            fs::write(&path, graph::control_flow(&analysis, &rom, function)).map_err(|error| format!("{}: {}", path.display(), error))?;
+        }
+    }
+    print!("{}", analysis.annotated(&rom));
+    Ok(())
+}
+
+// query <db> <question>...
+fn query(args: &[String]) -> Result<(), String> {
+    let db_path = args.first().ok_or("usage: query <db> <functions|tables|data|ports|port <number>|address|name>")?;
+    let text = fs::read_to_string(db_path).map_err(|error| format!("{}: {}", db_path, error))?;
+    let analysis = analysis::Analysis::parse(&text).map_err(|error| format!("{}: {}", db_path, error))?;
+
+    let words = args[1..].iter().map(String::as_str).collect::<Vec<_>>();
+    print!("{}", analysis.query(&words)?);
+    Ok(())
+}
+
+fn recompile(args: &[String]) -> Result<(), String> {
+    let (rom_path, out_path) = match args {
+        [rom_path, out_path, ..] => (rom_path, out_path),
+        _ => return Err("usage: recompile <rom> <out.rs> [entry...]".to_string()),
+    };
+    let mut entries = args[2..].iter()
+        .map(|entry| hex::address(entry).ok_or_else(|| format!("bad entry point {}", entry)))
+        .collect::<Result<Vec<_>, _>>()?;
+    if entries.is_empty() {
+        entries = (0..8).map(|n| n * 8).collect();
+    }
+
+    let rom = fs::read(rom_path).map_err(|error| format!("{}: {}", rom_path, error))?;
+    let command = format!("rust-8080 recompile {}", args.join(" "));
+    fs::write(out_path, recompiler::recompile(&rom, &entries, &command))
+        .map_err(|error| format!("{}: {}", out_path, error))
+}
+
+// runs the recompiled invaders next to the interpreter
+fn diff_native(args: &[String]) -> Result<(), String> {
+    let frames = args.first().and_then(|frames| frames.parse().ok()).unwrap_or(600);
+    native::diff_invaders(frames)?;
+    println!("{} frames identical", frames);
+    Ok(())
+}
+
+fn bench(args: &[String]) -> Result<(), String> {
+    const CYCLES: u64 = 200_000_000;
+    const FRAMES: u64 = 3000;
+    let block_cache = args.iter().any(|arg| arg == "--block-cache");
+    let report = |result: Result<bench::Measurement, error::CpuError>| {
+        let measurement = result.map_err(|error| error.to_string())?;
+        println!("{}", measurement);
+        Ok::<(), String>(())
+    };
+
+    report(bench::alu(CYCLES, block_cache))?;
+    report(bench::block_copy(CYCLES, block_cache))?;
+    let (measurement, checksum) = bench::operations(block_cache).map_err(|error| error.to_string())?;
+    println!("{}  checksum {:04x}", measurement, checksum);
+    if checksum != bench::OPERATIONS_CHECKSUM {
+        return Err(format!("the operations checksum is {:04x} instead of {:04x}", checksum, bench::OPERATIONS_CHECKSUM));
     }
+    report(bench::invaders(FRAMES, block_cache))?;
+    report(bench::invaders_native(FRAMES))?;
+
+    if let Some(path) = option(args, "--exerciser") {
+        let program = fs::read(path).map_err(|error| format!("{}: {}", path, error))?;
+        if program.len() > bench::MAX_PROGRAM_SIZE {
+            return Err(format!("{}: larger than {} bytes", path, bench::MAX_PROGRAM_SIZE));
+        }
 
+        let (measurement, output) = bench::exerciser(&program, block_cache).map_err(|error| error.to_string())?;
+        println!("{}", output.trim_end());
+        println!("{}", measurement);
+    }
+    Ok(())
 }
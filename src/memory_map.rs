@@ -0,0 +1,398 @@
+// something on the bus at a range of addresses instead of RAM, offsets are
+// from the start of the range
+pub trait Device {
+    fn read(&mut self, offset: u16) -> u8;
+    fn write(&mut self, offset: u16, value: u8);
+
+    // what a debugger sees, the bus floats high unless the device can be
+    // read without side effects
+    fn peek(&self, _offset: u16) -> u8 {
+        0xff
+    }
+}
+
+// a device made of a read and a write callback
+pub struct Callbacks<R, W> {
+    read: R,
+    write: W,
+}
+
+impl<R: FnMut(u16) -> u8, W: FnMut(u16, u8)> Callbacks<R, W> {
+    pub fn new(read: R, write: W) -> Self {
+        Callbacks { read, write }
+    }
+}
+
+impl<R: FnMut(u16) -> u8, W: FnMut(u16, u8)> Device for Callbacks<R, W> {
+    fn read(&mut self, offset: u16) -> u8 {
+        (self.read)(offset)
+    }
+
+    fn write(&mut self, offset: u16, value: u8) {
+        (self.write)(offset, value)
+    }
+}
+
+// what picks the bank of a region: the value last written to a port or to
+// an address, modulo the number of banks
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BankSelect {
+    Port(u8),
+    Address(u16),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Bank {
+    pub bytes: Vec<u8>,
+    // ROM banks ignore writes
+    pub writable: bool,
+}
+
+impl Bank {
+    pub fn ram(size: usize) -> Self {
+        Bank { bytes: vec![0; size], writable: true }
+    }
+
+    pub fn rom(bytes: Vec<u8>) -> Self {
+        Bank { bytes, writable: false }
+    }
+}
+
+// addresses that show one of several banks, e.g. a boot ROM over the RAM of
+// a CP/M machine until a port write swaps it out
+#[derive(Clone, Debug, PartialEq)]
+pub struct BankedRegion {
+    pub start: u16,
+    pub size: u32,
+    pub select: BankSelect,
+    banks: Vec<Bank>,
+    selected: usize,
+}
+
+impl BankedRegion {
+    // banks shorter than the region read as 0xff past their end
+    pub fn new(start: u16, size: u32, select: BankSelect, banks: Vec<Bank>) -> Result<Self, String> {
+        if banks.is_empty() {
+            return Err(format!("the region at {:04x} has no banks", start));
+        }
+        if let Some(bank) = banks.iter().find(|bank| bank.bytes.len() > size as usize) {
+            return Err(format!("a bank of {} bytes does not fit the {} bytes at {:04x}", bank.bytes.len(), size, start));
+        }
+        Ok(BankedRegion { start, size, select, banks, selected: 0 })
+    }
+
+    pub fn select(&mut self, value: u8) {
+        self.selected = value as usize % self.banks.len();
+    }
+
+    fn peek(&self, offset: u16) -> u8 {
+        self.banks[self.selected].bytes.get(offset as usize).copied().unwrap_or(0xff)
+    }
+
+    fn write(&mut self, offset: u16, value: u8, force: bool) {
+        let bank = &mut self.banks[self.selected];
+        if bank.writable || force {
+            if let Some(byte) = bank.bytes.get_mut(offset as usize) {
+                *byte = value;
+            }
+        }
+    }
+}
+
+// the bank each region shows and what its RAM banks hold, part of a
+// snapshot; the state of devices is their own
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct BankState {
+    regions: Vec<(usize, Vec<Vec<u8>>)>,
+}
+
+struct MappedDevice {
+    start: u16,
+    size: u32,
+    device: Box<dyn Device>,
+}
+
+// the devices and banked regions in front of the RAM, anything they do not
+// cover is RAM
+#[derive(Default)]
+pub struct MemoryMap {
+    devices: Vec<MappedDevice>,
+    regions: Vec<BankedRegion>,
+}
+
+impl MemoryMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.devices.is_empty() && self.regions.is_empty()
+    }
+
+    pub fn attach(&mut self, start: u16, size: u32, device: Box<dyn Device>) -> Result<(), String> {
+        self.check_free(start, size)?;
+        self.devices.push(MappedDevice { start, size, device });
+        Ok(())
+    }
+
+    pub fn add_region(&mut self, region: BankedRegion) -> Result<(), String> {
+        self.check_free(region.start, region.size)?;
+        self.regions.push(region);
+        Ok(())
+    }
+
+    fn check_free(&self, start: u16, size: u32) -> Result<(), String> {
+        let end = start as u32 + size;
+        if size == 0 || end > 0x10000 {
+            return Err(format!("{} bytes at {:04x} do not fit in 64 KiB", size, start));
+        }
+        let taken = self.devices.iter().map(|device| (device.start, device.size))
+            .chain(self.regions.iter().map(|region| (region.start, region.size)))
+            .find(|&(other, other_size)| (start as u32) < other as u32 + other_size && (other as u32) < end);
+        match taken {
+            Some((other, _)) => Err(format!("{:04x} overlaps what is mapped at {:04x}", start, other)),
+            None => Ok(()),
+        }
+    }
+
+    pub fn covers(&self, address: u16) -> bool {
+        self.device(address).is_some() || self.region(address).is_some()
+    }
+
+    fn device(&self, address: u16) -> Option<usize> {
+        self.devices.iter().position(|device| inside(address, device.start, device.size))
+    }
+
+    fn region(&self, address: u16) -> Option<usize> {
+        self.regions.iter().position(|region| inside(address, region.start, region.size))
+    }
+
+    // none where it is RAM
+    pub fn read(&mut self, address: u16) -> Option<u8> {
+        if let Some(index) = self.device(address) {
+            let device = &mut self.devices[index];
+            return Some(device.device.read(address - device.start));
+        }
+        self.region(address).map(|index| self.regions[index].peek(address - self.regions[index].start))
+    }
+
+    pub fn peek(&self, address: u16) -> Option<u8> {
+        if let Some(index) = self.device(address) {
+            let device = &self.devices[index];
+            return Some(device.device.peek(address - device.start));
+        }
+        self.region(address).map(|index| self.regions[index].peek(address - self.regions[index].start))
+    }
+
+    // false where it is RAM; a write to a bank select address switches the
+    // bank and goes nowhere else
+    pub fn write(&mut self, address: u16, value: u8) -> bool {
+        if self.select(BankSelect::Address(address), value) {
+            return true;
+        }
+        if let Some(index) = self.device(address) {
+            let device = &mut self.devices[index];
+            device.device.write(address - device.start, value);
+            return true;
+        }
+        match self.region(address) {
+            Some(index) => {
+                let region = &mut self.regions[index];
+                region.write(address - region.start, value, false);
+                true
+            },
+            None => false,
+        }
+    }
+
+    // the way a debugger patches memory, ROM banks included and devices
+    // left alone
+    pub fn poke(&mut self, address: u16, value: u8) -> bool {
+        if self.device(address).is_some() {
+            return true;
+        }
+        match self.region(address) {
+            Some(index) => {
+                let region = &mut self.regions[index];
+                region.write(address - region.start, value, true);
+                true
+            },
+            None => false,
+        }
+    }
+
+    // false when no region is switched by the port
+    pub fn output(&mut self, port: u8, value: u8) -> bool {
+        self.select(BankSelect::Port(port), value)
+    }
+
+    fn select(&mut self, select: BankSelect, value: u8) -> bool {
+        let mut switched = false;
+        for region in self.regions.iter_mut().filter(|region| region.select == select) {
+            region.select(value);
+            switched = true;
+        }
+        switched
+    }
+
+    pub fn bank_state(&self) -> BankState {
+        BankState {
+            regions: self.regions.iter()
+                .map(|region| {
+                    let ram = region.banks.iter().filter(|bank| bank.writable).map(|bank| bank.bytes.clone()).collect();
+                    (region.selected, ram)
+                })
+                .collect(),
+        }
+    }
+
+    // a state from the same map, the regions of other ones are left alone
+    pub fn restore_banks(&mut self, state: &BankState) {
+        for (region, (selected, ram)) in self.regions.iter_mut().zip(&state.regions) {
+            region.selected = *selected;
+            for (bank, bytes) in region.banks.iter_mut().filter(|bank| bank.writable).zip(ram) {
+                bank.bytes.clone_from(bytes);
+            }
+        }
+    }
+}
+
+fn inside(address: u16, start: u16, size: u32) -> bool {
+    (address.wrapping_sub(start) as u32) < size
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    use super::*;
+    use crate::assembler;
+    use crate::bench::Console;
+    use crate::cpu::State8080;
+    use crate::processor::Cpu;
+
+    // the program at 0 with `map` in front of the RAM, run up to its HLT
+    fn run(source: &str, map: impl FnOnce(&mut MemoryMap)) -> State8080 {
+        let assembly = assembler::assemble(source).unwrap();
+        let mut cpu = State8080::new();
+        cpu.set_memory_size(0x10000).unwrap();
+        cpu.load_image(&assembly.image).unwrap();
+        map(cpu.memory_map_mut());
+        let mut console = Console { output: String::new() };
+        while !cpu.halted() {
+            cpu.emulate(&mut console).unwrap();
+        }
+        cpu
+    }
+
+    // two RAM banks at 4000h switched by port 10h
+    fn two_banks(map: &mut MemoryMap) {
+        map.add_region(BankedRegion::new(0x4000, 0x1000, BankSelect::Port(0x10), vec![Bank::ram(0x1000), Bank::ram(0x1000)]).unwrap()).unwrap();
+    }
+
+    #[test]
+    fn a_device_takes_the_reads_and_writes_of_its_addresses() {
+        let written = Rc::new(RefCell::new(Vec::new()));
+        let log = Rc::clone(&written);
+        let cpu = run("
+            lda 8001h
+            sta 8000h
+            sta 8002h
+            hlt
+        ", |map| map.attach(0x8000, 2, Box::new(Callbacks::new(|offset| 0x40 + offset as u8, move |offset, value| log.borrow_mut().push((offset, value))))).unwrap());
+
+        assert_eq!(Cpu::registers(&cpu).a, 0x41);
+        assert_eq!(*written.borrow(), vec![(0, 0x41)]);
+        // past the device it is RAM, and the device is not read to peek
+        assert_eq!(cpu.memory()[0x8000..0x8003], [0, 0, 0x41]);
+        assert_eq!(cpu.peek(0x8001), 0xff);
+    }
+
+    #[test]
+    fn a_port_write_switches_the_bank() {
+        let cpu = run("
+            mvi a,11h
+            sta 4000h
+            mvi a,1
+            out 10h
+            mvi a,22h
+            sta 4000h
+            lda 4000h
+            mov b,a
+            xra a
+            out 10h
+            lda 4000h
+            hlt
+        ", two_banks);
+
+        let registers = Cpu::registers(&cpu);
+        assert_eq!((registers.a, registers.b), (0x11, 0x22));
+        assert_eq!(cpu.memory()[0x4000], 0);
+    }
+
+    #[test]
+    fn an_address_write_switches_the_bank_and_goes_nowhere_else() {
+        let mut map = MemoryMap::new();
+        map.add_region(BankedRegion::new(0, 2, BankSelect::Address(0xffff), vec![Bank::rom(vec![1, 2]), Bank::rom(vec![3])]).unwrap()).unwrap();
+
+        assert!(map.write(0, 9));
+        assert_eq!(map.read(0), Some(1));
+        assert!(map.write(0xffff, 3));
+        assert_eq!((map.read(0), map.peek(1), map.peek(2)), (Some(3), Some(0xff), None));
+        assert!(map.poke(0, 9));
+        assert_eq!(map.peek(0), Some(9));
+    }
+
+    #[test]
+    fn the_stack_follows_the_selected_bank() {
+        let cpu = run("
+            lxi sp,5000h
+            lxi b,1234h
+            push b
+            mvi a,1
+            out 10h
+            inx sp
+            inx sp
+            lxi b,5678h
+            push b
+            xra a
+            out 10h
+            pop h
+            mvi a,1
+            out 10h
+            dcx sp
+            dcx sp
+            pop d
+            hlt
+        ", two_banks);
+
+        let registers = Cpu::registers(&cpu);
+        assert_eq!((registers.h, registers.l), (0x12, 0x34));
+        assert_eq!((registers.d, registers.e), (0x56, 0x78));
+        assert_eq!(cpu.memory()[0x4ffe..0x5000], [0, 0]);
+    }
+
+    #[test]
+    fn a_snapshot_keeps_the_selected_bank_and_its_ram() {
+        let mut cpu = run("
+            mvi a,1
+            out 10h
+            mvi a,33h
+            sta 4000h
+            hlt
+        ", two_banks);
+        let snapshot = cpu.snapshot();
+
+        cpu.memory_map_mut().output(0x10, 0);
+        cpu.poke(0x4000, 0x44);
+        cpu.memory_map_mut().output(0x10, 1);
+        cpu.poke(0x4000, 0x55);
+        cpu.memory_map_mut().output(0x10, 0);
+        cpu.restore(&snapshot);
+
+        assert_eq!(cpu.peek(0x4000), 0x33);
+        cpu.memory_map_mut().output(0x10, 0);
+        assert_eq!(cpu.peek(0x4000), 0);
+    }
+}
@@ -0,0 +1,386 @@
+use crate::cpu::State8080;
+use crate::error::CpuError;
+use crate::invaders_native;
+use crate::processor::{Cpu, Interrupt, Registers};
+use crate::space_invader::{self, GameState, IOState, SpaceInvaderIO};
+
+// nested native calls beyond this run in the interpreter, a program that
+// never returns would otherwise overflow the host stack
+const MAX_NATIVE_DEPTH: usize = 256;
+
+// the bits of the flags byte, where PUSH PSW stores them
+pub const CARRY: u8 = 1;
+pub const PARITY: u8 = 1 << 2;
+pub const AUX_CARRY: u8 = 1 << 4;
+pub const ZERO: u8 = 1 << 6;
+pub const SIGN: u8 = 1 << 7;
+
+// sign, zero and parity of every result
+pub const SZP: [u8; 256] = szp();
+
+const fn szp() -> [u8; 256] {
+    let mut table = [0; 256];
+    let mut value = 0;
+    while value < 256 {
+        let byte = value as u8;
+        table[value] = byte & SIGN
+            | if byte == 0 { ZERO } else { 0 }
+            | if byte.count_ones().is_multiple_of(2) { PARITY } else { 0 };
+        value += 1;
+    }
+    table
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Stop {
+    // the host asked to stop at a deadline
+    Host,
+    Fault(CpuError),
+}
+
+impl From<CpuError> for Stop {
+    fn from(error: CpuError) -> Self {
+        Stop::Fault(error)
+    }
+}
+
+// a recompiled routine, it returns once the program executed a RET in it
+pub type Routine = fn(&mut Machine) -> Result<(), Stop>;
+
+// runs recompiled routines next to a State8080: the generated code works on
+// the registers here and goes to the core for memory and ports, EI, DI, HLT
+// and anything not recompiled are interpreted with the registers handed
+// over; the shadow call stack of the core is not kept
+pub struct Machine<'a> {
+    pub a: u8,
+    // S, Z, AC, P and C in the bits PUSH PSW stores them in
+    pub flags: u8,
+    pub b: u8,
+    pub c: u8,
+    pub d: u8,
+    pub e: u8,
+    pub h: u8,
+    pub l: u8,
+    pub sp: u16,
+    // where the code continues once the instruction is done, up to date
+    // between blocks
+    pub pc: u16,
+    cpu: &'a mut State8080,
+    io: &'a mut dyn IOState,
+    routines: fn(u16) -> Option<Routine>,
+    // native calls in progress
+    depth: usize,
+    cycles: u64,
+    budget: u64,
+    // called every `budget` cycles, like a frame of the host, returns
+    // whether to keep running
+    deadline: &'a mut dyn FnMut(&mut State8080, &mut dyn IOState) -> bool,
+}
+
+impl<'a> Machine<'a> {
+    pub fn new(
+        cpu: &'a mut State8080,
+        io: &'a mut dyn IOState,
+        routines: fn(u16) -> Option<Routine>,
+        budget: u64,
+        deadline: &'a mut dyn FnMut(&mut State8080, &mut dyn IOState) -> bool,
+    ) -> Self {
+        let mut machine = Self {
+            a: 0, flags: 0, b: 0, c: 0, d: 0, e: 0, h: 0, l: 0, sp: 0, pc: 0,
+            cpu, io, routines, depth: 0, cycles: 0, budget, deadline,
+        };
+        machine.load();
+        machine
+    }
+
+    // runs from the current PC until the deadline returns false or the core
+    // faults, the core has the registers afterwards
+    pub fn run(&mut self) -> Stop {
+        loop {
+            let result = match (self.routines)(self.pc) {
+                Some(routine) => routine(self),
+                None => self.interpret(),
+            };
+
+            if let Err(stop) = result {
+                self.store();
+                return stop;
+            }
+        }
+    }
+
+    pub fn bc(&self) -> u16 {
+        (self.b as u16) << 8 | self.c as u16
+    }
+
+    pub fn de(&self) -> u16 {
+        (self.d as u16) << 8 | self.e as u16
+    }
+
+    pub fn hl(&self) -> u16 {
+        (self.h as u16) << 8 | self.l as u16
+    }
+
+    pub fn set_bc(&mut self, value: u16) {
+        self.b = (value >> 8) as u8;
+        self.c = value as u8;
+    }
+
+    pub fn set_de(&mut self, value: u16) {
+        self.d = (value >> 8) as u8;
+        self.e = value as u8;
+    }
+
+    pub fn set_hl(&mut self, value: u16) {
+        self.h = (value >> 8) as u8;
+        self.l = value as u8;
+    }
+
+    // an instruction took `cycles` and the program goes on at `next`, the
+    // deadline and the interrupts it raises are served in between
+    #[inline]
+    pub fn tick(&mut self, cycles: u64, next: u16) -> Result<(), Stop> {
+        self.cycles += cycles;
+        if self.cycles >= self.budget {
+            self.pc = next;
+            self.store();
+            self.reach_deadline()?;
+            self.service()?;
+            self.load();
+        }
+        Ok(())
+    }
+
+    pub fn read(&mut self, address: u16) -> Result<u8, Stop> {
+        Ok(self.cpu.read_memory(address)?)
+    }
+
+    pub fn write(&mut self, address: u16, value: u8) -> Result<(), Stop> {
+        Ok(self.cpu.write_memory(address, value)?)
+    }
+
+    pub fn input(&mut self, port: u8) -> Result<u8, Stop> {
+        Ok(self.cpu.read_port(self.io, port)?)
+    }
+
+    pub fn output(&mut self, port: u8, value: u8) -> Result<(), Stop> {
+        Ok(self.cpu.write_port(self.io, port, value)?)
+    }
+
+    pub fn push(&mut self, value: u16) -> Result<(), Stop> {
+        if self.sp < 2 {
+            return Err(CpuError::StackWrap { sp: self.sp }.into());
+        }
+        self.sp = self.sp.wrapping_sub(2);
+        self.write(self.sp.wrapping_add(1), (value >> 8) as u8)?;
+        self.write(self.sp, value as u8)
+    }
+
+    pub fn pop(&mut self) -> Result<u16, Stop> {
+        if self.sp > 0xfffd {
+            return Err(CpuError::StackWrap { sp: self.sp }.into());
+        }
+        let lsb = self.read(self.sp)?;
+        let msb = self.read(self.sp.wrapping_add(1))?;
+        self.sp = self.sp.wrapping_add(2);
+        Ok((msb as u16) << 8 | lsb as u16)
+    }
+
+    // the interpreter's DAA, including the AC it leaves when there is no
+    // adjustment of the low digit
+    pub fn daa(&mut self) {
+        let mut result = self.a as u16;
+        let mut aux_carry = self.flags & AUX_CARRY;
+
+        let lsb = result & 0xf;
+        if aux_carry != 0 || lsb > 9 {
+            result += 6;
+            if result & 0xf < lsb {
+                aux_carry = AUX_CARRY;
+            }
+        }
+
+        let lsb = result & 0xf;
+        let mut msb = (result >> 4) & 0xf;
+        if self.flags & CARRY != 0 || msb > 9 {
+            msb += 6;
+        }
+
+        let result = (msb << 4) | lsb;
+        self.a = result as u8;
+        self.flags = SZP[self.a as usize] | aux_carry | (result > 0xff) as u8;
+    }
+
+    // the callee at the PC runs until control is back at `return_address`
+    pub fn call(&mut self, return_address: u16) -> Result<(), Stop> {
+        self.depth += 1;
+
+        while self.pc != return_address {
+            match (self.routines)(self.pc) {
+                Some(routine) if self.depth < MAX_NATIVE_DEPTH => routine(self)?,
+                _ => self.interpret()?,
+            }
+        }
+
+        self.depth -= 1;
+        Ok(())
+    }
+
+    // an instruction and the interrupts due after it in the interpreter;
+    // the one after EI goes along, an interrupt waits for it to complete
+    pub fn interpret(&mut self) -> Result<(), Stop> {
+        self.store();
+        let enables_interrupts = self.cpu.peek(self.pc) == 0xfb;
+
+        self.step()?;
+        if enables_interrupts {
+            self.step()?;
+        }
+        self.service()?;
+
+        self.load();
+        Ok(())
+    }
+
+    // takes the registers from the core
+    fn load(&mut self) {
+        let registers = Cpu::registers(&*self.cpu);
+        self.a = registers.a;
+        self.flags = registers.flags & (SIGN | ZERO | AUX_CARRY | PARITY | CARRY);
+        self.b = registers.b;
+        self.c = registers.c;
+        self.d = registers.d;
+        self.e = registers.e;
+        self.h = registers.h;
+        self.l = registers.l;
+        self.sp = registers.sp;
+        self.pc = registers.pc;
+    }
+
+    // hands the registers to the core
+    fn store(&mut self) {
+        self.cpu.set_registers(Registers {
+            a: self.a,
+            flags: self.flags,
+            b: self.b,
+            c: self.c,
+            d: self.d,
+            e: self.e,
+            h: self.h,
+            l: self.l,
+            sp: self.sp,
+            pc: self.pc,
+        });
+    }
+
+    // the rest work on the core, which has the registers
+
+    fn step(&mut self) -> Result<(), Stop> {
+        let cycles = self.cpu.emulate(self.io)?;
+        self.cycles += cycles;
+
+        if self.cycles >= self.budget {
+            self.reach_deadline()?;
+        }
+        Ok(())
+    }
+
+    fn reach_deadline(&mut self) -> Result<(), Stop> {
+        self.cycles = 0;
+        if !(self.deadline)(self.cpu, self.io) {
+            return Err(Stop::Host);
+        }
+        Ok(())
+    }
+
+    // idles a halted core and runs the handlers of acknowledged interrupts
+    // until the interrupted code can continue
+    fn service(&mut self) -> Result<(), Stop> {
+        let resume = self.cpu.pc();
+
+        while self.cpu.halted() || self.cpu.interrupt_due() {
+            let acknowledge = self.cpu.interrupt_due();
+
+            self.step()?;
+            if acknowledge {
+                self.load();
+                self.call(resume)?;
+                self.store();
+            }
+        }
+        Ok(())
+    }
+}
+
+// runs the recompiled invaders next to the interpreter until the frames
+// differ in registers or memory, raising the video interrupts at the same
+// cycles as `GameState::run_frame`
+pub fn diff_invaders(frames: u64) -> Result<(), String> {
+    let mut interpreter = GameState::new_game();
+    let mut cpu = space_invader::invaders_cpu();
+    let mut io = SpaceInvaderIO::new();
+    let mut halves = 0;
+    let mut result = Ok(());
+
+    let mut deadline = |cpu: &mut State8080, _: &mut dyn IOState| {
+        let is_top = halves % 2 == 0;
+        halves += 1;
+        cpu.interrupt(Interrupt::Rst(if is_top { 1 } else { 2 }));
+        if is_top {
+            return true;
+        }
+
+        let frame = halves / 2;
+        if let Err(error) = interpreter.run_frame() {
+            result = Err(format!("interpreter stopped in frame {}: {}", frame, error));
+            return false;
+        }
+
+        let (expected, actual) = (interpreter.cpu(), &*cpu);
+        if expected.registers() != actual.registers() {
+            result = Err(format!(
+                "registers differ after frame {}\ninterpreter {:?}\nnative      {:?}",
+                frame, expected.registers(), actual.registers(),
+            ));
+        } else if let Some(address) = (0..expected.memory().len()).find(|&i| expected.memory()[i] != actual.memory()[i]) {
+            result = Err(format!(
+                "memory differs after frame {} at {:04x}: interpreter {:02x}, native {:02x}",
+                frame, address, expected.memory()[address], actual.memory()[address],
+            ));
+        }
+        result.is_ok() && frame < frames
+    };
+
+    let budget = GameState::<State8080>::CYCLES_PER_FRAME / 2;
+    let stop = Machine::new(&mut cpu, &mut io, invaders_native::routine, budget, &mut deadline).run();
+
+    result?;
+    match stop {
+        Stop::Host => Ok(()),
+        Stop::Fault(error) => Err(format!("native code stopped: {}", error)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::recompiler;
+
+    #[test]
+    fn native_invaders_runs_the_same_frames_as_the_interpreter() {
+        diff_invaders(300).unwrap();
+    }
+
+    // the header names the command, which is not what keeps them in step
+    #[test]
+    fn invaders_native_is_what_recompile_writes() {
+        let entries: Vec<u16> = (0..8).map(|n| n * 8).collect();
+        let generated = recompiler::recompile(include_bytes!("invaders.rom"), &entries, "");
+        let checked_in = include_str!("invaders_native.rs");
+
+        let body = |text: &str| text.split_once('\n').map(|(_, body)| body.to_string());
+        // not assert_eq, the file is too long to print
+        assert!(body(checked_in) == body(&generated),
+            "src/invaders_native.rs is out of date, run rust-8080 recompile src/invaders.rom src/invaders_native.rs");
+    }
+}
@@ -0,0 +1,171 @@
+use std::fmt;
+
+use crate::hex;
+
+// the section code placed with ORG goes to, its offsets are addresses
+pub const ABSOLUTE: &str = "absolute";
+// first line of an object file
+const HEADER: &str = "8080 object 1";
+// bytes on a line of an object file
+const BYTES_PER_LINE: usize = 32;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RelocationKind {
+    Word,
+    // the low or the high byte of the address, from LOW and HIGH
+    Low,
+    High,
+}
+
+// what a relocatable value is relative to
+#[derive(Clone, Debug, PartialEq)]
+pub enum Target {
+    // the start of the object's part of a section
+    Section(String),
+    // a symbol another object makes public
+    External(String),
+}
+
+// a value the linker fills in once it knows the addresses
+#[derive(Clone, Debug, PartialEq)]
+pub struct Relocation {
+    pub section: String,
+    pub offset: u16,
+    pub kind: RelocationKind,
+    pub target: Target,
+    pub addend: i64,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Section {
+    pub name: String,
+    // bytes reserved, DS included
+    pub size: u32,
+    // runs of bytes and the offsets they start at
+    pub chunks: Vec<(u16, Vec<u8>)>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Symbol {
+    pub name: String,
+    // the section the value is an offset into
+    pub section: String,
+    pub value: u16,
+    // other objects can refer to it
+    pub public: bool,
+}
+
+// what the assembler produces for the linker, as lines of text:
+//
+// 8080 object 1
+// section code 0012
+// bytes code 0000 3e01cd0000c9
+// symbol start code 0000 public
+// extern print
+// relocation code 0003 word extern print 0
+// entry code 0000
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Object {
+    // the absolute section comes first
+    pub sections: Vec<Section>,
+    pub symbols: Vec<Symbol>,
+    pub externs: Vec<String>,
+    pub relocations: Vec<Relocation>,
+    pub entry: Option<(String, u16)>,
+}
+
+impl Object {
+    pub fn parse(text: &str) -> Result<Object, String> {
+        let mut lines = text.lines().enumerate();
+        match lines.next() {
+            Some((_, HEADER)) => (),
+            _ => return Err("not an object file".to_string()),
+        }
+
+        let mut object = Object::default();
+        for (index, line) in lines {
+            let fields = line.split_whitespace().collect::<Vec<_>>();
+            let error = || format!("line {}: bad {}", index + 1, fields.first().unwrap_or(&"line"));
+
+            match fields.as_slice() {
+                [] => (),
+                ["section", name, size] => object.sections.push(Section {
+                    name: name.to_string(),
+                    size: u32::from_str_radix(size, 16).map_err(|_| error())?,
+                    chunks: Vec::new(),
+                }),
+                ["bytes", section, offset, bytes] => {
+                    let offset = hex::address(offset).ok_or_else(error)?;
+                    let bytes = hex::bytes(bytes).ok_or_else(error)?;
+                    let section = object.sections.iter_mut().find(|candidate| candidate.name == *section).ok_or_else(error)?;
+                    section.chunks.push((offset, bytes));
+                },
+                ["symbol", name, section, value, rest @ ..] => object.symbols.push(Symbol {
+                    name: name.to_string(),
+                    section: section.to_string(),
+                    value: hex::address(value).ok_or_else(error)?,
+                    public: rest == ["public"],
+                }),
+                ["extern", name] => object.externs.push(name.to_string()),
+                ["relocation", section, offset, kind, target, name, addend] => object.relocations.push(Relocation {
+                    section: section.to_string(),
+                    offset: hex::address(offset).ok_or_else(error)?,
+                    kind: match *kind {
+                        "word" => RelocationKind::Word,
+                        "low" => RelocationKind::Low,
+                        "high" => RelocationKind::High,
+                        _ => return Err(error()),
+                    },
+                    target: match *target {
+                        "section" => Target::Section(name.to_string()),
+                        "extern" => Target::External(name.to_string()),
+                        _ => return Err(error()),
+                    },
+                    addend: addend.parse().map_err(|_| error())?,
+                }),
+                ["entry", section, offset] => object.entry = Some((section.to_string(), hex::address(offset).ok_or_else(error)?)),
+                _ => return Err(error()),
+            }
+        }
+        Ok(object)
+    }
+}
+
+impl fmt::Display for Object {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        writeln!(f, "{}", HEADER)?;
+
+        for section in &self.sections {
+            writeln!(f, "section {} {:04x}", section.name, section.size)?;
+            for (offset, bytes) in &section.chunks {
+                for (i, line) in bytes.chunks(BYTES_PER_LINE).enumerate() {
+                    let digits = line.iter().map(|byte| format!("{:02x}", byte)).collect::<String>();
+                    writeln!(f, "bytes {} {:04x} {}", section.name, offset.wrapping_add((i * BYTES_PER_LINE) as u16), digits)?;
+                }
+            }
+        }
+        for symbol in &self.symbols {
+            let public = if symbol.public { " public" } else { "" };
+            writeln!(f, "symbol {} {} {:04x}{}", symbol.name, symbol.section, symbol.value, public)?;
+        }
+        for name in &self.externs {
+            writeln!(f, "extern {}", name)?;
+        }
+        for relocation in &self.relocations {
+            let kind = match relocation.kind {
+                RelocationKind::Word => "word",
+                RelocationKind::Low => "low",
+                RelocationKind::High => "high",
+            };
+            let (target, name) = match &relocation.target {
+                Target::Section(name) => ("section", name),
+                Target::External(name) => ("extern", name),
+            };
+            writeln!(f, "relocation {} {:04x} {} {} {} {}", relocation.section, relocation.offset, kind, target, name, relocation.addend)?;
+        }
+        if let Some((section, offset)) = &self.entry {
+            writeln!(f, "entry {} {:04x}", section, offset)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,74 @@
+use crate::call_stack::Backtrace;
+use crate::error::CpuError;
+use crate::space_invader::IOState;
+
+// what the interrupting device puts on the data bus during the acknowledge
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Interrupt {
+    // RST n, continuing at 8 * n
+    Rst(u8),
+    // an arbitrary instruction, e.g. the CALL an 8259 supplies
+    Instruction([u8; 3]),
+}
+
+impl Interrupt {
+    // first byte on the data bus
+    pub fn opcode(self) -> u8 {
+        match self {
+            Interrupt::Rst(n) => 0xc7 | (n & 7) << 3,
+            Interrupt::Instruction(bytes) => bytes[0],
+        }
+    }
+}
+
+// the 8080 register set, cores with more registers report the shared subset
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Registers {
+    pub a: u8,
+    pub flags: u8,
+    pub b: u8,
+    pub c: u8,
+    pub d: u8,
+    pub e: u8,
+    pub h: u8,
+    pub l: u8,
+    pub sp: u16,
+    pub pc: u16,
+}
+
+pub trait Cpu {
+    // executes one instruction and returns the cycles it took
+    fn step(&mut self, state: &mut dyn IOState) -> Result<u64, CpuError>;
+    // steps until at least `budget` cycles have run, returning the cycles
+    // and the instructions executed
+    fn run_for(&mut self, budget: u64, state: &mut dyn IOState) -> Result<(u64, u64), CpuError> {
+        let mut cycles = 0;
+        let mut instructions = 0;
+
+        while cycles < budget {
+            cycles += self.step(state)?;
+            instructions += 1;
+        }
+        Ok((cycles, instructions))
+    }
+    // raises the INT line, the interrupt is acknowledged by the first step
+    // where interrupts are enabled, waking the core if it was halted
+    fn interrupt(&mut self, interrupt: Interrupt);
+    // pulses the NMI line, acknowledged by the next step even with
+    // interrupts disabled; false for cores without one
+    fn nmi(&mut self) -> bool {
+        false
+    }
+    fn reset(&mut self);
+    fn registers(&self) -> Registers;
+    fn halted(&self) -> bool;
+    fn memory(&self) -> &[u8];
+    // reads memory without a bus cycle, the bus floats high outside of it
+    fn peek(&self, address: u16) -> u8 {
+        self.memory().get(address as usize).copied().unwrap_or(0xff)
+    }
+    // return addresses of the calls the program is in, for cores that track them
+    fn backtrace(&self) -> Backtrace {
+        Backtrace::default()
+    }
+}
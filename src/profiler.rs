@@ -0,0 +1,321 @@
+use std::collections::HashMap;
+use std::io::{self, Write};
+
+use crate::call_stack::Frame;
+use crate::symbols::SymbolTable;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Counters {
+    pub instructions: u64,
+    pub cycles: u64,
+}
+
+impl Counters {
+    fn add(&mut self, other: Counters) {
+        self.instructions += other.instructions;
+        self.cycles += other.cycles;
+    }
+
+    fn since(self, start: Counters) -> Counters {
+        Counters {
+            instructions: self.instructions - start.instructions,
+            cycles: self.cycles - start.cycles,
+        }
+    }
+}
+
+// None is the code that runs outside of any call, from reset
+pub type Function = Option<u16>;
+
+// caller, call site and callee
+type Call = (Function, u16, u16);
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FunctionProfile {
+    pub calls: u64,
+    // spent in the function itself
+    pub exclusive: Counters,
+    // including its callees, recursive calls are counted once per level
+    pub inclusive: Counters,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct CallProfile {
+    pub calls: u64,
+    pub inclusive: Counters,
+}
+
+// an entered frame and the totals when it was entered
+struct Activation {
+    frame: Frame,
+    start: Counters,
+}
+
+// counts instructions and cycles per PC and per called routine, driven by
+// the shadow call stack of the core
+pub struct Profiler {
+    pcs: Vec<Counters>,
+    functions: HashMap<Function, FunctionProfile>,
+    calls: HashMap<Call, CallProfile>,
+    activations: Vec<Activation>,
+    // the function of every PC that ran, for the callgrind positions
+    owners: HashMap<(Function, u16), Counters>,
+    total: Counters,
+}
+
+impl Profiler {
+    pub fn new() -> Self {
+        Self {
+            pcs: vec![Counters::default(); 0x10000],
+            functions: HashMap::new(),
+            calls: HashMap::new(),
+            activations: Vec::new(),
+            owners: HashMap::new(),
+            total: Counters::default(),
+        }
+    }
+
+    fn current(&self) -> Function {
+        self.activations.last().map(|activation| activation.frame.target)
+    }
+
+    // the instruction at `address` took `cycles`, `frames` is the call stack
+    // after it executed
+    pub fn record(&mut self, address: u16, cycles: u64, frames: &[Frame]) {
+        let counters = Counters { instructions: 1, cycles };
+        let function = self.current();
+
+        self.pcs[address as usize].add(counters);
+        self.owners.entry((function, address)).or_default().add(counters);
+        self.functions.entry(function).or_default().exclusive.add(counters);
+        self.total.add(counters);
+
+        self.follow(frames);
+    }
+
+    // returns from every activation the call stack no longer has and enters
+    // the new frames
+    fn follow(&mut self, frames: &[Frame]) {
+        let unchanged = frames.len() == self.activations.len()
+            && frames.last().map(same_call) == self.activations.last().map(|a| same_call(&a.frame));
+        if unchanged {
+            return;
+        }
+
+        let common = self.activations.iter()
+            .zip(frames)
+            .take_while(|(activation, frame)| same_call(&activation.frame) == same_call(frame))
+            .count();
+
+        while self.activations.len() > common {
+            let activation = self.activations.pop().unwrap();
+            let inclusive = self.total.since(activation.start);
+            let caller = self.current();
+
+            self.functions.entry(Some(activation.frame.target)).or_default().inclusive.add(inclusive);
+            let call = self.calls
+                .entry((caller, activation.frame.call_site, activation.frame.target))
+                .or_default();
+            call.inclusive.add(inclusive);
+        }
+
+        for frame in &frames[common..] {
+            let caller = self.current();
+
+            self.functions.entry(Some(frame.target)).or_default().calls += 1;
+            self.calls.entry((caller, frame.call_site, frame.target)).or_default().calls += 1;
+            self.activations.push(Activation { frame: *frame, start: self.total });
+        }
+    }
+
+    // the activations still running, added to the inclusive costs without
+    // leaving them
+    fn running(&self) -> (HashMap<Function, Counters>, HashMap<Call, Counters>) {
+        let mut functions = HashMap::new();
+        let mut calls = HashMap::new();
+
+        for (depth, activation) in self.activations.iter().enumerate() {
+            let inclusive = self.total.since(activation.start);
+            let caller = if depth == 0 { None } else { Some(self.activations[depth - 1].frame.target) };
+            let frame = activation.frame;
+
+            functions.entry(Some(frame.target)).or_insert_with(Counters::default).add(inclusive);
+            calls.entry((caller, frame.call_site, frame.target)).or_insert_with(Counters::default).add(inclusive);
+        }
+        (functions, calls)
+    }
+
+    fn inclusive(&self, function: Function, running: &HashMap<Function, Counters>) -> Counters {
+        match function {
+            None => self.total,
+            Some(_) => {
+                let mut inclusive = self.functions[&function].inclusive;
+                if let Some(counters) = running.get(&function) {
+                    inclusive.add(*counters);
+                }
+                inclusive
+            },
+        }
+    }
+
+    // functions by exclusive cycles followed by the hottest instructions
+    pub fn write_report(&self, out: &mut dyn Write, symbols: &SymbolTable) -> io::Result<()> {
+        let (running, _) = self.running();
+        let total = self.total.cycles.max(1) as f64;
+
+        let mut functions: Vec<_> = self.functions.iter().collect();
+        functions.sort_by(|a, b| b.1.exclusive.cycles.cmp(&a.1.exclusive.cycles).then(a.0.cmp(b.0)));
+
+        writeln!(out, "{} instructions, {} cycles", self.total.instructions, self.total.cycles)?;
+        writeln!(out)?;
+        writeln!(
+            out, "{:>7} {:>12} {:>7} {:>12} {:>10} {:>12}  function",
+            "self %", "self cycles", "incl %", "incl cycles", "calls", "instructions"
+        )?;
+        for (&function, profile) in functions {
+            let inclusive = self.inclusive(function, &running);
+            writeln!(
+                out, "{:>6.2}% {:>12} {:>6.2}% {:>12} {:>10} {:>12}  {}",
+                100.0 * profile.exclusive.cycles as f64 / total,
+                profile.exclusive.cycles,
+                100.0 * inclusive.cycles as f64 / total,
+                inclusive.cycles,
+                profile.calls,
+                profile.exclusive.instructions,
+                function_name(function, symbols),
+            )?;
+        }
+
+        let mut pcs: Vec<_> = (0..=0xffff_u16).filter(|&pc| self.pcs[pc as usize].instructions > 0).collect();
+        pcs.sort_by(|&a, &b| self.pcs[b as usize].cycles.cmp(&self.pcs[a as usize].cycles).then(a.cmp(&b)));
+
+        writeln!(out)?;
+        writeln!(out, "{:>7} {:>12} {:>12}  address", "%", "cycles", "instructions")?;
+        for pc in pcs.into_iter().take(50) {
+            let counters = self.pcs[pc as usize];
+            writeln!(
+                out, "{:>6.2}% {:>12} {:>12}  {:04x}",
+                100.0 * counters.cycles as f64 / total, counters.cycles, counters.instructions, pc,
+            )?;
+        }
+        Ok(())
+    }
+
+    // callgrind format with instruction addresses as positions, readable by
+    // kcachegrind, qcachegrind and callgrind_annotate; `object` names the
+    // program that ran
+    pub fn write_callgrind(&self, out: &mut dyn Write, object: &str, symbols: &SymbolTable) -> io::Result<()> {
+        let (_, running) = self.running();
+        let mut names = HashMap::new();
+
+        writeln!(out, "# callgrind format")?;
+        writeln!(out, "version: 1")?;
+        writeln!(out, "creator: rust-8080")?;
+        writeln!(out, "positions: instr")?;
+        writeln!(out, "events: Instructions Cycles")?;
+        writeln!(out, "summary: {} {}", self.total.instructions, self.total.cycles)?;
+        writeln!(out)?;
+        writeln!(out, "ob={}", object)?;
+        writeln!(out, "fl={}", object)?;
+
+        let mut functions: Vec<Function> = self.functions.keys().copied().collect();
+        functions.sort();
+
+        for function in functions {
+            writeln!(out)?;
+            writeln!(out, "fn={}", compressed_name(&mut names, function, symbols))?;
+
+            let mut pcs: Vec<_> = self.owners.iter()
+                .filter(|((owner, _), _)| *owner == function)
+                .map(|((_, pc), counters)| (*pc, *counters))
+                .collect();
+            pcs.sort_by_key(|&(pc, _)| pc);
+            for (pc, counters) in pcs {
+                writeln!(out, "0x{:04x} {} {}", pc, counters.instructions, counters.cycles)?;
+            }
+
+            let mut calls: Vec<_> = self.calls.iter()
+                .filter(|((caller, _, _), _)| *caller == function)
+                .collect();
+            calls.sort_by_key(|(key, _)| **key);
+            for (&(_, call_site, callee), call) in calls {
+                let mut inclusive = call.inclusive;
+                if let Some(counters) = running.get(&(function, call_site, callee)) {
+                    inclusive.add(*counters);
+                }
+
+                writeln!(out, "cfn={}", compressed_name(&mut names, Some(callee), symbols))?;
+                writeln!(out, "calls={} 0x{:04x}", call.calls, callee)?;
+                writeln!(out, "0x{:04x} {} {}", call_site, inclusive.instructions, inclusive.cycles)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn same_call(frame: &Frame) -> (u16, u16, u16) {
+    (frame.slot, frame.call_site, frame.target)
+}
+
+pub fn function_name(function: Function, symbols: &SymbolTable) -> String {
+    match function {
+        Some(address) => symbols.label(address).unwrap_or_else(|| format!("sub_{:04x}", address)),
+        None => "(reset)".to_string(),
+    }
+}
+
+// callgrind names a function in full once and by its id afterwards
+fn compressed_name(names: &mut HashMap<Function, usize>, function: Function, symbols: &SymbolTable) -> String {
+    match names.get(&function) {
+        Some(id) => format!("({})", id),
+        None => {
+            let id = names.len() + 1;
+            names.insert(function, id);
+            format!("({}) {}", id, function_name(function, symbols))
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assembler;
+    use crate::bench::Console;
+    use crate::cpu::State8080;
+    use crate::processor::Cpu;
+
+    #[test]
+    fn callgrind_charges_a_nested_call_to_every_caller() {
+        let assembly = assembler::assemble("
+                lxi sp,1000h
+                call outer
+                hlt
+            outer:
+                call inner
+                ret
+            inner:
+                nop
+                ret
+        ").unwrap();
+        let mut cpu = State8080::new();
+        cpu.load_image(&assembly.image).unwrap();
+        cpu.set_profiler(Profiler::new());
+        let mut console = Console { output: String::new() };
+        while !cpu.halted() {
+            cpu.emulate(&mut console).unwrap();
+        }
+
+        let symbols = SymbolTable::parse("0007 outer\n000b inner\n").unwrap();
+        let mut out = Vec::new();
+        cpu.profiler().unwrap().write_callgrind(&mut out, "nested.com", &symbols).unwrap();
+        let text = String::from_utf8(out).unwrap();
+
+        assert!(text.contains("ob=nested.com\nfl=nested.com\n"));
+        // the CALL of outer costs its own 17 cycles, inner's NOP and RET
+        // and the RET of outer
+        assert!(text.contains("cfn=(2) outer\ncalls=1 0x0007\n0x0003 4 41\n"));
+        assert!(text.contains("cfn=(3) inner\ncalls=1 0x000b\n0x0007 2 14\n"));
+        assert!(text.contains("fn=(3)\n0x000b 1 4\n0x000c 1 10\n"));
+        assert!(text.contains("summary: 7 75\n"));
+    }
+}
@@ -0,0 +1,578 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write;
+
+use crate::block_cache::DecodedInstruction;
+use crate::cpu::{instruction_length, is_illegal};
+use crate::disassembler::disassemble;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Flow {
+    Next,
+    Jump(u16),
+    ConditionalJump(u16),
+    Call(u16),
+    Return,
+    ConditionalReturn,
+    // PCHL, the target is only known at run time
+    Indirect,
+}
+
+pub fn flow(opcode: u8, operand: u16) -> Flow {
+    match opcode {
+        0xc3 => Flow::Jump(operand),
+        0xc9 => Flow::Return,
+        0xcd => Flow::Call(operand),
+        0xe9 => Flow::Indirect,
+        _ if opcode & 0xc7 == 0xc2 => Flow::ConditionalJump(operand),
+        _ if opcode & 0xc7 == 0xc4 => Flow::Call(operand),
+        _ if opcode & 0xc7 == 0xc0 => Flow::ConditionalReturn,
+        _ if opcode & 0xc7 == 0xc7 => Flow::Call((opcode & 0x38) as u16),
+        _ => Flow::Next,
+    }
+}
+
+// the code reachable from an entry without following calls
+struct Routine {
+    instructions: BTreeMap<u16, DecodedInstruction>,
+    // addresses control can arrive at other than from the instruction before
+    leaders: BTreeSet<u16>,
+}
+
+fn decode(rom: &[u8], address: u16) -> Option<DecodedInstruction> {
+    let opcode = *rom.get(address as usize)?;
+    let length = instruction_length(opcode);
+    let end = address as usize + length as usize;
+
+    if is_illegal(opcode) || end > rom.len() {
+        return None;
+    }
+
+    let operand = match length {
+        2 => rom[address as usize + 1] as u16,
+        3 => (rom[address as usize + 2] as u16) << 8 | rom[address as usize + 1] as u16,
+        _ => 0,
+    };
+    Some(DecodedInstruction { address, opcode, operand, length })
+}
+
+// follows jumps and fall-throughs from the entry, collecting the callees
+fn discover(rom: &[u8], entry: u16, callees: &mut Vec<u16>) -> Routine {
+    let mut routine = Routine { instructions: BTreeMap::new(), leaders: BTreeSet::new() };
+    let mut pending = vec![entry];
+    routine.leaders.insert(entry);
+
+    while let Some(address) = pending.pop() {
+        if routine.instructions.contains_key(&address) {
+            continue;
+        }
+        // code outside the ROM is left to the interpreter
+        let instruction = match decode(rom, address) {
+            Some(instruction) => instruction,
+            None => continue,
+        };
+        routine.instructions.insert(address, instruction);
+
+        let next = address.wrapping_add(instruction.length);
+        match flow(instruction.opcode, instruction.operand) {
+            Flow::Jump(target) => {
+                routine.leaders.insert(target);
+                pending.push(target);
+            },
+            Flow::ConditionalJump(target) => {
+                routine.leaders.insert(target);
+                routine.leaders.insert(next);
+                pending.push(target);
+                pending.push(next);
+            },
+            Flow::Call(target) => {
+                callees.push(target);
+                pending.push(next);
+            },
+            Flow::Return | Flow::Indirect => (),
+            Flow::Next | Flow::ConditionalReturn => pending.push(next),
+        }
+    }
+
+    routine
+}
+
+fn ends_block(instruction: &DecodedInstruction) -> bool {
+    matches!(
+        flow(instruction.opcode, instruction.operand),
+        Flow::Jump(_) | Flow::ConditionalJump(_) | Flow::Return | Flow::Indirect
+    )
+}
+
+// EI, DI and HLT change the interrupt state of the core, they and the
+// instruction after EI run in the interpreter
+fn interpreted(opcode: u8) -> bool {
+    matches!(opcode, 0x76 | 0xf3 | 0xfb)
+}
+
+// Rust source for the routines reachable from the entry points, see
+// `native::Machine` for what the generated code runs on; the header names
+// `command` as what made it
+pub fn recompile(rom: &[u8], entries: &[u16], command: &str) -> String {
+    let mut routines = BTreeMap::new();
+    let mut pending = entries.to_vec();
+
+    while let Some(entry) = pending.pop() {
+        if !routines.contains_key(&entry) && decode(rom, entry).is_some() {
+            let routine = discover(rom, entry, &mut pending);
+            routines.insert(entry, routine);
+        }
+    }
+
+    let mut code = String::new();
+    writeln!(code, "pub fn routine(address: u16) -> Option<Routine> {{").unwrap();
+    writeln!(code, "    match address {{").unwrap();
+    for entry in routines.keys() {
+        writeln!(code, "        0x{:04x} => Some(sub_{:04x}),", entry, entry).unwrap();
+    }
+    writeln!(code, "        _ => None,").unwrap();
+    writeln!(code, "    }}").unwrap();
+    writeln!(code, "}}").unwrap();
+
+    for (entry, routine) in &routines {
+        writeln!(code).unwrap();
+        write_routine(&mut code, *entry, routine);
+    }
+
+    // the flags the code tests and sets
+    let mut imports = vec!["Machine", "Routine", "Stop"];
+    imports.extend(["AUX_CARRY", "CARRY", "PARITY", "SIGN", "SZP", "ZERO"].iter().filter(|name| mentions(&code, name)));
+
+    let mut source = String::new();
+    writeln!(source, "// Recompiled from an 8080 ROM by `{}`, do not edit.", command).unwrap();
+    writeln!(source).unwrap();
+    writeln!(source, "use crate::native::{{{}}};", imports.join(", ")).unwrap();
+    writeln!(source).unwrap();
+    source.push_str(&code);
+    source
+}
+
+// `name` as a whole word, AUX_CARRY does not mention CARRY
+fn mentions(code: &str, name: &str) -> bool {
+    let word = |c: char| c.is_ascii_alphanumeric() || c == '_';
+    code.match_indices(name).any(|(at, _)| {
+        !code[..at].ends_with(word) && !code[at + name.len()..].starts_with(word)
+    })
+}
+
+// a loop over the blocks of the routine: jumps inside it set the PC and go
+// round, the routine returns on a RET or when control leaves its code
+fn write_routine(source: &mut String, entry: u16, routine: &Routine) {
+    // the interpreted instructions are blocks of their own
+    let mut leaders = routine.leaders.clone();
+    for instruction in routine.instructions.values().filter(|instruction| interpreted(instruction.opcode)) {
+        let mut next = instruction.address.wrapping_add(instruction.length);
+        if instruction.opcode == 0xfb {
+            if let Some(following) = routine.instructions.get(&next) {
+                next = next.wrapping_add(following.length);
+            }
+        }
+        leaders.insert(instruction.address);
+        leaders.insert(next);
+    }
+
+    writeln!(source, "fn sub_{:04x}(m: &mut Machine) -> Result<(), Stop> {{", entry).unwrap();
+
+    // a routine of a single block that ends in RET needs no loop
+    let first = &routine.instructions[&entry];
+    if !interpreted(first.opcode) {
+        let (mut lines, returns) = block(routine, &leaders, entry);
+        if returns {
+            lines.pop();
+            lines.push("Ok(())".to_string());
+            for line in lines {
+                writeln!(source, "    {}", line).unwrap();
+            }
+            writeln!(source, "}}").unwrap();
+            return;
+        }
+    }
+
+    writeln!(source, "    loop {{").unwrap();
+    writeln!(source, "        match m.pc {{").unwrap();
+    for &leader in &leaders {
+        let instruction = match routine.instructions.get(&leader) {
+            Some(instruction) => instruction,
+            None => continue,
+        };
+
+        if interpreted(instruction.opcode) {
+            writeln!(source, "            // {:04x}  {}", leader, disassemble(instruction.opcode, instruction.operand)).unwrap();
+            writeln!(source, "            0x{:04x} => m.interpret()?,", leader).unwrap();
+            continue;
+        }
+
+        writeln!(source, "            0x{:04x} => {{", leader).unwrap();
+        for line in block(routine, &leaders, leader).0 {
+            writeln!(source, "                {}", line).unwrap();
+        }
+        writeln!(source, "            }},").unwrap();
+    }
+    writeln!(source, "            _ => return Ok(()),").unwrap();
+    writeln!(source, "        }}").unwrap();
+    writeln!(source, "    }}").unwrap();
+    writeln!(source, "}}").unwrap();
+}
+
+// the code of the block starting at `leader` up to the next leader or
+// jump, and whether it ends in a RET
+fn block(routine: &Routine, leaders: &BTreeSet<u16>, leader: u16) -> (Vec<String>, bool) {
+    let mut instruction = &routine.instructions[&leader];
+    let mut lines = Vec::new();
+    loop {
+        lines.extend(instruction_code(instruction));
+        if ends_block(instruction) {
+            return (lines, instruction.opcode == 0xc9);
+        }
+
+        let next = instruction.address.wrapping_add(instruction.length);
+        match routine.instructions.get(&next) {
+            Some(following) if !leaders.contains(&next) => instruction = following,
+            _ => {
+                lines.push(format!("m.pc = 0x{:04x};", next));
+                return (lines, false);
+            },
+        }
+    }
+}
+
+const REGISTERS: [&str; 8] = ["m.b", "m.c", "m.d", "m.e", "m.h", "m.l", "m", "m.a"];
+const PAIRS: [&str; 4] = ["bc", "de", "hl", "sp"];
+// the flag every condition of Jcc, Ccc and Rcc tests and whether it is set
+const CONDITIONS: [(&str, bool); 8] = [
+    ("ZERO", false), ("ZERO", true), ("CARRY", false), ("CARRY", true),
+    ("PARITY", false), ("PARITY", true), ("SIGN", false), ("SIGN", true),
+];
+
+fn pair(index: u8) -> String {
+    match PAIRS[index as usize & 3] {
+        "sp" => "m.sp".to_string(),
+        pair => format!("m.{}()", pair),
+    }
+}
+
+fn set_pair(index: u8, value: &str) -> String {
+    match PAIRS[index as usize & 3] {
+        "sp" => format!("m.sp = {};", value),
+        pair => format!("m.set_{}({});", pair, value),
+    }
+}
+
+fn condition(opcode: u8) -> String {
+    match CONDITIONS[(opcode >> 3 & 7) as usize] {
+        (flag, true) => format!("m.flags & {} != 0", flag),
+        (flag, false) => format!("m.flags & {} == 0", flag),
+    }
+}
+
+// ADD, ADC, SUB, SBB, ANA, XRA, ORA and CMP of A and `value`, with the flags
+// exactly as the interpreter leaves them
+fn alu(operation: u8) -> Vec<String> {
+    let flags = |carry: &str, aux_carry: &str| format!(
+        "m.flags = SZP[result as u8 as usize] | {} | if {} > 0xf {{ AUX_CARRY }} else {{ 0 }};", carry, aux_carry
+    );
+    let store = "m.a = result as u8;".to_string();
+
+    match operation & 7 {
+        0 => vec![
+            "let result = m.a as u16 + value as u16;".to_string(),
+            flags("(result >> 8) as u8", "(m.a & 0xf) + (value & 0xf)"),
+            store,
+        ],
+        1 => vec![
+            "let carry = m.flags & CARRY;".to_string(),
+            "let result = m.a as u16 + value as u16 + carry as u16;".to_string(),
+            flags("(result >> 8) as u8", "(m.a & 0xf) + (value & 0xf) + carry"),
+            store,
+        ],
+        2 | 7 => {
+            let mut lines = vec![
+                "let result = (m.a as u16).wrapping_sub(value as u16);".to_string(),
+                flags("(result > 0xff) as u8", "(m.a & 0xf).wrapping_sub(value & 0xf)"),
+            ];
+            if operation & 7 == 2 {
+                lines.push(store);
+            }
+            lines
+        },
+        3 => vec![
+            "let carry = m.flags & CARRY;".to_string(),
+            "let result = (m.a as u16).wrapping_sub(value as u16).wrapping_sub(carry as u16);".to_string(),
+            flags("(result > 0xff) as u8", "(m.a & 0xf).wrapping_sub(value & 0xf).wrapping_sub(carry)"),
+            store,
+        ],
+        4 => vec![
+            "m.a &= value;".to_string(),
+            "m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);".to_string(),
+        ],
+        5 => vec![
+            "m.a ^= value;".to_string(),
+            "m.flags = SZP[m.a as usize] | if m.a > 0xf { AUX_CARRY } else { 0 };".to_string(),
+        ],
+        _ => vec![
+            "m.a |= value;".to_string(),
+            "m.flags = SZP[m.a as usize] | (m.flags & AUX_CARRY);".to_string(),
+        ],
+    }
+}
+
+// INR or DCR of `value`, carry untouched
+fn increment(decrement: bool) -> Vec<String> {
+    let (operation, aux_carry) = if decrement {
+        ("wrapping_sub", "value & 0xf != 0xf")
+    } else {
+        ("wrapping_add", "value & 0xf == 0")
+    };
+    vec![
+        format!("let value = value.{}(1);", operation),
+        format!("m.flags = SZP[value as usize] | (m.flags & CARRY) | if {} {{ AUX_CARRY }} else {{ 0 }};", aux_carry),
+    ]
+}
+
+// the statements of one instruction, the cycles it takes are counted with
+// `tick` once it is done; control leaves the block with `return` or by
+// setting the PC at its end
+fn instruction_code(instruction: &DecodedInstruction) -> Vec<String> {
+    let DecodedInstruction { address, opcode, operand, length } = *instruction;
+    let next = address.wrapping_add(length);
+    let data = format!("0x{:02x}", operand as u8);
+    let target = format!("0x{:04x}", operand);
+    let destination = REGISTERS[(opcode >> 3 & 7) as usize];
+    let source_register = REGISTERS[(opcode & 7) as usize];
+
+    let mut lines: Vec<String> = Vec::new();
+    let mut cycles = 4;
+    // the PC the tick may hand to the interpreter, none when the
+    // instruction counts its cycles itself
+    let mut tick = Some(format!("0x{:04x}", next));
+
+    match opcode {
+        0x00 | 0x20 => (),
+        0x02 | 0x12 => {
+            lines.push(format!("m.write({}, m.a)?;", pair(opcode >> 4)));
+            cycles = 7;
+        },
+        0x0a | 0x1a => {
+            lines.push(format!("m.a = m.read({})?;", pair(opcode >> 4)));
+            cycles = 7;
+        },
+        0x07 => lines.extend(vec![
+            "m.flags = (m.flags & !CARRY) | (m.a >> 7);".to_string(),
+            "m.a = m.a.rotate_left(1);".to_string(),
+        ]),
+        0x0f => lines.extend(vec![
+            "m.flags = (m.flags & !CARRY) | (m.a & 1);".to_string(),
+            "m.a = m.a.rotate_right(1);".to_string(),
+        ]),
+        0x17 => lines.extend(vec![
+            "let carry = m.flags & CARRY;".to_string(),
+            "m.flags = (m.flags & !CARRY) | (m.a >> 7);".to_string(),
+            "m.a = (m.a << 1) | carry;".to_string(),
+        ]),
+        0x1f => lines.extend(vec![
+            "let carry = m.flags & CARRY;".to_string(),
+            "m.flags = (m.flags & !CARRY) | (m.a & 1);".to_string(),
+            "m.a = (m.a >> 1) | (carry << 7);".to_string(),
+        ]),
+        0x22 => {
+            lines.push(format!("m.write({}, m.l)?;", target));
+            lines.push(format!("m.write(0x{:04x}, m.h)?;", operand.wrapping_add(1)));
+            cycles = 16;
+        },
+        0x2a => {
+            lines.push(format!("m.l = m.read({})?;", target));
+            lines.push(format!("m.h = m.read(0x{:04x})?;", operand.wrapping_add(1)));
+            cycles = 16;
+        },
+        0x27 => lines.push("m.daa();".to_string()),
+        0x2f => lines.push("m.a = !m.a;".to_string()),
+        0x32 => {
+            lines.push(format!("m.write({}, m.a)?;", target));
+            cycles = 13;
+        },
+        0x3a => {
+            lines.push(format!("m.a = m.read({})?;", target));
+            cycles = 13;
+        },
+        0x37 => lines.push("m.flags |= CARRY;".to_string()),
+        0x3f => lines.push("m.flags ^= CARRY;".to_string()),
+        0x40..=0x7f => {
+            cycles = 7;
+            match (opcode >> 3 & 7, opcode & 7) {
+                (6, _) => lines.push(format!("m.write(m.hl(), {})?;", source_register)),
+                (_, 6) => lines.push(format!("{} = m.read(m.hl())?;", destination)),
+                // MOV A,A and the like
+                (to, from) if to == from => cycles = 5,
+                _ => {
+                    lines.push(format!("{} = {};", destination, source_register));
+                    cycles = 5;
+                },
+            }
+        },
+        0x80..=0xbf => {
+            if opcode & 7 == 6 {
+                lines.push("let value = m.read(m.hl())?;".to_string());
+                cycles = 7;
+            } else {
+                lines.push(format!("let value = {};", source_register));
+            }
+            lines.extend(alu(opcode >> 3));
+        },
+        0xc1 | 0xd1 | 0xe1 => {
+            lines.push("let value = m.pop()?;".to_string());
+            lines.push(set_pair(opcode >> 4, "value"));
+            cycles = 10;
+        },
+        0xf1 => {
+            lines.push("let value = m.pop()?;".to_string());
+            lines.push("m.a = (value >> 8) as u8;".to_string());
+            lines.push("m.flags = value as u8 & (SIGN | ZERO | AUX_CARRY | PARITY | CARRY);".to_string());
+            cycles = 10;
+        },
+        0xc5 | 0xd5 | 0xe5 => {
+            lines.push(format!("m.push({})?;", pair(opcode >> 4)));
+            cycles = 11;
+        },
+        0xf5 => {
+            lines.push("m.push((m.a as u16) << 8 | m.flags as u16)?;".to_string());
+            cycles = 11;
+        },
+        0xd3 => {
+            lines.push(format!("m.output({}, m.a)?;", data));
+            cycles = 10;
+        },
+        0xdb => {
+            lines.push(format!("m.a = m.input({})?;", data));
+            cycles = 10;
+        },
+        0xe3 => {
+            lines.push("let value = m.pop()?;".to_string());
+            lines.push("let hl = m.hl();".to_string());
+            lines.push("m.set_hl(value);".to_string());
+            lines.push("m.push(hl)?;".to_string());
+            cycles = 18;
+        },
+        0xeb => {
+            lines.push("std::mem::swap(&mut m.d, &mut m.h);".to_string());
+            lines.push("std::mem::swap(&mut m.e, &mut m.l);".to_string());
+        },
+        0xf9 => {
+            lines.push("m.sp = m.hl();".to_string());
+            cycles = 5;
+        },
+        0xc3 => {
+            lines.push(format!("m.pc = {};", target));
+            cycles = 10;
+            tick = Some(target.clone());
+        },
+        0xe9 => {
+            lines.push("m.pc = m.hl();".to_string());
+            cycles = 5;
+            tick = Some("m.pc".to_string());
+        },
+        0xc9 => {
+            lines.push("let target = m.pop()?;".to_string());
+            lines.push("m.pc = target;".to_string());
+            lines.push("m.tick(10, target)?;".to_string());
+            lines.push("return Ok(());".to_string());
+            tick = None;
+        },
+        0xcd => {
+            lines.extend(call(&target, next, 17));
+            tick = None;
+        },
+        _ => match opcode & 0xc7 {
+            0x01 => {
+                lines.push(match opcode & 8 {
+                    0 => set_pair(opcode >> 4, &target),
+                    _ => format!("let result = m.hl() as u32 + {} as u32;", pair(opcode >> 4)),
+                });
+                if opcode & 8 != 0 {
+                    lines.push("m.flags = (m.flags & !CARRY) | (result >> 16) as u8;".to_string());
+                    lines.push("m.set_hl(result as u16);".to_string());
+                }
+                cycles = 10;
+            },
+            0x03 => {
+                let operation = if opcode & 8 == 0 { "wrapping_add" } else { "wrapping_sub" };
+                lines.push(set_pair(opcode >> 4, &format!("{}.{}(1)", pair(opcode >> 4), operation)));
+                cycles = 5;
+            },
+            0x04 | 0x05 => {
+                if opcode >> 3 & 7 == 6 {
+                    lines.push("let value = m.read(m.hl())?;".to_string());
+                    lines.extend(increment(opcode & 1 != 0));
+                    lines.push("m.write(m.hl(), value)?;".to_string());
+                    cycles = 10;
+                } else {
+                    lines.push(format!("let value = {};", destination));
+                    lines.extend(increment(opcode & 1 != 0));
+                    lines.push(format!("{} = value;", destination));
+                    cycles = 5;
+                }
+            },
+            0x06 => {
+                if opcode >> 3 & 7 == 6 {
+                    lines.push(format!("m.write(m.hl(), {})?;", data));
+                    cycles = 10;
+                } else {
+                    lines.push(format!("{} = {};", destination, data));
+                    cycles = 7;
+                }
+            },
+            0xc6 => {
+                lines.push(format!("let value: u8 = {};", data));
+                lines.extend(alu(opcode >> 3));
+                cycles = 7;
+            },
+            0xc0 => {
+                lines.push(format!("if {} {{", condition(opcode)));
+                lines.push("    let target = m.pop()?;".to_string());
+                lines.push("    m.pc = target;".to_string());
+                lines.push("    m.tick(11, target)?;".to_string());
+                lines.push("    return Ok(());".to_string());
+                lines.push("}".to_string());
+                cycles = 5;
+            },
+            0xc2 => {
+                lines.push(format!("m.pc = if {} {{ {} }} else {{ 0x{:04x} }};", condition(opcode), target, next));
+                cycles = 10;
+                tick = Some("m.pc".to_string());
+            },
+            0xc4 => {
+                lines.push(format!("if {} {{", condition(opcode)));
+                lines.extend(call(&target, next, 17).into_iter().map(|line| format!("    {}", line)));
+                lines.push("} else {".to_string());
+                lines.push(format!("    m.tick(11, 0x{:04x})?;", next));
+                lines.push("}".to_string());
+                tick = None;
+            },
+            0xc7 => {
+                lines.extend(call(&format!("0x{:04x}", opcode & 0x38), next, 11));
+                tick = None;
+            },
+            _ => unreachable!("{:02x} is not recompiled", opcode),
+        },
+    }
+
+    lines.insert(0, format!("// {:04x}  {}", address, disassemble(opcode, operand)));
+    if let Some(next) = tick {
+        lines.push(format!("m.tick({}, {})?;", cycles, next));
+    }
+    lines
+}
+
+// CALL, a taken Ccc or RST: the return address is pushed and the callee
+// runs natively until it comes back to it
+fn call(target: &str, return_address: u16, cycles: u64) -> Vec<String> {
+    vec![
+        format!("m.push(0x{:04x})?;", return_address),
+        format!("m.pc = {};", target),
+        format!("m.tick({}, {})?;", cycles, target),
+        format!("m.call(0x{:04x})?;", return_address),
+    ]
+}
@@ -1,50 +1,228 @@
-use crate::cpu::{RegisterPair, State8080};
+use std::panic::{self, AssertUnwindSafe};
+
+use crate::call_stack::Backtrace;
+use crate::cpu::{self, RegisterPair, State8080};
+use crate::error::{CpuError, MachineError, UnmappedPortPolicy};
+use crate::processor::{Cpu, Interrupt};
+use crate::symbols::SymbolTable;
 use minifb::Window;
 
-pub struct GameState {
-    cpu: State8080,
+// the four 2 KiB invaders.h/g/f/e ROMs at 0
+pub const ROM_SIZE: usize = 0x2000;
+// 1 KiB of work RAM followed by the 7 KiB frame buffer
+pub const VIDEO_RAM: usize = 0x2400;
+pub const RAM_END: usize = 0x4000;
+
+pub struct GameState<C: Cpu = State8080> {
+    cpu: C,
     io_state: SpaceInvaderIO,
     instr_count: u64,
     cycles: u64,
     frames: u64,
+    // cycles run since the last video interrupt and whether the beam is in
+    // the bottom half of the screen
+    half_cycles: u64,
+    bottom_half: bool,
     window_state: [u32; 224 * 256],
 }
 
+// an 8080 with the invaders ROM, wired like the board
+pub fn invaders_cpu() -> State8080 {
+    let mut cpu = State8080::load_from_rom(include_bytes!("invaders.rom"), 0, 0);
+    // A14 and A15 are not decoded, sprites drawn past the bottom of the
+    // screen in the attract mode end up as writes to the ROM
+    cpu.set_memory_mirroring(true);
+    cpu.protect_rom(ROM_SIZE);
+    cpu.call_stack_mut().set_symbols(SymbolTable::invaders());
+    cpu
+}
+
+// the machine at one instruction, the inputs included so running on from
+// it repeats what happened
+#[derive(Clone)]
+pub struct Snapshot {
+    cpu: cpu::Snapshot,
+    io_state: SpaceInvaderIO,
+    instr_count: u64,
+    cycles: u64,
+    frames: u64,
+    half_cycles: u64,
+    bottom_half: bool,
+}
+
+impl Snapshot {
+    // instructions the machine had run
+    pub fn instructions(&self) -> u64 {
+        self.instr_count
+    }
+}
+
 impl GameState {
+    pub fn new_game() -> Self {
+        Self::with_cpu(invaders_cpu())
+    }
+
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            cpu: self.cpu.snapshot(),
+            io_state: self.io_state.clone(),
+            instr_count: self.instr_count,
+            cycles: self.cycles,
+            frames: self.frames,
+            half_cycles: self.half_cycles,
+            bottom_half: self.bottom_half,
+        }
+    }
+
+    pub fn restore(&mut self, snapshot: &Snapshot) {
+        self.cpu.restore(&snapshot.cpu);
+        self.io_state = snapshot.io_state.clone();
+        self.instr_count = snapshot.instr_count;
+        self.cycles = snapshot.cycles;
+        self.frames = snapshot.frames;
+        self.half_cycles = snapshot.half_cycles;
+        self.bottom_half = snapshot.bottom_half;
+    }
+}
+
+impl<C: Cpu> GameState<C> {
     const SCREEN_WIDTH: u64 = 224;
     const SCREEN_HEIGHT: u64 = 256;
-    const CYCLES_PER_FRAME: u64 = 4_000_000 / 60;
+    pub const CYCLES_PER_FRAME: u64 = 4_000_000 / 60;
 
-    pub fn new_game() -> Self {
+    // any core with the invaders ROM loaded at 0
+    pub fn with_cpu(cpu: C) -> Self {
         Self {
-            cpu: State8080::load_from_rom(include_bytes!("invaders.rom"), 0, 0),
+            cpu,
             io_state: SpaceInvaderIO::new(),
             instr_count: 0,
             cycles: 0,
             frames: 0,
+            half_cycles: 0,
+            bottom_half: false,
             window_state: [0; 224 * 256],
         }
     }
 
-    pub fn next_frame(&mut self, window: &mut Window) {
-        self.handle_half_render(window, true);
-        self.handle_half_render(window, false);
+    pub fn set_unmapped_port_policy(&mut self, policy: UnmappedPortPolicy) {
+        self.io_state.unmapped_ports = policy;
+    }
+
+    pub fn cpu(&self) -> &C {
+        &self.cpu
+    }
+
+    pub fn cpu_mut(&mut self) -> &mut C {
+        &mut self.cpu
+    }
+
+    pub fn cycles(&self) -> u64 {
+        self.cycles
+    }
+
+    pub fn instructions(&self) -> u64 {
+        self.instr_count
+    }
+
+    pub fn frames(&self) -> u64 {
+        self.frames
+    }
+
+    pub fn backtrace(&self) -> Backtrace {
+        self.cpu.backtrace()
+    }
+
+    // the reset button: the core starts over at 0 with interrupts off, the
+    // RAM and the counters keep going
+    pub fn reset(&mut self) {
+        self.cpu.reset();
+    }
+
+    pub fn next_frame(&mut self, window: &mut Window) -> Result<(), CpuError> {
+        for _ in 0..2 {
+            let is_top = self.run_half()?;
+            self.render_half(is_top);
+            self.show(window);
+        }
 
-        self.frames += 1;
         self.handle_input(&window);
         std::thread::sleep(std::time::Duration::from_millis(16));
+        Ok(())
+    }
+
+    // a frame without a window, as fast as the core runs
+    pub fn run_frame(&mut self) -> Result<(), CpuError> {
+        while self.run_half()? {}
+        Ok(())
+    }
+
+    // one instruction, raising the video interrupts once their time has
+    // come, true when it completed a frame
+    pub fn step(&mut self) -> Result<bool, CpuError> {
+        // a faulting instruction still ran, counting it keeps the count a
+        // position in the program that can be returned to
+        let result = self.cpu.step(&mut self.io_state);
+        self.instr_count += 1;
+        let cycles = result?;
+        self.cycles += cycles;
+        self.half_cycles += cycles;
+
+        Ok(self.half_cycles >= Self::CYCLES_PER_FRAME / 2 && !self.end_half())
     }
 
-    fn handle_half_render(&mut self, window: &mut Window, is_top: bool) {
-        let mut cycles_spent = 0;
-        while cycles_spent < Self::CYCLES_PER_FRAME / 2 {
-            let cycles = self.cpu.emulate(&mut self.io_state);
-            
-            cycles_spent += cycles_spent;
-            self.instr_count += 1;
-            self.cycles += cycles;
+    // draws the whole screen as it is in memory, for callers that run the
+    // machine with `step`
+    pub fn present(&mut self, window: &mut Window) {
+        self.render_half(true);
+        self.render_half(false);
+        self.show(window);
+        self.handle_input(window);
+    }
+
+    // runs up to the middle or the end of the screen, true when it was the
+    // middle
+    fn run_half(&mut self) -> Result<bool, CpuError> {
+        let (cpu, io_state) = (&mut self.cpu, &mut self.io_state);
+        let budget = (Self::CYCLES_PER_FRAME / 2).saturating_sub(self.half_cycles);
+
+        // a panic inside the core is re-raised with the program's backtrace
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            cpu.run_for(budget, io_state)
+        }));
+
+        match result {
+            Ok(result) => {
+                let (cycles, instructions) = result?;
+                self.instr_count += instructions;
+                self.cycles += cycles;
+                self.half_cycles += cycles;
+            },
+            Err(payload) => {
+                let message = payload.downcast_ref::<&str>().map(|message| message.to_string())
+                    .or_else(|| payload.downcast_ref::<String>().cloned())
+                    .unwrap_or_default();
+                panic!("{}\nbacktrace:\n{}", message, self.cpu.backtrace());
+            },
         }
 
+        Ok(self.end_half())
+    }
+
+    // raises the interrupt the video hardware gives in the middle or at the
+    // end of the screen, true when it was the middle
+    fn end_half(&mut self) -> bool {
+        let is_top = !self.bottom_half;
+
+        self.cpu.interrupt(Interrupt::Rst(if is_top { 1 } else { 2 }));
+        self.half_cycles = 0;
+        self.bottom_half = is_top;
+        if !is_top {
+            self.frames += 1;
+        }
+        is_top
+    }
+
+    fn render_half(&mut self, is_top: bool) {
         let (mem_start, pix_start) = if is_top {
             (0x2400, 0)
         } else {
@@ -67,11 +245,11 @@ impl GameState {
                 self.window_state[(x + y * Self::SCREEN_WIDTH) as usize] = color;
             }
         }
+    }
 
+    fn show(&self, window: &mut Window) {
         window.update_with_buffer(&self.window_state, Self::SCREEN_WIDTH as usize, Self::SCREEN_HEIGHT as usize)
             .unwrap_or_else(|e| println!("Error while updating window: {}", e));
-
-        self.cpu.interrupt(if is_top { 1 } else { 2 });
     }
 
     fn handle_input(&mut self, window: &Window) {
@@ -81,29 +259,32 @@ impl GameState {
 
 
 pub trait IOState {
-    fn input(&self, port: u8) -> u8;
-    fn output(&mut self, port: u8, value: u8);
+    fn input(&self, port: u8) -> Result<u8, MachineError>;
+    fn output(&mut self, port: u8, value: u8) -> Result<(), MachineError>;
 }
 
+#[derive(Clone)]
 pub struct SpaceInvaderIO {
     port0: u8,
     port1: u8,
     port2: u8,
     shift_register: RegisterPair,
     shift_offset: u8,
+    unmapped_ports: UnmappedPortPolicy,
 }
 
 impl IOState for SpaceInvaderIO {
-    fn input(&self, port: u8) -> u8 {
+    fn input(&self, port: u8) -> Result<u8, MachineError> {
         match port {
-            1 => self.port1,
-            2 => self.port2,
-            3 => (self.shift_register.both() >> (8 - self.shift_offset)) as u8,
-            _ => panic!("port {} is not readable", port),
+            0 => Ok(self.port0),
+            1 => Ok(self.port1),
+            2 => Ok(self.port2),
+            3 => Ok((self.shift_register.both() >> (8 - self.shift_offset)) as u8),
+            _ => self.unmapped_ports.read(port),
         }
     }
 
-    fn output(&mut self, port: u8, value: u8) {
+    fn output(&mut self, port: u8, value: u8) -> Result<(), MachineError> {
         match port {
             2 => self.shift_offset = value & 0b111,
             4 => {
@@ -113,12 +294,17 @@ impl IOState for SpaceInvaderIO {
             3 | 5 | 6 => {
 
             },
-            _ => panic!("port {} is not writable", port)
+            _ => return self.unmapped_ports.write(port, value),
         }
+        Ok(())
     }
 } 
 
 impl SpaceInvaderIO {
+    pub const INPUT_PORTS: [u8; 4] = [0, 1, 2, 3];
+    // 3 and 5 drive the sounds, 6 is the watchdog
+    pub const OUTPUT_PORTS: [u8; 5] = [2, 3, 4, 5, 6];
+
     pub fn new() -> Self {
         Self {
             shift_register: RegisterPair::new(),
@@ -126,6 +312,7 @@ impl SpaceInvaderIO {
             port0: 0b0111_0000,
             port1: 0b0001_0000,
             port2: 0b0000_0000,
+            unmapped_ports: UnmappedPortPolicy::OpenBus,
         }
     }
 }
@@ -0,0 +1,282 @@
+use std::fmt;
+
+use crate::bus::{CycleKind, MachineCycle};
+use crate::cpu::State8080;
+use crate::processor::Interrupt;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Access {
+    Read { address: u16, value: u8 },
+    Write { address: u16, value: u8 },
+    Input { port: u8, value: u8 },
+    Output { port: u8, value: u8 },
+}
+
+impl Access {
+    // operand, data and stack accesses, the opcode fetch is not an access
+    fn from_machine_cycle(cycle: &MachineCycle) -> Option<Access> {
+        let address = cycle.address;
+        let value = cycle.data;
+
+        match cycle.kind {
+            CycleKind::MemoryRead | CycleKind::StackRead => Some(Access::Read { address, value }),
+            CycleKind::MemoryWrite | CycleKind::StackWrite => Some(Access::Write { address, value }),
+            CycleKind::InputRead => Some(Access::Input { port: address as u8, value }),
+            CycleKind::OutputWrite => Some(Access::Output { port: address as u8, value }),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct StepRecord {
+    pub address: u16,
+    pub opcode: u8,
+    pub accesses: Vec<Access>,
+    // set when the step acknowledged an interrupt instead of fetching
+    pub interrupt: Option<Interrupt>,
+    pub cycles: u64,
+}
+
+impl StepRecord {
+    // a halted core performs no machine cycles, it is reported as the HLT
+    pub fn from_machine_cycles(
+        cycles: &[MachineCycle],
+        interrupt: Option<Interrupt>,
+        pc: u16,
+    ) -> Self {
+        let (address, opcode) = match cycles.first() {
+            Some(cycle) if cycle.kind == CycleKind::Fetch || cycle.kind == CycleKind::InterruptAck => {
+                (cycle.address, cycle.data)
+            },
+            _ => (pc.wrapping_sub(1), 0x76),
+        };
+
+        StepRecord {
+            address,
+            opcode,
+            accesses: cycles.iter().filter_map(Access::from_machine_cycle).collect(),
+            interrupt,
+            cycles: cycles.iter().map(|cycle| cycle.t_states as u64).sum(),
+        }
+    }
+}
+
+// the accesses in bus order, after the interrupt the step acknowledged
+impl fmt::Display for StepRecord {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.interrupt.is_some() {
+            write!(f, "interrupt {:02x} acknowledged", self.opcode)?;
+        }
+        for (index, access) in self.accesses.iter().enumerate() {
+            if index > 0 || self.interrupt.is_some() {
+                write!(f, "  ")?;
+            }
+            match *access {
+                Access::Read { address, value } => write!(f, "read {:04x}={:02x}", address, value)?,
+                Access::Write { address, value } => write!(f, "write {:04x}={:02x}", address, value)?,
+                Access::Input { port, value } => write!(f, "in {:02x}={:02x}", port, value)?,
+                Access::Output { port, value } => write!(f, "out {:02x}={:02x}", port, value)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+pub enum RunUntil<'a> {
+    // stop once at least this many cycles have run
+    Cycles(u64),
+    // stop before executing the instruction at the address
+    Pc(u16),
+    // stop after the first instruction the predicate holds for
+    Predicate(Box<dyn FnMut(&State8080) -> bool + 'a>),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum StopReason {
+    BudgetExhausted,
+    Breakpoint(u16),
+    Halted,
+    IllegalOpcode { address: u16, opcode: u8 },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assembler;
+    use crate::bench::Console;
+    use crate::block_cache::BlockCache;
+    use crate::processor::Cpu;
+
+    // the program at 0, run up to where the instruction under test starts
+    fn core(source: &str, skip: usize) -> (State8080, Console) {
+        let assembly = assembler::assemble(source).unwrap();
+        let mut cpu = State8080::new();
+        cpu.load_image(&assembly.image).unwrap();
+        let mut console = Console { output: String::new() };
+        for _ in 0..skip {
+            cpu.emulate(&mut console).unwrap();
+        }
+        (cpu, console)
+    }
+
+    fn step(cpu: &mut State8080, console: &mut Console) -> StepRecord {
+        cpu.emulate(console).unwrap();
+        cpu.last_step()
+    }
+
+    // kind, address, data and T-states of every machine cycle
+    fn bus(cpu: &State8080) -> Vec<(CycleKind, u16, u8, u8)> {
+        cpu.machine_cycles().iter().map(|cycle| (cycle.kind, cycle.address, cycle.data, cycle.t_states)).collect()
+    }
+
+    const MOVES: &str = "
+        lxi h,2000h
+        mvi a,42h
+        mov m,a
+        mov b,m
+        mov c,b
+    ";
+
+    #[test]
+    fn mov_to_memory_writes_after_the_fetch() {
+        let (mut cpu, mut console) = core(MOVES, 2);
+        let record = step(&mut cpu, &mut console);
+
+        assert_eq!(bus(&cpu), vec![(CycleKind::Fetch, 5, 0x77, 4), (CycleKind::MemoryWrite, 0x2000, 0x42, 3)]);
+        assert_eq!(record, StepRecord {
+            address: 5,
+            opcode: 0x77,
+            accesses: vec![Access::Write { address: 0x2000, value: 0x42 }],
+            interrupt: None,
+            cycles: 7,
+        });
+    }
+
+    #[test]
+    fn mov_from_memory_reads_after_the_fetch() {
+        let (mut cpu, mut console) = core(MOVES, 3);
+        let record = step(&mut cpu, &mut console);
+
+        assert_eq!(bus(&cpu), vec![(CycleKind::Fetch, 6, 0x46, 4), (CycleKind::MemoryRead, 0x2000, 0x42, 3)]);
+        assert_eq!(record.accesses, vec![Access::Read { address: 0x2000, value: 0x42 }]);
+        assert_eq!(record.cycles, 7);
+    }
+
+    #[test]
+    fn mov_between_registers_only_fetches() {
+        let (mut cpu, mut console) = core(MOVES, 4);
+        let record = step(&mut cpu, &mut console);
+
+        assert_eq!(bus(&cpu), vec![(CycleKind::Fetch, 7, 0x48, 5)]);
+        assert!(record.accesses.is_empty());
+        assert_eq!(record.cycles, 5);
+    }
+
+    // Z is set by the XRA, CNZ falls through and CZ calls
+    const CALLS: &str = "
+        lxi sp,3000h
+        xra a
+        cnz sub
+        cz sub
+        hlt
+sub:    ret
+    ";
+
+    #[test]
+    fn conditional_call_not_taken_reads_the_address_and_goes_on() {
+        let (mut cpu, mut console) = core(CALLS, 2);
+        let record = step(&mut cpu, &mut console);
+
+        assert_eq!(bus(&cpu), vec![
+            (CycleKind::Fetch, 4, 0xc4, 5),
+            (CycleKind::MemoryRead, 5, 0x0b, 3),
+            (CycleKind::MemoryRead, 6, 0x00, 3),
+        ]);
+        assert_eq!(record.accesses, vec![
+            Access::Read { address: 5, value: 0x0b },
+            Access::Read { address: 6, value: 0x00 },
+        ]);
+        assert_eq!(record.cycles, 11);
+        assert_eq!(cpu.pc(), 7);
+    }
+
+    #[test]
+    fn conditional_call_taken_pushes_the_return_address_high_byte_first() {
+        let (mut cpu, mut console) = core(CALLS, 3);
+        let record = step(&mut cpu, &mut console);
+
+        assert_eq!(bus(&cpu), vec![
+            (CycleKind::Fetch, 7, 0xcc, 5),
+            (CycleKind::MemoryRead, 8, 0x0b, 3),
+            (CycleKind::MemoryRead, 9, 0x00, 3),
+            (CycleKind::StackWrite, 0x2fff, 0x00, 3),
+            (CycleKind::StackWrite, 0x2ffe, 0x0a, 3),
+        ]);
+        assert_eq!(record.accesses[2..], [
+            Access::Write { address: 0x2fff, value: 0x00 },
+            Access::Write { address: 0x2ffe, value: 0x0a },
+        ]);
+        assert_eq!(record.cycles, 17);
+        assert_eq!(cpu.pc(), 0x0b);
+    }
+
+    #[test]
+    fn interrupt_acknowledge_takes_the_rst_from_the_bus_after_the_ei_delay() {
+        let (mut cpu, mut console) = core("lxi sp,3000h\nei\nnop\nnop\n", 2);
+        cpu.interrupt(Interrupt::Rst(1));
+
+        // the instruction after EI still runs
+        let record = step(&mut cpu, &mut console);
+        assert_eq!((record.address, record.opcode, record.interrupt), (4, 0x00, None));
+
+        let record = step(&mut cpu, &mut console);
+        assert_eq!(bus(&cpu), vec![
+            (CycleKind::InterruptAck, 5, 0xcf, 5),
+            (CycleKind::StackWrite, 0x2fff, 0x00, 3),
+            (CycleKind::StackWrite, 0x2ffe, 0x05, 3),
+        ]);
+        assert_eq!(record, StepRecord {
+            address: 5,
+            opcode: 0xcf,
+            accesses: vec![
+                Access::Write { address: 0x2fff, value: 0x00 },
+                Access::Write { address: 0x2ffe, value: 0x05 },
+            ],
+            interrupt: Some(Interrupt::Rst(1)),
+            cycles: 11,
+        });
+        assert_eq!(cpu.pc(), 8);
+        assert_eq!(record.to_string(), "interrupt cf acknowledged  write 2fff=00  write 2ffe=05");
+    }
+
+    #[test]
+    fn run_stops_at_the_pc_then_at_the_halt() {
+        let (mut cpu, mut console) = core(CALLS, 0);
+
+        assert_eq!(cpu.run(RunUntil::Pc(0x0b), &mut console).unwrap(), (StopReason::Breakpoint(0x0b), 10 + 4 + 11 + 17, 4));
+        assert_eq!(cpu.run(RunUntil::Cycles(1000), &mut console).unwrap(), (StopReason::Halted, 10 + 7, 2));
+    }
+
+    #[test]
+    fn run_stops_at_the_same_places_through_cached_blocks() {
+        let (mut cpu, mut console) = core(CALLS, 0);
+        cpu.set_block_cache(BlockCache::new());
+
+        assert_eq!(cpu.run(RunUntil::Pc(0x0b), &mut console).unwrap(), (StopReason::Breakpoint(0x0b), 10 + 4 + 11 + 17, 4));
+        assert_eq!(cpu.run(RunUntil::Cycles(1000), &mut console).unwrap(), (StopReason::Halted, 10 + 7, 2));
+    }
+
+    #[test]
+    fn run_asks_the_predicate_after_every_instruction() {
+        let (mut cpu, mut console) = core(MOVES, 0);
+        let mut pcs = Vec::new();
+
+        let until = RunUntil::Predicate(Box::new(|cpu: &State8080| {
+            pcs.push(cpu.pc());
+            cpu.pc() == 7
+        }));
+        assert_eq!(cpu.run(until, &mut console).unwrap(), (StopReason::Breakpoint(7), 10 + 7 + 7 + 7, 4));
+        assert_eq!(pcs, vec![3, 5, 6, 7]);
+    }
+}
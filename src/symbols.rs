@@ -0,0 +1,274 @@
+use std::collections::{BTreeMap, HashMap};
+use std::convert::TryFrom;
+use std::fs;
+use std::path::Path;
+
+use crate::assembler;
+
+// addresses farther from the label before them are shown on their own
+const MAX_OFFSET: u16 = 0x100;
+
+// names and comments for addresses, from the files assemblers and other
+// emulators write
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SymbolTable {
+    // the first name given to each address
+    labels: BTreeMap<u16, String>,
+    addresses: HashMap<String, u16>,
+    comments: BTreeMap<u16, String>,
+}
+
+impl SymbolTable {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // the routines and variables of the Space Invaders ROM
+    pub fn invaders() -> Self {
+        Self::parse(include_str!("invaders.sym")).expect("the invaders symbol map parses")
+    }
+
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let text = fs::read_to_string(path).map_err(|error| format!("{}: {}", path.display(), error))?;
+        Self::parse(&text).map_err(|error| format!("{}: {}", path.display(), error))
+    }
+
+    // a file of lines in any of these, `;` and `#` start comments:
+    //
+    // 18d4 Init                   the .sym files `asm` writes
+    // Init = 18d4h ; cold start   a name for an address, with a comment
+    // comadd 18d4,cold start      MAME debugger commands
+    //
+    // or a MAME comment file, <mamecommentfile> with a <comment address="">
+    // in decimal for each comment
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut table = Self::new();
+        if text.contains("<mamecommentfile") {
+            table.parse_mame_comments(text)?;
+            return Ok(table);
+        }
+
+        for (index, line) in text.lines().enumerate() {
+            let error = |message: String| format!("line {}: {}", index + 1, message);
+
+            if let Some(rest) = line.trim_start().strip_prefix("comadd ") {
+                let (address, text) = rest.split_once(',').ok_or_else(|| error("comadd needs an address and a comment".to_string()))?;
+                let address = parse_address(address.trim()).ok_or_else(|| error(format!("{} is not an address", address.trim())))?;
+                table.add_comment(address, text.trim());
+                continue;
+            }
+
+            let (code, comment) = match line.find([';', '#']) {
+                Some(start) => (&line[..start], line[start + 1..].trim()),
+                None => (line, ""),
+            };
+            let code = code.replace('=', " = ");
+            let words = code.split_whitespace().collect::<Vec<_>>();
+
+            let (name, address) = match words.as_slice() {
+                [] => continue,
+                [address, name] => (*name, *address),
+                [name, "=", address] => (*name, *address),
+                [name, equ, address] if equ.eq_ignore_ascii_case("equ") => (*name, *address),
+                _ => return Err(error(format!("can not read {}", line.trim()))),
+            };
+            // the names the assembler takes, so disassembled source using
+            // them assembles back
+            if !assembler::is_name(name) || assembler::is_reserved(name) {
+                return Err(error(format!("{} can not be a name", name)));
+            }
+            let address = parse_address(address).ok_or_else(|| error(format!("{} is not an address", address)))?;
+
+            table.insert(name, address);
+            if !comment.is_empty() {
+                table.add_comment(address, comment);
+            }
+        }
+        Ok(table)
+    }
+
+    // <comment address="6356" color="16711680" crc="...">text</comment>
+    fn parse_mame_comments(&mut self, text: &str) -> Result<(), String> {
+        let mut rest = text;
+        while let Some(start) = rest.find("<comment ") {
+            let tag_end = rest[start..].find('>').ok_or("unterminated <comment>")? + start;
+            let text_end = rest[tag_end..].find("</comment>").ok_or("<comment> without </comment>")? + tag_end;
+
+            let tag = &rest[start..tag_end];
+            let address = attribute(tag, "address")
+                .and_then(|address| address.parse::<u32>().ok())
+                .and_then(|address| u16::try_from(address).ok())
+                .ok_or_else(|| format!("{}> has no address", tag))?;
+            self.add_comment(address, &unescape(&rest[tag_end + 1..text_end]));
+
+            rest = &rest[text_end..];
+        }
+        Ok(())
+    }
+
+    // a name for an address, the first one stays the one shown
+    pub fn insert(&mut self, name: &str, address: u16) {
+        self.labels.entry(address).or_insert_with(|| name.to_string());
+        self.addresses.insert(name.to_string(), address);
+    }
+
+    pub fn add_comment(&mut self, address: u16, comment: &str) {
+        let text = self.comments.entry(address).or_default();
+        if !text.is_empty() {
+            text.push_str("; ");
+        }
+        text.push_str(comment);
+    }
+
+    // the names and comments of `other` next to these, its names are the
+    // ones shown where both name an address
+    pub fn merge(&mut self, other: &SymbolTable) {
+        self.labels.extend(other.labels.iter().map(|(&address, name)| (address, name.clone())));
+        self.addresses.extend(other.addresses.iter().map(|(name, &address)| (name.clone(), address)));
+        for (&address, comment) in &other.comments {
+            self.add_comment(address, comment);
+        }
+    }
+
+    // the name of exactly this address
+    pub fn name(&self, address: u16) -> Option<&str> {
+        self.labels.get(&address).map(String::as_str)
+    }
+
+    pub fn address(&self, name: &str) -> Option<u16> {
+        self.addresses.get(name).copied()
+    }
+
+    pub fn comment(&self, address: u16) -> Option<&str> {
+        self.comments.get(&address).map(String::as_str)
+    }
+
+    // every address with a name, in order
+    pub fn labels(&self) -> impl Iterator<Item = (u16, &str)> {
+        self.labels.iter().map(|(&address, name)| (address, name.as_str()))
+    }
+
+    pub fn comments(&self) -> impl Iterator<Item = (u16, &str)> {
+        self.comments.iter().map(|(&address, comment)| (address, comment.as_str()))
+    }
+
+    // the label at or before the address and how far after it the address
+    // is, e.g. BlockCopy+1; the offset is decimal so the text is an
+    // expression the assembler reads
+    pub fn label(&self, address: u16) -> Option<String> {
+        let (&start, name) = self.labels.range(..=address).next_back()?;
+        match address - start {
+            0 => Some(name.clone()),
+            offset if offset < MAX_OFFSET => Some(format!("{}+{}", name, offset)),
+            _ => None,
+        }
+    }
+
+    // the address in hex followed by its label when it has one
+    pub fn describe(&self, address: u16) -> String {
+        match self.label(address) {
+            Some(label) => format!("{:04x} {}", address, label),
+            None => format!("{:04x}", address),
+        }
+    }
+}
+
+// hex with an optional 0x or $ in front or h after
+fn parse_address(text: &str) -> Option<u16> {
+    let lower = text.to_ascii_lowercase();
+    let digits = lower.strip_prefix("0x")
+        .or_else(|| lower.strip_prefix('$'))
+        .or_else(|| lower.strip_suffix('h'))
+        .unwrap_or(&lower);
+    u16::from_str_radix(digits, 16).ok()
+}
+
+fn attribute<'a>(tag: &'a str, name: &str) -> Option<&'a str> {
+    let start = tag.find(&format!("{}=\"", name))? + name.len() + 2;
+    let end = tag[start..].find('"')? + start;
+    Some(&tag[start..end])
+}
+
+fn unescape(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sym_files_as_asm_writes_them() {
+        let table = SymbolTable::parse("0000 Reset\n18d4 Init\n18d4 ColdStart\n").unwrap();
+        assert_eq!(table.labels().collect::<Vec<_>>(), [(0x0000, "Reset"), (0x18d4, "Init")]);
+        assert_eq!(table.address("ColdStart"), Some(0x18d4));
+    }
+
+    #[test]
+    fn names_for_addresses_with_comments() {
+        let table = SymbolTable::parse("\
+# variables
+Score = 20f8h ; player one
+HiScore equ $20f4
+Credits EQU 0x20eb
+").unwrap();
+        assert_eq!(table.address("Score"), Some(0x20f8));
+        assert_eq!(table.address("HiScore"), Some(0x20f4));
+        assert_eq!(table.address("Credits"), Some(0x20eb));
+        assert_eq!(table.comment(0x20f8), Some("player one"));
+
+        assert_eq!(SymbolTable::parse("psw = 1234h").err(), Some("line 1: psw can not be a name".to_string()));
+        assert_eq!(SymbolTable::parse("Score = 20g8").err(), Some("line 1: 20g8 is not an address".to_string()));
+    }
+
+    #[test]
+    fn mame_comadd_commands() {
+        let table = SymbolTable::parse("comadd 18d4,cold start\ncomadd $18d7, again\n18d4 Init\n").unwrap();
+        assert_eq!(table.comment(0x18d4), Some("cold start"));
+        assert_eq!(table.comment(0x18d7), Some("again"));
+        assert_eq!(table.name(0x18d4), Some("Init"));
+    }
+
+    #[test]
+    fn mame_comment_files() {
+        let table = SymbolTable::parse(r#"<?xml version="1.0"?>
+<mamecommentfile version="1">
+    <system name="invaders">
+        <cpu tag=":maincpu">
+            <comment address="6356" color="16711680" crc="1234">cold &amp; &lt;start&gt;</comment>
+            <comment address="6356" color="16711680" crc="1234">again</comment>
+        </cpu>
+    </system>
+</mamecommentfile>
+"#).unwrap();
+        assert_eq!(table.comments().collect::<Vec<_>>(), [(0x18d4, "cold & <start>; again")]);
+        assert_eq!(table.labels().count(), 0);
+    }
+
+    #[test]
+    fn labels_reach_up_to_max_offset_after_them() {
+        let table = SymbolTable::parse("1000 Start\n").unwrap();
+        assert_eq!(table.label(0x1000), Some("Start".to_string()));
+        assert_eq!(table.label(0x1000 + MAX_OFFSET - 1), Some("Start+255".to_string()));
+        assert_eq!(table.label(0x1000 + MAX_OFFSET), None);
+        assert_eq!(table.label(0x0fff), None);
+        assert_eq!(table.describe(0x1001), "1001 Start+1");
+    }
+
+    #[test]
+    fn merged_names_win_and_comments_add_up() {
+        let mut table = SymbolTable::parse("18d4 Init ; first\n0000 Reset\n").unwrap();
+        let other = SymbolTable::parse("18d4 ColdStart ; second\n0100 Other\n").unwrap();
+        table.merge(&other);
+
+        assert_eq!(table.name(0x18d4), Some("ColdStart"));
+        assert_eq!(table.name(0x0000), Some("Reset"));
+        assert_eq!(table.name(0x0100), Some("Other"));
+        assert_eq!(table.address("Init"), Some(0x18d4));
+        assert_eq!(table.comment(0x18d4), Some("first; second"));
+    }
+}
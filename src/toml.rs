@@ -0,0 +1,459 @@
+use std::collections::HashSet;
+
+// just enough TOML for machine descriptions: tables, arrays of tables,
+// dotted keys, strings, integers, booleans, arrays and inline tables
+#[derive(Clone, Debug, PartialEq)]
+pub enum Toml {
+    String(String),
+    Integer(i64),
+    Boolean(bool),
+    Array(Vec<Toml>),
+    // keys in the order they were written
+    Table(Vec<(String, Toml)>),
+}
+
+impl Toml {
+    pub fn parse(text: &str) -> Result<Toml, String> {
+        let mut parser = Parser { text: text.as_bytes(), at: 0, statement: 0 };
+        let mut root = Vec::new();
+        parser.document(&mut root).map_err(|error| format!("line {}: {}", parser.line(), error))?;
+        Ok(Toml::Table(root))
+    }
+
+    // member of a table, none for anything else
+    pub fn get(&self, key: &str) -> Option<&Toml> {
+        self.as_table().and_then(|members| members.iter().find(|(name, _)| name == key).map(|(_, value)| value))
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Toml::String(string) => Some(string),
+            _ => None,
+        }
+    }
+
+    pub fn as_integer(&self) -> Option<i64> {
+        match *self {
+            Toml::Integer(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match *self {
+            Toml::Boolean(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn as_array(&self) -> Option<&[Toml]> {
+        match self {
+            Toml::Array(values) => Some(values),
+            _ => None,
+        }
+    }
+
+    pub fn as_table(&self) -> Option<&[(String, Toml)]> {
+        match self {
+            Toml::Table(members) => Some(members),
+            _ => None,
+        }
+    }
+}
+
+struct Parser<'a> {
+    text: &'a [u8],
+    at: usize,
+    // where the header or key that is being read starts, for errors
+    statement: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn document(&mut self, root: &mut Vec<(String, Toml)>) -> Result<(), String> {
+        // the table keys go into and the tables a [header] defined
+        let mut current = Vec::new();
+        let mut defined = HashSet::new();
+
+        loop {
+            self.blank();
+            self.statement = self.at;
+            match self.peek() {
+                None => return Ok(()),
+                Some(b'[') => {
+                    self.at += 1;
+                    let array = self.eat(b'[');
+                    self.spaces();
+                    let path = self.path()?;
+                    self.expect(b']')?;
+                    if array {
+                        self.expect(b']')?;
+                    }
+                    self.end_of_line()?;
+
+                    let (name, parent) = path.split_last().unwrap();
+                    let members = table(root, parent)?;
+                    if array {
+                        match members.iter_mut().find(|(key, _)| key == name) {
+                            Some((_, Toml::Array(values))) => values.push(Toml::Table(Vec::new())),
+                            Some(_) => return Err(format!("{} is not an array of tables", name)),
+                            None => members.push((name.clone(), Toml::Array(vec![Toml::Table(Vec::new())]))),
+                        }
+                    } else if !defined.insert(path.join(".")) {
+                        return Err(format!("[{}] is defined twice", path.join(".")));
+                    } else {
+                        table(members, &path[path.len() - 1..])?;
+                    }
+                    current = path;
+                },
+                Some(_) => {
+                    let path = self.path()?;
+                    self.expect(b'=')?;
+                    self.spaces();
+                    let value = self.value()?;
+                    self.end_of_line()?;
+
+                    let (name, parent) = path.split_last().unwrap();
+                    insert(table(table(root, &current)?, parent)?, name, value)?;
+                },
+            }
+        }
+    }
+
+    // keys separated by dots, followed by spaces
+    fn path(&mut self) -> Result<Vec<String>, String> {
+        let mut path = vec![self.key()?];
+        self.spaces();
+        while self.eat(b'.') {
+            self.spaces();
+            path.push(self.key()?);
+            self.spaces();
+        }
+        Ok(path)
+    }
+
+    fn key(&mut self) -> Result<String, String> {
+        match self.peek() {
+            Some(b'"') => self.basic_string(),
+            Some(b'\'') => self.literal_string(),
+            _ => {
+                let start = self.at;
+                while let Some(b'A'..=b'Z') | Some(b'a'..=b'z') | Some(b'0'..=b'9') | Some(b'_') | Some(b'-') = self.peek() {
+                    self.at += 1;
+                }
+                if self.at == start {
+                    return Err("expected a key".to_string());
+                }
+                Ok(String::from_utf8_lossy(&self.text[start..self.at]).into_owned())
+            },
+        }
+    }
+
+    fn value(&mut self) -> Result<Toml, String> {
+        match self.peek() {
+            Some(b'"') => self.basic_string().map(Toml::String),
+            Some(b'\'') => self.literal_string().map(Toml::String),
+            Some(b'[') => self.array(),
+            Some(b'{') => self.inline_table(),
+            Some(_) => self.scalar(),
+            None => Err("expected a value".to_string()),
+        }
+    }
+
+    fn array(&mut self) -> Result<Toml, String> {
+        self.at += 1;
+        let mut values = Vec::new();
+        loop {
+            self.blank();
+            if self.eat(b']') {
+                return Ok(Toml::Array(values));
+            }
+            values.push(self.value()?);
+            self.blank();
+            if !self.eat(b',') {
+                self.blank();
+                self.expect(b']')?;
+                return Ok(Toml::Array(values));
+            }
+        }
+    }
+
+    fn inline_table(&mut self) -> Result<Toml, String> {
+        self.at += 1;
+        let mut members = Vec::new();
+        self.spaces();
+        if self.eat(b'}') {
+            return Ok(Toml::Table(members));
+        }
+        loop {
+            let path = self.path()?;
+            self.expect(b'=')?;
+            self.spaces();
+            let value = self.value()?;
+            self.spaces();
+
+            let (name, parent) = path.split_last().unwrap();
+            insert(table(&mut members, parent)?, name, value)?;
+            if self.eat(b'}') {
+                return Ok(Toml::Table(members));
+            }
+            self.expect(b',')?;
+            self.spaces();
+        }
+    }
+
+    fn basic_string(&mut self) -> Result<String, String> {
+        self.at += 1;
+        let mut bytes = Vec::new();
+        loop {
+            match self.next() {
+                Some(b'"') => break,
+                Some(b'\\') => match self.next() {
+                    Some(b'n') => bytes.push(b'\n'),
+                    Some(b't') => bytes.push(b'\t'),
+                    Some(b'r') => bytes.push(b'\r'),
+                    Some(b'"') => bytes.push(b'"'),
+                    Some(b'\\') => bytes.push(b'\\'),
+                    Some(b'u') => {
+                        let digits = self.text.get(self.at..self.at + 4).ok_or("short \\u escape")?;
+                        let character = std::str::from_utf8(digits).ok()
+                            .and_then(|digits| u32::from_str_radix(digits, 16).ok())
+                            .and_then(std::char::from_u32)
+                            .ok_or("bad \\u escape")?;
+                        self.at += 4;
+                        bytes.extend_from_slice(character.to_string().as_bytes());
+                    },
+                    _ => return Err("unknown escape in a string".to_string()),
+                },
+                Some(b'\n') | None => return Err("unterminated string".to_string()),
+                Some(byte) => bytes.push(byte),
+            }
+        }
+        String::from_utf8(bytes).map_err(|_| "a string is not UTF-8".to_string())
+    }
+
+    fn literal_string(&mut self) -> Result<String, String> {
+        self.at += 1;
+        let start = self.at;
+        loop {
+            match self.next() {
+                Some(b'\'') => break,
+                Some(b'\n') | None => return Err("unterminated string".to_string()),
+                Some(_) => (),
+            }
+        }
+        String::from_utf8(self.text[start..self.at - 1].to_vec()).map_err(|_| "a string is not UTF-8".to_string())
+    }
+
+    // booleans and integers in decimal, 0x hex, 0o octal or 0b binary with
+    // _ between digits; floats and dates are not needed
+    fn scalar(&mut self) -> Result<Toml, String> {
+        let start = self.at;
+        while let Some(b'A'..=b'Z') | Some(b'a'..=b'z') | Some(b'0'..=b'9') | Some(b'_') | Some(b'+') | Some(b'-') = self.peek() {
+            self.at += 1;
+        }
+        let word = String::from_utf8_lossy(&self.text[start..self.at]).into_owned();
+        match word.as_str() {
+            "true" => return Ok(Toml::Boolean(true)),
+            "false" => return Ok(Toml::Boolean(false)),
+            _ => (),
+        }
+
+        let (negative, digits) = match word.as_bytes().first() {
+            Some(b'-') => (true, &word[1..]),
+            Some(b'+') => (false, &word[1..]),
+            _ => (false, &word[..]),
+        };
+        let (radix, digits) = match digits.get(..2) {
+            Some("0x") => (16, &digits[2..]),
+            Some("0o") => (8, &digits[2..]),
+            Some("0b") => (2, &digits[2..]),
+            _ => (10, digits),
+        };
+        if digits.is_empty() || digits.starts_with('_') || digits.ends_with('_') || digits.contains("__") {
+            return Err(format!("{} is not a value", word));
+        }
+        let value = i64::from_str_radix(&digits.replace('_', ""), radix).map_err(|_| format!("{} is not a value", word))?;
+        Ok(Toml::Integer(if negative { -value } else { value }))
+    }
+
+    // spaces and a comment up to the end of the line
+    fn end_of_line(&mut self) -> Result<(), String> {
+        self.spaces();
+        if self.peek() == Some(b'#') {
+            while !matches!(self.peek(), Some(b'\n') | None) {
+                self.at += 1;
+            }
+        }
+        match self.next() {
+            Some(b'\n') | None => Ok(()),
+            Some(b'\r') if self.eat(b'\n') => Ok(()),
+            Some(_) => Err("expected the end of the line".to_string()),
+        }
+    }
+
+    fn spaces(&mut self) {
+        while let Some(b' ') | Some(b'\t') = self.peek() {
+            self.at += 1;
+        }
+    }
+
+    // whitespace, line ends and comments
+    fn blank(&mut self) {
+        loop {
+            match self.peek() {
+                Some(b' ') | Some(b'\t') | Some(b'\r') | Some(b'\n') => self.at += 1,
+                Some(b'#') => {
+                    while !matches!(self.peek(), Some(b'\n') | None) {
+                        self.at += 1;
+                    }
+                },
+                _ => return,
+            }
+        }
+    }
+
+    fn expect(&mut self, byte: u8) -> Result<(), String> {
+        if self.eat(byte) {
+            Ok(())
+        } else {
+            Err(format!("expected '{}'", byte as char))
+        }
+    }
+
+    fn eat(&mut self, byte: u8) -> bool {
+        let matched = self.peek() == Some(byte);
+        if matched {
+            self.at += 1;
+        }
+        matched
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.text.get(self.at).copied()
+    }
+
+    fn next(&mut self) -> Option<u8> {
+        let byte = self.peek();
+        if byte.is_some() {
+            self.at += 1;
+        }
+        byte
+    }
+
+    fn line(&self) -> usize {
+        1 + self.text[..self.statement].iter().filter(|&&byte| byte == b'\n').count()
+    }
+}
+
+// the table at a path of keys below `members`, made where it is missing; a
+// path through an array of tables goes into its last table
+fn table<'a>(members: &'a mut Vec<(String, Toml)>, path: &[String]) -> Result<&'a mut Vec<(String, Toml)>, String> {
+    let (name, rest) = match path.split_first() {
+        Some(first) => first,
+        None => return Ok(members),
+    };
+    let index = match members.iter().position(|(key, _)| key == name) {
+        Some(index) => index,
+        None => {
+            members.push((name.clone(), Toml::Table(Vec::new())));
+            members.len() - 1
+        },
+    };
+    let next = match &mut members[index].1 {
+        Toml::Table(members) => members,
+        Toml::Array(values) => match values.last_mut() {
+            Some(Toml::Table(members)) => members,
+            _ => return Err(format!("{} is not a table", name)),
+        },
+        _ => return Err(format!("{} is not a table", name)),
+    };
+    table(next, rest)
+}
+
+fn insert(members: &mut Vec<(String, Toml)>, name: &str, value: Toml) -> Result<(), String> {
+    if members.iter().any(|(key, _)| key == name) {
+        return Err(format!("{} is set twice", name));
+    }
+    members.push((name.to_string(), value));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn toml_table(members: &[(&str, Toml)]) -> Toml {
+        Toml::Table(members.iter().map(|(key, value)| (key.to_string(), value.clone())).collect())
+    }
+
+    #[test]
+    fn dotted_keys_make_tables() {
+        let toml = Toml::parse("\
+cpu.type = \"8080\"
+cpu.clock = 2_000_000
+[video]
+size.width = 256 # wide
+size.\"height\" = 224
+inline = { a.b = true, c = 'literal' }
+").unwrap();
+        assert_eq!(toml, toml_table(&[
+            ("cpu", toml_table(&[("type", Toml::String("8080".to_string())), ("clock", Toml::Integer(2_000_000))])),
+            ("video", toml_table(&[
+                ("size", toml_table(&[("width", Toml::Integer(256)), ("height", Toml::Integer(224))])),
+                ("inline", toml_table(&[("a", toml_table(&[("b", Toml::Boolean(true))])), ("c", Toml::String("literal".to_string()))])),
+            ])),
+        ]));
+    }
+
+    #[test]
+    fn arrays_of_tables_add_a_table_each() {
+        let toml = Toml::parse("\
+[[rom]]
+file = \"a.bin\"
+[[rom]]
+file = \"b.bin\"
+[rom.check]
+crc32 = 0
+").unwrap();
+        let roms = toml.get("rom").and_then(Toml::as_array).unwrap();
+        assert_eq!(roms, [
+            toml_table(&[("file", Toml::String("a.bin".to_string()))]),
+            toml_table(&[("file", Toml::String("b.bin".to_string())), ("check", toml_table(&[("crc32", Toml::Integer(0))]))]),
+        ]);
+
+        assert_eq!(Toml::parse("rom = 1\n[[rom]]\n").err(), Some("line 2: rom is not an array of tables".to_string()));
+    }
+
+    #[test]
+    fn integers_in_every_radix_with_underscores() {
+        let toml = Toml::parse("hex = 0xb64c_a815\nbinary = 0b0111_0000\noctal = 0o17\nnegative = -1_000\nplus = +5\n").unwrap();
+        let integers = toml.as_table().unwrap().iter().map(|(_, value)| value.as_integer().unwrap()).collect::<Vec<_>>();
+        assert_eq!(integers, [0xb64c_a815, 0b0111_0000, 0o17, -1000, 5]);
+
+        assert_eq!(Toml::parse("a = 1__0").err(), Some("line 1: 1__0 is not a value".to_string()));
+        assert_eq!(Toml::parse("a = 0x_10").err(), Some("line 1: 0x_10 is not a value".to_string()));
+        assert_eq!(Toml::parse("a = 10_").err(), Some("line 1: 10_ is not a value".to_string()));
+        assert_eq!(Toml::parse("a = 0b102").err(), Some("line 1: 0b102 is not a value".to_string()));
+    }
+
+    #[test]
+    fn tables_and_keys_are_defined_once() {
+        assert_eq!(Toml::parse("[cpu]\nclock = 1\n\n[cpu]\n").err(), Some("line 4: [cpu] is defined twice".to_string()));
+        assert_eq!(Toml::parse("[cpu]\nclock = 1\nclock = 2\n").err(), Some("line 3: clock is set twice".to_string()));
+        assert_eq!(Toml::parse("cpu = 1\n[cpu]\n").err(), Some("line 2: cpu is not a table".to_string()));
+    }
+
+    #[test]
+    fn strings_and_arrays() {
+        let toml = Toml::parse("s = \"a\\tb\\u00e9\\\"\"\nvalues = [\n  1, # one\n  [2, 3],\n  'x',\n]\n").unwrap();
+        assert_eq!(toml.get("s").and_then(Toml::as_str), Some("a\tb\u{e9}\""));
+        assert_eq!(toml.get("values").and_then(Toml::as_array).unwrap(), [
+            Toml::Integer(1),
+            Toml::Array(vec![Toml::Integer(2), Toml::Integer(3)]),
+            Toml::String("x".to_string()),
+        ]);
+
+        assert_eq!(Toml::parse("s = \"open\n").err(), Some("line 1: unterminated string".to_string()));
+        assert_eq!(Toml::parse("a = 1 b = 2\n").err(), Some("line 1: expected the end of the line".to_string()));
+    }
+}
@@ -0,0 +1,706 @@
+use std::collections::{HashSet, VecDeque};
+use std::io::{self, Read, Write};
+use std::process::{Command, Stdio};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::cpu::{instruction_length, State8080};
+use crate::disassembler;
+use crate::history::History;
+use crate::processor::Cpu;
+use crate::space_invader::{GameState, VIDEO_RAM};
+use crate::step::Access;
+
+const FRAME_TIME: Duration = Duration::from_millis(16);
+const IO_LOG_SIZE: usize = 256;
+// columns of the disassembly, registers and stack panes
+const CODE_WIDTH: usize = 38;
+const HELP: &str = "s step  n next  o out  c run  S/C back  w who wrote  space pause  b break  g goto  tab pane  q quit";
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Key {
+    Char(char),
+    Up,
+    Down,
+    Left,
+    Right,
+    PageUp,
+    PageDown,
+    Tab,
+    Enter,
+    Escape,
+    Backspace,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Pane {
+    Code,
+    Memory,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct PortAccess {
+    frame: u64,
+    pc: u16,
+    port: u8,
+    value: u8,
+    output: bool,
+}
+
+// raw mode and the alternate screen while the debugger runs, through stty so
+// it works on any terminal, ssh included
+struct Terminal {
+    saved: String,
+}
+
+impl Terminal {
+    fn enter() -> io::Result<Self> {
+        let saved = stty(&["-g"])?;
+        stty(&["raw", "-echo"])?;
+        print!("\x1b[?1049h\x1b[?25l\x1b[2J");
+        io::stdout().flush()?;
+        Ok(Self { saved: saved.trim().to_string() })
+    }
+
+    // rows and columns
+    fn size() -> (usize, usize) {
+        let size = stty(&["size"]).unwrap_or_default();
+        let mut numbers = size.split_whitespace().filter_map(|number| number.parse().ok());
+
+        match (numbers.next(), numbers.next()) {
+            (Some(rows), Some(columns)) if rows > 0 && columns > 0 => (rows, columns),
+            _ => (40, 120),
+        }
+    }
+}
+
+impl Drop for Terminal {
+    fn drop(&mut self) {
+        print!("\x1b[0m\x1b[?25h\x1b[?1049l");
+        let _ = io::stdout().flush();
+        let _ = stty(&[self.saved.as_str()]);
+    }
+}
+
+fn stty(arguments: &[&str]) -> io::Result<String> {
+    let output = Command::new("stty").args(arguments).stdin(Stdio::inherit()).stderr(Stdio::null()).output()?;
+    if !output.status.success() {
+        return Err(io::Error::other("stty failed, stdin is not a terminal"));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+// keys in a chunk read from the terminal, escape sequences arrive whole
+fn parse_keys(bytes: &[u8]) -> Vec<Key> {
+    let mut keys = Vec::new();
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let key = match bytes[i] {
+            0x1b if bytes.get(i + 1) == Some(&b'[') => {
+                i += 2;
+                match bytes.get(i) {
+                    Some(b'A') => Key::Up,
+                    Some(b'B') => Key::Down,
+                    Some(b'C') => Key::Right,
+                    Some(b'D') => Key::Left,
+                    Some(b'5') if bytes.get(i + 1) == Some(&b'~') => {
+                        i += 1;
+                        Key::PageUp
+                    },
+                    Some(b'6') if bytes.get(i + 1) == Some(&b'~') => {
+                        i += 1;
+                        Key::PageDown
+                    },
+                    _ => Key::Escape,
+                }
+            },
+            0x1b => Key::Escape,
+            b'\t' => Key::Tab,
+            b'\r' | b'\n' => Key::Enter,
+            0x7f | 0x08 => Key::Backspace,
+            // Ctrl-C quits like q
+            0x03 => Key::Char('q'),
+            byte => Key::Char(byte as char),
+        };
+        keys.push(key);
+        i += 1;
+    }
+    keys
+}
+
+// a grid of characters, highlighted cells are drawn in reverse video
+struct Screen {
+    rows: usize,
+    columns: usize,
+    cells: Vec<(char, bool)>,
+}
+
+impl Screen {
+    fn new(rows: usize, columns: usize) -> Self {
+        Self { rows, columns, cells: vec![(' ', false); rows * columns] }
+    }
+
+    fn put(&mut self, row: usize, column: usize, text: &str, highlight: bool) {
+        if row >= self.rows {
+            return;
+        }
+        for (i, c) in text.chars().enumerate() {
+            if column + i >= self.columns {
+                break;
+            }
+            self.cells[row * self.columns + column + i] = (c, highlight);
+        }
+    }
+
+    // a box with the title in the top border
+    fn frame(&mut self, row: usize, column: usize, height: usize, width: usize, title: &str) {
+        if height < 2 || width < 2 {
+            return;
+        }
+        let horizontal = "─".repeat(width - 2);
+        self.put(row, column, &format!("┌{}┐", horizontal), false);
+        for i in 1..height - 1 {
+            self.put(row + i, column, "│", false);
+            self.put(row + i, column + width - 1, "│", false);
+        }
+        self.put(row + height - 1, column, &format!("└{}┘", horizontal), false);
+        self.put(row, column + 2, &format!(" {} ", title), false);
+    }
+
+    fn render(&self) -> String {
+        let mut out = String::from("\x1b[H");
+        for row in 0..self.rows {
+            let mut highlighted = false;
+            for &(c, highlight) in &self.cells[row * self.columns..(row + 1) * self.columns] {
+                if highlight != highlighted {
+                    out.push_str(if highlight { "\x1b[7m" } else { "\x1b[0m" });
+                    highlighted = highlight;
+                }
+                out.push(c);
+            }
+            out.push_str("\x1b[0m");
+            if row + 1 < self.rows {
+                out.push_str("\r\n");
+            }
+        }
+        out
+    }
+}
+
+// a full-screen debugger for the invaders machine on the terminal
+pub struct Tui {
+    breakpoints: HashSet<u16>,
+    running: bool,
+    // set when the program resumes, a breakpoint at the PC does not stop it
+    resumed: bool,
+    // return address and call depth a step over or out runs to
+    until: Option<(Option<u16>, usize)>,
+    focus: Pane,
+    // follows the PC when none
+    code_cursor: Option<u16>,
+    memory_cursor: u16,
+    // high nibble typed into the memory editor
+    nibble: Option<u8>,
+    prompt: Option<String>,
+    io_log: VecDeque<PortAccess>,
+    message: String,
+    history: History,
+    quit: bool,
+}
+
+impl Tui {
+    pub fn new() -> Self {
+        Self {
+            breakpoints: HashSet::new(),
+            running: false,
+            resumed: false,
+            until: None,
+            focus: Pane::Code,
+            code_cursor: None,
+            memory_cursor: 0x2000,
+            nibble: None,
+            prompt: None,
+            io_log: VecDeque::with_capacity(IO_LOG_SIZE),
+            message: String::new(),
+            history: History::new(),
+            quit: false,
+        }
+    }
+
+    // takes over the terminal until the user quits, the machine starts stopped
+    pub fn run(&mut self, game: &mut GameState) -> io::Result<()> {
+        let terminal = Terminal::enter()?;
+        let (sender, keys) = mpsc::channel();
+        thread::spawn(move || {
+            let mut buffer = [0; 64];
+            while let Ok(count) = io::stdin().read(&mut buffer) {
+                if count == 0 || sender.send(buffer[..count].to_vec()).is_err() {
+                    break;
+                }
+            }
+        });
+
+        let mut size = Terminal::size();
+        let mut measured = Instant::now();
+
+        while !self.quit {
+            let start = Instant::now();
+            if self.running {
+                self.run_frame(game);
+            }
+            if measured.elapsed() > Duration::from_secs(1) {
+                size = Terminal::size();
+                measured = Instant::now();
+            }
+
+            let screen = self.draw(game, size.0, size.1);
+            let mut stdout = io::stdout();
+            stdout.write_all(screen.render().as_bytes())?;
+            stdout.flush()?;
+
+            self.wait_for_keys(game, &keys, FRAME_TIME.checked_sub(start.elapsed()).unwrap_or_default());
+        }
+
+        drop(terminal);
+        Ok(())
+    }
+
+    fn wait_for_keys(&mut self, game: &mut GameState, keys: &Receiver<Vec<u8>>, timeout: Duration) {
+        let mut chunk = match keys.recv_timeout(timeout) {
+            Ok(chunk) => chunk,
+            Err(RecvTimeoutError::Timeout) => return,
+            Err(RecvTimeoutError::Disconnected) => {
+                self.quit = true;
+                return;
+            },
+        };
+
+        loop {
+            for key in parse_keys(&chunk) {
+                self.key(game, key);
+            }
+            chunk = match keys.try_recv() {
+                Ok(chunk) => chunk,
+                Err(_) => break,
+            };
+        }
+    }
+
+    fn key(&mut self, game: &mut GameState, key: Key) {
+        if let Some(prompt) = &mut self.prompt {
+            match key {
+                // an address in hex or a label
+                Key::Char(c) if (c.is_ascii_alphanumeric() || "_?@.".contains(c)) && prompt.len() < 32 => prompt.push(c),
+                Key::Backspace => {
+                    prompt.pop();
+                },
+                Key::Enter => {
+                    let address = game.cpu().call_stack().symbols().address(prompt)
+                        .or_else(|| u16::from_str_radix(prompt, 16).ok());
+                    if let Some(address) = address {
+                        match self.focus {
+                            Pane::Code => self.code_cursor = Some(address),
+                            Pane::Memory => self.memory_cursor = address,
+                        }
+                    }
+                    self.prompt = None;
+                },
+                Key::Escape => self.prompt = None,
+                _ => (),
+            }
+            return;
+        }
+
+        let pc = game.cpu().pc();
+        let depth = game.cpu().call_stack().frames().len();
+        self.message.clear();
+
+        match key {
+            // hex digits edit memory before they are commands
+            Key::Char(c) if self.focus == Pane::Memory && c.is_ascii_hexdigit() => self.edit_memory(game, key),
+            Key::Char('q') => self.quit = true,
+            Key::Char('s') if !self.running => self.step(game),
+            Key::Char('n') if !self.running => {
+                let opcode = game.cpu().peek(pc);
+                if opcode == 0xcd || opcode & 0xc7 == 0xc4 || opcode & 0xc7 == 0xc7 {
+                    self.resume(Some((Some(pc.wrapping_add(instruction_length(opcode))), depth)));
+                } else {
+                    self.step(game);
+                }
+            },
+            Key::Char('o') if !self.running => self.resume(Some((None, depth))),
+            Key::Char('c') if !self.running => self.resume(None),
+            Key::Char('S') if !self.running => {
+                self.code_cursor = None;
+                if !self.history.step_back(game) {
+                    self.message = String::from("at the start of the history");
+                }
+            },
+            Key::Char('C') if !self.running => self.continue_back(game),
+            Key::Char('w') => self.who_wrote(game),
+            Key::Char(' ') => {
+                if self.running {
+                    self.stop(String::from("paused"));
+                } else {
+                    self.resume(None);
+                }
+            },
+            Key::Char('b') => {
+                let address = self.code_cursor.unwrap_or(pc);
+                if !self.breakpoints.remove(&address) {
+                    self.breakpoints.insert(address);
+                }
+            },
+            Key::Char('g') => self.prompt = Some(String::new()),
+            Key::Char('f') => self.code_cursor = None,
+            Key::Tab => {
+                self.focus = if self.focus == Pane::Code { Pane::Memory } else { Pane::Code };
+                self.nibble = None;
+            },
+            _ => match self.focus {
+                Pane::Code => self.move_code_cursor(game.cpu(), key),
+                Pane::Memory => self.edit_memory(game, key),
+            },
+        }
+    }
+
+    fn move_code_cursor(&mut self, cpu: &State8080, key: Key) {
+        let peek = |address| cpu.peek(address);
+        let cursor = self.code_cursor.unwrap_or_else(|| cpu.pc());
+
+        self.code_cursor = Some(match key {
+            Key::Down => cursor.wrapping_add(disassembler::length(cpu.peek(cursor))),
+            Key::Up => disassembler::start_before(&peek, cursor, 1),
+            Key::PageDown => (0..16).fold(cursor, |at, _| at.wrapping_add(disassembler::length(cpu.peek(at)))),
+            Key::PageUp => disassembler::start_before(&peek, cursor, 16),
+            _ => return,
+        });
+    }
+
+    fn edit_memory(&mut self, game: &mut GameState, key: Key) {
+        let cursor = self.memory_cursor;
+
+        match key {
+            Key::Left => self.memory_cursor = cursor.wrapping_sub(1),
+            Key::Right => self.memory_cursor = cursor.wrapping_add(1),
+            Key::Up => self.memory_cursor = cursor.wrapping_sub(16),
+            Key::Down => self.memory_cursor = cursor.wrapping_add(16),
+            Key::PageUp => self.memory_cursor = cursor.wrapping_sub(0x100),
+            Key::PageDown => self.memory_cursor = cursor.wrapping_add(0x100),
+            Key::Char(c) if c.is_ascii_hexdigit() => {
+                let digit = c.to_digit(16).unwrap() as u8;
+                match self.nibble.take() {
+                    None => self.nibble = Some(digit),
+                    Some(high) => {
+                        game.cpu_mut().poke(cursor, high << 4 | digit);
+                        self.history.edited(game);
+                        self.memory_cursor = cursor.wrapping_add(1);
+                    },
+                }
+                return;
+            },
+            _ => (),
+        }
+        self.nibble = None;
+    }
+
+    fn resume(&mut self, until: Option<(Option<u16>, usize)>) {
+        self.running = true;
+        self.resumed = true;
+        self.until = until;
+        self.code_cursor = None;
+    }
+
+    fn stop(&mut self, message: String) {
+        self.running = false;
+        self.until = None;
+        self.message = message;
+    }
+
+    fn step(&mut self, game: &mut GameState) {
+        let pc = game.cpu().pc();
+        self.code_cursor = None;
+        self.history.record(game);
+        self.message = match game.step() {
+            Ok(_) => game.cpu().last_step().to_string(),
+            Err(error) => error.to_string(),
+        };
+        self.log_ports(game, pc);
+    }
+
+    // runs until the frame is complete or something stops the program
+    fn run_frame(&mut self, game: &mut GameState) {
+        loop {
+            let pc = game.cpu().pc();
+            let depth = game.cpu().call_stack().frames().len();
+
+            if !self.resumed && self.breakpoints.contains(&pc) {
+                return self.stop(format!("breakpoint at {}", game.cpu().call_stack().symbols().describe(pc)));
+            }
+            match self.until {
+                Some((Some(return_address), call_depth)) if pc == return_address && depth <= call_depth => {
+                    return self.stop(String::new());
+                },
+                Some((None, call_depth)) if depth < call_depth => return self.stop(String::new()),
+                _ => (),
+            }
+
+            self.resumed = false;
+            self.history.record(game);
+            let completed_frame = game.step();
+            self.log_ports(game, pc);
+
+            match completed_frame {
+                Ok(true) => return,
+                Ok(false) => (),
+                Err(error) => return self.stop(error.to_string()),
+            }
+        }
+    }
+
+    // back to the last instruction at a breakpoint, stopping before it ran
+    fn continue_back(&mut self, game: &mut GameState) {
+        let breakpoints = &self.breakpoints;
+        let found = self.history.find_back(game, &mut |_, pc| breakpoints.contains(&pc));
+
+        self.code_cursor = None;
+        match found {
+            Some(instruction) => {
+                self.history.travel(game, instruction);
+                self.message = format!("breakpoint at {}", game.cpu().call_stack().symbols().describe(game.cpu().pc()));
+            },
+            None => {
+                let oldest = self.history.oldest().unwrap_or(game.instructions());
+                self.history.travel(game, oldest);
+                self.message = String::from("at the start of the history");
+            },
+        }
+    }
+
+    // the instruction that last wrote the byte at the memory cursor, shown
+    // in the code pane
+    fn who_wrote(&mut self, game: &mut GameState) {
+        let address = self.memory_cursor;
+        let symbols = game.cpu().call_stack().symbols().clone();
+        self.message = match self.history.last_write(game, address) {
+            Some(write) => {
+                self.code_cursor = Some(write.pc);
+                format!(
+                    "{} written with {:02x} at {}, {} instructions ago",
+                    symbols.describe(address),
+                    write.value,
+                    symbols.describe(write.pc),
+                    game.instructions() - write.instruction,
+                )
+            },
+            None => format!("{} not written in the history", symbols.describe(address)),
+        };
+    }
+
+    // the port accesses of the instruction at `pc` that just ran
+    fn log_ports(&mut self, game: &GameState, pc: u16) {
+        for access in game.cpu().last_step().accesses {
+            let (port, value, output) = match access {
+                Access::Input { port, value } => (port, value, false),
+                Access::Output { port, value } => (port, value, true),
+                _ => continue,
+            };
+            if self.io_log.len() == IO_LOG_SIZE {
+                self.io_log.pop_front();
+            }
+            self.io_log.push_back(PortAccess { frame: game.frames(), pc, port, value, output });
+        }
+    }
+
+    fn draw(&self, game: &GameState, rows: usize, columns: usize) -> Screen {
+        let mut screen = Screen::new(rows, columns);
+        let height = rows.saturating_sub(1);
+
+        // the largest framebuffer that leaves room for the other panes
+        let scale = [1, 2, 4].iter().copied()
+            .find(|&scale| 64 / scale + 2 <= height && 112 / scale + 2 + CODE_WIDTH + 40 <= columns)
+            .unwrap_or(4);
+        let screen_width = 112 / scale + 2;
+        let screen_height = (64 / scale + 2).min(height);
+        let middle_width = columns.saturating_sub(CODE_WIDTH + screen_width);
+
+        let code_height = height * 11 / 20;
+        let registers_height = 6;
+        let stack_height = height.saturating_sub(code_height + registers_height);
+        let memory_height = height * 3 / 5;
+
+        self.draw_code(&mut screen, game.cpu(), 0, code_height);
+        draw_registers(&mut screen, game, code_height, registers_height);
+        draw_stack(&mut screen, game.cpu(), code_height + registers_height, stack_height);
+        self.draw_memory(&mut screen, game.cpu(), CODE_WIDTH, middle_width, memory_height);
+        self.draw_io_log(&mut screen, CODE_WIDTH, middle_width, memory_height, height.saturating_sub(memory_height));
+        draw_framebuffer(&mut screen, game.cpu(), CODE_WIDTH + middle_width, screen_width, screen_height, scale);
+
+        let status = match &self.prompt {
+            Some(prompt) => format!("goto: {}", prompt),
+            None => {
+                let state = if self.running { "running" } else { "stopped" };
+                format!("{} {}  {}", state, self.message, HELP)
+            },
+        };
+        screen.put(height, 0, &status, false);
+        screen
+    }
+
+    fn draw_code(&self, screen: &mut Screen, cpu: &State8080, row: usize, height: usize) {
+        let title = if self.focus == Pane::Code { "[disassembly]" } else { "disassembly" };
+        screen.frame(row, 0, height, CODE_WIDTH, title);
+
+        let peek = |address| cpu.peek(address);
+        let lines = height.saturating_sub(2) as u16;
+        let cursor = self.code_cursor.unwrap_or_else(|| cpu.pc());
+        let symbols = cpu.call_stack().symbols();
+        let mut address = disassembler::start_before(&peek, cursor, lines / 3);
+        // a labelled address takes a line for its name first
+        let mut named = None;
+
+        for line in 0..lines as usize {
+            if let Some(name) = symbols.name(address).filter(|_| named != Some(address)) {
+                screen.put(row + 1 + line, 1, &format!("{:<1$}", format!("{}:", name), CODE_WIDTH - 2), false);
+                named = Some(address);
+                continue;
+            }
+            let (text, length) = disassembler::disassemble_at(&peek, address, symbols);
+            let bytes = (0..length).map(|i| format!("{:02x}", cpu.peek(address.wrapping_add(i)))).collect::<Vec<_>>();
+            let marker = match (self.breakpoints.contains(&address), address == cpu.pc()) {
+                (true, true) => "●▶",
+                (true, false) => "● ",
+                (false, true) => " ▶",
+                (false, false) => "  ",
+            };
+
+            let line_text = format!("{}{:04x}  {:<9} {}", marker, address, bytes.join(" "), text);
+            screen.put(row + 1 + line, 1, &format!("{:<1$}", line_text, CODE_WIDTH - 2), address == cursor);
+            address = address.wrapping_add(length);
+        }
+    }
+
+    fn draw_memory(&self, screen: &mut Screen, cpu: &State8080, column: usize, width: usize, height: usize) {
+        let title = if self.focus == Pane::Memory { "[memory]" } else { "memory" };
+        screen.frame(0, column, height, width, title);
+
+        let per_row: u16 = if width >= 6 + 16 * 3 + 16 + 3 { 16 } else { 8 };
+        let lines = height.saturating_sub(2) as u16;
+        let top = (self.memory_cursor / per_row).wrapping_sub(lines / 2).wrapping_mul(per_row);
+
+        for line in 0..lines {
+            let address = top.wrapping_add(line * per_row);
+            screen.put(1 + line as usize, column + 1, &format!("{:04x}", address), false);
+
+            for i in 0..per_row {
+                let at = address.wrapping_add(i);
+                let value = cpu.peek(at);
+                let text = match self.nibble {
+                    Some(high) if at == self.memory_cursor => format!("{:x}_", high),
+                    _ => format!("{:02x}", value),
+                };
+                let selected = at == self.memory_cursor && self.focus == Pane::Memory;
+
+                screen.put(1 + line as usize, column + 7 + 3 * i as usize, &text, selected);
+                let printable = if (0x20..0x7f).contains(&value) { value as char } else { '.' };
+                screen.put(1 + line as usize, column + 8 + 3 * per_row as usize + i as usize, &printable.to_string(), selected);
+            }
+        }
+    }
+
+    fn draw_io_log(&self, screen: &mut Screen, column: usize, width: usize, row: usize, height: usize) {
+        screen.frame(row, column, height, width, "io ports");
+
+        let lines = height.saturating_sub(2);
+        let skip = self.io_log.len().saturating_sub(lines);
+        for (line, access) in self.io_log.iter().skip(skip).enumerate() {
+            let direction = if access.output { "out" } else { "in " };
+            screen.put(
+                row + 1 + line, column + 1,
+                &format!("frame {:<6} {:04x}  {} {:02x} = {:02x}", access.frame, access.pc, direction, access.port, access.value),
+                false,
+            );
+        }
+    }
+}
+
+fn draw_registers(screen: &mut Screen, game: &GameState, row: usize, height: usize) {
+    screen.frame(row, 0, height, CODE_WIDTH, "registers");
+
+    let registers = Cpu::registers(game.cpu());
+    let flags = [("s", 0x80), ("z", 0x40), ("ac", 0x10), ("p", 0x04), ("cy", 0x01)].iter()
+        .map(|&(name, mask)| if registers.flags & mask != 0 { name.to_uppercase() } else { "-".repeat(name.len()) })
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    let lines = [
+        format!("a  {:02x}     sp {:04x}   pc {:04x}", registers.a, registers.sp, registers.pc),
+        format!("bc {:02x}{:02x}   de {:02x}{:02x}   hl {:02x}{:02x}", registers.b, registers.c, registers.d, registers.e, registers.h, registers.l),
+        format!("flags {}{}", flags, if game.cpu().halted() { "  halted" } else { "" }),
+        format!("frame {}  cycles {}", game.frames(), game.cycles()),
+    ];
+    for (i, line) in lines.iter().enumerate().take(height.saturating_sub(2)) {
+        screen.put(row + 1 + i, 1, line, false);
+    }
+}
+
+// words from SP up, the return addresses the call stack knows are named;
+// the last line holds the latest anomaly once there has been one
+fn draw_stack(screen: &mut Screen, cpu: &State8080, row: usize, height: usize) {
+    screen.frame(row, 0, height, CODE_WIDTH, "stack");
+
+    let sp = Cpu::registers(cpu).sp;
+    let frames = cpu.call_stack().frames();
+    let mut lines = height.saturating_sub(2);
+
+    if let Some(anomaly) = cpu.call_stack().anomalies().last() {
+        if lines > 0 {
+            lines -= 1;
+            let text: String = anomaly.to_string().chars().take(CODE_WIDTH - 2).collect();
+            screen.put(row + 1 + lines, 1, &text, true);
+        }
+    }
+
+    for line in 0..lines {
+        let address = sp.wrapping_add(2 * line as u16);
+        let value = (cpu.peek(address.wrapping_add(1)) as u16) << 8 | cpu.peek(address) as u16;
+        let note = match frames.iter().rev().find(|frame| frame.slot == address) {
+            Some(frame) => format!("ret from {}", cpu.call_stack().symbols().describe(frame.target)),
+            None => String::new(),
+        };
+        screen.put(row + 1 + line, 1, &format!("{:04x}  {:04x}  {}", address, value, note), false);
+    }
+}
+
+// the rotated 224x256 screen, a braille character holds 2x4 dots and a dot
+// is lit when any pixel of the scale x scale block it stands for is
+fn draw_framebuffer(screen: &mut Screen, cpu: &State8080, column: usize, width: usize, height: usize, scale: usize) {
+    screen.frame(0, column, height, width, "screen");
+
+    const DOTS: [[u32; 4]; 2] = [[0x01, 0x02, 0x04, 0x40], [0x08, 0x10, 0x20, 0x80]];
+    let pixel = |x: usize, y: usize| {
+        let index = x * 256 + (255 - y);
+        cpu.peek((VIDEO_RAM + index / 8) as u16) & (1 << (index % 8)) != 0
+    };
+
+    for cell_y in 0..height.saturating_sub(2).min(64 / scale) {
+        let mut line = String::new();
+        for cell_x in 0..width.saturating_sub(2).min(112 / scale) {
+            let mut bits = 0;
+            for (dot_x, column_dots) in DOTS.iter().enumerate() {
+                for (dot_y, &bit) in column_dots.iter().enumerate() {
+                    let x = (cell_x * 2 + dot_x) * scale;
+                    let y = (cell_y * 4 + dot_y) * scale;
+                    let lit = (0..scale).any(|dy| (0..scale).any(|dx| pixel(x + dx, y + dy)));
+                    if lit {
+                        bits |= bit;
+                    }
+                }
+            }
+            line.push(std::char::from_u32(0x2800 + bits).unwrap_or(' '));
+        }
+        screen.put(1 + cell_y, column + 1, &line, false);
+    }
+}
@@ -0,0 +1,1513 @@
+use std::fmt;
+
+use crate::cpu::RegisterPair;
+use crate::error::CpuError;
+use crate::processor::{Cpu, Interrupt, Registers};
+use crate::space_invader::IOState;
+
+const MEMORY_SIZE: usize = 0x10000;
+
+const FLAG_C: u8 = 1;
+const FLAG_N: u8 = 1 << 1;
+const FLAG_PV: u8 = 1 << 2;
+const FLAG_X: u8 = 1 << 3;
+const FLAG_H: u8 = 1 << 4;
+const FLAG_Y: u8 = 1 << 5;
+const FLAG_Z: u8 = 1 << 6;
+const FLAG_S: u8 = 1 << 7;
+
+// base T-states of the unprefixed opcodes, conditional instructions are listed
+// with their not-taken timing and (HL) operands without an index displacement
+const CYCLES: [u8; 256] = [
+     4, 10,  7,  6,  4,  4,  7,  4,  4, 11,  7,  6,  4,  4,  7,  4,
+     8, 10,  7,  6,  4,  4,  7,  4, 12, 11,  7,  6,  4,  4,  7,  4,
+     7, 10, 16,  6,  4,  4,  7,  4,  7, 11, 16,  6,  4,  4,  7,  4,
+     7, 10, 13,  6, 11, 11, 10,  4,  7, 11, 13,  6,  4,  4,  7,  4,
+     4,  4,  4,  4,  4,  4,  7,  4,  4,  4,  4,  4,  4,  4,  7,  4,
+     4,  4,  4,  4,  4,  4,  7,  4,  4,  4,  4,  4,  4,  4,  7,  4,
+     4,  4,  4,  4,  4,  4,  7,  4,  4,  4,  4,  4,  4,  4,  7,  4,
+     7,  7,  7,  7,  7,  7,  4,  7,  4,  4,  4,  4,  4,  4,  7,  4,
+     4,  4,  4,  4,  4,  4,  7,  4,  4,  4,  4,  4,  4,  4,  7,  4,
+     4,  4,  4,  4,  4,  4,  7,  4,  4,  4,  4,  4,  4,  4,  7,  4,
+     4,  4,  4,  4,  4,  4,  7,  4,  4,  4,  4,  4,  4,  4,  7,  4,
+     4,  4,  4,  4,  4,  4,  7,  4,  4,  4,  4,  4,  4,  4,  7,  4,
+     5, 10, 10, 10, 10, 11,  7, 11,  5, 10, 10,  0, 10, 17,  7, 11,
+     5, 10, 10, 11, 10, 11,  7, 11,  5,  4, 10, 11, 10,  0,  7, 11,
+     5, 10, 10, 19, 10, 11,  7, 11,  5,  4, 10,  4, 10,  0,  7, 11,
+     5, 10, 10,  4, 10, 11,  7, 11,  5,  6, 10,  4, 10,  0,  7, 11,
+];
+
+// interrupt mode selected by ED 46/4E/56/5E/66/6E/76/7E
+const INTERRUPT_MODES: [u8; 8] = [0, 0, 1, 2, 0, 0, 1, 2];
+
+// register used wherever the unprefixed instruction names HL
+#[derive(Clone, Copy, PartialEq)]
+enum Index {
+    HL,
+    IX,
+    IY,
+}
+
+fn sz53(value: u8) -> u8 {
+    let mut flags = value & (FLAG_S | FLAG_Y | FLAG_X);
+    if value == 0 {
+        flags |= FLAG_Z;
+    }
+    flags
+}
+
+fn parity(value: u8) -> u8 {
+    if value.count_ones().is_multiple_of(2) {
+        FLAG_PV
+    } else {
+        0
+    }
+}
+
+fn sz53p(value: u8) -> u8 {
+    sz53(value) | parity(value)
+}
+
+pub struct StateZ80 {
+    a: u8,
+    f: u8,
+    bc: RegisterPair,
+    de: RegisterPair,
+    hl: RegisterPair,
+    ix: RegisterPair,
+    iy: RegisterPair,
+    af_shadow: u16,
+    bc_shadow: u16,
+    de_shadow: u16,
+    hl_shadow: u16,
+    sp: u16,
+    pc: u16,
+    i: u8,
+    r: u8,
+    // internal WZ register, only visible through the undocumented flags
+    memptr: u16,
+    iff1: bool,
+    iff2: bool,
+    interrupt_mode: u8,
+    // set by EI, interrupts are not accepted until the next instruction is done
+    interrupt_delay: bool,
+    // the INT line, held until the interrupt is acknowledged
+    pending_interrupt: Option<Interrupt>,
+    // an edge on the NMI line, acknowledged before the next instruction
+    // whatever IFF1 says
+    pending_nmi: bool,
+    // first error of the current instruction, reported once it completes
+    fault: Option<CpuError>,
+    halted: bool,
+    memory: Vec<u8>,
+}
+
+impl fmt::Display for StateZ80 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(
+        f,
+        "af={:04x} bc={:04x} de={:04x} hl={:04x} ix={:04x} iy={:04x}\n
+        af'={:04x} bc'={:04x} de'={:04x} hl'={:04x}\n
+        sp={:04x} pc={:04x} i={:02x} r={:02x} im={} iff={}{}",
+        (self.a as u16) << 8 | self.f as u16,
+        self.bc.both(),
+        self.de.both(),
+        self.hl.both(),
+        self.ix.both(),
+        self.iy.both(),
+        self.af_shadow,
+        self.bc_shadow,
+        self.de_shadow,
+        self.hl_shadow,
+        self.sp,
+        self.pc,
+        self.i,
+        self.r,
+        self.interrupt_mode,
+        self.iff1 as u8,
+        self.iff2 as u8)
+    }
+}
+
+impl StateZ80 {
+    pub fn new() -> StateZ80 {
+        StateZ80 {
+            a: 0xff,
+            f: 0xff,
+            bc: RegisterPair::new(),
+            de: RegisterPair::new(),
+            hl: RegisterPair::new(),
+            ix: RegisterPair::new(),
+            iy: RegisterPair::new(),
+            af_shadow: 0,
+            bc_shadow: 0,
+            de_shadow: 0,
+            hl_shadow: 0,
+            sp: 0xffff,
+            pc: 0,
+            i: 0,
+            r: 0,
+            memptr: 0,
+            iff1: false,
+            iff2: false,
+            interrupt_mode: 0,
+            interrupt_delay: false,
+            pending_interrupt: None,
+            pending_nmi: false,
+            fault: None,
+            halted: false,
+            memory: vec![0; MEMORY_SIZE],
+        }
+    }
+
+    pub fn load_from_rom(rom: &[u8], rom_start: usize, pc_start: u16) -> Self {
+        let mut cpu = Self::new();
+        cpu.load_rom(rom, rom_start);
+        cpu.pc = pc_start;
+        cpu
+    }
+
+    // acknowledges a maskable interrupt right away. The data bus holds an
+    // instruction in mode 0, is ignored in mode 1 and holds the low byte of
+    // the vector table address in mode 2. Returns the cycles spent, 0 if the
+    // interrupt was not accepted.
+    pub fn maskable_interrupt(&mut self, interrupt: Interrupt, state: &mut dyn IOState) -> u64 {
+        if !self.iff1 || self.interrupt_delay {
+            return 0;
+        }
+
+        self.halted = false;
+        self.iff1 = false;
+        self.iff2 = false;
+        self.increment_r();
+
+        match (self.interrupt_mode, interrupt) {
+            // the operands of a CALL come from the bus as well
+            (0, Interrupt::Instruction([0xcd, lsb, msb])) => {
+                self.push(self.pc);
+                self.pc = (msb as u16) << 8 | lsb as u16;
+                self.memptr = self.pc;
+                19
+            },
+            (0, _) => 2 + self.execute(interrupt.opcode(), state) as u64,
+            (1, _) => {
+                self.push(self.pc);
+                self.pc = 0x38;
+                self.memptr = self.pc;
+                13
+            },
+            _ => {
+                self.push(self.pc);
+                let vector = (self.i as u16) << 8 | interrupt.opcode() as u16;
+                self.pc = self.read_word(vector);
+                self.memptr = self.pc;
+                19
+            },
+        }
+    }
+
+    // IFF2 keeps what IFF1 was for RETN to restore
+    fn acknowledge_nmi(&mut self) -> u64 {
+        self.halted = false;
+        self.iff1 = false;
+        self.increment_r();
+        self.push(self.pc);
+        self.pc = 0x66;
+        self.memptr = self.pc;
+        11
+    }
+
+    fn load_rom(&mut self, rom: &[u8], rom_start: usize) {
+        self.memory[rom_start..rom_start + rom.len()].clone_from_slice(rom);
+    }
+
+    fn read_byte(&self, address: u16) -> u8 {
+        self.memory[address as usize]
+    }
+
+    fn read_word(&self, address: u16) -> u16 {
+        (self.read_byte(address.wrapping_add(1)) as u16) << 8 | self.read_byte(address) as u16
+    }
+
+    fn write_byte(&mut self, address: u16, value: u8) {
+        self.memory[address as usize] = value;
+    }
+
+    fn write_word(&mut self, address: u16, value: u16) {
+        self.write_byte(address, value as u8);
+        self.write_byte(address.wrapping_add(1), (value >> 8) as u8);
+    }
+
+    // a failing device reads as open bus, the error is reported after the instruction
+    fn input(&mut self, state: &mut dyn IOState, port: u8) -> u8 {
+        state.input(port).unwrap_or_else(|error| {
+            if self.fault.is_none() {
+                self.fault = Some(error.into());
+            }
+            0xff
+        })
+    }
+
+    fn output(&mut self, state: &mut dyn IOState, port: u8, value: u8) {
+        if let Err(error) = state.output(port, value) {
+            if self.fault.is_none() {
+                self.fault = Some(error.into());
+            }
+        }
+    }
+
+    fn fetch_byte(&mut self) -> u8 {
+        let value = self.read_byte(self.pc);
+        self.pc = self.pc.wrapping_add(1);
+        value
+    }
+
+    fn fetch_word(&mut self) -> u16 {
+        let value = self.read_word(self.pc);
+        self.pc = self.pc.wrapping_add(2);
+        value
+    }
+
+    // M1 cycles refresh memory, bumping the lower 7 bits of R
+    fn fetch_opcode(&mut self) -> u8 {
+        self.increment_r();
+        self.fetch_byte()
+    }
+
+    fn increment_r(&mut self) {
+        self.r = (self.r & 0x80) | (self.r.wrapping_add(1) & 0x7f);
+    }
+
+    fn push(&mut self, value: u16) {
+        self.sp = self.sp.wrapping_sub(2);
+        self.write_word(self.sp, value);
+    }
+
+    fn pop(&mut self) -> u16 {
+        let value = self.read_word(self.sp);
+        self.sp = self.sp.wrapping_add(2);
+        value
+    }
+
+    fn index_pair(&self, index: Index) -> RegisterPair {
+        match index {
+            Index::HL => self.hl,
+            Index::IX => self.ix,
+            Index::IY => self.iy,
+        }
+    }
+
+    fn index_pair_mut(&mut self, index: Index) -> &mut RegisterPair {
+        match index {
+            Index::HL => &mut self.hl,
+            Index::IX => &mut self.ix,
+            Index::IY => &mut self.iy,
+        }
+    }
+
+    // 8 bit register by its 3 bit encoding, 6 ((HL)) is handled by the caller
+    fn reg(&self, r: u8, index: Index) -> u8 {
+        match r {
+            0 => self.bc.msb(),
+            1 => self.bc.lsb(),
+            2 => self.de.msb(),
+            3 => self.de.lsb(),
+            4 => self.index_pair(index).msb(),
+            5 => self.index_pair(index).lsb(),
+            7 => self.a,
+            _ => unreachable!("(HL) is not a register"),
+        }
+    }
+
+    fn set_reg(&mut self, r: u8, index: Index, value: u8) {
+        match r {
+            0 => *self.bc.msb_mut() = value,
+            1 => *self.bc.lsb_mut() = value,
+            2 => *self.de.msb_mut() = value,
+            3 => *self.de.lsb_mut() = value,
+            4 => *self.index_pair_mut(index).msb_mut() = value,
+            5 => *self.index_pair_mut(index).lsb_mut() = value,
+            7 => self.a = value,
+            _ => unreachable!("(HL) is not a register"),
+        }
+    }
+
+    // register pair by its 2 bit encoding, 3 is SP
+    fn rp(&self, p: u8, index: Index) -> u16 {
+        match p {
+            0 => self.bc.both(),
+            1 => self.de.both(),
+            2 => self.index_pair(index).both(),
+            _ => self.sp,
+        }
+    }
+
+    fn set_rp(&mut self, p: u8, index: Index, value: u16) {
+        match p {
+            0 => *self.bc.both_mut() = value,
+            1 => *self.de.both_mut() = value,
+            2 => *self.index_pair_mut(index).both_mut() = value,
+            _ => self.sp = value,
+        }
+    }
+
+    // register pair used by PUSH and POP, 3 is AF
+    fn rp2(&self, p: u8, index: Index) -> u16 {
+        match p {
+            3 => (self.a as u16) << 8 | self.f as u16,
+            _ => self.rp(p, index),
+        }
+    }
+
+    fn set_rp2(&mut self, p: u8, index: Index, value: u16) {
+        match p {
+            3 => {
+                self.a = (value >> 8) as u8;
+                self.f = value as u8;
+            },
+            _ => self.set_rp(p, index, value),
+        }
+    }
+
+    // address of the (HL) operand, or (IX+d)/(IY+d) after reading the displacement
+    fn operand_address(&mut self, index: Index) -> u16 {
+        match index {
+            Index::HL => self.hl.both(),
+            _ => {
+                let displacement = self.fetch_byte() as i8;
+                let address = self.index_pair(index).both().wrapping_add(displacement as u16);
+                self.memptr = address;
+                address
+            },
+        }
+    }
+
+    fn condition(&self, cc: u8) -> bool {
+        match cc {
+            0 => self.f & FLAG_Z == 0,
+            1 => self.f & FLAG_Z != 0,
+            2 => self.f & FLAG_C == 0,
+            3 => self.f & FLAG_C != 0,
+            4 => self.f & FLAG_PV == 0,
+            5 => self.f & FLAG_PV != 0,
+            6 => self.f & FLAG_S == 0,
+            _ => self.f & FLAG_S != 0,
+        }
+    }
+
+    // 8 bit arithmetic
+
+    fn add8(&mut self, operand: u8, carry: bool) {
+        let carry = carry as u8;
+        let result = (self.a as u16) + (operand as u16) + (carry as u16);
+        let value = result as u8;
+
+        self.f = sz53(value);
+        if (self.a & 0xf) + (operand & 0xf) + carry > 0xf {
+            self.f |= FLAG_H;
+        }
+        if (self.a ^ operand) & 0x80 == 0 && (self.a ^ value) & 0x80 != 0 {
+            self.f |= FLAG_PV;
+        }
+        if result > 0xff {
+            self.f |= FLAG_C;
+        }
+        self.a = value;
+    }
+
+    fn sub8(&mut self, operand: u8, carry: bool) -> u8 {
+        let carry = carry as u8;
+        let result = (self.a as u16)
+            .wrapping_sub(operand as u16)
+            .wrapping_sub(carry as u16);
+        let value = result as u8;
+
+        self.f = sz53(value) | FLAG_N;
+        if (self.a & 0xf) < (operand & 0xf) + carry {
+            self.f |= FLAG_H;
+        }
+        if (self.a ^ operand) & 0x80 != 0 && (self.a ^ value) & 0x80 != 0 {
+            self.f |= FLAG_PV;
+        }
+        if result > 0xff {
+            self.f |= FLAG_C;
+        }
+        value
+    }
+
+    fn alu(&mut self, operation: u8, operand: u8) {
+        match operation {
+            // ADD
+            0 => self.add8(operand, false),
+            // ADC
+            1 => self.add8(operand, self.f & FLAG_C != 0),
+            // SUB
+            2 => self.a = self.sub8(operand, false),
+            // SBC
+            3 => self.a = self.sub8(operand, self.f & FLAG_C != 0),
+            // AND
+            4 => {
+                self.a &= operand;
+                self.f = sz53p(self.a) | FLAG_H;
+            },
+            // XOR
+            5 => {
+                self.a ^= operand;
+                self.f = sz53p(self.a);
+            },
+            // OR
+            6 => {
+                self.a |= operand;
+                self.f = sz53p(self.a);
+            },
+            // CP, the undocumented bits come from the operand
+            _ => {
+                self.sub8(operand, false);
+                self.f = (self.f & !(FLAG_X | FLAG_Y)) | (operand & (FLAG_X | FLAG_Y));
+            },
+        }
+    }
+
+    fn inc8(&mut self, operand: u8) -> u8 {
+        let result = operand.wrapping_add(1);
+        self.f = (self.f & FLAG_C) | sz53(result);
+        if result & 0xf == 0 {
+            self.f |= FLAG_H;
+        }
+        if result == 0x80 {
+            self.f |= FLAG_PV;
+        }
+        result
+    }
+
+    fn dec8(&mut self, operand: u8) -> u8 {
+        let result = operand.wrapping_sub(1);
+        self.f = (self.f & FLAG_C) | sz53(result) | FLAG_N;
+        if operand & 0xf == 0 {
+            self.f |= FLAG_H;
+        }
+        if result == 0x7f {
+            self.f |= FLAG_PV;
+        }
+        result
+    }
+
+    fn daa(&mut self) {
+        let mut correction = 0;
+        let mut carry = self.f & FLAG_C != 0;
+
+        if self.f & FLAG_H != 0 || self.a & 0xf > 9 {
+            correction |= 0x06;
+        }
+        if carry || self.a > 0x99 {
+            correction |= 0x60;
+            carry = true;
+        }
+
+        let subtract = self.f & FLAG_N != 0;
+        let half = if subtract {
+            self.f & FLAG_H != 0 && self.a & 0xf < 6
+        } else {
+            self.a & 0xf > 9
+        };
+
+        self.a = if subtract {
+            self.a.wrapping_sub(correction)
+        } else {
+            self.a.wrapping_add(correction)
+        };
+
+        self.f = sz53p(self.a) | (self.f & FLAG_N);
+        if half {
+            self.f |= FLAG_H;
+        }
+        if carry {
+            self.f |= FLAG_C;
+        }
+    }
+
+    // accumulator rotates keep S, Z and P/V
+    fn rotate_a(&mut self, operation: u8) {
+        let carry_in = self.f & FLAG_C;
+        let carry_out = match operation {
+            // RLCA
+            0 => {
+                self.a = self.a.rotate_left(1);
+                self.a & 1
+            },
+            // RRCA
+            1 => {
+                self.a = self.a.rotate_right(1);
+                self.a >> 7
+            },
+            // RLA
+            2 => {
+                let carry = self.a >> 7;
+                self.a = (self.a << 1) | carry_in;
+                carry
+            },
+            // RRA
+            _ => {
+                let carry = self.a & 1;
+                self.a = (self.a >> 1) | (carry_in << 7);
+                carry
+            },
+        };
+        self.f = (self.f & (FLAG_S | FLAG_Z | FLAG_PV)) | (self.a & (FLAG_X | FLAG_Y)) | carry_out;
+    }
+
+    // CB rotates and shifts, 6 is the undocumented SLL
+    fn rotate(&mut self, operation: u8, operand: u8) -> u8 {
+        let carry_in = self.f & FLAG_C;
+        let (result, carry_out) = match operation {
+            0 => (operand.rotate_left(1), operand >> 7),
+            1 => (operand.rotate_right(1), operand & 1),
+            2 => ((operand << 1) | carry_in, operand >> 7),
+            3 => ((operand >> 1) | (carry_in << 7), operand & 1),
+            4 => (operand << 1, operand >> 7),
+            5 => ((operand >> 1) | (operand & 0x80), operand & 1),
+            6 => ((operand << 1) | 1, operand >> 7),
+            _ => (operand >> 1, operand & 1),
+        };
+        self.f = sz53p(result) | carry_out;
+        result
+    }
+
+    // X and Y are copied from `undocumented`, which depends on the addressing mode
+    fn bit(&mut self, bit: u8, operand: u8, undocumented: u8) {
+        let value = operand & (1 << bit);
+        self.f = (self.f & FLAG_C) | FLAG_H | (undocumented & (FLAG_X | FLAG_Y));
+        if value == 0 {
+            self.f |= FLAG_Z | FLAG_PV;
+        }
+        if value & 0x80 != 0 {
+            self.f |= FLAG_S;
+        }
+    }
+
+    // 16 bit arithmetic
+
+    fn add16(&mut self, operand: u16, value: u16) -> u16 {
+        let result = (operand as u32) + (value as u32);
+        self.memptr = operand.wrapping_add(1);
+
+        self.f = (self.f & (FLAG_S | FLAG_Z | FLAG_PV)) | ((result >> 8) as u8 & (FLAG_X | FLAG_Y));
+        if (operand & 0xfff) + (value & 0xfff) > 0xfff {
+            self.f |= FLAG_H;
+        }
+        if result > 0xffff {
+            self.f |= FLAG_C;
+        }
+        result as u16
+    }
+
+    fn adc16(&mut self, value: u16) {
+        let hl = self.hl.both();
+        let carry = (self.f & FLAG_C) as u16;
+        let result = (hl as u32) + (value as u32) + (carry as u32);
+        let word = result as u16;
+        self.memptr = hl.wrapping_add(1);
+
+        self.f = (word >> 8) as u8 & (FLAG_S | FLAG_X | FLAG_Y);
+        if word == 0 {
+            self.f |= FLAG_Z;
+        }
+        if (hl & 0xfff) + (value & 0xfff) + carry > 0xfff {
+            self.f |= FLAG_H;
+        }
+        if (hl ^ value) & 0x8000 == 0 && (hl ^ word) & 0x8000 != 0 {
+            self.f |= FLAG_PV;
+        }
+        if result > 0xffff {
+            self.f |= FLAG_C;
+        }
+        *self.hl.both_mut() = word;
+    }
+
+    fn sbc16(&mut self, value: u16) {
+        let hl = self.hl.both();
+        let carry = (self.f & FLAG_C) as u16;
+        let result = (hl as u32)
+            .wrapping_sub(value as u32)
+            .wrapping_sub(carry as u32);
+        let word = result as u16;
+        self.memptr = hl.wrapping_add(1);
+
+        self.f = ((word >> 8) as u8 & (FLAG_S | FLAG_X | FLAG_Y)) | FLAG_N;
+        if word == 0 {
+            self.f |= FLAG_Z;
+        }
+        if (hl & 0xfff) < (value & 0xfff) + carry {
+            self.f |= FLAG_H;
+        }
+        if (hl ^ value) & 0x8000 != 0 && (hl ^ word) & 0x8000 != 0 {
+            self.f |= FLAG_PV;
+        }
+        if result > 0xffff {
+            self.f |= FLAG_C;
+        }
+        *self.hl.both_mut() = word;
+    }
+
+    pub fn emulate(&mut self, state: &mut dyn IOState) -> Result<u64, CpuError> {
+        let cycles = self.emulate_instruction(state);
+
+        match self.fault.take() {
+            Some(error) => Err(error),
+            None => Ok(cycles),
+        }
+    }
+
+    fn emulate_instruction(&mut self, state: &mut dyn IOState) -> u64 {
+        if self.pending_nmi {
+            self.pending_nmi = false;
+            return self.acknowledge_nmi();
+        }
+        if self.iff1 && !self.interrupt_delay {
+            if let Some(interrupt) = self.pending_interrupt.take() {
+                return self.maskable_interrupt(interrupt, state);
+            }
+        }
+        self.interrupt_delay = false;
+
+        if self.halted {
+            // HALT keeps executing NOPs until an interrupt arrives
+            self.increment_r();
+            return 4;
+        }
+
+        let opcode = self.fetch_opcode();
+        self.execute(opcode, state) as u64
+    }
+
+    fn execute(&mut self, opcode: u8, state: &mut dyn IOState) -> u32 {
+        match opcode {
+            0xcb => self.execute_cb(),
+            0xdd => self.execute_indexed(Index::IX, state),
+            0xed => self.execute_ed(state),
+            0xfd => self.execute_indexed(Index::IY, state),
+            _ => self.execute_main(opcode, Index::HL, state),
+        }
+    }
+
+    fn execute_indexed(&mut self, mut index: Index, state: &mut dyn IOState) -> u32 {
+        let mut cycles = 4;
+        let mut opcode = self.fetch_opcode();
+
+        // a chain of prefixes behaves as NOPs, only the last one counts
+        while opcode == 0xdd || opcode == 0xfd {
+            index = if opcode == 0xdd { Index::IX } else { Index::IY };
+            cycles += 4;
+            opcode = self.fetch_opcode();
+        }
+
+        match opcode {
+            0xcb => cycles - 4 + self.execute_indexed_cb(index),
+            0xed => cycles + self.execute_ed(state),
+            _ => cycles + self.execute_main(opcode, index, state),
+        }
+    }
+
+    fn execute_main(&mut self, opcode: u8, index: Index, state: &mut dyn IOState) -> u32 {
+        let x = opcode >> 6;
+        let y = (opcode >> 3) & 7;
+        let z = opcode & 7;
+        let p = y >> 1;
+        let q = y & 1;
+        // (IX+d) operands take the displacement fetch and an address calculation
+        let displacement_cycles = if index == Index::HL { 0 } else { 8 };
+        let mut cycles = CYCLES[opcode as usize] as u32;
+
+        match (x, z) {
+            (0, 0) => match y {
+                // NOP
+                0 => {},
+                // EX AF, AF'
+                1 => {
+                    let af = self.rp2(3, index);
+                    self.set_rp2(3, index, self.af_shadow);
+                    self.af_shadow = af;
+                },
+                // DJNZ d
+                2 => {
+                    let displacement = self.fetch_byte() as i8;
+                    *self.bc.msb_mut() = self.bc.msb().wrapping_sub(1);
+                    if self.bc.msb() != 0 {
+                        self.pc = self.pc.wrapping_add(displacement as u16);
+                        self.memptr = self.pc;
+                        cycles += 5;
+                    }
+                },
+                // JR d
+                3 => {
+                    let displacement = self.fetch_byte() as i8;
+                    self.pc = self.pc.wrapping_add(displacement as u16);
+                    self.memptr = self.pc;
+                },
+                // JR cc, d
+                _ => {
+                    let displacement = self.fetch_byte() as i8;
+                    if self.condition(y - 4) {
+                        self.pc = self.pc.wrapping_add(displacement as u16);
+                        self.memptr = self.pc;
+                        cycles += 5;
+                    }
+                },
+            },
+            (0, 1) => {
+                if q == 0 {
+                    // LD rp, nn
+                    let value = self.fetch_word();
+                    self.set_rp(p, index, value);
+                } else {
+                    // ADD HL, rp
+                    let result = self.add16(self.rp(2, index), self.rp(p, index));
+                    self.set_rp(2, index, result);
+                }
+            },
+            (0, 2) => match (q, p) {
+                // LD (BC), A / LD (DE), A
+                (0, 0) | (0, 1) => {
+                    let address = self.rp(p, index);
+                    self.write_byte(address, self.a);
+                    self.memptr = (self.a as u16) << 8 | (address.wrapping_add(1) & 0xff);
+                },
+                // LD (nn), HL
+                (0, 2) => {
+                    let address = self.fetch_word();
+                    self.write_word(address, self.rp(2, index));
+                    self.memptr = address.wrapping_add(1);
+                },
+                // LD (nn), A
+                (0, _) => {
+                    let address = self.fetch_word();
+                    self.write_byte(address, self.a);
+                    self.memptr = (self.a as u16) << 8 | (address.wrapping_add(1) & 0xff);
+                },
+                // LD A, (BC) / LD A, (DE)
+                (_, 0) | (_, 1) => {
+                    let address = self.rp(p, index);
+                    self.a = self.read_byte(address);
+                    self.memptr = address.wrapping_add(1);
+                },
+                // LD HL, (nn)
+                (_, 2) => {
+                    let address = self.fetch_word();
+                    let value = self.read_word(address);
+                    self.set_rp(2, index, value);
+                    self.memptr = address.wrapping_add(1);
+                },
+                // LD A, (nn)
+                _ => {
+                    let address = self.fetch_word();
+                    self.a = self.read_byte(address);
+                    self.memptr = address.wrapping_add(1);
+                },
+            },
+            // INC rp / DEC rp
+            (0, 3) => {
+                let value = self.rp(p, index);
+                let result = if q == 0 {
+                    value.wrapping_add(1)
+                } else {
+                    value.wrapping_sub(1)
+                };
+                self.set_rp(p, index, result);
+            },
+            // INC r / DEC r
+            (0, 4) | (0, 5) => {
+                if y == 6 {
+                    let address = self.operand_address(index);
+                    let value = self.read_byte(address);
+                    let result = if z == 4 { self.inc8(value) } else { self.dec8(value) };
+                    self.write_byte(address, result);
+                    cycles += displacement_cycles;
+                } else {
+                    let value = self.reg(y, index);
+                    let result = if z == 4 { self.inc8(value) } else { self.dec8(value) };
+                    self.set_reg(y, index, result);
+                }
+            },
+            // LD r, n
+            (0, 6) => {
+                if y == 6 {
+                    let address = self.operand_address(index);
+                    let value = self.fetch_byte();
+                    self.write_byte(address, value);
+                    // the displacement is added while n is read
+                    if index != Index::HL {
+                        cycles += 5;
+                    }
+                } else {
+                    let value = self.fetch_byte();
+                    self.set_reg(y, index, value);
+                }
+            },
+            (0, _) => match y {
+                // RLCA, RRCA, RLA, RRA
+                0..=3 => self.rotate_a(y),
+                // DAA
+                4 => self.daa(),
+                // CPL
+                5 => {
+                    self.a = !self.a;
+                    self.f = (self.f & (FLAG_S | FLAG_Z | FLAG_PV | FLAG_C))
+                        | FLAG_H | FLAG_N | (self.a & (FLAG_X | FLAG_Y));
+                },
+                // SCF
+                6 => {
+                    self.f = (self.f & (FLAG_S | FLAG_Z | FLAG_PV))
+                        | FLAG_C | (self.a & (FLAG_X | FLAG_Y));
+                },
+                // CCF
+                _ => {
+                    let carry = self.f & FLAG_C;
+                    self.f = (self.f & (FLAG_S | FLAG_Z | FLAG_PV))
+                        | (self.a & (FLAG_X | FLAG_Y)) | (carry << 4) | (carry ^ FLAG_C);
+                },
+            },
+            // HALT
+            (1, 6) if y == 6 => {
+                self.halted = true;
+            },
+            // LD r, r'
+            (1, _) => {
+                if z == 6 {
+                    // LD r, (HL) writes the real H and L even when prefixed
+                    let address = self.operand_address(index);
+                    let value = self.read_byte(address);
+                    self.set_reg(y, Index::HL, value);
+                    cycles += displacement_cycles;
+                } else if y == 6 {
+                    let address = self.operand_address(index);
+                    self.write_byte(address, self.reg(z, Index::HL));
+                    cycles += displacement_cycles;
+                } else {
+                    let value = self.reg(z, index);
+                    self.set_reg(y, index, value);
+                }
+            },
+            // ALU A, r
+            (2, _) => {
+                let value = if z == 6 {
+                    let address = self.operand_address(index);
+                    cycles += displacement_cycles;
+                    self.read_byte(address)
+                } else {
+                    self.reg(z, index)
+                };
+                self.alu(y, value);
+            },
+            // RET cc
+            (3, 0) => {
+                if self.condition(y) {
+                    self.pc = self.pop();
+                    self.memptr = self.pc;
+                    cycles += 6;
+                }
+            },
+            (3, 1) => match (q, p) {
+                // POP rp2
+                (0, _) => {
+                    let value = self.pop();
+                    self.set_rp2(p, index, value);
+                },
+                // RET
+                (_, 0) => {
+                    self.pc = self.pop();
+                    self.memptr = self.pc;
+                },
+                // EXX
+                (_, 1) => {
+                    let bc = self.bc.both();
+                    let de = self.de.both();
+                    let hl = self.hl.both();
+                    *self.bc.both_mut() = self.bc_shadow;
+                    *self.de.both_mut() = self.de_shadow;
+                    *self.hl.both_mut() = self.hl_shadow;
+                    self.bc_shadow = bc;
+                    self.de_shadow = de;
+                    self.hl_shadow = hl;
+                },
+                // JP (HL)
+                (_, 2) => {
+                    self.pc = self.rp(2, index);
+                },
+                // LD SP, HL
+                _ => {
+                    self.sp = self.rp(2, index);
+                },
+            },
+            // JP cc, nn
+            (3, 2) => {
+                let address = self.fetch_word();
+                self.memptr = address;
+                if self.condition(y) {
+                    self.pc = address;
+                }
+            },
+            (3, 3) => match y {
+                // JP nn
+                0 => {
+                    self.pc = self.fetch_word();
+                    self.memptr = self.pc;
+                },
+                // OUT (n), A
+                2 => {
+                    let port = self.fetch_byte();
+                    self.output(state, port, self.a);
+                    self.memptr = (self.a as u16) << 8 | (port.wrapping_add(1) as u16);
+                },
+                // IN A, (n)
+                3 => {
+                    let port = self.fetch_byte();
+                    self.memptr = ((self.a as u16) << 8 | port as u16).wrapping_add(1);
+                    self.a = self.input(state, port);
+                },
+                // EX (SP), HL
+                4 => {
+                    let value = self.read_word(self.sp);
+                    self.write_word(self.sp, self.rp(2, index));
+                    self.set_rp(2, index, value);
+                    self.memptr = value;
+                },
+                // EX DE, HL is never affected by a prefix
+                5 => {
+                    let de = self.de.both();
+                    *self.de.both_mut() = self.hl.both();
+                    *self.hl.both_mut() = de;
+                },
+                // DI
+                6 => {
+                    self.iff1 = false;
+                    self.iff2 = false;
+                },
+                // EI
+                7 => {
+                    self.iff1 = true;
+                    self.iff2 = true;
+                    self.interrupt_delay = true;
+                },
+                _ => unreachable!("CB prefix is dispatched before decoding"),
+            },
+            // CALL cc, nn
+            (3, 4) => {
+                let address = self.fetch_word();
+                self.memptr = address;
+                if self.condition(y) {
+                    self.push(self.pc);
+                    self.pc = address;
+                    cycles += 7;
+                }
+            },
+            (3, 5) => {
+                if q == 0 {
+                    // PUSH rp2
+                    self.push(self.rp2(p, index));
+                } else {
+                    // CALL nn, the other encodings are prefixes
+                    let address = self.fetch_word();
+                    self.memptr = address;
+                    self.push(self.pc);
+                    self.pc = address;
+                }
+            },
+            // ALU A, n
+            (3, 6) => {
+                let value = self.fetch_byte();
+                self.alu(y, value);
+            },
+            // RST y * 8
+            _ => {
+                self.push(self.pc);
+                self.pc = (y as u16) * 8;
+                self.memptr = self.pc;
+            },
+        }
+
+        cycles
+    }
+
+    fn execute_cb(&mut self) -> u32 {
+        let opcode = self.fetch_opcode();
+        let x = opcode >> 6;
+        let y = (opcode >> 3) & 7;
+        let z = opcode & 7;
+
+        if z == 6 {
+            let address = self.hl.both();
+            let value = self.read_byte(address);
+            let result = match x {
+                0 => self.rotate(y, value),
+                1 => {
+                    // BIT n, (HL) leaks the high byte of WZ
+                    self.bit(y, value, (self.memptr >> 8) as u8);
+                    return 12;
+                },
+                2 => value & !(1 << y),
+                _ => value | (1 << y),
+            };
+            self.write_byte(address, result);
+            15
+        } else {
+            let value = self.reg(z, Index::HL);
+            let result = match x {
+                0 => self.rotate(y, value),
+                1 => {
+                    self.bit(y, value, value);
+                    return 8;
+                },
+                2 => value & !(1 << y),
+                _ => value | (1 << y),
+            };
+            self.set_reg(z, Index::HL, result);
+            8
+        }
+    }
+
+    // DD CB d op / FD CB d op, the opcode byte is read without an M1 cycle
+    fn execute_indexed_cb(&mut self, index: Index) -> u32 {
+        let address = self.operand_address(index);
+        let opcode = self.fetch_byte();
+        let x = opcode >> 6;
+        let y = (opcode >> 3) & 7;
+        let z = opcode & 7;
+        let value = self.read_byte(address);
+
+        let result = match x {
+            0 => self.rotate(y, value),
+            1 => {
+                self.bit(y, value, (address >> 8) as u8);
+                return 20;
+            },
+            2 => value & !(1 << y),
+            _ => value | (1 << y),
+        };
+        self.write_byte(address, result);
+
+        // undocumented: the result is also copied into a register
+        if z != 6 {
+            self.set_reg(z, Index::HL, result);
+        }
+        23
+    }
+
+    fn execute_ed(&mut self, state: &mut dyn IOState) -> u32 {
+        let opcode = self.fetch_opcode();
+        let x = opcode >> 6;
+        let y = (opcode >> 3) & 7;
+        let z = opcode & 7;
+        let p = y >> 1;
+        let q = y & 1;
+
+        match (x, z) {
+            // IN r, (C), 6 only sets the flags
+            (1, 0) => {
+                let value = self.input(state, self.bc.lsb());
+                self.memptr = self.bc.both().wrapping_add(1);
+                self.f = (self.f & FLAG_C) | sz53p(value);
+                if y != 6 {
+                    self.set_reg(y, Index::HL, value);
+                }
+                12
+            },
+            // OUT (C), r, 6 outputs 0
+            (1, 1) => {
+                let value = if y == 6 { 0 } else { self.reg(y, Index::HL) };
+                self.output(state, self.bc.lsb(), value);
+                self.memptr = self.bc.both().wrapping_add(1);
+                12
+            },
+            // SBC HL, rp / ADC HL, rp
+            (1, 2) => {
+                let value = self.rp(p, Index::HL);
+                if q == 0 {
+                    self.sbc16(value);
+                } else {
+                    self.adc16(value);
+                }
+                15
+            },
+            // LD (nn), rp / LD rp, (nn)
+            (1, 3) => {
+                let address = self.fetch_word();
+                if q == 0 {
+                    self.write_word(address, self.rp(p, Index::HL));
+                } else {
+                    let value = self.read_word(address);
+                    self.set_rp(p, Index::HL, value);
+                }
+                self.memptr = address.wrapping_add(1);
+                20
+            },
+            // NEG
+            (1, 4) => {
+                let value = self.a;
+                self.a = 0;
+                self.a = self.sub8(value, false);
+                8
+            },
+            // RETN / RETI
+            (1, 5) => {
+                self.iff1 = self.iff2;
+                self.pc = self.pop();
+                self.memptr = self.pc;
+                14
+            },
+            // IM 0/1/2
+            (1, 6) => {
+                self.interrupt_mode = INTERRUPT_MODES[y as usize];
+                8
+            },
+            (1, _) => match y {
+                // LD I, A
+                0 => {
+                    self.i = self.a;
+                    9
+                },
+                // LD R, A
+                1 => {
+                    self.r = self.a;
+                    9
+                },
+                // LD A, I / LD A, R
+                2 | 3 => {
+                    self.a = if y == 2 { self.i } else { self.r };
+                    self.f = (self.f & FLAG_C) | sz53(self.a);
+                    if self.iff2 {
+                        self.f |= FLAG_PV;
+                    }
+                    9
+                },
+                // RRD / RLD
+                4 | 5 => {
+                    let address = self.hl.both();
+                    let value = self.read_byte(address);
+                    let result = if y == 4 {
+                        let result = (self.a << 4) | (value >> 4);
+                        self.a = (self.a & 0xf0) | (value & 0xf);
+                        result
+                    } else {
+                        let result = (value << 4) | (self.a & 0xf);
+                        self.a = (self.a & 0xf0) | (value >> 4);
+                        result
+                    };
+                    self.write_byte(address, result);
+                    self.memptr = address.wrapping_add(1);
+                    self.f = (self.f & FLAG_C) | sz53p(self.a);
+                    18
+                },
+                // NOP
+                _ => 8,
+            },
+            (2, 0..=3) if y >= 4 => self.block(y, z, state),
+            // undefined ED opcodes act as two NOPs
+            _ => 8,
+        }
+    }
+
+    // LDI/LDD/LDIR/LDDR, CPI/CPD/CPIR/CPDR, INI/IND/INIR/INDR, OUTI/OUTD/OTIR/OTDR
+    fn block(&mut self, y: u8, z: u8, state: &mut dyn IOState) -> u32 {
+        let increment = y & 1 == 0;
+        let repeat = y >= 6;
+        let step = |value: u16| {
+            if increment {
+                value.wrapping_add(1)
+            } else {
+                value.wrapping_sub(1)
+            }
+        };
+
+        let again = match z {
+            // LDI
+            0 => {
+                let value = self.read_byte(self.hl.both());
+                self.write_byte(self.de.both(), value);
+                *self.hl.both_mut() = step(self.hl.both());
+                *self.de.both_mut() = step(self.de.both());
+                *self.bc.both_mut() = self.bc.both().wrapping_sub(1);
+
+                let n = value.wrapping_add(self.a);
+                self.f = (self.f & (FLAG_S | FLAG_Z | FLAG_C)) | (n & FLAG_X) | ((n & 0x02) << 4);
+                if self.bc.both() != 0 {
+                    self.f |= FLAG_PV;
+                }
+                self.bc.both() != 0
+            },
+            // CPI
+            1 => {
+                let value = self.read_byte(self.hl.both());
+                let result = self.a.wrapping_sub(value);
+                let half = (self.a & 0xf) < (value & 0xf);
+                *self.hl.both_mut() = step(self.hl.both());
+                *self.bc.both_mut() = self.bc.both().wrapping_sub(1);
+                self.memptr = step(self.memptr);
+
+                let n = result.wrapping_sub(half as u8);
+                self.f = (self.f & FLAG_C) | FLAG_N | (sz53(result) & (FLAG_S | FLAG_Z))
+                    | (n & FLAG_X) | ((n & 0x02) << 4);
+                if half {
+                    self.f |= FLAG_H;
+                }
+                if self.bc.both() != 0 {
+                    self.f |= FLAG_PV;
+                }
+                self.bc.both() != 0 && result != 0
+            },
+            // INI
+            2 => {
+                let value = self.input(state, self.bc.lsb());
+                self.memptr = step(self.bc.both());
+                self.write_byte(self.hl.both(), value);
+                *self.hl.both_mut() = step(self.hl.both());
+                *self.bc.msb_mut() = self.bc.msb().wrapping_sub(1);
+
+                let k = value as u16 + step(self.bc.lsb() as u16) as u8 as u16;
+                self.block_io_flags(value, k);
+                self.bc.msb() != 0
+            },
+            // OUTI
+            _ => {
+                let value = self.read_byte(self.hl.both());
+                *self.bc.msb_mut() = self.bc.msb().wrapping_sub(1);
+                self.output(state, self.bc.lsb(), value);
+                *self.hl.both_mut() = step(self.hl.both());
+                self.memptr = step(self.bc.both());
+
+                let k = value as u16 + self.hl.lsb() as u16;
+                self.block_io_flags(value, k);
+                self.bc.msb() != 0
+            },
+        };
+
+        if repeat && again {
+            self.pc = self.pc.wrapping_sub(2);
+            self.memptr = self.pc.wrapping_add(1);
+            21
+        } else {
+            16
+        }
+    }
+
+    fn block_io_flags(&mut self, value: u8, k: u16) {
+        let b = self.bc.msb();
+        self.f = sz53(b) | parity((k & 7) as u8 ^ b);
+        if value & 0x80 != 0 {
+            self.f |= FLAG_N;
+        }
+        if k > 0xff {
+            self.f |= FLAG_H | FLAG_C;
+        }
+    }
+}
+
+impl Cpu for StateZ80 {
+    fn step(&mut self, state: &mut dyn IOState) -> Result<u64, CpuError> {
+        self.emulate(state)
+    }
+
+    fn interrupt(&mut self, interrupt: Interrupt) {
+        self.pending_interrupt = Some(interrupt);
+    }
+
+    fn nmi(&mut self) -> bool {
+        self.pending_nmi = true;
+        true
+    }
+
+    fn reset(&mut self) {
+        self.a = 0xff;
+        self.f = 0xff;
+        self.sp = 0xffff;
+        self.pc = 0;
+        self.i = 0;
+        self.r = 0;
+        self.iff1 = false;
+        self.iff2 = false;
+        self.interrupt_mode = 0;
+        self.interrupt_delay = false;
+        self.pending_interrupt = None;
+        self.pending_nmi = false;
+        self.halted = false;
+    }
+
+    fn registers(&self) -> Registers {
+        Registers {
+            a: self.a,
+            flags: self.f,
+            b: self.bc.msb(),
+            c: self.bc.lsb(),
+            d: self.de.msb(),
+            e: self.de.lsb(),
+            h: self.hl.msb(),
+            l: self.hl.lsb(),
+            sp: self.sp,
+            pc: self.pc,
+        }
+    }
+
+    fn halted(&self) -> bool {
+        self.halted
+    }
+
+    fn memory(&self) -> &[u8] {
+        &self.memory
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::MachineError;
+
+    // ports that read as open bus and ignore writes
+    struct Ports;
+
+    impl IOState for Ports {
+        fn input(&self, _port: u8) -> Result<u8, MachineError> {
+            Ok(0xff)
+        }
+
+        fn output(&mut self, _port: u8, _value: u8) -> Result<(), MachineError> {
+            Ok(())
+        }
+    }
+
+    // the cycles of each of `count` steps
+    fn run(cpu: &mut StateZ80, count: usize) -> Vec<u64> {
+        (0..count).map(|_| cpu.step(&mut Ports).unwrap()).collect()
+    }
+
+    #[test]
+    fn daa_after_add() {
+        // ld a,15h; add a,27h; daa
+        let mut cpu = StateZ80::load_from_rom(&[0x3e, 0x15, 0xc6, 0x27, 0x27], 0, 0);
+        run(&mut cpu, 3);
+        assert_eq!(cpu.a, 0x42);
+        assert_eq!(cpu.f, FLAG_H | FLAG_PV);
+    }
+
+    #[test]
+    fn daa_after_sub() {
+        // ld a,42h; sub 15h; daa
+        let mut cpu = StateZ80::load_from_rom(&[0x3e, 0x42, 0xd6, 0x15, 0x27], 0, 0);
+        run(&mut cpu, 3);
+        assert_eq!(cpu.a, 0x27);
+        assert_eq!(cpu.f, FLAG_Y | FLAG_PV | FLAG_N);
+    }
+
+    #[test]
+    fn undocumented_flags_come_from_the_result() {
+        // ld a,28h; or a
+        let mut cpu = StateZ80::load_from_rom(&[0x3e, 0x28, 0xb7], 0, 0);
+        run(&mut cpu, 2);
+        assert_eq!(cpu.f, FLAG_Y | FLAG_X | FLAG_PV);
+    }
+
+    #[test]
+    fn cp_takes_undocumented_flags_from_the_operand() {
+        // ld a,0; cp 28h
+        let mut cpu = StateZ80::load_from_rom(&[0x3e, 0x00, 0xfe, 0x28], 0, 0);
+        run(&mut cpu, 2);
+        assert_eq!(cpu.f, FLAG_S | FLAG_Y | FLAG_H | FLAG_X | FLAG_N | FLAG_C);
+    }
+
+    #[test]
+    fn memptr_after_loads_and_jumps() {
+        // ld a,(1234h); ld a,56h; ld (12ffh),a; jp 0100h
+        let mut cpu = StateZ80::load_from_rom(&[0x3a, 0x34, 0x12, 0x3e, 0x56, 0x32, 0xff, 0x12, 0xc3, 0x00, 0x01], 0, 0);
+        run(&mut cpu, 1);
+        assert_eq!(cpu.memptr, 0x1235);
+        run(&mut cpu, 2);
+        assert_eq!(cpu.memptr, 0x5600);
+        run(&mut cpu, 1);
+        assert_eq!(cpu.memptr, 0x0100);
+    }
+
+    #[test]
+    fn bit_on_memory_shows_memptr() {
+        // ld a,(2800h); ld hl,0100h; bit 0,(hl)
+        let mut cpu = StateZ80::load_from_rom(&[0x3a, 0x00, 0x28, 0x21, 0x00, 0x01, 0xcb, 0x46], 0, 0);
+        cpu.memory[0x100] = 0x01;
+        run(&mut cpu, 3);
+        // the carry is left as it was
+        assert_eq!(cpu.f, FLAG_Y | FLAG_H | FLAG_X | FLAG_C);
+    }
+
+    #[test]
+    fn ldir_copies_until_bc_is_zero() {
+        // xor a; ld hl,0100h; ld de,0200h; ld bc,3; ldir; halt
+        let program = [0xaf, 0x21, 0x00, 0x01, 0x11, 0x00, 0x02, 0x01, 0x03, 0x00, 0xed, 0xb0, 0x76];
+        let mut cpu = StateZ80::load_from_rom(&program, 0, 0);
+        cpu.memory[0x100..0x103].copy_from_slice(&[0x01, 0x02, 0x0a]);
+        run(&mut cpu, 4);
+
+        assert_eq!(run(&mut cpu, 3), [21, 21, 16]);
+        assert_eq!(&cpu.memory[0x200..0x203], &[0x01, 0x02, 0x0a]);
+        assert_eq!((cpu.hl.both(), cpu.de.both(), cpu.bc.both(), cpu.pc), (0x103, 0x203, 0, 12));
+        // Y and X are bits 1 and 3 of A plus the byte copied last
+        assert_eq!(cpu.f, FLAG_Z | FLAG_Y | FLAG_X);
+    }
+
+    #[test]
+    fn cpir_stops_on_a_match() {
+        // ld a,2; ld hl,0100h; ld bc,3; cpir
+        let program = [0x3e, 0x02, 0x21, 0x00, 0x01, 0x01, 0x03, 0x00, 0xed, 0xb1];
+        let mut cpu = StateZ80::load_from_rom(&program, 0, 0);
+        cpu.memory[0x100..0x103].copy_from_slice(&[0x01, 0x02, 0x03]);
+        run(&mut cpu, 3);
+
+        assert_eq!(run(&mut cpu, 2), [21, 16]);
+        assert_eq!((cpu.hl.both(), cpu.bc.both(), cpu.pc), (0x102, 1, 10));
+        assert_eq!(cpu.f & (FLAG_Z | FLAG_PV | FLAG_N), FLAG_Z | FLAG_PV | FLAG_N);
+    }
+
+    #[test]
+    fn nmi_is_taken_with_interrupts_disabled_and_retn_restores_iff1() {
+        // ei; nop, with retn at 66h
+        let mut cpu = StateZ80::load_from_rom(&[0xfb, 0x00], 0, 0);
+        cpu.memory[0x66..0x68].copy_from_slice(&[0xed, 0x45]);
+        run(&mut cpu, 1);
+
+        // the NMI does not wait for the instruction after EI either
+        assert!(Cpu::nmi(&mut cpu));
+        assert_eq!(run(&mut cpu, 1), [11]);
+        assert_eq!((cpu.pc, cpu.sp, cpu.iff1, cpu.iff2), (0x66, 0xfffd, false, true));
+
+        run(&mut cpu, 1);
+        assert_eq!((cpu.pc, cpu.iff1), (0x01, true));
+    }
+
+    // runs a CP/M build of zexdoc, e.g. ZEXDOC=zexdoc.com cargo test -- --ignored;
+    // it takes minutes
+    #[test]
+    #[ignore]
+    fn zexdoc() {
+        let path = std::env::var("ZEXDOC").expect("ZEXDOC names the zexdoc .COM to run");
+        let program = std::fs::read(&path).unwrap();
+        let mut cpu = StateZ80::load_from_rom(&program, 0x100, 0x100);
+        // BDOS calls return at once, a warm boot ends the run
+        cpu.memory[0x0005] = 0xc9;
+        cpu.sp = 0xf000;
+
+        let mut output = String::new();
+        while cpu.pc != 0 {
+            if cpu.pc == 0x0005 {
+                match cpu.bc.lsb() {
+                    2 => output.push(cpu.de.lsb() as char),
+                    9 => {
+                        let mut address = cpu.de.both();
+                        while cpu.memory[address as usize] != b'$' {
+                            output.push(cpu.memory[address as usize] as char);
+                            address = address.wrapping_add(1);
+                        }
+                    },
+                    _ => (),
+                }
+            }
+            cpu.step(&mut Ports).unwrap();
+        }
+        print!("{}", output);
+        assert!(output.contains("Tests complete") && !output.contains("ERROR"), "{}", output);
+    }
+}